@@ -0,0 +1,61 @@
+//! Batch throughput of the indicators implementing `NextBatch`, next to their streaming
+//! throughput on the same values, for several window sizes.
+//!
+//! `next_batch/SMA(50)` against `next/SMA(50)` is the gain of the batch implementation. Run
+//! with `--features simd` to measure the vectorized kernels.
+
+mod support;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use support::{closes, next_batch, next_values, Fixed, Ohlcv, BARS, SEED};
+use ta::indicators::{
+    fixed, AverageTrueRange, ExponentialMovingAverage, Maximum, Minimum,
+    MovingAverageConvergenceDivergence, RelativeStrengthIndex, SimpleMovingAverage,
+    StandardDeviation, TrueRange, WeightedMovingAverage,
+};
+
+const PERIODS: [usize; 3] = [14, 50, 200];
+
+/// Adds a streaming and a batch benchmark per period, `$new` builds the indicator from the
+/// period.
+macro_rules! periods {
+    ($group:ident, $values:ident, $($new:expr),* $(,)?) => {
+        $(
+            for period in PERIODS {
+                let indicator = ($new)(period).unwrap();
+                next_values(&mut $group, indicator.clone(), &$values);
+                next_batch(&mut $group, indicator, &$values);
+            }
+        )*
+    };
+}
+
+fn batch(c: &mut Criterion) {
+    let values = closes(&Ohlcv::bars(SEED, BARS));
+    let mut group = c.benchmark_group("batch");
+
+    periods!(
+        group,
+        values,
+        AverageTrueRange::new,
+        ExponentialMovingAverage::<f64>::new,
+        Maximum::new,
+        Minimum::new,
+        RelativeStrengthIndex::new,
+        SimpleMovingAverage::<f64>::new,
+        StandardDeviation::new,
+        WeightedMovingAverage::new,
+        |period| MovingAverageConvergenceDivergence::new(period / 2, period, 9),
+    );
+
+    next_values(&mut group, TrueRange::new(), &values);
+    next_batch(&mut group, TrueRange::new(), &values);
+    let sma = Fixed(fixed::SimpleMovingAverage::<14>::new());
+    next_values(&mut group, sma.clone(), &values);
+    next_batch(&mut group, sma, &values);
+
+    group.finish();
+}
+
+criterion_group!(benches, batch);
+criterion_main!(benches);
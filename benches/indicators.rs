@@ -1,52 +1,62 @@
-use bencher::{benchmark_group, benchmark_main, black_box, Bencher};
-use rand::Rng;
-use ta::indicators::{AverageTrueRange, BollingerBands, ChandelierExit, CommodityChannelIndex, EfficiencyRatio, ExponentialMovingAverage, FastStochastic, KeltnerChannel, Maximum, MeanAbsoluteDeviation, Minimum, MoneyFlowIndex, MovingAverageConvergenceDivergence, OnBalanceVolume, PercentagePriceOscillator, RateOfChange, RelativeStrengthIndex, SimpleMovingAverage, SlowStochastic, StandardDeviation, TrueRange, VolumeWeightedAveragePrice, WeightedMovingAverage};
-use ta::{DataItem, Next};
+//! Streaming throughput of every indicator with its default parameters.
 
-const ITEMS_COUNT: usize = 5_000;
+mod support;
 
-fn rand_data_item() -> DataItem {
-    let mut rng = rand::thread_rng();
+use std::hint::black_box;
 
-    let low = rng.gen_range(0.0, 500.0);
-    let high = rng.gen_range(500.0, 1000.0);
-    let open = rng.gen_range(low, high);
-    let close = rng.gen_range(low, high);
-    let volume = rng.gen_range(0.0, 10_000.0);
+use criterion::{criterion_group, criterion_main, Criterion};
+use support::{Ohlcv, BARS, SEED};
+use ta::indicators::{
+    AverageDailyRange, AverageTrueRange, BollingerBands, ChandelierExit, CommodityChannelIndex,
+    DeMarker, EfficiencyRatio, ElderImpulse, ExponentialMovingAverage, FastStochastic,
+    IntradayIntensity, KeltnerChannel, MarketStructure, Maximum, MeanAbsoluteDeviation,
+    MedianPrice, Minimum, MomentumScore, MoneyFlowIndex, MovingAverageConvergenceDivergence,
+    NormalizedAverageTrueRange, OnBalanceVolume, PercentagePriceOscillator, PivotHighLow,
+    PriorSessionLevels, RateOfChange, RelativeStrengthIndex, RollingDrawdown, RollingKurtosis,
+    RollingMedian, RollingQuantile, RollingSkewness, RollingSpectrum, SessionStats,
+    SimpleMovingAverage, SlowStochastic, StandardDeviation, StochasticMomentumIndex, TrueRange,
+    TtmSqueeze, TwiggsMoneyFlow, TypicalPrice, VolatilityAdjustedRateOfChange, VolatilityStop,
+    VolumeWeightedAveragePrice, VolumeWeightedMacd, VolumeWeightedMovingAverage,
+    VolumeZoneOscillator, WaveTrend, WaveletDecomposition, WeightedClose, WeightedMovingAverage,
+    WilliamsVixFix,
+};
+use ta::{DataItem, Next};
 
-    DataItem::builder()
-        .open(open)
-        .high(high)
-        .low(low)
-        .close(close)
-        .volume(volume)
-        .build()
-        .unwrap()
-}
+type FixedSimpleMovingAverage = ta::indicators::fixed::SimpleMovingAverage<9>;
+type FixedVolumeWeightedAveragePrice = ta::indicators::fixed::VolumeWeightedAveragePrice<14>;
 
 macro_rules! bench_indicators {
     ($($indicator:ident), *) => {
-        $(
-            #[allow(non_snake_case)]
-            fn $indicator(bench: &mut Bencher) {
-                let items: Vec<DataItem> = (0..ITEMS_COUNT).map( |_| rand_data_item() ).collect();
+        fn indicators(c: &mut Criterion) {
+            let items: Vec<DataItem> = Ohlcv::bars(SEED, BARS);
+            let mut group = c.benchmark_group("indicators");
+            $(
                 let mut indicator = $indicator::default();
+                group.bench_function(stringify!($indicator), |bencher| {
+                    bencher.iter(|| {
+                        for item in items.iter() {
+                            black_box(indicator.next(item));
+                        }
+                    })
+                });
+            )*
+            group.finish();
+        }
 
-                bench.iter(|| {
-                    for item in items.iter() {
-                        black_box(indicator.next(item));
-                    }
-                })
-            }
-        )*
-
-        benchmark_group!(benches, $($indicator,)*);
-        benchmark_main!(benches);
+        criterion_group!(benches, indicators);
+        criterion_main!(benches);
     }
 }
 
 bench_indicators!(
     AverageTrueRange,
+    AverageDailyRange,
+    NormalizedAverageTrueRange,
+    RollingMedian,
+    RollingQuantile,
+    RollingSkewness,
+    RollingKurtosis,
+    RollingDrawdown,
     ExponentialMovingAverage,
     MeanAbsoluteDeviation,
     BollingerBands,
@@ -54,19 +64,44 @@ bench_indicators!(
     EfficiencyRatio,
     FastStochastic,
     KeltnerChannel,
+    WilliamsVixFix,
+    TtmSqueeze,
+    WaveTrend,
+    StochasticMomentumIndex,
+    WaveletDecomposition,
+    DeMarker,
+    RollingSpectrum,
+    VolatilityStop,
+    TypicalPrice,
+    MedianPrice,
+    WeightedClose,
     Maximum,
     Minimum,
     MoneyFlowIndex,
     MovingAverageConvergenceDivergence,
+    ElderImpulse,
     OnBalanceVolume,
+    IntradayIntensity,
+    TwiggsMoneyFlow,
+    VolumeZoneOscillator,
+    PriorSessionLevels,
+    SessionStats,
     PercentagePriceOscillator,
     CommodityChannelIndex,
     RateOfChange,
+    VolatilityAdjustedRateOfChange,
     RelativeStrengthIndex,
     SimpleMovingAverage,
+    FixedSimpleMovingAverage,
     SlowStochastic,
     StandardDeviation,
     TrueRange,
     WeightedMovingAverage,
-    VolumeWeightedAveragePrice
+    VolumeWeightedAveragePrice,
+    FixedVolumeWeightedAveragePrice,
+    VolumeWeightedMovingAverage,
+    VolumeWeightedMacd,
+    MomentumScore,
+    PivotHighLow,
+    MarketStructure
 );
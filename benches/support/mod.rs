@@ -0,0 +1,156 @@
+//! Synthetic data and helpers shared by the benchmarks.
+//!
+//! The bars are generated from a fixed seed, so every run of a benchmark measures the same
+//! data, and a change of the numbers comes from the code, not from the input.
+
+#![allow(dead_code)]
+
+use std::fmt::{self, Display};
+use std::hint::black_box;
+use std::mem;
+
+use criterion::measurement::WallTime;
+use criterion::{BenchmarkGroup, Throughput};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ta::{Close, DataItem, Next, NextBatch};
+
+/// Number of bars of a benchmark run.
+pub const BARS: usize = 5_000;
+
+/// Seed of the bars of all the benchmarks.
+pub const SEED: u64 = 42;
+
+/// Generator of synthetic OHLCV bars.
+///
+/// The close follows a geometric random walk with a volatility, which changes in regimes, so
+/// the indicators see trends, ranges and gaps like in real data. Open, high and low are
+/// consistent with the close, and the volume grows with the size of the move.
+pub struct Ohlcv {
+    rng: StdRng,
+    close: f64,
+    volatility: f64,
+}
+
+impl Ohlcv {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+            close: 100.0,
+            volatility: 0.01,
+        }
+    }
+
+    /// The first `count` bars of the generator with the given seed.
+    pub fn bars(seed: u64, count: usize) -> Vec<DataItem> {
+        Self::new(seed).take(count).collect()
+    }
+}
+
+impl Iterator for Ohlcv {
+    type Item = DataItem;
+
+    fn next(&mut self) -> Option<DataItem> {
+        if self.rng.gen_bool(0.01) {
+            self.volatility = self.rng.gen_range(0.002..0.03);
+        }
+        let open = self.close * (1.0 + self.rng.gen_range(-0.5..0.5) * self.volatility);
+        let change = self.rng.gen_range(-1.0..1.0) * self.volatility;
+        let close = open * (1.0 + change);
+        let high = open.max(close) * (1.0 + self.rng.gen_range(0.0..self.volatility));
+        let low = open.min(close) * (1.0 - self.rng.gen_range(0.0..self.volatility));
+        let volume = 1_000.0 * (1.0 + change.abs() * 100.0) * self.rng.gen_range(0.5..1.5);
+        self.close = close;
+
+        let bar = DataItem::builder()
+            .open(open)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(volume)
+            .build()
+            .unwrap();
+        Some(bar)
+    }
+}
+
+/// The close prices of the bars.
+pub fn closes(bars: &[DataItem]) -> Vec<f64> {
+    bars.iter().map(Close::close).collect()
+}
+
+/// Benchmarks of a suite, named after its file.
+pub type Group<'a> = BenchmarkGroup<'a, WallTime>;
+
+/// An indicator of the `fixed` module, which has the same `Display` as the indicator with a
+/// runtime period, so its benchmark is named e.g. `windows/next/fixed::SMA(14)`.
+#[derive(Clone)]
+pub struct Fixed<I>(pub I);
+
+impl<I: Display> Display for Fixed<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "fixed::{}", self.0)
+    }
+}
+
+impl<T, I: Next<T>> Next<T> for Fixed<I> {
+    type Output = I::Output;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.0.next(input)
+    }
+}
+
+impl<T: Copy, I: NextBatch<T>> NextBatch<T> for Fixed<I> {
+    fn next_batch(&mut self, inputs: &[T], out: &mut Vec<Self::Output>) {
+        self.0.next_batch(inputs, out)
+    }
+}
+
+/// Streaming throughput: `next` for every bar, starting from `indicator`.
+///
+/// The bytes of the bars are reported as throughput, so the MB/s compare across benchmarks.
+pub fn next_bars<I>(group: &mut Group, mut indicator: I, bars: &[DataItem])
+where
+    I: for<'a> Next<&'a DataItem> + Display,
+{
+    group.throughput(Throughput::Bytes(mem::size_of_val(bars) as u64));
+    group.bench_function(format!("next/{}", indicator), |bencher| {
+        bencher.iter(|| {
+            for bar in bars {
+                black_box(indicator.next(bar));
+            }
+        })
+    });
+}
+
+/// Streaming throughput: `next` for every value, starting from `indicator`.
+pub fn next_values<I>(group: &mut Group, mut indicator: I, values: &[f64])
+where
+    I: Next<f64> + Display,
+{
+    group.throughput(Throughput::Bytes(mem::size_of_val(values) as u64));
+    group.bench_function(format!("next/{}", indicator), |bencher| {
+        bencher.iter(|| {
+            for &value in values {
+                black_box(indicator.next(value));
+            }
+        })
+    });
+}
+
+/// Batch throughput: one `next_batch` over all the values, into a reused output buffer.
+pub fn next_batch<I>(group: &mut Group, mut indicator: I, values: &[f64])
+where
+    I: NextBatch<f64> + Display,
+{
+    let mut out = Vec::with_capacity(values.len());
+    group.throughput(Throughput::Bytes(mem::size_of_val(values) as u64));
+    group.bench_function(format!("next_batch/{}", indicator), |bencher| {
+        bencher.iter(|| {
+            out.clear();
+            indicator.next_batch(values, &mut out);
+            black_box(&out);
+        })
+    });
+}
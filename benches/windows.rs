@@ -0,0 +1,102 @@
+//! Streaming throughput of the indicators with a window, for several window sizes.
+//!
+//! The indicators with default parameters are in `indicators.rs`. Here every indicator, whose
+//! cost may depend on its window, runs with the usual short, medium and long windows, so a
+//! regression of the window handling shows up as a growing gap between the sizes.
+
+mod support;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use support::{next_bars, Fixed, Ohlcv, BARS, SEED};
+use ta::indicators::{
+    fixed, AverageDailyRange, AverageTrueRange, BollingerBands, ChandelierExit,
+    CommodityChannelIndex, DeMarker, EfficiencyRatio, ExponentialMovingAverage, FastStochastic,
+    IntradayIntensity, KeltnerChannel, Maximum, MeanAbsoluteDeviation, Minimum, MoneyFlowIndex,
+    NormalizedAverageTrueRange, RateOfChange, RelativeStrengthIndex, RollingDrawdown,
+    RollingKurtosis, RollingMedian, RollingQuantile, RollingSkewness, SimpleMovingAverage,
+    StandardDeviation, TtmSqueeze, TwiggsMoneyFlow, VolatilityStop, VolumeWeightedAveragePrice,
+    VolumeWeightedMovingAverage, VolumeZoneOscillator, WeightedMovingAverage,
+};
+
+const PERIODS: [usize; 3] = [14, 50, 200];
+
+/// Adds a benchmark per period, `$new` builds the indicator from the period.
+macro_rules! periods {
+    ($group:ident, $bars:ident, $($new:expr),* $(,)?) => {
+        $(
+            for period in PERIODS {
+                next_bars(&mut $group, ($new)(period).unwrap(), &$bars);
+            }
+        )*
+    };
+}
+
+/// Adds a benchmark per const period of an indicator of the `fixed` module.
+macro_rules! fixed_periods {
+    ($group:ident, $bars:ident, $($indicator:ident),* $(,)?) => {
+        $(
+            next_bars(&mut $group, Fixed(fixed::$indicator::<14>::new()), &$bars);
+            next_bars(&mut $group, Fixed(fixed::$indicator::<50>::new()), &$bars);
+            next_bars(&mut $group, Fixed(fixed::$indicator::<200>::new()), &$bars);
+        )*
+    };
+}
+
+fn windows(c: &mut Criterion) {
+    let bars = Ohlcv::bars(SEED, BARS);
+    let mut group = c.benchmark_group("windows");
+
+    periods!(
+        group,
+        bars,
+        AverageDailyRange::new,
+        AverageTrueRange::new,
+        CommodityChannelIndex::new,
+        DeMarker::new,
+        EfficiencyRatio::new,
+        ExponentialMovingAverage::new,
+        FastStochastic::new,
+        IntradayIntensity::new,
+        Maximum::new,
+        MeanAbsoluteDeviation::new,
+        Minimum::new,
+        MoneyFlowIndex::new,
+        NormalizedAverageTrueRange::new,
+        RateOfChange::new,
+        RelativeStrengthIndex::new,
+        RollingDrawdown::new,
+        RollingKurtosis::new,
+        RollingMedian::new,
+        RollingSkewness::new,
+        SimpleMovingAverage::new,
+        StandardDeviation::new,
+        TwiggsMoneyFlow::new,
+        VolumeWeightedAveragePrice::new,
+        VolumeWeightedMovingAverage::new,
+        VolumeZoneOscillator::new,
+        WeightedMovingAverage::new,
+        |period| BollingerBands::new(period, 2.0),
+        |period| ChandelierExit::new(period, 3.0),
+        |period| KeltnerChannel::new(period, 2.0),
+        |period| RollingQuantile::new(period, 0.9),
+        |period| TtmSqueeze::new(period, 2.0, 1.5),
+        |period| VolatilityStop::new(period, 3.0),
+    );
+
+    fixed_periods!(
+        group,
+        bars,
+        Maximum,
+        Minimum,
+        RateOfChange,
+        SimpleMovingAverage,
+        StandardDeviation,
+        VolumeWeightedAveragePrice,
+        WeightedMovingAverage,
+    );
+
+    group.finish();
+}
+
+criterion_group!(benches, windows);
+criterion_main!(benches);
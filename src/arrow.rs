@@ -0,0 +1,248 @@
+//! Indicators computed over [Arrow](https://docs.rs/arrow) record batches (`arrow` feature).
+//!
+//! [compute](fn.compute.html) picks the OHLCV columns of a `RecordBatch` by their standard
+//! names `open`, `high`, `low`, `close` and `volume`, ignoring the case, runs the indicators of
+//! an [IndicatorSet](../registry/struct.IndicatorSet.html) over all the rows with
+//! [columns::compute](../columns/fn.compute.html), and returns a record batch with a `Float64`
+//! column per output, named like the outputs of the set. The indicators continue from their
+//! state, so the batches of a Parquet file or an Arrow Flight stream are computed one after the
+//! other. [apply](fn.apply.html) feeds a single array to an indicator taking values.
+//!
+//! `Float64` columns are read in place, other numeric columns are cast to `Float64` first.
+//! Nulls are rejected.
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use arrow::array::{Float64Array, Int64Array};
+//! use arrow::record_batch::RecordBatch;
+//! use ta::registry::IndicatorSet;
+//!
+//! let close = Arc::new(Float64Array::from(vec![10.0, 11.0, 12.0]));
+//! let bars = RecordBatch::try_from_iter([
+//!     ("open", close.clone() as _),
+//!     ("high", Arc::new(Float64Array::from(vec![10.5, 11.5, 12.5])) as _),
+//!     ("low", Arc::new(Float64Array::from(vec![9.5, 10.5, 11.5])) as _),
+//!     ("close", close as _),
+//!     ("volume", Arc::new(Int64Array::from(vec![100, 120, 80])) as _),
+//! ])
+//! .unwrap();
+//!
+//! let mut set = IndicatorSet::new();
+//! set.create("sma", "sma", &[("period", 2.0)]).unwrap();
+//! set.create("tr", "tr", &[]).unwrap();
+//!
+//! let results = ta::arrow::compute(&mut set, &bars).unwrap();
+//! assert_eq!(results.num_rows(), 3);
+//! let sma = results.column_by_name("sma").unwrap();
+//! assert_eq!(sma.as_ref(), &Float64Array::from(vec![10.0, 10.5, 11.5]));
+//! ```
+
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, AsArray, Float64Array};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Float64Type, Schema};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+
+use crate::columns::{self, OhlcvColumns};
+use crate::errors::{Result, TaError};
+use crate::registry::IndicatorSet;
+use crate::Next;
+
+/// Feeds all the rows of the OHLCV columns of `batch` to the indicators of `set`, and returns
+/// a record batch with a column per output, in the order of the indicators in the set.
+///
+/// The indicators continue from their current state, like feeding the rows with
+/// [next](../trait.Next.html). Fails with
+/// [DataItemIncomplete](../errors/enum.TaError.html#variant.DataItemIncomplete) for the first
+/// missing column, with [DataItemInvalid](../errors/enum.TaError.html#variant.DataItemInvalid)
+/// for a column, which is not numeric or has nulls, and with the error of the first invalid
+/// bar, e.g. a high below the low.
+pub fn compute(set: &mut IndicatorSet, batch: &RecordBatch) -> Result<RecordBatch> {
+    let open = float64(batch, "open")?;
+    let high = float64(batch, "high")?;
+    let low = float64(batch, "low")?;
+    let close = float64(batch, "close")?;
+    let volume = float64(batch, "volume")?;
+    let columns = OhlcvColumns {
+        open: values(&open),
+        high: values(&high),
+        low: values(&low),
+        close: values(&close),
+        volume: values(&volume),
+    };
+
+    let results = columns::compute(set, &columns)?;
+    let fields: Vec<Field> = results
+        .iter()
+        .map(|(name, _)| Field::new(name, DataType::Float64, false))
+        .collect();
+    let arrays: Vec<ArrayRef> = results
+        .into_iter()
+        .map(|(_, values)| Arc::new(Float64Array::from(values)) as ArrayRef)
+        .collect();
+    // a set without indicators has no columns, but still the rows of the batch
+    let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+    let results =
+        RecordBatch::try_new_with_options(Arc::new(Schema::new(fields)), arrays, &options);
+    // the outputs of a set have distinct names and a value per row
+    Ok(results.expect("columns of the batch length"))
+}
+
+/// Feeds every value of `array` to `indicator`, and returns the outputs.
+///
+/// Fails with [DataItemInvalid](../errors/enum.TaError.html#variant.DataItemInvalid), if the
+/// array is not numeric or has nulls.
+pub fn apply<I>(indicator: &mut I, array: &dyn Array) -> Result<Float64Array>
+where
+    I: Next<f64, Output = f64>,
+{
+    let array = to_float64(array, "value")?;
+    Ok(values(&array)
+        .iter()
+        .map(|&value| indicator.next(value))
+        .collect())
+}
+
+/// The column named `field`, ignoring the case, as a `Float64` array without nulls.
+fn float64(batch: &RecordBatch, field: &'static str) -> Result<ArrayRef> {
+    let schema = batch.schema();
+    let index = schema
+        .fields()
+        .iter()
+        .position(|column| column.name().eq_ignore_ascii_case(field))
+        .ok_or(TaError::DataItemIncomplete { field })?;
+    to_float64(batch.column(index).as_ref(), field)
+}
+
+/// The array as a `Float64` array without nulls, cast if it has another numeric type.
+fn to_float64(array: &dyn Array, field: &'static str) -> Result<ArrayRef> {
+    if !array.data_type().is_numeric() || array.null_count() > 0 {
+        return Err(TaError::DataItemInvalid { field });
+    }
+    cast(array, &DataType::Float64).map_err(|_| TaError::DataItemInvalid { field })
+}
+
+/// The values of a `Float64` array, borrowed.
+fn values(array: &ArrayRef) -> &[f64] {
+    array.as_primitive::<Float64Type>().values()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::ExponentialMovingAverage;
+    use arrow::array::{Int64Array, StringArray};
+
+    const HIGH: [f64; 5] = [10.5, 11.5, 12.5, 11.5, 13.0];
+    const LOW: [f64; 5] = [9.5, 10.5, 11.5, 10.5, 12.0];
+    const CLOSE: [f64; 5] = [10.0, 11.0, 12.0, 11.0, 12.5];
+    const VOLUME: [f64; 5] = [100.0, 120.0, 80.0, 90.0, 150.0];
+
+    fn array(values: &[f64]) -> ArrayRef {
+        Arc::new(Float64Array::from(values.to_vec()))
+    }
+
+    fn batch() -> RecordBatch {
+        RecordBatch::try_from_iter([
+            (
+                "Timestamp",
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5])) as _,
+            ),
+            ("Open", array(&CLOSE)),
+            ("High", array(&HIGH)),
+            ("Low", array(&LOW)),
+            ("Close", array(&CLOSE)),
+            ("Volume", array(&VOLUME)),
+        ])
+        .unwrap()
+    }
+
+    fn set() -> IndicatorSet {
+        let mut set = IndicatorSet::new();
+        set.create("rsi", "rsi", &[("period", 3.0)]).unwrap();
+        set.create("macd", "macd", &[]).unwrap();
+        set
+    }
+
+    #[test]
+    fn test_compute() {
+        let results = compute(&mut set(), &batch()).unwrap();
+        assert_eq!(results.num_rows(), 5);
+
+        let columns = OhlcvColumns {
+            open: &CLOSE,
+            high: &HIGH,
+            low: &LOW,
+            close: &CLOSE,
+            volume: &VOLUME,
+        };
+        let expected = columns::compute(&mut set(), &columns).unwrap();
+        assert_eq!(results.num_columns(), expected.len());
+        for (name, values) in expected {
+            let column = results.column_by_name(&name).unwrap();
+            assert_eq!(column.as_primitive::<Float64Type>().values(), &values[..]);
+        }
+    }
+
+    #[test]
+    fn test_continues_from_state() {
+        let mut first = set();
+        let all = compute(&mut first, &batch()).unwrap();
+
+        let mut second = set();
+        compute(&mut second, &batch().slice(0, 2)).unwrap();
+        let rest = compute(&mut second, &batch().slice(2, 3)).unwrap();
+        assert_eq!(rest, all.slice(2, 3));
+    }
+
+    #[test]
+    fn test_empty_set() {
+        let results = compute(&mut IndicatorSet::new(), &batch()).unwrap();
+        assert_eq!(results.num_columns(), 0);
+        assert_eq!(results.num_rows(), 5);
+    }
+
+    #[test]
+    fn test_invalid_columns() {
+        let batch = batch();
+        let without_low = batch.project(&[1, 2, 4, 5]).unwrap();
+        assert_eq!(
+            compute(&mut set(), &without_low),
+            Err(TaError::DataItemIncomplete { field: "low" })
+        );
+
+        let nulls = Arc::new(Float64Array::from(vec![Some(1.0), None, None, None, None]));
+        let batch = RecordBatch::try_from_iter([
+            ("open", array(&CLOSE)),
+            ("high", array(&HIGH)),
+            ("low", array(&LOW)),
+            ("close", array(&CLOSE)),
+            ("volume", nulls as _),
+        ])
+        .unwrap();
+        assert_eq!(
+            compute(&mut set(), &batch),
+            Err(TaError::DataItemInvalid { field: "volume" })
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        let outputs = apply(&mut ema, &Int64Array::from(vec![10, 11, 12])).unwrap();
+
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        let expected: Vec<f64> = [10.0, 11.0, 12.0].map(|close| ema.next(close)).to_vec();
+        assert_eq!(outputs.values(), &expected[..]);
+
+        let strings = StringArray::from(vec!["10.0"]);
+        assert_eq!(
+            apply(&mut ema, &strings),
+            Err(TaError::DataItemInvalid { field: "value" })
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use core::fmt;
+
+use crate::bars::partial_bar::PartialBar;
+use crate::errors::{Result, TaError};
+use crate::{Close, DataItem, Next, Reset, Timestamped, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Single trade: its price, quantity and optionally its time.
+///
+/// Implements [Close](../trait.Close.html) and [Volume](../trait.Volume.html), so indicators
+/// taking only these can be fed with trades directly.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    pub price: f64,
+    pub quantity: f64,
+    pub timestamp: Option<i64>,
+}
+
+impl Trade {
+    pub fn new(price: f64, quantity: f64) -> Self {
+        Self {
+            price,
+            quantity,
+            timestamp: None,
+        }
+    }
+
+    pub fn with_timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Fails for a price, which is not finite, and a negative or NaN quantity.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !self.price.is_finite() {
+            return Err(TaError::DataItemInvalid { field: "price" });
+        }
+        if self.quantity.is_nan() || self.quantity < 0.0 {
+            return Err(TaError::DataItemInvalid { field: "quantity" });
+        }
+        Ok(())
+    }
+}
+
+impl Close for Trade {
+    fn close(&self) -> f64 {
+        self.price
+    }
+}
+
+impl Volume for Trade {
+    fn volume(&self) -> f64 {
+        self.quantity
+    }
+}
+
+impl Timestamped for Trade {
+    fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+}
+
+/// When a bar of a [BarAggregator](struct.BarAggregator.html) is complete.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarScheme {
+    /// Bars of a fixed time interval, in the unit of the timestamps of the trades.
+    Time(i64),
+    /// Bars of a fixed number of trades.
+    Ticks(u64),
+    /// Bars of at least the given traded quantity.
+    Volume(f64),
+    /// Bars of at least the given traded value, the sum of price times quantity.
+    Dollar(f64),
+}
+
+impl fmt::Display for BarScheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BarScheme::Time(interval) => write!(f, "time {}", interval),
+            BarScheme::Ticks(ticks) => write!(f, "ticks {}", ticks),
+            BarScheme::Volume(volume) => write!(f, "volume {}", volume),
+            BarScheme::Dollar(value) => write!(f, "dollar {}", value),
+        }
+    }
+}
+
+/// Builds bars from trades.
+///
+/// Time bars sample the trades by the clock, the other [schemes](enum.BarScheme.html) by the
+/// market activity: a bar is complete after a fixed number of trades, a fixed traded quantity
+/// or a fixed traded value. Activity based bars have returns closer to normally distributed
+/// than time bars, and sample more often when more information arrives.
+///
+/// `next` returns the completed bar or `None`. Tick, volume and dollar bars are complete with
+/// the trade reaching the threshold, which is not split, so a bar can exceed the threshold
+/// by the rest of its last trade. Time bars are complete, when a trade of a later interval
+/// arrives, and their timestamp is the start of their interval. The other bars take the
+/// timestamp of their first trade, if it has one. The trade count of a bar is the number of
+/// its trades. [in_progress](#method.in_progress) shows the bar still forming and
+/// [flush](#method.flush) completes it.
+///
+/// Trades with a price which is not finite, or a negative or NaN quantity, are rejected with
+/// `DataItemInvalid`. Time bars reject trades without a timestamp with `DataItemIncomplete`,
+/// and trades of an earlier interval than the current one with `DataItemInvalid`.
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::{BarAggregator, BarScheme, Trade};
+/// use ta::{Close, Next, TradeCount, Volume};
+///
+/// let mut bars = BarAggregator::new(BarScheme::Volume(100.0)).unwrap();
+/// assert_eq!(bars.next(&Trade::new(10.0, 40.0)), Ok(None));
+/// assert_eq!(bars.next(&Trade::new(10.5, 30.0)), Ok(None));
+///
+/// let bar = bars.next(&Trade::new(10.2, 50.0)).unwrap().unwrap();
+/// assert_eq!(bar.close(), 10.2);
+/// assert_eq!(bar.volume(), 120.0);
+/// assert_eq!(bar.trade_count(), Some(3));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct BarAggregator {
+    scheme: BarScheme,
+    bar: Option<PartialBar>,
+    ticks: u64,
+    volume: f64,
+    value: f64,
+}
+
+impl BarAggregator {
+    pub fn new(scheme: BarScheme) -> Result<Self> {
+        match scheme {
+            BarScheme::Time(interval) if interval <= 0 => {
+                return Err(TaError::invalid_parameter(
+                    "interval",
+                    interval as f64,
+                    "must be greater than 0",
+                ));
+            }
+            BarScheme::Ticks(0) => return Err(TaError::zero_period("ticks")),
+            BarScheme::Volume(threshold) | BarScheme::Dollar(threshold)
+                if !(threshold.is_finite() && threshold > 0.0) =>
+            {
+                return Err(TaError::invalid_parameter(
+                    "threshold",
+                    threshold,
+                    "must be a positive number",
+                ));
+            }
+            _ => {}
+        }
+        Ok(Self {
+            scheme,
+            bar: None,
+            ticks: 0,
+            volume: 0.0,
+            value: 0.0,
+        })
+    }
+
+    pub fn scheme(&self) -> BarScheme {
+        self.scheme
+    }
+
+    /// The bar still forming, as it is so far.
+    pub fn in_progress(&self) -> Option<DataItem> {
+        self.bar.as_ref().map(PartialBar::to_item)
+    }
+
+    /// Completes the bar still forming, e.g. at the end of the data.
+    pub fn flush(&mut self) -> Option<DataItem> {
+        self.ticks = 0;
+        self.volume = 0.0;
+        self.value = 0.0;
+        self.bar.take().map(|bar| bar.to_item())
+    }
+
+    fn next_time(&mut self, trade: &Trade, interval: i64) -> Result<Option<DataItem>> {
+        let timestamp = trade
+            .timestamp
+            .ok_or(TaError::DataItemIncomplete { field: "timestamp" })?;
+        let start = timestamp - timestamp.rem_euclid(interval);
+
+        if let Some(bar) = &mut self.bar {
+            match bar.start {
+                Some(current) if start == current => {
+                    bar.update_trade(trade);
+                    return Ok(None);
+                }
+                Some(current) if start < current => {
+                    return Err(TaError::DataItemInvalid { field: "timestamp" });
+                }
+                _ => {}
+            }
+        }
+        let completed = self.bar.replace(PartialBar::from_trade(Some(start), trade));
+        Ok(completed.map(|bar| bar.to_item()))
+    }
+}
+
+impl<'a> Next<&'a Trade> for BarAggregator {
+    type Output = Result<Option<DataItem>>;
+
+    fn next(&mut self, trade: &'a Trade) -> Self::Output {
+        trade.validate()?;
+        if let BarScheme::Time(interval) = self.scheme {
+            return self.next_time(trade, interval);
+        }
+
+        match &mut self.bar {
+            Some(bar) => bar.update_trade(trade),
+            None => self.bar = Some(PartialBar::from_trade(trade.timestamp, trade)),
+        }
+        self.ticks += 1;
+        self.volume += trade.quantity;
+        self.value += trade.price * trade.quantity;
+
+        let complete = match self.scheme {
+            BarScheme::Ticks(ticks) => self.ticks >= ticks,
+            BarScheme::Volume(threshold) => self.volume >= threshold,
+            BarScheme::Dollar(threshold) => self.value >= threshold,
+            BarScheme::Time(_) => false,
+        };
+        if complete {
+            Ok(self.flush())
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl Reset for BarAggregator {
+    fn reset(&mut self) {
+        self.flush();
+    }
+}
+
+impl fmt::Display for BarAggregator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BAR_AGGREGATOR({})", self.scheme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{High, Low, Open, TradeCount};
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec::Vec};
+
+    fn aggregate(scheme: BarScheme, trades: &[Trade]) -> Vec<DataItem> {
+        let mut bars = BarAggregator::new(scheme).unwrap();
+        trades
+            .iter()
+            .filter_map(|trade| bars.next(trade).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(BarAggregator::new(BarScheme::Time(0)).is_err());
+        assert!(BarAggregator::new(BarScheme::Ticks(0)).is_err());
+        assert!(BarAggregator::new(BarScheme::Volume(-1.0)).is_err());
+        assert!(BarAggregator::new(BarScheme::Dollar(f64::NAN)).is_err());
+        assert!(BarAggregator::new(BarScheme::Dollar(f64::INFINITY)).is_err());
+        assert!(BarAggregator::new(BarScheme::Ticks(10)).is_ok());
+    }
+
+    #[test]
+    fn test_ticks() {
+        let trades: Vec<Trade> = [10.0, 12.0, 9.0, 11.0, 10.0]
+            .iter()
+            .map(|&price| Trade::new(price, 1.0))
+            .collect();
+        let bars = aggregate(BarScheme::Ticks(2), &trades);
+        assert_eq!(bars.len(), 2);
+        assert_eq!(
+            (
+                bars[0].open(),
+                bars[0].high(),
+                bars[0].low(),
+                bars[0].close()
+            ),
+            (10.0, 12.0, 10.0, 12.0)
+        );
+        assert_eq!(bars[1].low(), 9.0);
+        assert_eq!(bars[1].trade_count(), Some(2));
+        assert_eq!(bars[1].timestamp(), None);
+    }
+
+    #[test]
+    fn test_volume() {
+        let trades = [
+            Trade::new(10.0, 60.0).with_timestamp(1),
+            Trade::new(11.0, 60.0).with_timestamp(2),
+            Trade::new(12.0, 100.0).with_timestamp(3),
+            Trade::new(13.0, 10.0).with_timestamp(4),
+        ];
+        let bars = aggregate(BarScheme::Volume(100.0), &trades);
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].volume(), 120.0);
+        assert_eq!(bars[0].timestamp(), Some(1));
+        // the overshoot of a bar does not carry over
+        assert_eq!(bars[1].volume(), 100.0);
+        assert_eq!(bars[1].trade_count(), Some(1));
+    }
+
+    #[test]
+    fn test_dollar() {
+        let trades = [
+            Trade::new(100.0, 4.0),
+            Trade::new(50.0, 10.0),
+            Trade::new(200.0, 2.0),
+            Trade::new(200.0, 2.0),
+        ];
+        let bars = aggregate(BarScheme::Dollar(800.0), &trades);
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].close(), 50.0);
+        assert_eq!(bars[1].volume(), 4.0);
+    }
+
+    #[test]
+    fn test_time() {
+        let trades = [
+            Trade::new(10.0, 1.0).with_timestamp(5),
+            Trade::new(11.0, 2.0).with_timestamp(59),
+            Trade::new(12.0, 3.0).with_timestamp(60),
+            Trade::new(9.0, 1.0).with_timestamp(200),
+        ];
+        let mut bars = BarAggregator::new(BarScheme::Time(60)).unwrap();
+        let completed: Vec<DataItem> = trades
+            .iter()
+            .filter_map(|trade| bars.next(trade).unwrap())
+            .collect();
+        assert_eq!(completed.len(), 2);
+        assert_eq!(completed[0].timestamp(), Some(0));
+        assert_eq!(completed[0].volume(), 3.0);
+        assert_eq!(completed[1].timestamp(), Some(60));
+        assert_eq!(bars.in_progress().unwrap().timestamp(), Some(180));
+        assert_eq!(bars.flush().unwrap().close(), 9.0);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut bars = BarAggregator::new(BarScheme::Time(60)).unwrap();
+        assert_eq!(
+            bars.next(&Trade::new(10.0, 1.0)),
+            Err(TaError::DataItemIncomplete { field: "timestamp" })
+        );
+        bars.next(&Trade::new(10.0, 1.0).with_timestamp(120))
+            .unwrap();
+        assert_eq!(
+            bars.next(&Trade::new(10.0, 1.0).with_timestamp(100)),
+            Err(TaError::DataItemInvalid { field: "timestamp" })
+        );
+        assert_eq!(
+            bars.next(&Trade::new(f64::NAN, 1.0).with_timestamp(130)),
+            Err(TaError::DataItemInvalid { field: "price" })
+        );
+        assert_eq!(
+            bars.next(&Trade::new(10.0, -1.0).with_timestamp(130)),
+            Err(TaError::DataItemInvalid { field: "quantity" })
+        );
+    }
+
+    #[test]
+    fn test_flush_and_reset() {
+        let mut bars = BarAggregator::new(BarScheme::Ticks(3)).unwrap();
+        bars.next(&Trade::new(10.0, 1.0)).unwrap();
+        bars.next(&Trade::new(11.0, 1.0)).unwrap();
+        assert_eq!(bars.in_progress().unwrap().close(), 11.0);
+        assert_eq!(bars.flush().unwrap().trade_count(), Some(2));
+
+        // the count starts over after a flush
+        bars.next(&Trade::new(12.0, 1.0)).unwrap();
+        bars.next(&Trade::new(13.0, 1.0)).unwrap();
+        assert_eq!(
+            bars.next(&Trade::new(14.0, 1.0)).unwrap().unwrap().open(),
+            12.0
+        );
+
+        bars.next(&Trade::new(15.0, 1.0)).unwrap();
+        bars.reset();
+        assert_eq!(bars.in_progress(), None);
+    }
+
+    #[test]
+    fn test_trade() {
+        let trade = Trade::new(10.0, 2.0).with_timestamp(7);
+        assert_eq!(trade.close(), 10.0);
+        assert_eq!(trade.volume(), 2.0);
+        assert_eq!(trade.timestamp(), Some(7));
+    }
+
+    #[test]
+    fn test_display() {
+        let bars = BarAggregator::new(BarScheme::Dollar(1_000_000.0)).unwrap();
+        assert_eq!(format!("{}", bars), "BAR_AGGREGATOR(dollar 1000000)");
+        let bars = BarAggregator::new(BarScheme::Ticks(100)).unwrap();
+        assert_eq!(format!("{}", bars), "BAR_AGGREGATOR(ticks 100)");
+    }
+}
@@ -0,0 +1,316 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, DataItem, Next, OpenInterest, Reset, Timestamped};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// What a [GapFiller](struct.GapFiller.html) does with missing bars.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillPolicy {
+    /// Inserts a bar for every missing one, with all prices at the last close and no volume.
+    ForwardFill,
+    /// Passes the bars on as they are, the gap is only counted.
+    Skip,
+    /// Returns a `Gap` error instead of the first bar after the gap.
+    Error,
+}
+
+impl fmt::Display for FillPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FillPolicy::ForwardFill => write!(f, "forward fill"),
+            FillPolicy::Skip => write!(f, "skip"),
+            FillPolicy::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Detects missing bars in a stream of timestamped bars of a fixed interval.
+///
+/// Bars are expected every `interval`, in the unit of the timestamps. Every full interval
+/// beyond the first between two bars is a missing bar, so a bar arriving a bit late is not a
+/// gap. The [policy](enum.FillPolicy.html) decides what happens with the missing bars.
+///
+/// Time-sensitive indicators, e.g. time-weighted or time-decayed averages, count a missing bar
+/// as if no time passed. With `ForwardFill` they see the time pass with an unchanged price
+/// instead. The synthetic bars have the timestamps of the missing bars, and keep the open
+/// interest of the last bar.
+///
+/// `next` returns the bars to feed on: the synthetic bars, if any, followed by the input. Bars
+/// without a timestamp are rejected with `DataItemIncomplete`, bars not later than the last
+/// one with `DataItemInvalid`. After a `Gap` error the filler continues after the rejected
+/// bar, so the next bar is checked against it.
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::{FillPolicy, GapFiller};
+/// use ta::{Close, DataItem, Next, Timestamped, Volume};
+///
+/// fn bar(minute: i64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(close).high(close).low(close).close(close).volume(10.0)
+///         .timestamp(minute * 60_000)
+///         .build().unwrap()
+/// }
+///
+/// let mut filler = GapFiller::new(60_000, FillPolicy::ForwardFill).unwrap();
+/// assert_eq!(filler.next(&bar(0, 10.0)).unwrap().len(), 1);
+///
+/// // the bars of minute 1 and 2 are missing
+/// let bars = filler.next(&bar(3, 12.0)).unwrap();
+/// assert_eq!(bars.len(), 3);
+/// assert_eq!(bars[0].timestamp(), Some(60_000));
+/// assert_eq!((bars[0].close(), bars[0].volume()), (10.0, 0.0));
+/// assert_eq!(bars[2].close(), 12.0);
+/// assert_eq!(filler.missing(), 2);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct GapFiller {
+    interval: i64,
+    policy: FillPolicy,
+    last: Option<i64>,
+    close: f64,
+    open_interest: Option<f64>,
+    missing: usize,
+}
+
+impl GapFiller {
+    pub fn new(interval: i64, policy: FillPolicy) -> Result<Self> {
+        if interval <= 0 {
+            return Err(TaError::invalid_parameter(
+                "interval",
+                interval as f64,
+                "must be greater than 0",
+            ));
+        }
+        Ok(Self {
+            interval,
+            policy,
+            last: None,
+            close: 0.0,
+            open_interest: None,
+            missing: 0,
+        })
+    }
+
+    pub fn interval(&self) -> i64 {
+        self.interval
+    }
+
+    pub fn policy(&self) -> FillPolicy {
+        self.policy
+    }
+
+    /// Number of bars missing before the last input.
+    pub fn missing(&self) -> usize {
+        self.missing
+    }
+
+    fn filler(&self, timestamp: i64) -> DataItem {
+        let mut builder = DataItem::builder()
+            .open(self.close)
+            .high(self.close)
+            .low(self.close)
+            .close(self.close)
+            .volume(0.0)
+            .timestamp(timestamp);
+        if let Some(open_interest) = self.open_interest {
+            builder = builder.open_interest(open_interest);
+        }
+        // the last close of a valid bar
+        builder.build().unwrap()
+    }
+}
+
+impl<'a> Next<&'a DataItem> for GapFiller {
+    type Output = Result<Vec<DataItem>>;
+
+    fn next(&mut self, item: &'a DataItem) -> Self::Output {
+        let timestamp = item
+            .timestamp()
+            .ok_or(TaError::DataItemIncomplete { field: "timestamp" })?;
+        let missing = match self.last {
+            Some(last) if timestamp <= last => {
+                return Err(TaError::DataItemInvalid { field: "timestamp" });
+            }
+            Some(last) => ((timestamp - last) / self.interval).saturating_sub(1) as usize,
+            None => 0,
+        };
+
+        let mut bars = Vec::new();
+        if missing > 0 {
+            match self.policy {
+                FillPolicy::ForwardFill => {
+                    let last = self.last.unwrap_or(timestamp);
+                    bars.extend(
+                        (1..=missing as i64).map(|i| self.filler(last + i * self.interval)),
+                    );
+                }
+                FillPolicy::Skip => {}
+                FillPolicy::Error => {
+                    self.last = Some(timestamp);
+                    self.missing = missing;
+                    return Err(TaError::Gap { missing });
+                }
+            }
+        }
+        bars.push(item.clone());
+
+        self.last = Some(timestamp);
+        self.close = item.close();
+        self.open_interest = item.open_interest().or(self.open_interest);
+        self.missing = missing;
+        Ok(bars)
+    }
+}
+
+impl Reset for GapFiller {
+    fn reset(&mut self) {
+        self.last = None;
+        self.close = 0.0;
+        self.open_interest = None;
+        self.missing = 0;
+    }
+}
+
+impl fmt::Display for GapFiller {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GAP_FILLER({}, {})", self.interval, self.policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Volume;
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec};
+
+    fn bar(timestamp: i64, close: f64) -> DataItem {
+        DataItem::builder()
+            .open(close)
+            .high(close)
+            .low(close)
+            .close(close)
+            .volume(5.0)
+            .timestamp(timestamp)
+            .build()
+            .unwrap()
+    }
+
+    fn timestamps(bars: &[DataItem]) -> Vec<i64> {
+        bars.iter().filter_map(|bar| bar.timestamp()).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(GapFiller::new(0, FillPolicy::Skip).is_err());
+        assert!(GapFiller::new(-1, FillPolicy::Skip).is_err());
+        assert!(GapFiller::new(1, FillPolicy::Skip).is_ok());
+    }
+
+    #[test]
+    fn test_forward_fill() {
+        let mut filler = GapFiller::new(10, FillPolicy::ForwardFill).unwrap();
+        assert_eq!(filler.next(&bar(0, 1.0)), Ok(vec![bar(0, 1.0)]));
+        assert_eq!(filler.next(&bar(10, 2.0)), Ok(vec![bar(10, 2.0)]));
+        assert_eq!(filler.missing(), 0);
+
+        let bars = filler.next(&bar(40, 3.0)).unwrap();
+        assert_eq!(timestamps(&bars), vec![20, 30, 40]);
+        assert_eq!(bars[0].close(), 2.0);
+        assert_eq!(bars[1].volume(), 0.0);
+        assert_eq!(bars[2], bar(40, 3.0));
+        assert_eq!(filler.missing(), 2);
+    }
+
+    #[test]
+    fn test_late_bars() {
+        let mut filler = GapFiller::new(10, FillPolicy::ForwardFill).unwrap();
+        filler.next(&bar(0, 1.0)).unwrap();
+        // a late bar is no gap, the next one is expected an interval later
+        assert_eq!(filler.next(&bar(19, 1.0)).unwrap().len(), 1);
+        assert_eq!(
+            timestamps(&filler.next(&bar(41, 1.0)).unwrap()),
+            vec![29, 41]
+        );
+    }
+
+    #[test]
+    fn test_open_interest() {
+        let mut filler = GapFiller::new(10, FillPolicy::ForwardFill).unwrap();
+        let item = DataItem::builder()
+            .open(1.0)
+            .high(1.0)
+            .low(1.0)
+            .close(1.0)
+            .volume(1.0)
+            .open_interest(500.0)
+            .timestamp(0)
+            .build()
+            .unwrap();
+        filler.next(&item).unwrap();
+        let bars = filler.next(&bar(20, 1.0)).unwrap();
+        assert_eq!(bars[0].open_interest(), Some(500.0));
+    }
+
+    #[test]
+    fn test_skip() {
+        let mut filler = GapFiller::new(10, FillPolicy::Skip).unwrap();
+        filler.next(&bar(0, 1.0)).unwrap();
+        assert_eq!(filler.next(&bar(50, 2.0)), Ok(vec![bar(50, 2.0)]));
+        assert_eq!(filler.missing(), 4);
+    }
+
+    #[test]
+    fn test_error() {
+        let mut filler = GapFiller::new(10, FillPolicy::Error).unwrap();
+        filler.next(&bar(0, 1.0)).unwrap();
+        assert_eq!(filler.next(&bar(30, 2.0)), Err(TaError::Gap { missing: 2 }));
+        // continues after the rejected bar
+        assert_eq!(filler.next(&bar(40, 2.0)), Ok(vec![bar(40, 2.0)]));
+    }
+
+    #[test]
+    fn test_invalid_timestamps() {
+        let mut filler = GapFiller::new(10, FillPolicy::ForwardFill).unwrap();
+        let no_timestamp = DataItem::builder()
+            .open(1.0)
+            .high(1.0)
+            .low(1.0)
+            .close(1.0)
+            .volume(1.0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            filler.next(&no_timestamp),
+            Err(TaError::DataItemIncomplete { field: "timestamp" })
+        );
+
+        filler.next(&bar(10, 1.0)).unwrap();
+        let invalid = Err(TaError::DataItemInvalid { field: "timestamp" });
+        assert_eq!(filler.next(&bar(10, 1.0)), invalid);
+        assert_eq!(filler.next(&bar(5, 1.0)), invalid);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut filler = GapFiller::new(10, FillPolicy::ForwardFill).unwrap();
+        filler.next(&bar(100, 1.0)).unwrap();
+        filler.reset();
+        assert_eq!(filler.next(&bar(0, 1.0)).unwrap().len(), 1);
+        assert_eq!(filler.missing(), 0);
+    }
+
+    #[test]
+    fn test_display() {
+        let filler = GapFiller::new(60, FillPolicy::ForwardFill).unwrap();
+        assert_eq!(format!("{}", filler), "GAP_FILLER(60, forward fill)");
+        assert_eq!(format!("{}", FillPolicy::Error), "error");
+    }
+}
@@ -0,0 +1,36 @@
+//! Bars built from other bars or from trades.
+//!
+//! [Resampler](struct.Resampler.html) aggregates bars of a lower timeframe into bars of a
+//! higher one, e.g. 5 minute bars into hourly or daily bars, using the
+//! [timestamps](../trait.Timestamped.html) of the bars. It emits every higher timeframe bar
+//! once it is complete and exposes the bar still forming, so multi-timeframe analysis can
+//! run on a single feed.
+//!
+//! [BarAggregator](struct.BarAggregator.html) builds bars from single
+//! [trades](struct.Trade.html): time bars, or tick, volume and dollar bars, which sample by the
+//! market activity instead of the clock. [RangeBarBuilder](struct.RangeBarBuilder.html) builds
+//! range bars, each covering the same price move.
+//!
+//! The builders take one input per call of [next](../trait.Next.html) and return
+//! `Result<Option<DataItem>>`: the bar completed by the input, if any.
+//!
+//! [GapFiller](struct.GapFiller.html) detects missing bars in a feed and fills them in, skips
+//! them or reports them. [Mtf](struct.Mtf.html) runs an indicator on resampled bars and returns
+//! its latest value with every input bar, without looking ahead.
+
+mod partial_bar;
+
+mod resampler;
+pub use self::resampler::Resampler;
+
+mod bar_aggregator;
+pub use self::bar_aggregator::{BarAggregator, BarScheme, Trade};
+
+mod range_bar_builder;
+pub use self::range_bar_builder::RangeBarBuilder;
+
+mod gap_filler;
+pub use self::gap_filler::{FillPolicy, GapFiller};
+
+mod mtf;
+pub use self::mtf::Mtf;
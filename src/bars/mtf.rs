@@ -0,0 +1,259 @@
+use core::fmt;
+
+use crate::bars::Resampler;
+use crate::errors::Result;
+use crate::{Current, DataItem, IsReady, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Runs an indicator on a higher timeframe, while it is fed the bars of a lower one.
+///
+/// Every bar goes into a [Resampler](struct.Resampler.html), and only the completed higher
+/// timeframe bars go into the indicator. `next` returns the latest value of the indicator on
+/// every lower timeframe bar, so both timeframes can be used side by side in the same loop.
+///
+/// The value never looks ahead: a higher timeframe bar is only complete, when the first bar of
+/// the next interval arrives, so the value returned with a bar only covers the higher
+/// timeframe bars, which closed before it. Within an interval the value stays the same. At
+/// the end of the data [flush](#method.flush) completes the last higher timeframe bar.
+///
+/// Bars are rejected with the errors of the resampler, e.g. when they have no timestamp.
+/// [Period](../trait.Period.html) and [IsReady](../trait.IsReady.html) count the bars of the
+/// higher timeframe.
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::{Mtf, Resampler};
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(minute: i64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(close).high(close).low(close).close(close).volume(10.0)
+///         .timestamp(minute * 60_000)
+///         .build().unwrap()
+/// }
+///
+/// // SMA of the 5 minute closes, fed with 1 minute bars
+/// let sma = SimpleMovingAverage::new(2).unwrap();
+/// let mut mtf = Mtf::new(Resampler::new(5 * 60_000).unwrap(), sma);
+///
+/// for minute in 0..5 {
+///     assert_eq!(mtf.next(&bar(minute, 10.0)), Ok(None));
+/// }
+/// // the first 5 minute bar closed at 10
+/// assert_eq!(mtf.next(&bar(5, 12.0)), Ok(Some(10.0)));
+/// assert_eq!(mtf.next(&bar(9, 14.0)), Ok(Some(10.0)));
+/// // the second one at 14
+/// assert_eq!(mtf.next(&bar(10, 11.0)), Ok(Some(12.0)));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Mtf<I> {
+    resampler: Resampler,
+    indicator: I,
+}
+
+impl<I> Mtf<I> {
+    pub fn new(resampler: Resampler, indicator: I) -> Self {
+        Self {
+            resampler,
+            indicator,
+        }
+    }
+
+    pub fn resampler(&self) -> &Resampler {
+        &self.resampler
+    }
+
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+}
+
+impl<I> Mtf<I>
+where
+    I: for<'a> Next<&'a DataItem> + Current,
+{
+    /// Completes the higher timeframe bar still forming, feeds it into the indicator and
+    /// returns the new value.
+    pub fn flush(&mut self) -> Option<<I as Current>::Output> {
+        if let Some(bar) = self.resampler.flush() {
+            self.indicator.next(&bar);
+        }
+        self.indicator.current()
+    }
+}
+
+impl<'a, I> Next<&'a DataItem> for Mtf<I>
+where
+    I: for<'b> Next<&'b DataItem> + Current,
+{
+    type Output = Result<Option<<I as Current>::Output>>;
+
+    fn next(&mut self, item: &'a DataItem) -> Self::Output {
+        if let Some(bar) = self.resampler.next(item)? {
+            self.indicator.next(&bar);
+        }
+        Ok(self.indicator.current())
+    }
+}
+
+impl<I: Period> Period for Mtf<I> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: IsReady> IsReady for Mtf<I> {
+    fn bars_until_ready(&self) -> usize {
+        self.indicator.bars_until_ready()
+    }
+}
+
+impl<I: Current> Current for Mtf<I> {
+    type Output = I::Output;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.indicator.current()
+    }
+}
+
+impl<I: Reset> Reset for Mtf<I> {
+    fn reset(&mut self) {
+        self.resampler.reset();
+        self.indicator.reset();
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for Mtf<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MTF({}, {})", self.resampler, self.indicator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::TaError;
+    use crate::indicators::{AverageTrueRange, SimpleMovingAverage};
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec::Vec};
+
+    fn bar(timestamp: i64, high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .open(close)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(1.0)
+            .timestamp(timestamp)
+            .build()
+            .unwrap()
+    }
+
+    fn mtf_sma(period: usize) -> Mtf<SimpleMovingAverage> {
+        let sma = SimpleMovingAverage::new(period).unwrap();
+        Mtf::new(Resampler::new(3).unwrap(), sma)
+    }
+
+    #[test]
+    fn test_next() {
+        let mut mtf = mtf_sma(2);
+        let closes = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        let values: Vec<Option<f64>> = closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| mtf.next(&bar(i as i64, close, close, close)).unwrap())
+            .collect();
+        // the higher timeframe bars close at 3 and 6, known with the bars at 3 and 6
+        let expected = [None, None, None, Some(3.0), Some(3.0), Some(3.0), Some(4.5)];
+        assert_eq!(values[..7], expected);
+        assert_eq!(values[7], Some(4.5));
+    }
+
+    #[test]
+    fn test_no_look_ahead() {
+        // the value within an interval does not depend on the bars of the interval
+        let mut rising = mtf_sma(1);
+        let mut falling = mtf_sma(1);
+        for i in 0..3 {
+            rising.next(&bar(i, 1.0, 1.0, 1.0)).unwrap();
+            falling.next(&bar(i, 1.0, 1.0, 1.0)).unwrap();
+        }
+        for i in 3..6 {
+            let price = 1.0 + i as f64;
+            assert_eq!(rising.next(&bar(i, price, price, price)), Ok(Some(1.0)));
+            assert_eq!(falling.next(&bar(i, -price, -price, -price)), Ok(Some(1.0)));
+        }
+    }
+
+    #[test]
+    fn test_ohlc_indicator() {
+        let atr = AverageTrueRange::new(2).unwrap();
+        let mut mtf = Mtf::new(Resampler::new(2).unwrap(), atr);
+        mtf.next(&bar(0, 11.0, 9.0, 10.0)).unwrap();
+        mtf.next(&bar(1, 13.0, 10.0, 12.0)).unwrap();
+        // the resampled bar ranges from 9 to 13
+        assert_eq!(mtf.next(&bar(2, 12.0, 12.0, 12.0)), Ok(Some(4.0)));
+    }
+
+    #[test]
+    fn test_flush() {
+        let mut mtf = mtf_sma(2);
+        assert_eq!(mtf.flush(), None);
+        mtf.next(&bar(0, 2.0, 2.0, 2.0)).unwrap();
+        mtf.next(&bar(1, 4.0, 4.0, 4.0)).unwrap();
+        assert_eq!(mtf.current(), None);
+        assert_eq!(mtf.flush(), Some(4.0));
+        assert_eq!(mtf.resampler().in_progress(), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut mtf = mtf_sma(2);
+        let no_timestamp = DataItem::builder()
+            .open(1.0)
+            .high(1.0)
+            .low(1.0)
+            .close(1.0)
+            .volume(1.0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            mtf.next(&no_timestamp),
+            Err(TaError::DataItemIncomplete { field: "timestamp" })
+        );
+    }
+
+    #[test]
+    fn test_period_and_ready() {
+        let mut mtf = mtf_sma(2);
+        assert_eq!(mtf.period(), 2);
+        assert_eq!(mtf.bars_until_ready(), 2);
+        for i in 0..4 {
+            mtf.next(&bar(i, 1.0, 1.0, 1.0)).unwrap();
+        }
+        assert_eq!(mtf.bars_until_ready(), 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mtf = mtf_sma(1);
+        for i in 0..4 {
+            mtf.next(&bar(i, 1.0, 1.0, 1.0)).unwrap();
+        }
+        assert_eq!(mtf.current(), Some(1.0));
+        mtf.reset();
+        assert_eq!(mtf.current(), None);
+        assert_eq!(mtf.resampler().in_progress(), None);
+        assert_eq!(mtf.next(&bar(0, 1.0, 1.0, 1.0)), Ok(None));
+    }
+
+    #[test]
+    fn test_display() {
+        let mtf = mtf_sma(2);
+        assert_eq!(format!("{}", mtf), "MTF(RESAMPLER(3), SMA(2))");
+    }
+}
@@ -0,0 +1,109 @@
+use core::ops::Add;
+
+use crate::bars::Trade;
+use crate::{
+    AskVolume, BidVolume, Close, DataItem, High, Low, Open, OpenInterest, TradeCount, Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Bar, which is still aggregated from bars or trades.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub(crate) struct PartialBar {
+    /// Timestamp of the bar, e.g. the start of its interval.
+    pub(crate) start: Option<i64>,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    open_interest: Option<f64>,
+    trade_count: Option<u64>,
+    bid_volume: Option<f64>,
+    ask_volume: Option<f64>,
+}
+
+fn sum<T: Add<Output = T>>(sum: Option<T>, value: Option<T>) -> Option<T> {
+    match (sum, value) {
+        (Some(sum), Some(value)) => Some(sum + value),
+        (sum, value) => sum.or(value),
+    }
+}
+
+impl PartialBar {
+    pub(crate) fn from_item(start: Option<i64>, item: &DataItem) -> Self {
+        Self {
+            start,
+            open: item.open(),
+            high: item.high(),
+            low: item.low(),
+            close: item.close(),
+            volume: item.volume(),
+            open_interest: item.open_interest(),
+            trade_count: item.trade_count(),
+            bid_volume: item.bid_volume(),
+            ask_volume: item.ask_volume(),
+        }
+    }
+
+    pub(crate) fn from_trade(start: Option<i64>, trade: &Trade) -> Self {
+        Self {
+            start,
+            open: trade.price,
+            high: trade.price,
+            low: trade.price,
+            close: trade.price,
+            volume: trade.quantity,
+            open_interest: None,
+            trade_count: Some(1),
+            bid_volume: None,
+            ask_volume: None,
+        }
+    }
+
+    pub(crate) fn update_item(&mut self, item: &DataItem) {
+        self.high = self.high.max(item.high());
+        self.low = self.low.min(item.low());
+        self.close = item.close();
+        self.volume += item.volume();
+        self.open_interest = item.open_interest().or(self.open_interest);
+        self.trade_count = sum(self.trade_count, item.trade_count());
+        self.bid_volume = sum(self.bid_volume, item.bid_volume());
+        self.ask_volume = sum(self.ask_volume, item.ask_volume());
+    }
+
+    pub(crate) fn update_trade(&mut self, trade: &Trade) {
+        self.high = self.high.max(trade.price);
+        self.low = self.low.min(trade.price);
+        self.close = trade.price;
+        self.volume += trade.quantity;
+        self.trade_count = sum(self.trade_count, Some(1));
+    }
+
+    pub(crate) fn to_item(&self) -> DataItem {
+        let mut builder = DataItem::builder()
+            .open(self.open)
+            .high(self.high)
+            .low(self.low)
+            .close(self.close)
+            .volume(self.volume);
+        if let Some(start) = self.start {
+            builder = builder.timestamp(start);
+        }
+        if let Some(open_interest) = self.open_interest {
+            builder = builder.open_interest(open_interest);
+        }
+        if let Some(trade_count) = self.trade_count {
+            builder = builder.trade_count(trade_count);
+        }
+        if let Some(bid_volume) = self.bid_volume {
+            builder = builder.bid_volume(bid_volume);
+        }
+        if let Some(ask_volume) = self.ask_volume {
+            builder = builder.ask_volume(ask_volume);
+        }
+        // the aggregate of valid bars or trades is valid
+        builder.build().unwrap()
+    }
+}
@@ -0,0 +1,203 @@
+use core::fmt;
+
+use crate::bars::partial_bar::PartialBar;
+use crate::bars::Trade;
+use crate::errors::{Result, TaError};
+use crate::{DataItem, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Builds range bars from trades.
+///
+/// A bar is complete with the first trade, whose price is at least `range` away from the open
+/// of the bar, up or down. The next trade opens the next bar. Range bars ignore the time:
+/// quiet markets produce few bars, and every bar covers a similar price move. The timestamp of
+/// a bar is the one of its first trade, if it has one.
+///
+/// Like the [BarAggregator](struct.BarAggregator.html), `next` returns the completed bar or
+/// `None`, [in_progress](#method.in_progress) shows the bar still forming and
+/// [flush](#method.flush) completes it. Trades with a price which is not finite, or a negative
+/// or NaN quantity, are rejected with `DataItemInvalid`.
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::{RangeBarBuilder, Trade};
+/// use ta::{Close, High, Low, Next, Open};
+///
+/// let mut bars = RangeBarBuilder::new(1.0).unwrap();
+/// assert_eq!(bars.next(&Trade::new(10.0, 5.0)), Ok(None));
+/// assert_eq!(bars.next(&Trade::new(10.6, 5.0)), Ok(None));
+/// assert_eq!(bars.next(&Trade::new(9.5, 5.0)), Ok(None));
+///
+/// let bar = bars.next(&Trade::new(8.9, 5.0)).unwrap().unwrap();
+/// assert_eq!((bar.open(), bar.high(), bar.low(), bar.close()), (10.0, 10.6, 8.9, 8.9));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RangeBarBuilder {
+    range: f64,
+    open: f64,
+    bar: Option<PartialBar>,
+}
+
+impl RangeBarBuilder {
+    pub fn new(range: f64) -> Result<Self> {
+        if !(range.is_finite() && range > 0.0) {
+            return Err(TaError::invalid_parameter(
+                "range",
+                range,
+                "must be a positive number",
+            ));
+        }
+        Ok(Self {
+            range,
+            open: 0.0,
+            bar: None,
+        })
+    }
+
+    pub fn range(&self) -> f64 {
+        self.range
+    }
+
+    /// The bar still forming, as it is so far.
+    pub fn in_progress(&self) -> Option<DataItem> {
+        self.bar.as_ref().map(PartialBar::to_item)
+    }
+
+    /// Completes the bar still forming, e.g. at the end of the data.
+    pub fn flush(&mut self) -> Option<DataItem> {
+        self.bar.take().map(|bar| bar.to_item())
+    }
+}
+
+impl<'a> Next<&'a Trade> for RangeBarBuilder {
+    type Output = Result<Option<DataItem>>;
+
+    fn next(&mut self, trade: &'a Trade) -> Self::Output {
+        trade.validate()?;
+        match &mut self.bar {
+            Some(bar) => bar.update_trade(trade),
+            None => {
+                self.open = trade.price;
+                self.bar = Some(PartialBar::from_trade(trade.timestamp, trade));
+            }
+        }
+        if (trade.price - self.open).abs() >= self.range {
+            Ok(self.flush())
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl Reset for RangeBarBuilder {
+    fn reset(&mut self) {
+        self.bar = None;
+    }
+}
+
+impl fmt::Display for RangeBarBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RANGE_BARS({})", self.range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Close, High, Low, Open, Timestamped, TradeCount, Volume};
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec::Vec};
+
+    fn range_bars(range: f64, prices: &[f64]) -> Vec<DataItem> {
+        let mut bars = RangeBarBuilder::new(range).unwrap();
+        prices
+            .iter()
+            .filter_map(|&price| bars.next(&Trade::new(price, 1.0)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(RangeBarBuilder::new(0.0).is_err());
+        assert!(RangeBarBuilder::new(-1.0).is_err());
+        assert!(RangeBarBuilder::new(f64::NAN).is_err());
+        assert!(RangeBarBuilder::new(0.5).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let bars = range_bars(2.0, &[10.0, 11.0, 12.0, 12.5, 11.0, 10.5, 14.5, 13.0]);
+        assert_eq!(bars.len(), 2);
+        assert_eq!(
+            (
+                bars[0].open(),
+                bars[0].high(),
+                bars[0].low(),
+                bars[0].close()
+            ),
+            (10.0, 12.0, 10.0, 12.0)
+        );
+        // the next trade opens the next bar, the move is measured from its open
+        assert_eq!(bars[1].open(), 12.5);
+        assert_eq!(bars[1].low(), 10.5);
+        assert_eq!(bars[1].close(), 10.5);
+        assert_eq!(bars[1].trade_count(), Some(3));
+    }
+
+    #[test]
+    fn test_gap() {
+        // a trade far beyond the range completes the bar in one step
+        let bars = range_bars(1.0, &[10.0, 15.0, 15.2]);
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].high(), 15.0);
+    }
+
+    #[test]
+    fn test_timestamp_and_volume() {
+        let mut bars = RangeBarBuilder::new(1.0).unwrap();
+        bars.next(&Trade::new(10.0, 3.0).with_timestamp(100))
+            .unwrap();
+        let bar = bars
+            .next(&Trade::new(11.0, 4.0).with_timestamp(160))
+            .unwrap()
+            .unwrap();
+        assert_eq!(bar.timestamp(), Some(100));
+        assert_eq!(bar.volume(), 7.0);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut bars = RangeBarBuilder::new(1.0).unwrap();
+        assert_eq!(
+            bars.next(&Trade::new(f64::INFINITY, 1.0)),
+            Err(TaError::DataItemInvalid { field: "price" })
+        );
+        assert_eq!(bars.in_progress(), None);
+    }
+
+    #[test]
+    fn test_flush_and_reset() {
+        let mut bars = RangeBarBuilder::new(1.0).unwrap();
+        bars.next(&Trade::new(10.0, 1.0)).unwrap();
+        bars.next(&Trade::new(10.5, 1.0)).unwrap();
+        assert_eq!(bars.in_progress().unwrap().close(), 10.5);
+        assert_eq!(bars.flush().unwrap().open(), 10.0);
+
+        // the next bar opens at the next trade
+        assert_eq!(bars.next(&Trade::new(11.2, 1.0)), Ok(None));
+        bars.reset();
+        assert_eq!(bars.in_progress(), None);
+        assert_eq!(bars.next(&Trade::new(12.0, 1.0)), Ok(None));
+        assert_eq!(bars.in_progress().unwrap().open(), 12.0);
+    }
+
+    #[test]
+    fn test_display() {
+        let bars = RangeBarBuilder::new(0.25).unwrap();
+        assert_eq!(format!("{}", bars), "RANGE_BARS(0.25)");
+        assert_eq!(bars.range(), 0.25);
+    }
+}
@@ -0,0 +1,327 @@
+use core::fmt;
+
+use crate::bars::partial_bar::PartialBar;
+use crate::errors::{Result, TaError};
+use crate::{DataItem, Next, Reset, Timestamped};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Aggregates bars into bars of a higher timeframe.
+///
+/// Every bar falls into the interval containing its timestamp. Intervals start at the
+/// multiples of `interval` after `offset`, e.g. with timestamps in milliseconds an interval of
+/// `3_600_000` builds hourly bars, and an offset of `-3_600_000` starts daily bars at 23:00 UTC
+/// instead of midnight. A higher timeframe bar takes the open of the first bar of its
+/// interval, the close of the last one, the highest high, the lowest low and the sum of the
+/// volumes. Its timestamp is the start of the interval. The trade count and the bid and ask
+/// volumes are summed up, and the open interest is the last one given, when the bars carry
+/// them.
+///
+/// `next` returns the completed bar, when a bar of a later interval arrives, and `None`
+/// otherwise. Intervals without bars produce no bar. The bar of the last interval is only
+/// complete after [flush](#method.flush), while [in_progress](#method.in_progress) shows it
+/// as it forms, e.g. to feed it into a [LiveBar](../live/struct.LiveBar.html).
+///
+/// Bars without a timestamp are rejected with `DataItemIncomplete`, bars of an earlier
+/// interval than the current one with `DataItemInvalid`. Within an interval, the bars are
+/// aggregated in the order they arrive.
+///
+/// # Example
+///
+/// ```
+/// use ta::bars::Resampler;
+/// use ta::{Close, DataItem, High, Next, Timestamped};
+///
+/// fn bar(minute: i64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(close).high(close).low(close).close(close).volume(10.0)
+///         .timestamp(minute * 60_000)
+///         .build().unwrap()
+/// }
+///
+/// // 5 minute bars from 1 minute bars
+/// let mut resampler = Resampler::new(5 * 60_000).unwrap();
+/// assert_eq!(resampler.next(&bar(0, 10.0)), Ok(None));
+/// assert_eq!(resampler.next(&bar(1, 12.0)), Ok(None));
+/// assert_eq!(resampler.in_progress().unwrap().high(), 12.0);
+/// assert_eq!(resampler.next(&bar(4, 11.0)), Ok(None));
+///
+/// let completed = resampler.next(&bar(5, 13.0)).unwrap().unwrap();
+/// assert_eq!(completed.timestamp(), Some(0));
+/// assert_eq!(completed.high(), 12.0);
+/// assert_eq!(completed.close(), 11.0);
+///
+/// let last = resampler.flush().unwrap();
+/// assert_eq!(last.timestamp(), Some(5 * 60_000));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Resampler {
+    interval: i64,
+    offset: i64,
+    bar: Option<PartialBar>,
+}
+
+impl Resampler {
+    /// Creates a resampler to bars of `interval`, in the unit of the timestamps.
+    pub fn new(interval: i64) -> Result<Self> {
+        if interval <= 0 {
+            return Err(TaError::invalid_parameter(
+                "interval",
+                interval as f64,
+                "must be greater than 0",
+            ));
+        }
+        Ok(Self {
+            interval,
+            offset: 0,
+            bar: None,
+        })
+    }
+
+    /// Shifts the start of the intervals by `offset`, e.g. to start daily bars at the open
+    /// of a session.
+    pub fn with_offset(mut self, offset: i64) -> Self {
+        self.offset = offset.rem_euclid(self.interval);
+        self
+    }
+
+    pub fn interval(&self) -> i64 {
+        self.interval
+    }
+
+    /// Offset of the intervals, between 0 and `interval`.
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// Start of the interval containing `timestamp`.
+    pub fn interval_start(&self, timestamp: i64) -> i64 {
+        timestamp - (timestamp - self.offset).rem_euclid(self.interval)
+    }
+
+    /// The bar of the current interval, as it is so far.
+    pub fn in_progress(&self) -> Option<DataItem> {
+        self.bar.as_ref().map(PartialBar::to_item)
+    }
+
+    /// Completes the bar of the current interval, e.g. at the end of the data. The next input
+    /// starts a new bar, even within the same interval.
+    pub fn flush(&mut self) -> Option<DataItem> {
+        self.bar.take().map(|bar| bar.to_item())
+    }
+}
+
+impl<'a> Next<&'a DataItem> for Resampler {
+    type Output = Result<Option<DataItem>>;
+
+    fn next(&mut self, item: &'a DataItem) -> Self::Output {
+        let timestamp = item
+            .timestamp()
+            .ok_or(TaError::DataItemIncomplete { field: "timestamp" })?;
+        let start = self.interval_start(timestamp);
+
+        if let Some(bar) = &mut self.bar {
+            match bar.start {
+                Some(current) if start == current => {
+                    bar.update_item(item);
+                    return Ok(None);
+                }
+                Some(current) if start < current => {
+                    return Err(TaError::DataItemInvalid { field: "timestamp" });
+                }
+                _ => {}
+            }
+        }
+        let completed = self.bar.replace(PartialBar::from_item(Some(start), item));
+        Ok(completed.map(|bar| bar.to_item()))
+    }
+}
+
+impl Reset for Resampler {
+    fn reset(&mut self) {
+        self.bar = None;
+    }
+}
+
+impl fmt::Display for Resampler {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.offset == 0 {
+            write!(f, "RESAMPLER({})", self.interval)
+        } else {
+            write!(f, "RESAMPLER({}, {})", self.interval, self.offset)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AskVolume, BidVolume, Close, Open, OpenInterest, TradeCount, Volume};
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec::Vec};
+
+    fn bar(timestamp: i64, open: f64, high: f64, low: f64, close: f64) -> DataItem {
+        DataItem::builder()
+            .open(open)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(100.0)
+            .timestamp(timestamp)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Resampler::new(0).is_err());
+        assert!(Resampler::new(-60).is_err());
+        assert!(Resampler::new(60).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut resampler = Resampler::new(60).unwrap();
+        assert_eq!(resampler.next(&bar(0, 10.0, 11.0, 9.0, 10.5)), Ok(None));
+        assert_eq!(resampler.next(&bar(20, 10.5, 12.0, 10.0, 11.0)), Ok(None));
+        assert_eq!(resampler.next(&bar(40, 11.0, 11.5, 8.5, 9.0)), Ok(None));
+
+        let completed = resampler.next(&bar(60, 9.0, 9.5, 8.0, 9.5)).unwrap();
+        let expected = DataItem::builder()
+            .open(10.0)
+            .high(12.0)
+            .low(8.5)
+            .close(9.0)
+            .volume(300.0)
+            .timestamp(0)
+            .build()
+            .unwrap();
+        assert_eq!(completed, Some(expected));
+        assert_eq!(resampler.in_progress().unwrap().volume(), 100.0);
+    }
+
+    #[test]
+    fn test_gaps() {
+        // empty intervals produce no bars, timestamps within an interval are not aligned
+        let mut resampler = Resampler::new(60).unwrap();
+        resampler.next(&bar(75, 10.0, 10.0, 10.0, 10.0)).unwrap();
+        let completed = resampler.next(&bar(250, 11.0, 11.0, 11.0, 11.0)).unwrap();
+        assert_eq!(completed.unwrap().timestamp(), Some(60));
+        assert_eq!(resampler.in_progress().unwrap().timestamp(), Some(240));
+    }
+
+    #[test]
+    fn test_offset() {
+        let resampler = Resampler::new(100).unwrap().with_offset(-30);
+        assert_eq!(resampler.offset(), 70);
+        assert_eq!(resampler.interval_start(69), -30);
+        assert_eq!(resampler.interval_start(70), 70);
+        assert_eq!(resampler.interval_start(-31), -130);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut resampler = Resampler::new(60).unwrap();
+        let no_timestamp = DataItem::builder()
+            .open(1.0)
+            .high(1.0)
+            .low(1.0)
+            .close(1.0)
+            .volume(1.0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            resampler.next(&no_timestamp),
+            Err(TaError::DataItemIncomplete { field: "timestamp" })
+        );
+
+        resampler.next(&bar(120, 1.0, 1.0, 1.0, 1.0)).unwrap();
+        assert_eq!(
+            resampler.next(&bar(59, 1.0, 1.0, 1.0, 1.0)),
+            Err(TaError::DataItemInvalid { field: "timestamp" })
+        );
+        // out of order within the interval is fine
+        assert_eq!(resampler.next(&bar(121, 1.0, 1.0, 1.0, 1.0)), Ok(None));
+        assert_eq!(resampler.next(&bar(120, 1.0, 1.0, 1.0, 1.0)), Ok(None));
+    }
+
+    #[test]
+    fn test_extended_fields() {
+        let item = |timestamp: i64, open_interest: Option<f64>| {
+            let builder = DataItem::builder()
+                .open(1.0)
+                .high(1.0)
+                .low(1.0)
+                .close(1.0)
+                .volume(10.0)
+                .trade_count(3)
+                .bid_volume(4.0)
+                .ask_volume(6.0)
+                .timestamp(timestamp);
+            match open_interest {
+                Some(open_interest) => builder.open_interest(open_interest),
+                None => builder,
+            }
+            .build()
+            .unwrap()
+        };
+        let mut resampler = Resampler::new(60).unwrap();
+        resampler.next(&item(0, Some(500.0))).unwrap();
+        resampler.next(&item(10, Some(510.0))).unwrap();
+        resampler.next(&item(20, None)).unwrap();
+
+        let resampled = resampler.flush().unwrap();
+        assert_eq!(resampled.volume(), 30.0);
+        assert_eq!(resampled.trade_count(), Some(9));
+        assert_eq!(resampled.bid_volume(), Some(12.0));
+        assert_eq!(resampled.ask_volume(), Some(18.0));
+        assert_eq!(resampled.open_interest(), Some(510.0));
+
+        resampler.next(&bar(60, 1.0, 1.0, 1.0, 1.0)).unwrap();
+        assert_eq!(resampler.in_progress().unwrap().trade_count(), None);
+    }
+
+    #[test]
+    fn test_flush_and_reset() {
+        let mut resampler = Resampler::new(60).unwrap();
+        assert_eq!(resampler.flush(), None);
+
+        resampler.next(&bar(0, 1.0, 2.0, 1.0, 2.0)).unwrap();
+        assert_eq!(resampler.flush().unwrap().close(), 2.0);
+        assert_eq!(resampler.in_progress(), None);
+
+        // a flushed interval starts over
+        resampler.next(&bar(10, 3.0, 3.0, 3.0, 3.0)).unwrap();
+        assert_eq!(resampler.in_progress().unwrap().open(), 3.0);
+
+        resampler.reset();
+        assert_eq!(resampler.in_progress(), None);
+        assert_eq!(resampler.next(&bar(0, 1.0, 1.0, 1.0, 1.0)), Ok(None));
+    }
+
+    #[test]
+    fn test_hourly_to_daily() {
+        const HOUR: i64 = 3_600_000;
+        let mut resampler = Resampler::new(24 * HOUR).unwrap();
+        let days: Vec<DataItem> = (0..72)
+            .filter_map(|hour| {
+                let close = 100.0 + hour as f64;
+                resampler
+                    .next(&bar(hour * HOUR, close, close, close, close))
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[1].open(), 124.0);
+        assert_eq!(days[1].close(), 147.0);
+        assert_eq!(days[1].volume(), 2400.0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Resampler::new(60).unwrap()), "RESAMPLER(60)");
+        let resampler = Resampler::new(60).unwrap().with_offset(15);
+        assert_eq!(format!("{}", resampler), "RESAMPLER(60, 15)");
+    }
+}
@@ -0,0 +1,409 @@
+//! A price history shared by many window indicators.
+//!
+//! Every window indicator keeps its own copy of the last _period_ inputs. A screener running
+//! 20 indicators on the closes of a symbol stores the same closes 20 times. A
+//! [PriceBuffer](struct.PriceBuffer.html) stores the last bars once, and the window
+//! indicators of this module read their windows from it instead: they only hold their
+//! parameters, so their size doesn't grow with the period.
+//!
+//! The buffer is fed with [push](struct.PriceBuffer.html#method.push), then the indicators are
+//! fed with the buffer. An indicator returns `None` until the buffer holds a full window, so a
+//! buffer needs a capacity of at least the longest period reading from it.
+//!
+//! Custom window computations can use [window](struct.PriceBuffer.html#method.window) directly.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::buffer::{Field, Highest, PriceBuffer, Sma};
+//! use ta::{DataItem, Next};
+//!
+//! let mut buffer = PriceBuffer::new(3).unwrap();
+//! let mut sma = Sma::new(Field::Close, 3).unwrap();
+//! let mut highest = Highest::new(Field::High, 2).unwrap();
+//!
+//! for &(high, close) in &[(11.0, 10.0), (14.0, 13.0), (12.0, 10.0)] {
+//!     let bar = DataItem::builder()
+//!         .open(close).high(high).low(close).close(close).volume(1.0)
+//!         .build().unwrap();
+//!     buffer.push(&bar);
+//! }
+//! assert_eq!(sma.next(&buffer), Some(11.0));
+//! assert_eq!(highest.next(&buffer), Some(14.0));
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, High, Low, Next, Open, Period, Reset, Volume};
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A field of the bars stored in a [PriceBuffer](struct.PriceBuffer.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Open,
+    High,
+    Low,
+    Close,
+    Volume,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Field::Open => write!(f, "open"),
+            Field::High => write!(f, "high"),
+            Field::Low => write!(f, "low"),
+            Field::Close => write!(f, "close"),
+            Field::Volume => write!(f, "volume"),
+        }
+    }
+}
+
+/// Stores the last bars, up to its capacity, once for many indicators.
+///
+/// The fields are stored in one ring buffer each. Pushing a bar into a full buffer drops the
+/// oldest one.
+///
+/// # Parameters
+///
+/// * _capacity_ - number of bars kept (integer greater than 0).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct PriceBuffer {
+    capacity: usize,
+    columns: [Vec<f64>; 5],
+    index: usize,
+    len: usize,
+}
+
+impl PriceBuffer {
+    pub fn new(capacity: usize) -> Result<Self> {
+        if capacity == 0 {
+            return Err(TaError::zero_period("capacity"));
+        }
+        let column = vec![0.0; capacity];
+        Ok(Self {
+            capacity,
+            columns: [
+                column.clone(),
+                column.clone(),
+                column.clone(),
+                column.clone(),
+                column,
+            ],
+            index: 0,
+            len: 0,
+        })
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of bars stored.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push<T: Open + High + Low + Close + Volume>(&mut self, bar: &T) {
+        let values = [bar.open(), bar.high(), bar.low(), bar.close(), bar.volume()];
+        for (column, value) in self.columns.iter_mut().zip(values) {
+            column[self.index] = value;
+        }
+        self.advance();
+    }
+
+    /// Pushes a single value, e.g. of an index, as a bar with all the prices at the value and
+    /// no volume.
+    pub fn push_value(&mut self, value: f64) {
+        for column in &mut self.columns[..4] {
+            column[self.index] = value;
+        }
+        self.columns[Field::Volume as usize][self.index] = 0.0;
+        self.advance();
+    }
+
+    fn advance(&mut self) {
+        self.index = if self.index + 1 < self.capacity {
+            self.index + 1
+        } else {
+            0
+        };
+        self.len = (self.len + 1).min(self.capacity);
+    }
+
+    /// The last `n` values of a field, oldest first, or `None` if fewer than `n` bars are
+    /// stored.
+    pub fn window(&self, field: Field, n: usize) -> Option<Window<'_>> {
+        if n > self.len {
+            return None;
+        }
+        let column = &self.columns[field as usize];
+        let start = (self.index + self.capacity - n) % self.capacity;
+        Some(if start + n <= self.capacity {
+            Window {
+                head: &column[start..start + n],
+                tail: &[],
+            }
+        } else {
+            Window {
+                head: &column[start..],
+                tail: &column[..self.index],
+            }
+        })
+    }
+
+    /// The value of a field of the last bar.
+    pub fn latest(&self, field: Field) -> Option<f64> {
+        self.window(field, 1).map(|window| window.head[0])
+    }
+}
+
+impl Reset for PriceBuffer {
+    fn reset(&mut self) {
+        self.index = 0;
+        self.len = 0;
+    }
+}
+
+impl fmt::Display for PriceBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PRICE_BUFFER({})", self.capacity)
+    }
+}
+
+/// The last values of a field, borrowed from a [PriceBuffer](struct.PriceBuffer.html).
+#[derive(Debug, Clone, Copy)]
+pub struct Window<'a> {
+    head: &'a [f64],
+    tail: &'a [f64],
+}
+
+impl<'a> Window<'a> {
+    pub fn len(&self) -> usize {
+        self.head.len() + self.tail.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The values, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = f64> + 'a {
+        self.head.iter().chain(self.tail.iter()).copied()
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.iter().sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.sum() / self.len() as f64
+    }
+
+    pub fn max(&self) -> f64 {
+        self.iter().fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    pub fn min(&self) -> f64 {
+        self.iter().fold(f64::INFINITY, f64::min)
+    }
+
+    /// Population standard deviation, like the
+    /// [StandardDeviation](../indicators/struct.StandardDeviation.html).
+    pub fn std_dev(&self) -> f64 {
+        let mean = self.mean();
+        let variance = self.iter().map(|value| (value - mean).powi(2)).sum::<f64>();
+        (variance / self.len() as f64).sqrt()
+    }
+}
+
+macro_rules! window_indicator {
+    ($(#[$doc:meta])* $name:ident, $label:expr, $method:ident) => {
+        $(#[$doc])*
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct $name {
+            field: Field,
+            period: usize,
+        }
+
+        impl $name {
+            pub fn new(field: Field, period: usize) -> Result<Self> {
+                match period {
+                    0 => Err(TaError::zero_period("period")),
+                    _ => Ok(Self { field, period }),
+                }
+            }
+
+            pub fn field(&self) -> Field {
+                self.field
+            }
+        }
+
+        impl Period for $name {
+            fn period(&self) -> usize {
+                self.period
+            }
+        }
+
+        impl<'a> Next<&'a PriceBuffer> for $name {
+            type Output = Option<f64>;
+
+            fn next(&mut self, buffer: &'a PriceBuffer) -> Self::Output {
+                buffer
+                    .window(self.field, self.period)
+                    .map(|window| window.$method())
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}({}, {})", $label, self.field, self.period)
+            }
+        }
+    };
+}
+
+window_indicator!(
+    /// Simple moving average of a field, read from a [PriceBuffer](struct.PriceBuffer.html).
+    Sma,
+    "SMA",
+    mean
+);
+window_indicator!(
+    /// Highest value of a field, read from a [PriceBuffer](struct.PriceBuffer.html).
+    Highest,
+    "MAX",
+    max
+);
+window_indicator!(
+    /// Lowest value of a field, read from a [PriceBuffer](struct.PriceBuffer.html).
+    Lowest,
+    "MIN",
+    min
+);
+window_indicator!(
+    /// Standard deviation of a field, read from a [PriceBuffer](struct.PriceBuffer.html).
+    StdDev,
+    "SD",
+    std_dev
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{Maximum, SimpleMovingAverage, StandardDeviation};
+    use crate::test_helper::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    fn buffer(capacity: usize, closes: &[f64]) -> PriceBuffer {
+        let mut buffer = PriceBuffer::new(capacity).unwrap();
+        for &close in closes {
+            buffer.push_value(close);
+        }
+        buffer
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(PriceBuffer::new(0).is_err());
+        assert!(PriceBuffer::new(1).is_ok());
+        assert!(Sma::new(Field::Close, 0).is_err());
+        assert!(Sma::new(Field::Close, 1).is_ok());
+    }
+
+    #[test]
+    fn test_window() {
+        let buffer = buffer(3, &[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(buffer.len(), 3);
+        let window = buffer.window(Field::Close, 3).unwrap();
+        assert_eq!(window.iter().collect::<Vec<_>>(), vec![3.0, 4.0, 5.0]);
+        let window = buffer.window(Field::Close, 2).unwrap();
+        assert_eq!(window.iter().collect::<Vec<_>>(), vec![4.0, 5.0]);
+        assert!(buffer.window(Field::Close, 4).is_none());
+        assert_eq!(buffer.latest(Field::Close), Some(5.0));
+        assert_eq!(buffer.latest(Field::Volume), Some(0.0));
+    }
+
+    #[test]
+    fn test_fields() {
+        let mut buffer = PriceBuffer::new(2).unwrap();
+        buffer.push(&Bar::new().open(1).high(4).low(0.5).close(2).volume(100.0));
+        let bar = |field| buffer.latest(field).unwrap();
+        assert_eq!(
+            (bar(Field::Open), bar(Field::High), bar(Field::Low)),
+            (1.0, 4.0, 0.5)
+        );
+        assert_eq!((bar(Field::Close), bar(Field::Volume)), (2.0, 100.0));
+    }
+
+    #[test]
+    fn test_matches_indicators() {
+        let closes = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+        let mut buffer = PriceBuffer::new(4).unwrap();
+        let mut sma = Sma::new(Field::Close, 4).unwrap();
+        let mut highest = Highest::new(Field::Close, 3).unwrap();
+        let mut sd = StdDev::new(Field::Close, 4).unwrap();
+        let mut sma_ref = SimpleMovingAverage::new(4).unwrap();
+        let mut max_ref = Maximum::new(3).unwrap();
+        let mut sd_ref = StandardDeviation::new(4).unwrap();
+
+        for (i, &close) in closes.iter().enumerate() {
+            buffer.push_value(close);
+            let (sma_value, max_value) = (sma_ref.next(close), max_ref.next(close));
+            let sd_value = sd_ref.next(close);
+            if i >= 3 {
+                assert_eq!(round(sma.next(&buffer).unwrap()), round(sma_value));
+                assert_eq!(round(sd.next(&buffer).unwrap()), round(sd_value));
+            } else {
+                assert_eq!(sma.next(&buffer), None);
+            }
+            if i >= 2 {
+                assert_eq!(highest.next(&buffer), Some(max_value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_lowest() {
+        let buffer = buffer(5, &[4.0, 2.0, 3.0]);
+        let mut lowest = Lowest::new(Field::Close, 2).unwrap();
+        assert_eq!(lowest.next(&buffer), Some(2.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut buffer = buffer(2, &[1.0, 2.0]);
+        buffer.reset();
+        assert!(buffer.is_empty());
+        assert!(buffer.window(Field::Close, 1).is_none());
+        buffer.push_value(3.0);
+        assert_eq!(buffer.latest(Field::Close), Some(3.0));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", PriceBuffer::new(100).unwrap()),
+            "PRICE_BUFFER(100)"
+        );
+        let sma = Sma::new(Field::Close, 20).unwrap();
+        assert_eq!(format!("{}", sma), "SMA(close, 20)");
+        assert_eq!(sma.period(), 20);
+        let sd = StdDev::new(Field::High, 5).unwrap();
+        assert_eq!(format!("{}", sd), "SD(high, 5)");
+    }
+}
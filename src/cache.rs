@@ -0,0 +1,383 @@
+//! Memoization of batch computations.
+//!
+//! Optimizers and notebooks often run the same indicator over the same data many times. A
+//! [BatchCache](struct.BatchCache.html) stores the outputs of such runs keyed by the indicator
+//! spec, a [Fingerprint](struct.Fingerprint.html) of the data and the range of the data, and
+//! returns the stored outputs instead of recomputing them.
+//!
+//! The caller names every computation with a spec, e.g. `EMA(9)`, and computations with the
+//! same spec share the cached outputs. The spec must identify the indicator and all its
+//! parameters: the `Display` string does not, e.g. the plain and the compensated SMA are both
+//! `SMA(3)`, and `MAP(SMA(2))` looks the same for any closure. Every computation starts from a
+//! reset clone of the indicator, so the outputs don't depend on its current state.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::cache::{BatchCache, Fingerprint};
+//! use ta::indicators::ExponentialMovingAverage;
+//!
+//! let closes = [1.0, 2.0, 3.0, 4.0];
+//! let data = Fingerprint::of_values(&closes);
+//! let ema = ExponentialMovingAverage::new(3).unwrap();
+//!
+//! let mut cache = BatchCache::new(16).unwrap();
+//! let outputs = cache
+//!     .compute("EMA(3)", &ema, data, 0..4, closes.iter().copied())
+//!     .to_vec();
+//! assert_eq!(outputs, vec![1.0, 1.5, 2.25, 3.125]);
+//!
+//! // the second run is served from the cache
+//! cache.compute("EMA(3)", &ema, data, 0..4, closes.iter().copied());
+//! assert_eq!(cache.hits(), 1);
+//! assert_eq!(cache.misses(), 1);
+//! ```
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, High, Low, Next, Open, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A hash of a data set, which identifies it in a [BatchCache](struct.BatchCache.html).
+///
+/// Computing the fingerprint reads the whole data set, so compute it once and reuse it for all
+/// the lookups on the same data.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    /// Fingerprint of a series of values, e.g. close prices.
+    pub fn of_values(values: &[f64]) -> Self {
+        let mut hasher = DefaultHasher::new();
+        values.len().hash(&mut hasher);
+        for value in values {
+            value.to_bits().hash(&mut hasher);
+        }
+        Self(hasher.finish())
+    }
+
+    /// Fingerprint of a series of bars. All the prices and the volume are included.
+    pub fn of_bars<T: Open + High + Low + Close + Volume>(bars: &[T]) -> Self {
+        let mut hasher = DefaultHasher::new();
+        bars.len().hash(&mut hasher);
+        for bar in bars {
+            for value in [bar.open(), bar.high(), bar.low(), bar.close(), bar.volume()] {
+                value.to_bits().hash(&mut hasher);
+            }
+        }
+        Self(hasher.finish())
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    spec: String,
+    data: Fingerprint,
+    start: usize,
+    end: usize,
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry<O> {
+    outputs: Vec<O>,
+    last_used: u64,
+}
+
+/// Stores outputs of batch computations with outputs of type `O`.
+///
+/// When the cache is full, the least recently used entry is evicted.
+///
+/// # Parameters
+///
+/// * _capacity_ - maximum number of stored computations (integer greater than 0).
+#[derive(Debug, Clone)]
+pub struct BatchCache<O> {
+    capacity: usize,
+    entries: HashMap<CacheKey, CacheEntry<O>>,
+    clock: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl<O> BatchCache<O> {
+    pub fn new(capacity: usize) -> Result<Self> {
+        match capacity {
+            0 => Err(TaError::zero_period("capacity")),
+            _ => Ok(Self {
+                capacity,
+                entries: HashMap::new(),
+                clock: 0,
+                hits: 0,
+                misses: 0,
+            }),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of stored computations.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of computations served from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Number of computations, which had to be run.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Returns the outputs of `indicator` over the `range` of the data set identified by `data`.
+    ///
+    /// `spec` identifies the indicator with its parameters: the outputs cached under the same
+    /// spec are returned, whatever the indicator. The `inputs` must be the items of the range.
+    /// They are consumed only when the outputs are not cached yet.
+    pub fn compute<I, T>(
+        &mut self,
+        spec: &str,
+        indicator: &I,
+        data: Fingerprint,
+        range: Range<usize>,
+        inputs: impl IntoIterator<Item = T>,
+    ) -> &[O]
+    where
+        I: Clone + Reset + Next<T, Output = O>,
+    {
+        let key = CacheKey {
+            spec: spec.to_string(),
+            data,
+            start: range.start,
+            end: range.end,
+        };
+        self.clock += 1;
+
+        if self.entries.contains_key(&key) {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+            if self.entries.len() == self.capacity {
+                self.evict();
+            }
+
+            let mut indicator = indicator.clone();
+            indicator.reset();
+            let outputs = inputs.into_iter().map(|x| indicator.next(x)).collect();
+            self.entries.insert(
+                key.clone(),
+                CacheEntry {
+                    outputs,
+                    last_used: 0,
+                },
+            );
+        }
+
+        let entry = self.entries.get_mut(&key).unwrap();
+        entry.last_used = self.clock;
+        &entry.outputs
+    }
+
+    /// Removes all the stored computations over the data set identified by `data`.
+    pub fn invalidate(&mut self, data: Fingerprint) {
+        self.entries.retain(|key, _| key.data != data);
+    }
+
+    /// Removes all the stored computations.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn evict(&mut self) {
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone());
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{AverageTrueRange, ExponentialMovingAverage, SimpleMovingAverage};
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(BatchCache::<f64>::new(0).is_err());
+        assert!(BatchCache::<f64>::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        let a = Fingerprint::of_values(&[1.0, 2.0, 3.0]);
+        assert_eq!(a, Fingerprint::of_values(&[1.0, 2.0, 3.0]));
+        assert_ne!(a, Fingerprint::of_values(&[1.0, 2.0, 3.5]));
+        assert_ne!(a, Fingerprint::of_values(&[1.0, 2.0]));
+
+        let bars = [Bar::new().close(1.0), Bar::new().close(2.0)];
+        let revised = [Bar::new().close(1.0), Bar::new().close(2.0).volume(5.0)];
+        assert_ne!(Fingerprint::of_bars(&bars), Fingerprint::of_bars(&revised));
+    }
+
+    #[test]
+    fn test_compute() {
+        let closes = [1.0, 2.0, 3.0, 4.0];
+        let data = Fingerprint::of_values(&closes);
+        let mut cache = BatchCache::new(4).unwrap();
+
+        let sma = SimpleMovingAverage::new(2).unwrap();
+        assert_eq!(
+            cache.compute("SMA(2)", &sma, data, 0..4, closes.iter().copied()),
+            &[1.0, 1.5, 2.5, 3.5]
+        );
+        // the inputs are not consumed, when the outputs are cached
+        assert_eq!(
+            cache.compute("SMA(2)", &sma, data, 0..4, std::iter::empty::<f64>()),
+            &[1.0, 1.5, 2.5, 3.5]
+        );
+
+        // other parameters, range or data are computed
+        let sma3 = SimpleMovingAverage::new(3).unwrap();
+        assert_eq!(
+            cache
+                .compute("SMA(3)", &sma3, data, 0..4, closes.iter().copied())
+                .len(),
+            4
+        );
+        assert_eq!(
+            cache.compute("SMA(2)", &sma, data, 1..4, closes[1..].iter().copied()),
+            &[2.0, 2.5, 3.5]
+        );
+        let other = Fingerprint::of_values(&[5.0]);
+        assert_eq!(
+            cache.compute("SMA(2)", &sma, other, 0..1, [5.0].iter().copied()),
+            &[5.0]
+        );
+
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 4);
+        assert_eq!(cache.len(), 4);
+    }
+
+    #[test]
+    fn test_indicator_state() {
+        let closes = [2.0, 4.0];
+        let data = Fingerprint::of_values(&closes);
+        let mut cache = BatchCache::new(1).unwrap();
+
+        // a used indicator is reset before the computation
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        ema.next(100.0);
+        assert_eq!(
+            cache.compute("EMA(3)", &ema, data, 0..2, closes.iter().copied()),
+            &[2.0, 3.0]
+        );
+        assert_eq!(ema.next(100.0), 100.0);
+    }
+
+    #[test]
+    fn test_bars() {
+        let bars = [
+            Bar::new().high(2.0).low(1.0).close(1.5),
+            Bar::new().high(3.0).low(2.0).close(2.5),
+        ];
+        let data = Fingerprint::of_bars(&bars);
+        let mut cache = BatchCache::new(1).unwrap();
+
+        let atr = AverageTrueRange::new(3).unwrap();
+        assert_eq!(
+            cache.compute("ATR(3)", &atr, data, 0..2, bars.iter()),
+            &[1.0, 1.25]
+        );
+    }
+
+    #[test]
+    fn test_eviction() {
+        let closes = [1.0, 2.0];
+        let data = Fingerprint::of_values(&closes);
+        let mut cache = BatchCache::new(2).unwrap();
+        let mut sma = |period| {
+            let sma = SimpleMovingAverage::new(period).unwrap();
+            cache.compute(&sma.to_string(), &sma, data, 0..2, closes.iter().copied());
+        };
+
+        sma(1);
+        sma(2);
+        sma(1);
+
+        // SMA(2) is the least recently used
+        sma(3);
+        sma(1);
+        sma(2);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.hits(), 2);
+        assert_eq!(cache.misses(), 4);
+    }
+
+    #[test]
+    fn test_spec() {
+        let closes = [1e16, 1.0, -1e16];
+        let data = Fingerprint::of_values(&closes);
+        let mut cache = BatchCache::new(4).unwrap();
+
+        // same Display, other outputs
+        let plain = SimpleMovingAverage::new(3).unwrap();
+        let compensated = SimpleMovingAverage::compensated(3).unwrap();
+        assert_eq!(plain.to_string(), compensated.to_string());
+
+        let plain = cache
+            .compute("SMA(3)", &plain, data, 0..3, closes.iter().copied())
+            .to_vec();
+        let compensated = cache
+            .compute(
+                "SMA(3, compensated)",
+                &compensated,
+                data,
+                0..3,
+                closes.iter().copied(),
+            )
+            .to_vec();
+        assert_ne!(plain[2], compensated[2]);
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let closes = [1.0, 2.0];
+        let data = Fingerprint::of_values(&closes);
+        let other = Fingerprint::of_values(&[3.0]);
+        let mut cache = BatchCache::new(4).unwrap();
+        let sma = SimpleMovingAverage::new(2).unwrap();
+
+        cache.compute("SMA(2)", &sma, data, 0..2, closes.iter().copied());
+        cache.compute("SMA(2)", &sma, other, 0..1, [3.0].iter().copied());
+
+        cache.invalidate(data);
+        assert_eq!(cache.len(), 1);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
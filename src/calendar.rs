@@ -0,0 +1,588 @@
+//! Trading calendars, which tell when the sessions of a market open and close.
+//!
+//! Session-aware indicators, e.g. the [session stats](../indicators/struct.SessionStats.html)
+//! with their session VWAP or the
+//! [prior session levels](../indicators/struct.PriorSessionLevels.html), start over at every
+//! session. A [TradingCalendar](trait.TradingCalendar.html) finds the
+//! session of a timestamp, and the [Sessions](struct.Sessions.html) wrapper signals the
+//! boundaries to the indicator, when the session of the bars changes.
+//!
+//! The [Calendar](struct.Calendar.html) is a configurable calendar with a daily session, the
+//! trading days of the week, holidays and a fixed offset to UTC.
+//! [Calendar::crypto](struct.Calendar.html#method.crypto) is the calendar of markets trading
+//! around the clock, with a session per UTC day.
+//!
+//! Timestamps are milliseconds since the Unix epoch.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::calendar::{Calendar, Session, Sessions, HOUR, MINUTE};
+//! use ta::indicators::SessionStats;
+//! use ta::{DataItem, Next};
+//!
+//! fn bar(timestamp: i64, close: f64) -> DataItem {
+//!     DataItem::builder()
+//!         .open(close).high(close).low(close).close(close).volume(100.0)
+//!         .timestamp(timestamp)
+//!         .build().unwrap()
+//! }
+//!
+//! // 09:30 to 16:00 at UTC-5, Monday to Friday
+//! let session = Session::new(9 * HOUR + 30 * MINUTE, 16 * HOUR).unwrap();
+//! let calendar = Calendar::new(session).with_utc_offset(-5 * HOUR);
+//! let mut stats = Sessions::new(SessionStats::new(), calendar);
+//!
+//! // Monday 2024-01-08, 15:00 and 15:30 at UTC-5
+//! let monday = 1_704_672_000_000 + 5 * HOUR;
+//! stats.next(&bar(monday + 15 * HOUR, 10.0)).unwrap();
+//! assert_eq!(stats.next(&bar(monday + 15 * HOUR + 30 * MINUTE, 12.0)).unwrap().open, 10.0);
+//!
+//! // the session of Tuesday starts over
+//! let tuesday = monday + 24 * HOUR;
+//! let out = stats.next(&bar(tuesday + 10 * HOUR, 11.0)).unwrap();
+//! assert_eq!(out.open, 11.0);
+//! assert_eq!(out.volume, 100.0);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Current, IsReady, Next, Period, Reset, SessionAware, Timestamped};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// One minute in milliseconds.
+pub const MINUTE: i64 = 60_000;
+/// One hour in milliseconds.
+pub const HOUR: i64 = 60 * MINUTE;
+/// One day in milliseconds.
+pub const DAY: i64 = 24 * HOUR;
+
+/// Finds the trading sessions of a market.
+pub trait TradingCalendar {
+    /// Start of the session containing `timestamp`, `None` when the market is closed.
+    fn session_start(&self, timestamp: i64) -> Option<i64>;
+
+    /// Returns `true`, when the market is open at `timestamp`.
+    fn is_open(&self, timestamp: i64) -> bool {
+        self.session_start(timestamp).is_some()
+    }
+}
+
+/// Day of the week.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    /// Day of the week of a day counted from the Unix epoch, a Thursday.
+    fn of_day(day: i64) -> usize {
+        (day + 3).rem_euclid(7) as usize
+    }
+}
+
+/// Opening hours of a daily session, in milliseconds after midnight.
+///
+/// A session closing before it opens runs overnight, e.g. from 18:00 to 17:00 the next day,
+/// and belongs to the trading day, on which it closes.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Session {
+    open: i64,
+    close: i64,
+}
+
+impl Session {
+    pub fn new(open: i64, close: i64) -> Result<Self> {
+        if !(0..DAY).contains(&open) {
+            return Err(TaError::invalid_parameter(
+                "open",
+                open as f64,
+                "must be within a day",
+            ));
+        }
+        if !(0..=DAY).contains(&close) {
+            return Err(TaError::invalid_parameter(
+                "close",
+                close as f64,
+                "must be within a day",
+            ));
+        }
+        if open == close {
+            return Err(TaError::invalid_parameter(
+                "close",
+                close as f64,
+                "must not be equal to open",
+            ));
+        }
+        Ok(Self { open, close })
+    }
+
+    /// Session lasting the whole day.
+    pub fn all_day() -> Self {
+        Self {
+            open: 0,
+            close: DAY,
+        }
+    }
+
+    pub fn open(&self) -> i64 {
+        self.open
+    }
+
+    pub fn close(&self) -> i64 {
+        self.close
+    }
+
+    /// Returns `true`, when the session opens the day before it closes.
+    pub fn is_overnight(&self) -> bool {
+        self.close < self.open
+    }
+}
+
+/// Calendar with one session per trading day.
+///
+/// By default Monday to Friday are trading days, without holidays, and the session times are
+/// UTC. Holidays are trading days without a session.
+///
+/// # Example
+///
+/// ```
+/// use ta::calendar::{Calendar, Session, TradingCalendar, Weekday, HOUR};
+///
+/// // 18:00 to 17:00 the next day, Sunday evening to Friday
+/// let session = Session::new(18 * HOUR, 17 * HOUR).unwrap();
+/// let calendar = Calendar::new(session)
+///     .with_holiday(2024, 12, 25)
+///     .unwrap();
+///
+/// // Monday 2024-01-08
+/// let monday = 1_704_672_000_000;
+/// assert_eq!(calendar.session_start(monday + 16 * HOUR), Some(monday - 6 * HOUR));
+/// assert!(!calendar.is_open(monday + 17 * HOUR));
+/// assert_eq!(calendar.session_start(monday + 19 * HOUR), Some(monday + 18 * HOUR));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calendar {
+    session: Session,
+    utc_offset: i64,
+    weekdays: [bool; 7],
+    holidays: Vec<i64>,
+}
+
+impl Calendar {
+    pub fn new(session: Session) -> Self {
+        Self {
+            session,
+            utc_offset: 0,
+            weekdays: [true, true, true, true, true, false, false],
+            holidays: Vec::new(),
+        }
+    }
+
+    /// Calendar of a market trading around the clock, every day of the week, with a session
+    /// per UTC day.
+    pub fn crypto() -> Self {
+        Self::new(Session::all_day()).with_weekdays(&[
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+            Weekday::Sunday,
+        ])
+    }
+
+    /// Sets the offset of the local time of the market to UTC, e.g. `-5 * HOUR` for New York
+    /// in winter. The session times and the days are local times.
+    pub fn with_utc_offset(mut self, offset: i64) -> Self {
+        self.utc_offset = offset;
+        self
+    }
+
+    /// Sets the trading days of the week.
+    pub fn with_weekdays(mut self, weekdays: &[Weekday]) -> Self {
+        self.weekdays = [false; 7];
+        for &weekday in weekdays {
+            self.weekdays[weekday as usize] = true;
+        }
+        self
+    }
+
+    /// Adds a holiday, a day without a session.
+    pub fn with_holiday(mut self, year: i32, month: u32, day: u32) -> Result<Self> {
+        let day = days_from_civil(year, month, day)?;
+        if let Err(index) = self.holidays.binary_search(&day) {
+            self.holidays.insert(index, day);
+        }
+        Ok(self)
+    }
+
+    pub fn session(&self) -> Session {
+        self.session
+    }
+
+    pub fn utc_offset(&self) -> i64 {
+        self.utc_offset
+    }
+
+    fn is_trading_day(&self, day: i64) -> bool {
+        self.weekdays[Weekday::of_day(day)] && self.holidays.binary_search(&day).is_err()
+    }
+}
+
+impl TradingCalendar for Calendar {
+    fn session_start(&self, timestamp: i64) -> Option<i64> {
+        let local = timestamp + self.utc_offset;
+        let today = local.div_euclid(DAY);
+        // an overnight session of the next trading day may already be open
+        let last = if self.session.is_overnight() {
+            today + 1
+        } else {
+            today
+        };
+        (today..=last)
+            .filter(|&day| self.is_trading_day(day))
+            .map(|day| {
+                let close = day * DAY + self.session.close;
+                let mut open = day * DAY + self.session.open;
+                if self.session.is_overnight() {
+                    open -= DAY;
+                }
+                (open, close)
+            })
+            .find(|&(open, close)| open <= local && local < close)
+            .map(|(open, _)| open - self.utc_offset)
+    }
+}
+
+/// Days since the Unix epoch of a date of the proleptic Gregorian calendar.
+fn days_from_civil(year: i32, month: u32, day: u32) -> Result<i64> {
+    if !(1..=12).contains(&month) {
+        return Err(TaError::invalid_parameter(
+            "month",
+            month as f64,
+            "must be between 1 and 12",
+        ));
+    }
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if !(1..=days_in_month).contains(&day) {
+        return Err(TaError::invalid_parameter(
+            "day",
+            day as f64,
+            "must be a day of the month",
+        ));
+    }
+    // years start in March, so the leap day is the last day of a year
+    let year = if month <= 2 { year - 1 } else { year } as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month as i64 + 9) % 12) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    Ok(era * 146_097 + day_of_era - 719_468)
+}
+
+/// Wraps a [session-aware](../trait.SessionAware.html) indicator, so that it follows the
+/// sessions of a trading calendar.
+///
+/// A session opens with the first bar within it, and closes with the first bar outside of it,
+/// i.e. outside of all sessions or in the next session. Bars outside of all sessions are still
+/// fed into the indicator, e.g. as the overnight trading of the
+/// [prior session levels](../indicators/struct.PriorSessionLevels.html).
+///
+/// Bars without a timestamp are rejected with `DataItemIncomplete`, without feeding them into
+/// the indicator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Sessions<I, C = Calendar> {
+    indicator: I,
+    calendar: C,
+    session: Option<i64>,
+}
+
+impl<I, C> Sessions<I, C> {
+    pub fn new(indicator: I, calendar: C) -> Self {
+        Self {
+            indicator,
+            calendar,
+            session: None,
+        }
+    }
+
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn calendar(&self) -> &C {
+        &self.calendar
+    }
+
+    /// Start of the session of the last bar, `None` when it was outside of all sessions.
+    pub fn session_start(&self) -> Option<i64> {
+        self.session
+    }
+}
+
+impl<'a, I, C, T> Next<&'a T> for Sessions<I, C>
+where
+    I: Next<&'a T> + SessionAware,
+    C: TradingCalendar,
+    T: Timestamped,
+{
+    type Output = Result<I::Output>;
+
+    fn next(&mut self, input: &'a T) -> Self::Output {
+        let timestamp = input
+            .timestamp()
+            .ok_or(TaError::DataItemIncomplete { field: "timestamp" })?;
+        let session = self.calendar.session_start(timestamp);
+        if session != self.session {
+            if self.session.is_some() {
+                self.indicator.close_session();
+            }
+            if session.is_some() {
+                self.indicator.open_session();
+            }
+            self.session = session;
+        }
+        Ok(self.indicator.next(input))
+    }
+}
+
+impl<I: Period, C> Period for Sessions<I, C> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: IsReady, C> IsReady for Sessions<I, C> {
+    fn bars_until_ready(&self) -> usize {
+        self.indicator.bars_until_ready()
+    }
+}
+
+impl<I: Current, C> Current for Sessions<I, C> {
+    type Output = I::Output;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.indicator.current()
+    }
+}
+
+impl<I: Reset, C> Reset for Sessions<I, C> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.session = None;
+    }
+}
+
+impl<I: fmt::Display, C> fmt::Display for Sessions<I, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SESSIONS({})", self.indicator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{PriorSessionLevels, SessionStats};
+    use crate::DataItem;
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    // Monday 2024-01-08 00:00 UTC
+    const MONDAY: i64 = 1_704_672_000_000;
+
+    fn bar(timestamp: i64, high: f64, low: f64) -> DataItem {
+        DataItem::builder()
+            .open(low)
+            .high(high)
+            .low(low)
+            .close(high)
+            .volume(10.0)
+            .timestamp(timestamp)
+            .build()
+            .unwrap()
+    }
+
+    fn regular_hours() -> Calendar {
+        Calendar::new(Session::new(9 * HOUR + 30 * MINUTE, 16 * HOUR).unwrap())
+    }
+
+    #[test]
+    fn test_session_new() {
+        assert!(Session::new(-1, HOUR).is_err());
+        assert!(Session::new(DAY, HOUR).is_err());
+        assert!(Session::new(HOUR, DAY + 1).is_err());
+        assert!(Session::new(HOUR, HOUR).is_err());
+        assert!(!Session::new(0, DAY).unwrap().is_overnight());
+        assert!(Session::new(18 * HOUR, 17 * HOUR).unwrap().is_overnight());
+        assert_eq!(Session::all_day(), Session::new(0, DAY).unwrap());
+    }
+
+    #[test]
+    fn test_days_from_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), Ok(0));
+        assert_eq!(days_from_civil(2024, 1, 8), Ok(MONDAY / DAY));
+        assert_eq!(days_from_civil(2000, 3, 1), Ok(11_017));
+        assert_eq!(days_from_civil(1969, 12, 31), Ok(-1));
+        assert!(days_from_civil(2024, 2, 29).is_ok());
+        assert!(days_from_civil(2023, 2, 29).is_err());
+        assert!(days_from_civil(2100, 2, 29).is_err());
+        assert!(days_from_civil(2024, 13, 1).is_err());
+        assert!(days_from_civil(2024, 4, 31).is_err());
+        assert_eq!(Weekday::of_day(MONDAY / DAY), Weekday::Monday as usize);
+        assert_eq!(Weekday::of_day(-1), Weekday::Wednesday as usize);
+    }
+
+    #[test]
+    fn test_calendar() {
+        let calendar = regular_hours();
+        let open = MONDAY + 9 * HOUR + 30 * MINUTE;
+        assert_eq!(calendar.session_start(open - 1), None);
+        assert_eq!(calendar.session_start(open), Some(open));
+        assert_eq!(calendar.session_start(MONDAY + 16 * HOUR - 1), Some(open));
+        assert_eq!(calendar.session_start(MONDAY + 16 * HOUR), None);
+        // Saturday and Sunday
+        assert!(!calendar.is_open(open + 5 * DAY));
+        assert!(!calendar.is_open(open + 6 * DAY));
+        assert!(calendar.is_open(open + 7 * DAY));
+    }
+
+    #[test]
+    fn test_utc_offset() {
+        let calendar = regular_hours().with_utc_offset(-5 * HOUR);
+        let open = MONDAY + 14 * HOUR + 30 * MINUTE;
+        assert_eq!(calendar.session_start(open), Some(open));
+        assert!(calendar.is_open(MONDAY + 20 * HOUR + 59 * MINUTE));
+        assert!(!calendar.is_open(MONDAY + 21 * HOUR));
+        assert_eq!(calendar.utc_offset(), -5 * HOUR);
+    }
+
+    #[test]
+    fn test_overnight() {
+        let session = Session::new(18 * HOUR, 17 * HOUR).unwrap();
+        let calendar = Calendar::new(session);
+        // the session of Monday opens on Sunday
+        let sunday_open = MONDAY - 6 * HOUR;
+        assert_eq!(calendar.session_start(sunday_open), Some(sunday_open));
+        assert_eq!(calendar.session_start(MONDAY + HOUR), Some(sunday_open));
+        assert!(!calendar.is_open(sunday_open - 1));
+        // the session of Friday closes on Friday, nothing opens on Friday evening
+        let friday = MONDAY + 4 * DAY;
+        assert!(calendar.is_open(friday + 16 * HOUR));
+        assert!(!calendar.is_open(friday + 19 * HOUR));
+        assert!(!calendar.is_open(friday + DAY + 12 * HOUR));
+    }
+
+    #[test]
+    fn test_holidays_and_weekdays() {
+        let calendar = regular_hours()
+            .with_holiday(2024, 1, 9)
+            .unwrap()
+            .with_holiday(2024, 1, 9)
+            .unwrap();
+        assert_eq!(calendar.holidays.len(), 1);
+        assert!(calendar.is_open(MONDAY + 10 * HOUR));
+        assert!(!calendar.is_open(MONDAY + DAY + 10 * HOUR));
+        assert!(regular_hours().with_holiday(2024, 2, 30).is_err());
+
+        let calendar = regular_hours().with_weekdays(&[Weekday::Sunday]);
+        assert!(!calendar.is_open(MONDAY + 10 * HOUR));
+        assert!(calendar.is_open(MONDAY - DAY + 10 * HOUR));
+    }
+
+    #[test]
+    fn test_crypto() {
+        let calendar = Calendar::crypto();
+        let saturday = MONDAY + 5 * DAY;
+        assert_eq!(calendar.session_start(saturday), Some(saturday));
+        assert_eq!(calendar.session_start(saturday + DAY - 1), Some(saturday));
+        assert_eq!(calendar.session_start(saturday + DAY), Some(saturday + DAY));
+        assert_eq!(calendar.session_start(-1), Some(-DAY));
+    }
+
+    #[test]
+    fn test_sessions_next() {
+        let mut stats = Sessions::new(SessionStats::new(), Calendar::crypto());
+        stats.next(&bar(MONDAY + HOUR, 11.0, 10.0)).unwrap();
+        let out = stats.next(&bar(MONDAY + 2 * HOUR, 13.0, 12.0)).unwrap();
+        assert_eq!((out.open, out.high, out.volume), (10.0, 13.0, 20.0));
+        assert_eq!(stats.session_start(), Some(MONDAY));
+
+        let out = stats.next(&bar(MONDAY + DAY, 9.0, 8.0)).unwrap();
+        assert_eq!((out.open, out.high, out.volume), (8.0, 9.0, 10.0));
+    }
+
+    #[test]
+    fn test_sessions_close() {
+        let mut levels = Sessions::new(PriorSessionLevels::new(), regular_hours());
+        levels.next(&bar(MONDAY + 10 * HOUR, 12.0, 10.0)).unwrap();
+        levels.next(&bar(MONDAY + 11 * HOUR, 13.0, 11.0)).unwrap();
+        assert!(levels.indicator().in_session());
+
+        // after the close
+        let out = levels.next(&bar(MONDAY + 17 * HOUR, 14.0, 12.5)).unwrap();
+        assert!(!levels.indicator().in_session());
+        assert_eq!(levels.session_start(), None);
+        assert_eq!(out.high, Some(13.0));
+        assert_eq!(out.low, Some(10.0));
+
+        let out = levels
+            .next(&bar(MONDAY + DAY + 10 * HOUR, 13.5, 12.0))
+            .unwrap();
+        assert_eq!(out.overnight_high, Some(14.0));
+    }
+
+    #[test]
+    fn test_sessions_errors() {
+        let mut stats = Sessions::new(SessionStats::new(), Calendar::crypto());
+        let no_timestamp = DataItem::builder()
+            .open(1.0)
+            .high(1.0)
+            .low(1.0)
+            .close(1.0)
+            .volume(1.0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            stats.next(&no_timestamp),
+            Err(TaError::DataItemIncomplete { field: "timestamp" })
+        );
+        assert_eq!(stats.indicator().bars(), 0);
+    }
+
+    #[test]
+    fn test_sessions_reset() {
+        let mut stats = Sessions::new(SessionStats::new(), Calendar::crypto());
+        stats.next(&bar(MONDAY, 1.0, 1.0)).unwrap();
+        stats.reset();
+        assert_eq!(stats.session_start(), None);
+        assert_eq!(stats.current(), None);
+        assert!(stats.next(&bar(MONDAY, 1.0, 1.0)).is_ok());
+    }
+
+    #[test]
+    fn test_display() {
+        let stats = Sessions::new(SessionStats::new(), Calendar::crypto());
+        assert_eq!(format!("{}", stats), "SESSIONS(SESSION)");
+    }
+}
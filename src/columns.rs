@@ -0,0 +1,439 @@
+//! Indicators computed over bars stored by column.
+//!
+//! Dataframes store the bars by column: one slice of opens, one of highs and so on.
+//! [compute](fn.compute.html) runs the indicators of an
+//! [IndicatorSet](../registry/struct.IndicatorSet.html) over all the rows of such
+//! [OhlcvColumns](struct.OhlcvColumns.html), and returns a column of values per output, so
+//! the results go back into the dataframe without a loop over the rows by hand. The columns
+//! are named like the outputs of the set: the name of the indicator, or `name.output` for
+//! indicators with several outputs, e.g. `bb.upper`.
+//!
+//! Bars stored by row, e.g. a 2-D array with a row per bar, are read through
+//! [OhlcvRows](struct.OhlcvRows.html) and [compute_rows](fn.compute_rows.html) instead.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::columns::{compute, OhlcvColumns};
+//! use ta::registry::IndicatorSet;
+//!
+//! let close = [10.0, 11.0, 12.0, 11.0];
+//! let columns = OhlcvColumns {
+//!     open: &close,
+//!     high: &[10.5, 11.5, 12.5, 11.5],
+//!     low: &[9.5, 10.5, 11.5, 10.5],
+//!     close: &close,
+//!     volume: &[100.0, 120.0, 80.0, 90.0],
+//! };
+//!
+//! let mut set = IndicatorSet::new();
+//! set.create("sma", "sma", &[("period", 2.0)]).unwrap();
+//! set.create("bb", "bb", &[("period", 2.0)]).unwrap();
+//!
+//! let results = compute(&mut set, &columns).unwrap();
+//! assert_eq!(results[0], ("sma".to_string(), vec![10.0, 10.5, 11.5, 11.5]));
+//! assert_eq!(results[2].0, "bb.upper");
+//! assert_eq!(results.len(), 4);
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, string::ToString, vec::Vec};
+
+use crate::errors::{Result, TaError};
+use crate::registry::IndicatorSet;
+use crate::DataItem;
+
+/// Bars stored by column, e.g. the columns of a dataframe. All columns have the same length.
+#[derive(Debug, Clone, Copy)]
+pub struct OhlcvColumns<'a> {
+    pub open: &'a [f64],
+    pub high: &'a [f64],
+    pub low: &'a [f64],
+    pub close: &'a [f64],
+    pub volume: &'a [f64],
+}
+
+impl<'a> OhlcvColumns<'a> {
+    /// Picks the columns by their standard names `open`, `high`, `low`, `close` and `volume`,
+    /// ignoring the case, from named columns, e.g. the fields of a record batch. Other columns
+    /// are ignored, the slices are borrowed, not copied.
+    ///
+    /// Fails with [DataItemIncomplete](../errors/enum.TaError.html#variant.DataItemIncomplete)
+    /// for the first missing column.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ta::columns::OhlcvColumns;
+    ///
+    /// let close = [10.0, 11.0];
+    /// let named: [(&str, &[f64]); 6] = [
+    ///     ("timestamp", &[1.0, 2.0]),
+    ///     ("Open", &[10.0, 11.0]),
+    ///     ("High", &[10.5, 11.5]),
+    ///     ("Low", &[9.5, 10.5]),
+    ///     ("Close", &close),
+    ///     ("Volume", &[100.0, 120.0]),
+    /// ];
+    /// let columns = OhlcvColumns::from_named(&named).unwrap();
+    /// assert_eq!(columns.close, close);
+    /// ```
+    pub fn from_named(columns: &[(&str, &'a [f64])]) -> Result<Self> {
+        let find = |field: &'static str| {
+            columns
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(field))
+                .map(|&(_, column)| column)
+                .ok_or(TaError::DataItemIncomplete { field })
+        };
+        Ok(Self {
+            open: find("open")?,
+            high: find("high")?,
+            low: find("low")?,
+            close: find("close")?,
+            volume: find("volume")?,
+        })
+    }
+
+    /// Number of rows, the length of the close column.
+    pub fn len(&self) -> usize {
+        self.close.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.close.is_empty()
+    }
+
+    /// Checks that all the columns have as many rows as the close column.
+    fn validate(&self) -> Result<()> {
+        for column in [self.open, self.high, self.low, self.volume] {
+            if column.len() != self.len() {
+                return Err(TaError::LengthMismatch {
+                    expected: self.len(),
+                    actual: column.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The bar of a row, with the validation of the
+    /// [DataItem builder](../struct.DataItemBuilder.html).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds of one of the columns.
+    pub fn bar(&self, row: usize) -> Result<DataItem> {
+        DataItem::builder()
+            .open(self.open[row])
+            .high(self.high[row])
+            .low(self.low[row])
+            .close(self.close[row])
+            .volume(self.volume[row])
+            .build()
+    }
+}
+
+/// Bars stored by row, e.g. a 2-D array in row-major order, whose rows are the bars and whose
+/// columns are open, high, low, close and volume.
+///
+/// # Example
+///
+/// ```
+/// use ta::columns::{compute_rows, OhlcvRows};
+/// use ta::registry::IndicatorSet;
+///
+/// #[rustfmt::skip]
+/// let values = [
+///     10.0, 10.5, 9.5, 10.0, 100.0,
+///     11.0, 11.5, 10.5, 11.0, 120.0,
+/// ];
+/// let rows = OhlcvRows::new(&values).unwrap();
+/// assert_eq!(rows.len(), 2);
+///
+/// let mut set = IndicatorSet::new();
+/// set.create("tr", "tr", &[]).unwrap();
+/// assert_eq!(compute_rows(&mut set, &rows).unwrap()[0].1, [1.0, 1.5]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct OhlcvRows<'a> {
+    values: &'a [f64],
+}
+
+impl<'a> OhlcvRows<'a> {
+    /// Number of values of a row.
+    pub const COLUMNS: usize = 5;
+
+    /// Fails with [LengthMismatch](../errors/enum.TaError.html#variant.LengthMismatch), if the
+    /// last row is incomplete.
+    pub fn new(values: &'a [f64]) -> Result<Self> {
+        if !values.len().is_multiple_of(Self::COLUMNS) {
+            return Err(TaError::LengthMismatch {
+                expected: values.len().div_ceil(Self::COLUMNS) * Self::COLUMNS,
+                actual: values.len(),
+            });
+        }
+        Ok(Self { values })
+    }
+
+    /// Number of rows.
+    pub fn len(&self) -> usize {
+        self.values.len() / Self::COLUMNS
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The bar of a row, with the validation of the
+    /// [DataItem builder](../struct.DataItemBuilder.html).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is out of bounds.
+    pub fn bar(&self, row: usize) -> Result<DataItem> {
+        let values = &self.values[row * Self::COLUMNS..(row + 1) * Self::COLUMNS];
+        DataItem::builder()
+            .open(values[0])
+            .high(values[1])
+            .low(values[2])
+            .close(values[3])
+            .volume(values[4])
+            .build()
+    }
+}
+
+/// Feeds all the rows of `columns` to the indicators of `set`, and returns a named column of
+/// values per output, in the order of the indicators in the set.
+///
+/// The indicators continue from their current state, like feeding the rows with
+/// [next](../trait.Next.html). Fails with
+/// [LengthMismatch](../errors/enum.TaError.html#variant.LengthMismatch), if the columns have
+/// different lengths, and with the error of the first invalid bar, e.g. a high below the low.
+/// All the bars are checked before the first one is fed, so on an error the indicators keep
+/// their state.
+pub fn compute(set: &mut IndicatorSet, columns: &OhlcvColumns) -> Result<Vec<(String, Vec<f64>)>> {
+    columns.validate()?;
+    compute_bars(set, columns.len(), |row| columns.bar(row))
+}
+
+/// Same as [compute](fn.compute.html) for bars stored by row.
+pub fn compute_rows(set: &mut IndicatorSet, rows: &OhlcvRows) -> Result<Vec<(String, Vec<f64>)>> {
+    compute_bars(set, rows.len(), |row| rows.bar(row))
+}
+
+fn compute_bars<F>(set: &mut IndicatorSet, len: usize, bar: F) -> Result<Vec<(String, Vec<f64>)>>
+where
+    F: Fn(usize) -> Result<DataItem>,
+{
+    let mut results = Vec::new();
+    for (name, indicator) in set.iter_mut() {
+        match indicator.output_names() {
+            [_] => results.push((name.to_string(), Vec::with_capacity(len))),
+            output_names => {
+                for output in output_names {
+                    let column = format!("{}.{}", name, output);
+                    results.push((column, Vec::with_capacity(len)));
+                }
+            }
+        }
+    }
+
+    let bars = (0..len).map(bar).collect::<Result<Vec<_>>>()?;
+    for bar in bars.iter() {
+        let mut results = results.iter_mut();
+        for (_, indicator) in set.iter_mut() {
+            for (value, (_, column)) in indicator.next_values(bar).into_iter().zip(&mut results) {
+                column.push(value);
+            }
+        }
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::create;
+    use crate::Next;
+
+    const HIGH: [f64; 5] = [10.5, 11.5, 12.5, 11.5, 13.0];
+    const LOW: [f64; 5] = [9.5, 10.5, 11.5, 10.5, 12.0];
+    const CLOSE: [f64; 5] = [10.0, 11.0, 12.0, 11.0, 12.5];
+    const VOLUME: [f64; 5] = [100.0, 120.0, 80.0, 90.0, 150.0];
+
+    fn columns() -> OhlcvColumns<'static> {
+        OhlcvColumns {
+            open: &CLOSE,
+            high: &HIGH,
+            low: &LOW,
+            close: &CLOSE,
+            volume: &VOLUME,
+        }
+    }
+
+    fn set() -> IndicatorSet {
+        let mut set = IndicatorSet::new();
+        set.create("rsi", "rsi", &[("period", 3.0)]).unwrap();
+        set.create("macd", "macd", &[]).unwrap();
+        set.create("atr", "atr", &[("period", 2.0)]).unwrap();
+        set
+    }
+
+    #[test]
+    fn test_names() {
+        let results = compute(&mut set(), &columns()).unwrap();
+        let names: Vec<&str> = results.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["rsi", "macd.macd", "macd.signal", "macd.histogram", "atr"]
+        );
+        assert!(results.iter().all(|(_, column)| column.len() == 5));
+    }
+
+    #[test]
+    fn test_same_as_next() {
+        let results = compute(&mut set(), &columns()).unwrap();
+
+        let mut set = set();
+        for row in 0..5 {
+            let values = set.next(&columns().bar(row).unwrap());
+            for (name, column) in results.iter() {
+                assert_eq!(column[row], values[name]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_continues_from_state() {
+        let mut first = IndicatorSet::new();
+        first.insert("ema", create("ema", &[("period", 3.0)]).unwrap());
+        let mut second = IndicatorSet::new();
+        second.insert("ema", create("ema", &[("period", 3.0)]).unwrap());
+
+        let all = compute(&mut first, &columns()).unwrap();
+        let head = OhlcvColumns {
+            open: &CLOSE[..2],
+            high: &HIGH[..2],
+            low: &LOW[..2],
+            close: &CLOSE[..2],
+            volume: &VOLUME[..2],
+        };
+        let tail = OhlcvColumns {
+            open: &CLOSE[2..],
+            high: &HIGH[2..],
+            low: &LOW[2..],
+            close: &CLOSE[2..],
+            volume: &VOLUME[2..],
+        };
+        compute(&mut second, &head).unwrap();
+        let rest = compute(&mut second, &tail).unwrap();
+        assert_eq!(rest[0].1, all[0].1[2..]);
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        let columns = OhlcvColumns {
+            volume: &VOLUME[..3],
+            ..columns()
+        };
+        assert_eq!(
+            compute(&mut set(), &columns),
+            Err(TaError::LengthMismatch {
+                expected: 5,
+                actual: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_bar() {
+        let low = [9.5, 10.5, 13.0, 10.5, 12.0];
+        let columns = OhlcvColumns {
+            low: &low,
+            ..columns()
+        };
+        assert_eq!(
+            compute(&mut set(), &columns),
+            Err(TaError::DataItemInvalid { field: "low" })
+        );
+    }
+
+    #[test]
+    fn test_invalid_bar_keeps_state() {
+        let low = [9.5, 10.5, 11.5, 10.5, 14.0];
+        let invalid = OhlcvColumns {
+            low: &low,
+            ..columns()
+        };
+        let mut first = set();
+        assert!(compute(&mut first, &invalid).is_err());
+        assert_eq!(
+            compute(&mut first, &columns()),
+            compute(&mut set(), &columns())
+        );
+    }
+
+    #[test]
+    fn test_from_named() {
+        let named: [(&str, &[f64]); 5] = [
+            ("VOLUME", &VOLUME),
+            ("close", &CLOSE),
+            ("low", &LOW),
+            ("high", &HIGH),
+            ("open", &CLOSE),
+        ];
+        let columns = OhlcvColumns::from_named(&named).unwrap();
+        assert_eq!(columns.volume, VOLUME);
+        assert_eq!(
+            compute(&mut set(), &columns),
+            compute(&mut set(), &self::columns())
+        );
+
+        assert_eq!(
+            OhlcvColumns::from_named(&named[1..]).unwrap_err(),
+            TaError::DataItemIncomplete { field: "volume" }
+        );
+    }
+
+    #[test]
+    fn test_rows() {
+        let values: Vec<f64> = (0..5)
+            .flat_map(|row| [CLOSE[row], HIGH[row], LOW[row], CLOSE[row], VOLUME[row]])
+            .collect();
+        let rows = OhlcvRows::new(&values).unwrap();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(
+            compute_rows(&mut set(), &rows),
+            compute(&mut set(), &columns())
+        );
+    }
+
+    #[test]
+    fn test_incomplete_row() {
+        let values = [10.0; 12];
+        assert_eq!(
+            OhlcvRows::new(&values).unwrap_err(),
+            TaError::LengthMismatch {
+                expected: 15,
+                actual: 12
+            }
+        );
+        assert!(OhlcvRows::new(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_empty() {
+        let columns = OhlcvColumns {
+            open: &[],
+            high: &[],
+            low: &[],
+            close: &[],
+            volume: &[],
+        };
+        assert!(columns.is_empty());
+        let results = compute(&mut set(), &columns).unwrap();
+        assert_eq!(results.len(), 5);
+        assert!(results.iter().all(|(_, column)| column.is_empty()));
+    }
+}
@@ -0,0 +1,457 @@
+//! Indicators built from other indicators.
+//!
+//! The [Combine](trait.Combine.html) trait adds adapters to every indicator, so derived
+//! indicators can be declared instead of written by hand:
+//!
+//! * [map](trait.Combine.html#method.map) transforms the outputs of an indicator
+//! * [then](trait.Combine.html#method.then) feeds the outputs of an indicator into another
+//!   one, e.g. an EMA of the RSI
+//! * [zip](trait.Combine.html#method.zip) feeds the same input into two indicators and returns
+//!   both outputs
+//! * [diff](trait.Combine.html#method.diff) feeds the same input into two indicators and
+//!   returns the difference of their outputs, e.g. the spread of two EMAs
+//!
+//! The adapters implement [Next](../trait.Next.html), [Reset](../trait.Reset.html),
+//! [Period](../trait.Period.html), [IsReady](../trait.IsReady.html) and
+//! [Current](../trait.Current.html), so they can be combined further.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::combinators::Combine;
+//! use ta::indicators::{ExponentialMovingAverage as Ema, RelativeStrengthIndex as Rsi};
+//! use ta::{Next, Period};
+//!
+//! // smoothed RSI
+//! let mut smoothed = Rsi::new(3).unwrap().then(Ema::new(2).unwrap());
+//! assert_eq!(smoothed.period(), 4);
+//!
+//! // spread of two EMAs in percent of the slow one
+//! let mut spread = Ema::new(2)
+//!     .unwrap()
+//!     .zip(Ema::new(4).unwrap())
+//!     .map(|(fast, slow): (f64, f64)| (fast - slow) / slow * 100.0);
+//!
+//! for close in [10.0, 10.5, 11.0, 10.8] {
+//!     smoothed.next(close);
+//!     spread.next(close);
+//! }
+//! ```
+
+use core::fmt;
+use core::ops::Sub;
+
+use crate::{Current, IsReady, MemoryFootprint, Next, Period, Reset};
+
+/// Adapters combining indicators, implemented for every indicator.
+pub trait Combine: Reset + Sized {
+    /// Applies `f` to every output.
+    ///
+    /// The argument of a closure usually needs a type annotation, e.g. `|x: f64| x * 2.0`, since
+    /// the output type of an indicator depends on the input type.
+    fn map<F>(self, f: F) -> Map<Self, F> {
+        Map { indicator: self, f }
+    }
+
+    /// Feeds every output into `next`, which returns the final output.
+    fn then<J>(self, next: J) -> Then<Self, J> {
+        Then {
+            first: self,
+            second: next,
+            count: 0,
+        }
+    }
+
+    /// Feeds every input into both indicators and returns both outputs.
+    fn zip<J>(self, other: J) -> Zip<Self, J> {
+        Zip {
+            first: self,
+            second: other,
+        }
+    }
+
+    /// Feeds every input into both indicators and returns the output of this one minus the
+    /// output of `other`.
+    fn diff<J>(self, other: J) -> Diff<Self, J> {
+        Diff {
+            first: self,
+            second: other,
+        }
+    }
+}
+
+impl<I: Reset> Combine for I {}
+
+/// Indicator with transformed outputs, see [map](trait.Combine.html#method.map).
+#[derive(Debug, Clone)]
+pub struct Map<I, F> {
+    indicator: I,
+    f: F,
+}
+
+impl<I, F> Map<I, F> {
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+}
+
+impl<I: Period, F> Period for Map<I, F> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: IsReady, F> IsReady for Map<I, F> {
+    fn bars_until_ready(&self) -> usize {
+        self.indicator.bars_until_ready()
+    }
+}
+
+impl<I, F, O, T> Next<T> for Map<I, F>
+where
+    I: Next<T>,
+    F: Fn(I::Output) -> O,
+{
+    type Output = O;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        (self.f)(self.indicator.next(input))
+    }
+}
+
+impl<I, F, O> Current for Map<I, F>
+where
+    I: Current,
+    F: Fn(I::Output) -> O,
+{
+    type Output = O;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.indicator.current().map(&self.f)
+    }
+}
+
+impl<I: Reset, F> Reset for Map<I, F> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+    }
+}
+
+impl<I: MemoryFootprint, F> MemoryFootprint for Map<I, F> {
+    fn heap_size(&self) -> usize {
+        self.indicator.heap_size()
+    }
+}
+
+impl<I: fmt::Display, F> fmt::Display for Map<I, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MAP({})", self.indicator)
+    }
+}
+
+/// Indicator fed with the outputs of another one, see
+/// [then](trait.Combine.html#method.then).
+///
+/// The period is the number of bars, until the second indicator got a full period of outputs
+/// from the first one: the sum of both periods minus 1.
+#[derive(Debug, Clone)]
+pub struct Then<I, J> {
+    first: I,
+    second: J,
+    count: usize,
+}
+
+impl<I, J> Then<I, J> {
+    pub fn first(&self) -> &I {
+        &self.first
+    }
+
+    pub fn second(&self) -> &J {
+        &self.second
+    }
+}
+
+impl<I: Period, J: Period> Period for Then<I, J> {
+    fn period(&self) -> usize {
+        self.first.period() + self.second.period() - 1
+    }
+}
+
+impl<I: Period, J: Period> IsReady for Then<I, J> {
+    fn bars_until_ready(&self) -> usize {
+        self.period().saturating_sub(self.count)
+    }
+}
+
+impl<I, J, T> Next<T> for Then<I, J>
+where
+    I: Next<T>,
+    J: Next<I::Output>,
+{
+    type Output = J::Output;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.count = self.count.saturating_add(1);
+        self.second.next(self.first.next(input))
+    }
+}
+
+impl<I, J: Current> Current for Then<I, J> {
+    type Output = J::Output;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.second.current()
+    }
+}
+
+impl<I: Reset, J: Reset> Reset for Then<I, J> {
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.count = 0;
+    }
+}
+
+impl<I: MemoryFootprint, J: MemoryFootprint> MemoryFootprint for Then<I, J> {
+    fn heap_size(&self) -> usize {
+        self.first.heap_size() + self.second.heap_size()
+    }
+}
+
+impl<I: fmt::Display, J: fmt::Display> fmt::Display for Then<I, J> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "THEN({}, {})", self.first, self.second)
+    }
+}
+
+/// Two indicators fed with the same inputs, see [zip](trait.Combine.html#method.zip).
+#[derive(Debug, Clone)]
+pub struct Zip<I, J> {
+    first: I,
+    second: J,
+}
+
+/// Two indicators fed with the same inputs, see [diff](trait.Combine.html#method.diff).
+#[derive(Debug, Clone)]
+pub struct Diff<I, J> {
+    first: I,
+    second: J,
+}
+
+macro_rules! impl_pair {
+    ($($pair:ident, $name:literal);*) => {$(
+        impl<I, J> $pair<I, J> {
+            pub fn first(&self) -> &I {
+                &self.first
+            }
+
+            pub fn second(&self) -> &J {
+                &self.second
+            }
+        }
+
+        impl<I: Period, J: Period> Period for $pair<I, J> {
+            fn period(&self) -> usize {
+                self.first.period().max(self.second.period())
+            }
+        }
+
+        impl<I: IsReady, J: IsReady> IsReady for $pair<I, J> {
+            fn bars_until_ready(&self) -> usize {
+                self.first
+                    .bars_until_ready()
+                    .max(self.second.bars_until_ready())
+            }
+        }
+
+        impl<I: Reset, J: Reset> Reset for $pair<I, J> {
+            fn reset(&mut self) {
+                self.first.reset();
+                self.second.reset();
+            }
+        }
+
+        impl<I: MemoryFootprint, J: MemoryFootprint> MemoryFootprint for $pair<I, J> {
+            fn heap_size(&self) -> usize {
+                self.first.heap_size() + self.second.heap_size()
+            }
+        }
+
+        impl<I: fmt::Display, J: fmt::Display> fmt::Display for $pair<I, J> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}({}, {})", $name, self.first, self.second)
+            }
+        }
+    )*};
+}
+
+impl_pair!(Zip, "ZIP"; Diff, "DIFF");
+
+impl<I, J, T> Next<T> for Zip<I, J>
+where
+    I: Next<T>,
+    J: Next<T>,
+    T: Clone,
+{
+    type Output = (I::Output, J::Output);
+
+    fn next(&mut self, input: T) -> Self::Output {
+        (self.first.next(input.clone()), self.second.next(input))
+    }
+}
+
+impl<I: Current, J: Current> Current for Zip<I, J> {
+    type Output = (I::Output, J::Output);
+
+    fn current(&self) -> Option<Self::Output> {
+        Some((self.first.current()?, self.second.current()?))
+    }
+}
+
+impl<I, J, T> Next<T> for Diff<I, J>
+where
+    I: Next<T>,
+    J: Next<T>,
+    I::Output: Sub<J::Output>,
+    T: Clone,
+{
+    type Output = <I::Output as Sub<J::Output>>::Output;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        self.first.next(input.clone()) - self.second.next(input)
+    }
+}
+
+impl<I, J> Current for Diff<I, J>
+where
+    I: Current,
+    J: Current,
+    I::Output: Sub<J::Output>,
+{
+    type Output = <I::Output as Sub<J::Output>>::Output;
+
+    fn current(&self) -> Option<Self::Output> {
+        Some(self.first.current()? - self.second.current()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        ExponentialMovingAverage as Ema, Maximum, Minimum, RelativeStrengthIndex as Rsi,
+        SimpleMovingAverage as Sma,
+    };
+    use crate::test_helper::*;
+
+    const CLOSES: [f64; 8] = [10.0, 10.5, 11.2, 10.9, 11.5, 12.0, 11.4, 11.8];
+
+    fn ema(period: usize) -> Ema {
+        Ema::new(period).unwrap()
+    }
+
+    fn sma(period: usize) -> Sma {
+        Sma::new(period).unwrap()
+    }
+
+    #[test]
+    fn test_map() {
+        let mut doubled = sma(2).map(|x| x * 2.0);
+        let mut plain = sma(2);
+
+        for close in CLOSES {
+            assert_eq!(doubled.next(close), plain.next(close) * 2.0);
+        }
+        assert_eq!(doubled.period(), 2);
+        assert_eq!(doubled.indicator().period(), 2);
+    }
+
+    #[test]
+    fn test_then() {
+        let mut smoothed = Rsi::new(3).unwrap().then(ema(2));
+        let mut rsi = Rsi::new(3).unwrap();
+        let mut smoothing = ema(2);
+
+        for close in CLOSES {
+            assert_eq!(smoothed.next(close), smoothing.next(rsi.next(close)));
+        }
+        assert_eq!(smoothed.first().period(), 3);
+        assert_eq!(smoothed.second().period(), 2);
+    }
+
+    #[test]
+    fn test_zip() {
+        let mut zipped = sma(2).zip(ema(3));
+        let (mut first, mut second) = (sma(2), ema(3));
+
+        for close in CLOSES {
+            assert_eq!(zipped.next(close), (first.next(close), second.next(close)));
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut spread = ema(2).diff(ema(4));
+        let (mut fast, mut slow) = (ema(2), ema(4));
+
+        for close in CLOSES {
+            assert_eq!(spread.next(close), fast.next(close) - slow.next(close));
+        }
+    }
+
+    #[test]
+    fn test_bars() {
+        let bar = |high: f64, low: f64| Bar::new().high(high).low(low).close(low);
+        let mut range = Maximum::new(2).unwrap().diff(Minimum::new(2).unwrap());
+
+        assert_eq!(range.next(&bar(10.0, 8.0)), 2.0);
+        assert_eq!(range.next(&bar(12.0, 9.0)), 4.0);
+        assert_eq!(range.next(&bar(11.0, 10.0)), 3.0);
+    }
+
+    #[test]
+    fn test_nested() {
+        let mut nested = ema(2).diff(ema(4)).then(sma(3)).map(|x: f64| x.abs());
+        let (mut fast, mut slow, mut signal) = (ema(2), ema(4), sma(3));
+
+        for close in CLOSES {
+            let expected = signal.next(fast.next(close) - slow.next(close)).abs();
+            assert_eq!(nested.next(close), expected);
+        }
+        assert_eq!(nested.period(), 6);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut combined = sma(2).then(ema(2)).zip(sma(3).diff(sma(2)));
+        let first = combined.next(1.0);
+        combined.next(5.0);
+
+        combined.reset();
+        assert_eq!(combined.next(1.0), first);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut sma(3).then(sma(2)), 1.0, 4);
+        assert_warm_up(&mut sma(3).zip(sma(5)), 1.0, 5);
+        assert_warm_up(&mut sma(3).diff(sma(2)), 1.0, 3);
+        assert_warm_up(&mut sma(3).map(|x| x + 1.0), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut sma(2).map(|x| x * 2.0), CLOSES);
+        assert_current(&mut sma(2).then(ema(2)), CLOSES);
+        assert_current(&mut sma(2).zip(ema(3)), CLOSES);
+        assert_current(&mut ema(2).diff(ema(4)), CLOSES);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", sma(2).map(|x: f64| x)), "MAP(SMA(2))");
+        assert_eq!(format!("{}", sma(2).then(ema(3))), "THEN(SMA(2), EMA(3))");
+        assert_eq!(format!("{}", sma(2).zip(ema(3))), "ZIP(SMA(2), EMA(3))");
+        assert_eq!(format!("{}", ema(2).diff(ema(4))), "DIFF(EMA(2), EMA(4))");
+    }
+}
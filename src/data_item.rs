@@ -1,16 +1,22 @@
 use crate::errors::*;
-use crate::traits::{Close, High, Low, Open, Volume};
+use crate::traits::{
+    AskVolume, BidVolume, Close, High, Low, Open, OpenInterest, Timestamped, TradeCount, Volume,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Data item is used as an input for indicators.
 ///
+/// The timestamp is optional and only needed by inputs of time based tools, see
+/// [Timestamped](trait.Timestamped.html). The open interest, the trade count and the volume
+/// traded at the bid and at the ask are optional as well, for futures and order flow data.
+///
 /// # Example
 ///
 /// ```
 /// use ta::DataItem;
-/// use ta::{Open, High, Low, Close, Volume};
+/// use ta::{Open, High, Low, Close, Volume, Timestamped};
 ///
 /// let item = DataItem::builder()
 ///     .open(20.0)
@@ -18,6 +24,7 @@ use serde::{Deserialize, Serialize};
 ///     .low(15.0)
 ///     .close(21.0)
 ///     .volume(7500.0)
+///     .timestamp(1_700_000_000_000)
 ///     .build()
 ///     .unwrap();
 ///
@@ -26,6 +33,7 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(item.low(), 15.0);
 /// assert_eq!(item.close(), 21.0);
 /// assert_eq!(item.volume(), 7500.0);
+/// assert_eq!(item.timestamp(), Some(1_700_000_000_000));
 /// ```
 ///
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -36,6 +44,16 @@ pub struct DataItem {
     low: f64,
     close: f64,
     volume: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    timestamp: Option<i64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    open_interest: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    trade_count: Option<u64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    bid_volume: Option<f64>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    ask_volume: Option<f64>,
 }
 
 impl DataItem {
@@ -74,12 +92,54 @@ impl Volume for DataItem {
     }
 }
 
+impl Timestamped for DataItem {
+    fn timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+}
+
+impl OpenInterest for DataItem {
+    fn open_interest(&self) -> Option<f64> {
+        self.open_interest
+    }
+}
+
+impl TradeCount for DataItem {
+    fn trade_count(&self) -> Option<u64> {
+        self.trade_count
+    }
+}
+
+impl BidVolume for DataItem {
+    fn bid_volume(&self) -> Option<f64> {
+        self.bid_volume
+    }
+}
+
+impl AskVolume for DataItem {
+    fn ask_volume(&self) -> Option<f64> {
+        self.ask_volume
+    }
+}
+
+/// Builder of a [DataItem](struct.DataItem.html).
+///
+/// By default the prices must be consistent: the low must not be above the open, the close
+/// and the high, and the high must not be below the open and the close. Zero volume, equal
+/// prices and negative prices, e.g. of spreads, are valid. Data from feeds which do not keep
+/// the prices consistent can be accepted with [lenient](#method.lenient).
 pub struct DataItemBuilder {
     open: Option<f64>,
     high: Option<f64>,
     low: Option<f64>,
     close: Option<f64>,
     volume: Option<f64>,
+    timestamp: Option<i64>,
+    open_interest: Option<f64>,
+    trade_count: Option<u64>,
+    bid_volume: Option<f64>,
+    ask_volume: Option<f64>,
+    lenient: bool,
 }
 
 impl DataItemBuilder {
@@ -90,6 +150,12 @@ impl DataItemBuilder {
             low: None,
             close: None,
             volume: None,
+            timestamp: None,
+            open_interest: None,
+            trade_count: None,
+            bid_volume: None,
+            ask_volume: None,
+            lenient: false,
         }
     }
 
@@ -118,32 +184,119 @@ impl DataItemBuilder {
         self
     }
 
+    pub fn timestamp(mut self, val: i64) -> Self {
+        self.timestamp = Some(val);
+        self
+    }
+
+    pub fn open_interest(mut self, val: f64) -> Self {
+        self.open_interest = Some(val);
+        self
+    }
+
+    pub fn trade_count(mut self, val: u64) -> Self {
+        self.trade_count = Some(val);
+        self
+    }
+
+    pub fn bid_volume(mut self, val: f64) -> Self {
+        self.bid_volume = Some(val);
+        self
+    }
+
+    pub fn ask_volume(mut self, val: f64) -> Self {
+        self.ask_volume = Some(val);
+        self
+    }
+
+    /// Accepts inconsistent prices instead of rejecting them: the high becomes the highest
+    /// and the low the lowest of the four prices. NaN prices and negative volume are still
+    /// invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ta::{DataItem, High, Low};
+    ///
+    /// let builder = || DataItem::builder().open(10.2).high(10.0).low(9.5).close(9.8).volume(0.0);
+    /// assert!(builder().build().is_err());
+    ///
+    /// let item = builder().lenient().build().unwrap();
+    /// assert_eq!(item.high(), 10.2);
+    /// assert_eq!(item.low(), 9.5);
+    /// ```
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
     pub fn build(self) -> Result<DataItem> {
-        if let (Some(open), Some(high), Some(low), Some(close), Some(volume)) =
-            (self.open, self.high, self.low, self.close, self.volume)
-        {
-            // validate
-            if low <= open
-                && low <= close
-                && low <= high
-                && high >= open
-                && high >= close
-                && volume >= 0.0
-            {
-                let item = DataItem {
-                    open,
-                    high,
-                    low,
-                    close,
-                    volume,
-                };
-                Ok(item)
-            } else {
-                Err(TaError::DataItemInvalid)
+        let open = self
+            .open
+            .ok_or(TaError::DataItemIncomplete { field: "open" })?;
+        let high = self
+            .high
+            .ok_or(TaError::DataItemIncomplete { field: "high" })?;
+        let low = self
+            .low
+            .ok_or(TaError::DataItemIncomplete { field: "low" })?;
+        let close = self
+            .close
+            .ok_or(TaError::DataItemIncomplete { field: "close" })?;
+        let volume = self
+            .volume
+            .ok_or(TaError::DataItemIncomplete { field: "volume" })?;
+
+        let (high, low) = if self.lenient {
+            let prices = [
+                ("open", open),
+                ("high", high),
+                ("low", low),
+                ("close", close),
+            ];
+            if let Some(&(field, _)) = prices.iter().find(|(_, price)| price.is_nan()) {
+                return Err(TaError::DataItemInvalid { field });
             }
+            let highest = open.max(high).max(low).max(close);
+            let lowest = open.min(high).min(low).min(close);
+            (highest, lowest)
         } else {
-            Err(TaError::DataItemIncomplete)
+            (high, low)
+        };
+
+        // validate
+        if !(low <= open && low <= close && low <= high) {
+            return Err(TaError::DataItemInvalid { field: "low" });
+        }
+        if !(high >= open && high >= close) {
+            return Err(TaError::DataItemInvalid { field: "high" });
+        }
+        if volume.is_nan() || volume < 0.0 {
+            return Err(TaError::DataItemInvalid { field: "volume" });
         }
+        let optional = [
+            ("open_interest", self.open_interest),
+            ("bid_volume", self.bid_volume),
+            ("ask_volume", self.ask_volume),
+        ];
+        for (field, value) in optional {
+            if matches!(value, Some(value) if value.is_nan() || value < 0.0) {
+                return Err(TaError::DataItemInvalid { field });
+            }
+        }
+        let item = DataItem {
+            open,
+            high,
+            low,
+            close,
+            volume,
+            timestamp: self.timestamp,
+            open_interest: self.open_interest,
+            trade_count: self.trade_count,
+            bid_volume: self.bid_volume,
+            ask_volume: self.ask_volume,
+        };
+        Ok(item)
     }
 }
 
@@ -173,7 +326,7 @@ mod tests {
                 .close(close)
                 .volume(volume)
                 .build();
-            assert_eq!(result, Err(TaError::DataItemInvalid));
+            assert!(matches!(result, Err(TaError::DataItemInvalid { .. })));
         }
 
         let valid_records = vec![
@@ -202,4 +355,126 @@ mod tests {
             assert_invalid(record)
         }
     }
+
+    #[test]
+    fn test_builder_errors() {
+        let builder = || DataItem::builder().open(20.0).high(25.0).low(15.0);
+        assert_eq!(
+            builder().volume(100.0).build(),
+            Err(TaError::DataItemIncomplete { field: "close" })
+        );
+        assert_eq!(
+            builder().close(21.0).volume(f64::NAN).build(),
+            Err(TaError::DataItemInvalid { field: "volume" })
+        );
+        assert_eq!(
+            builder().close(25.5).volume(100.0).build(),
+            Err(TaError::DataItemInvalid { field: "high" })
+        );
+    }
+
+    #[test]
+    fn test_strict() {
+        let build = |open: f64, high: f64, low: f64, close: f64, volume: f64| {
+            DataItem::builder()
+                .open(open)
+                .high(high)
+                .low(low)
+                .close(close)
+                .volume(volume)
+                .build()
+        };
+        // zero volume, a bar without range and negative prices are valid data
+        assert!(build(10.0, 11.0, 9.0, 10.0, 0.0).is_ok());
+        assert!(build(10.0, 10.0, 10.0, 10.0, 5.0).is_ok());
+        assert!(build(-2.5, -1.0, -3.0, -2.0, 5.0).is_ok());
+    }
+
+    #[test]
+    fn test_lenient() {
+        let build = |open: f64, high: f64, low: f64, close: f64, volume: f64| {
+            DataItem::builder()
+                .open(open)
+                .high(high)
+                .low(low)
+                .close(close)
+                .volume(volume)
+                .lenient()
+                .build()
+        };
+        let item = build(10.0, 9.0, 11.0, 10.5, 0.0).unwrap();
+        assert_eq!((item.high(), item.low()), (11.0, 9.0));
+
+        let item = build(8.0, 10.0, 9.0, 10.5, 1.0).unwrap();
+        assert_eq!((item.open(), item.close()), (8.0, 10.5));
+        assert_eq!((item.high(), item.low()), (10.5, 8.0));
+
+        assert_eq!(
+            build(10.0, 11.0, 9.0, f64::NAN, 1.0),
+            Err(TaError::DataItemInvalid { field: "close" })
+        );
+        assert_eq!(
+            build(10.0, 11.0, 9.0, 10.0, -1.0),
+            Err(TaError::DataItemInvalid { field: "volume" })
+        );
+    }
+
+    #[test]
+    fn test_extended_fields() {
+        let builder = || {
+            DataItem::builder()
+                .open(20.0)
+                .high(25.0)
+                .low(15.0)
+                .close(21.0)
+                .volume(100.0)
+        };
+        let item = builder().build().unwrap();
+        assert_eq!(item.open_interest(), None);
+        assert_eq!(item.trade_count(), None);
+        assert_eq!(item.bid_volume(), None);
+        assert_eq!(item.ask_volume(), None);
+
+        let item = builder()
+            .open_interest(5000.0)
+            .trade_count(42)
+            .bid_volume(40.0)
+            .ask_volume(60.0)
+            .build()
+            .unwrap();
+        assert_eq!(item.open_interest(), Some(5000.0));
+        assert_eq!(item.trade_count(), Some(42));
+        assert_eq!(item.bid_volume(), Some(40.0));
+        assert_eq!(item.ask_volume(), Some(60.0));
+
+        assert_eq!(
+            builder().open_interest(-1.0).build(),
+            Err(TaError::DataItemInvalid {
+                field: "open_interest"
+            })
+        );
+        assert_eq!(
+            builder().ask_volume(f64::NAN).build(),
+            Err(TaError::DataItemInvalid {
+                field: "ask_volume"
+            })
+        );
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let builder = || {
+            DataItem::builder()
+                .open(20.0)
+                .high(25.0)
+                .low(15.0)
+                .close(21.0)
+                .volume(100.0)
+        };
+        assert_eq!(builder().build().unwrap().timestamp(), None);
+        assert_eq!(
+            builder().timestamp(-60).build().unwrap().timestamp(),
+            Some(-60)
+        );
+    }
 }
@@ -0,0 +1,189 @@
+use core::fmt;
+
+use rust_decimal::Decimal;
+
+use super::{Close, High, Low, Volume};
+use crate::{Current, MemoryFootprint, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Anchored Volume Weighted Average Price (VWAP) in exact decimal arithmetic.
+///
+/// The average typical price of all the bars since the anchor, weighted by their volume. The
+/// anchor is the first bar after creation or [reset](../trait.Reset.html), e.g. the session
+/// open or an earnings release. The price-volume and the volume sums are exact, only the
+/// final division is rounded to 28 significant digits.
+///
+/// # Formula
+///
+/// VWAP = sum(typical price * volume) / sum(volume)
+///
+/// Where typical price = (high + low + close) / 3. The VWAP is `None` while no volume was
+/// traded since the anchor.
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal::Decimal;
+/// use ta::decimal::{AnchoredVolumeWeightedAveragePrice, DecimalItem};
+/// use ta::Next;
+///
+/// let bar = |price: i64, volume: i64| {
+///     let price = Decimal::new(price, 2);
+///     DecimalItem::builder()
+///         .open(price).high(price).low(price).close(price)
+///         .volume(Decimal::new(volume, 0))
+///         .build().unwrap()
+/// };
+///
+/// let mut vwap = AnchoredVolumeWeightedAveragePrice::new();
+/// vwap.next(&bar(1010, 100));
+/// assert_eq!(vwap.next(&bar(1020, 300)), Some(Decimal::new(10175, 3)));
+/// ```
+#[doc(alias = "VWAP")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct AnchoredVolumeWeightedAveragePrice {
+    price_volume: Decimal,
+    volume: Decimal,
+}
+
+impl AnchoredVolumeWeightedAveragePrice {
+    pub fn new() -> Self {
+        Self {
+            price_volume: Decimal::ZERO,
+            volume: Decimal::ZERO,
+        }
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for AnchoredVolumeWeightedAveragePrice {
+    type Output = Option<Decimal>;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let typical_price = (input.high() + input.low() + input.close()) / Decimal::from(3);
+        self.price_volume += typical_price * input.volume();
+        self.volume += input.volume();
+        self.current()
+    }
+}
+
+impl Current for AnchoredVolumeWeightedAveragePrice {
+    type Output = Decimal;
+
+    /// Same as the output of the last bar: `None` while no volume was traded since the
+    /// anchor.
+    fn current(&self) -> Option<Self::Output> {
+        if self.volume > Decimal::ZERO {
+            Some(self.price_volume / self.volume)
+        } else {
+            None
+        }
+    }
+}
+
+impl Reset for AnchoredVolumeWeightedAveragePrice {
+    fn reset(&mut self) {
+        self.price_volume = Decimal::ZERO;
+        self.volume = Decimal::ZERO;
+    }
+}
+
+impl MemoryFootprint for AnchoredVolumeWeightedAveragePrice {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for AnchoredVolumeWeightedAveragePrice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for AnchoredVolumeWeightedAveragePrice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "AVWAP")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decimal::DecimalItem;
+
+    fn bar(high: &str, low: &str, close: &str, volume: &str) -> DecimalItem {
+        let close: Decimal = close.parse().unwrap();
+        DecimalItem::builder()
+            .open(close)
+            .high(high.parse().unwrap())
+            .low(low.parse().unwrap())
+            .close(close)
+            .volume(volume.parse().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    fn next(vwap: &mut AnchoredVolumeWeightedAveragePrice, bar: &DecimalItem) -> Option<String> {
+        vwap.next(bar)
+            .map(|v| v.round_dp(4).normalize().to_string())
+    }
+
+    #[test]
+    fn test_next() {
+        let mut vwap = AnchoredVolumeWeightedAveragePrice::new();
+
+        assert_eq!(next(&mut vwap, &bar("10.5", "9.5", "10", "0")), None);
+        assert_eq!(
+            next(&mut vwap, &bar("10.5", "9.5", "10", "100")),
+            Some("10".to_string())
+        );
+        assert_eq!(
+            next(&mut vwap, &bar("11", "10", "10.5", "200")),
+            Some("10.3333".to_string())
+        );
+        // no volume does not move the VWAP
+        assert_eq!(
+            next(&mut vwap, &bar("20", "20", "20", "0")),
+            Some("10.3333".to_string())
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vwap = AnchoredVolumeWeightedAveragePrice::new();
+
+        vwap.next(&bar("10.5", "9.5", "10", "100"));
+
+        vwap.reset();
+        assert_eq!(
+            next(&mut vwap, &bar("11", "10", "10.5", "200")),
+            Some("10.5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_current() {
+        let mut vwap = AnchoredVolumeWeightedAveragePrice::new();
+        assert_eq!(vwap.current(), None);
+
+        vwap.next(&bar("10.5", "9.5", "10", "0"));
+        assert_eq!(vwap.current(), None);
+        let output = vwap.next(&bar("10.5", "9.5", "10", "100"));
+        assert_eq!(vwap.current(), output);
+
+        vwap.reset();
+        assert_eq!(vwap.current(), None);
+    }
+
+    #[test]
+    fn test_default() {
+        AnchoredVolumeWeightedAveragePrice::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let vwap = AnchoredVolumeWeightedAveragePrice::new();
+        assert_eq!(format!("{}", vwap), "AVWAP");
+    }
+}
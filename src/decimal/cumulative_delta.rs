@@ -0,0 +1,165 @@
+use core::fmt;
+
+use rust_decimal::Decimal;
+
+use super::{BuyVolume, SellVolume};
+use crate::{Current, IsReady, MemoryFootprint, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Cumulative Delta in exact decimal arithmetic.
+///
+/// Same as [CumulativeDelta](../indicators/struct.CumulativeDelta.html): the sum of the buy
+/// volume minus the sell volume since the first bar. With `Next<Decimal>` the input is the
+/// delta of a bar.
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal::Decimal;
+/// use ta::decimal::CumulativeDelta;
+/// use ta::Next;
+///
+/// let mut cd = CumulativeDelta::new();
+/// cd.next(Decimal::new(3, 1));
+/// assert_eq!(cd.next(Decimal::new(-1, 1)), Decimal::new(2, 1));
+/// ```
+#[doc(alias = "CVD")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct CumulativeDelta {
+    value: Decimal,
+    output: Option<Decimal>,
+}
+
+impl CumulativeDelta {
+    pub fn new() -> Self {
+        Self {
+            value: Decimal::ZERO,
+            output: None,
+        }
+    }
+}
+
+impl IsReady for CumulativeDelta {
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
+}
+
+impl Next<Decimal> for CumulativeDelta {
+    type Output = Decimal;
+
+    fn next(&mut self, input: Decimal) -> Self::Output {
+        self.value += input;
+        self.output = Some(self.value);
+        self.value
+    }
+}
+
+impl<T: BuyVolume + SellVolume> Next<&T> for CumulativeDelta {
+    type Output = Decimal;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.buy_volume() - input.sell_volume())
+    }
+}
+
+impl Current for CumulativeDelta {
+    type Output = Decimal;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for CumulativeDelta {
+    fn reset(&mut self) {
+        self.value = Decimal::ZERO;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for CumulativeDelta {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for CumulativeDelta {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for CumulativeDelta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CD")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::assert_current;
+
+    struct Footprint(Decimal, Decimal);
+
+    impl BuyVolume for Footprint {
+        fn buy_volume(&self) -> Decimal {
+            self.0
+        }
+    }
+
+    impl SellVolume for Footprint {
+        fn sell_volume(&self) -> Decimal {
+            self.1
+        }
+    }
+
+    fn bar(buy: &str, sell: &str) -> Footprint {
+        Footprint(buy.parse().unwrap(), sell.parse().unwrap())
+    }
+
+    #[test]
+    fn test_next() {
+        let mut cd = CumulativeDelta::new();
+
+        assert_eq!(cd.next(&bar("0.7", "0.4")).to_string(), "0.3");
+        assert_eq!(cd.next(&bar("0.1", "0.2")).to_string(), "0.2");
+        assert_eq!(cd.next(Decimal::new(-5, 1)).to_string(), "-0.3");
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cd = CumulativeDelta::new();
+
+        cd.next(&bar("0.7", "0.4"));
+
+        cd.reset();
+        assert_eq!(cd.next(&bar("0.1", "0.2")).to_string(), "-0.1");
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert!(CumulativeDelta::new().is_ready());
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(
+            &mut CumulativeDelta::new(),
+            [Decimal::new(3, 1), Decimal::new(-1, 1)],
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        CumulativeDelta::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", CumulativeDelta::new()), "CD");
+    }
+}
@@ -0,0 +1,228 @@
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use super::{Close, High, Low, Open, Volume};
+use crate::errors::*;
+use crate::DataItem;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Data item with decimal prices and volume.
+///
+/// The decimal counterpart of [DataItem](../struct.DataItem.html), validated the same way.
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal::Decimal;
+/// use ta::decimal::{Close, DecimalItem};
+/// use ta::DataItem;
+///
+/// let price = Decimal::new(2105, 2); // 21.05
+/// let item = DecimalItem::builder()
+///     .open(price)
+///     .high(price)
+///     .low(price)
+///     .close(price)
+///     .volume(Decimal::new(7500, 0))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(item.close().to_string(), "21.05");
+///
+/// // feeds any f64 indicator as well
+/// let item = DataItem::from(&item);
+/// assert_eq!(ta::Close::close(&item), 21.05);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecimalItem {
+    open: Decimal,
+    high: Decimal,
+    low: Decimal,
+    close: Decimal,
+    volume: Decimal,
+}
+
+impl DecimalItem {
+    pub fn builder() -> DecimalItemBuilder {
+        DecimalItemBuilder::new()
+    }
+}
+
+impl Open for DecimalItem {
+    fn open(&self) -> Decimal {
+        self.open
+    }
+}
+
+impl High for DecimalItem {
+    fn high(&self) -> Decimal {
+        self.high
+    }
+}
+
+impl Low for DecimalItem {
+    fn low(&self) -> Decimal {
+        self.low
+    }
+}
+
+impl Close for DecimalItem {
+    fn close(&self) -> Decimal {
+        self.close
+    }
+}
+
+impl Volume for DecimalItem {
+    fn volume(&self) -> Decimal {
+        self.volume
+    }
+}
+
+impl From<&DecimalItem> for DataItem {
+    fn from(item: &DecimalItem) -> Self {
+        // rounding to the nearest f64 keeps the order of the values, so the item stays valid
+        let f = |d: Decimal| d.to_f64().unwrap();
+        DataItem::builder()
+            .open(f(item.open))
+            .high(f(item.high))
+            .low(f(item.low))
+            .close(f(item.close))
+            .volume(f(item.volume))
+            .build()
+            .unwrap()
+    }
+}
+
+pub struct DecimalItemBuilder {
+    open: Option<Decimal>,
+    high: Option<Decimal>,
+    low: Option<Decimal>,
+    close: Option<Decimal>,
+    volume: Option<Decimal>,
+}
+
+impl DecimalItemBuilder {
+    pub fn new() -> Self {
+        Self {
+            open: None,
+            high: None,
+            low: None,
+            close: None,
+            volume: None,
+        }
+    }
+
+    pub fn open(mut self, val: Decimal) -> Self {
+        self.open = Some(val);
+        self
+    }
+
+    pub fn high(mut self, val: Decimal) -> Self {
+        self.high = Some(val);
+        self
+    }
+
+    pub fn low(mut self, val: Decimal) -> Self {
+        self.low = Some(val);
+        self
+    }
+
+    pub fn close(mut self, val: Decimal) -> Self {
+        self.close = Some(val);
+        self
+    }
+
+    pub fn volume(mut self, val: Decimal) -> Self {
+        self.volume = Some(val);
+        self
+    }
+
+    pub fn build(self) -> Result<DecimalItem> {
+        let open = self
+            .open
+            .ok_or(TaError::DataItemIncomplete { field: "open" })?;
+        let high = self
+            .high
+            .ok_or(TaError::DataItemIncomplete { field: "high" })?;
+        let low = self
+            .low
+            .ok_or(TaError::DataItemIncomplete { field: "low" })?;
+        let close = self
+            .close
+            .ok_or(TaError::DataItemIncomplete { field: "close" })?;
+        let volume = self
+            .volume
+            .ok_or(TaError::DataItemIncomplete { field: "volume" })?;
+
+        // validate
+        if !(low <= open && low <= close && low <= high) {
+            return Err(TaError::DataItemInvalid { field: "low" });
+        }
+        if !(high >= open && high >= close) {
+            return Err(TaError::DataItemInvalid { field: "high" });
+        }
+        if volume < Decimal::ZERO {
+            return Err(TaError::DataItemInvalid { field: "volume" });
+        }
+        Ok(DecimalItem {
+            open,
+            high,
+            low,
+            close,
+            volume,
+        })
+    }
+}
+
+impl Default for DecimalItemBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(open: i64, high: i64, low: i64, close: i64, volume: i64) -> Result<DecimalItem> {
+        DecimalItem::builder()
+            .open(Decimal::new(open, 1))
+            .high(Decimal::new(high, 1))
+            .low(Decimal::new(low, 1))
+            .close(Decimal::new(close, 1))
+            .volume(Decimal::new(volume, 0))
+            .build()
+    }
+
+    #[test]
+    fn test_builder() {
+        assert!(build(200, 250, 150, 210, 7500).is_ok());
+        assert!(build(0, 0, 0, 0, 0).is_ok());
+
+        let invalid = |field| Err(TaError::DataItemInvalid { field });
+        assert_eq!(build(149, 250, 150, 210, 7500), invalid("low"));
+        assert_eq!(build(200, 250, 150, 251, 7500), invalid("high"));
+        assert_eq!(build(200, 150, 250, 210, 7500), invalid("low"));
+        assert_eq!(build(200, 250, 150, 210, -1), invalid("volume"));
+
+        let incomplete = DecimalItem::builder().open(Decimal::ONE).build();
+        assert_eq!(
+            incomplete,
+            Err(TaError::DataItemIncomplete { field: "high" })
+        );
+    }
+
+    #[test]
+    fn test_into_data_item() {
+        let item = build(200, 250, 150, 210, 7500).unwrap();
+        let item = DataItem::from(&item);
+        assert_eq!(crate::Open::open(&item), 20.0);
+        assert_eq!(crate::High::high(&item), 25.0);
+        assert_eq!(crate::Low::low(&item), 15.0);
+        assert_eq!(crate::Close::close(&item), 21.0);
+        assert_eq!(crate::Volume::volume(&item), 7500.0);
+    }
+}
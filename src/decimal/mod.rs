@@ -0,0 +1,83 @@
+//! Exact decimal arithmetic (`decimal` feature).
+//!
+//! Cumulative indicators add up every bar since the start, so the rounding errors of binary
+//! floats add up as well: after millions of bars an OBV computed in `f64` can be off by a few
+//! units, which is unacceptable when the numbers have to reconcile with accounting. The
+//! indicators of this module compute in [Decimal](https://docs.rs/rust_decimal), where sums
+//! and differences of decimal prices and volumes are exact.
+//!
+//! Inputs implement the decimal counterparts of the price traits of the crate, e.g. a
+//! [DecimalItem](struct.DecimalItem.html). A `DecimalItem` converts into a
+//! [DataItem](../struct.DataItem.html) to feed any other indicator.
+//!
+//! Arithmetic overflows of `Decimal` (values beyond about 7.9 * 10<sup>28</sup>) panic.
+//!
+//! # Example
+//!
+//! ```
+//! use rust_decimal::Decimal;
+//! use ta::decimal::{DecimalItem, OnBalanceVolume};
+//! use ta::Next;
+//!
+//! let bar = |close: &str, volume: &str| {
+//!     let close: Decimal = close.parse().unwrap();
+//!     DecimalItem::builder()
+//!         .open(close).high(close).low(close).close(close)
+//!         .volume(volume.parse().unwrap())
+//!         .build().unwrap()
+//! };
+//!
+//! let mut obv = OnBalanceVolume::new();
+//! obv.next(&bar("10.1", "0.1"));
+//! obv.next(&bar("10.2", "0.2"));
+//! assert_eq!(obv.next(&bar("10.3", "0.3")).to_string(), "0.6");
+//! ```
+
+use rust_decimal::Decimal;
+
+mod data_item;
+pub use self::data_item::{DecimalItem, DecimalItemBuilder};
+
+mod on_balance_volume;
+pub use self::on_balance_volume::OnBalanceVolume;
+
+mod cumulative_delta;
+pub use self::cumulative_delta::CumulativeDelta;
+
+mod anchored_volume_weighted_average_price;
+pub use self::anchored_volume_weighted_average_price::AnchoredVolumeWeightedAveragePrice;
+
+/// Open price of a particular period, as a decimal.
+pub trait Open {
+    fn open(&self) -> Decimal;
+}
+
+/// Close price of a particular period, as a decimal.
+pub trait Close {
+    fn close(&self) -> Decimal;
+}
+
+/// Lowest price of a particular period, as a decimal.
+pub trait Low {
+    fn low(&self) -> Decimal;
+}
+
+/// Highest price of a particular period, as a decimal.
+pub trait High {
+    fn high(&self) -> Decimal;
+}
+
+/// Trading volume of a particular trading period, as a decimal.
+pub trait Volume {
+    fn volume(&self) -> Decimal;
+}
+
+/// Volume of a particular period traded at the ask, as a decimal.
+pub trait BuyVolume {
+    fn buy_volume(&self) -> Decimal;
+}
+
+/// Volume of a particular period traded at the bid, as a decimal.
+pub trait SellVolume {
+    fn sell_volume(&self) -> Decimal;
+}
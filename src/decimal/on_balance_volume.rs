@@ -0,0 +1,183 @@
+use core::fmt;
+
+use rust_decimal::Decimal;
+
+use super::{Close, Volume};
+use crate::{Current, IsReady, MemoryFootprint, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// On Balance Volume (OBV) in exact decimal arithmetic.
+///
+/// Same as [OnBalanceVolume](../indicators/struct.OnBalanceVolume.html): the volume is added
+/// on bars closing up and subtracted on bars closing down.
+///
+/// # Example
+///
+/// ```
+/// use rust_decimal::Decimal;
+/// use ta::decimal::{DecimalItem, OnBalanceVolume};
+/// use ta::Next;
+///
+/// let bar = |close: i64, volume: i64| {
+///     let close = Decimal::new(close, 1);
+///     DecimalItem::builder()
+///         .open(close).high(close).low(close).close(close)
+///         .volume(Decimal::new(volume, 1))
+///         .build().unwrap()
+/// };
+///
+/// let mut obv = OnBalanceVolume::new();
+/// assert_eq!(obv.next(&bar(15, 10)), Decimal::new(10, 1));
+/// assert_eq!(obv.next(&bar(14, 3)), Decimal::new(7, 1));
+/// ```
+#[doc(alias = "OBV")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct OnBalanceVolume {
+    obv: Decimal,
+    prev_close: Decimal,
+    output: Option<Decimal>,
+}
+
+impl OnBalanceVolume {
+    pub fn new() -> Self {
+        Self {
+            obv: Decimal::ZERO,
+            prev_close: Decimal::ZERO,
+            output: None,
+        }
+    }
+}
+
+impl IsReady for OnBalanceVolume {
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for OnBalanceVolume {
+    type Output = Decimal;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        if input.close() > self.prev_close {
+            self.obv += input.volume();
+        } else if input.close() < self.prev_close {
+            self.obv -= input.volume();
+        }
+        self.prev_close = input.close();
+        self.output = Some(self.obv);
+        self.obv
+    }
+}
+
+impl Default for OnBalanceVolume {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for OnBalanceVolume {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "OBV")
+    }
+}
+
+impl Current for OnBalanceVolume {
+    type Output = Decimal;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for OnBalanceVolume {
+    fn reset(&mut self) {
+        self.obv = Decimal::ZERO;
+        self.prev_close = Decimal::ZERO;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for OnBalanceVolume {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decimal::DecimalItem;
+    use crate::test_helper::assert_current;
+
+    fn bar(close: &str, volume: &str) -> DecimalItem {
+        let close: Decimal = close.parse().unwrap();
+        DecimalItem::builder()
+            .open(close)
+            .high(close)
+            .low(close)
+            .close(close)
+            .volume(volume.parse().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_next() {
+        let mut obv = OnBalanceVolume::new();
+
+        assert_eq!(obv.next(&bar("1.5", "1000")).to_string(), "1000");
+        assert_eq!(obv.next(&bar("5", "5000")).to_string(), "6000");
+        assert_eq!(obv.next(&bar("4", "9000")).to_string(), "-3000");
+        assert_eq!(obv.next(&bar("4", "4000")).to_string(), "-3000");
+    }
+
+    #[test]
+    fn test_exact() {
+        let mut obv = OnBalanceVolume::new();
+        let mut float = 0.0;
+
+        // 0.1 has no exact binary representation
+        for i in 1..=1000 {
+            obv.next(&bar(&i.to_string(), "0.1"));
+            float += 0.1;
+        }
+        assert_eq!(obv.next(&bar("0", "0.1")).to_string(), "99.9");
+        assert_ne!(float - 0.1, 99.9);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut obv = OnBalanceVolume::new();
+
+        obv.next(&bar("1.5", "1000"));
+        obv.next(&bar("4", "2000"));
+
+        obv.reset();
+        assert_eq!(obv.next(&bar("3", "500")).to_string(), "500");
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert!(OnBalanceVolume::new().is_ready());
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(
+            &mut OnBalanceVolume::new(),
+            &[bar("10.1", "0.1"), bar("10", "0.2")],
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        OnBalanceVolume::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", OnBalanceVolume::new()), "OBV");
+    }
+}
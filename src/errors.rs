@@ -1,31 +1,154 @@
+use core::fmt::{Display, Formatter};
+#[cfg(feature = "std")]
 use std::error::Error;
-use std::fmt::{Display, Formatter};
 
-pub type Result<T> = std::result::Result<T, TaError>;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+pub type Result<T> = core::result::Result<T, TaError>;
+
+/// Error of the crate.
+///
+/// Each variant tells the cause of the failure, and the fields tell which parameter or field
+/// caused it, so callers can match on them instead of on the message:
+///
+/// ```
+/// use ta::errors::TaError;
+/// use ta::indicators::BollingerBands;
+///
+/// match BollingerBands::new(20, -2.0) {
+///     Err(TaError::InvalidParameter { name: "multiplier", .. }) => {}
+///     _ => unreachable!(),
+/// }
+/// ```
+///
+/// More variants may be added in the future, so matches need a wildcard arm.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub enum TaError {
-    InvalidParameter,
-    DataItemIncomplete,
-    DataItemInvalid,
+    /// A period, window length or other count is out of range, e.g. a period of 0.
+    InvalidPeriod {
+        name: &'static str,
+        value: usize,
+        reason: &'static str,
+    },
+    /// Any other numeric parameter is out of range, e.g. a negative multiplier.
+    InvalidParameter {
+        name: &'static str,
+        value: f64,
+        reason: &'static str,
+    },
+    /// A data item is missing a required field.
+    DataItemIncomplete { field: &'static str },
+    /// A field of a data item is not a valid value, or contradicts the other fields, e.g. a
+    /// low above the high.
+    DataItemInvalid { field: &'static str },
+    /// An output is requested from an indicator, which has none yet.
+    NotReady,
+    /// The number of inputs differs from the expected number.
+    LengthMismatch { expected: usize, actual: usize },
+    /// Bars are missing between two timestamped bars.
+    Gap { missing: usize },
+    /// No indicator is known by this name.
+    UnknownIndicator { name: String },
+    /// The indicator takes no parameter by this name.
+    UnknownParameter { name: String },
 }
 
-impl Display for TaError {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        match *self {
-            TaError::InvalidParameter => write!(f, "invalid parameter"),
-            TaError::DataItemIncomplete => write!(f, "data item is incomplete"),
-            TaError::DataItemInvalid => write!(f, "data item is invalid"),
+impl TaError {
+    pub(crate) fn invalid_period(name: &'static str, value: usize, reason: &'static str) -> Self {
+        TaError::InvalidPeriod {
+            name,
+            value,
+            reason,
+        }
+    }
+
+    pub(crate) fn invalid_parameter(name: &'static str, value: f64, reason: &'static str) -> Self {
+        TaError::InvalidParameter {
+            name,
+            value,
+            reason,
         }
     }
+
+    /// Error for a period, which must be greater than 0.
+    pub(crate) fn zero_period(name: &'static str) -> Self {
+        Self::invalid_period(name, 0, "must be greater than 0")
+    }
 }
 
-impl Error for TaError {
-    fn source(&self) -> Option<&(dyn Error + 'static)> {
-        match *self {
-            TaError::InvalidParameter => None,
-            TaError::DataItemIncomplete => None,
-            TaError::DataItemInvalid => None,
+impl Display for TaError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            TaError::InvalidPeriod {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid period {} = {}: {}", name, value, reason),
+            TaError::InvalidParameter {
+                name,
+                value,
+                reason,
+            } => write!(f, "invalid parameter {} = {}: {}", name, value, reason),
+            TaError::DataItemIncomplete { field } => {
+                write!(f, "data item is incomplete: {} is missing", field)
+            }
+            TaError::DataItemInvalid { field } => {
+                write!(f, "data item is invalid: {} is not valid", field)
+            }
+            TaError::NotReady => write!(f, "indicator is not ready"),
+            TaError::LengthMismatch { expected, actual } => {
+                write!(f, "expected {} inputs, got {}", expected, actual)
+            }
+            TaError::Gap { missing } => write!(f, "gap of {} missing bars", missing),
+            TaError::UnknownIndicator { name } => write!(f, "unknown indicator {:?}", name),
+            TaError::UnknownParameter { name } => write!(f, "unknown parameter {:?}", name),
         }
     }
 }
+
+#[cfg(feature = "std")]
+impl Error for TaError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::format;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", TaError::zero_period("period")),
+            "invalid period period = 0: must be greater than 0"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                TaError::invalid_parameter("multiplier", -2.0, "must not be negative")
+            ),
+            "invalid parameter multiplier = -2: must not be negative"
+        );
+        assert_eq!(
+            format!("{}", TaError::DataItemIncomplete { field: "close" }),
+            "data item is incomplete: close is missing"
+        );
+        assert_eq!(
+            format!("{}", TaError::Gap { missing: 3 }),
+            "gap of 3 missing bars"
+        );
+        assert_eq!(
+            format!("{}", TaError::UnknownIndicator { name: "FOO".into() }),
+            "unknown indicator \"FOO\""
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_error() {
+        let error: Box<dyn Error> = Box::new(TaError::NotReady);
+        assert!(error.source().is_none());
+        assert_eq!(error.to_string(), "indicator is not ready");
+    }
+}
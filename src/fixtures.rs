@@ -0,0 +1,513 @@
+//! Golden output fixtures for indicators.
+//!
+//! A fixture stores the outputs of an indicator configuration over a fixed input, so they can
+//! be compared after a crate upgrade: if the implementation of an indicator changes, the
+//! comparison reports the first bar, where the outputs differ. This module is available with
+//! the `fixtures` feature and is meant for tests of downstream crates.
+//!
+//! Inputs are read from a CSV file with a header containing (in any order, case insensitive)
+//! the columns `open`, `high`, `low`, `close` and `volume`, and optionally a `timestamp` column
+//! with whole numbers. Other columns, e.g. a date, are ignored. Fields must not be quoted.
+//!
+//! A fixture file is a plain text file: the first line is `#` followed by the indicator
+//! specification (its `Display` output, e.g. `SMA(3)`), and every following line holds the
+//! comma separated output values of one bar.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "fixtures")]
+//! # {
+//! use ta::fixtures::{assert_golden, Fixture};
+//! use ta::indicators::SimpleMovingAverage;
+//!
+//! let closes = [1.0, 2.0, 3.0, 4.0];
+//! let sma = SimpleMovingAverage::new(2).unwrap();
+//!
+//! let fixture = Fixture::generate(&sma, closes.iter().copied(), |&x| vec![x]);
+//! assert_eq!(fixture.spec(), "SMA(2)");
+//! assert_eq!(fixture.rows()[1], vec![1.5]);
+//!
+//! // the first run writes the fixture, later runs compare with it
+//! let path = std::env::temp_dir().join("ta_fixtures_doc_sma.txt");
+//! assert_golden(&path, &sma, closes.iter().copied(), |&x| vec![x], 1e-9);
+//! assert_golden(&path, &sma, closes.iter().copied(), |&x| vec![x], 1e-9);
+//! # std::fs::remove_file(&path).unwrap();
+//! # }
+//! ```
+
+use core::fmt;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{DataItem, Next, Reset};
+
+/// Error of reading, writing or comparing fixtures.
+#[derive(Debug)]
+pub enum FixtureError {
+    Io(io::Error),
+    /// A line of a file could not be parsed (1-based line number).
+    Parse {
+        line: usize,
+    },
+    /// A data item of the input file is invalid (1-based line number).
+    InvalidItem {
+        line: usize,
+    },
+    /// The fixtures were generated by different indicator configurations.
+    SpecMismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The fixtures have a different number of bars.
+    LengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    /// The outputs of a bar (0-based index) differ.
+    ValueMismatch {
+        index: usize,
+        expected: Vec<f64>,
+        actual: Vec<f64>,
+    },
+}
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FixtureError::Io(err) => write!(f, "fixture I/O error: {}", err),
+            FixtureError::Parse { line } => write!(f, "cannot parse line {}", line),
+            FixtureError::InvalidItem { line } => write!(f, "invalid data item at line {}", line),
+            FixtureError::SpecMismatch { expected, actual } => {
+                write!(f, "expected indicator {}, got {}", expected, actual)
+            }
+            FixtureError::LengthMismatch { expected, actual } => {
+                write!(f, "expected {} bars, got {}", expected, actual)
+            }
+            FixtureError::ValueMismatch {
+                index,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "outputs differ at bar {}: expected {:?}, got {:?}",
+                index, expected, actual
+            ),
+        }
+    }
+}
+
+impl Error for FixtureError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FixtureError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for FixtureError {
+    fn from(err: io::Error) -> Self {
+        FixtureError::Io(err)
+    }
+}
+
+/// Reads data items from a CSV file.
+pub fn read_csv<P: AsRef<Path>>(path: P) -> Result<Vec<DataItem>, FixtureError> {
+    let content = fs::read_to_string(path)?;
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+
+    let header: Vec<String> = match lines.next() {
+        Some((_, header)) => header.split(',').map(|c| c.trim().to_lowercase()).collect(),
+        None => return Ok(Vec::new()),
+    };
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|c| c == name)
+            .ok_or(FixtureError::Parse { line: 1 })
+    };
+    let columns = [
+        column("open")?,
+        column("high")?,
+        column("low")?,
+        column("close")?,
+        column("volume")?,
+    ];
+    let timestamp_column = header.iter().position(|c| c == "timestamp");
+
+    lines
+        .map(|(i, line)| {
+            let line_number = i + 1;
+            let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+            let mut values = [0.0; 5];
+            for (value, &column) in values.iter_mut().zip(columns.iter()) {
+                *value = fields
+                    .get(column)
+                    .and_then(|f| f.parse().ok())
+                    .ok_or(FixtureError::Parse { line: line_number })?;
+            }
+            let mut builder = DataItem::builder()
+                .open(values[0])
+                .high(values[1])
+                .low(values[2])
+                .close(values[3])
+                .volume(values[4]);
+            if let Some(column) = timestamp_column {
+                let timestamp = fields
+                    .get(column)
+                    .and_then(|f| f.parse().ok())
+                    .ok_or(FixtureError::Parse { line: line_number })?;
+                builder = builder.timestamp(timestamp);
+            }
+            builder
+                .build()
+                .map_err(|_| FixtureError::InvalidItem { line: line_number })
+        })
+        .collect()
+}
+
+/// Outputs of an indicator configuration over a fixed input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixture {
+    spec: String,
+    rows: Vec<Vec<f64>>,
+}
+
+impl Fixture {
+    /// Generates a fixture by feeding the inputs into a reset clone of the indicator.
+    ///
+    /// `values` turns an output into the values to store, e.g. `|&x| vec![x]` for an
+    /// indicator returning `f64`.
+    pub fn generate<I, T, F>(indicator: &I, inputs: impl IntoIterator<Item = T>, values: F) -> Self
+    where
+        I: Clone + Reset + fmt::Display + Next<T>,
+        F: Fn(&I::Output) -> Vec<f64>,
+    {
+        let mut indicator = indicator.clone();
+        indicator.reset();
+        Self {
+            spec: indicator.to_string(),
+            rows: inputs
+                .into_iter()
+                .map(|input| values(&indicator.next(input)))
+                .collect(),
+        }
+    }
+
+    /// Indicator specification, i.e. the `Display` output of the indicator.
+    pub fn spec(&self) -> &str {
+        &self.spec
+    }
+
+    /// Output values of every bar.
+    pub fn rows(&self) -> &[Vec<f64>] {
+        &self.rows
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), FixtureError> {
+        let mut content = format!("#{}\n", self.spec);
+        for row in self.rows.iter() {
+            let row: Vec<String> = row.iter().map(|x| x.to_string()).collect();
+            content.push_str(&row.join(","));
+            content.push('\n');
+        }
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, FixtureError> {
+        let content = fs::read_to_string(path)?;
+        let mut lines = content.lines();
+        let spec = lines
+            .next()
+            .and_then(|line| line.strip_prefix('#'))
+            .ok_or(FixtureError::Parse { line: 1 })?
+            .to_string();
+        let rows = lines
+            .enumerate()
+            .map(|(i, line)| {
+                if line.is_empty() {
+                    return Ok(Vec::new());
+                }
+                line.split(',')
+                    .map(|x| x.parse().map_err(|_| FixtureError::Parse { line: i + 2 }))
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { spec, rows })
+    }
+
+    /// Compares the fixture with an expected one. Values differing by at most `tolerance` are
+    /// equal, NaN is equal to NaN.
+    pub fn compare(&self, expected: &Fixture, tolerance: f64) -> Result<(), FixtureError> {
+        if self.spec != expected.spec {
+            return Err(FixtureError::SpecMismatch {
+                expected: expected.spec.clone(),
+                actual: self.spec.clone(),
+            });
+        }
+        if self.rows.len() != expected.rows.len() {
+            return Err(FixtureError::LengthMismatch {
+                expected: expected.rows.len(),
+                actual: self.rows.len(),
+            });
+        }
+        let equal = |a: &f64, b: &f64| (a.is_nan() && b.is_nan()) || (a - b).abs() <= tolerance;
+        for (index, (actual, expected)) in self.rows.iter().zip(&expected.rows).enumerate() {
+            if actual.len() != expected.len()
+                || !actual.iter().zip(expected).all(|(a, b)| equal(a, b))
+            {
+                return Err(FixtureError::ValueMismatch {
+                    index,
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Asserts, that the indicator produces the outputs stored in the fixture file.
+///
+/// If the file does not exist, the fixture is generated and saved instead, so the first run
+/// locks the behavior in. Delete the file to regenerate it.
+///
+/// # Panics
+///
+/// Panics, when the outputs differ by more than `tolerance`, or the file cannot be read or
+/// written.
+pub fn assert_golden<P, I, T, F>(
+    path: P,
+    indicator: &I,
+    inputs: impl IntoIterator<Item = T>,
+    values: F,
+    tolerance: f64,
+) where
+    P: AsRef<Path>,
+    I: Clone + Reset + fmt::Display + Next<T>,
+    F: Fn(&I::Output) -> Vec<f64>,
+{
+    let path = path.as_ref();
+    let actual = Fixture::generate(indicator, inputs, values);
+    if !path.exists() {
+        if let Err(err) = actual.save(path) {
+            panic!("cannot save fixture {}: {}", path.display(), err);
+        }
+        return;
+    }
+    let result = Fixture::load(path).and_then(|expected| actual.compare(&expected, tolerance));
+    if let Err(err) = result {
+        panic!("fixture {}: {}", path.display(), err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{BollingerBands, SimpleMovingAverage};
+    use crate::Timestamped;
+    use std::path::PathBuf;
+
+    fn temp_file(name: &str, content: Option<&str>) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("ta_fixtures_{}_{}", std::process::id(), name));
+        match content {
+            Some(content) => fs::write(&path, content).unwrap(),
+            None => {
+                let _ = fs::remove_file(&path);
+            }
+        }
+        path
+    }
+
+    #[test]
+    fn test_read_csv() {
+        let path = temp_file(
+            "input.csv",
+            Some("Date,Close,Open,High,Low,Volume\n2021-01-04,10.5,10,11,9.5,1000\n\n2021-01-05,11,10.5,11.5,10,1200\n"),
+        );
+        let items = read_csv(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[0],
+            DataItem::builder()
+                .open(10.0)
+                .high(11.0)
+                .low(9.5)
+                .close(10.5)
+                .volume(1000.0)
+                .build()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_read_csv_timestamp() {
+        let path = temp_file(
+            "timestamp.csv",
+            Some(
+                "timestamp,open,high,low,close,volume
+60,1,2,0.5,1.5,10
+120,1,2,0.5,1.5,10
+",
+            ),
+        );
+        let items = read_csv(&path).unwrap();
+        assert_eq!(items[0].timestamp(), Some(60));
+        assert_eq!(items[1].timestamp(), Some(120));
+
+        fs::write(
+            &path,
+            "timestamp,open,high,low,close,volume
+1.5,1,2,0.5,1.5,10
+",
+        )
+        .unwrap();
+        assert!(matches!(
+            read_csv(&path),
+            Err(FixtureError::Parse { line: 2 })
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_read_csv_errors() {
+        let path = temp_file("no_volume.csv", Some("open,high,low,close\n1,2,0.5,1.5\n"));
+        assert!(matches!(
+            read_csv(&path),
+            Err(FixtureError::Parse { line: 1 })
+        ));
+
+        fs::write(
+            &path,
+            "open,high,low,close,volume\n1,2,0.5,1.5,10\n1,x,0.5,1.5,10\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            read_csv(&path),
+            Err(FixtureError::Parse { line: 3 })
+        ));
+
+        // high is below low
+        fs::write(&path, "open,high,low,close,volume\n1,0.5,2,1.5,10\n").unwrap();
+        assert!(matches!(
+            read_csv(&path),
+            Err(FixtureError::InvalidItem { line: 2 })
+        ));
+        fs::remove_file(&path).unwrap();
+
+        assert!(matches!(read_csv(&path), Err(FixtureError::Io(_))));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let bb = BollingerBands::new(3, 2.0).unwrap();
+        let inputs = [1.0, 2.5, 3.0, 1.0 / 3.0];
+        let fixture = Fixture::generate(&bb, inputs.iter().copied(), |out| {
+            vec![out.lower, out.average, out.upper]
+        });
+        assert_eq!(fixture.spec(), "BB(3, 2)");
+        assert_eq!(fixture.rows().len(), 4);
+
+        let path = temp_file("bb.txt", None);
+        fixture.save(&path).unwrap();
+        let loaded = Fixture::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // values round trip exactly
+        assert_eq!(loaded, fixture);
+        assert!(loaded.compare(&fixture, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_compare() {
+        let inputs = [1.0, 2.0, 3.0];
+        let sma2 = SimpleMovingAverage::new(2).unwrap();
+        let sma3 = SimpleMovingAverage::new(3).unwrap();
+        let expected = Fixture::generate(&sma2, inputs.iter().copied(), |&x| vec![x]);
+
+        let actual = Fixture::generate(&sma3, inputs.iter().copied(), |&x| vec![x]);
+        assert!(matches!(
+            actual.compare(&expected, 0.0),
+            Err(FixtureError::SpecMismatch { .. })
+        ));
+
+        let actual = Fixture::generate(&sma2, inputs[..2].iter().copied(), |&x| vec![x]);
+        assert!(matches!(
+            actual.compare(&expected, 0.0),
+            Err(FixtureError::LengthMismatch {
+                expected: 3,
+                actual: 2
+            })
+        ));
+
+        let actual = Fixture::generate(&sma2, [1.0, 2.0, 3.1].iter().copied(), |&x| vec![x]);
+        match actual.compare(&expected, 0.01) {
+            Err(FixtureError::ValueMismatch { index, .. }) => assert_eq!(index, 2),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(actual.compare(&expected, 0.1).is_ok());
+    }
+
+    #[test]
+    fn test_nan() {
+        let fixture = Fixture {
+            spec: "X".to_string(),
+            rows: vec![vec![f64::NAN, 1.0]],
+        };
+        let path = temp_file("nan.txt", None);
+        fixture.save(&path).unwrap();
+        let loaded = Fixture::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded.compare(&fixture, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_load_errors() {
+        let path = temp_file("broken.txt", Some("SMA(2)\n1\n"));
+        assert!(matches!(
+            Fixture::load(&path),
+            Err(FixtureError::Parse { line: 1 })
+        ));
+
+        fs::write(&path, "#SMA(2)\n1\n1,a\n").unwrap();
+        assert!(matches!(
+            Fixture::load(&path),
+            Err(FixtureError::Parse { line: 3 })
+        ));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_assert_golden() {
+        let path = temp_file("golden.txt", None);
+        let sma = SimpleMovingAverage::new(2).unwrap();
+        let inputs = [1.0, 2.0, 3.0];
+
+        assert_golden(&path, &sma, inputs.iter().copied(), |&x| vec![x], 0.0);
+        assert!(path.exists());
+        assert_golden(&path, &sma, inputs.iter().copied(), |&x| vec![x], 0.0);
+
+        let result = std::panic::catch_unwind(|| {
+            assert_golden(
+                &path,
+                &sma,
+                [1.0, 2.0, 4.0].iter().copied(),
+                |&x| vec![x],
+                0.0,
+            );
+        });
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+}
@@ -1,11 +1,179 @@
+use core::fmt;
+
+use num_traits::{Float, ToPrimitive};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ParamValue;
+
+/// Converts a number to the value type of a generic indicator. Every primitive number fits
+/// into a float (possibly rounded), so it never fails.
+pub fn cast<T: Float, N: ToPrimitive>(n: N) -> T {
+    T::from(n).unwrap()
+}
+
+/// Writes an indicator as `NAME(parameter, ...)`.
+pub fn fmt_params(
+    f: &mut fmt::Formatter,
+    name: &str,
+    params: &[(&str, ParamValue)],
+) -> fmt::Result {
+    write!(f, "{}(", name)?;
+    for (i, (_, value)) in params.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", value)?;
+    }
+    write!(f, ")")
+}
+
 /// Returns the largest of 3 given numbers.
 pub fn max3(a: f64, b: f64, c: f64) -> f64 {
     a.max(b).max(c)
 }
 
+/// Running sum of a cumulative or rolling indicator.
+///
+/// A compensated sum keeps the rounding errors of the additions (Neumaier's variant of Kahan
+/// summation) and adds them back to the value, so it does not drift after millions of inputs.
+/// A plain sum adds in the same order as `sum = sum + x` and gives the same results.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct Accumulator<T = f64> {
+    sum: T,
+    /// Sum of the rounding errors, `None` for a plain sum.
+    compensation: Option<T>,
+}
+
+impl<T: Float> Accumulator<T> {
+    pub fn new(compensated: bool) -> Self {
+        Self {
+            sum: T::zero(),
+            compensation: if compensated { Some(T::zero()) } else { None },
+        }
+    }
+
+    pub fn is_compensated(&self) -> bool {
+        self.compensation.is_some()
+    }
+
+    pub fn add(&mut self, x: T) {
+        let sum = self.sum + x;
+        if let Some(compensation) = self.compensation.as_mut() {
+            // the low-order bits lost by the larger of the two operands
+            *compensation = *compensation
+                + if self.sum.abs() >= x.abs() {
+                    (self.sum - sum) + x
+                } else {
+                    (x - sum) + self.sum
+                };
+        }
+        self.sum = sum;
+    }
+
+    pub fn value(&self) -> T {
+        match self.compensation {
+            Some(compensation) => self.sum + compensation,
+            None => self.sum,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.is_compensated());
+    }
+}
+
+/// SplitMix64 pseudo random generator: small, fast and good enough for shuffling and
+/// simulations.
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform number in range [0, 1).
+    pub fn uniform(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform index in range [0, n).
+    pub fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// Fisher-Yates shuffle.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, vec, vec::Vec};
+
+    struct Named(&'static str, Vec<(&'static str, ParamValue)>);
+
+    impl fmt::Display for Named {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            fmt_params(f, self.0, &self.1)
+        }
+    }
+
+    #[test]
+    fn test_fmt_params() {
+        assert_eq!(format!("{}", Named("TR", vec![])), "TR()");
+        let params = vec![
+            ("period", ParamValue::Period(20)),
+            ("multiplier", ParamValue::Float(2.5)),
+        ];
+        assert_eq!(format!("{}", Named("BB", params)), "BB(20, 2.5)");
+    }
+
+    #[test]
+    fn test_accumulator() {
+        let mut plain = Accumulator::new(false);
+        let mut compensated = Accumulator::new(true);
+        assert!(!plain.is_compensated());
+        assert!(compensated.is_compensated());
+
+        for x in [1e16, 1.0, -1e16] {
+            plain.add(x);
+            compensated.add(x);
+        }
+        assert_eq!(plain.value(), 0.0);
+        assert_eq!(compensated.value(), 1.0);
+
+        compensated.reset();
+        assert!(compensated.is_compensated());
+        assert_eq!(compensated.value(), 0.0);
+    }
+
+    #[test]
+    fn test_accumulator_drift() {
+        let mut plain = Accumulator::new(false);
+        let mut compensated = Accumulator::new(true);
+        for _ in 0..1_000_000 {
+            plain.add(0.1);
+            compensated.add(0.1);
+        }
+        assert!((plain.value() - 100_000.0).abs() > 1e-6);
+        assert_eq!(compensated.value(), 100_000.0);
+    }
 
     #[test]
     fn test_max3() {
@@ -13,4 +181,15 @@ mod tests {
         assert_eq!(max3(2.0, 3.0, 1.0), 3.0);
         assert_eq!(max3(2.0, 1.0, 3.0), 3.0);
     }
+
+    #[test]
+    fn test_shuffle() {
+        let mut rng = SplitMix64::new(5);
+        let mut items: Vec<usize> = (0..20).collect();
+        rng.shuffle(&mut items);
+
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
 }
@@ -0,0 +1,221 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::SimpleMovingAverage;
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Average daily range (ADR).
+///
+/// A simple volatility gauge: the average distance between the high and the low of a bar.
+/// Unlike the [average true range](struct.AverageTrueRange.html) it ignores gaps between the
+/// bars, which makes it a measure of the intraday movement day traders can expect.
+///
+/// The percentage variant expresses the range relative to the latest close, so it can be
+/// compared across instruments with different price levels.
+///
+/// # Formula
+///
+/// ADR = SMA(period) of (high - low)
+///
+/// ADR% = ADR / close * 100
+///
+/// Where:
+///
+/// * _SMA(period)_ - [simple moving average](struct.SimpleMovingAverage.html) with _period_
+///
+/// ADR% is 0, when the close is 0.
+///
+/// # Parameters
+///
+/// * _period_ - number of bars (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::AverageDailyRange;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(high: f64, low: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(close).high(high).low(low).close(close).volume(100.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut adr = AverageDailyRange::new(2).unwrap();
+/// assert_eq!(adr.next(&bar(11.0, 9.0, 10.0)).range, 2.0);
+///
+/// let out = adr.next(&bar(21.0, 17.0, 20.0));
+/// assert_eq!(out.range, 3.0);
+/// assert_eq!(out.percent, 15.0);
+/// ```
+#[doc(alias = "ADR")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct AverageDailyRange {
+    sma: SimpleMovingAverage,
+    output: Option<AverageDailyRangeOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AverageDailyRangeOutput {
+    pub range: f64,
+    pub percent: f64,
+}
+
+impl AverageDailyRange {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            sma: SimpleMovingAverage::new(period)?,
+            output: None,
+        })
+    }
+}
+
+impl Period for AverageDailyRange {
+    fn period(&self) -> usize {
+        self.sma.period()
+    }
+}
+
+impl IsReady for AverageDailyRange {
+    fn bars_until_ready(&self) -> usize {
+        self.sma.bars_until_ready()
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for AverageDailyRange {
+    type Output = AverageDailyRangeOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let range = self.sma.next(input.high() - input.low());
+        let close = input.close();
+        let percent = if close != 0.0 {
+            range / close * 100.0
+        } else {
+            0.0
+        };
+        let output = AverageDailyRangeOutput { range, percent };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for AverageDailyRange {
+    type Output = AverageDailyRangeOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for AverageDailyRange {
+    fn reset(&mut self) {
+        self.sma.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for AverageDailyRange {
+    fn heap_size(&self) -> usize {
+        self.sma.heap_size()
+    }
+}
+
+impl Default for AverageDailyRange {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for AverageDailyRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ADR({})", self.sma.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_hlcv_indicator!(AverageDailyRange);
+
+    fn bar(high: f64, low: f64, close: f64) -> Bar {
+        Bar::new().high(high).low(low).close(close)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(AverageDailyRange::new(0).is_err());
+        assert!(AverageDailyRange::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut adr = AverageDailyRange::new(3).unwrap();
+
+        assert_eq!(adr.next(&bar(10.0, 8.0, 9.0)).range, 2.0);
+        assert_eq!(adr.next(&bar(12.0, 8.0, 10.0)).range, 3.0);
+        let out = adr.next(&bar(13.0, 10.0, 12.0));
+        assert_eq!(out.range, 3.0);
+        assert_eq!(out.percent, 25.0);
+        // the first range drops out of the window
+        let out = adr.next(&bar(15.0, 13.0, 14.0));
+        assert_eq!(out.range, 3.0);
+        assert_eq!(round(out.percent), 21.429);
+    }
+
+    #[test]
+    fn test_ignores_gaps() {
+        let mut adr = AverageDailyRange::new(2).unwrap();
+
+        adr.next(&bar(10.0, 9.0, 9.5));
+        assert_eq!(adr.next(&bar(21.0, 20.0, 20.5)).range, 1.0);
+    }
+
+    #[test]
+    fn test_zero_close() {
+        let mut adr = AverageDailyRange::new(2).unwrap();
+        assert_eq!(adr.next(&bar(1.0, 0.0, 0.0)).percent, 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut adr = AverageDailyRange::new(3).unwrap();
+
+        adr.next(&bar(10.0, 8.0, 9.0));
+        adr.next(&bar(12.0, 8.0, 10.0));
+
+        adr.reset();
+        assert_eq!(adr.next(&bar(11.0, 10.0, 10.0)).range, 1.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(2).low(1).close(1.5);
+        assert_warm_up(&mut AverageDailyRange::new(3).unwrap(), &bar, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut AverageDailyRange::default(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        AverageDailyRange::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let adr = AverageDailyRange::new(14).unwrap();
+        assert_eq!(format!("{}", adr), "ADR(14)");
+    }
+}
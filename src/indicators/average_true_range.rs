@@ -1,8 +1,15 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 use crate::errors::Result;
+use crate::helpers::fmt_params;
 use crate::indicators::{ExponentialMovingAverage, TrueRange};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::registry::Params;
+use crate::{
+    Close, Configure, Current, High, IsReady, Low, MemoryFootprint, Next, NextBatch, ParamValue,
+    Parameters, Period, Reset,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -26,6 +33,9 @@ use serde::{Deserialize, Serialize};
 ///
 /// * _period_ - smoothing period of EMA (integer greater than 0)
 ///
+/// A single `f64` input, e.g. an index value or a spread, has no range, so the true range falls
+/// back to the absolute change from the previous input, and is 0 for the first one.
+///
 /// # Example
 ///
 /// ```
@@ -79,6 +89,18 @@ impl Period for AverageTrueRange {
     }
 }
 
+impl Configure for AverageTrueRange {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        self.ema.set_period(period)
+    }
+}
+
+impl IsReady for AverageTrueRange {
+    fn bars_until_ready(&self) -> usize {
+        self.ema.bars_until_ready()
+    }
+}
+
 impl Next<f64> for AverageTrueRange {
     type Output = f64;
 
@@ -87,6 +109,16 @@ impl Next<f64> for AverageTrueRange {
     }
 }
 
+impl NextBatch<f64> for AverageTrueRange {
+    fn next_batch(&mut self, inputs: &[f64], out: &mut Vec<Self::Output>) {
+        let start = out.len();
+        self.true_range.next_batch(inputs, out);
+        for value in &mut out[start..] {
+            *value = self.ema.next(*value);
+        }
+    }
+}
+
 impl<T: High + Low + Close> Next<&T> for AverageTrueRange {
     type Output = f64;
 
@@ -95,6 +127,14 @@ impl<T: High + Low + Close> Next<&T> for AverageTrueRange {
     }
 }
 
+impl Current for AverageTrueRange {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.ema.current()
+    }
+}
+
 impl Reset for AverageTrueRange {
     fn reset(&mut self) {
         self.true_range.reset();
@@ -102,6 +142,23 @@ impl Reset for AverageTrueRange {
     }
 }
 
+impl MemoryFootprint for AverageTrueRange {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Parameters for AverageTrueRange {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.ema.period()))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::new(params.period("period", 14)?)
+    }
+}
+
 impl Default for AverageTrueRange {
     fn default() -> Self {
         Self::new(14).unwrap()
@@ -110,7 +167,7 @@ impl Default for AverageTrueRange {
 
 impl fmt::Display for AverageTrueRange {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "ATR({})", self.ema.period())
+        fmt_params(f, "ATR", &self.params())
     }
 }
 
@@ -139,6 +196,23 @@ mod tests {
         assert_eq!(atr.next(&bar3), 3.375);
     }
 
+    #[test]
+    fn test_next_batch() {
+        let inputs: Vec<f64> = (0..30)
+            .map(|i| 50.0 + (i as f64 * 0.9).cos() * 3.0)
+            .collect();
+        let mut expected = AverageTrueRange::new(5).unwrap();
+        let expected: Vec<f64> = inputs.iter().map(|&x| expected.next(x)).collect();
+
+        let mut atr = AverageTrueRange::new(5).unwrap();
+        let mut out = Vec::new();
+        for chunk in inputs.chunks(7) {
+            atr.next_batch(chunk, &mut out);
+        }
+        assert_eq!(out, expected);
+        assert_eq!(atr.current(), expected.last().copied());
+    }
+
     #[test]
     fn test_reset() {
         let mut atr = AverageTrueRange::new(9).unwrap();
@@ -154,6 +228,16 @@ mod tests {
         assert_eq!(atr.next(&bar3), 45.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut AverageTrueRange::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut AverageTrueRange::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_default() {
         AverageTrueRange::default();
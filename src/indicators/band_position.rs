@@ -0,0 +1,241 @@
+use core::fmt;
+
+use crate::{Bands, Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Band position (%B) and band width of any band indicator.
+///
+/// Wraps an indicator, whose output implements [Bands](../trait.Bands.html), e.g.
+/// [Bollinger Bands](struct.BollingerBands.html) or [Keltner Channel](struct.KeltnerChannel.html),
+/// and reports where the price is within the bands, so the same %B logic can be applied to
+/// any of them.
+///
+/// # Formula
+///
+/// position = (price - lower) / (upper - lower)
+///
+/// width = (upper - lower) / middle
+///
+/// The position is 0 at the lower band, 1 at the upper band, and outside of [0, 1], when the
+/// price is outside of the bands. It is 0.5, when the bands have no width. The width is 0,
+/// when the middle band is 0. For bars the price is the close.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{BandPosition, BollingerBands};
+/// use ta::Next;
+///
+/// let mut bp = BandPosition::new(BollingerBands::new(3, 1.0).unwrap());
+///
+/// bp.next(10.0);
+/// // bands are 10.0 and 12.0
+/// let out = bp.next(12.0);
+/// assert_eq!(out.position, 1.0);
+/// assert_eq!(out.width, 2.0 / 11.0);
+/// ```
+#[doc(alias = "%B")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct BandPosition<I> {
+    indicator: I,
+    output: Option<BandPositionOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BandPositionOutput {
+    pub position: f64,
+    pub width: f64,
+}
+
+impl<I> BandPosition<I> {
+    pub fn new(indicator: I) -> Self {
+        Self {
+            indicator,
+            output: None,
+        }
+    }
+
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    fn position<B: Bands>(&mut self, price: f64, bands: B) -> BandPositionOutput {
+        let (upper, middle, lower) = (bands.upper(), bands.middle(), bands.lower());
+        let range = upper - lower;
+
+        let output = BandPositionOutput {
+            position: if range != 0.0 {
+                (price - lower) / range
+            } else {
+                0.5
+            },
+            width: if middle != 0.0 { range / middle } else { 0.0 },
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<I: Period> Period for BandPosition<I> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: IsReady> IsReady for BandPosition<I> {
+    fn bars_until_ready(&self) -> usize {
+        self.indicator.bars_until_ready()
+    }
+}
+
+impl<I> Next<f64> for BandPosition<I>
+where
+    I: Next<f64>,
+    I::Output: Bands,
+{
+    type Output = BandPositionOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let bands = self.indicator.next(input);
+        self.position(input, bands)
+    }
+}
+
+impl<'a, I, T> Next<&'a T> for BandPosition<I>
+where
+    I: Next<&'a T>,
+    I::Output: Bands,
+    T: Close,
+{
+    type Output = BandPositionOutput;
+
+    fn next(&mut self, input: &'a T) -> Self::Output {
+        let bands = self.indicator.next(input);
+        self.position(input.close(), bands)
+    }
+}
+
+impl<I> Current for BandPosition<I> {
+    type Output = BandPositionOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<I: Reset> Reset for BandPosition<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.output = None;
+    }
+}
+
+impl<I: MemoryFootprint> MemoryFootprint for BandPosition<I> {
+    fn heap_size(&self) -> usize {
+        self.indicator.heap_size()
+    }
+}
+
+impl<I: Default> Default for BandPosition<I> {
+    fn default() -> Self {
+        Self::new(I::default())
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for BandPosition<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BP({})", self.indicator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{BollingerBands, KeltnerChannel};
+    use crate::test_helper::*;
+
+    type BollingerPosition = BandPosition<BollingerBands>;
+
+    test_indicator!(BollingerPosition);
+
+    #[test]
+    fn test_bollinger_bands() {
+        let mut bp = BandPosition::new(BollingerBands::new(3, 2.0).unwrap());
+
+        // no width yet
+        let out = bp.next(2.0);
+        assert_eq!(out.position, 0.5);
+        assert_eq!(out.width, 0.0);
+
+        // the same as %B computed from the bands
+        let mut bb = BollingerBands::new(3, 2.0).unwrap();
+        bb.next(2.0);
+        for &x in &[5.0, 1.0, 6.25] {
+            let bands = bb.next(x);
+            let out = bp.next(x);
+            assert_eq!(
+                out.position,
+                (x - bands.lower) / (bands.upper - bands.lower)
+            );
+            assert_eq!(out.width, (bands.upper - bands.lower) / bands.average);
+        }
+    }
+
+    #[test]
+    fn test_keltner_channel() {
+        let mut bp = BandPosition::new(KeltnerChannel::new(3, 2.0).unwrap());
+
+        bp.next(2.0);
+        // channel is 0.5 - 6.5 around 3.5
+        let out = bp.next(5.0);
+        assert_eq!(out.position, 0.75);
+        assert_eq!(round(out.width), 1.714);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut bp = BandPosition::new(KeltnerChannel::new(3, 2.0).unwrap());
+
+        // typical price is 1.4, ATR is 0.5, so the channel is 0.4 - 2.4
+        let out = bp.next(&Bar::new().low(1.2).high(1.7).close(1.3));
+        assert_eq!(round(out.position), 0.45);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bp = BandPosition::new(BollingerBands::new(3, 2.0).unwrap());
+
+        bp.next(2.0);
+        bp.next(5.0);
+
+        bp.reset();
+        assert_eq!(bp.next(4.0).position, 0.5);
+        assert_eq!(bp.next(6.0).position, 0.75);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bb = BollingerBands::new(3, 2.0).unwrap();
+        assert_warm_up(&mut BandPosition::new(bb), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        let bb = BollingerBands::new(3, 2.0).unwrap();
+        assert_current(&mut BandPosition::new(bb), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        BandPosition::<KeltnerChannel>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let bp = BandPosition::new(BollingerBands::new(20, 2.0).unwrap());
+        assert_eq!(format!("{}", bp), "BP(BB(20, 2))");
+    }
+}
@@ -1,8 +1,17 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
-use crate::errors::Result;
+use num_traits::Float;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{cast, fmt_params};
 use crate::indicators::StandardDeviation as Sd;
-use crate::{Close, Next, Period, Reset};
+use crate::registry::Params;
+use crate::{
+    Bands, Close, Configure, Current, IsReady, MemoryFootprint, Next, NextBatch, ParamValue,
+    Parameters, Period, Reset, SolveForInput,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +30,20 @@ use serde::{Deserialize, Serialize};
 ///  * _BB<sub>Upper Band</sub>_ = SMA + SD of observation * multipler (usually 2.0)
 ///  * _BB<sub>Lower Band</sub>_ = SMA - SD of observation * multipler (usually 2.0)
 ///
+/// # Solving for a %B
+///
+/// [SolveForInput](../trait.SolveForInput.html) takes a target %B, i.e. the position of the
+/// input between the bands (0 at the lower band, 1 at the upper band), and returns the input
+/// which would be at that position after it is added to the window. The input changes the bands
+/// as well, so a %B can only be reached, when _|2 * %B - 1| * multiplier_ is less than the square
+/// root of the number of other values in the window.
+///
+/// # Value type
+///
+/// The values are `f64` by default. Any [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html)
+/// type can be used with [with_period](#method.with_period), e.g.
+/// `BollingerBands::<f32>::with_period(20, 2.0)`. The multiplier stays an `f64` parameter.
+///
 /// # Example
 ///
 ///```
@@ -48,25 +71,56 @@ use serde::{Deserialize, Serialize};
 #[doc(alias = "BB")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct BollingerBands {
+pub struct BollingerBands<T = f64> {
     period: usize,
     multiplier: f64,
-    sd: Sd,
+    sd: Sd<T>,
+    output: Option<BollingerBandsOutput<T>>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct BollingerBandsOutput {
-    pub average: f64,
-    pub upper: f64,
-    pub lower: f64,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BollingerBandsOutput<T = f64> {
+    pub average: T,
+    pub upper: T,
+    pub lower: T,
+}
+
+impl Bands for BollingerBandsOutput {
+    fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    fn middle(&self) -> f64 {
+        self.average
+    }
+
+    fn lower(&self) -> f64 {
+        self.lower
+    }
 }
 
 impl BollingerBands {
     pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        Self::with_period(period, multiplier)
+    }
+}
+
+impl<T: Float> BollingerBands<T> {
+    /// Same as `new`, for any value type.
+    pub fn with_period(period: usize, multiplier: f64) -> Result<Self> {
+        if multiplier.is_nan() || multiplier <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "multiplier",
+                multiplier,
+                "must be greater than 0",
+            ));
+        }
         Ok(Self {
             period,
             multiplier,
-            sd: Sd::new(period)?,
+            sd: Sd::with_period(period)?,
+            output: None,
         })
     }
 
@@ -75,38 +129,137 @@ impl BollingerBands {
     }
 }
 
-impl Period for BollingerBands {
+impl<T> Period for BollingerBands<T> {
     fn period(&self) -> usize {
         self.period
     }
 }
 
-impl Next<f64> for BollingerBands {
-    type Output = BollingerBandsOutput;
+impl<T: Float> Configure for BollingerBands<T> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        self.sd.set_period(period)?;
+        self.period = period;
+        Ok(())
+    }
+
+    fn set_multiplier(&mut self, multiplier: f64) -> Result<()> {
+        if multiplier.is_nan() || multiplier <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "multiplier",
+                multiplier,
+                "must be greater than 0",
+            ));
+        }
+        self.multiplier = multiplier;
+        Ok(())
+    }
+}
+
+impl<T> IsReady for BollingerBands<T> {
+    fn bars_until_ready(&self) -> usize {
+        self.sd.bars_until_ready()
+    }
+}
+
+impl<T: Float> Next<T> for BollingerBands<T> {
+    type Output = BollingerBandsOutput<T>;
 
-    fn next(&mut self, input: f64) -> Self::Output {
+    fn next(&mut self, input: T) -> Self::Output {
         let sd = self.sd.next(input);
         let mean = self.sd.mean();
+        let multiplier: T = cast(self.multiplier);
 
-        Self::Output {
+        let output = Self::Output {
             average: mean,
-            upper: mean + sd * self.multiplier,
-            lower: mean - sd * self.multiplier,
+            upper: mean + sd * multiplier,
+            lower: mean - sd * multiplier,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Float> NextBatch<T> for BollingerBands<T> {}
+
+impl SolveForInput for BollingerBands {
+    fn solve_for_input(&self, target: f64) -> Option<f64> {
+        let (mut r, mut s, mut q) = (0.0, 0.0, 0.0);
+        for x in self.sd.retained() {
+            r += 1.0;
+            s += x;
+            q += x * x;
         }
+        let n = r + 1.0;
+        // the input x is c standard deviations away from the mean of the new window:
+        // ((n - 1) * x - s)^2 = c^2 * (n * (q + x^2) - (s + x)^2)
+        let c = self.multiplier * (2.0 * target - 1.0);
+        let a = r * (r - c * c);
+        if a <= 0.0 {
+            return None;
+        }
+        let b = -2.0 * s * (r - c * c);
+        let d = b * b - 4.0 * a * (s * s - c * c * (n * q - s * s));
+        let root = d.max(0.0).sqrt();
+        // of the two roots take the one on the side of the mean given by the sign of c
+        let x = if c >= 0.0 {
+            (-b + root) / (2.0 * a)
+        } else {
+            (-b - root) / (2.0 * a)
+        };
+        Some(x)
     }
 }
 
-impl<T: Close> Next<&T> for BollingerBands {
-    type Output = BollingerBandsOutput;
+// Bars are only accepted by the primitive floats, a generic impl would overlap `Next<T>`.
+macro_rules! impl_close {
+    ($($t:ty),*) => {$(
+        impl<C: Close> Next<&C> for BollingerBands<$t> {
+            type Output = BollingerBandsOutput<$t>;
+
+            fn next(&mut self, input: &C) -> Self::Output {
+                self.next(input.close() as $t)
+            }
+        }
+    )*};
+}
+
+impl_close!(f32, f64);
+
+impl<T: Copy> Current for BollingerBands<T> {
+    type Output = BollingerBandsOutput<T>;
 
-    fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
-impl Reset for BollingerBands {
+impl<T: Float> Reset for BollingerBands<T> {
     fn reset(&mut self) {
         self.sd.reset();
+        self.output = None;
+    }
+}
+
+impl<T> MemoryFootprint for BollingerBands<T> {
+    fn heap_size(&self) -> usize {
+        self.sd.heap_size()
+    }
+}
+
+impl<T: Float> Parameters for BollingerBands<T> {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![
+            ("period", ParamValue::Period(self.period)),
+            ("multiplier", ParamValue::Float(self.multiplier)),
+        ]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period", "multiplier"])?;
+        Self::with_period(
+            params.period("period", 9)?,
+            params.float("multiplier", 2.0)?,
+        )
     }
 }
 
@@ -116,9 +269,9 @@ impl Default for BollingerBands {
     }
 }
 
-impl fmt::Display for BollingerBands {
+impl<T: Float> fmt::Display for BollingerBands<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "BB({}, {})", self.period, self.multiplier)
+        fmt_params(f, "BB", &self.params())
     }
 }
 
@@ -132,6 +285,9 @@ mod tests {
     #[test]
     fn test_new() {
         assert!(BollingerBands::new(0, 2_f64).is_err());
+        assert!(BollingerBands::new(2, 0.0).is_err());
+        assert!(BollingerBands::new(2, -2.0).is_err());
+        assert!(BollingerBands::new(2, f64::NAN).is_err());
         assert!(BollingerBands::new(1, 2_f64).is_ok());
         assert!(BollingerBands::new(2, 2_f64).is_ok());
     }
@@ -161,6 +317,33 @@ mod tests {
         assert_eq!(round(d.lower), -0.395);
     }
 
+    #[test]
+    fn test_f32() {
+        let mut bb = BollingerBands::<f32>::with_period(3, 2.0).unwrap();
+        let a = bb.next(2.0f32);
+        let b = bb.next(&Bar::new().close(5));
+        assert_eq!(a.upper, 2.0f32);
+        assert_eq!((b.average, b.upper, b.lower), (3.5, 6.5, 0.5));
+        assert_eq!(format!("{}", bb), "BB(3, 2)");
+    }
+
+    #[test]
+    fn test_solve_for_input() {
+        let mut bb = BollingerBands::new(3, 1.0).unwrap();
+        assert_eq!(bb.solve_for_input(1.0), None);
+
+        bb.next(2.0);
+        bb.next(5.0);
+        bb.next(1.0);
+        for &target in &[0.0, 0.2, 0.5, 0.9, 1.0] {
+            let input = bb.solve_for_input(target).unwrap();
+            let out = bb.clone().next(input);
+            assert_eq!(round((input - out.lower) / (out.upper - out.lower)), target);
+        }
+        // 2 * SD is too far for a window of 3 values
+        assert_eq!(bb.solve_for_input(1.5), None);
+    }
+
     #[test]
     fn test_reset() {
         let mut bb = BollingerBands::new(5, 2.0_f64).unwrap();
@@ -188,6 +371,38 @@ mod tests {
         assert_eq!(out.lower, 3.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut BollingerBands::new(3, 2.0).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut BollingerBands::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_configure() {
+        let mut bb = BollingerBands::new(5, 2.0).unwrap();
+        for x in [2.0, 5.0, 1.0, 3.0] {
+            bb.next(x);
+        }
+        bb.set_period(2).unwrap();
+        bb.set_multiplier(1.0).unwrap();
+        assert_eq!(format!("{}", bb), "BB(2, 1)");
+
+        // the same as BB(2, 1) fed with the last input
+        let mut expected = BollingerBands::new(2, 1.0).unwrap();
+        expected.next(3.0);
+        assert_eq!(bb.next(4.0), expected.next(4.0));
+
+        assert!(bb.set_multiplier(0.0).is_err());
+        assert!(bb.set_multiplier(f64::NAN).is_err());
+        assert!(bb.set_period(0).is_err());
+        assert_eq!(bb.multiplier(), 1.0);
+        assert_eq!(bb.period(), 2);
+    }
+
     #[test]
     fn test_default() {
         BollingerBands::default();
@@ -198,4 +413,18 @@ mod tests {
         let bb = BollingerBands::new(10, 3.0_f64).unwrap();
         assert_eq!(format!("{}", bb), "BB(10, 3)");
     }
+
+    #[test]
+    fn test_parameters() {
+        let mut bb = BollingerBands::new(10, 3.0).unwrap();
+        bb.next(1.0);
+        bb.set_param("period", ParamValue::Period(5)).unwrap();
+        assert_eq!(bb.params()[0], ("period", ParamValue::Period(5)));
+        assert_eq!(bb.current(), None);
+
+        // the indicator is unchanged on failure
+        assert!(bb.set_param("multiplier", ParamValue::Float(-1.0)).is_err());
+        assert!(bb.set_param("period", ParamValue::Float(2.5)).is_err());
+        assert_eq!(format!("{}", bb), "BB(5, 3)");
+    }
 }
@@ -0,0 +1,178 @@
+use core::fmt;
+
+use crate::helpers::Accumulator;
+use crate::{Breadth, Current, IsReady, MemoryFootprint, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Advance/Decline Line (ADL).
+///
+/// A cumulative sum of the net advances, i.e. of the difference between the number of
+/// advancing and declining issues. A rising line confirms an uptrend of the index, while a
+/// line falling during an index rally shows that fewer and fewer issues take part in it.
+///
+/// # Formula
+///
+/// ADL = previous ADL + (advances - declines)
+///
+/// The line starts at 0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::breadth::{AdvanceDeclineLine, BreadthItem};
+/// use ta::Next;
+///
+/// let mut adl = AdvanceDeclineLine::new();
+///
+/// assert_eq!(adl.next(&BreadthItem::new(300.0, 200.0).unwrap()), 100.0);
+/// assert_eq!(adl.next(&BreadthItem::new(150.0, 350.0).unwrap()), -100.0);
+/// ```
+///
+/// # Links
+///
+/// * [Advance-Decline Line, Investopedia](https://www.investopedia.com/terms/a/adline.asp)
+///
+#[doc(alias = "ADL")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct AdvanceDeclineLine {
+    value: Accumulator,
+    output: Option<f64>,
+}
+
+impl AdvanceDeclineLine {
+    pub fn new() -> Self {
+        Self {
+            value: Accumulator::new(false),
+            output: None,
+        }
+    }
+
+    /// Same as `new`, with compensated summation of the net advances, e.g. for fractional
+    /// breadth like the advancing volume.
+    pub fn compensated() -> Self {
+        Self {
+            value: Accumulator::new(true),
+            output: None,
+        }
+    }
+}
+
+impl IsReady for AdvanceDeclineLine {
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
+}
+
+impl<T: Breadth> Next<&T> for AdvanceDeclineLine {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.value.add(input.advances() - input.declines());
+        let value = self.value.value();
+        self.output = Some(value);
+        value
+    }
+}
+
+impl Current for AdvanceDeclineLine {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for AdvanceDeclineLine {
+    fn reset(&mut self) {
+        self.value.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for AdvanceDeclineLine {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for AdvanceDeclineLine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for AdvanceDeclineLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ADL")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::breadth::BreadthItem;
+    use crate::test_helper::assert_current;
+
+    fn item(advances: f64, declines: f64) -> BreadthItem {
+        BreadthItem::new(advances, declines).unwrap()
+    }
+
+    #[test]
+    fn test_next() {
+        let mut adl = AdvanceDeclineLine::new();
+
+        assert_eq!(adl.next(&item(10.0, 5.0)), 5.0);
+        assert_eq!(adl.next(&item(7.0, 7.0)), 5.0);
+        assert_eq!(adl.next(&item(2.0, 9.0)), -2.0);
+        assert_eq!(adl.next(&item(6.0, 3.0)), 1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut adl = AdvanceDeclineLine::new();
+
+        adl.next(&item(10.0, 5.0));
+        adl.next(&item(8.0, 5.0));
+
+        adl.reset();
+        assert_eq!(adl.next(&item(1.0, 5.0)), -4.0);
+    }
+
+    #[test]
+    fn test_compensated() {
+        let mut adl = AdvanceDeclineLine::new();
+        let mut compensated = AdvanceDeclineLine::compensated();
+        for item in [item(1e16, 0.0), item(1.0, 0.0), item(0.0, 1e16)] {
+            adl.next(&item);
+            compensated.next(&item);
+        }
+        assert_eq!(adl.current(), Some(0.0));
+        assert_eq!(compensated.current(), Some(1.0));
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert!(AdvanceDeclineLine::new().is_ready());
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(
+            &mut AdvanceDeclineLine::default(),
+            &[item(300.0, 200.0), item(150.0, 350.0)],
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        AdvanceDeclineLine::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let adl = AdvanceDeclineLine::new();
+        assert_eq!(format!("{}", adl), "ADL");
+    }
+}
@@ -0,0 +1,221 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::SimpleMovingAverage as Sma;
+use crate::{Breadth, BreadthVolume, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Arms Index (TRIN), also known as the Short-Term Trading Index.
+///
+/// Compares the advance/decline ratio with the ratio of the advancing and declining volume.
+/// Values below 1 show that the volume flows into advancing issues (bullish), values above 1
+/// that it flows into declining issues (bearish). The raw index is noisy, so it is often
+/// smoothed with a moving average, e.g. over 10 days.
+///
+/// # Formula
+///
+/// TRIN = (advances / declines) / (advancing volume / declining volume)
+///
+/// Smoothed TRIN = SMA(TRIN, _period_)
+///
+/// TRIN is 1 (neutral) for a period without declines or without advancing volume, where the
+/// ratios are not defined.
+///
+/// # Parameters
+///
+/// * _period_ - period of the smoothing SMA (integer greater than 0), 1 for the raw index.
+///   Default is 1.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::breadth::{ArmsIndex, BreadthItem};
+/// use ta::Next;
+///
+/// let mut trin = ArmsIndex::new(1).unwrap();
+///
+/// // twice as many advances, but only as much volume as the declines
+/// let item = BreadthItem::new(2000.0, 1000.0)
+///     .unwrap()
+///     .with_volume(5.0e8, 5.0e8)
+///     .unwrap();
+/// assert_eq!(trin.next(&item), 2.0);
+/// ```
+///
+/// # Links
+///
+/// * [Arms Index (TRIN), Investopedia](https://www.investopedia.com/terms/a/arms.asp)
+///
+#[doc(alias = "TRIN")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ArmsIndex {
+    sma: Sma,
+    output: Option<f64>,
+}
+
+impl ArmsIndex {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            sma: Sma::new(period)?,
+            output: None,
+        })
+    }
+}
+
+impl Period for ArmsIndex {
+    fn period(&self) -> usize {
+        self.sma.period()
+    }
+}
+
+impl IsReady for ArmsIndex {
+    fn bars_until_ready(&self) -> usize {
+        self.sma.bars_until_ready()
+    }
+}
+
+impl<T: Breadth + BreadthVolume> Next<&T> for ArmsIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let numerator = input.advances() * input.declining_volume();
+        let denominator = input.declines() * input.advancing_volume();
+        let trin = if denominator > 0.0 {
+            numerator / denominator
+        } else {
+            1.0
+        };
+        let output = self.sma.next(trin);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for ArmsIndex {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for ArmsIndex {
+    fn reset(&mut self) {
+        self.sma.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for ArmsIndex {
+    fn heap_size(&self) -> usize {
+        self.sma.heap_size()
+    }
+}
+
+impl Default for ArmsIndex {
+    fn default() -> Self {
+        Self::new(1).unwrap()
+    }
+}
+
+impl fmt::Display for ArmsIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TRIN({})", self.sma.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::breadth::BreadthItem;
+    use crate::test_helper::{assert_current, assert_warm_up};
+
+    fn item(advances: f64, declines: f64, up_volume: f64, down_volume: f64) -> BreadthItem {
+        BreadthItem::new(advances, declines)
+            .unwrap()
+            .with_volume(up_volume, down_volume)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(ArmsIndex::new(0).is_err());
+        assert!(ArmsIndex::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut trin = ArmsIndex::new(1).unwrap();
+
+        assert_eq!(trin.next(&item(20.0, 10.0, 200.0, 100.0)), 1.0);
+        assert_eq!(trin.next(&item(30.0, 10.0, 100.0, 100.0)), 3.0);
+        assert_eq!(trin.next(&item(10.0, 20.0, 100.0, 400.0)), 2.0);
+        assert_eq!(trin.next(&item(10.0, 20.0, 400.0, 100.0)), 0.125);
+    }
+
+    #[test]
+    fn test_smoothing() {
+        let mut trin = ArmsIndex::new(2).unwrap();
+
+        assert_eq!(trin.next(&item(20.0, 10.0, 200.0, 100.0)), 1.0);
+        assert_eq!(trin.next(&item(30.0, 10.0, 100.0, 100.0)), 2.0);
+        assert_eq!(trin.next(&item(10.0, 20.0, 100.0, 400.0)), 2.5);
+    }
+
+    #[test]
+    fn test_undefined() {
+        let mut trin = ArmsIndex::new(1).unwrap();
+
+        // no declines
+        assert_eq!(trin.next(&item(10.0, 0.0, 100.0, 0.0)), 1.0);
+        // no advancing volume
+        assert_eq!(trin.next(&item(10.0, 5.0, 0.0, 100.0)), 1.0);
+        // no volume data
+        assert_eq!(trin.next(&BreadthItem::new(10.0, 5.0).unwrap()), 1.0);
+        // no advances
+        assert_eq!(trin.next(&item(0.0, 5.0, 10.0, 100.0)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut trin = ArmsIndex::new(2).unwrap();
+
+        trin.next(&item(30.0, 10.0, 100.0, 100.0));
+
+        trin.reset();
+        assert_eq!(trin.next(&item(20.0, 10.0, 200.0, 100.0)), 1.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(
+            &mut ArmsIndex::new(3).unwrap(),
+            &item(20.0, 10.0, 200.0, 100.0),
+            3,
+        );
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(
+            &mut ArmsIndex::default(),
+            &[
+                item(300.0, 200.0, 10.0, 20.0),
+                item(150.0, 350.0, 20.0, 10.0),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        ArmsIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let trin = ArmsIndex::new(10).unwrap();
+        assert_eq!(format!("{}", trin), "TRIN(10)");
+    }
+}
@@ -0,0 +1,134 @@
+use crate::errors::{Result, TaError};
+use crate::{Breadth, BreadthVolume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Breadth data of a period, used as an input for breadth indicators.
+///
+/// The advancing and declining volume are 0, unless they are set with
+/// [with_volume](#method.with_volume).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::breadth::BreadthItem;
+/// use ta::{Breadth, BreadthVolume};
+///
+/// let item = BreadthItem::new(1800.0, 1200.0)
+///     .unwrap()
+///     .with_volume(9.0e8, 4.0e8)
+///     .unwrap();
+///
+/// assert_eq!(item.advances(), 1800.0);
+/// assert_eq!(item.declines(), 1200.0);
+/// assert_eq!(item.advancing_volume(), 9.0e8);
+/// assert_eq!(item.declining_volume(), 4.0e8);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BreadthItem {
+    advances: f64,
+    declines: f64,
+    advancing_volume: f64,
+    declining_volume: f64,
+}
+
+impl BreadthItem {
+    /// Returns an error, when a count is negative or NaN.
+    pub fn new(advances: f64, declines: f64) -> Result<Self> {
+        if !is_valid(advances) {
+            return Err(TaError::invalid_parameter(
+                "advances",
+                advances,
+                "must not be negative",
+            ));
+        }
+        if !is_valid(declines) {
+            return Err(TaError::invalid_parameter(
+                "declines",
+                declines,
+                "must not be negative",
+            ));
+        }
+        Ok(Self {
+            advances,
+            declines,
+            advancing_volume: 0.0,
+            declining_volume: 0.0,
+        })
+    }
+
+    /// Sets the advancing and declining volume. Returns an error, when a volume is negative
+    /// or NaN.
+    pub fn with_volume(mut self, advancing_volume: f64, declining_volume: f64) -> Result<Self> {
+        if !is_valid(advancing_volume) {
+            return Err(TaError::invalid_parameter(
+                "advancing_volume",
+                advancing_volume,
+                "must not be negative",
+            ));
+        }
+        if !is_valid(declining_volume) {
+            return Err(TaError::invalid_parameter(
+                "declining_volume",
+                declining_volume,
+                "must not be negative",
+            ));
+        }
+        self.advancing_volume = advancing_volume;
+        self.declining_volume = declining_volume;
+        Ok(self)
+    }
+}
+
+fn is_valid(value: f64) -> bool {
+    value >= 0.0
+}
+
+impl Breadth for BreadthItem {
+    fn advances(&self) -> f64 {
+        self.advances
+    }
+
+    fn declines(&self) -> f64 {
+        self.declines
+    }
+}
+
+impl BreadthVolume for BreadthItem {
+    fn advancing_volume(&self) -> f64 {
+        self.advancing_volume
+    }
+
+    fn declining_volume(&self) -> f64 {
+        self.declining_volume
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(BreadthItem::new(-1.0, 10.0).is_err());
+        assert!(BreadthItem::new(10.0, -1.0).is_err());
+        assert!(BreadthItem::new(f64::NAN, 10.0).is_err());
+        assert!(BreadthItem::new(0.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_with_volume() {
+        let item = BreadthItem::new(10.0, 5.0).unwrap();
+        assert_eq!(item.advancing_volume(), 0.0);
+        assert_eq!(item.declining_volume(), 0.0);
+
+        assert!(item.with_volume(-1.0, 10.0).is_err());
+        assert!(item.with_volume(10.0, f64::NAN).is_err());
+
+        let item = item.with_volume(100.0, 50.0).unwrap();
+        assert_eq!(item.advances(), 10.0);
+        assert_eq!(item.advancing_volume(), 100.0);
+        assert_eq!(item.declining_volume(), 50.0);
+    }
+}
@@ -0,0 +1,201 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::ExponentialMovingAverage as Ema;
+use crate::{Breadth, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// McClellan Oscillator.
+///
+/// A breadth momentum oscillator: the difference between a fast and a slow EMA of the net
+/// advances. Positive values show that more issues are advancing than on average recently,
+/// crossings of the zero line are used as buy and sell signals for the index.
+///
+/// # Formula
+///
+/// net advances = advances - declines
+///
+/// McClellan Oscillator = EMA(net advances, _fast_period_) - EMA(net advances, _slow_period_)
+///
+/// # Parameters
+///
+/// * _fast_period_ - period of the fast EMA (integer greater than 0). Default is 19.
+/// * _slow_period_ - period of the slow EMA (integer greater than 0). Default is 39.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::breadth::{BreadthItem, McClellanOscillator};
+/// use ta::Next;
+///
+/// let mut mco = McClellanOscillator::new(2, 3).unwrap();
+///
+/// assert_eq!(mco.next(&BreadthItem::new(300.0, 200.0).unwrap()), 0.0);
+/// assert!(mco.next(&BreadthItem::new(100.0, 400.0).unwrap()) < 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [McClellan Oscillator, Wikipedia](https://en.wikipedia.org/wiki/McClellan_oscillator)
+///
+#[doc(alias = "MCO")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct McClellanOscillator {
+    fast_ema: Ema,
+    slow_ema: Ema,
+    count: usize,
+    output: Option<f64>,
+}
+
+impl McClellanOscillator {
+    pub fn new(fast_period: usize, slow_period: usize) -> Result<Self> {
+        Ok(Self {
+            fast_ema: Ema::new(fast_period)?,
+            slow_ema: Ema::new(slow_period)?,
+            count: 0,
+            output: None,
+        })
+    }
+}
+
+impl Period for McClellanOscillator {
+    fn period(&self) -> usize {
+        self.fast_ema.period().max(self.slow_ema.period())
+    }
+}
+
+impl IsReady for McClellanOscillator {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
+}
+
+impl<T: Breadth> Next<&T> for McClellanOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        if self.count < self.period() {
+            self.count += 1;
+        }
+
+        let net_advances = input.advances() - input.declines();
+        let output = self.fast_ema.next(net_advances) - self.slow_ema.next(net_advances);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for McClellanOscillator {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for McClellanOscillator {
+    fn reset(&mut self) {
+        self.fast_ema.reset();
+        self.slow_ema.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for McClellanOscillator {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for McClellanOscillator {
+    fn default() -> Self {
+        Self::new(19, 39).unwrap()
+    }
+}
+
+impl fmt::Display for McClellanOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "MCO({}, {})",
+            self.fast_ema.period(),
+            self.slow_ema.period()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::breadth::BreadthItem;
+    use crate::test_helper::*;
+
+    fn item(advances: f64, declines: f64) -> BreadthItem {
+        BreadthItem::new(advances, declines).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(McClellanOscillator::new(0, 39).is_err());
+        assert!(McClellanOscillator::new(19, 0).is_err());
+        assert!(McClellanOscillator::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut mco = McClellanOscillator::new(2, 3).unwrap();
+
+        assert_eq!(mco.next(&item(10.0, 5.0)), 0.0);
+        assert_eq!(round(mco.next(&item(7.0, 7.0))), -0.833);
+        assert_eq!(round(mco.next(&item(2.0, 9.0))), -1.861);
+        assert_eq!(round(mco.next(&item(6.0, 3.0))), 0.255);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut mco = McClellanOscillator::new(2, 3).unwrap();
+
+        mco.next(&item(10.0, 5.0));
+        mco.next(&item(2.0, 9.0));
+
+        mco.reset();
+        assert_eq!(mco.next(&item(2.0, 9.0)), 0.0);
+    }
+
+    #[test]
+    fn test_period() {
+        let mo = McClellanOscillator::new(19, 39).unwrap();
+        assert_eq!(mo.period(), 39);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(
+            &mut McClellanOscillator::new(2, 3).unwrap(),
+            &item(2.0, 1.0),
+            3,
+        );
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(
+            &mut McClellanOscillator::default(),
+            &[item(300.0, 200.0), item(150.0, 350.0)],
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        McClellanOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let mco = McClellanOscillator::default();
+        assert_eq!(format!("{}", mco), "MCO(19, 39)");
+    }
+}
@@ -0,0 +1,32 @@
+//! Market breadth indicators.
+//!
+//! Breadth indicators measure the participation in a market move: instead of the prices of a
+//! single instrument, they consume the number of advancing and declining issues of an index or
+//! exchange for every period, provided by any type implementing the
+//! [Breadth](../../trait.Breadth.html) trait, e.g. [BreadthItem](struct.BreadthItem.html).
+//! Volume based indicators additionally consume the volume of the advancing and declining
+//! issues through the [BreadthVolume](../../trait.BreadthVolume.html) trait.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::breadth::{AdvanceDeclineLine, BreadthItem};
+//! use ta::Next;
+//!
+//! let mut adl = AdvanceDeclineLine::new();
+//!
+//! assert_eq!(adl.next(&BreadthItem::new(1800.0, 1200.0).unwrap()), 600.0);
+//! assert_eq!(adl.next(&BreadthItem::new(1000.0, 2000.0).unwrap()), -400.0);
+//! ```
+
+mod breadth_item;
+pub use self::breadth_item::BreadthItem;
+
+mod advance_decline_line;
+pub use self::advance_decline_line::AdvanceDeclineLine;
+
+mod mcclellan_oscillator;
+pub use self::mcclellan_oscillator::McClellanOscillator;
+
+mod arms_index;
+pub use self::arms_index::ArmsIndex;
@@ -1,11 +1,18 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::errors::Result;
+use crate::errors::{Result, TaError};
+use crate::helpers::fmt_params;
 use crate::indicators::{AverageTrueRange, Maximum, Minimum};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::registry::Params;
+use crate::{
+    Close, Current, High, IsReady, Low, MemoryFootprint, Next, ParamValue, Parameters, Period,
+    Reset,
+};
 
 /// Chandelier Exit (CE).
 ///
@@ -22,7 +29,11 @@ use crate::{Close, High, Low, Next, Period, Reset};
 /// # Parameters
 ///
 /// * _period_ - number of periods (integer greater than 0). Default is 22.
-/// * _multipler_ - ATR factor. Default is 3.
+/// * _multipler_ - ATR factor (number greater than 0). Default is 3.
+///
+/// A single `f64` input is taken as a bar without a range: the extremes are the ones of the
+/// inputs, and the ATR falls back to the absolute changes between them, like the
+/// [AverageTrueRange](struct.AverageTrueRange.html) does.
 ///
 /// # Example
 ///
@@ -58,15 +69,24 @@ pub struct ChandelierExit {
     min: Minimum,
     max: Maximum,
     multiplier: f64,
+    output: Option<ChandelierExitOutput>,
 }
 
 impl ChandelierExit {
     pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        if multiplier.is_nan() || multiplier <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "multiplier",
+                multiplier,
+                "must be greater than 0",
+            ));
+        }
         Ok(Self {
             atr: AverageTrueRange::new(period)?,
             min: Minimum::new(period)?,
             max: Maximum::new(period)?,
             multiplier,
+            output: None,
         })
     }
 
@@ -75,7 +95,8 @@ impl ChandelierExit {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ChandelierExitOutput {
     pub long: f64,
     pub short: f64,
@@ -93,6 +114,29 @@ impl Period for ChandelierExit {
     }
 }
 
+impl IsReady for ChandelierExit {
+    fn bars_until_ready(&self) -> usize {
+        self.atr.bars_until_ready()
+    }
+}
+
+impl Next<f64> for ChandelierExit {
+    type Output = ChandelierExitOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let atr = self.atr.next(input) * self.multiplier;
+        let min = self.min.next(input);
+        let max = self.max.next(input);
+
+        let output = ChandelierExitOutput {
+            long: max - atr,
+            short: min + atr,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
 impl<T: Low + High + Close> Next<&T> for ChandelierExit {
     type Output = ChandelierExitOutput;
 
@@ -101,10 +145,20 @@ impl<T: Low + High + Close> Next<&T> for ChandelierExit {
         let min = self.min.next(input);
         let max = self.max.next(input);
 
-        ChandelierExitOutput {
+        let output = ChandelierExitOutput {
             long: max - atr,
             short: min + atr,
-        }
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for ChandelierExit {
+    type Output = ChandelierExitOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
@@ -113,6 +167,30 @@ impl Reset for ChandelierExit {
         self.atr.reset();
         self.min.reset();
         self.max.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for ChandelierExit {
+    fn heap_size(&self) -> usize {
+        self.min.heap_size() + self.max.heap_size()
+    }
+}
+
+impl Parameters for ChandelierExit {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![
+            ("period", ParamValue::Period(self.atr.period())),
+            ("multiplier", ParamValue::Float(self.multiplier)),
+        ]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period", "multiplier"])?;
+        Self::new(
+            params.period("period", 22)?,
+            params.float("multiplier", 3.0)?,
+        )
     }
 }
 
@@ -124,7 +202,7 @@ impl Default for ChandelierExit {
 
 impl fmt::Display for ChandelierExit {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "CE({}, {})", self.atr.period(), self.multiplier)
+        fmt_params(f, "CE", &self.params())
     }
 }
 
@@ -145,6 +223,9 @@ mod tests {
     #[test]
     fn test_new() {
         assert!(Ce::new(0, 0.0).is_err());
+        assert!(Ce::new(2, 0.0).is_err());
+        assert!(Ce::new(2, -2.0).is_err());
+        assert!(Ce::new(2, f64::NAN).is_err());
         assert!(Ce::new(1, 1.0).is_ok());
         assert!(Ce::new(22, 3.0).is_ok());
     }
@@ -172,6 +253,14 @@ mod tests {
         assert_eq!(round(ce.next(&bar6).into()), (2.92, 7.08));
     }
 
+    #[test]
+    fn test_next_f64() {
+        let mut ce = Ce::new(2, 2.0).unwrap();
+        assert_eq!(round(ce.next(10.0).into()), (10.0, 10.0));
+        // ATR of the changes 0 and 3 is 2, extremes 10 and 13
+        assert_eq!(round(ce.next(13.0).into()), (9.0, 14.0));
+    }
+
     #[test]
     fn test_reset() {
         let mut ce = Ce::new(5, 2.0).unwrap();
@@ -188,6 +277,21 @@ mod tests {
         assert_eq!(round(ce.next(&bar2).into()), (1.33, 4.67));
     }
 
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(2).low(1).close(1.5);
+        assert_warm_up(&mut ChandelierExit::new(3, 2.0).unwrap(), &bar, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut ChandelierExit::default(), &bars);
+    }
+
     #[test]
     fn test_default() {
         Ce::default();
@@ -1,11 +1,18 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::errors::Result;
+use crate::helpers::fmt_params;
 use crate::indicators::{MeanAbsoluteDeviation, SimpleMovingAverage};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::registry::Params;
+use crate::{
+    Close, Current, High, IsReady, Low, MemoryFootprint, Next, ParamValue, Parameters, Period,
+    Reset,
+};
 
 /// Commodity Channel Index (CCI)
 ///
@@ -19,6 +26,9 @@ use crate::{Close, High, Low, Next, Period, Reset};
 ///
 /// CCI(_period_) = (TP - SMA(_period_) of TP) / (MAD(_period_) * 0.015)
 ///
+/// A single `f64` input, e.g. an index value or a spread, is taken as the typical price, so
+/// the mean absolute deviation is the one of the inputs.
+///
 /// # Parameters
 ///
 /// * _period_ - number of periods (integer greater than 0). Default is 20.
@@ -33,6 +43,7 @@ use crate::{Close, High, Low, Next, Period, Reset};
 pub struct CommodityChannelIndex {
     sma: SimpleMovingAverage,
     mad: MeanAbsoluteDeviation,
+    output: Option<f64>,
 }
 
 impl CommodityChannelIndex {
@@ -40,6 +51,7 @@ impl CommodityChannelIndex {
         Ok(Self {
             sma: SimpleMovingAverage::new(period)?,
             mad: MeanAbsoluteDeviation::new(period)?,
+            output: None,
         })
     }
 }
@@ -50,6 +62,29 @@ impl Period for CommodityChannelIndex {
     }
 }
 
+impl IsReady for CommodityChannelIndex {
+    fn bars_until_ready(&self) -> usize {
+        self.sma.bars_until_ready()
+    }
+}
+
+impl Next<f64> for CommodityChannelIndex {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let sma = self.sma.next(input);
+        let mad = self.mad.next(input);
+
+        let output = if mad == 0.0 {
+            0.0
+        } else {
+            (input - sma) / (mad * 0.015)
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
 impl<T: Close + High + Low> Next<&T> for CommodityChannelIndex {
     type Output = f64;
 
@@ -58,11 +93,21 @@ impl<T: Close + High + Low> Next<&T> for CommodityChannelIndex {
         let sma = self.sma.next(tp);
         let mad = self.mad.next(input);
 
-        if mad == 0.0 {
-            return 0.0;
-        }
+        let output = if mad == 0.0 {
+            0.0
+        } else {
+            (tp - sma) / (mad * 0.015)
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for CommodityChannelIndex {
+    type Output = f64;
 
-        (tp - sma) / (mad * 0.015)
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
@@ -70,6 +115,24 @@ impl Reset for CommodityChannelIndex {
     fn reset(&mut self) {
         self.sma.reset();
         self.mad.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for CommodityChannelIndex {
+    fn heap_size(&self) -> usize {
+        self.sma.heap_size() + self.mad.heap_size()
+    }
+}
+
+impl Parameters for CommodityChannelIndex {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.sma.period()))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::new(params.period("period", 20)?)
     }
 }
 
@@ -81,7 +144,7 @@ impl Default for CommodityChannelIndex {
 
 impl fmt::Display for CommodityChannelIndex {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "CCI({})", self.sma.period())
+        fmt_params(f, "CCI", &self.params())
     }
 }
 
@@ -119,6 +182,22 @@ mod tests {
         assert_eq!(round(cci.next(&bar6)), -126.126);
     }
 
+    #[test]
+    fn test_next_f64() {
+        let mut cci = CommodityChannelIndex::new(3).unwrap();
+        assert_eq!(cci.next(2.0), 0.0);
+        // mean 3, mean absolute deviation 1
+        assert_eq!(round(cci.next(4.0)), 66.667);
+        assert_eq!(round(cci.next(6.0)), 100.0);
+
+        // the same as bars without a range
+        let mut bars = CommodityChannelIndex::new(3).unwrap();
+        for &price in &[2.0, 4.0, 6.0] {
+            bars.next(&Bar::new().high(price).low(price).close(price));
+        }
+        assert_eq!(bars.current(), cci.current());
+    }
+
     #[test]
     fn test_reset() {
         let mut cci = CommodityChannelIndex::new(5).unwrap();
@@ -135,6 +214,21 @@ mod tests {
         assert_eq!(round(cci.next(&bar2)), 66.667);
     }
 
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(2).low(1).close(1.5);
+        assert_warm_up(&mut CommodityChannelIndex::new(3).unwrap(), &bar, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut CommodityChannelIndex::default(), &bars);
+    }
+
     #[test]
     fn test_default() {
         CommodityChannelIndex::default();
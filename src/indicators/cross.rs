@@ -0,0 +1,365 @@
+use core::fmt;
+
+use crate::{Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Direction, in which the first value crossed the second one.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrossEvent {
+    /// The first value moved from below the second one to above it (crossover).
+    Above,
+    /// The first value moved from above the second one to below it (crossunder).
+    Below,
+    #[default]
+    None,
+}
+
+/// Crossovers and crossunders of two series.
+///
+/// Takes a pair `(a, b)` of values per bar and reports, when _a_ crosses _b_. A cross needs
+/// _a_ strictly on one side of _b_ before and strictly on the other side now. Bars with equal
+/// values keep the side, on which _a_ was before, so touching the other value and turning
+/// back is not a cross, while moving through it over several bars is reported once, on the
+/// bar where _a_ leaves the touch on the other side. Pairs containing NaN are ignored.
+///
+/// The first bar only sets the side, so it never reports a cross. To cross the outputs of two
+/// indicators, use [Crossover](struct.Crossover.html).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{Cross, CrossEvent};
+/// use ta::Next;
+///
+/// let mut cross = Cross::new();
+/// assert_eq!(cross.next((9.0, 10.0)), CrossEvent::None);
+/// assert_eq!(cross.next((10.0, 10.0)), CrossEvent::None);
+/// assert_eq!(cross.next((11.0, 10.0)), CrossEvent::Above);
+/// assert_eq!(cross.next((9.5, 10.0)), CrossEvent::Below);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Cross {
+    side: Option<f64>,
+    output: Option<CrossEvent>,
+}
+
+impl Cross {
+    pub fn new() -> Self {
+        Self {
+            side: None,
+            output: None,
+        }
+    }
+}
+
+impl IsReady for Cross {
+    fn bars_until_ready(&self) -> usize {
+        if self.side.is_some() {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl Next<(f64, f64)> for Cross {
+    type Output = CrossEvent;
+
+    fn next(&mut self, (a, b): (f64, f64)) -> Self::Output {
+        let diff = a - b;
+        let output = match self.side {
+            _ if diff.is_nan() => CrossEvent::None,
+            Some(side) if side < 0.0 && diff > 0.0 => CrossEvent::Above,
+            Some(side) if side > 0.0 && diff < 0.0 => CrossEvent::Below,
+            _ => CrossEvent::None,
+        };
+        // an equal pair keeps the side, unless there is none yet
+        if !diff.is_nan() && (diff != 0.0 || self.side.is_none()) {
+            self.side = Some(diff);
+        }
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for Cross {
+    type Output = CrossEvent;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for Cross {
+    fn reset(&mut self) {
+        self.side = None;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for Cross {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for Cross {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Cross {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CROSS")
+    }
+}
+
+/// Crossovers and crossunders of the outputs of two indicators.
+///
+/// Both indicators get the same inputs. Their outputs are compared like in
+/// [Cross](struct.Cross.html), once both indicators are [ready](../trait.IsReady.html): the
+/// outputs during the warm-up neither report a cross nor set the side, so a cross is reported
+/// at the earliest on the second bar, after both indicators are ready.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{CrossEvent, Crossover, SimpleMovingAverage as Sma};
+/// use ta::Next;
+///
+/// let mut golden_cross = Crossover::new(Sma::new(2).unwrap(), Sma::new(3).unwrap());
+///
+/// let events: Vec<CrossEvent> = [10.0, 9.0, 8.0, 9.0, 11.0]
+///     .iter()
+///     .map(|&close| golden_cross.next(close))
+///     .collect();
+/// assert_eq!(events[4], CrossEvent::Above);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Crossover<I, J> {
+    first: I,
+    second: J,
+    cross: Cross,
+}
+
+impl<I, J> Crossover<I, J> {
+    pub fn new(first: I, second: J) -> Self {
+        Self {
+            first,
+            second,
+            cross: Cross::new(),
+        }
+    }
+
+    pub fn first(&self) -> &I {
+        &self.first
+    }
+
+    pub fn second(&self) -> &J {
+        &self.second
+    }
+}
+
+impl<I: Period, J: Period> Period for Crossover<I, J> {
+    fn period(&self) -> usize {
+        self.first.period().max(self.second.period())
+    }
+}
+
+impl<I: IsReady, J: IsReady> IsReady for Crossover<I, J> {
+    fn bars_until_ready(&self) -> usize {
+        self.first
+            .bars_until_ready()
+            .max(self.second.bars_until_ready())
+            .max(self.cross.bars_until_ready())
+    }
+}
+
+impl<I, J, T> Next<T> for Crossover<I, J>
+where
+    I: Next<T, Output = f64> + IsReady,
+    J: Next<T, Output = f64> + IsReady,
+    T: Clone,
+{
+    type Output = CrossEvent;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let a = self.first.next(input.clone());
+        let b = self.second.next(input);
+        if self.first.is_ready() && self.second.is_ready() {
+            self.cross.next((a, b))
+        } else {
+            CrossEvent::None
+        }
+    }
+}
+
+impl<I, J> Current for Crossover<I, J> {
+    type Output = CrossEvent;
+
+    /// `None` during the warm-up of the indicators.
+    fn current(&self) -> Option<Self::Output> {
+        self.cross.current()
+    }
+}
+
+impl<I: Reset, J: Reset> Reset for Crossover<I, J> {
+    fn reset(&mut self) {
+        self.first.reset();
+        self.second.reset();
+        self.cross.reset();
+    }
+}
+
+impl<I: MemoryFootprint, J: MemoryFootprint> MemoryFootprint for Crossover<I, J> {
+    fn heap_size(&self) -> usize {
+        self.first.heap_size() + self.second.heap_size()
+    }
+}
+
+impl<I: Default, J: Default> Default for Crossover<I, J> {
+    fn default() -> Self {
+        Self::new(I::default(), J::default())
+    }
+}
+
+impl<I: fmt::Display, J: fmt::Display> fmt::Display for Crossover<I, J> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CROSS({}, {})", self.first, self.second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage as Sma;
+    use crate::test_helper::*;
+    use CrossEvent::*;
+
+    fn crosses(pairs: &[(f64, f64)]) -> Vec<CrossEvent> {
+        let mut cross = Cross::new();
+        pairs.iter().map(|&pair| cross.next(pair)).collect()
+    }
+
+    #[test]
+    fn test_next() {
+        assert_eq!(
+            crosses(&[(1.0, 2.0), (3.0, 2.0), (4.0, 2.0), (1.0, 2.0), (0.0, 2.0)]),
+            vec![None, Above, None, Below, None]
+        );
+    }
+
+    #[test]
+    fn test_equal_values() {
+        // touching and turning back is no cross
+        assert_eq!(
+            crosses(&[(1.0, 2.0), (2.0, 2.0), (1.0, 2.0)]),
+            vec![None, None, None]
+        );
+        // moving through over several bars is one cross, when leaving the touch
+        assert_eq!(
+            crosses(&[(1.0, 2.0), (2.0, 2.0), (2.0, 2.0), (3.0, 2.0), (2.0, 2.0)]),
+            vec![None, None, None, Above, None]
+        );
+        // starting equal sets no side, the first move away is no cross
+        assert_eq!(
+            crosses(&[(2.0, 2.0), (3.0, 2.0), (1.0, 2.0)]),
+            vec![None, None, Below]
+        );
+    }
+
+    #[test]
+    fn test_nan() {
+        assert_eq!(
+            crosses(&[(1.0, 2.0), (f64::NAN, 2.0), (3.0, 2.0), (1.0, f64::NAN)]),
+            vec![None, None, Above, None]
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cross = Cross::new();
+        cross.next((1.0, 2.0));
+
+        cross.reset();
+        assert_eq!(cross.next((3.0, 2.0)), None);
+        assert_eq!(cross.next((1.0, 2.0)), Below);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut Cross::new(), (1.0, 2.0), 1);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut Cross::new(), [(1.0, 2.0), (3.0, 2.0), (2.5, 2.0)]);
+    }
+
+    #[test]
+    fn test_crossover() {
+        let mut crossover = Crossover::new(Sma::new(1).unwrap(), Sma::new(3).unwrap());
+        let closes = [5.0, 1.0, 1.0, 1.0, 5.0, 5.0, 0.0];
+        let events: Vec<CrossEvent> = closes.iter().map(|&c| crossover.next(c)).collect();
+
+        // the SMA(3) is ready on the third bar, where the close is below it
+        assert_eq!(events, vec![None, None, None, None, Above, None, Below]);
+    }
+
+    #[test]
+    fn test_crossover_warm_up() {
+        // the close is below the SMA(3) during its warm-up, but that sets no side
+        let mut crossover = Crossover::new(Sma::new(1).unwrap(), Sma::new(3).unwrap());
+        assert_eq!(crossover.next(9.0), None);
+        assert_eq!(crossover.next(1.0), None);
+        assert_eq!(crossover.current(), Option::None);
+        assert_eq!(crossover.next(20.0), None);
+        assert_eq!(crossover.next(20.0), None);
+    }
+
+    #[test]
+    fn test_crossover_bars() {
+        let bar = |close: f64| Bar::new().close(close);
+        let mut crossover = Crossover::new(Sma::new(1).unwrap(), Sma::new(2).unwrap());
+
+        crossover.next(&bar(2.0));
+        assert_eq!(crossover.next(&bar(1.0)), None);
+        assert_eq!(crossover.next(&bar(3.0)), Above);
+    }
+
+    #[test]
+    fn test_crossover_reset() {
+        let mut crossover = Crossover::new(Sma::new(1).unwrap(), Sma::new(2).unwrap());
+        crossover.next(1.0);
+        crossover.next(2.0);
+
+        crossover.reset();
+        assert_eq!(crossover.current(), Option::None);
+        assert_eq!(crossover.next(5.0), None);
+    }
+
+    #[test]
+    fn test_crossover_is_ready() {
+        let mut crossover = Crossover::new(Sma::new(2).unwrap(), Sma::new(4).unwrap());
+        assert_eq!(crossover.period(), 4);
+        assert_warm_up(&mut crossover, 1.0, 4);
+    }
+
+    #[test]
+    fn test_default() {
+        Cross::default();
+        Crossover::<Sma, Sma>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Cross::new()), "CROSS");
+        let crossover = Crossover::new(Sma::new(2).unwrap(), Sma::new(4).unwrap());
+        assert_eq!(format!("{}", crossover), "CROSS(SMA(2), SMA(4))");
+    }
+}
@@ -0,0 +1,203 @@
+use core::fmt;
+
+use crate::helpers::Accumulator;
+use crate::{BuyVolume, Current, IsReady, MemoryFootprint, Next, Reset, SellVolume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Cumulative Delta.
+///
+/// An order flow indicator. The delta of a bar is the volume bought at the ask minus the
+/// volume sold at the bid, i.e. the net aggression of the market participants. The cumulative
+/// delta sums it up since the first bar. A price rising while the cumulative delta falls shows
+/// that the rally is not driven by aggressive buyers.
+///
+/// Needs inputs with order flow data implementing [BuyVolume](../trait.BuyVolume.html) and
+/// [SellVolume](../trait.SellVolume.html). With `Next<f64>` the input is the delta of a bar.
+/// See [RollingDelta](struct.RollingDelta.html) for the delta over a window.
+///
+/// # Formula
+///
+/// delta = buy volume - sell volume
+///
+/// CD = previous CD + delta
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::CumulativeDelta;
+/// use ta::{BuyVolume, Next, SellVolume};
+///
+/// struct Footprint {
+///     buy: f64,
+///     sell: f64,
+/// }
+///
+/// impl BuyVolume for Footprint {
+///     fn buy_volume(&self) -> f64 {
+///         self.buy
+///     }
+/// }
+///
+/// impl SellVolume for Footprint {
+///     fn sell_volume(&self) -> f64 {
+///         self.sell
+///     }
+/// }
+///
+/// let mut cd = CumulativeDelta::new();
+///
+/// assert_eq!(cd.next(&Footprint { buy: 700.0, sell: 300.0 }), 400.0);
+/// assert_eq!(cd.next(&Footprint { buy: 200.0, sell: 500.0 }), 100.0);
+/// ```
+#[doc(alias = "CVD")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct CumulativeDelta {
+    value: Accumulator,
+    output: Option<f64>,
+}
+
+impl CumulativeDelta {
+    pub fn new() -> Self {
+        Self {
+            value: Accumulator::new(false),
+            output: None,
+        }
+    }
+
+    /// Same as `new`, with compensated summation of the deltas, so the line does not drift
+    /// after millions of bars.
+    pub fn compensated() -> Self {
+        Self {
+            value: Accumulator::new(true),
+            output: None,
+        }
+    }
+}
+
+impl IsReady for CumulativeDelta {
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
+}
+
+impl Next<f64> for CumulativeDelta {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.value.add(input);
+        let value = self.value.value();
+        self.output = Some(value);
+        value
+    }
+}
+
+impl<T: BuyVolume + SellVolume> Next<&T> for CumulativeDelta {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.buy_volume() - input.sell_volume())
+    }
+}
+
+impl Current for CumulativeDelta {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for CumulativeDelta {
+    fn reset(&mut self) {
+        self.value.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for CumulativeDelta {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for CumulativeDelta {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for CumulativeDelta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CD")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(CumulativeDelta);
+
+    fn bar(buy: f64, sell: f64) -> Bar {
+        Bar::new().buy_volume(buy).sell_volume(sell)
+    }
+
+    #[test]
+    fn test_next() {
+        let mut cd = CumulativeDelta::new();
+
+        assert_eq!(cd.next(&bar(10.0, 5.0)), 5.0);
+        assert_eq!(cd.next(&bar(7.0, 7.0)), 5.0);
+        assert_eq!(cd.next(&bar(2.0, 9.0)), -2.0);
+        assert_eq!(cd.next(3.0), 1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut cd = CumulativeDelta::new();
+
+        cd.next(&bar(10.0, 5.0));
+        cd.next(&bar(8.0, 5.0));
+
+        cd.reset();
+        assert_eq!(cd.next(&bar(1.0, 4.0)), -3.0);
+    }
+
+    #[test]
+    fn test_compensated() {
+        let mut cd = CumulativeDelta::new();
+        let mut compensated = CumulativeDelta::compensated();
+        for delta in [1e16, 1.0, -1e16] {
+            cd.next(delta);
+            compensated.next(delta);
+        }
+        assert_eq!(cd.current(), Some(0.0));
+        assert_eq!(compensated.current(), Some(1.0));
+
+        compensated.reset();
+        assert_eq!(compensated.next(0.1), 0.1);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert!(CumulativeDelta::new().is_ready());
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut CumulativeDelta::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        CumulativeDelta::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", CumulativeDelta::new()), "CD");
+    }
+}
@@ -0,0 +1,223 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::SimpleMovingAverage as Sma;
+use crate::{Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// DeMarker indicator (DeM).
+///
+/// An oscillator by Tom DeMark, which compares the highs and lows of consecutive bars to
+/// measure the demand for an instrument. It ranges from 0 to 1: values above 0.7 are usually
+/// considered overbought and values below 0.3 oversold.
+///
+/// # Formula
+///
+/// DeMax = max(high - previous high, 0)
+///
+/// DeMin = max(previous low - low, 0)
+///
+/// DeM = SMA(DeMax) / (SMA(DeMax) + SMA(DeMin))
+///
+/// Both are 0 for the first bar. DeM is 0.5, when there were no moves in the window.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::DeMarker;
+/// use ta::Next;
+///
+/// let mut dem = DeMarker::new(2).unwrap();
+/// assert_eq!(dem.next(10.0), 0.5);
+/// assert_eq!(dem.next(12.0), 1.0);
+/// assert_eq!(dem.next(10.0), 0.5);
+/// ```
+///
+/// # Links
+///
+/// * [DeMarker Indicator, Investopedia](https://www.investopedia.com/terms/d/demarkerindicator.asp)
+///
+#[doc(alias = "DeM")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct DeMarker {
+    period: usize,
+    prev: Option<(f64, f64)>,
+    de_max: Sma,
+    de_min: Sma,
+    output: Option<f64>,
+}
+
+impl DeMarker {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            period,
+            prev: None,
+            de_max: Sma::new(period)?,
+            de_min: Sma::new(period)?,
+            output: None,
+        })
+    }
+
+    fn update(&mut self, high: f64, low: f64) -> f64 {
+        let (de_max, de_min) = match self.prev {
+            Some((prev_high, prev_low)) => ((high - prev_high).max(0.0), (prev_low - low).max(0.0)),
+            None => (0.0, 0.0),
+        };
+        self.prev = Some((high, low));
+
+        let de_max = self.de_max.next(de_max);
+        let de_min = self.de_min.next(de_min);
+        let output = if de_max + de_min > 0.0 {
+            de_max / (de_max + de_min)
+        } else {
+            0.5
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Period for DeMarker {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for DeMarker {
+    fn bars_until_ready(&self) -> usize {
+        self.de_max.bars_until_ready()
+    }
+}
+
+impl Next<f64> for DeMarker {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.update(input, input)
+    }
+}
+
+impl<T: High + Low> Next<&T> for DeMarker {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.update(input.high(), input.low())
+    }
+}
+
+impl Current for DeMarker {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for DeMarker {
+    fn reset(&mut self) {
+        self.prev = None;
+        self.de_max.reset();
+        self.de_min.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for DeMarker {
+    fn heap_size(&self) -> usize {
+        self.de_max.heap_size() + self.de_min.heap_size()
+    }
+}
+
+impl Default for DeMarker {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for DeMarker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DEM({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(DeMarker);
+
+    fn bar(high: f64, low: f64) -> Bar {
+        Bar::new().high(high).low(low)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(DeMarker::new(0).is_err());
+        assert!(DeMarker::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut dem = DeMarker::new(2).unwrap();
+
+        assert_eq!(dem.next(&bar(10.0, 8.0)), 0.5);
+        // DeMax = 2, DeMin = 0
+        assert_eq!(dem.next(&bar(12.0, 9.0)), 1.0);
+        // DeMax = 0, DeMin = 2
+        assert_eq!(dem.next(&bar(11.0, 7.0)), 0.5);
+        // DeMax = 1, DeMin = 1
+        assert_eq!(dem.next(&bar(12.0, 6.0)), 0.25);
+        // an outside bar counts in both directions
+        assert_eq!(round(dem.next(&bar(15.0, 5.0))), 0.667);
+    }
+
+    #[test]
+    fn test_no_moves() {
+        let mut dem = DeMarker::new(2).unwrap();
+
+        dem.next(&bar(10.0, 8.0));
+        dem.next(&bar(11.0, 8.0));
+        assert_eq!(dem.next(&bar(11.0, 8.0)), 1.0);
+        assert_eq!(dem.next(&bar(11.0, 8.0)), 0.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut dem = DeMarker::new(2).unwrap();
+
+        dem.next(&bar(10.0, 8.0));
+        dem.next(&bar(12.0, 9.0));
+
+        dem.reset();
+        assert_eq!(dem.next(&bar(20.0, 18.0)), 0.5);
+        assert_eq!(dem.next(&bar(19.0, 17.0)), 0.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut DeMarker::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut DeMarker::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        DeMarker::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let dem = DeMarker::new(7).unwrap();
+        assert_eq!(format!("{}", dem), "DEM(7)");
+    }
+}
@@ -0,0 +1,479 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{Maximum, Minimum, PivotHighLow};
+use crate::{Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Divergence detector.
+///
+/// Consumes pairs of _(price, oscillator)_ values (e.g. close and [RSI](struct.RelativeStrengthIndex.html)),
+/// tracks swing pivots in both series and reports a divergence, when two consecutive swings of
+/// the price and the oscillator disagree.
+///
+/// Price swings are found with [PivotHighLow](struct.PivotHighLow.html), so a swing is confirmed
+/// only _right_ bars later. The oscillator swing paired with it is the lowest (highest)
+/// oscillator value in the same window, so the oscillator may bottom out a few bars apart from
+/// the price.
+///
+/// When a new swing is confirmed and the previous swing of the same side is not more than
+/// _max_range_ bars away, the swings are compared:
+///
+/// | Kind            | Price       | Oscillator  |
+/// |-----------------|-------------|-------------|
+/// | Regular bullish | lower low   | higher low  |
+/// | Hidden bullish  | higher low  | lower low   |
+/// | Regular bearish | higher high | lower high  |
+/// | Hidden bearish  | lower high  | higher high |
+///
+/// # Parameters
+///
+/// * _left_ - number of bars before a swing (integer greater than 0). Default is 5.
+/// * _right_ - number of bars after a swing (integer greater than 0). Default is 5.
+/// * _max_range_ - maximum distance between two compared swings in bars (integer greater than 0). Default is 60.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{DivergenceDetector, DivergenceKind};
+/// use ta::Next;
+///
+/// let mut detector = DivergenceDetector::new(1, 1, 10).unwrap();
+///
+/// // price makes a lower low, while the oscillator makes a higher low
+/// let data = [(10.0, 40.0), (8.0, 20.0), (10.0, 40.0), (7.0, 30.0), (9.0, 45.0)];
+/// let events: Vec<_> = data.iter().filter_map(|&pair| detector.next(pair)).collect();
+///
+/// assert_eq!(events.len(), 1);
+/// assert_eq!(events[0].kind, DivergenceKind::RegularBullish);
+/// assert_eq!(events[0].span, 2);
+/// ```
+///
+/// # Links
+///
+/// * [Divergence, Investopedia](https://www.investopedia.com/terms/d/divergence.asp)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct DivergenceDetector {
+    max_range: usize,
+    bar: usize,
+    pivots: PivotHighLow,
+    oscillator_min: Minimum,
+    oscillator_max: Maximum,
+    last_low: Option<Swing>,
+    last_high: Option<Swing>,
+    output: Option<Option<Divergence>>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DivergenceKind {
+    RegularBullish,
+    HiddenBullish,
+    RegularBearish,
+    HiddenBearish,
+}
+
+impl DivergenceKind {
+    pub fn is_bullish(&self) -> bool {
+        matches!(
+            self,
+            DivergenceKind::RegularBullish | DivergenceKind::HiddenBullish
+        )
+    }
+
+    pub fn is_bearish(&self) -> bool {
+        !self.is_bullish()
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    pub kind: DivergenceKind,
+    /// Number of bars from the latest swing to the current bar (equal to _right_).
+    pub bars_ago: usize,
+    /// Number of bars between the two compared swings.
+    pub span: usize,
+    /// Price at the previous and at the latest swing.
+    pub price: SwingPair,
+    /// Oscillator at the previous and at the latest swing.
+    pub oscillator: SwingPair,
+}
+
+/// Values at the previous and at the latest of two compared swings.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwingPair {
+    pub previous: f64,
+    pub latest: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+struct Swing {
+    bar: usize,
+    price: f64,
+    oscillator: f64,
+}
+
+impl DivergenceDetector {
+    pub fn new(left: usize, right: usize, max_range: usize) -> Result<Self> {
+        if max_range == 0 {
+            return Err(TaError::zero_period("max_range"));
+        }
+        let pivots = PivotHighLow::new(left, right)?;
+        Ok(Self {
+            max_range,
+            bar: 0,
+            oscillator_min: Minimum::new(pivots.period())?,
+            oscillator_max: Maximum::new(pivots.period())?,
+            pivots,
+            last_low: None,
+            last_high: None,
+            output: None,
+        })
+    }
+
+    pub fn left(&self) -> usize {
+        self.pivots.left()
+    }
+
+    pub fn right(&self) -> usize {
+        self.pivots.right()
+    }
+
+    pub fn max_range(&self) -> usize {
+        self.max_range
+    }
+
+    fn compare(&self, previous: Swing, latest: Swing, is_low: bool) -> Option<Divergence> {
+        let span = latest.bar - previous.bar;
+        if span > self.max_range {
+            return None;
+        }
+        let price_up = latest.price > previous.price;
+        let price_down = latest.price < previous.price;
+        let osc_up = latest.oscillator > previous.oscillator;
+        let osc_down = latest.oscillator < previous.oscillator;
+
+        let kind = match (is_low, price_up, price_down, osc_up, osc_down) {
+            (true, _, true, true, _) => DivergenceKind::RegularBullish,
+            (true, true, _, _, true) => DivergenceKind::HiddenBullish,
+            (false, true, _, _, true) => DivergenceKind::RegularBearish,
+            (false, _, true, true, _) => DivergenceKind::HiddenBearish,
+            _ => return None,
+        };
+        Some(Divergence {
+            kind,
+            bars_ago: self.right(),
+            span,
+            price: SwingPair {
+                previous: previous.price,
+                latest: latest.price,
+            },
+            oscillator: SwingPair {
+                previous: previous.oscillator,
+                latest: latest.oscillator,
+            },
+        })
+    }
+}
+
+impl Period for DivergenceDetector {
+    fn period(&self) -> usize {
+        self.pivots.period()
+    }
+}
+
+impl IsReady for DivergenceDetector {
+    fn bars_until_ready(&self) -> usize {
+        self.pivots.bars_until_ready()
+    }
+}
+
+impl Next<(f64, f64)> for DivergenceDetector {
+    type Output = Option<Divergence>;
+
+    fn next(&mut self, (price, oscillator): (f64, f64)) -> Self::Output {
+        self.bar += 1;
+        let pivots = self.pivots.next(price);
+        // the pivot window covers exactly the last `left + right + 1` bars
+        let oscillator_low = self.oscillator_min.next(oscillator);
+        let oscillator_high = self.oscillator_max.next(oscillator);
+
+        let output = if let Some(pivot) = pivots.low {
+            let low = Swing {
+                bar: self.bar - pivot.bars_ago,
+                price: pivot.value,
+                oscillator: oscillator_low,
+            };
+            let divergence = self
+                .last_low
+                .and_then(|previous| self.compare(previous, low, true));
+            self.last_low = Some(low);
+            divergence
+        } else if let Some(pivot) = pivots.high {
+            let high = Swing {
+                bar: self.bar - pivot.bars_ago,
+                price: pivot.value,
+                oscillator: oscillator_high,
+            };
+            let divergence = self
+                .last_high
+                .and_then(|previous| self.compare(previous, high, false));
+            self.last_high = Some(high);
+            divergence
+        } else {
+            None
+        };
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Current for DivergenceDetector {
+    type Output = Option<Divergence>;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl Reset for DivergenceDetector {
+    fn reset(&mut self) {
+        self.bar = 0;
+        self.pivots.reset();
+        self.oscillator_min.reset();
+        self.oscillator_max.reset();
+        self.last_low = None;
+        self.last_high = None;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for DivergenceDetector {
+    fn heap_size(&self) -> usize {
+        self.pivots.heap_size() + self.oscillator_min.heap_size() + self.oscillator_max.heap_size()
+    }
+}
+
+impl Default for DivergenceDetector {
+    fn default() -> Self {
+        Self::new(5, 5, 60).unwrap()
+    }
+}
+
+impl fmt::Display for DivergenceDetector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DIV({}, {}, {})",
+            self.left(),
+            self.right(),
+            self.max_range
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::{assert_current, assert_warm_up};
+
+    fn events(detector: &mut DivergenceDetector, data: &[(f64, f64)]) -> Vec<Divergence> {
+        data.iter()
+            .filter_map(|&pair| detector.next(pair))
+            .collect()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(DivergenceDetector::new(0, 1, 1).is_err());
+        assert!(DivergenceDetector::new(1, 0, 1).is_err());
+        assert!(DivergenceDetector::new(1, 1, 0).is_err());
+        assert!(DivergenceDetector::new(1, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_regular_bullish() {
+        let mut detector = DivergenceDetector::new(2, 1, 20).unwrap();
+        let data = [
+            (12.0, 50.0),
+            (11.0, 40.0),
+            (8.0, 20.0), // swing low
+            (10.0, 35.0),
+            (11.0, 45.0),
+            (9.0, 30.0),
+            (7.0, 28.0), // lower low in price, higher low in the oscillator
+            (9.0, 40.0),
+        ];
+        let found = events(&mut detector, &data);
+
+        assert_eq!(
+            found,
+            vec![Divergence {
+                kind: DivergenceKind::RegularBullish,
+                bars_ago: 1,
+                span: 4,
+                price: SwingPair {
+                    previous: 8.0,
+                    latest: 7.0,
+                },
+                oscillator: SwingPair {
+                    previous: 20.0,
+                    latest: 28.0,
+                },
+            }]
+        );
+        assert!(found[0].kind.is_bullish());
+    }
+
+    #[test]
+    fn test_hidden_bullish() {
+        let mut detector = DivergenceDetector::new(1, 1, 20).unwrap();
+        let data = [
+            (10.0, 40.0),
+            (8.0, 30.0),
+            (10.0, 50.0),
+            (9.0, 25.0),
+            (11.0, 50.0),
+        ];
+        let found = events(&mut detector, &data);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, DivergenceKind::HiddenBullish);
+        assert_eq!((found[0].price.previous, found[0].price.latest), (8.0, 9.0));
+        assert_eq!(
+            (found[0].oscillator.previous, found[0].oscillator.latest),
+            (30.0, 25.0)
+        );
+    }
+
+    #[test]
+    fn test_bearish() {
+        let mut detector = DivergenceDetector::new(1, 1, 20).unwrap();
+        let data = [
+            (10.0, 50.0),
+            (12.0, 70.0),
+            (10.0, 50.0),
+            (13.0, 65.0), // higher high, lower oscillator high
+            (11.0, 50.0),
+            (12.0, 75.0), // lower high, higher oscillator high
+            (10.0, 40.0),
+        ];
+        let found = events(&mut detector, &data);
+
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].kind, DivergenceKind::RegularBearish);
+        assert_eq!(
+            (found[0].price.previous, found[0].price.latest),
+            (12.0, 13.0)
+        );
+        assert_eq!(found[1].kind, DivergenceKind::HiddenBearish);
+        assert_eq!(
+            (found[1].oscillator.previous, found[1].oscillator.latest),
+            (65.0, 75.0)
+        );
+        assert!(found[1].kind.is_bearish());
+    }
+
+    #[test]
+    fn test_oscillator_extreme_near_swing() {
+        // the oscillator bottoms out one bar after the price
+        let mut detector = DivergenceDetector::new(1, 1, 20).unwrap();
+        let data = [
+            (10.0, 40.0),
+            (8.0, 30.0),
+            (10.0, 40.0),
+            (7.0, 38.0),
+            (9.0, 33.0),
+            (10.0, 40.0),
+        ];
+        let found = events(&mut detector, &data);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, DivergenceKind::RegularBullish);
+        assert_eq!(
+            (found[0].oscillator.previous, found[0].oscillator.latest),
+            (30.0, 33.0)
+        );
+    }
+
+    #[test]
+    fn test_max_range() {
+        let mut detector = DivergenceDetector::new(1, 1, 3).unwrap();
+        let data = [
+            (10.0, 40.0),
+            (8.0, 20.0),
+            (10.0, 40.0),
+            (11.0, 40.0),
+            (12.0, 40.0),
+            (7.0, 30.0),
+            (9.0, 45.0),
+        ];
+        assert!(events(&mut detector, &data).is_empty());
+    }
+
+    #[test]
+    fn test_no_divergence() {
+        let mut detector = DivergenceDetector::new(1, 1, 20).unwrap();
+        // price and oscillator agree
+        let data = [
+            (10.0, 40.0),
+            (8.0, 20.0),
+            (10.0, 40.0),
+            (7.0, 15.0),
+            (9.0, 45.0),
+        ];
+        assert!(events(&mut detector, &data).is_empty());
+    }
+
+    #[test]
+    fn test_flat_bottom() {
+        // only the first bar of a flat bottom is a swing
+        let mut detector = DivergenceDetector::new(1, 1, 20).unwrap();
+        let data = [(10.0, 40.0), (8.0, 20.0), (8.0, 25.0), (10.0, 40.0)];
+        assert!(events(&mut detector, &data).is_empty());
+        assert_eq!(detector.last_low.unwrap().bar, 2);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut detector = DivergenceDetector::new(1, 1, 10).unwrap();
+        let data = [(10.0, 40.0), (8.0, 20.0), (10.0, 40.0)];
+        events(&mut detector, &data);
+
+        detector.reset();
+        // the swing low from before the reset is forgotten
+        let data = [(10.0, 40.0), (7.0, 30.0), (9.0, 45.0)];
+        assert!(events(&mut detector, &data).is_empty());
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(
+            &mut DivergenceDetector::new(2, 1, 20).unwrap(),
+            (1.0, 1.0),
+            4,
+        );
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut DivergenceDetector::default(), [(1.0, 2.0), (3.0, 1.0)]);
+    }
+
+    #[test]
+    fn test_default() {
+        DivergenceDetector::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let detector = DivergenceDetector::new(3, 2, 40).unwrap();
+        assert_eq!(format!("{}", detector), "DIV(3, 2, 40)");
+    }
+}
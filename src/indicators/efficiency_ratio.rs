@@ -1,7 +1,14 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::traits::{Close, Next, Period, Reset};
+use crate::helpers::fmt_params;
+use crate::registry::Params;
+use crate::ring_buffer::RingBuffer;
+use crate::traits::{
+    Close, Current, IsReady, MemoryFootprint, Next, ParamValue, Parameters, Period, Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -28,26 +35,23 @@ use serde::{Deserialize, Serialize};
 /// assert_eq!(er.next(18.0), 0.8);
 /// assert_eq!(er.next(19.0), 0.75);
 /// ```
-
 #[doc(alias = "ER")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct EfficiencyRatio {
     period: usize,
-    index: usize,
-    count: usize,
-    deque: Box<[f64]>,
+    window: RingBuffer,
+    output: Option<f64>,
 }
 
 impl EfficiencyRatio {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::zero_period("period")),
             _ => Ok(Self {
                 period,
-                index: 0,
-                count: 0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                window: RingBuffer::new(period),
+                output: None,
             }),
         }
     }
@@ -59,36 +63,29 @@ impl Period for EfficiencyRatio {
     }
 }
 
+impl IsReady for EfficiencyRatio {
+    fn bars_until_ready(&self) -> usize {
+        self.period - self.window.len()
+    }
+}
+
 impl Next<f64> for EfficiencyRatio {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> f64 {
-        let first = if self.count >= self.period {
-            self.deque[self.index]
-        } else {
-            self.count += 1;
-            self.deque[0]
-        };
-        self.deque[self.index] = input;
-
-        self.index = if self.index + 1 < self.period {
-            self.index + 1
-        } else {
-            0
-        };
+        let first = self.window.oldest().unwrap_or(0.0);
+        self.window.push(input);
 
         let mut volatility = 0.0;
         let mut previous = first;
-        for n in &self.deque[self.index..self.count] {
+        for n in self.window.iter() {
             volatility += (previous - n).abs();
-            previous = *n;
-        }
-        for n in &self.deque[0..self.index] {
-            volatility += (previous - n).abs();
-            previous = *n;
+            previous = n;
         }
 
-        (first - input).abs() / volatility
+        let output = (first - input).abs() / volatility;
+        self.output = Some(output);
+        output
     }
 }
 
@@ -100,13 +97,35 @@ impl<T: Close> Next<&T> for EfficiencyRatio {
     }
 }
 
+impl Current for EfficiencyRatio {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
 impl Reset for EfficiencyRatio {
     fn reset(&mut self) {
-        self.index = 0;
-        self.count = 0;
-        for i in 0..self.period {
-            self.deque[i] = 0.0;
-        }
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for EfficiencyRatio {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl Parameters for EfficiencyRatio {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::new(params.period("period", 14)?)
     }
 }
 
@@ -118,7 +137,7 @@ impl Default for EfficiencyRatio {
 
 impl fmt::Display for EfficiencyRatio {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "ER({})", self.period)
+        fmt_params(f, "ER", &self.params())
     }
 }
 
@@ -164,6 +183,16 @@ mod tests {
         assert_eq!(round(er.next(3.0)), 0.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut EfficiencyRatio::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut EfficiencyRatio::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_display() {
         let er = EfficiencyRatio::new(17).unwrap();
@@ -0,0 +1,303 @@
+use core::fmt;
+
+use num_traits::Float;
+
+use crate::errors::Result;
+use crate::indicators::{
+    ExponentialMovingAverage as Ema, MovingAverageConvergenceDivergence as Macd,
+};
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Color of a bar in the Elder Impulse System.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImpulseColor {
+    /// Trend and momentum rise: buying is allowed, shorting is not.
+    Green,
+    /// Trend and momentum fall: shorting is allowed, buying is not.
+    Red,
+    /// Trend and momentum disagree: no restriction.
+    Blue,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElderImpulseOutput<T = f64> {
+    pub color: ImpulseColor,
+    pub ema: T,
+    /// MACD histogram.
+    pub histogram: T,
+}
+
+/// Elder Impulse System.
+///
+/// Developed by Alexander Elder. Every bar is colored by combining the slope of an
+/// [EMA](struct.ExponentialMovingAverage.html), which measures the trend, with the slope of
+/// the [MACD](struct.MovingAverageConvergenceDivergence.html) histogram, which measures the
+/// momentum. A bar is green when both rise, red when both fall and blue otherwise. The system
+/// is meant as a censor: no longs on red bars, no shorts on green bars.
+///
+/// The first bar has no slope and is blue.
+///
+/// # Parameters
+///
+/// * _ema_period_ - period of the EMA (integer greater than 0). Default is 13.
+/// * _fast_period_, _slow_period_, _signal_period_ - periods of the MACD (integers greater
+///   than 0, the fast period less than the slow one). Default is 12, 26, 9.
+///
+/// # Value type
+///
+/// The values are `f64` by default. Any [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html)
+/// type can be used with [with_periods](#method.with_periods), e.g.
+/// `ElderImpulse::<f32>::with_periods(13, 12, 26, 9)`.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{ElderImpulse, ImpulseColor};
+/// use ta::Next;
+///
+/// let mut impulse = ElderImpulse::new(2, 2, 3, 2).unwrap();
+///
+/// assert_eq!(impulse.next(10.0).color, ImpulseColor::Blue);
+/// assert_eq!(impulse.next(11.0).color, ImpulseColor::Green);
+/// assert_eq!(impulse.next(12.0).color, ImpulseColor::Green);
+/// assert_eq!(impulse.next(11.0).color, ImpulseColor::Red);
+/// ```
+///
+/// # Links
+///
+/// * [Elder Impulse System, StockCharts](https://school.stockcharts.com/doku.php?id=chart_analysis:elder_impulse_system)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ElderImpulse<T = f64> {
+    ema: Ema<T>,
+    macd: Macd<T>,
+    prev: Option<(T, T)>,
+    count: usize,
+    output: Option<ElderImpulseOutput<T>>,
+}
+
+impl ElderImpulse {
+    pub fn new(
+        ema_period: usize,
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<Self> {
+        Self::with_periods(ema_period, fast_period, slow_period, signal_period)
+    }
+}
+
+impl<T: Float> ElderImpulse<T> {
+    /// Same as `new`, for any value type.
+    pub fn with_periods(
+        ema_period: usize,
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<Self> {
+        Ok(Self {
+            ema: Ema::with_period(ema_period)?,
+            macd: Macd::with_periods(fast_period, slow_period, signal_period)?,
+            prev: None,
+            count: 0,
+            output: None,
+        })
+    }
+}
+
+impl<T> Period for ElderImpulse<T> {
+    fn period(&self) -> usize {
+        self.ema.period().max(self.macd.period())
+    }
+}
+
+impl<T> IsReady for ElderImpulse<T> {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
+}
+
+impl<T: Float> Next<T> for ElderImpulse<T> {
+    type Output = ElderImpulseOutput<T>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        if self.count < self.period() {
+            self.count += 1;
+        }
+
+        let ema = self.ema.next(input);
+        let histogram = self.macd.next(input).histogram;
+
+        let color = match self.prev {
+            Some((prev_ema, prev_histogram)) if ema > prev_ema && histogram > prev_histogram => {
+                ImpulseColor::Green
+            }
+            Some((prev_ema, prev_histogram)) if ema < prev_ema && histogram < prev_histogram => {
+                ImpulseColor::Red
+            }
+            _ => ImpulseColor::Blue,
+        };
+        self.prev = Some((ema, histogram));
+
+        let output = ElderImpulseOutput {
+            color,
+            ema,
+            histogram,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+// Bars are only accepted by the primitive floats, a generic impl would overlap `Next<T>`.
+macro_rules! impl_close {
+    ($($t:ty),*) => {$(
+        impl<C: Close> Next<&C> for ElderImpulse<$t> {
+            type Output = ElderImpulseOutput<$t>;
+
+            fn next(&mut self, input: &C) -> Self::Output {
+                self.next(input.close() as $t)
+            }
+        }
+    )*};
+}
+
+impl_close!(f32, f64);
+
+impl<T: Copy> Current for ElderImpulse<T> {
+    type Output = ElderImpulseOutput<T>;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<T: Float> Reset for ElderImpulse<T> {
+    fn reset(&mut self) {
+        self.ema.reset();
+        self.macd.reset();
+        self.prev = None;
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl<T> MemoryFootprint for ElderImpulse<T> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for ElderImpulse {
+    fn default() -> Self {
+        Self::new(13, 12, 26, 9).unwrap()
+    }
+}
+
+impl<T: Float> fmt::Display for ElderImpulse<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IMPULSE({}, {})", self.ema.period(), self.macd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(ElderImpulse);
+
+    #[test]
+    fn test_new() {
+        assert!(ElderImpulse::new(0, 12, 26, 9).is_err());
+        assert!(ElderImpulse::new(13, 0, 26, 9).is_err());
+        assert!(ElderImpulse::new(13, 12, 0, 9).is_err());
+        assert!(ElderImpulse::new(13, 12, 26, 0).is_err());
+        assert!(ElderImpulse::new(13, 26, 26, 9).is_err());
+        assert!(ElderImpulse::new(1, 1, 2, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut impulse = ElderImpulse::new(2, 2, 3, 2).unwrap();
+        let mut next = |input: f64| {
+            let out = impulse.next(input);
+            (out.color, round(out.ema), round(out.histogram))
+        };
+
+        assert_eq!(next(10.0), (ImpulseColor::Blue, 10.0, 0.0));
+        assert_eq!(next(11.0), (ImpulseColor::Green, 10.667, 0.056));
+        assert_eq!(next(12.0), (ImpulseColor::Green, 11.556, 0.065));
+        assert_eq!(next(11.0), (ImpulseColor::Red, 11.185, -0.06));
+        assert_eq!(next(10.0), (ImpulseColor::Red, 10.395, -0.096));
+        assert_eq!(next(10.5), (ImpulseColor::Green, 10.465, 0.002));
+        assert_eq!(next(13.0), (ImpulseColor::Green, 12.155, 0.152));
+        // the trend still rises, the momentum falls
+        assert_eq!(next(12.5), (ImpulseColor::Blue, 12.385, 0.005));
+    }
+
+    #[test]
+    fn test_f32() {
+        let mut impulse = ElderImpulse::<f32>::with_periods(2, 2, 3, 2).unwrap();
+        let colors: Vec<ImpulseColor> = [10.0f32, 11.0, 12.0, 11.0]
+            .iter()
+            .map(|&input| impulse.next(input).color)
+            .collect();
+        assert_eq!(
+            colors,
+            [
+                ImpulseColor::Blue,
+                ImpulseColor::Green,
+                ImpulseColor::Green,
+                ImpulseColor::Red
+            ]
+        );
+        assert_eq!(
+            round(impulse.next(&Bar::new().close(10)).ema.into()),
+            10.395
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut impulse = ElderImpulse::new(2, 2, 3, 2).unwrap();
+
+        impulse.next(10.0);
+        impulse.next(11.0);
+
+        impulse.reset();
+        assert_eq!(impulse.next(12.0).color, ImpulseColor::Blue);
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(ElderImpulse::new(13, 12, 26, 9).unwrap().period(), 34);
+        assert_eq!(ElderImpulse::new(50, 12, 26, 9).unwrap().period(), 50);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut ElderImpulse::new(3, 3, 5, 2).unwrap(), 1.0, 6);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut ElderImpulse::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        ElderImpulse::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let impulse = ElderImpulse::default();
+        assert_eq!(format!("{}", impulse), "IMPULSE(13, MACD(12, 26, 9))");
+    }
+}
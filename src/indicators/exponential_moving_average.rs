@@ -1,7 +1,16 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use num_traits::Float;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::helpers::{cast, fmt_params};
+use crate::registry::Params;
+use crate::{
+    Close, Configure, Current, IsReady, MemoryFootprint, Next, NextBatch, ParamValue, Parameters,
+    Period, ProjectNext, Reset, SolveForInput,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -34,6 +43,12 @@ use serde::{Deserialize, Serialize};
 ///
 /// * _period_ - number of periods (integer greater than 0)
 ///
+/// # Value type
+///
+/// The values are `f64` by default. Any [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html)
+/// type can be used with [with_period](#method.with_period), e.g.
+/// `ExponentialMovingAverage::<f32>::with_period(3)`.
+///
 /// # Example
 ///
 /// ```
@@ -51,63 +66,156 @@ use serde::{Deserialize, Serialize};
 ///
 /// * [Exponential moving average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Exponential_moving_average)
 ///
-
 #[doc(alias = "EMA")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct ExponentialMovingAverage {
+pub struct ExponentialMovingAverage<T = f64> {
     period: usize,
-    k: f64,
-    current: f64,
+    k: T,
+    current: T,
     is_new: bool,
+    count: usize,
 }
 
 impl ExponentialMovingAverage {
     pub fn new(period: usize) -> Result<Self> {
+        Self::with_period(period)
+    }
+}
+
+impl<T: Float> ExponentialMovingAverage<T> {
+    /// Same as `new`, for any value type.
+    pub fn with_period(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::zero_period("period")),
             _ => Ok(Self {
                 period,
-                k: 2.0 / (period + 1) as f64,
-                current: 0.0,
+                k: cast::<T, _>(2.0) / cast(period + 1),
+                current: T::zero(),
                 is_new: true,
+                count: 0,
             }),
         }
     }
 }
 
-impl Period for ExponentialMovingAverage {
+impl<T> Period for ExponentialMovingAverage<T> {
     fn period(&self) -> usize {
         self.period
     }
 }
 
-impl Next<f64> for ExponentialMovingAverage {
-    type Output = f64;
+impl<T: Float> Configure for ExponentialMovingAverage<T> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        let ema = Self::with_period(period)?;
+        self.period = period;
+        self.k = ema.k;
+        self.count = self.count.min(period);
+        Ok(())
+    }
+}
+
+impl<T> IsReady for ExponentialMovingAverage<T> {
+    fn bars_until_ready(&self) -> usize {
+        self.period - self.count
+    }
+}
+
+impl<T: Float> Next<T> for ExponentialMovingAverage<T> {
+    type Output = T;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        if self.count < self.period {
+            self.count += 1;
+        }
+
+        self.current = self.project_next(input);
+        self.is_new = false;
+        self.current
+    }
+}
+
+impl<T: Float> NextBatch<T> for ExponentialMovingAverage<T> {}
 
-    fn next(&mut self, input: f64) -> Self::Output {
+impl<T: Float> ProjectNext<T> for ExponentialMovingAverage<T> {
+    type Output = T;
+
+    fn project_next(&self, input: T) -> Self::Output {
         if self.is_new {
-            self.is_new = false;
-            self.current = input;
+            input
         } else {
-            self.current = self.k * input + (1.0 - self.k) * self.current;
+            self.k * input + (T::one() - self.k) * self.current
         }
-        self.current
     }
 }
 
-impl<T: Close> Next<&T> for ExponentialMovingAverage {
-    type Output = f64;
+// Bars are only accepted by the primitive floats, a generic impl would overlap `Next<T>`.
+macro_rules! impl_close {
+    ($($t:ty),*) => {$(
+        impl<C: Close> Next<&C> for ExponentialMovingAverage<$t> {
+            type Output = $t;
+
+            fn next(&mut self, input: &C) -> Self::Output {
+                self.next(input.close() as $t)
+            }
+        }
+
+        impl<C: Close> ProjectNext<&C> for ExponentialMovingAverage<$t> {
+            type Output = $t;
+
+            fn project_next(&self, input: &C) -> Self::Output {
+                self.project_next(input.close() as $t)
+            }
+        }
+    )*};
+}
+
+impl_close!(f32, f64);
 
-    fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+impl SolveForInput for ExponentialMovingAverage {
+    fn solve_for_input(&self, target: f64) -> Option<f64> {
+        if self.is_new {
+            Some(target)
+        } else {
+            Some((target - (1.0 - self.k) * self.current) / self.k)
+        }
+    }
+}
+
+impl<T: Copy> Current for ExponentialMovingAverage<T> {
+    type Output = T;
+
+    fn current(&self) -> Option<Self::Output> {
+        if self.is_new {
+            None
+        } else {
+            Some(self.current)
+        }
     }
 }
 
-impl Reset for ExponentialMovingAverage {
+impl<T: Float> Reset for ExponentialMovingAverage<T> {
     fn reset(&mut self) {
-        self.current = 0.0;
+        self.current = T::zero();
         self.is_new = true;
+        self.count = 0;
+    }
+}
+
+impl<T> MemoryFootprint for ExponentialMovingAverage<T> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T: Float> Parameters for ExponentialMovingAverage<T> {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::with_period(params.period("period", 9)?)
     }
 }
 
@@ -117,9 +225,9 @@ impl Default for ExponentialMovingAverage {
     }
 }
 
-impl fmt::Display for ExponentialMovingAverage {
+impl<T: Float> fmt::Display for ExponentialMovingAverage<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "EMA({})", self.period)
+        fmt_params(f, "EMA", &self.params())
     }
 }
 
@@ -152,6 +260,31 @@ mod tests {
         assert_eq!(ema.next(&bar2), 3.5);
     }
 
+    #[test]
+    fn test_project_next() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+
+        for &x in &[2.0, 5.0, 1.0, 6.25] {
+            let projected = ema.project_next(x);
+            assert_eq!(ema.project_next(&Bar::new().close(x)), projected);
+            // projecting does not change the state
+            assert_eq!(ema.project_next(x), projected);
+            assert_eq!(ema.next(x), projected);
+        }
+    }
+
+    #[test]
+    fn test_solve_for_input() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        assert_eq!(ema.solve_for_input(7.0), Some(7.0));
+
+        ema.next(2.0);
+        ema.next(5.0);
+        let input = ema.solve_for_input(4.0).unwrap();
+        assert_eq!(input, 4.5);
+        assert_eq!(ema.next(input), 4.0);
+    }
+
     #[test]
     fn test_reset() {
         let mut ema = ExponentialMovingAverage::new(5).unwrap();
@@ -166,6 +299,50 @@ mod tests {
         assert_eq!(ema.next(4.0), 4.0);
     }
 
+    #[test]
+    fn test_f32() {
+        let mut ema = ExponentialMovingAverage::<f32>::with_period(3).unwrap();
+        assert_eq!(ema.next(2.0f32), 2.0f32);
+        assert_eq!(ema.next(5.0), 3.5);
+        assert_eq!(ema.next(&Bar::new().close(1)), 2.25);
+        assert_eq!(ema.project_next(6.25), 4.25);
+        assert_eq!(format!("{}", ema), "EMA(3)");
+
+        ema.reset();
+        assert_eq!(ema.next(4.0), 4.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut ExponentialMovingAverage::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut ExponentialMovingAverage::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_set_period() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        ema.next(2.0);
+        ema.next(5.0);
+        assert_eq!(ema.current(), Some(3.5));
+
+        // the value is kept and smoothed with the new factor
+        ema.set_period(1).unwrap();
+        assert_eq!(ema.period(), 1);
+        assert!(ema.is_ready());
+        assert_eq!(ema.next(6.0), 6.0);
+
+        ema.set_period(4).unwrap();
+        assert_eq!(ema.bars_until_ready(), 3);
+        assert_eq!(round(ema.next(1.0)), 4.0);
+
+        assert!(ema.set_period(0).is_err());
+        assert_eq!(ema.period(), 4);
+    }
+
     #[test]
     fn test_default() {
         ExponentialMovingAverage::default();
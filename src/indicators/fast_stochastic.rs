@@ -1,8 +1,15 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 use crate::errors::Result;
+use crate::helpers::fmt_params;
 use crate::indicators::{Maximum, Minimum};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::registry::Params;
+use crate::{
+    Close, Current, High, IsReady, Low, MemoryFootprint, Next, ParamValue, Parameters, Period,
+    Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -46,6 +53,7 @@ pub struct FastStochastic {
     period: usize,
     minimum: Minimum,
     maximum: Maximum,
+    output: Option<f64>,
 }
 
 impl FastStochastic {
@@ -54,6 +62,7 @@ impl FastStochastic {
             period,
             minimum: Minimum::new(period)?,
             maximum: Maximum::new(period)?,
+            output: None,
         })
     }
 }
@@ -64,6 +73,12 @@ impl Period for FastStochastic {
     }
 }
 
+impl IsReady for FastStochastic {
+    fn bars_until_ready(&self) -> usize {
+        self.minimum.bars_until_ready()
+    }
+}
+
 impl Next<f64> for FastStochastic {
     type Output = f64;
 
@@ -71,13 +86,15 @@ impl Next<f64> for FastStochastic {
         let min = self.minimum.next(input);
         let max = self.maximum.next(input);
 
-        if min == max {
+        let output = if min == max {
             // When only 1 input was given, than min and max are the same,
             // therefore it makes sense to return 50
             50.0
         } else {
             (input - min) / (max - min) * 100.0
-        }
+        };
+        self.output = Some(output);
+        output
     }
 }
 
@@ -89,12 +106,22 @@ impl<T: High + Low + Close> Next<&T> for FastStochastic {
         let lowest = self.minimum.next(input.low());
         let close = input.close();
 
-        if highest == lowest {
+        let output = if highest == lowest {
             // To avoid division by zero, return 50.0
             50.0
         } else {
             (close - lowest) / (highest - lowest) * 100.0
-        }
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for FastStochastic {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
@@ -102,6 +129,24 @@ impl Reset for FastStochastic {
     fn reset(&mut self) {
         self.minimum.reset();
         self.maximum.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for FastStochastic {
+    fn heap_size(&self) -> usize {
+        self.minimum.heap_size() + self.maximum.heap_size()
+    }
+}
+
+impl Parameters for FastStochastic {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::new(params.period("period", 14)?)
     }
 }
 
@@ -113,7 +158,7 @@ impl Default for FastStochastic {
 
 impl fmt::Display for FastStochastic {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "FAST_STOCH({})", self.period)
+        fmt_params(f, "FAST_STOCH", &self.params())
     }
 }
 
@@ -174,6 +219,16 @@ mod tests {
         assert_eq!(indicator.next(12.5), 25.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut FastStochastic::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut FastStochastic::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_default() {
         FastStochastic::default();
@@ -0,0 +1,395 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::Pivot;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ratios of the standard retracement levels, from the end of the swing back to its start.
+const RETRACEMENTS: [f64; 7] = [0.0, 0.236, 0.382, 0.5, 0.618, 0.786, 1.0];
+/// Ratios of the standard extension levels beyond the end of the swing.
+const EXTENSIONS: [f64; 4] = [1.272, 1.618, 2.0, 2.618];
+
+/// Direction of a swing.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwingDirection {
+    /// From the low up to the high.
+    Up,
+    /// From the high down to the low.
+    Down,
+}
+
+/// Whether a level is a retracement or an extension.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FibonacciKind {
+    Retracement,
+    Extension,
+}
+
+/// A single level.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FibonacciLevel {
+    pub kind: FibonacciKind,
+    pub ratio: f64,
+    pub price: f64,
+}
+
+/// Prices of the retracement levels. `r236` is the 23.6% retracement and so on.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FibonacciRetracements {
+    /// End of the swing.
+    pub r0: f64,
+    pub r236: f64,
+    pub r382: f64,
+    pub r500: f64,
+    pub r618: f64,
+    pub r786: f64,
+    /// Start of the swing.
+    pub r1000: f64,
+}
+
+/// Prices of the extension levels. `e1618` is the 161.8% extension and so on.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FibonacciExtensions {
+    pub e1272: f64,
+    pub e1618: f64,
+    pub e2000: f64,
+    pub e2618: f64,
+}
+
+/// Position of a price relative to the levels.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FibonacciZone {
+    /// Below the lowest level.
+    Below(FibonacciLevel),
+    /// Between two adjacent levels, the lower one first.
+    Between(FibonacciLevel, FibonacciLevel),
+    /// Above the highest level.
+    Above(FibonacciLevel),
+}
+
+/// Fibonacci retracement and extension levels.
+///
+/// Computes the standard Fibonacci levels of a swing. Retracements are measured from the end
+/// of the swing back towards its start: after an upswing the 38.2% retracement is 38.2% of
+/// the swing below the high. Extensions project the swing beyond its end: after an upswing
+/// the 161.8% extension is 161.8% of the swing above the low.
+///
+/// The swing is given either as a high/low pair and a direction, or as a pivot high and
+/// a pivot low found by [PivotHighLow](struct.PivotHighLow.html), where the more recent pivot
+/// is the end of the swing. [zone](#method.zone) tells between which pair of levels a price
+/// (e.g. the current close) sits.
+///
+/// # Formula
+///
+/// retracement(r) = end - r * (end - start)
+///
+/// extension(e) = start + e * (end - start)
+///
+/// Retracement ratios: 0, 0.236, 0.382, 0.5, 0.618, 0.786 and 1. Extension ratios: 1.272,
+/// 1.618, 2 and 2.618.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{FibonacciLevels, FibonacciZone, SwingDirection};
+///
+/// let fib = FibonacciLevels::new(200.0, 100.0, SwingDirection::Up).unwrap();
+/// assert_eq!(fib.retracements().r500, 150.0);
+/// assert_eq!(fib.extensions().e2000, 300.0);
+///
+/// match fib.zone(155.0) {
+///     FibonacciZone::Between(lower, upper) => {
+///         assert_eq!(lower.ratio, 0.5);
+///         assert_eq!(upper.ratio, 0.382);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+///
+/// # Links
+///
+/// * [Fibonacci retracement, Wikipedia](https://en.wikipedia.org/wiki/Fibonacci_retracement)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FibonacciLevels {
+    high: f64,
+    low: f64,
+    direction: SwingDirection,
+}
+
+impl FibonacciLevels {
+    /// Returns an error, when the prices are not finite or the high is not above the low.
+    pub fn new(high: f64, low: f64, direction: SwingDirection) -> Result<Self> {
+        if !high.is_finite() {
+            return Err(TaError::invalid_parameter("high", high, "must be finite"));
+        }
+        if !low.is_finite() || high <= low {
+            return Err(TaError::invalid_parameter(
+                "low",
+                low,
+                "must be finite and below the high",
+            ));
+        }
+        Ok(Self {
+            high,
+            low,
+            direction,
+        })
+    }
+
+    /// Levels of the swing between a pivot high and a pivot low, the more recent pivot is the
+    /// end of the swing. `bars_ago` of both pivots must count from the same bar. Returns an
+    /// error, when both pivots are on the same bar or the high is not above the low.
+    pub fn from_pivots(high: Pivot, low: Pivot) -> Result<Self> {
+        let direction = if high.bars_ago < low.bars_ago {
+            SwingDirection::Up
+        } else if low.bars_ago < high.bars_ago {
+            SwingDirection::Down
+        } else {
+            return Err(TaError::invalid_period(
+                "bars_ago",
+                high.bars_ago,
+                "the high and the low must be on different bars",
+            ));
+        };
+        Self::new(high.value, low.value, direction)
+    }
+
+    pub fn high(&self) -> f64 {
+        self.high
+    }
+
+    pub fn low(&self) -> f64 {
+        self.low
+    }
+
+    pub fn direction(&self) -> SwingDirection {
+        self.direction
+    }
+
+    fn start_and_move(&self) -> (f64, f64) {
+        match self.direction {
+            SwingDirection::Up => (self.low, self.high - self.low),
+            SwingDirection::Down => (self.high, self.low - self.high),
+        }
+    }
+
+    /// Price of the retracement with an arbitrary ratio.
+    pub fn retracement(&self, ratio: f64) -> f64 {
+        let (start, change) = self.start_and_move();
+        start + change - ratio * change
+    }
+
+    /// Price of the extension with an arbitrary ratio.
+    pub fn extension(&self, ratio: f64) -> f64 {
+        let (start, change) = self.start_and_move();
+        start + ratio * change
+    }
+
+    pub fn retracements(&self) -> FibonacciRetracements {
+        FibonacciRetracements {
+            r0: self.retracement(0.0),
+            r236: self.retracement(0.236),
+            r382: self.retracement(0.382),
+            r500: self.retracement(0.5),
+            r618: self.retracement(0.618),
+            r786: self.retracement(0.786),
+            r1000: self.retracement(1.0),
+        }
+    }
+
+    pub fn extensions(&self) -> FibonacciExtensions {
+        FibonacciExtensions {
+            e1272: self.extension(1.272),
+            e1618: self.extension(1.618),
+            e2000: self.extension(2.0),
+            e2618: self.extension(2.618),
+        }
+    }
+
+    /// All the standard levels ordered by price, from the lowest to the highest.
+    pub fn levels(&self) -> Vec<FibonacciLevel> {
+        let retracements = RETRACEMENTS.iter().map(|&ratio| FibonacciLevel {
+            kind: FibonacciKind::Retracement,
+            ratio,
+            price: self.retracement(ratio),
+        });
+        let extensions = EXTENSIONS.iter().map(|&ratio| FibonacciLevel {
+            kind: FibonacciKind::Extension,
+            ratio,
+            price: self.extension(ratio),
+        });
+        let mut levels: Vec<_> = retracements.chain(extensions).collect();
+        levels.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap());
+        levels
+    }
+
+    /// Pair of adjacent levels around the price. A price on a level is between that level
+    /// and the next higher one, except on the highest level.
+    pub fn zone(&self, price: f64) -> FibonacciZone {
+        let levels = self.levels();
+        let lowest = levels[0];
+        let highest = levels[levels.len() - 1];
+        if price < lowest.price {
+            return FibonacciZone::Below(lowest);
+        }
+        if price > highest.price {
+            return FibonacciZone::Above(highest);
+        }
+        levels
+            .windows(2)
+            .find(|pair| price < pair[1].price)
+            .map(|pair| FibonacciZone::Between(pair[0], pair[1]))
+            .unwrap_or(FibonacciZone::Between(levels[levels.len() - 2], highest))
+    }
+}
+
+impl fmt::Display for SwingDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SwingDirection::Up => write!(f, "up"),
+            SwingDirection::Down => write!(f, "down"),
+        }
+    }
+}
+
+impl fmt::Display for FibonacciLevels {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FIB({}, {}, {})", self.high, self.low, self.direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn up() -> FibonacciLevels {
+        FibonacciLevels::new(200.0, 100.0, SwingDirection::Up).unwrap()
+    }
+
+    fn ratios(zone: FibonacciZone) -> (f64, f64) {
+        match zone {
+            FibonacciZone::Between(lower, upper) => (lower.ratio, upper.ratio),
+            FibonacciZone::Below(level) => (f64::NEG_INFINITY, level.ratio),
+            FibonacciZone::Above(level) => (level.ratio, f64::INFINITY),
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(FibonacciLevels::new(100.0, 100.0, SwingDirection::Up).is_err());
+        assert!(FibonacciLevels::new(90.0, 100.0, SwingDirection::Up).is_err());
+        assert!(FibonacciLevels::new(f64::NAN, 100.0, SwingDirection::Up).is_err());
+        assert!(FibonacciLevels::new(f64::INFINITY, 100.0, SwingDirection::Down).is_err());
+        assert!(FibonacciLevels::new(101.0, 100.0, SwingDirection::Down).is_ok());
+    }
+
+    #[test]
+    fn test_up() {
+        let fib = up();
+
+        let r = fib.retracements();
+        assert_eq!(r.r0, 200.0);
+        assert_eq!(round(r.r236), 176.4);
+        assert_eq!(round(r.r382), 161.8);
+        assert_eq!(r.r500, 150.0);
+        assert_eq!(round(r.r618), 138.2);
+        assert_eq!(round(r.r786), 121.4);
+        assert_eq!(r.r1000, 100.0);
+
+        let e = fib.extensions();
+        assert_eq!(round(e.e1272), 227.2);
+        assert_eq!(round(e.e1618), 261.8);
+        assert_eq!(e.e2000, 300.0);
+        assert_eq!(round(e.e2618), 361.8);
+    }
+
+    #[test]
+    fn test_down() {
+        let fib = FibonacciLevels::new(200.0, 100.0, SwingDirection::Down).unwrap();
+
+        let r = fib.retracements();
+        assert_eq!(r.r0, 100.0);
+        assert_eq!(round(r.r236), 123.6);
+        assert_eq!(r.r1000, 200.0);
+
+        let e = fib.extensions();
+        assert_eq!(round(e.e1618), 38.2);
+        assert_eq!(e.e2000, 0.0);
+    }
+
+    #[test]
+    fn test_levels() {
+        let levels = up().levels();
+        assert_eq!(levels.len(), 11);
+        assert_eq!(levels[0].ratio, 1.0);
+        assert_eq!(levels[0].kind, FibonacciKind::Retracement);
+        assert_eq!(levels[6].ratio, 0.0);
+        assert_eq!(levels[7].ratio, 1.272);
+        assert_eq!(levels[7].kind, FibonacciKind::Extension);
+        assert!(levels.windows(2).all(|pair| pair[0].price < pair[1].price));
+
+        let levels = FibonacciLevels::new(200.0, 100.0, SwingDirection::Down)
+            .unwrap()
+            .levels();
+        assert_eq!(levels[0].ratio, 2.618);
+        assert_eq!(levels[10].ratio, 1.0);
+    }
+
+    #[test]
+    fn test_zone() {
+        let fib = up();
+
+        assert_eq!(ratios(fib.zone(155.0)), (0.5, 0.382));
+        // on a level
+        assert_eq!(ratios(fib.zone(150.0)), (0.5, 0.382));
+        assert_eq!(ratios(fib.zone(210.0)), (0.0, 1.272));
+        assert_eq!(ratios(fib.zone(361.8 + 1.0)), (2.618, f64::INFINITY));
+        assert_eq!(ratios(fib.zone(99.0)), (f64::NEG_INFINITY, 1.0));
+        // on the highest level
+        assert_eq!(ratios(fib.zone(fib.extension(2.618))), (2.0, 2.618));
+    }
+
+    #[test]
+    fn test_from_pivots() {
+        let high = Pivot {
+            value: 200.0,
+            bars_ago: 3,
+        };
+        let low = Pivot {
+            value: 100.0,
+            bars_ago: 10,
+        };
+        let fib = FibonacciLevels::from_pivots(high, low).unwrap();
+        assert_eq!(fib.direction(), SwingDirection::Up);
+
+        let low = Pivot {
+            value: 100.0,
+            bars_ago: 1,
+        };
+        let fib = FibonacciLevels::from_pivots(high, low).unwrap();
+        assert_eq!(fib.direction(), SwingDirection::Down);
+
+        let low = Pivot {
+            value: 100.0,
+            bars_ago: 3,
+        };
+        assert!(FibonacciLevels::from_pivots(high, low).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", up()), "FIB(200, 100, up)");
+    }
+}
@@ -0,0 +1,170 @@
+use core::fmt;
+
+use super::window::Window;
+use crate::{Current, High, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Returns the highest value of the last `N` inputs, with a period known at compile time.
+///
+/// Same as [Maximum](../struct.Maximum.html), but the window is a fixed-size array. Every update
+/// scans the window, which for the small windows is cheaper than keeping the candidates in a
+/// deque, and the compiler can vectorize the scan.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::fixed::Maximum;
+/// use ta::Next;
+///
+/// let mut max = Maximum::<3>::new();
+/// assert_eq!(max.next(7.0), 7.0);
+/// assert_eq!(max.next(5.0), 7.0);
+/// assert_eq!(max.next(4.0), 7.0);
+/// assert_eq!(max.next(4.0), 5.0);
+/// assert_eq!(max.next(8.0), 8.0);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Maximum<const N: usize> {
+    window: Window<N>,
+    output: Option<f64>,
+}
+
+impl<const N: usize> Maximum<N> {
+    const NON_ZERO: () = assert!(N > 0, "the period must be greater than 0");
+
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::NON_ZERO;
+        Self {
+            window: Window::new(),
+            output: None,
+        }
+    }
+}
+
+impl<const N: usize> Period for Maximum<N> {
+    fn period(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> IsReady for Maximum<N> {
+    fn bars_until_ready(&self) -> usize {
+        N - self.window.len()
+    }
+}
+
+impl<const N: usize> Next<f64> for Maximum<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.window.push(input);
+        let output = self.window.values().iter().copied().fold(input, f64::max);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High, const N: usize> Next<&T> for Maximum<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.high())
+    }
+}
+
+impl<const N: usize> Current for Maximum<N> {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<const N: usize> Reset for Maximum<N> {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl<const N: usize> MemoryFootprint for Maximum<N> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<const N: usize> Default for Maximum<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Display for Maximum<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MAX({})", N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::Maximum as DynMax;
+    use crate::test_helper::*;
+
+    type Max3 = Maximum<3>;
+
+    test_indicator!(Max3);
+
+    #[test]
+    fn test_next() {
+        let mut max = Maximum::<3>::new();
+
+        assert_eq!(max.next(4.0), 4.0);
+        assert_eq!(max.next(1.2), 4.0);
+        assert_eq!(max.next(5.0), 5.0);
+        assert_eq!(max.next(3.0), 5.0);
+        assert_eq!(max.next(4.0), 5.0);
+        assert_eq!(max.next(0.0), 4.0);
+        assert_eq!(max.next(-1.0), 4.0);
+        assert_eq!(max.next(-2.0), 0.0);
+        assert_eq!(max.next(-1.5), -1.0);
+    }
+
+    #[test]
+    fn test_same_as_runtime_period() {
+        let mut fixed = Maximum::<5>::new();
+        let mut dynamic = DynMax::new(5).unwrap();
+
+        for i in 0..50 {
+            let bar = Bar::new().high(100.0 + (i as f64 * 0.9).sin() * 3.0);
+            assert_eq!(fixed.next(&bar), dynamic.next(&bar));
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut max = Maximum::<3>::new();
+        max.next(10.0);
+
+        max.reset();
+        assert_eq!(max.next(4.0), 4.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut Maximum::<3>::new(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut Maximum::<3>::new(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Maximum::<7>::new()), "MAX(7)");
+    }
+}
@@ -0,0 +1,170 @@
+use core::fmt;
+
+use super::window::Window;
+use crate::{Current, IsReady, Low, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Returns the lowest value of the last `N` inputs, with a period known at compile time.
+///
+/// Same as [Minimum](../struct.Minimum.html), but the window is a fixed-size array. Every update
+/// scans the window, which for the small windows is cheaper than keeping the candidates in a
+/// deque, and the compiler can vectorize the scan.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::fixed::Minimum;
+/// use ta::Next;
+///
+/// let mut min = Minimum::<3>::new();
+/// assert_eq!(min.next(4.0), 4.0);
+/// assert_eq!(min.next(5.0), 4.0);
+/// assert_eq!(min.next(6.0), 4.0);
+/// assert_eq!(min.next(7.0), 5.0);
+/// assert_eq!(min.next(2.0), 2.0);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Minimum<const N: usize> {
+    window: Window<N>,
+    output: Option<f64>,
+}
+
+impl<const N: usize> Minimum<N> {
+    const NON_ZERO: () = assert!(N > 0, "the period must be greater than 0");
+
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::NON_ZERO;
+        Self {
+            window: Window::new(),
+            output: None,
+        }
+    }
+}
+
+impl<const N: usize> Period for Minimum<N> {
+    fn period(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> IsReady for Minimum<N> {
+    fn bars_until_ready(&self) -> usize {
+        N - self.window.len()
+    }
+}
+
+impl<const N: usize> Next<f64> for Minimum<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.window.push(input);
+        let output = self.window.values().iter().copied().fold(input, f64::min);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Low, const N: usize> Next<&T> for Minimum<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.low())
+    }
+}
+
+impl<const N: usize> Current for Minimum<N> {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<const N: usize> Reset for Minimum<N> {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl<const N: usize> MemoryFootprint for Minimum<N> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<const N: usize> Default for Minimum<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Display for Minimum<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MIN({})", N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::Minimum as DynMin;
+    use crate::test_helper::*;
+
+    type Min3 = Minimum<3>;
+
+    test_indicator!(Min3);
+
+    #[test]
+    fn test_next() {
+        let mut min = Minimum::<3>::new();
+
+        assert_eq!(min.next(4.0), 4.0);
+        assert_eq!(min.next(1.2), 1.2);
+        assert_eq!(min.next(5.0), 1.2);
+        assert_eq!(min.next(3.0), 1.2);
+        assert_eq!(min.next(4.0), 3.0);
+        assert_eq!(min.next(6.0), 3.0);
+        assert_eq!(min.next(7.0), 4.0);
+        assert_eq!(min.next(8.0), 6.0);
+        assert_eq!(min.next(-9.0), -9.0);
+    }
+
+    #[test]
+    fn test_same_as_runtime_period() {
+        let mut fixed = Minimum::<5>::new();
+        let mut dynamic = DynMin::new(5).unwrap();
+
+        for i in 0..50 {
+            let bar = Bar::new().low(100.0 + (i as f64 * 0.9).sin() * 3.0);
+            assert_eq!(fixed.next(&bar), dynamic.next(&bar));
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut min = Minimum::<3>::new();
+        min.next(10.0);
+
+        min.reset();
+        assert_eq!(min.next(4.0), 4.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut Minimum::<3>::new(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut Minimum::<3>::new(), [5.0, 3.0, 2.0]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Minimum::<7>::new()), "MIN(7)");
+    }
+}
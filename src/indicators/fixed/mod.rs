@@ -0,0 +1,83 @@
+//! Indicators with a window size known at compile time.
+//!
+//! The period is a const generic parameter and the window is a fixed-size array instead of a
+//! heap allocated buffer, so construction does not allocate and cannot fail, and the compiler
+//! can unroll and vectorize the loops over the window. The outputs are the same as of the
+//! indicators with a runtime period of the same name in the
+//! [indicators](../index.html) module.
+//!
+//! An indicator of this module holds no heap pointer at all, its window is part of the struct.
+//! For the usual windows of 14 to 50 bars, thousands of instances packed in a `Vec`, e.g. one
+//! per symbol, are a single contiguous allocation instead of one window per instance somewhere
+//! else on the heap, which keeps the updates of all of them cache friendly.
+//!
+//! A period of 0 is rejected at compile time:
+//!
+//! ```compile_fail
+//! use ta::indicators::fixed::SimpleMovingAverage;
+//!
+//! let sma = SimpleMovingAverage::<0>::new();
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::fixed::SimpleMovingAverage;
+//! use ta::Next;
+//!
+//! let mut sma = SimpleMovingAverage::<3>::new();
+//! assert_eq!(sma.next(10.0), 10.0);
+//! assert_eq!(sma.next(11.0), 10.5);
+//! assert_eq!(sma.next(12.0), 11.0);
+//! assert_eq!(sma.next(13.0), 12.0);
+//! ```
+
+mod window;
+
+mod simple_moving_average;
+pub use self::simple_moving_average::SimpleMovingAverage;
+
+mod weighted_moving_average;
+pub use self::weighted_moving_average::WeightedMovingAverage;
+
+mod standard_deviation;
+pub use self::standard_deviation::StandardDeviation;
+
+mod maximum;
+pub use self::maximum::Maximum;
+
+mod minimum;
+pub use self::minimum::Minimum;
+
+mod rate_of_change;
+pub use self::rate_of_change::RateOfChange;
+
+mod volume_weighted_average_price;
+pub use self::volume_weighted_average_price::VolumeWeightedAveragePrice;
+
+/// Serializes the windows as sequences, serde implements arrays only up to 32 elements.
+#[cfg(feature = "serde")]
+mod array {
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+    use core::convert::TryInto;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        array: &[f64; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        array[..].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[f64; N], D::Error> {
+        let values = Vec::<f64>::deserialize(deserializer)?;
+        let len = values.len();
+        values
+            .try_into()
+            .map_err(|_| D::Error::invalid_length(len, &"a window of the period length"))
+    }
+}
@@ -0,0 +1,175 @@
+use core::fmt;
+
+use super::window::Window;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rate of Change (ROC) with a period known at compile time.
+///
+/// Same as [RateOfChange](../struct.RateOfChange.html), but the window of the `N` last inputs
+/// is a fixed-size array.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::fixed::RateOfChange;
+/// use ta::Next;
+///
+/// let mut roc = RateOfChange::<2>::new();
+/// assert_eq!(roc.next(10.0), 0.0);
+/// assert_eq!(roc.next(12.0), 20.0);
+/// assert_eq!(roc.next(15.0), 50.0);
+/// ```
+#[doc(alias = "ROC")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RateOfChange<const N: usize> {
+    count: usize,
+    window: Window<N>,
+    output: Option<f64>,
+}
+
+impl<const N: usize> RateOfChange<N> {
+    const NON_ZERO: () = assert!(N > 0, "the period must be greater than 0");
+
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::NON_ZERO;
+        Self {
+            count: 0,
+            window: Window::new(),
+            output: None,
+        }
+    }
+}
+
+impl<const N: usize> Period for RateOfChange<N> {
+    fn period(&self) -> usize {
+        // the first bar has no previous value
+        N + 1
+    }
+}
+
+impl<const N: usize> IsReady for RateOfChange<N> {
+    fn bars_until_ready(&self) -> usize {
+        N + 1 - self.count
+    }
+}
+
+impl<const N: usize> Next<f64> for RateOfChange<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        // the value `N` bars ago, or the first one before
+        let previous = self.window.oldest().unwrap_or(input);
+        self.window.push(input);
+        self.count = (self.count + 1).min(N + 1);
+
+        let output = (input - previous) / previous * 100.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<C: Close, const N: usize> Next<&C> for RateOfChange<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: &C) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl<const N: usize> Current for RateOfChange<N> {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<const N: usize> Reset for RateOfChange<N> {
+    fn reset(&mut self) {
+        self.count = 0;
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl<const N: usize> MemoryFootprint for RateOfChange<N> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<const N: usize> Default for RateOfChange<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Display for RateOfChange<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ROC({})", N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::RateOfChange as DynRoc;
+    use crate::test_helper::*;
+
+    type Roc3 = RateOfChange<3>;
+
+    test_indicator!(Roc3);
+
+    #[test]
+    fn test_next() {
+        let mut roc = RateOfChange::<3>::new();
+        assert_eq!(round(roc.next(10.0)), 0.0);
+        assert_eq!(round(roc.next(10.4)), 4.0);
+        assert_eq!(round(roc.next(10.57)), 5.7);
+        assert_eq!(round(roc.next(10.8)), 8.0);
+        assert_eq!(round(roc.next(10.9)), 4.808);
+        assert_eq!(round(roc.next(10.0)), -5.393);
+    }
+
+    #[test]
+    fn test_same_as_runtime_period() {
+        let mut fixed = RateOfChange::<5>::new();
+        let mut dynamic = DynRoc::new(5).unwrap();
+
+        for i in 0..50 {
+            let bar = Bar::new().close(100.0 + (i as f64 * 0.9).sin() * 3.0);
+            assert_eq!(fixed.next(&bar), dynamic.next(&bar));
+            assert_eq!(fixed.bars_until_ready(), dynamic.bars_until_ready());
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut roc = RateOfChange::<3>::new();
+        roc.next(12.3);
+        roc.next(15.0);
+
+        roc.reset();
+        assert_eq!(roc.next(13.0), 0.0);
+        assert_eq!(roc.next(26.0), 100.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RateOfChange::<3>::new(), 1.0, 4);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut RateOfChange::<3>::new(), [1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", RateOfChange::<5>::new()), "ROC(5)");
+    }
+}
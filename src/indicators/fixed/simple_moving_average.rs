@@ -0,0 +1,196 @@
+use core::fmt;
+
+use super::window::Window;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, NextBatch, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Simple moving average (SMA) with a period known at compile time.
+///
+/// Same as [SimpleMovingAverage](../struct.SimpleMovingAverage.html) with `f64` values, but
+/// the window of the `N` last inputs is a fixed-size array.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::fixed::SimpleMovingAverage;
+/// use ta::Next;
+///
+/// let mut sma = SimpleMovingAverage::<2>::new();
+/// assert_eq!(sma.next(10.0), 10.0);
+/// assert_eq!(sma.next(11.0), 10.5);
+/// assert_eq!(sma.next(13.0), 12.0);
+/// ```
+#[doc(alias = "SMA")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct SimpleMovingAverage<const N: usize> {
+    sum: f64,
+    window: Window<N>,
+    output: Option<f64>,
+}
+
+impl<const N: usize> SimpleMovingAverage<N> {
+    const NON_ZERO: () = assert!(N > 0, "the period must be greater than 0");
+
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::NON_ZERO;
+        Self {
+            sum: 0.0,
+            window: Window::new(),
+            output: None,
+        }
+    }
+}
+
+impl<const N: usize> Period for SimpleMovingAverage<N> {
+    fn period(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> IsReady for SimpleMovingAverage<N> {
+    fn bars_until_ready(&self) -> usize {
+        N - self.window.len()
+    }
+}
+
+impl<const N: usize> Next<f64> for SimpleMovingAverage<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let oldest = self.window.push(input).unwrap_or(0.0);
+        self.sum = self.sum - oldest + input;
+        let output = self.sum / self.window.len() as f64;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<const N: usize> NextBatch<f64> for SimpleMovingAverage<N> {}
+
+impl<C: Close, const N: usize> Next<&C> for SimpleMovingAverage<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: &C) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl<const N: usize> Current for SimpleMovingAverage<N> {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<const N: usize> Reset for SimpleMovingAverage<N> {
+    fn reset(&mut self) {
+        self.sum = 0.0;
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl<const N: usize> MemoryFootprint for SimpleMovingAverage<N> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<const N: usize> Default for SimpleMovingAverage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Display for SimpleMovingAverage<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SMA({})", N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage as DynSma;
+    use crate::test_helper::*;
+
+    type Sma4 = SimpleMovingAverage<4>;
+
+    test_indicator!(Sma4);
+
+    #[test]
+    fn test_next() {
+        let mut sma = SimpleMovingAverage::<4>::new();
+        assert_eq!(sma.next(4.0), 4.0);
+        assert_eq!(sma.next(5.0), 4.5);
+        assert_eq!(sma.next(6.0), 5.0);
+        assert_eq!(sma.next(6.0), 5.25);
+        assert_eq!(sma.next(6.0), 5.75);
+        assert_eq!(sma.next(6.0), 6.0);
+        assert_eq!(sma.next(2.0), 5.0);
+    }
+
+    #[test]
+    fn test_same_as_runtime_period() {
+        let mut fixed = SimpleMovingAverage::<5>::new();
+        let mut dynamic = DynSma::new(5).unwrap();
+
+        for i in 0..50 {
+            let bar = Bar::new().close(100.0 + (i as f64 * 0.9).sin() * 3.0);
+            assert_eq!(fixed.next(&bar), dynamic.next(&bar));
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        fn bar(close: f64) -> Bar {
+            Bar::new().close(close)
+        }
+
+        let mut sma = SimpleMovingAverage::<3>::new();
+        assert_eq!(sma.next(&bar(4.0)), 4.0);
+        assert_eq!(sma.next(&bar(4.0)), 4.0);
+        assert_eq!(sma.next(&bar(7.0)), 5.0);
+        assert_eq!(sma.next(&bar(1.0)), 4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sma = SimpleMovingAverage::<4>::new();
+        assert_eq!(sma.next(4.0), 4.0);
+        assert_eq!(sma.next(5.0), 4.5);
+
+        sma.reset();
+        assert_eq!(sma.next(99.0), 99.0);
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(SimpleMovingAverage::<7>::new().period(), 7);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut SimpleMovingAverage::<3>::new(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut SimpleMovingAverage::<3>::new(), [1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        SimpleMovingAverage::<9>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = SimpleMovingAverage::<5>::new();
+        assert_eq!(format!("{}", sma), "SMA(5)");
+    }
+}
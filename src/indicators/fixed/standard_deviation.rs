@@ -0,0 +1,174 @@
+use core::fmt;
+
+use super::window::Window;
+use crate::rolling_variance::RollingVariance;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Standard deviation (SD) with a period known at compile time.
+///
+/// Same as [StandardDeviation](../struct.StandardDeviation.html), but the window of the `N`
+/// last inputs is a fixed-size array, and the same Welford update gives the same values.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::fixed::StandardDeviation;
+/// use ta::Next;
+///
+/// let mut sd = StandardDeviation::<3>::new();
+/// assert_eq!(sd.next(10.0), 0.0);
+/// assert_eq!(sd.next(20.0), 5.0);
+/// ```
+#[doc(alias = "SD")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct StandardDeviation<const N: usize> {
+    stats: RollingVariance,
+    window: Window<N>,
+    output: Option<f64>,
+}
+
+impl<const N: usize> StandardDeviation<N> {
+    const NON_ZERO: () = assert!(N > 0, "the period must be greater than 0");
+
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::NON_ZERO;
+        Self {
+            stats: RollingVariance::new(),
+            window: Window::new(),
+            output: None,
+        }
+    }
+}
+
+impl<const N: usize> Period for StandardDeviation<N> {
+    fn period(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> IsReady for StandardDeviation<N> {
+    fn bars_until_ready(&self) -> usize {
+        N - self.window.len()
+    }
+}
+
+impl<const N: usize> Next<f64> for StandardDeviation<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        match self.window.push(input) {
+            Some(oldest) => self.stats.replace(oldest, input),
+            None => self.stats.add(input),
+        }
+
+        let output = self.stats.variance().sqrt();
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<C: Close, const N: usize> Next<&C> for StandardDeviation<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: &C) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl<const N: usize> Current for StandardDeviation<N> {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<const N: usize> Reset for StandardDeviation<N> {
+    fn reset(&mut self) {
+        self.stats.clear();
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl<const N: usize> MemoryFootprint for StandardDeviation<N> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<const N: usize> Default for StandardDeviation<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Display for StandardDeviation<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SD({})", N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::StandardDeviation as DynSd;
+    use crate::test_helper::*;
+
+    type Sd4 = StandardDeviation<4>;
+
+    test_indicator!(Sd4);
+
+    #[test]
+    fn test_next() {
+        let mut sd = StandardDeviation::<4>::new();
+        assert_eq!(sd.next(10.0), 0.0);
+        assert_eq!(sd.next(20.0), 5.0);
+        assert_eq!(round(sd.next(30.0)), 8.165);
+        assert_eq!(round(sd.next(20.0)), 7.071);
+        assert_eq!(round(sd.next(10.0)), 7.071);
+        assert_eq!(round(sd.next(100.0)), 35.355);
+    }
+
+    #[test]
+    fn test_same_as_runtime_period() {
+        let mut fixed = StandardDeviation::<5>::new();
+        let mut dynamic = DynSd::new(5).unwrap();
+
+        for i in 0..50 {
+            let bar = Bar::new().close(100.0 + (i as f64 * 0.9).sin() * 3.0);
+            assert_eq!(fixed.next(&bar), dynamic.next(&bar));
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sd = StandardDeviation::<4>::new();
+        sd.next(10.0);
+        sd.next(20.0);
+
+        sd.reset();
+        assert_eq!(sd.next(99.0), 0.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut StandardDeviation::<3>::new(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut StandardDeviation::<3>::new(), [1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", StandardDeviation::<5>::new()), "SD(5)");
+    }
+}
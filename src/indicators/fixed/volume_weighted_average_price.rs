@@ -0,0 +1,231 @@
+use core::fmt;
+
+use super::window::Window;
+use crate::indicators::volume_weighted_average_price::WindowSums;
+use crate::indicators::VolumeWeightedAveragePriceBands;
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volume Weighted Average Price (VWAP) over a window known at compile time.
+///
+/// Same as [VolumeWeightedAveragePrice](../struct.VolumeWeightedAveragePrice.html), but the
+/// price-volume and the volume of the `N` last bars are kept in fixed-size ring buffers
+/// instead of vectors, and the same running sums give the same values.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::fixed::VolumeWeightedAveragePrice;
+/// use ta::{DataItem, Next};
+///
+/// let bar = |price: f64, volume: f64| {
+///     DataItem::builder()
+///         .open(price).high(price).low(price).close(price).volume(volume)
+///         .build().unwrap()
+/// };
+///
+/// let mut vwap = VolumeWeightedAveragePrice::<2>::new();
+/// assert_eq!(vwap.next(&bar(10.0, 100.0)), 10.0);
+/// assert_eq!(vwap.next(&bar(12.0, 300.0)), 11.5);
+/// assert_eq!(vwap.next(&bar(13.0, 100.0)), 12.25);
+/// ```
+#[doc(alias = "VWAP")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct VolumeWeightedAveragePrice<const N: usize> {
+    price_volume: Window<N>,
+    volume: Window<N>,
+    sums: WindowSums,
+    vwap: f64,
+    std_dev: f64,
+}
+
+impl<const N: usize> VolumeWeightedAveragePrice<N> {
+    const NON_ZERO: () = assert!(N > 0, "the window must be greater than 0");
+
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::NON_ZERO;
+        Self {
+            price_volume: Window::new(),
+            volume: Window::new(),
+            sums: WindowSums::new(),
+            vwap: 0.0,
+            std_dev: 0.0,
+        }
+    }
+
+    pub fn vwap(&self) -> f64 {
+        self.vwap
+    }
+
+    pub fn std_dev(&self, offset: f64, band_direction: VolumeWeightedAveragePriceBands) -> f64 {
+        match band_direction {
+            VolumeWeightedAveragePriceBands::Up => self.vwap + offset * self.std_dev,
+            VolumeWeightedAveragePriceBands::Down => self.vwap - offset * self.std_dev,
+        }
+    }
+}
+
+impl<const N: usize> Period for VolumeWeightedAveragePrice<N> {
+    fn period(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> IsReady for VolumeWeightedAveragePrice<N> {
+    fn bars_until_ready(&self) -> usize {
+        N - self.volume.len()
+    }
+}
+
+impl<T: High + Low + Close + Volume, const N: usize> Next<&T> for VolumeWeightedAveragePrice<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let typical_price = (input.high() + input.low() + input.close()) / 3.0;
+        let price_volume = typical_price * input.volume();
+        let oldest_price_volume = self.price_volume.push(price_volume);
+        if let (Some(oldest_price_volume), Some(oldest_volume)) =
+            (oldest_price_volume, self.volume.push(input.volume()))
+        {
+            self.sums.update(oldest_price_volume, oldest_volume, -1.0);
+        }
+        self.sums.update(price_volume, input.volume(), 1.0);
+
+        if let Some(vwap) = self.sums.vwap() {
+            self.vwap = vwap;
+        }
+        if let Some(std_dev) = self.sums.std_dev(self.vwap) {
+            self.std_dev = std_dev;
+        }
+
+        self.vwap
+    }
+}
+
+impl<const N: usize> Current for VolumeWeightedAveragePrice<N> {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        if self.volume.len() == 0 {
+            None
+        } else {
+            Some(self.vwap)
+        }
+    }
+}
+
+impl<const N: usize> Reset for VolumeWeightedAveragePrice<N> {
+    fn reset(&mut self) {
+        self.price_volume.clear();
+        self.volume.clear();
+        self.sums = WindowSums::new();
+        self.vwap = 0.0;
+        self.std_dev = 0.0;
+    }
+}
+
+impl<const N: usize> MemoryFootprint for VolumeWeightedAveragePrice<N> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<const N: usize> Default for VolumeWeightedAveragePrice<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Display for VolumeWeightedAveragePrice<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VWAP({})", N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::VolumeWeightedAveragePrice as DynVwap;
+    use crate::test_helper::*;
+
+    type Vwap3 = VolumeWeightedAveragePrice<3>;
+
+    test_hlcv_indicator!(Vwap3);
+
+    fn bar(i: usize) -> Bar {
+        let close = 100.0 + (i as f64 * 0.7).sin() * 4.0;
+        Bar::new()
+            .high(close + 1.0 + (i % 3) as f64)
+            .low(close - 1.0)
+            .close(close)
+            .volume(1000.0 + (i % 5) as f64 * 300.0)
+    }
+
+    #[test]
+    fn test_same_as_runtime_window() {
+        let mut fixed = VolumeWeightedAveragePrice::<4>::new();
+        let mut dynamic = DynVwap::new(4).unwrap();
+
+        for i in 0..40 {
+            assert_eq!(fixed.next(&bar(i)), dynamic.next(&bar(i)));
+            assert_eq!(
+                fixed.std_dev(2.0, VolumeWeightedAveragePriceBands::Up),
+                dynamic.std_dev(2.0, VolumeWeightedAveragePriceBands::Up)
+            );
+            assert_eq!(
+                fixed.std_dev(2.0, VolumeWeightedAveragePriceBands::Down),
+                dynamic.std_dev(2.0, VolumeWeightedAveragePriceBands::Down)
+            );
+        }
+    }
+
+    #[test]
+    fn test_zero_volume() {
+        let mut vwap = VolumeWeightedAveragePrice::<2>::new();
+        assert_eq!(vwap.next(&bar(0).volume(0.0)), 0.0);
+        let output = vwap.next(&bar(1));
+        assert_eq!(vwap.next(&bar(2).volume(0.0)), output);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vwap = VolumeWeightedAveragePrice::<2>::new();
+        let first = vwap.next(&bar(0));
+        vwap.next(&bar(1));
+        vwap.next(&bar(2));
+
+        vwap.reset();
+        assert_eq!(vwap.vwap(), 0.0);
+        assert_eq!(vwap.next(&bar(0)), first);
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(VolumeWeightedAveragePrice::<20>::new().period(), 20);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut VolumeWeightedAveragePrice::<3>::new(), &bar(0), 3);
+    }
+
+    #[test]
+    fn test_current() {
+        let bars: Vec<Bar> = (0..5).map(bar).collect();
+        assert_current(&mut VolumeWeightedAveragePrice::<3>::new(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        VolumeWeightedAveragePrice::<14>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let vwap = VolumeWeightedAveragePrice::<7>::new();
+        assert_eq!(format!("{}", vwap), "VWAP(7)");
+    }
+}
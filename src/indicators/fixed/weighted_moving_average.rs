@@ -0,0 +1,185 @@
+use core::fmt;
+
+use super::window::Window;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Weighted moving average (WMA) with a period known at compile time.
+///
+/// Same as [WeightedMovingAverage](../struct.WeightedMovingAverage.html), but the window of
+/// the `N` last inputs is a fixed-size array, and the same running sums give the same values.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::fixed::WeightedMovingAverage;
+/// use ta::Next;
+///
+/// let mut wma = WeightedMovingAverage::<3>::new();
+/// assert_eq!(wma.next(10.0), 10.0);
+/// assert_eq!(wma.next(13.0), 12.0);
+/// assert_eq!(wma.next(16.0), 14.0);
+/// assert_eq!(wma.next(14.0), 14.5);
+/// ```
+#[doc(alias = "WMA")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct WeightedMovingAverage<const N: usize> {
+    weight: f64,
+    sum: f64,
+    sum_flat: f64,
+    window: Window<N>,
+    output: Option<f64>,
+}
+
+impl<const N: usize> WeightedMovingAverage<N> {
+    const NON_ZERO: () = assert!(N > 0, "the period must be greater than 0");
+
+    pub fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::NON_ZERO;
+        Self {
+            weight: 0.0,
+            sum: 0.0,
+            sum_flat: 0.0,
+            window: Window::new(),
+            output: None,
+        }
+    }
+}
+
+impl<const N: usize> Period for WeightedMovingAverage<N> {
+    fn period(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> IsReady for WeightedMovingAverage<N> {
+    fn bars_until_ready(&self) -> usize {
+        N - self.window.len()
+    }
+}
+
+impl<const N: usize> Next<f64> for WeightedMovingAverage<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let old_val = match self.window.push(input) {
+            Some(oldest) => {
+                self.sum = self.sum - self.sum_flat + (input * self.weight);
+                oldest
+            }
+            None => {
+                self.weight = self.window.len() as f64;
+                self.sum += input * self.weight;
+                0.0
+            }
+        };
+        self.sum_flat = self.sum_flat - old_val + input;
+        let output = self.sum / (self.weight * (self.weight + 1.0) / 2.0);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<C: Close, const N: usize> Next<&C> for WeightedMovingAverage<N> {
+    type Output = f64;
+
+    fn next(&mut self, input: &C) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl<const N: usize> Current for WeightedMovingAverage<N> {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<const N: usize> Reset for WeightedMovingAverage<N> {
+    fn reset(&mut self) {
+        self.weight = 0.0;
+        self.sum = 0.0;
+        self.sum_flat = 0.0;
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl<const N: usize> MemoryFootprint for WeightedMovingAverage<N> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<const N: usize> Default for WeightedMovingAverage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Display for WeightedMovingAverage<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WMA({})", N)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::WeightedMovingAverage as DynWma;
+    use crate::test_helper::*;
+
+    type Wma3 = WeightedMovingAverage<3>;
+
+    test_indicator!(Wma3);
+
+    #[test]
+    fn test_next() {
+        let mut wma = WeightedMovingAverage::<3>::new();
+        assert_eq!(wma.next(12.0), 12.0);
+        assert_eq!(wma.next(3.0), 6.0);
+        assert_eq!(wma.next(3.0), 4.5);
+        assert_eq!(wma.next(5.0), 4.0);
+    }
+
+    #[test]
+    fn test_same_as_runtime_period() {
+        let mut fixed = WeightedMovingAverage::<5>::new();
+        let mut dynamic = DynWma::new(5).unwrap();
+
+        for i in 0..50 {
+            let bar = Bar::new().close(100.0 + (i as f64 * 0.9).sin() * 3.0);
+            assert_eq!(fixed.next(&bar), dynamic.next(&bar));
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut wma = WeightedMovingAverage::<3>::new();
+        wma.next(12.0);
+        wma.next(3.0);
+
+        wma.reset();
+        assert_eq!(wma.next(4.0), 4.0);
+        assert_eq!(wma.next(7.0), 6.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut WeightedMovingAverage::<3>::new(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut WeightedMovingAverage::<3>::new(), [1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", WeightedMovingAverage::<5>::new()), "WMA(5)");
+    }
+}
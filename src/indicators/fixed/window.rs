@@ -0,0 +1,101 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Ring buffer of the last `N` values, stored inline in the indicator.
+///
+/// The counterpart of the heap allocated ring buffer of the indicators with a runtime period:
+/// pushing into a full window overwrites the oldest value and returns it.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub(super) struct Window<const N: usize> {
+    #[cfg_attr(feature = "serde", serde(with = "super::array"))]
+    values: [f64; N],
+    /// Position of the oldest value once the window is full.
+    head: usize,
+    len: usize,
+}
+
+impl<const N: usize> Window<N> {
+    pub(super) fn new() -> Self {
+        Self {
+            values: [0.0; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Appends a value and returns the oldest one, if it left the window.
+    pub(super) fn push(&mut self, value: f64) -> Option<f64> {
+        if self.len < N {
+            self.values[self.len] = value;
+            self.len += 1;
+            return None;
+        }
+        let oldest = core::mem::replace(&mut self.values[self.head], value);
+        self.head = if self.head + 1 < N { self.head + 1 } else { 0 };
+        Some(oldest)
+    }
+
+    /// The oldest value, which the next push into a full window replaces.
+    pub(super) fn oldest(&self) -> Option<f64> {
+        match self.len {
+            0 => None,
+            _ => Some(self.values[self.head]),
+        }
+    }
+
+    /// The values in no particular order.
+    pub(super) fn values(&self) -> &[f64] {
+        &self.values[..self.len]
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push() {
+        let mut window = Window::<3>::new();
+        assert_eq!(window.oldest(), None);
+        assert_eq!(window.push(1.0), None);
+        assert_eq!(window.push(2.0), None);
+        assert_eq!(window.push(3.0), None);
+        assert_eq!(window.push(4.0), Some(1.0));
+        assert_eq!(window.push(5.0), Some(2.0));
+        assert_eq!(window.oldest(), Some(3.0));
+        assert_eq!(window.values(), &[4.0, 5.0, 3.0]);
+        assert_eq!(window.len(), 3);
+    }
+
+    #[test]
+    fn test_values() {
+        let mut window = Window::<4>::new();
+        window.push(1.0);
+        window.push(2.0);
+        assert_eq!(window.values(), &[1.0, 2.0]);
+        assert_eq!(window.oldest(), Some(1.0));
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut window = Window::<2>::new();
+        for value in 0..5 {
+            window.push(value as f64);
+        }
+        window.clear();
+        assert_eq!(window.len(), 0);
+        window.push(7.0);
+        assert_eq!(window.values(), &[7.0]);
+        assert_eq!(window.oldest(), Some(7.0));
+    }
+}
@@ -0,0 +1,305 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// What happens to the state of an indicator after a time gap.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GapPolicy {
+    /// The indicator is reset before the first bar after the gap, so it starts over.
+    Reset,
+    /// The state is kept, the gap is only reported.
+    Keep,
+}
+
+impl fmt::Display for GapPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GapPolicy::Reset => write!(f, "reset"),
+            GapPolicy::Keep => write!(f, "keep"),
+        }
+    }
+}
+
+/// Time gap handling for any indicator.
+///
+/// Wraps an indicator and watches the timestamps of its inputs. When the time between two
+/// consecutive inputs is larger than _max_gap_ (a weekend, a trading halt, missing data), the
+/// gap is reported and, depending on the [policy](enum.GapPolicy.html), the indicator is reset
+/// before it gets the first input after the gap. Otherwise stale window contents are carried
+/// across the gap, which silently skews values like the ATR or the VWAP of the new session.
+///
+/// Inputs are `(time, input)` tuples, where `input` is anything the wrapped indicator accepts.
+/// Times can be in any unit (e.g. seconds or days), as long as _max_gap_ is in the same unit.
+/// A time, which is not after the previous one, is never a gap.
+///
+/// # Parameters
+///
+/// * _indicator_ - the wrapped indicator
+/// * _max_gap_ - largest time between two inputs, which is not a gap (number greater than 0)
+/// * _policy_ - what happens after a gap
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{GapGuard, GapPolicy, SimpleMovingAverage};
+/// use ta::Next;
+///
+/// let sma = SimpleMovingAverage::new(2).unwrap();
+/// // timestamps in minutes, anything above 5 minutes is a gap
+/// let mut guarded = GapGuard::new(sma, 5.0, GapPolicy::Reset).unwrap();
+///
+/// assert_eq!(guarded.next((0.0, 10.0)).value, 10.0);
+/// assert_eq!(guarded.next((1.0, 12.0)).value, 11.0);
+///
+/// // the next morning
+/// let out = guarded.next((960.0, 20.0));
+/// assert!(out.gap);
+/// assert_eq!(out.value, 20.0);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct GapGuard<I> {
+    indicator: I,
+    max_gap: f64,
+    policy: GapPolicy,
+    last_time: Option<f64>,
+    gap: bool,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GapGuardOutput<O> {
+    /// Output of the wrapped indicator.
+    pub value: O,
+    /// Whether a gap preceded this input.
+    pub gap: bool,
+}
+
+impl<I> GapGuard<I> {
+    pub fn new(indicator: I, max_gap: f64, policy: GapPolicy) -> Result<Self> {
+        if max_gap.is_nan() || max_gap <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "max_gap",
+                max_gap,
+                "must be greater than 0",
+            ));
+        }
+        Ok(Self {
+            indicator,
+            max_gap,
+            policy,
+            last_time: None,
+            gap: false,
+        })
+    }
+
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn max_gap(&self) -> f64 {
+        self.max_gap
+    }
+
+    pub fn policy(&self) -> GapPolicy {
+        self.policy
+    }
+}
+
+impl<I: Period> Period for GapGuard<I> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: IsReady> IsReady for GapGuard<I> {
+    fn bars_until_ready(&self) -> usize {
+        self.indicator.bars_until_ready()
+    }
+}
+
+impl<I, T> Next<(f64, T)> for GapGuard<I>
+where
+    I: Next<T> + Reset,
+{
+    type Output = GapGuardOutput<I::Output>;
+
+    fn next(&mut self, (time, input): (f64, T)) -> Self::Output {
+        let gap = match self.last_time {
+            Some(last_time) => time - last_time > self.max_gap,
+            None => false,
+        };
+        if gap && self.policy == GapPolicy::Reset {
+            self.indicator.reset();
+        }
+        self.last_time = Some(self.last_time.map_or(time, |last| last.max(time)));
+        self.gap = gap;
+
+        GapGuardOutput {
+            value: self.indicator.next(input),
+            gap,
+        }
+    }
+}
+
+impl<I: Current> Current for GapGuard<I> {
+    type Output = GapGuardOutput<I::Output>;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.indicator.current().map(|value| GapGuardOutput {
+            value,
+            gap: self.gap,
+        })
+    }
+}
+
+impl<I: Reset> Reset for GapGuard<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.last_time = None;
+        self.gap = false;
+    }
+}
+
+impl<I: MemoryFootprint> MemoryFootprint for GapGuard<I> {
+    fn heap_size(&self) -> usize {
+        self.indicator.heap_size()
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for GapGuard<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "GAP({}, {}, {})",
+            self.indicator, self.max_gap, self.policy
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{AverageTrueRange, SimpleMovingAverage};
+    use crate::test_helper::*;
+
+    fn sma(policy: GapPolicy) -> GapGuard<SimpleMovingAverage> {
+        GapGuard::new(SimpleMovingAverage::new(3).unwrap(), 10.0, policy).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let new = |max_gap| {
+            GapGuard::new(
+                SimpleMovingAverage::new(3).unwrap(),
+                max_gap,
+                GapPolicy::Reset,
+            )
+        };
+        assert!(new(0.0).is_err());
+        assert!(new(-1.0).is_err());
+        assert!(new(f64::NAN).is_err());
+        assert!(new(0.5).is_ok());
+    }
+
+    #[test]
+    fn test_reset_policy() {
+        let mut guarded = sma(GapPolicy::Reset);
+
+        assert_eq!(
+            guarded.next((0.0, 3.0)),
+            GapGuardOutput {
+                value: 3.0,
+                gap: false
+            }
+        );
+        assert_eq!(guarded.next((10.0, 6.0)).value, 4.5);
+        // exactly max_gap is not a gap
+        assert_eq!(guarded.next((20.0, 9.0)).value, 6.0);
+
+        let out = guarded.next((31.0, 30.0));
+        assert!(out.gap);
+        assert_eq!(out.value, 30.0);
+        let out = guarded.next((32.0, 33.0));
+        assert!(!out.gap);
+        assert_eq!(out.value, 31.5);
+    }
+
+    #[test]
+    fn test_keep_policy() {
+        let mut guarded = sma(GapPolicy::Keep);
+
+        guarded.next((0.0, 3.0));
+        guarded.next((1.0, 6.0));
+        let out = guarded.next((100.0, 9.0));
+        assert!(out.gap);
+        assert_eq!(out.value, 6.0);
+    }
+
+    #[test]
+    fn test_time_going_back() {
+        let mut guarded = sma(GapPolicy::Reset);
+
+        guarded.next((50.0, 3.0));
+        assert!(!guarded.next((20.0, 6.0)).gap);
+        // the gap is measured from the latest time seen
+        assert!(!guarded.next((55.0, 9.0)).gap);
+        assert_eq!(guarded.next((56.0, 12.0)).value, 9.0);
+    }
+
+    #[test]
+    fn test_bars() {
+        let atr = AverageTrueRange::new(3).unwrap();
+        let mut guarded = GapGuard::new(atr, 1.0, GapPolicy::Reset).unwrap();
+
+        let bar1 = Bar::new().high(11.0).low(9.0).close(10.0);
+        let bar2 = Bar::new().high(21.0).low(20.0).close(20.5);
+        guarded.next((0.0, &bar1));
+        // without the reset the true range would include the gap from 10 to 20
+        let out = guarded.next((3.0, &bar2));
+        assert!(out.gap);
+        assert_eq!(out.value, 1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut guarded = sma(GapPolicy::Reset);
+
+        guarded.next((0.0, 3.0));
+        guarded.next((1.0, 6.0));
+
+        guarded.reset();
+        let out = guarded.next((100.0, 9.0));
+        assert!(!out.gap);
+        assert_eq!(out.value, 9.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let sma = SimpleMovingAverage::new(3).unwrap();
+        let mut guard = GapGuard::new(sma, 1.0, GapPolicy::Reset).unwrap();
+        guard.next((0.0, 1.0));
+        guard.next((1.0, 1.0));
+        assert_eq!(guard.bars_until_ready(), 1);
+        // the gap restarts the warm-up
+        guard.next((5.0, 1.0));
+        assert_eq!(guard.bars_until_ready(), 2);
+    }
+
+    #[test]
+    fn test_current() {
+        let sma = SimpleMovingAverage::new(3).unwrap();
+        let mut guard = GapGuard::new(sma, 1.0, GapPolicy::Reset).unwrap();
+        assert_current(&mut guard, [(0.0, 1.0), (1.0, 2.0), (5.0, 3.0)]);
+    }
+
+    #[test]
+    fn test_display() {
+        let guarded = sma(GapPolicy::Reset);
+        assert_eq!(format!("{}", guarded), "GAP(SMA(3), 10, reset)");
+    }
+}
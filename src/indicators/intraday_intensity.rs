@@ -0,0 +1,282 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::helpers::Accumulator;
+use crate::indicators::SimpleMovingAverage as Sma;
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Intraday Intensity Index (II).
+///
+/// A volume indicator by David Bostian. The volume of every bar is weighted by the position of
+/// the close within the range of the bar: +1 for a close at the high, -1 for a close at the low.
+/// A positive intensity shows accumulation, a negative one distribution.
+///
+/// The indicator returns two forms:
+///
+/// * _line_ - the cumulative sum of the intensity since the first bar
+/// * _percent_ - the intensity over the last _period_ bars as a percentage of the volume
+///   traded over them, ranging from -100 to +100
+///
+/// # Formula
+///
+/// II = (2 * close - high - low) / (high - low) * volume
+///
+/// line = previous line + II
+///
+/// percent = sum(II, _period_) / sum(volume, _period_) * 100
+///
+/// II is 0 for a bar with no range, percent is 0, when no volume was traded.
+///
+/// # Parameters
+///
+/// * _period_ - number of bars of the normalized form (integer greater than 0). Default is 21.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::IntradayIntensity;
+/// use ta::{DataItem, Next};
+///
+/// let bar = DataItem::builder()
+///     .open(10.0).high(12.0).low(9.0).close(11.5).volume(300.0)
+///     .build().unwrap();
+///
+/// let mut ii = IntradayIntensity::new(21).unwrap();
+/// let out = ii.next(&bar);
+/// // (23 - 21) / 3 * 300
+/// assert_eq!(out.line, 200.0);
+/// assert_eq!(round(out.percent), 66.667);
+///
+/// fn round(x: f64) -> f64 {
+///     (x * 1000.0).round() / 1000.0
+/// }
+/// ```
+///
+/// # Links
+///
+/// * [Intraday Intensity, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:intraday_intensity)
+///
+#[doc(alias = "II")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct IntradayIntensity {
+    line: Accumulator,
+    intensity: Sma,
+    volume: Sma,
+    output: Option<IntradayIntensityOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntradayIntensityOutput {
+    /// Cumulative intensity.
+    pub line: f64,
+    /// Intensity over the window as a percentage of the volume.
+    pub percent: f64,
+}
+
+impl IntradayIntensity {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            line: Accumulator::new(false),
+            intensity: Sma::new(period)?,
+            volume: Sma::new(period)?,
+            output: None,
+        })
+    }
+
+    /// Same as `new`, with compensated summation of the line and the windows, so they do not
+    /// drift after millions of bars.
+    pub fn compensated(period: usize) -> Result<Self> {
+        Ok(Self {
+            line: Accumulator::new(true),
+            intensity: Sma::compensated(period)?,
+            volume: Sma::compensated(period)?,
+            output: None,
+        })
+    }
+}
+
+impl Period for IntradayIntensity {
+    fn period(&self) -> usize {
+        self.intensity.period()
+    }
+}
+
+impl IsReady for IntradayIntensity {
+    fn bars_until_ready(&self) -> usize {
+        self.intensity.bars_until_ready()
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for IntradayIntensity {
+    type Output = IntradayIntensityOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let range = input.high() - input.low();
+        let intensity = if range > 0.0 {
+            (2.0 * input.close() - input.high() - input.low()) / range * input.volume()
+        } else {
+            0.0
+        };
+        self.line.add(intensity);
+
+        // the ratio of the averages over the same window is the ratio of the sums
+        let average_intensity = self.intensity.next(intensity);
+        let average_volume = self.volume.next(input.volume());
+        let percent = if average_volume > 0.0 {
+            average_intensity / average_volume * 100.0
+        } else {
+            0.0
+        };
+
+        let output = IntradayIntensityOutput {
+            line: self.line.value(),
+            percent,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for IntradayIntensity {
+    type Output = IntradayIntensityOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for IntradayIntensity {
+    fn reset(&mut self) {
+        self.line.reset();
+        self.intensity.reset();
+        self.volume.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for IntradayIntensity {
+    fn heap_size(&self) -> usize {
+        self.intensity.heap_size() + self.volume.heap_size()
+    }
+}
+
+impl Default for IntradayIntensity {
+    fn default() -> Self {
+        Self::new(21).unwrap()
+    }
+}
+
+impl fmt::Display for IntradayIntensity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "II({})", self.intensity.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_hlcv_indicator!(IntradayIntensity);
+
+    fn bar(high: f64, low: f64, close: f64, volume: f64) -> Bar {
+        Bar::new().high(high).low(low).close(close).volume(volume)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(IntradayIntensity::new(0).is_err());
+        assert!(IntradayIntensity::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ii = IntradayIntensity::new(2).unwrap();
+
+        let out = ii.next(&bar(10.0, 8.0, 9.0, 100.0));
+        assert_eq!(out.line, 0.0);
+        assert_eq!(out.percent, 0.0);
+
+        let out = ii.next(&bar(12.0, 9.0, 12.0, 200.0));
+        assert_eq!(out.line, 200.0);
+        assert_eq!(round(out.percent), 66.667);
+
+        let out = ii.next(&bar(11.0, 7.0, 8.0, 100.0));
+        assert_eq!(out.line, 150.0);
+        assert_eq!(round(out.percent), 50.0);
+
+        // no range
+        let out = ii.next(&bar(11.0, 11.0, 11.0, 50.0));
+        assert_eq!(out.line, 150.0);
+        assert_eq!(round(out.percent), -33.333);
+    }
+
+    #[test]
+    fn test_no_volume() {
+        let mut ii = IntradayIntensity::new(2).unwrap();
+
+        let out = ii.next(&bar(12.0, 9.0, 12.0, 0.0));
+        assert_eq!(out.line, 0.0);
+        assert_eq!(out.percent, 0.0);
+    }
+
+    #[test]
+    fn test_compensated() {
+        let bars = [
+            bar(2.0, 1.0, 2.0, 1e16),
+            bar(2.0, 1.0, 2.0, 1.0),
+            bar(2.0, 1.0, 1.0, 1e16),
+        ];
+        let mut ii = IntradayIntensity::new(3).unwrap();
+        let mut compensated = IntradayIntensity::compensated(3).unwrap();
+        for bar in &bars {
+            ii.next(bar);
+            compensated.next(bar);
+        }
+        assert_eq!(ii.current().unwrap().line, 0.0);
+        assert_eq!(compensated.current().unwrap().line, 1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ii = IntradayIntensity::new(2).unwrap();
+
+        ii.next(&bar(12.0, 9.0, 12.0, 200.0));
+        ii.next(&bar(11.0, 7.0, 8.0, 100.0));
+
+        ii.reset();
+        let out = ii.next(&bar(11.0, 7.0, 8.0, 100.0));
+        assert_eq!(out.line, -50.0);
+        assert_eq!(out.percent, -50.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(2).low(1).close(1.5).volume(100.0);
+        assert_warm_up(&mut IntradayIntensity::new(3).unwrap(), &bar, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut IntradayIntensity::default(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        IntradayIntensity::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ii = IntradayIntensity::new(10).unwrap();
+        assert_eq!(format!("{}", ii), "II(10)");
+    }
+}
@@ -1,8 +1,15 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
-use crate::errors::Result;
+use crate::errors::{Result, TaError};
+use crate::helpers::fmt_params;
 use crate::indicators::{AverageTrueRange, ExponentialMovingAverage};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::registry::Params;
+use crate::{
+    Bands, Close, Configure, Current, High, IsReady, Low, MemoryFootprint, Next, ParamValue,
+    Parameters, Period, Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +29,9 @@ use serde::{Deserialize, Serialize};
 ///  * _KC<sub>Upper Band</sub>_ = EMA + ATR of observation * multipler (usually 2.0)
 ///  * _KC<sub>Lower Band</sub>_ = EMA - ATR of observation * multipler (usually 2.0)
 ///
+/// Bars are averaged on their typical price. A single `f64` input is the midline price itself,
+/// and the ATR falls back to the absolute changes of the inputs.
+///
 /// # Example
 ///
 ///```
@@ -54,22 +64,46 @@ pub struct KeltnerChannel {
     multiplier: f64,
     atr: AverageTrueRange,
     ema: ExponentialMovingAverage,
+    output: Option<KeltnerChannelOutput>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct KeltnerChannelOutput {
     pub average: f64,
     pub upper: f64,
     pub lower: f64,
 }
 
+impl Bands for KeltnerChannelOutput {
+    fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    fn middle(&self) -> f64 {
+        self.average
+    }
+
+    fn lower(&self) -> f64 {
+        self.lower
+    }
+}
+
 impl KeltnerChannel {
     pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        if multiplier.is_nan() || multiplier <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "multiplier",
+                multiplier,
+                "must be greater than 0",
+            ));
+        }
         Ok(Self {
             period,
             multiplier,
             atr: AverageTrueRange::new(period)?,
             ema: ExponentialMovingAverage::new(period)?,
+            output: None,
         })
     }
 
@@ -84,6 +118,33 @@ impl Period for KeltnerChannel {
     }
 }
 
+impl Configure for KeltnerChannel {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        self.ema.set_period(period)?;
+        self.atr.set_period(period)?;
+        self.period = period;
+        Ok(())
+    }
+
+    fn set_multiplier(&mut self, multiplier: f64) -> Result<()> {
+        if multiplier.is_nan() || multiplier <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "multiplier",
+                multiplier,
+                "must be greater than 0",
+            ));
+        }
+        self.multiplier = multiplier;
+        Ok(())
+    }
+}
+
+impl IsReady for KeltnerChannel {
+    fn bars_until_ready(&self) -> usize {
+        self.ema.bars_until_ready()
+    }
+}
+
 impl Next<f64> for KeltnerChannel {
     type Output = KeltnerChannelOutput;
 
@@ -91,11 +152,13 @@ impl Next<f64> for KeltnerChannel {
         let atr = self.atr.next(input);
         let average = self.ema.next(input);
 
-        Self::Output {
+        let output = Self::Output {
             average,
             upper: average + atr * self.multiplier,
             lower: average - atr * self.multiplier,
-        }
+        };
+        self.output = Some(output);
+        output
     }
 }
 
@@ -108,11 +171,21 @@ impl<T: Close + High + Low> Next<&T> for KeltnerChannel {
         let average = self.ema.next(typical_price);
         let atr = self.atr.next(input);
 
-        Self::Output {
+        let output = Self::Output {
             average,
             upper: average + atr * self.multiplier,
             lower: average - atr * self.multiplier,
-        }
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for KeltnerChannel {
+    type Output = KeltnerChannelOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
@@ -120,6 +193,30 @@ impl Reset for KeltnerChannel {
     fn reset(&mut self) {
         self.atr.reset();
         self.ema.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for KeltnerChannel {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Parameters for KeltnerChannel {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![
+            ("period", ParamValue::Period(self.period)),
+            ("multiplier", ParamValue::Float(self.multiplier)),
+        ]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period", "multiplier"])?;
+        Self::new(
+            params.period("period", 10)?,
+            params.float("multiplier", 2.0)?,
+        )
     }
 }
 
@@ -131,7 +228,7 @@ impl Default for KeltnerChannel {
 
 impl fmt::Display for KeltnerChannel {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "KC({}, {})", self.period, self.multiplier)
+        fmt_params(f, "KC", &self.params())
     }
 }
 
@@ -145,6 +242,9 @@ mod tests {
     #[test]
     fn test_new() {
         assert!(KeltnerChannel::new(0, 2_f64).is_err());
+        assert!(KeltnerChannel::new(2, 0.0).is_err());
+        assert!(KeltnerChannel::new(2, -2.0).is_err());
+        assert!(KeltnerChannel::new(2, f64::NAN).is_err());
         assert!(KeltnerChannel::new(1, 2_f64).is_ok());
         assert!(KeltnerChannel::new(2, 2_f64).is_ok());
     }
@@ -224,6 +324,42 @@ mod tests {
         assert_eq!(out.upper, 3.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut KeltnerChannel::new(3, 2.0).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut KeltnerChannel::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_configure() {
+        let mut kc = KeltnerChannel::new(5, 2.0).unwrap();
+        for x in [2.0, 5.0, 1.0] {
+            kc.next(x);
+        }
+        kc.set_period(2).unwrap();
+        kc.set_multiplier(1.5).unwrap();
+        assert_eq!(kc.period(), 2);
+        assert_eq!(kc.multiplier(), 1.5);
+        assert!(kc.is_ready());
+
+        let mut wide = kc.clone();
+        wide.set_multiplier(3.0).unwrap();
+        let (out, wide_out) = (kc.next(4.0), wide.next(4.0));
+        assert_eq!(out.average, wide_out.average);
+        assert_eq!(
+            round(wide_out.upper - wide_out.average),
+            round(2.0 * (out.upper - out.average))
+        );
+
+        assert!(kc.set_multiplier(-1.0).is_err());
+        assert!(kc.set_period(0).is_err());
+        assert_eq!(kc.multiplier(), 1.5);
+    }
+
     #[test]
     fn test_default() {
         KeltnerChannel::default();
@@ -0,0 +1,450 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::{Pivot, PivotHighLow};
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Market structure tracker.
+///
+/// Follows the sequence of swing highs and lows found by [PivotHighLow](struct.PivotHighLow.html)
+/// and labels every confirmed swing relative to the previous swing of the same side:
+///
+/// * higher high (HH) or lower high (LH) for swing highs
+/// * higher low (HL) or lower low (LL) for swing lows
+///
+/// A swing equal to the previous one counts as a lower high or a higher low, because it does
+/// not extend the structure. The very first swing of each side has no label.
+///
+/// The structural trend becomes bullish after a higher high and a higher low, and bearish after
+/// a lower high and a lower low. A break of structure (BOS) happens, when the price closes above
+/// the last swing high or below the last swing low. It sets the trend to the direction of the
+/// break and is reported only once per swing level.
+///
+/// With `Next<f64>` swings and breaks are found in the same series. With `Next<&T>` swings are
+/// found in the highs and lows, and breaks are checked with the close.
+///
+/// # Parameters
+///
+/// * _left_ - number of bars before a swing (integer greater than 0). Default is 5.
+/// * _right_ - number of bars after a swing (integer greater than 0). Default is 5.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{MarketStructure, StructureTrend};
+/// use ta::Next;
+///
+/// let mut structure = MarketStructure::new(1, 1).unwrap();
+///
+/// structure.next(1.0);
+/// structure.next(3.0);
+/// let out = structure.next(2.0);
+/// assert_eq!(out.high.unwrap().value, 3.0);
+/// assert_eq!(out.trend, StructureTrend::Neutral);
+///
+/// // close above the last swing high
+/// let out = structure.next(4.0);
+/// assert_eq!(out.break_of_structure.unwrap().level, 3.0);
+/// assert_eq!(out.trend, StructureTrend::Bullish);
+/// ```
+///
+/// # Links
+///
+/// * [Market structure, Investopedia](https://www.investopedia.com/terms/m/marketstructure.asp)
+///
+#[doc(alias = "BOS")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct MarketStructure {
+    pivots: PivotHighLow,
+    last_high: Option<f64>,
+    last_low: Option<f64>,
+    high_label: Option<SwingLabel>,
+    low_label: Option<SwingLabel>,
+    high_broken: bool,
+    low_broken: bool,
+    trend: StructureTrend,
+    output: Option<MarketStructureOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwingLabel {
+    HigherHigh,
+    LowerHigh,
+    HigherLow,
+    LowerLow,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StructureTrend {
+    Bullish,
+    Bearish,
+    #[default]
+    Neutral,
+}
+
+/// Newly confirmed swing.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StructureSwing {
+    pub value: f64,
+    /// Number of bars from the swing to the current bar.
+    pub bars_ago: usize,
+    /// `None` for the first swing of its side.
+    pub label: Option<SwingLabel>,
+}
+
+/// Close beyond a swing level.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StructureBreak {
+    /// Direction of the break, either bullish or bearish.
+    pub trend: StructureTrend,
+    /// The swing level that was broken.
+    pub level: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MarketStructureOutput {
+    pub high: Option<StructureSwing>,
+    pub low: Option<StructureSwing>,
+    pub trend: StructureTrend,
+    pub break_of_structure: Option<StructureBreak>,
+}
+
+impl MarketStructure {
+    pub fn new(left: usize, right: usize) -> Result<Self> {
+        Ok(Self {
+            pivots: PivotHighLow::new(left, right)?,
+            last_high: None,
+            last_low: None,
+            high_label: None,
+            low_label: None,
+            high_broken: false,
+            low_broken: false,
+            trend: StructureTrend::Neutral,
+            output: None,
+        })
+    }
+
+    pub fn left(&self) -> usize {
+        self.pivots.left()
+    }
+
+    pub fn right(&self) -> usize {
+        self.pivots.right()
+    }
+
+    pub fn trend(&self) -> StructureTrend {
+        self.trend
+    }
+
+    fn swing_high(&mut self, pivot: Pivot) -> StructureSwing {
+        let label = self.last_high.map(|last| {
+            if pivot.value > last {
+                SwingLabel::HigherHigh
+            } else {
+                SwingLabel::LowerHigh
+            }
+        });
+        self.last_high = Some(pivot.value);
+        self.high_label = label;
+        self.high_broken = false;
+        StructureSwing {
+            value: pivot.value,
+            bars_ago: pivot.bars_ago,
+            label,
+        }
+    }
+
+    fn swing_low(&mut self, pivot: Pivot) -> StructureSwing {
+        let label = self.last_low.map(|last| {
+            if pivot.value < last {
+                SwingLabel::LowerLow
+            } else {
+                SwingLabel::HigherLow
+            }
+        });
+        self.last_low = Some(pivot.value);
+        self.low_label = label;
+        self.low_broken = false;
+        StructureSwing {
+            value: pivot.value,
+            bars_ago: pivot.bars_ago,
+            label,
+        }
+    }
+
+    fn break_of_structure(&mut self, close: f64) -> Option<StructureBreak> {
+        match (self.last_high, self.last_low) {
+            (Some(level), _) if !self.high_broken && close > level => {
+                self.high_broken = true;
+                Some(StructureBreak {
+                    trend: StructureTrend::Bullish,
+                    level,
+                })
+            }
+            (_, Some(level)) if !self.low_broken && close < level => {
+                self.low_broken = true;
+                Some(StructureBreak {
+                    trend: StructureTrend::Bearish,
+                    level,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn update(
+        &mut self,
+        high: Option<Pivot>,
+        low: Option<Pivot>,
+        close: f64,
+    ) -> MarketStructureOutput {
+        let high = high.map(|pivot| self.swing_high(pivot));
+        let low = low.map(|pivot| self.swing_low(pivot));
+
+        if high.is_some() || low.is_some() {
+            match (self.high_label, self.low_label) {
+                (Some(SwingLabel::HigherHigh), Some(SwingLabel::HigherLow)) => {
+                    self.trend = StructureTrend::Bullish
+                }
+                (Some(SwingLabel::LowerHigh), Some(SwingLabel::LowerLow)) => {
+                    self.trend = StructureTrend::Bearish
+                }
+                _ => {}
+            }
+        }
+
+        let break_of_structure = self.break_of_structure(close);
+        if let Some(brk) = break_of_structure {
+            self.trend = brk.trend;
+        }
+
+        let output = MarketStructureOutput {
+            high,
+            low,
+            trend: self.trend,
+            break_of_structure,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Period for MarketStructure {
+    fn period(&self) -> usize {
+        self.pivots.period()
+    }
+}
+
+impl IsReady for MarketStructure {
+    fn bars_until_ready(&self) -> usize {
+        self.pivots.bars_until_ready()
+    }
+}
+
+impl Next<f64> for MarketStructure {
+    type Output = MarketStructureOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let pivots = self.pivots.next(input);
+        self.update(pivots.high, pivots.low, input)
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for MarketStructure {
+    type Output = MarketStructureOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let pivots = self.pivots.next(input);
+        self.update(pivots.high, pivots.low, input.close())
+    }
+}
+
+impl Current for MarketStructure {
+    type Output = MarketStructureOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for MarketStructure {
+    fn reset(&mut self) {
+        self.pivots.reset();
+        self.last_high = None;
+        self.last_low = None;
+        self.high_label = None;
+        self.low_label = None;
+        self.high_broken = false;
+        self.low_broken = false;
+        self.trend = StructureTrend::Neutral;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for MarketStructure {
+    fn heap_size(&self) -> usize {
+        self.pivots.heap_size()
+    }
+}
+
+impl Default for MarketStructure {
+    fn default() -> Self {
+        Self::new(5, 5).unwrap()
+    }
+}
+
+impl fmt::Display for MarketStructure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MS({}, {})", self.left(), self.right())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(MarketStructure);
+
+    #[test]
+    fn test_new() {
+        assert!(MarketStructure::new(0, 1).is_err());
+        assert!(MarketStructure::new(1, 0).is_err());
+        assert!(MarketStructure::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut structure = MarketStructure::new(1, 1).unwrap();
+
+        let outputs: Vec<_> = [1.0, 3.0, 2.0, 4.0, 3.0, 5.0, 4.0, 1.0, 2.0, 1.5]
+            .iter()
+            .map(|&x| structure.next(x))
+            .collect();
+
+        assert_eq!(outputs[1], MarketStructureOutput::default());
+        assert_eq!(outputs[2].high.unwrap().label, None);
+        assert_eq!(outputs[3].low.unwrap().label, None);
+
+        // 4.0 closes above the swing high 3.0
+        let brk = outputs[3].break_of_structure.unwrap();
+        assert_eq!(brk.trend, StructureTrend::Bullish);
+        assert_eq!(brk.level, 3.0);
+        assert_eq!(outputs[3].trend, StructureTrend::Bullish);
+
+        assert_eq!(outputs[4].high.unwrap().label, Some(SwingLabel::HigherHigh));
+        assert_eq!(outputs[4].break_of_structure, None);
+        assert_eq!(outputs[5].low.unwrap().label, Some(SwingLabel::HigherLow));
+        assert_eq!(outputs[5].break_of_structure.unwrap().level, 4.0);
+        assert_eq!(outputs[6].high.unwrap().label, Some(SwingLabel::HigherHigh));
+
+        // 1.0 closes below the swing low 3.0
+        let brk = outputs[7].break_of_structure.unwrap();
+        assert_eq!(brk.trend, StructureTrend::Bearish);
+        assert_eq!(brk.level, 3.0);
+        assert_eq!(outputs[7].trend, StructureTrend::Bearish);
+
+        assert_eq!(outputs[8].low.unwrap().label, Some(SwingLabel::LowerLow));
+        assert_eq!(outputs[9].high.unwrap().label, Some(SwingLabel::LowerHigh));
+        assert_eq!(outputs[9].trend, StructureTrend::Bearish);
+        assert_eq!(structure.trend(), StructureTrend::Bearish);
+    }
+
+    #[test]
+    fn test_trend_from_swings() {
+        let mut structure = MarketStructure::new(1, 1).unwrap();
+
+        // the closes never break the last swing levels
+        let bars = [
+            (5.0, 4.0, 4.5),
+            (4.5, 3.0, 3.5),
+            (6.0, 4.0, 5.0),
+            (5.5, 3.5, 4.5),
+            (7.0, 5.0, 5.5),
+            (6.0, 4.5, 5.0),
+        ];
+        for &(high, low, close) in &bars {
+            let out = structure.next(&Bar::new().high(high).low(low).close(close));
+            assert_eq!(out.break_of_structure, None);
+        }
+        // lows 3.0 -> 3.5 and highs 6.0 -> 7.0
+        assert_eq!(structure.high_label, Some(SwingLabel::HigherHigh));
+        assert_eq!(structure.low_label, Some(SwingLabel::HigherLow));
+        assert_eq!(structure.trend(), StructureTrend::Bullish);
+    }
+
+    #[test]
+    fn test_break_reported_once() {
+        let mut structure = MarketStructure::new(1, 1).unwrap();
+
+        structure.next(1.0);
+        structure.next(3.0);
+        structure.next(2.0);
+        assert!(structure.next(4.0).break_of_structure.is_some());
+        assert!(structure.next(4.5).break_of_structure.is_none());
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut structure = MarketStructure::new(1, 1).unwrap();
+
+        let bar = |high: f64, low: f64, close: f64| Bar::new().high(high).low(low).close(close);
+
+        structure.next(&bar(10.0, 9.0, 9.5));
+        structure.next(&bar(12.0, 10.0, 11.0));
+        let out = structure.next(&bar(11.0, 10.0, 10.5));
+        assert_eq!(out.high.unwrap().value, 12.0);
+        // the high pierces the swing high, but the close does not
+        assert_eq!(
+            structure.next(&bar(12.5, 11.0, 11.5)).break_of_structure,
+            None
+        );
+        let out = structure.next(&bar(13.0, 12.0, 12.5));
+        assert_eq!(out.break_of_structure.unwrap().level, 12.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut structure = MarketStructure::new(1, 1).unwrap();
+
+        structure.next(1.0);
+        structure.next(3.0);
+        structure.next(2.0);
+        structure.next(4.0);
+
+        structure.reset();
+        assert_eq!(structure.trend(), StructureTrend::Neutral);
+        assert_eq!(structure.next(1.0), MarketStructureOutput::default());
+        assert_eq!(structure.next(3.0), MarketStructureOutput::default());
+        assert_eq!(structure.next(2.0).high.unwrap().label, None);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut MarketStructure::new(2, 1).unwrap(), 1.0, 4);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut MarketStructure::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        MarketStructure::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let structure = MarketStructure::new(3, 2).unwrap();
+        assert_eq!(format!("{}", structure), "MS(3, 2)");
+    }
+}
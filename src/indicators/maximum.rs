@@ -1,12 +1,22 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{High, Next, Period, Reset};
+use crate::helpers::fmt_params;
+use crate::registry::Params;
+use crate::rolling_extremum::RollingExtremum;
+use crate::{
+    Current, High, IsReady, MemoryFootprint, Next, NextBatch, ParamValue, Parameters, Period, Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Returns the highest value in a given time frame.
 ///
+/// Only the values, which can still become the highest, are kept, so an update takes O(1)
+/// amortized time for any period, e.g. for 252 bar highs.
+///
 /// # Parameters
 ///
 /// * _period_ - size of the time frame (integer greater than 0). Default value is 14.
@@ -28,37 +38,21 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone)]
 pub struct Maximum {
     period: usize,
-    max_index: usize,
-    cur_index: usize,
-    deque: Box<[f64]>,
+    window: RollingExtremum,
+    output: Option<f64>,
 }
 
 impl Maximum {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::zero_period("period")),
             _ => Ok(Self {
                 period,
-                max_index: 0,
-                cur_index: 0,
-                deque: vec![f64::NEG_INFINITY; period].into_boxed_slice(),
+                window: RollingExtremum::highest(period),
+                output: None,
             }),
         }
     }
-
-    fn find_max_index(&self) -> usize {
-        let mut max = f64::NEG_INFINITY;
-        let mut index: usize = 0;
-
-        for (i, &val) in self.deque.iter().enumerate() {
-            if val > max {
-                max = val;
-                index = i;
-            }
-        }
-
-        index
-    }
 }
 
 impl Period for Maximum {
@@ -67,28 +61,24 @@ impl Period for Maximum {
     }
 }
 
+impl IsReady for Maximum {
+    fn bars_until_ready(&self) -> usize {
+        self.period - self.window.len()
+    }
+}
+
 impl Next<f64> for Maximum {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        self.deque[self.cur_index] = input;
-
-        if input > self.deque[self.max_index] {
-            self.max_index = self.cur_index;
-        } else if self.max_index == self.cur_index {
-            self.max_index = self.find_max_index();
-        }
-
-        self.cur_index = if self.cur_index + 1 < self.period {
-            self.cur_index + 1
-        } else {
-            0
-        };
-
-        self.deque[self.max_index]
+        let output = self.window.push(input);
+        self.output = Some(output);
+        output
     }
 }
 
+impl NextBatch<f64> for Maximum {}
+
 impl<T: High> Next<&T> for Maximum {
     type Output = f64;
 
@@ -97,11 +87,35 @@ impl<T: High> Next<&T> for Maximum {
     }
 }
 
+impl Current for Maximum {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
 impl Reset for Maximum {
     fn reset(&mut self) {
-        for i in 0..self.period {
-            self.deque[i] = f64::NEG_INFINITY;
-        }
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for Maximum {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl Parameters for Maximum {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::new(params.period("period", 14)?)
     }
 }
 
@@ -113,7 +127,7 @@ impl Default for Maximum {
 
 impl fmt::Display for Maximum {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "MAX({})", self.period)
+        fmt_params(f, "MAX", &self.params())
     }
 }
 
@@ -170,6 +184,16 @@ mod tests {
         assert_eq!(max.next(4.0), 4.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut Maximum::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut Maximum::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_default() {
         Maximum::default();
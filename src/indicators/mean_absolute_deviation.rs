@@ -1,10 +1,17 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::helpers::fmt_params;
+use crate::registry::Params;
+use crate::ring_buffer::RingBuffer;
+use crate::{
+    Close, Current, IsReady, MemoryFootprint, Next, ParamValue, Parameters, Period, Reset,
+};
 
 /// Mean Absolute Deviation (MAD)
 ///
@@ -31,22 +38,20 @@ use crate::{Close, Next, Period, Reset};
 #[derive(Debug, Clone)]
 pub struct MeanAbsoluteDeviation {
     period: usize,
-    index: usize,
-    count: usize,
     sum: f64,
-    deque: Box<[f64]>,
+    window: RingBuffer,
+    output: Option<f64>,
 }
 
 impl MeanAbsoluteDeviation {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::zero_period("period")),
             _ => Ok(Self {
                 period,
-                index: 0,
-                count: 0,
                 sum: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                window: RingBuffer::new(period),
+                output: None,
             }),
         }
     }
@@ -58,31 +63,31 @@ impl Period for MeanAbsoluteDeviation {
     }
 }
 
+impl IsReady for MeanAbsoluteDeviation {
+    fn bars_until_ready(&self) -> usize {
+        self.period - self.window.len()
+    }
+}
+
 impl Next<f64> for MeanAbsoluteDeviation {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        self.sum = if self.count < self.period {
-            self.count = self.count + 1;
-            self.sum + input
-        } else {
-            self.sum + input - self.deque[self.index]
+        self.sum = match self.window.push(input) {
+            Some(oldest) => self.sum + input - oldest,
+            None => self.sum + input,
         };
 
-        self.deque[self.index] = input;
-        self.index = if self.index + 1 < self.period {
-            self.index + 1
-        } else {
-            0
-        };
-
-        let mean = self.sum / self.count as f64;
+        let count = self.window.len() as f64;
+        let mean = self.sum / count;
 
         let mut mad = 0.0;
-        for value in &self.deque[..self.count] {
+        for value in self.window.iter() {
             mad += (value - mean).abs();
         }
-        mad / self.count as f64
+        let output = mad / count;
+        self.output = Some(output);
+        output
     }
 }
 
@@ -94,14 +99,36 @@ impl<T: Close> Next<&T> for MeanAbsoluteDeviation {
     }
 }
 
+impl Current for MeanAbsoluteDeviation {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
 impl Reset for MeanAbsoluteDeviation {
     fn reset(&mut self) {
-        self.index = 0;
-        self.count = 0;
         self.sum = 0.0;
-        for i in 0..self.period {
-            self.deque[i] = 0.0;
-        }
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for MeanAbsoluteDeviation {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl Parameters for MeanAbsoluteDeviation {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::new(params.period("period", 9)?)
     }
 }
 
@@ -113,7 +140,7 @@ impl Default for MeanAbsoluteDeviation {
 
 impl fmt::Display for MeanAbsoluteDeviation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "MAD({})", self.period)
+        fmt_params(f, "MAD", &self.params())
     }
 }
 
@@ -155,6 +182,16 @@ mod tests {
         assert_eq!(round(mad.next(4.0)), 1.25);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut MeanAbsoluteDeviation::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut MeanAbsoluteDeviation::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_default() {
         MeanAbsoluteDeviation::default();
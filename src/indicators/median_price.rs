@@ -0,0 +1,131 @@
+use core::fmt;
+
+use crate::{Current, High, IsReady, Low, MemoryFootprint, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Median price (MP).
+///
+/// Midpoint of the high and low prices of a bar, also known as HL2.
+///
+/// # Formula
+///
+/// MP = (high + low) / 2
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{MedianPrice, SimpleMovingAverage};
+/// use ta::{DataItem, Next};
+///
+/// let bar = DataItem::builder()
+///     .open(10.0).high(12.0).low(9.0).close(9.0).volume(100.0)
+///     .build().unwrap();
+///
+/// let mut price = MedianPrice::new();
+/// let mut sma = SimpleMovingAverage::new(3).unwrap();
+/// assert_eq!(sma.next(price.next(&bar)), 10.5);
+/// ```
+#[doc(alias = "MP")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct MedianPrice {
+    output: Option<f64>,
+}
+
+impl MedianPrice {
+    pub fn new() -> Self {
+        Self { output: None }
+    }
+}
+
+impl IsReady for MedianPrice {
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
+}
+
+impl<T: High + Low> Next<&T> for MedianPrice {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let output = (input.high() + input.low()) / 2.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for MedianPrice {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for MedianPrice {
+    fn reset(&mut self) {
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for MedianPrice {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for MedianPrice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for MedianPrice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MP")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_hlcv_indicator!(MedianPrice);
+
+    #[test]
+    fn test_next() {
+        let mut price = MedianPrice::new();
+
+        assert_eq!(price.next(&Bar::new().high(12.0).low(9.0)), 10.5);
+        assert_eq!(
+            round(price.next(&Bar::new().high(10.0).low(8.0).close(9.5))),
+            9.0
+        );
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert!(MedianPrice::new().is_ready());
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut MedianPrice::default(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        MedianPrice::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", MedianPrice::new()), "MP");
+    }
+}
@@ -1,12 +1,22 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Low, Next, Period, Reset};
+use crate::helpers::fmt_params;
+use crate::registry::Params;
+use crate::rolling_extremum::RollingExtremum;
+use crate::{
+    Current, IsReady, Low, MemoryFootprint, Next, NextBatch, ParamValue, Parameters, Period, Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Returns the lowest value in a given time frame.
 ///
+/// Only the values, which can still become the lowest, are kept, so an update takes O(1)
+/// amortized time for any period.
+///
 /// # Parameters
 ///
 /// * _period_ - size of the time frame (integer greater than 0). Default value is 14.
@@ -27,37 +37,21 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone)]
 pub struct Minimum {
     period: usize,
-    min_index: usize,
-    cur_index: usize,
-    deque: Box<[f64]>,
+    window: RollingExtremum,
+    output: Option<f64>,
 }
 
 impl Minimum {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::zero_period("period")),
             _ => Ok(Self {
                 period,
-                min_index: 0,
-                cur_index: 0,
-                deque: vec![f64::INFINITY; period].into_boxed_slice(),
+                window: RollingExtremum::lowest(period),
+                output: None,
             }),
         }
     }
-
-    fn find_min_index(&self) -> usize {
-        let mut min = f64::INFINITY;
-        let mut index: usize = 0;
-
-        for (i, &val) in self.deque.iter().enumerate() {
-            if val < min {
-                min = val;
-                index = i;
-            }
-        }
-
-        index
-    }
 }
 
 impl Period for Minimum {
@@ -66,28 +60,24 @@ impl Period for Minimum {
     }
 }
 
+impl IsReady for Minimum {
+    fn bars_until_ready(&self) -> usize {
+        self.period - self.window.len()
+    }
+}
+
 impl Next<f64> for Minimum {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        self.deque[self.cur_index] = input;
-
-        if input < self.deque[self.min_index] {
-            self.min_index = self.cur_index;
-        } else if self.min_index == self.cur_index {
-            self.min_index = self.find_min_index();
-        }
-
-        self.cur_index = if self.cur_index + 1 < self.period {
-            self.cur_index + 1
-        } else {
-            0
-        };
-
-        self.deque[self.min_index]
+        let output = self.window.push(input);
+        self.output = Some(output);
+        output
     }
 }
 
+impl NextBatch<f64> for Minimum {}
+
 impl<T: Low> Next<&T> for Minimum {
     type Output = f64;
 
@@ -96,11 +86,35 @@ impl<T: Low> Next<&T> for Minimum {
     }
 }
 
+impl Current for Minimum {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
 impl Reset for Minimum {
     fn reset(&mut self) {
-        for i in 0..self.period {
-            self.deque[i] = f64::INFINITY;
-        }
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for Minimum {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl Parameters for Minimum {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::new(params.period("period", 14)?)
     }
 }
 
@@ -112,7 +126,7 @@ impl Default for Minimum {
 
 impl fmt::Display for Minimum {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "MIN({})", self.period)
+        fmt_params(f, "MIN", &self.params())
     }
 }
 
@@ -170,6 +184,16 @@ mod tests {
         assert_eq!(min.next(8.0), 8.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut Minimum::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut Minimum::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_default() {
         Minimum::default();
@@ -1,3 +1,12 @@
+mod typical_price;
+pub use self::typical_price::TypicalPrice;
+
+mod median_price;
+pub use self::median_price::MedianPrice;
+
+mod weighted_close;
+pub use self::weighted_close::WeightedClose;
+
 mod exponential_moving_average;
 pub use self::exponential_moving_average::ExponentialMovingAverage;
 
@@ -13,6 +22,34 @@ pub use self::standard_deviation::StandardDeviation;
 mod mean_absolute_deviation;
 pub use self::mean_absolute_deviation::MeanAbsoluteDeviation;
 
+mod order_statistics;
+
+mod rolling_median;
+pub use self::rolling_median::RollingMedian;
+
+mod rolling_quantile;
+pub use self::rolling_quantile::RollingQuantile;
+
+mod rolling_moments;
+
+mod rolling_skewness;
+pub use self::rolling_skewness::RollingSkewness;
+
+mod rolling_kurtosis;
+pub use self::rolling_kurtosis::RollingKurtosis;
+
+mod rolling_sharpe;
+pub use self::rolling_sharpe::RollingSharpe;
+
+mod rolling_drawdown;
+pub use self::rolling_drawdown::{RollingDrawdown, RollingDrawdownOutput};
+
+mod wavelet_decomposition;
+pub use self::wavelet_decomposition::{WaveletDecomposition, WaveletDecompositionOutput};
+
+mod rolling_spectrum;
+pub use self::rolling_spectrum::{Detrend, RollingSpectrum, RollingSpectrumOutput};
+
 mod relative_strength_index;
 pub use self::relative_strength_index::RelativeStrengthIndex;
 
@@ -28,17 +65,32 @@ pub use self::fast_stochastic::FastStochastic;
 mod slow_stochastic;
 pub use self::slow_stochastic::SlowStochastic;
 
+mod stochastic_momentum_index;
+pub use self::stochastic_momentum_index::{StochasticMomentumIndex, StochasticMomentumIndexOutput};
+
+mod de_marker;
+pub use self::de_marker::DeMarker;
+
 mod true_range;
 pub use self::true_range::TrueRange;
 
 mod average_true_range;
 pub use self::average_true_range::AverageTrueRange;
 
+mod normalized_average_true_range;
+pub use self::normalized_average_true_range::NormalizedAverageTrueRange;
+
+mod average_daily_range;
+pub use self::average_daily_range::{AverageDailyRange, AverageDailyRangeOutput};
+
 mod moving_average_convergence_divergence;
 pub use self::moving_average_convergence_divergence::{
     MovingAverageConvergenceDivergence, MovingAverageConvergenceDivergenceOutput,
 };
 
+mod elder_impulse;
+pub use self::elder_impulse::{ElderImpulse, ElderImpulseOutput, ImpulseColor};
+
 mod percentage_price_oscillator;
 pub use self::percentage_price_oscillator::{
     PercentagePriceOscillator, PercentagePriceOscillatorOutput,
@@ -47,26 +99,109 @@ pub use self::percentage_price_oscillator::{
 mod commodity_channel_index;
 pub use self::commodity_channel_index::CommodityChannelIndex;
 
+mod wave_trend;
+pub use self::wave_trend::{WaveTrend, WaveTrendCross, WaveTrendOutput};
+
+mod cross;
+pub use self::cross::{Cross, CrossEvent, Crossover};
+
 mod efficiency_ratio;
 pub use self::efficiency_ratio::EfficiencyRatio;
 
 mod bollinger_bands;
 pub use self::bollinger_bands::{BollingerBands, BollingerBandsOutput};
 
+mod williams_vix_fix;
+pub use self::williams_vix_fix::{WilliamsVixFix, WilliamsVixFixOutput};
+
+mod ttm_squeeze;
+pub use self::ttm_squeeze::{TtmSqueeze, TtmSqueezeOutput};
+
+mod band_position;
+pub use self::band_position::{BandPosition, BandPositionOutput};
+
+mod gap_guard;
+pub use self::gap_guard::{GapGuard, GapGuardOutput, GapPolicy};
+
 mod chandelier_exit;
 pub use self::chandelier_exit::{ChandelierExit, ChandelierExitOutput};
 
+mod volatility_stop;
+pub use self::volatility_stop::{VolatilityStop, VolatilityStopOutput};
+
 mod keltner_channel;
 pub use self::keltner_channel::{KeltnerChannel, KeltnerChannelOutput};
 
 mod rate_of_change;
 pub use self::rate_of_change::RateOfChange;
 
+mod volatility_adjusted_rate_of_change;
+pub use self::volatility_adjusted_rate_of_change::VolatilityAdjustedRateOfChange;
+
 mod money_flow_index;
 pub use self::money_flow_index::MoneyFlowIndex;
 
 mod on_balance_volume;
 mod volume_weighted_average_price;
-pub use self::volume_weighted_average_price::VolumeWeightedAveragePrice;
+pub use self::volume_weighted_average_price::{
+    VolumeWeightedAveragePrice, VolumeWeightedAveragePriceBands,
+};
+
+mod time_decayed_volume_weighted_average_price;
+pub use self::time_decayed_volume_weighted_average_price::TimeDecayedVolumeWeightedAveragePrice;
 
 pub use self::on_balance_volume::OnBalanceVolume;
+
+mod intraday_intensity;
+pub use self::intraday_intensity::{IntradayIntensity, IntradayIntensityOutput};
+
+mod twiggs_money_flow;
+pub use self::twiggs_money_flow::TwiggsMoneyFlow;
+
+mod volume_zone_oscillator;
+pub use self::volume_zone_oscillator::VolumeZoneOscillator;
+
+mod cumulative_delta;
+pub use self::cumulative_delta::CumulativeDelta;
+
+mod rolling_delta;
+pub use self::rolling_delta::RollingDelta;
+
+mod prior_session_levels;
+pub use self::prior_session_levels::{PriorSessionLevels, PriorSessionLevelsOutput};
+
+mod session_stats;
+pub use self::session_stats::{SessionStats, SessionStatsOutput};
+
+pub mod breadth;
+pub mod fixed;
+
+mod volume_weighted_moving_average;
+pub use self::volume_weighted_moving_average::VolumeWeightedMovingAverage;
+
+mod volume_weighted_macd;
+pub use self::volume_weighted_macd::{VolumeWeightedMacd, VolumeWeightedMacdOutput};
+
+mod momentum_score;
+pub use self::momentum_score::MomentumScore;
+
+mod divergence_detector;
+pub use self::divergence_detector::{Divergence, DivergenceDetector, DivergenceKind, SwingPair};
+
+mod rolling_ic;
+pub use self::rolling_ic::RollingIC;
+
+mod pivot_high_low;
+pub use self::pivot_high_low::{Pivot, PivotHighLow, PivotHighLowOutput};
+
+mod market_structure;
+pub use self::market_structure::{
+    MarketStructure, MarketStructureOutput, StructureBreak, StructureSwing, StructureTrend,
+    SwingLabel,
+};
+
+mod fibonacci_levels;
+pub use self::fibonacci_levels::{
+    FibonacciExtensions, FibonacciKind, FibonacciLevel, FibonacciLevels, FibonacciRetracements,
+    FibonacciZone, SwingDirection,
+};
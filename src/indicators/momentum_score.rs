@@ -0,0 +1,330 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::ring_buffer::RingBuffer;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Composite momentum score.
+///
+/// Weighted blend of returns over several horizons, where the most recent _skip_ periods are
+/// excluded from every horizon. The classic "12-1" momentum factor used for monthly rotation
+/// on resampled bars is the return from 12 periods ago to 1 period ago.
+///
+/// # Formula
+///
+/// R<sub>i</sub> = (P<sub>t-skip</sub> - P<sub>t-n<sub>i</sub></sub>) / P<sub>t-n<sub>i</sub></sub> * 100
+///
+/// Score = Σ(w<sub>i</sub> * R<sub>i</sub>) / Σ(w<sub>i</sub>)
+///
+/// Where:
+///
+/// * _n<sub>i</sub>_ - lookback of the horizon _i_
+/// * _w<sub>i</sub>_ - weight of the horizon _i_
+/// * _skip_ - number of the most recent periods left out
+///
+/// Until enough history is collected, the oldest available price is used instead of
+/// P<sub>t-n<sub>i</sub></sub> (the same way as in [RateOfChange](struct.RateOfChange.html)).
+///
+/// # Parameters
+///
+/// * _horizons_ - pairs of lookback (integer greater than _skip_) and weight (non-negative,
+///   with a positive total)
+/// * _skip_ - number of the most recent periods to skip. Default is 1.
+///
+/// Default is the 12-1 momentum.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::MomentumScore;
+/// use ta::Next;
+///
+/// // 3-1 momentum
+/// let mut score = MomentumScore::new(3, 1).unwrap();
+/// assert_eq!(score.next(10.0), 0.0);
+/// assert_eq!(score.next(11.0), 0.0);
+/// assert_eq!(score.next(12.0), 10.0);  // 11 / 10
+/// assert_eq!(score.next(20.0), 20.0);  // 12 / 10
+/// assert_eq!(score.next(9.0).round(), 82.0);  // 20 / 11
+///
+/// // equal blend of 2-0 and 4-0 returns
+/// let mut score = MomentumScore::with_horizons(&[(2, 1.0), (4, 1.0)], 0).unwrap();
+/// for price in &[10.0, 20.0, 20.0, 10.0] {
+///     score.next(*price);
+/// }
+/// assert_eq!(score.next(40.0), 200.0);  // (100% + 300%) / 2
+/// ```
+///
+/// # Links
+///
+/// * [Momentum investing, Wikipedia](https://en.wikipedia.org/wiki/Momentum_investing)
+///
+#[doc(alias = "MOM")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct MomentumScore {
+    horizons: Vec<(usize, f64)>,
+    total_weight: f64,
+    skip: usize,
+    window: RingBuffer,
+    output: Option<f64>,
+}
+
+impl MomentumScore {
+    /// Creates a single horizon score, e.g. `new(12, 1)` for the 12-1 momentum.
+    pub fn new(lookback: usize, skip: usize) -> Result<Self> {
+        Self::with_horizons(&[(lookback, 1.0)], skip)
+    }
+
+    /// Creates a weighted blend of several horizons, e.g. `&[(3, 1.0), (6, 1.0), (12, 1.0)]`.
+    pub fn with_horizons(horizons: &[(usize, f64)], skip: usize) -> Result<Self> {
+        if horizons.is_empty() {
+            return Err(TaError::invalid_period(
+                "horizons",
+                0,
+                "at least one horizon is required",
+            ));
+        }
+        let mut total_weight = 0.0;
+        for &(lookback, weight) in horizons {
+            if lookback <= skip {
+                return Err(TaError::invalid_period(
+                    "lookback",
+                    lookback,
+                    "must be greater than skip",
+                ));
+            }
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(TaError::invalid_parameter(
+                    "weight",
+                    weight,
+                    "must not be negative",
+                ));
+            }
+            total_weight += weight;
+        }
+        if total_weight <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "weight",
+                total_weight,
+                "the weights must not all be 0",
+            ));
+        }
+
+        let longest = horizons
+            .iter()
+            .map(|&(lookback, _)| lookback)
+            .max()
+            .unwrap();
+        Ok(Self {
+            horizons: horizons.to_vec(),
+            total_weight,
+            skip,
+            window: RingBuffer::new(longest + 1),
+            output: None,
+        })
+    }
+
+    pub fn skip(&self) -> usize {
+        self.skip
+    }
+
+    /// Price `ago` periods back, or the oldest one if the history is not long enough yet.
+    fn price_ago(&self, ago: usize) -> f64 {
+        let ago = ago.min(self.window.len() - 1);
+        self.window.get_back(ago).unwrap()
+    }
+}
+
+impl Period for MomentumScore {
+    fn period(&self) -> usize {
+        // the longest lookback and the price it starts from
+        self.window.capacity()
+    }
+}
+
+impl IsReady for MomentumScore {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.window.len()
+    }
+}
+
+impl Next<f64> for MomentumScore {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.window.push(input);
+
+        let recent = self.price_ago(self.skip);
+        let weighted_sum: f64 = self
+            .horizons
+            .iter()
+            .map(|&(lookback, weight)| {
+                let previous = self.price_ago(lookback);
+                weight * (recent - previous) / previous * 100.0
+            })
+            .sum();
+        let output = weighted_sum / self.total_weight;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for MomentumScore {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Current for MomentumScore {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for MomentumScore {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for MomentumScore {
+    fn heap_size(&self) -> usize {
+        self.horizons.capacity() * core::mem::size_of::<(usize, f64)>() + self.window.heap_size()
+    }
+}
+
+impl Default for MomentumScore {
+    fn default() -> Self {
+        Self::new(12, 1).unwrap()
+    }
+}
+
+impl fmt::Display for MomentumScore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MOM_SCORE(")?;
+        for (i, (lookback, weight)) in self.horizons.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            if self.horizons.len() == 1 {
+                write!(f, "{}", lookback)?;
+            } else {
+                write!(f, "{}*{}", weight, lookback)?;
+            }
+        }
+        write!(f, "; {})", self.skip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(MomentumScore);
+
+    #[test]
+    fn test_new() {
+        assert!(MomentumScore::new(0, 0).is_err());
+        assert!(MomentumScore::new(1, 1).is_err());
+        assert!(MomentumScore::new(1, 0).is_ok());
+        assert!(MomentumScore::new(12, 1).is_ok());
+
+        assert!(MomentumScore::with_horizons(&[], 0).is_err());
+        assert!(MomentumScore::with_horizons(&[(3, -1.0)], 0).is_err());
+        assert!(MomentumScore::with_horizons(&[(3, 0.0)], 0).is_err());
+        assert!(MomentumScore::with_horizons(&[(3, f64::NAN)], 0).is_err());
+        assert!(MomentumScore::with_horizons(&[(3, 1.0), (6, 0.0)], 0).is_ok());
+        assert!(MomentumScore::with_horizons(&[(3, 1.0), (2, 1.0)], 2).is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut score = MomentumScore::new(2, 0).unwrap();
+
+        assert_eq!(score.next(10.0), 0.0);
+        assert_eq!(score.next(12.0), 20.0);
+        assert_eq!(score.next(15.0), 50.0);
+        assert_eq!(score.next(6.0), -50.0);
+    }
+
+    #[test]
+    fn test_next_with_skip() {
+        let mut score = MomentumScore::new(3, 1).unwrap();
+
+        assert_eq!(score.next(&Bar::new().close(10.0)), 0.0);
+        assert_eq!(score.next(&Bar::new().close(20.0)), 0.0);
+        assert_eq!(score.next(&Bar::new().close(15.0)), 100.0);
+        assert_eq!(score.next(&Bar::new().close(30.0)), 50.0);
+        // the latest price is ignored: 30 / 20
+        assert_eq!(score.next(&Bar::new().close(1.0)), 50.0);
+    }
+
+    #[test]
+    fn test_next_with_horizons() {
+        let mut score = MomentumScore::with_horizons(&[(1, 3.0), (3, 1.0)], 0).unwrap();
+
+        score.next(10.0);
+        score.next(10.0);
+        score.next(10.0);
+        // 1-period return is 10%, 3-period return is 10%
+        assert_eq!(round(score.next(11.0)), 10.0);
+        // 1-period return is 0%, 3-period return is 10%
+        assert_eq!(round(score.next(11.0)), 2.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut score = MomentumScore::new(3, 1).unwrap();
+
+        score.next(10.0);
+        score.next(20.0);
+        score.next(30.0);
+
+        score.reset();
+        assert_eq!(score.next(5.0), 0.0);
+        assert_eq!(score.next(10.0), 0.0);
+        assert_eq!(score.next(10.0), 100.0);
+    }
+
+    #[test]
+    fn test_period() {
+        let score = MomentumScore::with_horizons(&[(3, 1.0), (12, 1.0), (6, 1.0)], 1).unwrap();
+        assert_eq!(score.period(), 13);
+        assert_eq!(score.skip(), 1);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut MomentumScore::new(3, 1).unwrap(), 1.0, 4);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut MomentumScore::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        MomentumScore::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let score = MomentumScore::new(12, 1).unwrap();
+        assert_eq!(format!("{}", score), "MOM_SCORE(12; 1)");
+
+        let score = MomentumScore::with_horizons(&[(3, 1.0), (6, 0.5)], 0).unwrap();
+        assert_eq!(format!("{}", score), "MOM_SCORE(1*3, 0.5*6; 0)");
+    }
+}
@@ -1,7 +1,15 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, High, Low, Next, Period, Reset, Volume};
+use crate::helpers::fmt_params;
+use crate::registry::Params;
+use crate::ring_buffer::RingBuffer;
+use crate::{
+    Close, Current, High, IsReady, Low, MemoryFootprint, Next, ParamValue, Parameters, Period,
+    Reset, Volume,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -52,32 +60,30 @@ use serde::{Deserialize, Serialize};
 /// # Links
 /// * [Money Flow Index, Wikipedia](https://en.wikipedia.org/wiki/Money_flow_index)
 /// * [Money Flow Index, stockcharts](https://stockcharts.com/school/doku.php?id=chart_school:technical_indicators:money_flow_index_mfi)
-
 #[doc(alias = "MFI")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct MoneyFlowIndex {
     period: usize,
-    index: usize,
-    count: usize,
     previous_typical_price: f64,
     total_positive_money_flow: f64,
     total_negative_money_flow: f64,
-    deque: Box<[f64]>,
+    /// Signed money flows, negative for a falling typical price.
+    flows: RingBuffer,
+    output: Option<f64>,
 }
 
 impl MoneyFlowIndex {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::zero_period("period")),
             _ => Ok(Self {
                 period,
-                index: 0,
-                count: 0,
                 previous_typical_price: 0.0,
                 total_positive_money_flow: 0.0,
                 total_negative_money_flow: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                flows: RingBuffer::new(period),
+                output: None,
             }),
         }
     }
@@ -89,49 +95,62 @@ impl Period for MoneyFlowIndex {
     }
 }
 
+impl IsReady for MoneyFlowIndex {
+    fn bars_until_ready(&self) -> usize {
+        self.period - self.flows.len()
+    }
+}
+
 impl<T: High + Low + Close + Volume> Next<&T> for MoneyFlowIndex {
     type Output = f64;
 
     fn next(&mut self, input: &T) -> f64 {
         let tp = (input.close() + input.high() + input.low()) / 3.0;
 
-        self.index = if self.index + 1 < self.period {
-            self.index + 1
-        } else {
-            0
-        };
-
-        if self.count < self.period {
-            self.count = self.count + 1;
-            if self.count == 1 {
-                self.previous_typical_price = tp;
-                return 50.0;
-            }
-        } else {
-            let popped = self.deque[self.index];
-            if popped.is_sign_positive() {
-                self.total_positive_money_flow -= popped;
-            } else {
-                self.total_negative_money_flow += popped;
-            }
+        if self.flows.is_empty() {
+            // the first bar has no previous typical price, so no money flow
+            self.flows.push(0.0);
+            self.previous_typical_price = tp;
+            self.output = Some(50.0);
+            return 50.0;
         }
 
-        if tp > self.previous_typical_price {
+        let flow = if tp > self.previous_typical_price {
             let raw_money_flow = tp * input.volume();
             self.total_positive_money_flow += raw_money_flow;
-            self.deque[self.index] = raw_money_flow;
+            raw_money_flow
         } else if tp < self.previous_typical_price {
             let raw_money_flow = tp * input.volume();
             self.total_negative_money_flow += raw_money_flow;
-            self.deque[self.index] = -raw_money_flow;
+            -raw_money_flow
         } else {
-            self.deque[self.index] = 0.0;
+            0.0
+        };
+        if let Some(popped) = self.flows.push(flow) {
+            if popped.is_sign_positive() {
+                self.total_positive_money_flow -= popped;
+            } else {
+                self.total_negative_money_flow += popped;
+            }
         }
         self.previous_typical_price = tp;
 
-        self.total_positive_money_flow
+        let output = self.total_positive_money_flow
             / (self.total_positive_money_flow + self.total_negative_money_flow)
-            * 100.0
+            * 100.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Parameters for MoneyFlowIndex {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::new(params.period("period", 14)?)
     }
 }
 
@@ -143,20 +162,31 @@ impl Default for MoneyFlowIndex {
 
 impl fmt::Display for MoneyFlowIndex {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "MFI({})", self.period)
+        fmt_params(f, "MFI", &self.params())
+    }
+}
+
+impl Current for MoneyFlowIndex {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
 impl Reset for MoneyFlowIndex {
     fn reset(&mut self) {
-        self.index = 0;
-        self.count = 0;
         self.previous_typical_price = 0.0;
         self.total_positive_money_flow = 0.0;
         self.total_negative_money_flow = 0.0;
-        for i in 0..self.period {
-            self.deque[i] = 0.0;
-        }
+        self.flows.clear();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for MoneyFlowIndex {
+    fn heap_size(&self) -> usize {
+        self.flows.heap_size()
     }
 }
 
@@ -216,6 +246,21 @@ mod tests {
         assert_eq!(round(mfi.next(&bar2)), 100.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(2).low(1).close(1.5).volume(1000.0);
+        assert_warm_up(&mut MoneyFlowIndex::new(3).unwrap(), &bar, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut MoneyFlowIndex::default(), &bars);
+    }
+
     #[test]
     fn test_default() {
         MoneyFlowIndex::default();
@@ -1,8 +1,17 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
-use crate::errors::Result;
+use num_traits::Float;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::fmt_params;
 use crate::indicators::ExponentialMovingAverage as Ema;
-use crate::{Close, Next, Period, Reset};
+use crate::registry::Params;
+use crate::{
+    Close, Current, IsReady, MemoryFootprint, Next, NextBatch, ParamValue, Parameters, Period,
+    Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -25,9 +34,15 @@ use serde::{Deserialize, Serialize};
 /// # Parameters
 ///
 /// * _fast_period_ - period for the fast EMA. Default is 12.
-/// * _slow_period_ - period for the slow EMA. Default is 26.
+/// * _slow_period_ - period for the slow EMA, greater than the fast period. Default is 26.
 /// * _signal_period_ - period for the signal EMA. Default is 9.
 ///
+/// # Value type
+///
+/// The values are `f64` by default. Any [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html)
+/// type can be used with [with_periods](#method.with_periods), e.g.
+/// `MovingAverageConvergenceDivergence::<f32>::with_periods(12, 26, 9)`.
+///
 /// # Example
 ///
 /// ```
@@ -53,39 +68,78 @@ use serde::{Deserialize, Serialize};
 #[doc(alias = "MACD")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct MovingAverageConvergenceDivergence {
-    fast_ema: Ema,
-    slow_ema: Ema,
-    signal_ema: Ema,
+pub struct MovingAverageConvergenceDivergence<T = f64> {
+    fast_ema: Ema<T>,
+    slow_ema: Ema<T>,
+    signal_ema: Ema<T>,
+    count: usize,
+    output: Option<MovingAverageConvergenceDivergenceOutput<T>>,
 }
 
 impl MovingAverageConvergenceDivergence {
     pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Result<Self> {
+        Self::with_periods(fast_period, slow_period, signal_period)
+    }
+}
+
+impl<T: Float> MovingAverageConvergenceDivergence<T> {
+    /// Same as `new`, for any value type.
+    pub fn with_periods(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<Self> {
+        if fast_period >= slow_period {
+            return Err(TaError::invalid_period(
+                "fast_period",
+                fast_period,
+                "must be less than slow_period",
+            ));
+        }
         Ok(Self {
-            fast_ema: Ema::new(fast_period)?,
-            slow_ema: Ema::new(slow_period)?,
-            signal_ema: Ema::new(signal_period)?,
+            fast_ema: Ema::with_period(fast_period)?,
+            slow_ema: Ema::with_period(slow_period)?,
+            signal_ema: Ema::with_period(signal_period)?,
+            count: 0,
+            output: None,
         })
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct MovingAverageConvergenceDivergenceOutput {
-    pub macd: f64,
-    pub signal: f64,
-    pub histogram: f64,
+impl<T> Period for MovingAverageConvergenceDivergence<T> {
+    fn period(&self) -> usize {
+        self.fast_ema.period().max(self.slow_ema.period()) + self.signal_ema.period() - 1
+    }
+}
+
+impl<T> IsReady for MovingAverageConvergenceDivergence<T> {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
 }
 
-impl From<MovingAverageConvergenceDivergenceOutput> for (f64, f64, f64) {
-    fn from(mo: MovingAverageConvergenceDivergenceOutput) -> Self {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovingAverageConvergenceDivergenceOutput<T = f64> {
+    pub macd: T,
+    pub signal: T,
+    pub histogram: T,
+}
+
+impl<T> From<MovingAverageConvergenceDivergenceOutput<T>> for (T, T, T) {
+    fn from(mo: MovingAverageConvergenceDivergenceOutput<T>) -> Self {
         (mo.macd, mo.signal, mo.histogram)
     }
 }
 
-impl Next<f64> for MovingAverageConvergenceDivergence {
-    type Output = MovingAverageConvergenceDivergenceOutput;
+impl<T: Float> Next<T> for MovingAverageConvergenceDivergence<T> {
+    type Output = MovingAverageConvergenceDivergenceOutput<T>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        if self.count < self.period() {
+            self.count += 1;
+        }
 
-    fn next(&mut self, input: f64) -> Self::Output {
         let fast_val = self.fast_ema.next(input);
         let slow_val = self.slow_ema.next(input);
 
@@ -93,27 +147,76 @@ impl Next<f64> for MovingAverageConvergenceDivergence {
         let signal = self.signal_ema.next(macd);
         let histogram = macd - signal;
 
-        MovingAverageConvergenceDivergenceOutput {
+        let output = MovingAverageConvergenceDivergenceOutput {
             macd,
             signal,
             histogram,
-        }
+        };
+        self.output = Some(output);
+        output
     }
 }
 
-impl<T: Close> Next<&T> for MovingAverageConvergenceDivergence {
-    type Output = MovingAverageConvergenceDivergenceOutput;
+impl<T: Float> NextBatch<T> for MovingAverageConvergenceDivergence<T> {}
+
+// Bars are only accepted by the primitive floats, a generic impl would overlap `Next<T>`.
+macro_rules! impl_close {
+    ($($t:ty),*) => {$(
+        impl<C: Close> Next<&C> for MovingAverageConvergenceDivergence<$t> {
+            type Output = MovingAverageConvergenceDivergenceOutput<$t>;
+
+            fn next(&mut self, input: &C) -> Self::Output {
+                self.next(input.close() as $t)
+            }
+        }
+    )*};
+}
+
+impl_close!(f32, f64);
 
-    fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+impl<T: Copy> Current for MovingAverageConvergenceDivergence<T> {
+    type Output = MovingAverageConvergenceDivergenceOutput<T>;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
-impl Reset for MovingAverageConvergenceDivergence {
+impl<T: Float> Reset for MovingAverageConvergenceDivergence<T> {
     fn reset(&mut self) {
         self.fast_ema.reset();
         self.slow_ema.reset();
         self.signal_ema.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl<T> MemoryFootprint for MovingAverageConvergenceDivergence<T> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T: Float> Parameters for MovingAverageConvergenceDivergence<T> {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![
+            ("fast_period", ParamValue::Period(self.fast_ema.period())),
+            ("slow_period", ParamValue::Period(self.slow_ema.period())),
+            (
+                "signal_period",
+                ParamValue::Period(self.signal_ema.period()),
+            ),
+        ]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["fast_period", "slow_period", "signal_period"])?;
+        Self::with_periods(
+            params.period("fast_period", 12)?,
+            params.period("slow_period", 26)?,
+            params.period("signal_period", 9)?,
+        )
     }
 }
 
@@ -123,15 +226,9 @@ impl Default for MovingAverageConvergenceDivergence {
     }
 }
 
-impl fmt::Display for MovingAverageConvergenceDivergence {
+impl<T: Float> fmt::Display for MovingAverageConvergenceDivergence<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "MACD({}, {}, {})",
-            self.fast_ema.period(),
-            self.slow_ema.period(),
-            self.signal_ema.period()
-        )
+        fmt_params(f, "MACD", &self.params())
     }
 }
 
@@ -155,7 +252,9 @@ mod tests {
         assert!(Macd::new(0, 1, 1).is_err());
         assert!(Macd::new(1, 0, 1).is_err());
         assert!(Macd::new(1, 1, 0).is_err());
-        assert!(Macd::new(1, 1, 1).is_ok());
+        assert!(Macd::new(2, 2, 1).is_err());
+        assert!(Macd::new(3, 2, 1).is_err());
+        assert!(Macd::new(1, 2, 1).is_ok());
     }
 
     #[test]
@@ -170,6 +269,20 @@ mod tests {
         assert_eq!(round(macd.next(6.5).into()), (0.94, 0.87, 0.07));
     }
 
+    #[test]
+    fn test_f32() {
+        let mut macd = MovingAverageConvergenceDivergence::<f32>::with_periods(3, 6, 4).unwrap();
+        let expected = [(0.0, 0.0, 0.0), (0.21, 0.09, 0.13), (0.52, 0.26, 0.26)];
+        for (input, expected) in [2.0f32, 3.0, 4.2].into_iter().zip(expected) {
+            let (macd, signal, histogram) = macd.next(input).into();
+            let actual = round((macd.into(), signal.into(), histogram.into()));
+            assert_eq!(actual, expected);
+        }
+        let macd_7 = macd.next(&Bar::new().close(7)).macd;
+        assert_eq!((macd_7 * 100.0).round(), 115.0);
+        assert_eq!(format!("{}", macd), "MACD(3, 6, 4)");
+    }
+
     #[test]
     fn test_reset() {
         let mut macd = Macd::new(3, 6, 4).unwrap();
@@ -183,6 +296,26 @@ mod tests {
         assert_eq!(round(macd.next(3.0).into()), (0.21, 0.09, 0.13));
     }
 
+    #[test]
+    fn test_period() {
+        let macd = MovingAverageConvergenceDivergence::new(12, 26, 9).unwrap();
+        assert_eq!(macd.period(), 34);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut macd = MovingAverageConvergenceDivergence::new(3, 5, 2).unwrap();
+        assert_warm_up(&mut macd, 1.0, 6);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(
+            &mut MovingAverageConvergenceDivergence::default(),
+            [2.0, 3.0, 5.0],
+        );
+    }
+
     #[test]
     fn test_default() {
         Macd::default();
@@ -193,4 +326,23 @@ mod tests {
         let indicator = Macd::new(13, 30, 10).unwrap();
         assert_eq!(format!("{}", indicator), "MACD(13, 30, 10)");
     }
+
+    #[test]
+    fn test_parameters() {
+        let mut macd = Macd::new(3, 6, 4).unwrap();
+        assert_eq!(
+            macd.params(),
+            [
+                ("fast_period", ParamValue::Period(3)),
+                ("slow_period", ParamValue::Period(6)),
+                ("signal_period", ParamValue::Period(4)),
+            ]
+        );
+        assert!(macd
+            .set_param("fast_period", ParamValue::Period(6))
+            .is_err());
+        macd.set_param("slow_period", ParamValue::Period(8))
+            .unwrap();
+        assert_eq!(format!("{}", macd), "MACD(3, 8, 4)");
+    }
 }
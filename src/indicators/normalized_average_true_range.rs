@@ -0,0 +1,234 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use crate::errors::Result;
+use crate::helpers::fmt_params;
+use crate::indicators::AverageTrueRange;
+use crate::registry::Params;
+use crate::{
+    Close, Current, High, IsReady, Low, MemoryFootprint, Next, ParamValue, Parameters, Period,
+    Reset,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Normalized average true range (NATR).
+///
+/// The [average true range](struct.AverageTrueRange.html) expressed as a percentage of the
+/// close price, so the volatility of instruments with very different price levels can be
+/// compared.
+///
+/// # Formula
+///
+/// NATR = ATR(period) / close * 100
+///
+/// NATR is 0, when the close is 0.
+///
+/// # Parameters
+///
+/// * _period_ - smoothing period of the ATR (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::NormalizedAverageTrueRange;
+/// use ta::{DataItem, Next};
+///
+/// let mut natr = NormalizedAverageTrueRange::new(3).unwrap();
+///
+/// let bar = DataItem::builder()
+///     .open(48.0).high(52.0).low(47.0).close(50.0).volume(1000.0)
+///     .build().unwrap();
+/// // ATR is 5.0
+/// assert_eq!(natr.next(&bar), 10.0);
+/// ```
+///
+/// # Links
+///
+/// * [Average true range, Wikipedia](https://en.wikipedia.org/wiki/Average_true_range)
+///
+#[doc(alias = "NATR")]
+#[doc(alias = "ATRP")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct NormalizedAverageTrueRange {
+    atr: AverageTrueRange,
+    output: Option<f64>,
+}
+
+impl NormalizedAverageTrueRange {
+    pub fn new(period: usize) -> Result<Self> {
+        Ok(Self {
+            atr: AverageTrueRange::new(period)?,
+            output: None,
+        })
+    }
+}
+
+fn normalize(atr: f64, close: f64) -> f64 {
+    if close != 0.0 {
+        atr / close * 100.0
+    } else {
+        0.0
+    }
+}
+
+impl Period for NormalizedAverageTrueRange {
+    fn period(&self) -> usize {
+        self.atr.period()
+    }
+}
+
+impl IsReady for NormalizedAverageTrueRange {
+    fn bars_until_ready(&self) -> usize {
+        self.atr.bars_until_ready()
+    }
+}
+
+impl Next<f64> for NormalizedAverageTrueRange {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let output = normalize(self.atr.next(input), input);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for NormalizedAverageTrueRange {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let output = normalize(self.atr.next(input), input.close());
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for NormalizedAverageTrueRange {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for NormalizedAverageTrueRange {
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for NormalizedAverageTrueRange {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Parameters for NormalizedAverageTrueRange {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.atr.period()))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::new(params.period("period", 14)?)
+    }
+}
+
+impl Default for NormalizedAverageTrueRange {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for NormalizedAverageTrueRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_params(f, "NATR", &self.params())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(NormalizedAverageTrueRange);
+
+    #[test]
+    fn test_new() {
+        assert!(NormalizedAverageTrueRange::new(0).is_err());
+        assert!(NormalizedAverageTrueRange::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut natr = NormalizedAverageTrueRange::new(3).unwrap();
+
+        let bar1 = Bar::new().high(10).low(7.5).close(9);
+        let bar2 = Bar::new().high(11).low(9).close(9.5);
+        let bar3 = Bar::new().high(9).low(5).close(8);
+
+        // ATR: 2.5, 2.25, 3.375
+        assert_eq!(round(natr.next(&bar1)), 27.778);
+        assert_eq!(round(natr.next(&bar2)), 23.684);
+        assert_eq!(round(natr.next(&bar3)), 42.188);
+    }
+
+    #[test]
+    fn test_scale_invariant() {
+        let mut cheap = NormalizedAverageTrueRange::new(3).unwrap();
+        let mut expensive = NormalizedAverageTrueRange::new(3).unwrap();
+
+        for &(high, low, close) in &[(10.0, 8.0, 9.0), (11.0, 9.0, 10.5), (10.5, 9.5, 10.0)] {
+            let a = cheap.next(&Bar::new().high(high).low(low).close(close));
+            let b = expensive.next(
+                &Bar::new()
+                    .high(high * 100.0)
+                    .low(low * 100.0)
+                    .close(close * 100.0),
+            );
+            assert_eq!(round(a), round(b));
+        }
+    }
+
+    #[test]
+    fn test_zero_close() {
+        let mut natr = NormalizedAverageTrueRange::new(3).unwrap();
+        assert_eq!(natr.next(&Bar::new().high(1).low(0).close(0)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut natr = NormalizedAverageTrueRange::new(9).unwrap();
+
+        natr.next(&Bar::new().high(10).low(7.5).close(9));
+        natr.next(&Bar::new().high(11).low(9).close(9.5));
+
+        natr.reset();
+        assert_eq!(natr.next(&Bar::new().high(60).low(15).close(50)), 90.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut NormalizedAverageTrueRange::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut NormalizedAverageTrueRange::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        NormalizedAverageTrueRange::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let natr = NormalizedAverageTrueRange::new(8).unwrap();
+        assert_eq!(format!("{}", natr), "NATR(8)");
+    }
+}
@@ -1,6 +1,13 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
-use crate::{Close, Next, Reset, Volume};
+use crate::errors::Result;
+use crate::helpers::Accumulator;
+use crate::registry::Params;
+use crate::{
+    Close, Current, IsReady, MemoryFootprint, Next, ParamValue, Parameters, Reset, Volume,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -25,6 +32,9 @@ use serde::{Deserialize, Serialize};
 ///
 /// obv - on the balance volume
 ///
+/// [compensated](#method.compensated) sums the volume with compensated summation, so the OBV
+/// does not drift after millions of bars.
+///
 /// # Example
 ///
 /// ```
@@ -57,22 +67,37 @@ use serde::{Deserialize, Serialize};
 ///
 /// * [On Balance Volume, Wikipedia](https://en.wikipedia.org/wiki/On-balance_volume)
 /// * [On Balance Volume, stockcharts](https://stockcharts.com/school/doku.php?id=chart_school:technical_indicators:on_balance_volume_obv)
-
 #[doc(alias = "OBV")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct OnBalanceVolume {
-    obv: f64,
+    obv: Accumulator,
     prev_close: f64,
+    output: Option<f64>,
 }
 
 impl OnBalanceVolume {
     pub fn new() -> Self {
         Self {
-            obv: 0.0,
+            obv: Accumulator::new(false),
             prev_close: 0.0,
+            output: None,
         }
     }
+
+    /// Same as `new`, with compensated summation of the volume.
+    pub fn compensated() -> Self {
+        Self {
+            obv: Accumulator::new(true),
+            ..Self::new()
+        }
+    }
+}
+
+impl IsReady for OnBalanceVolume {
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
 }
 
 impl<T: Close + Volume> Next<&T> for OnBalanceVolume {
@@ -80,12 +105,25 @@ impl<T: Close + Volume> Next<&T> for OnBalanceVolume {
 
     fn next(&mut self, input: &T) -> f64 {
         if input.close() > self.prev_close {
-            self.obv = self.obv + input.volume();
+            self.obv.add(input.volume());
         } else if input.close() < self.prev_close {
-            self.obv = self.obv - input.volume();
+            self.obv.add(-input.volume());
         }
         self.prev_close = input.close();
-        self.obv
+        let obv = self.obv.value();
+        self.output = Some(obv);
+        obv
+    }
+}
+
+impl Parameters for OnBalanceVolume {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&[])?;
+        Ok(Self::new())
     }
 }
 
@@ -101,10 +139,25 @@ impl fmt::Display for OnBalanceVolume {
     }
 }
 
+impl Current for OnBalanceVolume {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
 impl Reset for OnBalanceVolume {
     fn reset(&mut self) {
-        self.obv = 0.0;
+        self.obv.reset();
         self.prev_close = 0.0;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for OnBalanceVolume {
+    fn heap_size(&self) -> usize {
+        0
     }
 }
 
@@ -153,6 +206,43 @@ mod tests {
         assert_eq!(obv.next(&bar3), 6000.0);
     }
 
+    #[test]
+    fn test_compensated() {
+        let bars = [
+            Bar::new().close(1).volume(1e16),
+            Bar::new().close(2).volume(1.0),
+            Bar::new().close(1).volume(1e16),
+        ];
+        let mut obv = OnBalanceVolume::new();
+        let mut compensated = OnBalanceVolume::compensated();
+        for bar in &bars {
+            obv.next(bar);
+            compensated.next(bar);
+        }
+        assert_eq!(obv.current(), Some(0.0));
+        assert_eq!(compensated.current(), Some(1.0));
+
+        compensated.reset();
+        for bar in &bars {
+            compensated.next(bar);
+        }
+        assert_eq!(compensated.current(), Some(1.0));
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert!(OnBalanceVolume::new().is_ready());
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut OnBalanceVolume::default(), &bars);
+    }
+
     #[test]
     fn test_default() {
         OnBalanceVolume::default();
@@ -0,0 +1,311 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec};
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+use crate::MemoryFootprint;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Binary heap of the slots of a window, ordered by their values: the largest value on top for
+/// `MAX`, the smallest otherwise.
+///
+/// The heap has room for the whole window, so it never allocates, and `positions` tracks the
+/// index of every slot in its heap, so any slot can be removed in _O(log(period))_.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+struct SlotHeap<const MAX: bool> {
+    slots: Box<[usize]>,
+    len: usize,
+}
+
+impl<const MAX: bool> SlotHeap<MAX> {
+    fn new(period: usize) -> Self {
+        Self {
+            slots: vec![0; period].into_boxed_slice(),
+            len: 0,
+        }
+    }
+
+    fn top(&self) -> Option<usize> {
+        self.slots[..self.len].first().copied()
+    }
+
+    /// Whether `slot` is in this heap.
+    fn contains(&self, slot: usize, positions: &[usize]) -> bool {
+        positions[slot] < self.len && self.slots[positions[slot]] == slot
+    }
+
+    fn push(&mut self, slot: usize, values: &[f64], positions: &mut [usize]) {
+        self.slots[self.len] = slot;
+        positions[slot] = self.len;
+        self.len += 1;
+        self.sift_up(self.len - 1, values, positions);
+    }
+
+    fn pop(&mut self, values: &[f64], positions: &mut [usize]) -> usize {
+        let top = self.slots[0];
+        self.remove(top, values, positions);
+        top
+    }
+
+    fn remove(&mut self, slot: usize, values: &[f64], positions: &mut [usize]) {
+        let i = positions[slot];
+        self.len -= 1;
+        if i < self.len {
+            self.slots[i] = self.slots[self.len];
+            positions[self.slots[i]] = i;
+            self.sift_down(i, values, positions);
+            self.sift_up(i, values, positions);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Whether the value of slot `a` belongs above the value of slot `b`.
+    fn above(values: &[f64], a: usize, b: usize) -> bool {
+        let order = values[a].total_cmp(&values[b]);
+        if MAX {
+            order.is_gt()
+        } else {
+            order.is_lt()
+        }
+    }
+
+    fn swap(&mut self, i: usize, j: usize, positions: &mut [usize]) {
+        self.slots.swap(i, j);
+        positions[self.slots[i]] = i;
+        positions[self.slots[j]] = j;
+    }
+
+    fn sift_up(&mut self, mut i: usize, values: &[f64], positions: &mut [usize]) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !Self::above(values, self.slots[i], self.slots[parent]) {
+                break;
+            }
+            self.swap(i, parent, positions);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize, values: &[f64], positions: &mut [usize]) {
+        loop {
+            let mut top = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.len && Self::above(values, self.slots[child], self.slots[top]) {
+                    top = child;
+                }
+            }
+            if top == i {
+                break;
+            }
+            self.swap(i, top, positions);
+            i = top;
+        }
+    }
+}
+
+/// Quantile `q` of the last `period` values.
+///
+/// The sorted window is split between two heaps: a max-heap with the values up to the rank of
+/// the quantile and a min-heap with the rest, so the two closest ranks are at the tops of the
+/// heaps. An update removes the leaving value from its heap, adds the new one and moves at
+/// most two values between the heaps, which takes _O(log(period))_ and never allocates.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub(crate) struct RollingOrderStatistics {
+    period: usize,
+    q: f64,
+    index: usize,
+    count: usize,
+    deque: Box<[f64]>,
+    /// Index of every slot of the window in its heap.
+    positions: Box<[usize]>,
+    lower: SlotHeap<true>,
+    upper: SlotHeap<false>,
+}
+
+impl RollingOrderStatistics {
+    /// `period` must be greater than 0 and `q` in range [0, 1].
+    pub(crate) fn new(period: usize, q: f64) -> Self {
+        Self {
+            period,
+            q,
+            index: 0,
+            count: 0,
+            deque: vec![0.0; period].into_boxed_slice(),
+            positions: vec![0; period].into_boxed_slice(),
+            lower: SlotHeap::new(period),
+            upper: SlotHeap::new(period),
+        }
+    }
+
+    pub(crate) fn period(&self) -> usize {
+        self.period
+    }
+
+    pub(crate) fn bars_until_ready(&self) -> usize {
+        self.period - self.count
+    }
+
+    /// Adds a value, dropping the oldest one when the window is full.
+    pub(crate) fn push(&mut self, value: f64) {
+        let slot = self.index;
+        if self.count == self.period {
+            if self.lower.contains(slot, &self.positions) {
+                self.lower.remove(slot, &self.deque, &mut self.positions);
+            } else {
+                self.upper.remove(slot, &self.deque, &mut self.positions);
+            }
+        } else {
+            self.count += 1;
+        }
+        self.deque[slot] = value;
+        self.index = if self.index + 1 < self.period {
+            self.index + 1
+        } else {
+            0
+        };
+
+        // every value of the lower heap stays below every value of the upper heap
+        match self.upper.top() {
+            Some(bottom) if value.total_cmp(&self.deque[bottom]).is_gt() => {
+                self.upper.push(slot, &self.deque, &mut self.positions)
+            }
+            _ => self.lower.push(slot, &self.deque, &mut self.positions),
+        }
+
+        let (rank, _) = self.rank();
+        while self.lower.len > rank + 1 {
+            let top = self.lower.pop(&self.deque, &mut self.positions);
+            self.upper.push(top, &self.deque, &mut self.positions);
+        }
+        while self.lower.len < rank + 1 {
+            let bottom = self.upper.pop(&self.deque, &mut self.positions);
+            self.lower.push(bottom, &self.deque, &mut self.positions);
+        }
+    }
+
+    /// Rank of the quantile in the sorted window and the fraction towards the next rank.
+    fn rank(&self) -> (usize, f64) {
+        let pos = self.q * (self.count - 1) as f64;
+        let rank = pos.floor() as usize;
+        (rank, pos - rank as f64)
+    }
+
+    /// Quantile of the values in the window, linearly interpolated between the closest ranks.
+    /// Returns 0 for an empty window.
+    pub(crate) fn quantile(&self) -> f64 {
+        let below = match self.lower.top() {
+            Some(top) => self.deque[top],
+            None => return 0.0,
+        };
+        let (_, fraction) = self.rank();
+        match self.upper.top() {
+            Some(bottom) if fraction > 0.0 => below + fraction * (self.deque[bottom] - below),
+            _ => below,
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        self.lower.clear();
+        self.upper.clear();
+    }
+}
+
+impl MemoryFootprint for RollingOrderStatistics {
+    fn heap_size(&self) -> usize {
+        core::mem::size_of_val(&*self.deque)
+            + core::mem::size_of_val(&*self.positions)
+            + core::mem::size_of_val(&*self.lower.slots)
+            + core::mem::size_of_val(&*self.upper.slots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive_quantile(window: &[f64], q: f64) -> f64 {
+        let mut sorted = window.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let pos = q * (sorted.len() - 1) as f64;
+        let rank = pos.floor() as usize;
+        let fraction = pos - rank as f64;
+        if fraction > 0.0 {
+            sorted[rank] + fraction * (sorted[rank + 1] - sorted[rank])
+        } else {
+            sorted[rank]
+        }
+    }
+
+    #[test]
+    fn test_quantile() {
+        let mut median = RollingOrderStatistics::new(4, 0.5);
+        assert_eq!(median.quantile(), 0.0);
+
+        median.push(3.0);
+        assert_eq!(median.quantile(), 3.0);
+        median.push(1.0);
+        median.push(2.0);
+        assert_eq!(median.quantile(), 2.0);
+        median.push(5.0);
+        assert_eq!(median.quantile(), 2.5);
+        // 3.0 drops out of the window
+        median.push(4.0);
+        assert_eq!(median.quantile(), 3.0);
+
+        let mut q25 = RollingOrderStatistics::new(4, 0.25);
+        for &value in &[3.0, 1.0, 2.0, 5.0, 4.0] {
+            q25.push(value);
+        }
+        assert_eq!(q25.quantile(), 1.75);
+    }
+
+    #[test]
+    fn test_matches_sorting() {
+        let values: Vec<f64> = (0..500)
+            .map(|i| ((i * 37 % 101) as f64 - 50.0) / 4.0)
+            .collect();
+
+        for &period in &[1, 2, 5, 16] {
+            for &q in &[0.0, 0.1, 0.5, 0.9, 1.0] {
+                let mut stats = RollingOrderStatistics::new(period, q);
+                for (i, &value) in values.iter().enumerate() {
+                    stats.push(value);
+                    let window = &values[(i + 1).saturating_sub(period)..=i];
+                    assert_eq!(stats.quantile(), naive_quantile(window, q));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let window = [2.0, 3.0, 2.0, 0.0];
+        for &q in &[0.0, 0.25, 0.5, 0.75, 1.0] {
+            let mut stats = RollingOrderStatistics::new(4, q);
+            for &value in &[2.0, 2.0, 1.0, 2.0, 3.0, 2.0, 0.0] {
+                stats.push(value);
+            }
+            assert_eq!(stats.quantile(), naive_quantile(&window, q));
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut stats = RollingOrderStatistics::new(3, 0.5);
+        stats.push(10.0);
+        stats.push(20.0);
+
+        stats.reset();
+        stats.push(1.0);
+        assert_eq!(stats.quantile(), 1.0);
+    }
+}
@@ -1,8 +1,16 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
-use crate::errors::Result;
+use num_traits::Float;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::{cast, fmt_params};
 use crate::indicators::ExponentialMovingAverage as Ema;
-use crate::{Close, Next, Period, Reset};
+use crate::registry::Params;
+use crate::{
+    Close, Current, IsReady, MemoryFootprint, Next, ParamValue, Parameters, Period, Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -25,9 +33,15 @@ use serde::{Deserialize, Serialize};
 /// # Parameters
 ///
 /// * _fast_period_ - period for the fast EMA. Default is 12.
-/// * _slow_period_ - period for the slow EMA. Default is 26.
+/// * _slow_period_ - period for the slow EMA, greater than the fast period. Default is 26.
 /// * _signal_period_ - period for the signal EMA. Default is 9.
 ///
+/// # Value type
+///
+/// The values are `f64` by default. Any [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html)
+/// type can be used with [with_periods](#method.with_periods), e.g.
+/// `PercentagePriceOscillator::<f32>::with_periods(12, 26, 9)`.
+///
 /// # Example
 ///
 /// ```
@@ -53,67 +67,153 @@ use serde::{Deserialize, Serialize};
 #[doc(alias = "PPO")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct PercentagePriceOscillator {
-    fast_ema: Ema,
-    slow_ema: Ema,
-    signal_ema: Ema,
+pub struct PercentagePriceOscillator<T = f64> {
+    fast_ema: Ema<T>,
+    slow_ema: Ema<T>,
+    signal_ema: Ema<T>,
+    count: usize,
+    output: Option<PercentagePriceOscillatorOutput<T>>,
 }
 
 impl PercentagePriceOscillator {
     pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Result<Self> {
+        Self::with_periods(fast_period, slow_period, signal_period)
+    }
+}
+
+impl<T: Float> PercentagePriceOscillator<T> {
+    /// Same as `new`, for any value type.
+    pub fn with_periods(
+        fast_period: usize,
+        slow_period: usize,
+        signal_period: usize,
+    ) -> Result<Self> {
+        if fast_period >= slow_period {
+            return Err(TaError::invalid_period(
+                "fast_period",
+                fast_period,
+                "must be less than slow_period",
+            ));
+        }
         Ok(PercentagePriceOscillator {
-            fast_ema: Ema::new(fast_period)?,
-            slow_ema: Ema::new(slow_period)?,
-            signal_ema: Ema::new(signal_period)?,
+            fast_ema: Ema::with_period(fast_period)?,
+            slow_ema: Ema::with_period(slow_period)?,
+            signal_ema: Ema::with_period(signal_period)?,
+            count: 0,
+            output: None,
         })
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct PercentagePriceOscillatorOutput {
-    pub ppo: f64,
-    pub signal: f64,
-    pub histogram: f64,
+impl<T> Period for PercentagePriceOscillator<T> {
+    fn period(&self) -> usize {
+        self.fast_ema.period().max(self.slow_ema.period()) + self.signal_ema.period() - 1
+    }
+}
+
+impl<T> IsReady for PercentagePriceOscillator<T> {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
 }
 
-impl From<PercentagePriceOscillatorOutput> for (f64, f64, f64) {
-    fn from(po: PercentagePriceOscillatorOutput) -> Self {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PercentagePriceOscillatorOutput<T = f64> {
+    pub ppo: T,
+    pub signal: T,
+    pub histogram: T,
+}
+
+impl<T> From<PercentagePriceOscillatorOutput<T>> for (T, T, T) {
+    fn from(po: PercentagePriceOscillatorOutput<T>) -> Self {
         (po.ppo, po.signal, po.histogram)
     }
 }
 
-impl Next<f64> for PercentagePriceOscillator {
-    type Output = PercentagePriceOscillatorOutput;
+impl<T: Float> Next<T> for PercentagePriceOscillator<T> {
+    type Output = PercentagePriceOscillatorOutput<T>;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        if self.count < self.period() {
+            self.count += 1;
+        }
 
-    fn next(&mut self, input: f64) -> Self::Output {
         let fast_val = self.fast_ema.next(input);
         let slow_val = self.slow_ema.next(input);
 
-        let ppo = (fast_val - slow_val) / slow_val * 100.0;
+        let ppo = (fast_val - slow_val) / slow_val * cast(100);
         let signal = self.signal_ema.next(ppo);
         let histogram = ppo - signal;
 
-        PercentagePriceOscillatorOutput {
+        let output = PercentagePriceOscillatorOutput {
             ppo,
             signal,
             histogram,
-        }
+        };
+        self.output = Some(output);
+        output
     }
 }
 
-impl<T: Close> Next<&T> for PercentagePriceOscillator {
-    type Output = PercentagePriceOscillatorOutput;
+// Bars are only accepted by the primitive floats, a generic impl would overlap `Next<T>`.
+macro_rules! impl_close {
+    ($($t:ty),*) => {$(
+        impl<C: Close> Next<&C> for PercentagePriceOscillator<$t> {
+            type Output = PercentagePriceOscillatorOutput<$t>;
 
-    fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+            fn next(&mut self, input: &C) -> Self::Output {
+                self.next(input.close() as $t)
+            }
+        }
+    )*};
+}
+
+impl_close!(f32, f64);
+
+impl<T: Copy> Current for PercentagePriceOscillator<T> {
+    type Output = PercentagePriceOscillatorOutput<T>;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
-impl Reset for PercentagePriceOscillator {
+impl<T: Float> Reset for PercentagePriceOscillator<T> {
     fn reset(&mut self) {
         self.fast_ema.reset();
         self.slow_ema.reset();
         self.signal_ema.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl<T> MemoryFootprint for PercentagePriceOscillator<T> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T: Float> Parameters for PercentagePriceOscillator<T> {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![
+            ("fast_period", ParamValue::Period(self.fast_ema.period())),
+            ("slow_period", ParamValue::Period(self.slow_ema.period())),
+            (
+                "signal_period",
+                ParamValue::Period(self.signal_ema.period()),
+            ),
+        ]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["fast_period", "slow_period", "signal_period"])?;
+        Self::with_periods(
+            params.period("fast_period", 12)?,
+            params.period("slow_period", 26)?,
+            params.period("signal_period", 9)?,
+        )
     }
 }
 
@@ -123,15 +223,9 @@ impl Default for PercentagePriceOscillator {
     }
 }
 
-impl fmt::Display for PercentagePriceOscillator {
+impl<T: Float> fmt::Display for PercentagePriceOscillator<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "PPO({}, {}, {})",
-            self.fast_ema.period(),
-            self.slow_ema.period(),
-            self.signal_ema.period()
-        )
+        fmt_params(f, "PPO", &self.params())
     }
 }
 
@@ -155,7 +249,9 @@ mod tests {
         assert!(Ppo::new(0, 1, 1).is_err());
         assert!(Ppo::new(1, 0, 1).is_err());
         assert!(Ppo::new(1, 1, 0).is_err());
-        assert!(Ppo::new(1, 1, 1).is_ok());
+        assert!(Ppo::new(2, 2, 1).is_err());
+        assert!(Ppo::new(3, 2, 1).is_err());
+        assert!(Ppo::new(1, 2, 1).is_ok());
     }
 
     #[test]
@@ -170,6 +266,22 @@ mod tests {
         assert_eq!(round(ppo.next(6.5).into()), (17.84, 19.08, -1.24));
     }
 
+    #[test]
+    fn test_f32() {
+        let mut ppo = PercentagePriceOscillator::<f32>::with_periods(3, 6, 4).unwrap();
+        let mut ppo_f64 = Ppo::new(3, 6, 4).unwrap();
+        for input in [2.0f32, 3.0, 4.2, 7.0, 6.7, 6.5] {
+            let actual = ppo.next(input);
+            let expected = ppo_f64.next(f64::from(input));
+            assert!((f64::from(actual.ppo) - expected.ppo).abs() < 1e-4);
+            assert!((f64::from(actual.signal) - expected.signal).abs() < 1e-4);
+            assert!((f64::from(actual.histogram) - expected.histogram).abs() < 1e-4);
+        }
+        let bar = Bar::new().close(5);
+        assert!((f64::from(ppo.next(&bar).ppo) - ppo_f64.next(&bar).ppo).abs() < 1e-4);
+        assert_eq!(format!("{}", ppo), "PPO(3, 6, 4)");
+    }
+
     #[test]
     fn test_reset() {
         let mut ppo = Ppo::new(3, 6, 4).unwrap();
@@ -183,6 +295,23 @@ mod tests {
         assert_eq!(round(ppo.next(3.0).into()), (9.38, 3.75, 5.63));
     }
 
+    #[test]
+    fn test_period() {
+        let ppo = PercentagePriceOscillator::new(12, 26, 9).unwrap();
+        assert_eq!(ppo.period(), 34);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut ppo = PercentagePriceOscillator::new(3, 5, 2).unwrap();
+        assert_warm_up(&mut ppo, 1.0, 6);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut PercentagePriceOscillator::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_default() {
         Ppo::default();
@@ -0,0 +1,285 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::ring_buffer::RingBuffer;
+use crate::{Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Pivot high/low detector.
+///
+/// Finds local extrema (swing points) of a series. A bar is a pivot high, when its value is
+/// higher than the values of the _left_ bars before it and not lower than the values of the
+/// _right_ bars after it (a pivot low is defined the same way). The non strict comparison on the
+/// right side makes the first bar of a flat top or bottom the pivot.
+///
+/// A pivot can only be confirmed _right_ bars after it happened, so every reported pivot comes
+/// with its offset from the current bar. It is a building block for swing based tools like
+/// ZigZag, divergences and support/resistance levels.
+///
+/// With `Next<f64>` both pivot highs and lows are searched in the same series. With `Next<&T>`
+/// pivot highs are searched in the highs and pivot lows in the lows, so an outside bar can be
+/// a pivot high and a pivot low at the same time.
+///
+/// # Parameters
+///
+/// * _left_ - number of bars before a pivot (integer greater than 0). Default is 5.
+/// * _right_ - number of bars after a pivot (integer greater than 0). Default is 5.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{Pivot, PivotHighLow};
+/// use ta::Next;
+///
+/// let mut pivots = PivotHighLow::new(1, 1).unwrap();
+///
+/// assert_eq!(pivots.next(3.0).high, None);
+/// assert_eq!(pivots.next(4.0).high, None);
+/// assert_eq!(pivots.next(6.0).high, None);
+/// assert_eq!(pivots.next(5.0).high, Some(Pivot { value: 6.0, bars_ago: 1 }));
+/// assert_eq!(pivots.next(7.0).low, Some(Pivot { value: 5.0, bars_ago: 1 }));
+/// ```
+///
+/// # Links
+///
+/// * [Pivot points, Investopedia](https://www.investopedia.com/terms/p/pivotpoint.asp)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct PivotHighLow {
+    left: usize,
+    right: usize,
+    /// Pairs of high and low.
+    window: RingBuffer<(f64, f64)>,
+    output: Option<PivotHighLowOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pivot {
+    pub value: f64,
+    /// Number of bars from the pivot to the current bar.
+    pub bars_ago: usize,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PivotHighLowOutput {
+    pub high: Option<Pivot>,
+    pub low: Option<Pivot>,
+}
+
+impl PivotHighLow {
+    pub fn new(left: usize, right: usize) -> Result<Self> {
+        if left == 0 {
+            return Err(TaError::zero_period("left"));
+        }
+        if right == 0 {
+            return Err(TaError::zero_period("right"));
+        }
+        Ok(Self {
+            left,
+            right,
+            window: RingBuffer::new(left + right + 1),
+            output: None,
+        })
+    }
+
+    pub fn left(&self) -> usize {
+        self.left
+    }
+
+    pub fn right(&self) -> usize {
+        self.right
+    }
+
+    /// Checks whether the bar in the middle of the window is a pivot.
+    /// `sign` is 1.0 for pivot highs and -1.0 for pivot lows.
+    fn pivot(&self, sign: f64, value: fn((f64, f64)) -> f64) -> Option<Pivot> {
+        let center = sign * value(self.window.get_back(self.right).unwrap());
+        for (i, pair) in self.window.iter().enumerate() {
+            let ago = self.window.len() - 1 - i;
+            let other = sign * value(pair);
+            if (ago > self.right && other >= center) || (ago < self.right && other > center) {
+                return None;
+            }
+        }
+        Some(Pivot {
+            value: sign * center,
+            bars_ago: self.right,
+        })
+    }
+
+    fn push(&mut self, high: f64, low: f64) -> PivotHighLowOutput {
+        self.window.push((high, low));
+
+        let output = if !self.window.is_full() {
+            PivotHighLowOutput::default()
+        } else {
+            PivotHighLowOutput {
+                high: self.pivot(1.0, |(high, _)| high),
+                low: self.pivot(-1.0, |(_, low)| low),
+            }
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Period for PivotHighLow {
+    fn period(&self) -> usize {
+        self.window.capacity()
+    }
+}
+
+impl IsReady for PivotHighLow {
+    fn bars_until_ready(&self) -> usize {
+        self.window.capacity() - self.window.len()
+    }
+}
+
+impl Next<f64> for PivotHighLow {
+    type Output = PivotHighLowOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.push(input, input)
+    }
+}
+
+impl<T: High + Low> Next<&T> for PivotHighLow {
+    type Output = PivotHighLowOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.push(input.high(), input.low())
+    }
+}
+
+impl Current for PivotHighLow {
+    type Output = PivotHighLowOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for PivotHighLow {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for PivotHighLow {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl Default for PivotHighLow {
+    fn default() -> Self {
+        Self::new(5, 5).unwrap()
+    }
+}
+
+impl fmt::Display for PivotHighLow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PIVOT({}, {})", self.left, self.right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(PivotHighLow);
+
+    fn pivot(value: f64, bars_ago: usize) -> Option<Pivot> {
+        Some(Pivot { value, bars_ago })
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(PivotHighLow::new(0, 1).is_err());
+        assert!(PivotHighLow::new(1, 0).is_err());
+        assert!(PivotHighLow::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut pivots = PivotHighLow::new(1, 2).unwrap();
+
+        let outputs: Vec<_> = [1.0, 3.0, 2.0, 2.5, 1.5, 1.0, 4.0, 5.0]
+            .iter()
+            .map(|&x| pivots.next(x))
+            .collect();
+
+        assert_eq!(outputs[0], PivotHighLowOutput::default());
+        assert_eq!(outputs[1], PivotHighLowOutput::default());
+        assert_eq!(outputs[2], PivotHighLowOutput::default());
+        // 3.0 is higher than 1.0 before it and than 2.0 and 2.5 after it
+        assert_eq!(outputs[3].high, pivot(3.0, 2));
+        assert_eq!(outputs[3].low, None);
+        // 2.0 is lower than 3.0, but 1.5 comes after it
+        assert_eq!(outputs[4], PivotHighLowOutput::default());
+        assert_eq!(outputs[5].high, pivot(2.5, 2));
+        assert_eq!(outputs[5].low, None);
+        assert_eq!(outputs[6], PivotHighLowOutput::default());
+        assert_eq!(outputs[7].low, pivot(1.0, 2));
+    }
+
+    #[test]
+    fn test_flat_top() {
+        let mut pivots = PivotHighLow::new(1, 1).unwrap();
+
+        pivots.next(1.0);
+        pivots.next(2.0);
+        assert_eq!(pivots.next(2.0).high, pivot(2.0, 1));
+        assert_eq!(pivots.next(1.0).high, None);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut pivots = PivotHighLow::new(1, 1).unwrap();
+
+        pivots.next(&Bar::new().high(10.0).low(8.0));
+        pivots.next(&Bar::new().high(12.0).low(7.0));
+        // outside bar is a pivot high and a pivot low at once
+        let out = pivots.next(&Bar::new().high(11.0).low(9.0));
+        assert_eq!(out.high, pivot(12.0, 1));
+        assert_eq!(out.low, pivot(7.0, 1));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut pivots = PivotHighLow::new(1, 1).unwrap();
+
+        pivots.next(1.0);
+        pivots.next(3.0);
+        pivots.reset();
+        assert_eq!(pivots.next(2.0), PivotHighLowOutput::default());
+        assert_eq!(pivots.next(2.0), PivotHighLowOutput::default());
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut PivotHighLow::new(2, 1).unwrap(), 1.0, 4);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut PivotHighLow::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        PivotHighLow::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let pivots = PivotHighLow::new(3, 2).unwrap();
+        assert_eq!(format!("{}", pivots), "PIVOT(3, 2)");
+    }
+}
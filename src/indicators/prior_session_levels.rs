@@ -0,0 +1,374 @@
+use core::fmt;
+
+use crate::{
+    Close, Current, High, IsReady, Low, MemoryFootprint, Next, Reset, SessionAware, Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Prior session reference levels.
+///
+/// Tracks the high, low, close and VWAP of the last completed regular session together with the
+/// high and low of the overnight (out of session) trading that preceded the current session.
+/// The levels only change at a session roll, so they can be used as fixed reference prices
+/// during the whole session.
+///
+/// Session boundaries are signalled explicitly:
+///
+/// * [open_session](#method.open_session) - the regular session starts. If a session is still
+///   open, it is completed first. The overnight high/low collected since the last close is published.
+/// * [close_session](#method.close_session) - the regular session ends. The session high, low,
+///   close and VWAP are published. All following bars are treated as overnight bars until the
+///   next session opens.
+///
+/// Bars fed before the first session is opened are treated as overnight bars.
+///
+/// VWAP is calculated from the typical price _(high + low + close) / 3_, the same way as in
+/// [VolumeWeightedAveragePrice](struct.VolumeWeightedAveragePrice.html).
+///
+/// Every level is `None` until the corresponding data is available. The indicator is ready once
+/// the first session with bars has closed.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::PriorSessionLevels;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(high: f64, low: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(close).high(high).low(low).close(close).volume(100.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut levels = PriorSessionLevels::new();
+///
+/// levels.open_session();
+/// levels.next(&bar(12.0, 10.0, 11.0));
+/// levels.next(&bar(13.0, 11.0, 12.0));
+/// levels.close_session();
+///
+/// // overnight trading
+/// levels.next(&bar(14.0, 12.5, 13.0));
+///
+/// levels.open_session();
+/// let out = levels.next(&bar(13.5, 12.0, 13.0));
+/// assert_eq!(out.high, Some(13.0));
+/// assert_eq!(out.low, Some(10.0));
+/// assert_eq!(out.close, Some(12.0));
+/// assert_eq!(out.overnight_high, Some(14.0));
+/// assert_eq!(out.overnight_low, Some(12.5));
+/// ```
+#[doc(alias = "PDH")]
+#[doc(alias = "PDL")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct PriorSessionLevels {
+    in_session: bool,
+    session: Option<SessionRange>,
+    overnight: Option<SessionRange>,
+    levels: PriorSessionLevelsOutput,
+    output: Option<PriorSessionLevelsOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PriorSessionLevelsOutput {
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    pub close: Option<f64>,
+    pub vwap: Option<f64>,
+    pub overnight_high: Option<f64>,
+    pub overnight_low: Option<f64>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+struct SessionRange {
+    high: f64,
+    low: f64,
+    close: f64,
+    price_volume: f64,
+    volume: f64,
+}
+
+impl SessionRange {
+    fn new<T: High + Low + Close + Volume>(input: &T) -> Self {
+        let mut range = Self {
+            high: input.high(),
+            low: input.low(),
+            close: input.close(),
+            price_volume: 0.0,
+            volume: 0.0,
+        };
+        range.add_volume(input);
+        range
+    }
+
+    fn update<T: High + Low + Close + Volume>(&mut self, input: &T) {
+        self.high = self.high.max(input.high());
+        self.low = self.low.min(input.low());
+        self.close = input.close();
+        self.add_volume(input);
+    }
+
+    fn add_volume<T: High + Low + Close + Volume>(&mut self, input: &T) {
+        let typical_price = (input.high() + input.low() + input.close()) / 3.0;
+        self.price_volume += typical_price * input.volume();
+        self.volume += input.volume();
+    }
+
+    fn vwap(&self) -> Option<f64> {
+        if self.volume > 0.0 {
+            Some(self.price_volume / self.volume)
+        } else {
+            None
+        }
+    }
+}
+
+impl PriorSessionLevels {
+    pub fn new() -> Self {
+        Self {
+            in_session: false,
+            session: None,
+            overnight: None,
+            levels: PriorSessionLevelsOutput::default(),
+            output: None,
+        }
+    }
+
+    /// Starts a new regular session.
+    pub fn open_session(&mut self) {
+        if self.in_session {
+            self.close_session();
+        }
+        let overnight = self.overnight.take();
+        self.levels.overnight_high = overnight.as_ref().map(|r| r.high);
+        self.levels.overnight_low = overnight.as_ref().map(|r| r.low);
+        self.in_session = true;
+    }
+
+    /// Completes the current regular session.
+    pub fn close_session(&mut self) {
+        if !self.in_session {
+            return;
+        }
+        if let Some(session) = self.session.take() {
+            self.levels.high = Some(session.high);
+            self.levels.low = Some(session.low);
+            self.levels.close = Some(session.close);
+            self.levels.vwap = session.vwap();
+        }
+        self.in_session = false;
+    }
+
+    /// Returns `true` while a regular session is open.
+    pub fn in_session(&self) -> bool {
+        self.in_session
+    }
+
+    /// Returns the current reference levels.
+    pub fn levels(&self) -> &PriorSessionLevelsOutput {
+        &self.levels
+    }
+}
+
+impl SessionAware for PriorSessionLevels {
+    fn open_session(&mut self) {
+        PriorSessionLevels::open_session(self);
+    }
+
+    fn close_session(&mut self) {
+        PriorSessionLevels::close_session(self);
+    }
+}
+
+impl IsReady for PriorSessionLevels {
+    fn bars_until_ready(&self) -> usize {
+        // the session boundaries are signalled, so at least one more bar is needed
+        if self.levels.close.is_some() {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for PriorSessionLevels {
+    type Output = PriorSessionLevelsOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let range = if self.in_session {
+            &mut self.session
+        } else {
+            &mut self.overnight
+        };
+        match range {
+            Some(range) => range.update(input),
+            None => *range = Some(SessionRange::new(input)),
+        }
+        let output = self.levels.clone();
+        self.output = Some(output.clone());
+        output
+    }
+}
+
+impl Current for PriorSessionLevels {
+    type Output = PriorSessionLevelsOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output.clone()
+    }
+}
+
+impl Reset for PriorSessionLevels {
+    fn reset(&mut self) {
+        self.in_session = false;
+        self.session = None;
+        self.overnight = None;
+        self.levels = PriorSessionLevelsOutput::default();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for PriorSessionLevels {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for PriorSessionLevels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for PriorSessionLevels {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PSL")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_hlcv_indicator!(PriorSessionLevels);
+
+    fn bar(high: f64, low: f64, close: f64, volume: f64) -> Bar {
+        Bar::new().high(high).low(low).close(close).volume(volume)
+    }
+
+    #[test]
+    fn test_next() {
+        let mut psl = PriorSessionLevels::new();
+
+        psl.open_session();
+        let out = psl.next(&bar(10.0, 8.0, 9.0, 100.0));
+        assert_eq!(out, PriorSessionLevelsOutput::default());
+        psl.next(&bar(12.0, 9.0, 11.0, 200.0));
+        psl.next(&bar(11.0, 7.0, 8.0, 100.0));
+        psl.close_session();
+
+        let out = psl.next(&bar(9.0, 8.5, 8.8, 10.0));
+        assert_eq!(out.high, Some(12.0));
+        assert_eq!(out.low, Some(7.0));
+        assert_eq!(out.close, Some(8.0));
+        // (9 * 100 + 10.667 * 200 + 8.667 * 100) / 400
+        assert_eq!(round(out.vwap.unwrap()), 9.75);
+        assert_eq!(out.overnight_high, None);
+        assert_eq!(out.overnight_low, None);
+
+        psl.next(&bar(9.5, 8.2, 9.0, 10.0));
+        psl.open_session();
+        let out = psl.next(&bar(10.0, 9.0, 9.5, 100.0));
+        assert_eq!(out.high, Some(12.0));
+        assert_eq!(out.overnight_high, Some(9.5));
+        assert_eq!(out.overnight_low, Some(8.2));
+    }
+
+    #[test]
+    fn test_back_to_back_sessions() {
+        let mut psl = PriorSessionLevels::new();
+
+        psl.open_session();
+        psl.next(&bar(10.0, 8.0, 9.0, 100.0));
+        psl.open_session();
+        let out = psl.next(&bar(20.0, 18.0, 19.0, 100.0));
+        assert_eq!(out.high, Some(10.0));
+        assert_eq!(out.low, Some(8.0));
+        assert_eq!(out.close, Some(9.0));
+        assert_eq!(out.vwap, Some(9.0));
+        assert_eq!(out.overnight_high, None);
+
+        psl.close_session();
+        assert!(!psl.in_session());
+        assert_eq!(psl.levels().high, Some(20.0));
+    }
+
+    #[test]
+    fn test_zero_volume_session() {
+        let mut psl = PriorSessionLevels::new();
+
+        psl.open_session();
+        psl.next(&bar(10.0, 8.0, 9.0, 0.0));
+        psl.close_session();
+        assert_eq!(psl.levels().close, Some(9.0));
+        assert_eq!(psl.levels().vwap, None);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut psl = PriorSessionLevels::new();
+
+        psl.next(&bar(10.0, 8.0, 9.0, 100.0));
+        psl.open_session();
+        psl.close_session();
+        assert!(!psl.is_ready());
+
+        psl.open_session();
+        psl.next(&bar(10.0, 8.0, 9.0, 100.0));
+        assert!(!psl.is_ready());
+        psl.close_session();
+        assert!(psl.is_ready());
+
+        psl.reset();
+        assert_eq!(psl.bars_until_ready(), 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut psl = PriorSessionLevels::new();
+
+        psl.open_session();
+        psl.next(&bar(10.0, 8.0, 9.0, 100.0));
+        psl.close_session();
+        assert_eq!(psl.levels().high, Some(10.0));
+
+        psl.reset();
+        assert!(!psl.in_session());
+        assert_eq!(psl.levels(), &PriorSessionLevelsOutput::default());
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut PriorSessionLevels::default(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        PriorSessionLevels::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let psl = PriorSessionLevels::new();
+        assert_eq!(format!("{}", psl), "PSL");
+    }
+}
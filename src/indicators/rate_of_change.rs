@@ -1,7 +1,16 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use num_traits::Float;
 
 use crate::errors::{Result, TaError};
-use crate::traits::{Close, Next, Period, Reset};
+use crate::helpers::{cast, fmt_params};
+use crate::registry::Params;
+use crate::ring_buffer::RingBuffer;
+use crate::traits::{
+    Close, Current, IsReady, MemoryFootprint, Next, ParamValue, Parameters, Period, Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +30,12 @@ use serde::{Deserialize, Serialize};
 ///
 /// * _period_ - number of periods integer greater than 0
 ///
+/// # Value type
+///
+/// The values are `f64` by default. Any [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html)
+/// type can be used with [with_period](#method.with_period), e.g.
+/// `RateOfChange::<f32>::with_period(9)`.
+///
 /// # Example
 ///
 /// ```
@@ -41,64 +56,85 @@ use serde::{Deserialize, Serialize};
 #[doc(alias = "ROC")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct RateOfChange {
+pub struct RateOfChange<T = f64> {
     period: usize,
-    index: usize,
     count: usize,
-    deque: Box<[f64]>,
+    window: RingBuffer<T>,
+    output: Option<T>,
 }
 
 impl RateOfChange {
     pub fn new(period: usize) -> Result<Self> {
+        Self::with_period(period)
+    }
+}
+
+impl<T: Float> RateOfChange<T> {
+    /// Same as `new`, for any value type.
+    pub fn with_period(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::zero_period("period")),
             _ => Ok(Self {
                 period,
-                index: 0,
                 count: 0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                window: RingBuffer::new(period),
+                output: None,
             }),
         }
     }
 }
 
-impl Period for RateOfChange {
+impl<T> Period for RateOfChange<T> {
     fn period(&self) -> usize {
-        self.period
+        // the first bar has no previous value
+        self.period + 1
     }
 }
 
-impl Next<f64> for RateOfChange {
-    type Output = f64;
-
-    fn next(&mut self, input: f64) -> f64 {
-        let previous = if self.count > self.period {
-            self.deque[self.index]
-        } else {
-            self.count += 1;
-            if self.count == 1 {
-                input
-            } else {
-                self.deque[0]
-            }
-        };
-        self.deque[self.index] = input;
+impl<T> IsReady for RateOfChange<T> {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
+}
 
-        self.index = if self.index + 1 < self.period {
-            self.index + 1
-        } else {
-            0
-        };
+impl<T: Float> Next<T> for RateOfChange<T> {
+    type Output = T;
 
-        (input - previous) / previous * 100.0
+    fn next(&mut self, input: T) -> T {
+        // the value `period` bars ago, or the first one before
+        let previous = self.window.oldest().unwrap_or(input);
+        self.window.push(input);
+        self.count = (self.count + 1).min(self.period + 1);
+
+        let output = (input - previous) / previous * cast(100);
+        self.output = Some(output);
+        output
     }
 }
 
-impl<T: Close> Next<&T> for RateOfChange {
-    type Output = f64;
+// Bars are only accepted by the primitive floats, a generic impl would overlap `Next<T>`.
+macro_rules! impl_close {
+    ($($t:ty),*) => {$(
+        impl<C: Close> Next<&C> for RateOfChange<$t> {
+            type Output = $t;
+
+            fn next(&mut self, input: &C) -> $t {
+                self.next(input.close() as $t)
+            }
+        }
+    )*};
+}
 
-    fn next(&mut self, input: &T) -> f64 {
-        self.next(input.close())
+impl_close!(f32, f64);
+
+impl<T: Float> Parameters for RateOfChange<T> {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::with_period(params.period("period", 9)?)
     }
 }
 
@@ -108,19 +144,31 @@ impl Default for RateOfChange {
     }
 }
 
-impl fmt::Display for RateOfChange {
+impl<T: Float> fmt::Display for RateOfChange<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "ROC({})", self.period)
+        fmt_params(f, "ROC", &self.params())
     }
 }
 
-impl Reset for RateOfChange {
+impl<T: Copy> Current for RateOfChange<T> {
+    type Output = T;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<T> Reset for RateOfChange<T> {
     fn reset(&mut self) {
-        self.index = 0;
         self.count = 0;
-        for i in 0..self.period {
-            self.deque[i] = 0.0;
-        }
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl<T> MemoryFootprint for RateOfChange<T> {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
     }
 }
 
@@ -150,6 +198,16 @@ mod tests {
         assert_eq!(round(roc.next(10.0)), -5.393);
     }
 
+    #[test]
+    fn test_f32() {
+        let mut roc = RateOfChange::<f32>::with_period(3).unwrap();
+
+        assert_eq!(roc.next(10.0f32), 0.0f32);
+        assert_eq!(round(roc.next(10.4).into()), 4.0);
+        assert_eq!(round(roc.next(&Bar::new().close(10.57)).into()), 5.7);
+        assert_eq!(format!("{}", roc), "ROC(3)");
+    }
+
     #[test]
     fn test_next_bar() {
         fn bar(close: f64) -> Bar {
@@ -176,4 +234,14 @@ mod tests {
         assert_eq!(round(roc.next(10.4)), 4.0);
         assert_eq!(round(roc.next(10.57)), 5.7);
     }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RateOfChange::new(3).unwrap(), 1.0, 4);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut RateOfChange::default(), [2.0, 3.0, 5.0]);
+    }
 }
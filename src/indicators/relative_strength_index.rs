@@ -1,8 +1,17 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use num_traits::Float;
 
 use crate::errors::Result;
+use crate::helpers::{cast, fmt_params};
 use crate::indicators::ExponentialMovingAverage as Ema;
-use crate::{Close, Next, Period, Reset};
+use crate::registry::Params;
+use crate::{
+    Close, Configure, Current, IsReady, MemoryFootprint, Next, NextBatch, ParamValue, Parameters,
+    Period, ProjectNext, Reset, SolveForInput,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -51,6 +60,12 @@ use serde::{Deserialize, Serialize};
 ///
 /// * _period_ - number of periods (integer greater than 0). Default value is 14.
 ///
+/// # Value type
+///
+/// The values are `f64` by default. Any [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html)
+/// type can be used with [with_period](#method.with_period), e.g.
+/// `RelativeStrengthIndex::<f32>::with_period(14)`.
+///
 /// # Example
 ///
 /// ```
@@ -71,73 +86,178 @@ use serde::{Deserialize, Serialize};
 #[doc(alias = "RSI")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct RelativeStrengthIndex {
+pub struct RelativeStrengthIndex<T = f64> {
     period: usize,
-    up_ema_indicator: Ema,
-    down_ema_indicator: Ema,
-    prev_val: f64,
+    up_ema_indicator: Ema<T>,
+    down_ema_indicator: Ema<T>,
+    prev_val: T,
     is_new: bool,
+    output: Option<T>,
 }
 
 impl RelativeStrengthIndex {
     pub fn new(period: usize) -> Result<Self> {
+        Self::with_period(period)
+    }
+}
+
+impl<T: Float> RelativeStrengthIndex<T> {
+    /// Same as `new`, for any value type.
+    pub fn with_period(period: usize) -> Result<Self> {
         Ok(Self {
             period,
-            up_ema_indicator: Ema::new(period)?,
-            down_ema_indicator: Ema::new(period)?,
-            prev_val: 0.0,
+            up_ema_indicator: Ema::with_period(period)?,
+            down_ema_indicator: Ema::with_period(period)?,
+            prev_val: T::zero(),
             is_new: true,
+            output: None,
         })
     }
+
+    /// Up and down moves from the previous input.
+    fn moves(&self, input: T) -> (T, T) {
+        if self.is_new {
+            // Initialize with some small seed numbers to avoid division by zero
+            (cast(0.1), cast(0.1))
+        } else if input > self.prev_val {
+            (input - self.prev_val, T::zero())
+        } else {
+            (T::zero(), self.prev_val - input)
+        }
+    }
+
+    fn rsi(up_ema: T, down_ema: T) -> T {
+        cast::<T, _>(100) * up_ema / (up_ema + down_ema)
+    }
 }
 
-impl Period for RelativeStrengthIndex {
+impl<T> Period for RelativeStrengthIndex<T> {
     fn period(&self) -> usize {
         self.period
     }
 }
 
-impl Next<f64> for RelativeStrengthIndex {
-    type Output = f64;
+impl<T: Float> Configure for RelativeStrengthIndex<T> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        self.up_ema_indicator.set_period(period)?;
+        self.down_ema_indicator.set_period(period)?;
+        self.period = period;
+        Ok(())
+    }
+}
 
-    fn next(&mut self, input: f64) -> Self::Output {
-        let mut up = 0.0;
-        let mut down = 0.0;
+impl<T> IsReady for RelativeStrengthIndex<T> {
+    fn bars_until_ready(&self) -> usize {
+        self.up_ema_indicator.bars_until_ready()
+    }
+}
 
-        if self.is_new {
-            self.is_new = false;
-            // Initialize with some small seed numbers to avoid division by zero
-            up = 0.1;
-            down = 0.1;
-        } else {
-            if input > self.prev_val {
-                up = input - self.prev_val;
-            } else {
-                down = self.prev_val - input;
-            }
-        }
+impl<T: Float> Next<T> for RelativeStrengthIndex<T> {
+    type Output = T;
 
+    fn next(&mut self, input: T) -> Self::Output {
+        let (up, down) = self.moves(input);
+        self.is_new = false;
         self.prev_val = input;
         let up_ema = self.up_ema_indicator.next(up);
         let down_ema = self.down_ema_indicator.next(down);
-        100.0 * up_ema / (up_ema + down_ema)
+        let output = Self::rsi(up_ema, down_ema);
+        self.output = Some(output);
+        output
     }
 }
 
-impl<T: Close> Next<&T> for RelativeStrengthIndex {
-    type Output = f64;
+impl<T: Float> NextBatch<T> for RelativeStrengthIndex<T> {}
 
-    fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+impl<T: Float> ProjectNext<T> for RelativeStrengthIndex<T> {
+    type Output = T;
+
+    fn project_next(&self, input: T) -> Self::Output {
+        let (up, down) = self.moves(input);
+        let up_ema = self.up_ema_indicator.project_next(up);
+        let down_ema = self.down_ema_indicator.project_next(down);
+        Self::rsi(up_ema, down_ema)
     }
 }
 
-impl Reset for RelativeStrengthIndex {
+// Bars are only accepted by the primitive floats, a generic impl would overlap `Next<T>`.
+macro_rules! impl_close {
+    ($($t:ty),*) => {$(
+        impl<C: Close> Next<&C> for RelativeStrengthIndex<$t> {
+            type Output = $t;
+
+            fn next(&mut self, input: &C) -> Self::Output {
+                self.next(input.close() as $t)
+            }
+        }
+
+        impl<C: Close> ProjectNext<&C> for RelativeStrengthIndex<$t> {
+            type Output = $t;
+
+            fn project_next(&self, input: &C) -> Self::Output {
+                self.project_next(input.close() as $t)
+            }
+        }
+    )*};
+}
+
+impl_close!(f32, f64);
+
+impl SolveForInput for RelativeStrengthIndex {
+    /// Returns `None` for a target outside of range (0, 100) and before the first input, since
+    /// the first output does not depend on the input.
+    fn solve_for_input(&self, target: f64) -> Option<f64> {
+        let r = target / 100.0;
+        if self.is_new || !(r > 0.0 && r < 1.0) {
+            return None;
+        }
+        // EMAs of the up and down moves are a + k * up and b + k * down
+        let k = 2.0 / (self.period + 1) as f64;
+        let a = self.up_ema_indicator.project_next(0.0);
+        let b = self.down_ema_indicator.project_next(0.0);
+
+        if r > a / (a + b) {
+            let up = (r * (a + b) - a) / (k * (1.0 - r));
+            Some(self.prev_val + up)
+        } else {
+            let down = (a / r - a - b) / k;
+            Some(self.prev_val - down)
+        }
+    }
+}
+
+impl<T: Copy> Current for RelativeStrengthIndex<T> {
+    type Output = T;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<T: Float> Reset for RelativeStrengthIndex<T> {
     fn reset(&mut self) {
         self.is_new = true;
-        self.prev_val = 0.0;
+        self.prev_val = T::zero();
         self.up_ema_indicator.reset();
         self.down_ema_indicator.reset();
+        self.output = None;
+    }
+}
+
+impl<T> MemoryFootprint for RelativeStrengthIndex<T> {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl<T: Float> Parameters for RelativeStrengthIndex<T> {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::with_period(params.period("period", 14)?)
     }
 }
 
@@ -147,9 +267,9 @@ impl Default for RelativeStrengthIndex {
     }
 }
 
-impl fmt::Display for RelativeStrengthIndex {
+impl<T: Float> fmt::Display for RelativeStrengthIndex<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "RSI({})", self.period)
+        fmt_params(f, "RSI", &self.params())
     }
 }
 
@@ -175,6 +295,49 @@ mod tests {
         assert_eq!(rsi.next(9.5).round(), 16.0);
     }
 
+    #[test]
+    fn test_f32() {
+        let mut rsi = RelativeStrengthIndex::<f32>::with_period(3).unwrap();
+        assert_eq!(rsi.next(10.0f32), 50.0f32);
+        assert_eq!(rsi.next(&Bar::new().close(10.5)).round(), 86.0);
+        assert_eq!(rsi.project_next(10.0).round(), 35.0);
+        assert_eq!(rsi.next(10.0).round(), 35.0);
+        assert_eq!(format!("{}", rsi), "RSI(3)");
+    }
+
+    #[test]
+    fn test_project_next() {
+        let mut rsi = RelativeStrengthIndex::new(3).unwrap();
+
+        for &x in &[10.0, 10.5, 10.0, 9.5, 9.5] {
+            let projected = rsi.project_next(x);
+            assert_eq!(rsi.project_next(&Bar::new().close(x)), projected);
+            // projecting does not change the state
+            assert_eq!(rsi.project_next(x), projected);
+            assert_eq!(rsi.next(x), projected);
+        }
+    }
+
+    #[test]
+    fn test_solve_for_input() {
+        let mut rsi = RelativeStrengthIndex::new(3).unwrap();
+        assert_eq!(rsi.solve_for_input(50.0), None);
+
+        for &x in &[10.0, 10.5, 10.0, 9.5] {
+            rsi.next(x);
+        }
+        assert_eq!(rsi.solve_for_input(0.0), None);
+        assert_eq!(rsi.solve_for_input(100.0), None);
+
+        for &target in &[70.0, 30.0, 10.0] {
+            let input = rsi.solve_for_input(target).unwrap();
+            assert_eq!(round(rsi.project_next(input)), target);
+        }
+        // a rise is needed to reach 70, a fall to reach 10
+        assert!(rsi.solve_for_input(70.0).unwrap() > 9.5);
+        assert!(rsi.solve_for_input(10.0).unwrap() < 9.5);
+    }
+
     #[test]
     fn test_reset() {
         let mut rsi = RelativeStrengthIndex::new(3).unwrap();
@@ -186,6 +349,16 @@ mod tests {
         assert_eq!(rsi.next(10.5).round(), 86.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RelativeStrengthIndex::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut RelativeStrengthIndex::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_default() {
         RelativeStrengthIndex::default();
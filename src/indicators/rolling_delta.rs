@@ -0,0 +1,216 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::Accumulator;
+use crate::ring_buffer::RingBuffer;
+use crate::{BuyVolume, Current, IsReady, MemoryFootprint, Next, Period, Reset, SellVolume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling Delta.
+///
+/// The sum of the order flow delta (volume bought at the ask minus volume sold at the bid)
+/// over the last _period_ bars. Unlike the [cumulative delta](struct.CumulativeDelta.html) it
+/// does not drift with the whole history and shows the recent net aggression only.
+///
+/// Needs inputs with order flow data implementing [BuyVolume](../trait.BuyVolume.html) and
+/// [SellVolume](../trait.SellVolume.html). With `Next<f64>` the input is the delta of a bar.
+///
+/// # Formula
+///
+/// delta = buy volume - sell volume
+///
+/// RD = sum(delta, _period_)
+///
+/// # Parameters
+///
+/// * _period_ - number of bars (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RollingDelta;
+/// use ta::Next;
+///
+/// let mut delta = RollingDelta::new(2).unwrap();
+///
+/// assert_eq!(delta.next(400.0), 400.0);
+/// assert_eq!(delta.next(-300.0), 100.0);
+/// assert_eq!(delta.next(-100.0), -400.0);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RollingDelta {
+    period: usize,
+    sum: Accumulator,
+    window: RingBuffer,
+    output: Option<f64>,
+}
+
+impl RollingDelta {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::zero_period("period")),
+            _ => Ok(Self {
+                period,
+                sum: Accumulator::new(false),
+                window: RingBuffer::new(period),
+                output: None,
+            }),
+        }
+    }
+
+    /// Same as `new`, with compensated summation of the window, so the sum does not drift
+    /// after millions of bars.
+    pub fn compensated(period: usize) -> Result<Self> {
+        let mut delta = Self::new(period)?;
+        delta.sum = Accumulator::new(true);
+        Ok(delta)
+    }
+}
+
+impl Period for RollingDelta {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for RollingDelta {
+    fn bars_until_ready(&self) -> usize {
+        self.period - self.window.len()
+    }
+}
+
+impl Next<f64> for RollingDelta {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if let Some(oldest) = self.window.push(input) {
+            self.sum.add(-oldest);
+        }
+        self.sum.add(input);
+        let sum = self.sum.value();
+        self.output = Some(sum);
+        sum
+    }
+}
+
+impl<T: BuyVolume + SellVolume> Next<&T> for RollingDelta {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.buy_volume() - input.sell_volume())
+    }
+}
+
+impl Current for RollingDelta {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for RollingDelta {
+    fn reset(&mut self) {
+        self.sum.reset();
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for RollingDelta {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl Default for RollingDelta {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for RollingDelta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DELTA({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingDelta);
+
+    fn bar(buy: f64, sell: f64) -> Bar {
+        Bar::new().buy_volume(buy).sell_volume(sell)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(RollingDelta::new(0).is_err());
+        assert!(RollingDelta::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut delta = RollingDelta::new(3).unwrap();
+
+        assert_eq!(delta.next(&bar(10.0, 5.0)), 5.0);
+        assert_eq!(delta.next(&bar(7.0, 7.0)), 5.0);
+        assert_eq!(delta.next(&bar(2.0, 9.0)), -2.0);
+        // 5 drops out
+        assert_eq!(delta.next(&bar(6.0, 3.0)), -4.0);
+        assert_eq!(delta.next(&bar(6.0, 6.0)), -4.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut delta = RollingDelta::new(2).unwrap();
+
+        delta.next(&bar(10.0, 5.0));
+        delta.next(&bar(8.0, 5.0));
+
+        delta.reset();
+        assert_eq!(delta.next(&bar(1.0, 4.0)), -3.0);
+        assert_eq!(delta.next(&bar(4.0, 1.0)), 0.0);
+    }
+
+    #[test]
+    fn test_compensated() {
+        let mut delta = RollingDelta::new(2).unwrap();
+        let mut compensated = RollingDelta::compensated(2).unwrap();
+        for x in [1e16, 1.0, 2.0] {
+            delta.next(x);
+            compensated.next(x);
+        }
+        assert_eq!(delta.current(), Some(2.0));
+        assert_eq!(compensated.current(), Some(3.0));
+
+        compensated.reset();
+        assert_eq!(compensated.next(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RollingDelta::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut RollingDelta::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingDelta::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let delta = RollingDelta::new(10).unwrap();
+        assert_eq!(format!("{}", delta), "DELTA(10)");
+    }
+}
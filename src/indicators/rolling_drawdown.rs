@@ -0,0 +1,496 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec};
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling drawdown.
+///
+/// Tracks how far a series is below its peak. Works the same on prices and on the equity
+/// curve of a strategy. Two values are returned on every input:
+///
+/// * _drawdown_ - the current decline from the peak
+/// * _max_drawdown_ - the largest decline from a peak to a later trough
+///
+/// Both are measured over the last _period_ values, or over all the values since the last
+/// reset when the indicator is created with [since_reset](#method.since_reset).
+///
+/// # Formula
+///
+/// drawdown = (peak - value) / peak
+///
+/// Where:
+///
+/// * _peak_ - highest value before or at _value_
+///
+/// Drawdowns are fractions between 0 and 1 (0.25 is a decline of 25%). The drawdown from a
+/// peak which is not positive is 0.
+///
+/// Over a window, both values are updated in O(1) amortized time per input, without scanning
+/// the window.
+///
+/// # Parameters
+///
+/// * _period_ - size of the window (integer greater than 0). Default is 252.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RollingDrawdown;
+/// use ta::Next;
+///
+/// let mut dd = RollingDrawdown::since_reset();
+/// dd.next(100.0);
+/// assert_eq!(dd.next(80.0).drawdown, 0.2);
+///
+/// let out = dd.next(90.0);
+/// assert_eq!(out.drawdown, 0.1);
+/// assert_eq!(out.max_drawdown, 0.2);
+/// ```
+///
+/// # Links
+///
+/// * [Drawdown, Wikipedia](https://en.wikipedia.org/wiki/Drawdown_(economics))
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RollingDrawdown {
+    period: Option<usize>,
+    window: DrawdownWindow,
+    peak: f64,
+    max_drawdown: f64,
+    output: Option<RollingDrawdownOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RollingDrawdownOutput {
+    /// Decline of the current value from the peak.
+    pub drawdown: f64,
+    /// Largest decline from a peak to a later trough.
+    pub max_drawdown: f64,
+}
+
+impl RollingDrawdown {
+    /// Drawdowns over the last _period_ values.
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::zero_period("period")),
+            _ => Ok(Self {
+                period: Some(period),
+                window: DrawdownWindow::new(period),
+                peak: f64::NEG_INFINITY,
+                max_drawdown: 0.0,
+                output: None,
+            }),
+        }
+    }
+
+    /// Drawdowns over all the values since the creation or the last reset.
+    pub fn since_reset() -> Self {
+        Self {
+            period: None,
+            window: DrawdownWindow::new(0),
+            peak: f64::NEG_INFINITY,
+            max_drawdown: 0.0,
+            output: None,
+        }
+    }
+
+    /// Size of the window, `None` when the drawdowns are measured since the last reset.
+    pub fn window(&self) -> Option<usize> {
+        self.period
+    }
+}
+
+impl Period for RollingDrawdown {
+    /// The size of the window, 0 when the drawdowns are measured since the last reset, as there
+    /// is no window to fill.
+    fn period(&self) -> usize {
+        self.period.unwrap_or(0)
+    }
+}
+
+impl IsReady for RollingDrawdown {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.window.len()
+    }
+}
+
+fn drawdown(peak: f64, value: f64) -> f64 {
+    if peak > 0.0 {
+        (peak - value) / peak
+    } else {
+        0.0
+    }
+}
+
+impl Next<f64> for RollingDrawdown {
+    type Output = RollingDrawdownOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        match self.period {
+            None => {
+                self.peak = self.peak.max(input);
+                self.max_drawdown = self.max_drawdown.max(drawdown(self.peak, input));
+            }
+            Some(period) => {
+                if self.window.len() == period {
+                    self.window.pop();
+                }
+                self.window.push(input);
+                self.peak = self.window.peak();
+                self.max_drawdown = self.window.max_drawdown();
+            }
+        }
+
+        let output = RollingDrawdownOutput {
+            drawdown: drawdown(self.peak, input),
+            max_drawdown: self.max_drawdown,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for RollingDrawdown {
+    type Output = RollingDrawdownOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Current for RollingDrawdown {
+    type Output = RollingDrawdownOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for RollingDrawdown {
+    fn reset(&mut self) {
+        self.window.clear();
+        self.peak = f64::NEG_INFINITY;
+        self.max_drawdown = 0.0;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for RollingDrawdown {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl Default for RollingDrawdown {
+    fn default() -> Self {
+        Self::new(252).unwrap()
+    }
+}
+
+impl fmt::Display for RollingDrawdown {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.period {
+            Some(period) => write!(f, "DD({})", period),
+            None => write!(f, "DD"),
+        }
+    }
+}
+
+/// A value of the front of the window, with the peak of the values from it to the newest value
+/// of the front, and the max drawdown of these values.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Default)]
+struct Suffix {
+    value: f64,
+    peak: f64,
+    /// Lowest value from this one up to the next higher value.
+    trough: f64,
+    max_drawdown: f64,
+    /// Position of the next higher value in the front, `usize::MAX` if there is none.
+    next: usize,
+}
+
+/// A value of the back of the window, with the peak, the lowest value and the drawdown of the
+/// values from the oldest value of the back to it.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Default)]
+struct Prefix {
+    value: f64,
+    peak: f64,
+    trough: f64,
+    drawdown: f64,
+}
+
+/// Peak and max drawdown of a sliding window, in O(1) amortized per value.
+///
+/// The window is a queue of two stacks. New values are pushed on the back, which keeps the
+/// running peak, trough and drawdown of its values. The oldest values are popped from the
+/// front, which keeps the peak and the max drawdown of the values from every one of them to
+/// its end. When the front is empty, the back is moved over at once. Every value is moved
+/// once, the next higher values are followed as links, so the move is linear.
+///
+/// The drawdowns of the back values are measured from the higher of their own peak and the
+/// peak of the front. The peak of the front falls, when its values leave, so the back values
+/// measured from their own peak only grow towards the oldest one, from the `split` on.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+struct DrawdownWindow {
+    /// Oldest value last.
+    front: Box<[Suffix]>,
+    front_len: usize,
+    back: Box<[Prefix]>,
+    back_len: usize,
+    /// First back value with a peak above the peak of the front, `back_len` if there is none.
+    split: usize,
+    /// Max drawdown of the back values from the `split` on.
+    split_max_drawdown: f64,
+}
+
+impl DrawdownWindow {
+    fn new(period: usize) -> Self {
+        Self {
+            front: vec![Suffix::default(); period].into_boxed_slice(),
+            front_len: 0,
+            back: vec![Prefix::default(); period].into_boxed_slice(),
+            back_len: 0,
+            split: 0,
+            split_max_drawdown: 0.0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.front_len + self.back_len
+    }
+
+    fn front_peak(&self) -> f64 {
+        self.front_len
+            .checked_sub(1)
+            .map_or(f64::NEG_INFINITY, |i| self.front[i].peak)
+    }
+
+    fn push(&mut self, value: f64) {
+        let i = self.back_len;
+        let (peak, trough) = match i.checked_sub(1).map(|j| self.back[j]) {
+            Some(last) => (last.peak.max(value), last.trough.min(value)),
+            None => (value, value),
+        };
+        let prefix = Prefix {
+            value,
+            peak,
+            trough,
+            drawdown: drawdown(peak, value),
+        };
+        self.back[i] = prefix;
+        self.back_len += 1;
+
+        if self.split < i {
+            self.split_max_drawdown = self.split_max_drawdown.max(prefix.drawdown);
+        } else if peak > self.front_peak() {
+            self.split_max_drawdown = prefix.drawdown;
+        } else {
+            self.split = self.back_len;
+        }
+    }
+
+    /// Removes the oldest value.
+    fn pop(&mut self) {
+        if self.front_len == 0 {
+            self.move_back();
+        }
+        self.front_len -= 1;
+
+        let front_peak = self.front_peak();
+        while self.split > 0 && self.back[self.split - 1].peak > front_peak {
+            self.split -= 1;
+            let drawdown = self.back[self.split].drawdown;
+            self.split_max_drawdown = self.split_max_drawdown.max(drawdown);
+        }
+    }
+
+    /// Moves the back to the front, from the newest value to the oldest one.
+    fn move_back(&mut self) {
+        let mut peak = f64::NEG_INFINITY;
+        for i in 0..self.back_len {
+            let value = self.back[self.back_len - 1 - i].value;
+            // the newer values up to the next higher one are below their peak `value`
+            let mut trough = value;
+            let mut next = i.checked_sub(1).unwrap_or(usize::MAX);
+            while next != usize::MAX && self.front[next].value <= value {
+                trough = trough.min(self.front[next].trough);
+                next = self.front[next].next;
+            }
+            let later = match next {
+                usize::MAX => 0.0,
+                next => self.front[next].max_drawdown,
+            };
+            peak = peak.max(value);
+            self.front[i] = Suffix {
+                value,
+                peak,
+                trough,
+                max_drawdown: later.max(drawdown(value, trough)),
+                next,
+            };
+        }
+        self.front_len = self.back_len;
+        self.back_len = 0;
+        self.split = 0;
+        self.split_max_drawdown = 0.0;
+    }
+
+    fn peak(&self) -> f64 {
+        match self.back_len.checked_sub(1) {
+            Some(last) => self.front_peak().max(self.back[last].peak),
+            None => self.front_peak(),
+        }
+    }
+
+    fn max_drawdown(&self) -> f64 {
+        let front = self
+            .front_len
+            .checked_sub(1)
+            .map_or(0.0, |i| self.front[i].max_drawdown);
+        // the back values before the split are measured from the peak of the front
+        let below_front_peak = match self.split.checked_sub(1) {
+            Some(last) => drawdown(self.front_peak(), self.back[last].trough),
+            None => 0.0,
+        };
+        front.max(below_front_peak).max(self.split_max_drawdown)
+    }
+
+    fn clear(&mut self) {
+        self.front_len = 0;
+        self.back_len = 0;
+        self.split = 0;
+        self.split_max_drawdown = 0.0;
+    }
+
+    fn heap_size(&self) -> usize {
+        core::mem::size_of_val(&*self.front) + core::mem::size_of_val(&*self.back)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingDrawdown);
+
+    fn next(dd: &mut RollingDrawdown, input: f64) -> (f64, f64) {
+        let out = dd.next(input);
+        (round(out.drawdown), round(out.max_drawdown))
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(RollingDrawdown::new(0).is_err());
+        assert!(RollingDrawdown::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut dd = RollingDrawdown::new(3).unwrap();
+
+        assert_eq!(next(&mut dd, 100.0), (0.0, 0.0));
+        assert_eq!(next(&mut dd, 110.0), (0.0, 0.0));
+        assert_eq!(next(&mut dd, 99.0), (0.1, 0.1));
+        assert_eq!(next(&mut dd, 104.5), (0.05, 0.1));
+        // the peak 110 left the window, 99 -> 104.5 -> 120 only rises
+        assert_eq!(next(&mut dd, 120.0), (0.0, 0.0));
+        assert_eq!(next(&mut dd, 90.0), (0.25, 0.25));
+    }
+
+    #[test]
+    fn test_same_as_scan() {
+        // crosses 0, so that troughs below 0 are measured from positive peaks
+        let values: [f64; 200] = core::array::from_fn(|i| ((i * 37) % 23) as f64 - 4.0);
+        for period in [1, 2, 3, 7, 16] {
+            let mut dd = RollingDrawdown::new(period).unwrap();
+            for (i, &value) in values.iter().enumerate() {
+                let window = &values[(i + 1).saturating_sub(period)..=i];
+                let mut peak = f64::NEG_INFINITY;
+                let mut max_drawdown = 0.0;
+                for &x in window {
+                    peak = peak.max(x);
+                    max_drawdown = f64::max(max_drawdown, drawdown(peak, x));
+                }
+                let expected = RollingDrawdownOutput {
+                    drawdown: drawdown(peak, value),
+                    max_drawdown,
+                };
+                assert_eq!(dd.next(value), expected, "period {} at {}", period, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_since_reset() {
+        let mut dd = RollingDrawdown::since_reset();
+
+        assert_eq!(next(&mut dd, 100.0), (0.0, 0.0));
+        assert_eq!(next(&mut dd, 110.0), (0.0, 0.0));
+        assert_eq!(next(&mut dd, 99.0), (0.1, 0.1));
+        assert_eq!(next(&mut dd, 104.5), (0.05, 0.1));
+        assert_eq!(next(&mut dd, 120.0), (0.0, 0.1));
+        assert_eq!(next(&mut dd, 114.0), (0.05, 0.1));
+        assert_eq!(next(&mut dd, 90.0), (0.25, 0.25));
+    }
+
+    #[test]
+    fn test_non_positive_peak() {
+        let mut dd = RollingDrawdown::since_reset();
+
+        assert_eq!(next(&mut dd, -1.0), (0.0, 0.0));
+        assert_eq!(next(&mut dd, -5.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut dd = RollingDrawdown::new(3).unwrap();
+        dd.next(100.0);
+        dd.next(50.0);
+        dd.reset();
+        assert_eq!(next(&mut dd, 80.0), (0.0, 0.0));
+
+        let mut dd = RollingDrawdown::since_reset();
+        dd.next(100.0);
+        dd.next(50.0);
+        dd.reset();
+        assert_eq!(next(&mut dd, 80.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(RollingDrawdown::new(3).unwrap().period(), 3);
+        assert_eq!(RollingDrawdown::since_reset().period(), 0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RollingDrawdown::new(3).unwrap(), 1.0, 3);
+        assert!(RollingDrawdown::since_reset().is_ready());
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut RollingDrawdown::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingDrawdown::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", RollingDrawdown::new(20).unwrap()), "DD(20)");
+        assert_eq!(format!("{}", RollingDrawdown::since_reset()), "DD");
+    }
+}
@@ -0,0 +1,393 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::ring_buffer::RingBuffer;
+use crate::{Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling information coefficient (IC).
+///
+/// Measures how well a predictive signal (e.g. an indicator value) ranks the returns that
+/// follow it. The IC is the Spearman rank correlation between the signal and the _horizon_-bar
+/// forward return over the last _period_ observations.
+///
+/// The input is a pair _(signal, price)_ for the current bar. To avoid look-ahead, a signal is
+/// held back until its forward return is known: the signal of bar _t_ - _horizon_ is paired with
+/// the return from bar _t_ - _horizon_ to bar _t_. So the first pair enters the window only after
+/// _horizon_ + 1 bars.
+///
+/// # Formula
+///
+/// IC = Pearson correlation of rank(signal<sub>t-h</sub>) and rank(P<sub>t</sub> / P<sub>t-h</sub> - 1)
+///
+/// Where:
+///
+/// * _h_ - horizon
+/// * tied values get the average of their ranks
+///
+/// The output is in the range of -1..1. It is 0 while there are less than 2 pairs in the window
+/// and when either series in the window is constant.
+///
+/// # Parameters
+///
+/// * _period_ - number of signal/return pairs in the window (integer greater than 1). Default is 20.
+/// * _horizon_ - number of bars of the forward return (integer greater than 0). Default is 1.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RollingIC;
+/// use ta::Next;
+///
+/// let mut ic = RollingIC::new(3, 1).unwrap();
+///
+/// // the signal perfectly predicts the order of the returns that follow
+/// assert_eq!(ic.next((0.0, 100.0)), 0.0);
+/// assert_eq!(ic.next((2.0, 101.0)), 0.0);
+/// assert_eq!(ic.next((1.0, 103.0)), 1.0);
+/// assert_eq!(ic.next((3.0, 104.5)), 1.0);
+/// ```
+///
+/// # Links
+///
+/// * [Information coefficient, Wikipedia](https://en.wikipedia.org/wiki/Information_coefficient)
+/// * [Spearman's rank correlation coefficient, Wikipedia](https://en.wikipedia.org/wiki/Spearman%27s_rank_correlation_coefficient)
+///
+#[doc(alias = "IC")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RollingIC {
+    period: usize,
+    horizon: usize,
+    /// Signals and prices waiting for their forward return.
+    lag: RingBuffer<(f64, f64)>,
+    /// Signals paired with their forward returns.
+    pairs: RingBuffer<(f64, f64)>,
+    scratch: Scratch,
+    output: Option<f64>,
+}
+
+/// Buffers reused between calls to avoid allocating on every bar. They are cloned and
+/// serialized as their capacity, so a cloned or deserialized indicator does not allocate in
+/// `next` either.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "usize", into = "usize"))]
+#[derive(Debug)]
+struct Scratch {
+    order: Vec<usize>,
+    signal_ranks: Vec<f64>,
+    return_ranks: Vec<f64>,
+}
+
+impl Scratch {
+    fn new(period: usize) -> Self {
+        Self {
+            order: Vec::with_capacity(period),
+            signal_ranks: Vec::with_capacity(period),
+            return_ranks: Vec::with_capacity(period),
+        }
+    }
+}
+
+impl Clone for Scratch {
+    fn clone(&self) -> Self {
+        Self::new(self.order.capacity())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<usize> for Scratch {
+    fn from(capacity: usize) -> Self {
+        Self::new(capacity)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Scratch> for usize {
+    fn from(scratch: Scratch) -> Self {
+        scratch.order.capacity()
+    }
+}
+
+impl MemoryFootprint for Scratch {
+    fn heap_size(&self) -> usize {
+        self.order.capacity() * core::mem::size_of::<usize>()
+            + (self.signal_ranks.capacity() + self.return_ranks.capacity())
+                * core::mem::size_of::<f64>()
+    }
+}
+
+impl RollingIC {
+    pub fn new(period: usize, horizon: usize) -> Result<Self> {
+        if period < 2 {
+            return Err(TaError::invalid_period(
+                "period",
+                period,
+                "must be at least 2",
+            ));
+        }
+        if horizon == 0 {
+            return Err(TaError::zero_period("horizon"));
+        }
+        Ok(Self {
+            period,
+            horizon,
+            lag: RingBuffer::new(horizon),
+            pairs: RingBuffer::new(period),
+            scratch: Scratch::new(period),
+            output: None,
+        })
+    }
+
+    pub fn horizon(&self) -> usize {
+        self.horizon
+    }
+
+    fn spearman(&mut self) -> f64 {
+        let pairs = &self.pairs;
+        let scratch = &mut self.scratch;
+        rank(
+            pairs.iter().map(|p| p.0),
+            &mut scratch.order,
+            &mut scratch.signal_ranks,
+        );
+        rank(
+            pairs.iter().map(|p| p.1),
+            &mut scratch.order,
+            &mut scratch.return_ranks,
+        );
+        pearson(&scratch.signal_ranks, &scratch.return_ranks)
+    }
+}
+
+/// Writes the ranks (starting from 1) of `values` into `ranks`, ties get the average rank.
+fn rank<I: Iterator<Item = f64>>(values: I, order: &mut Vec<usize>, ranks: &mut Vec<f64>) {
+    ranks.clear();
+    ranks.extend(values);
+    order.clear();
+    order.extend(0..ranks.len());
+    // ties get the same rank in any order, and the unstable sort needs no buffer
+    order.sort_unstable_by(|&a, &b| ranks[a].total_cmp(&ranks[b]));
+
+    let mut start = 0;
+    while start < order.len() {
+        let value = ranks[order[start]];
+        let mut end = start + 1;
+        while end < order.len() && ranks[order[end]] == value {
+            end += 1;
+        }
+        let average_rank = (start + end + 1) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = average_rank;
+        }
+        start = end;
+    }
+}
+
+fn pearson(xs: &[f64], ys: &[f64]) -> f64 {
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+
+    let mut cov = 0.0;
+    let mut var_x = 0.0;
+    let mut var_y = 0.0;
+    for (x, y) in xs.iter().zip(ys) {
+        let dx = x - mean_x;
+        let dy = y - mean_y;
+        cov += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+
+    if var_x == 0.0 || var_y == 0.0 {
+        0.0
+    } else {
+        cov / (var_x * var_y).sqrt()
+    }
+}
+
+impl Period for RollingIC {
+    fn period(&self) -> usize {
+        // the first pair enters the window after horizon + 1 bars
+        self.period + self.horizon
+    }
+}
+
+impl IsReady for RollingIC {
+    fn bars_until_ready(&self) -> usize {
+        (self.horizon - self.lag.len()) + (self.period - self.pairs.len())
+    }
+}
+
+impl Next<(f64, f64)> for RollingIC {
+    type Output = f64;
+
+    fn next(&mut self, (signal, price): (f64, f64)) -> Self::Output {
+        if let Some((old_signal, old_price)) = self.lag.push((signal, price)) {
+            self.pairs
+                .push((old_signal, (price - old_price) / old_price));
+        }
+
+        let output = if self.pairs.len() < 2 {
+            0.0
+        } else {
+            self.spearman()
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for RollingIC {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for RollingIC {
+    fn reset(&mut self) {
+        self.lag.clear();
+        self.pairs.clear();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for RollingIC {
+    fn heap_size(&self) -> usize {
+        self.lag.heap_size() + self.pairs.heap_size() + self.scratch.heap_size()
+    }
+}
+
+impl Default for RollingIC {
+    fn default() -> Self {
+        Self::new(20, 1).unwrap()
+    }
+}
+
+impl fmt::Display for RollingIC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IC({}, {})", self.period, self.horizon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(RollingIC::new(0, 1).is_err());
+        assert!(RollingIC::new(1, 1).is_err());
+        assert!(RollingIC::new(2, 0).is_err());
+        assert!(RollingIC::new(2, 1).is_ok());
+    }
+
+    #[test]
+    fn test_rank() {
+        let mut order = Vec::new();
+        let mut ranks = Vec::new();
+        rank(
+            [3.0, 1.0, 4.0, 1.0, 5.0].iter().cloned(),
+            &mut order,
+            &mut ranks,
+        );
+        assert_eq!(ranks, vec![3.0, 1.5, 4.0, 1.5, 5.0]);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ic = RollingIC::new(4, 1).unwrap();
+
+        // signals: 1, 2, 3, 4; following returns: 1%, -1%, 2%, 3%
+        assert_eq!(ic.next((1.0, 100.0)), 0.0);
+        assert_eq!(ic.next((2.0, 101.0)), 0.0);
+        assert_eq!(ic.next((3.0, 99.99)), -1.0);
+        assert_eq!(round(ic.next((4.0, 101.9898))), 0.5);
+        // ranks (1, 2, 3, 4) vs (2, 1, 3, 4)
+        assert_eq!(round(ic.next((0.0, 105.049494))), 0.8);
+    }
+
+    #[test]
+    fn test_no_lookahead() {
+        let mut ic = RollingIC::new(2, 2).unwrap();
+
+        // the signal of the bar is not used before its 2-bar return is known
+        assert_eq!(ic.next((1.0, 10.0)), 0.0);
+        assert_eq!(ic.next((2.0, 10.0)), 0.0);
+        assert_eq!(ic.next((3.0, 11.0)), 0.0);
+        assert_eq!(ic.pairs.get(0), Some((1.0, 0.1)));
+        assert_eq!(ic.pairs.len(), 1);
+        // signal 2 is paired with 10 -> 9
+        assert_eq!(ic.next((4.0, 9.0)), -1.0);
+    }
+
+    #[test]
+    fn test_constant_series() {
+        let mut ic = RollingIC::new(3, 1).unwrap();
+
+        ic.next((1.0, 10.0));
+        ic.next((1.0, 11.0));
+        assert_eq!(ic.next((1.0, 12.0)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ic = RollingIC::new(3, 1).unwrap();
+
+        ic.next((1.0, 10.0));
+        ic.next((2.0, 11.0));
+        ic.next((3.0, 12.0));
+
+        ic.reset();
+        assert_eq!(ic.next((1.0, 10.0)), 0.0);
+        assert_eq!(ic.next((1.0, 10.0)), 0.0);
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut ic = RollingIC::new(20, 1).unwrap();
+        ic.next((1.0, 10.0));
+
+        // the scratch buffers keep their capacity
+        let clone = ic.clone();
+        assert_eq!(clone.heap_size(), ic.heap_size());
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(RollingIC::new(20, 5).unwrap().period(), 25);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RollingIC::new(3, 2).unwrap(), (1.0, 1.0), 5);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(
+            &mut RollingIC::default(),
+            [(1.0, 10.0), (2.0, 11.0), (1.5, 10.5)],
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        RollingIC::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ic = RollingIC::new(20, 5).unwrap();
+        assert_eq!(format!("{}", ic), "IC(20, 5)");
+    }
+}
@@ -0,0 +1,199 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::rolling_moments::RollingMoments;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling kurtosis.
+///
+/// Returns the excess kurtosis of the last _period_ values: how heavy the tails of their
+/// distribution are compared to the normal distribution. Positive values mean fat tails, i.e.
+/// extreme moves are more frequent than a normal distribution would suggest.
+///
+/// The central moments of the window are updated incrementally as values enter and leave it,
+/// so every update takes constant time.
+///
+/// # Formula
+///
+/// kurtosis = m<sub>4</sub> / m<sub>2</sub><sup>2</sup> - 3
+///
+/// Where:
+///
+/// * _m<sub>k</sub>_ - _k_-th central moment of the window: mean of (x<sub>i</sub> - mean)<sup>k</sup>
+///
+/// This is the population (biased) excess kurtosis. It is 0, when all the values in the window
+/// are equal.
+///
+/// # Parameters
+///
+/// * _period_ - size of the window (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RollingKurtosis;
+/// use ta::Next;
+///
+/// let mut kurt = RollingKurtosis::new(4).unwrap();
+/// kurt.next(1.0);
+/// kurt.next(-1.0);
+/// kurt.next(1.0);
+/// // two point distribution has the lowest possible kurtosis
+/// assert_eq!(kurt.next(-1.0), -2.0);
+/// ```
+///
+/// # Links
+///
+/// * [Kurtosis, Wikipedia](https://en.wikipedia.org/wiki/Kurtosis)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RollingKurtosis {
+    moments: RollingMoments,
+    output: Option<f64>,
+}
+
+impl RollingKurtosis {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::zero_period("period")),
+            _ => Ok(Self {
+                moments: RollingMoments::new(period),
+                output: None,
+            }),
+        }
+    }
+}
+
+impl Period for RollingKurtosis {
+    fn period(&self) -> usize {
+        self.moments.period()
+    }
+}
+
+impl IsReady for RollingKurtosis {
+    fn bars_until_ready(&self) -> usize {
+        self.moments.bars_until_ready()
+    }
+}
+
+impl Next<f64> for RollingKurtosis {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.moments.push(input);
+        let output = self.moments.kurtosis();
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for RollingKurtosis {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Current for RollingKurtosis {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for RollingKurtosis {
+    fn reset(&mut self) {
+        self.moments.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for RollingKurtosis {
+    fn heap_size(&self) -> usize {
+        self.moments.heap_size()
+    }
+}
+
+impl Default for RollingKurtosis {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for RollingKurtosis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KURT({})", self.moments.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingKurtosis);
+
+    #[test]
+    fn test_new() {
+        assert!(RollingKurtosis::new(0).is_err());
+        assert!(RollingKurtosis::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut kurt = RollingKurtosis::new(4).unwrap();
+
+        assert_eq!(kurt.next(1.0), 0.0);
+        assert_eq!(kurt.next(2.0), -2.0);
+        assert_eq!(round(kurt.next(6.0)), -1.5);
+        assert_eq!(round(kurt.next(3.0)), -1.0);
+        // 1.0 drops out: [2, 6, 3, 20]
+        assert_eq!(round(kurt.next(20.0)), -0.785);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut kurt = RollingKurtosis::new(3).unwrap();
+
+        kurt.next(&Bar::new().close(4));
+        kurt.next(&Bar::new().close(4));
+        assert_eq!(kurt.next(&Bar::new().close(4)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut kurt = RollingKurtosis::new(3).unwrap();
+
+        kurt.next(1.0);
+        kurt.next(7.0);
+
+        kurt.reset();
+        assert_eq!(kurt.next(5.0), 0.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RollingKurtosis::new(4).unwrap(), 1.0, 4);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut RollingKurtosis::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingKurtosis::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let kurt = RollingKurtosis::new(30).unwrap();
+        assert_eq!(format!("{}", kurt), "KURT(30)");
+    }
+}
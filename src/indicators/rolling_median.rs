@@ -0,0 +1,198 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::order_statistics::RollingOrderStatistics;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling median.
+///
+/// Returns the median of the last _period_ values: the middle value of the sorted window, or
+/// the mean of the two middle values when the window has an even size. Unlike a moving
+/// average it is not pulled away by a single outlier, which makes it a robust smoother for
+/// noisy prices with occasional bad ticks.
+///
+/// The window is split between two heaps at the middle, so an update takes _O(log(period))_
+/// instead of sorting the window on every bar, and never allocates.
+///
+/// # Parameters
+///
+/// * _period_ - size of the window (integer greater than 0). Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RollingMedian;
+/// use ta::Next;
+///
+/// let mut median = RollingMedian::new(3).unwrap();
+/// assert_eq!(median.next(10.0), 10.0);
+/// assert_eq!(median.next(12.0), 11.0);
+/// assert_eq!(median.next(11.0), 11.0);
+/// // the outlier does not move the median
+/// assert_eq!(median.next(100.0), 12.0);
+/// assert_eq!(median.next(13.0), 13.0);
+/// ```
+///
+/// # Links
+///
+/// * [Median filter, Wikipedia](https://en.wikipedia.org/wiki/Median_filter)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RollingMedian {
+    window: RollingOrderStatistics,
+    output: Option<f64>,
+}
+
+impl RollingMedian {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::zero_period("period")),
+            _ => Ok(Self {
+                window: RollingOrderStatistics::new(period, 0.5),
+                output: None,
+            }),
+        }
+    }
+}
+
+impl Period for RollingMedian {
+    fn period(&self) -> usize {
+        self.window.period()
+    }
+}
+
+impl IsReady for RollingMedian {
+    fn bars_until_ready(&self) -> usize {
+        self.window.bars_until_ready()
+    }
+}
+
+impl Next<f64> for RollingMedian {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.window.push(input);
+        let output = self.window.quantile();
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for RollingMedian {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Current for RollingMedian {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for RollingMedian {
+    fn reset(&mut self) {
+        self.window.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for RollingMedian {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl Default for RollingMedian {
+    fn default() -> Self {
+        Self::new(9).unwrap()
+    }
+}
+
+impl fmt::Display for RollingMedian {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MEDIAN({})", self.window.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingMedian);
+
+    #[test]
+    fn test_new() {
+        assert!(RollingMedian::new(0).is_err());
+        assert!(RollingMedian::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut median = RollingMedian::new(4).unwrap();
+
+        assert_eq!(median.next(4.0), 4.0);
+        assert_eq!(median.next(2.0), 3.0);
+        assert_eq!(median.next(8.0), 4.0);
+        assert_eq!(median.next(6.0), 5.0);
+        // 4.0 drops out: [2, 8, 6, 1]
+        assert_eq!(median.next(1.0), 4.0);
+        assert_eq!(median.next(1.0), 3.5);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut median = RollingMedian::new(2).unwrap();
+
+        median.next(&Bar::new().close(10));
+        assert_eq!(median.next(&Bar::new().close(20)), 15.0);
+    }
+
+    #[test]
+    fn test_period_one() {
+        let mut median = RollingMedian::new(1).unwrap();
+
+        assert_eq!(median.next(3.0), 3.0);
+        assert_eq!(median.next(-1.0), -1.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut median = RollingMedian::new(3).unwrap();
+
+        median.next(10.0);
+        median.next(20.0);
+
+        median.reset();
+        assert_eq!(median.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RollingMedian::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut RollingMedian::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingMedian::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let median = RollingMedian::new(7).unwrap();
+        assert_eq!(format!("{}", median), "MEDIAN(7)");
+    }
+}
@@ -0,0 +1,183 @@
+use crate::ring_buffer::RingBuffer;
+use crate::MemoryFootprint;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Central moments of the last `period` values.
+///
+/// Values entering and leaving the window update the mean and the sums of the 2nd, 3rd and 4th
+/// powers of the deviations from it, so every update takes constant time and, unlike raw power
+/// sums, does not lose precision, when the values are far from 0.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub(crate) struct RollingMoments {
+    /// Number of values in the moments.
+    count: usize,
+    mean: f64,
+    m2: f64,
+    m3: f64,
+    m4: f64,
+    window: RingBuffer,
+}
+
+impl RollingMoments {
+    /// `period` must be greater than 0.
+    pub(crate) fn new(period: usize) -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            m3: 0.0,
+            m4: 0.0,
+            window: RingBuffer::new(period),
+        }
+    }
+
+    pub(crate) fn period(&self) -> usize {
+        self.window.capacity()
+    }
+
+    pub(crate) fn bars_until_ready(&self) -> usize {
+        self.window.capacity() - self.window.len()
+    }
+
+    /// Adds a value, dropping the oldest one when the window is full.
+    pub(crate) fn push(&mut self, value: f64) {
+        if let Some(oldest) = self.window.push(value) {
+            self.remove(oldest);
+        }
+        self.add(value);
+    }
+
+    fn add(&mut self, value: f64) {
+        self.count += 1;
+        let n = self.count as f64;
+        let delta = value - self.mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * (n - 1.0);
+
+        self.mean += delta_n;
+        self.m4 += term * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+        self.m3 += term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m2 += term;
+    }
+
+    /// Reverses `add` of the given value.
+    fn remove(&mut self, value: f64) {
+        if self.count == 1 {
+            self.count = 0;
+            self.mean = 0.0;
+            self.m2 = 0.0;
+            self.m3 = 0.0;
+            self.m4 = 0.0;
+            return;
+        }
+        let n = self.count as f64;
+        let mean = (n * self.mean - value) / (n - 1.0);
+        let delta = value - mean;
+        let delta_n = delta / n;
+        let delta_n2 = delta_n * delta_n;
+        let term = delta * delta_n * (n - 1.0);
+
+        self.count -= 1;
+        self.mean = mean;
+        self.m2 = (self.m2 - term).max(0.0);
+        self.m3 -= term * delta_n * (n - 2.0) - 3.0 * delta_n * self.m2;
+        self.m4 -= term * delta_n2 * (n * n - 3.0 * n + 3.0) + 6.0 * delta_n2 * self.m2
+            - 4.0 * delta_n * self.m3;
+    }
+
+    /// Whether the values in the window are (almost) equal, so the shape of the distribution
+    /// is undefined.
+    fn is_flat(&self) -> bool {
+        self.m2 <= f64::EPSILON * self.count as f64 * self.mean * self.mean
+    }
+
+    /// Population skewness: _√n * m3 / m2<sup>3/2</sup>_.
+    pub(crate) fn skewness(&self) -> f64 {
+        if self.is_flat() {
+            return 0.0;
+        }
+        (self.count as f64).sqrt() * self.m3 / self.m2.powf(1.5)
+    }
+
+    /// Population excess kurtosis: _n * m4 / m2<sup>2</sup> - 3_.
+    pub(crate) fn kurtosis(&self) -> f64 {
+        if self.is_flat() {
+            return 0.0;
+        }
+        self.count as f64 * self.m4 / (self.m2 * self.m2) - 3.0
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.window.clear();
+        self.count = 0;
+        self.mean = 0.0;
+        self.m2 = 0.0;
+        self.m3 = 0.0;
+        self.m4 = 0.0;
+    }
+}
+
+impl MemoryFootprint for RollingMoments {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive(window: &[f64]) -> (f64, f64) {
+        let n = window.len() as f64;
+        let mean = window.iter().sum::<f64>() / n;
+        let moment = |k: i32| window.iter().map(|x| (x - mean).powi(k)).sum::<f64>() / n;
+        let (m2, m3, m4) = (moment(2), moment(3), moment(4));
+        (m3 / m2.powf(1.5), m4 / (m2 * m2) - 3.0)
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let values: Vec<f64> = (0..300)
+            .map(|i| 1000.0 + ((i * 37 % 101) as f64).powf(1.3) / 7.0)
+            .collect();
+
+        for &period in &[3, 4, 10, 50] {
+            let mut moments = RollingMoments::new(period);
+            for (i, &value) in values.iter().enumerate() {
+                moments.push(value);
+                if i + 1 < 3 {
+                    continue;
+                }
+                let window = &values[(i + 1).saturating_sub(period)..=i];
+                let (skewness, kurtosis) = naive(window);
+                assert!((moments.skewness() - skewness).abs() < 1e-6);
+                assert!((moments.kurtosis() - kurtosis).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_flat() {
+        let mut moments = RollingMoments::new(3);
+        for _ in 0..5 {
+            moments.push(42.1);
+        }
+        assert_eq!(moments.skewness(), 0.0);
+        assert_eq!(moments.kurtosis(), 0.0);
+    }
+
+    #[test]
+    fn test_period_one() {
+        let mut moments = RollingMoments::new(1);
+        moments.push(1.0);
+        moments.push(5.0);
+        assert_eq!(moments.mean, 5.0);
+        assert_eq!(moments.skewness(), 0.0);
+    }
+}
@@ -0,0 +1,224 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::order_statistics::RollingOrderStatistics;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling quantile (percentile).
+///
+/// Returns the value below which the given fraction of the last _period_ values falls. The
+/// value is linearly interpolated between the two closest ranks of the sorted window, so a
+/// quantile of 0.5 is the [median](struct.RollingMedian.html), 0 the minimum and 1 the maximum.
+///
+/// A pair of rolling quantiles (e.g. 0.1 and 0.9) forms a percentile channel, which unlike
+/// Bollinger Bands makes no assumption about the distribution of the prices. Comparing the
+/// price with a quantile gives a percent rank style filter, e.g. "close is in the top 20% of
+/// the last 50 closes".
+///
+/// The window is split between two heaps at the rank of the quantile, so an update takes
+/// _O(log(period))_ and never allocates.
+///
+/// # Parameters
+///
+/// * _period_ - size of the window (integer greater than 0). Default is 20.
+/// * _quantile_ - fraction of the values below the result (number in range [0, 1]).
+///   Default is 0.5.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RollingQuantile;
+/// use ta::Next;
+///
+/// let mut q90 = RollingQuantile::new(5, 0.9).unwrap();
+/// for x in [3.0, 1.0, 4.0, 1.0] {
+///     q90.next(x);
+/// }
+/// // sorted window: [1, 1, 3, 4, 5], rank 3.6
+/// assert_eq!(q90.next(5.0), 4.6);
+/// ```
+///
+/// # Links
+///
+/// * [Quantile, Wikipedia](https://en.wikipedia.org/wiki/Quantile)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RollingQuantile {
+    quantile: f64,
+    window: RollingOrderStatistics,
+    output: Option<f64>,
+}
+
+impl RollingQuantile {
+    pub fn new(period: usize, quantile: f64) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::zero_period("period"));
+        }
+        if !(0.0..=1.0).contains(&quantile) {
+            return Err(TaError::invalid_parameter(
+                "quantile",
+                quantile,
+                "must be between 0 and 1",
+            ));
+        }
+        Ok(Self {
+            quantile,
+            window: RollingOrderStatistics::new(period, quantile),
+            output: None,
+        })
+    }
+
+    pub fn quantile(&self) -> f64 {
+        self.quantile
+    }
+}
+
+impl Period for RollingQuantile {
+    fn period(&self) -> usize {
+        self.window.period()
+    }
+}
+
+impl IsReady for RollingQuantile {
+    fn bars_until_ready(&self) -> usize {
+        self.window.bars_until_ready()
+    }
+}
+
+impl Next<f64> for RollingQuantile {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.window.push(input);
+        let output = self.window.quantile();
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for RollingQuantile {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Current for RollingQuantile {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for RollingQuantile {
+    fn reset(&mut self) {
+        self.window.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for RollingQuantile {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl Default for RollingQuantile {
+    fn default() -> Self {
+        Self::new(20, 0.5).unwrap()
+    }
+}
+
+impl fmt::Display for RollingQuantile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "QUANTILE({}, {})", self.window.period(), self.quantile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingQuantile);
+
+    #[test]
+    fn test_new() {
+        assert!(RollingQuantile::new(0, 0.5).is_err());
+        assert!(RollingQuantile::new(1, -0.1).is_err());
+        assert!(RollingQuantile::new(1, 1.1).is_err());
+        assert!(RollingQuantile::new(1, f64::NAN).is_err());
+        assert!(RollingQuantile::new(1, 0.0).is_ok());
+        assert!(RollingQuantile::new(1, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut q25 = RollingQuantile::new(4, 0.25).unwrap();
+
+        assert_eq!(q25.next(8.0), 8.0);
+        assert_eq!(q25.next(4.0), 5.0);
+        assert_eq!(q25.next(6.0), 5.0);
+        assert_eq!(q25.next(2.0), 3.5);
+        // 8.0 drops out: [2, 4, 6, 10]
+        assert_eq!(q25.next(10.0), 3.5);
+    }
+
+    #[test]
+    fn test_extremes() {
+        let mut min = RollingQuantile::new(3, 0.0).unwrap();
+        let mut max = RollingQuantile::new(3, 1.0).unwrap();
+
+        for &x in &[5.0, 2.0, 7.0, 6.0, 4.0] {
+            min.next(x);
+            max.next(x);
+        }
+        assert_eq!(min.next(5.0), 4.0);
+        assert_eq!(max.next(5.0), 6.0);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut quantile = RollingQuantile::new(2, 1.0).unwrap();
+
+        quantile.next(&Bar::new().close(10));
+        assert_eq!(quantile.next(&Bar::new().close(7)), 10.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut quantile = RollingQuantile::new(3, 0.9).unwrap();
+
+        quantile.next(10.0);
+        quantile.next(20.0);
+
+        quantile.reset();
+        assert_eq!(quantile.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RollingQuantile::new(3, 0.9).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut RollingQuantile::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingQuantile::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let quantile = RollingQuantile::new(50, 0.8).unwrap();
+        assert_eq!(format!("{}", quantile), "QUANTILE(50, 0.8)");
+    }
+}
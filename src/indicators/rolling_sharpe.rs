@@ -0,0 +1,273 @@
+use core::fmt;
+
+use num_traits::Float;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::cast;
+use crate::indicators::StandardDeviation as Sd;
+use crate::{Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling Sharpe ratio.
+///
+/// The annualized Sharpe ratio of the last _period_ returns: the mean excess return over the
+/// risk-free rate divided by the standard deviation of the returns. Fed with the per-bar
+/// returns of a strategy, it monitors the risk-adjusted performance as a stream.
+///
+/// Inputs are simple per-bar returns (e.g. 0.01 for +1%), not prices.
+///
+/// # Formula
+///
+/// Sharpe = (mean(r, _period_) - _risk_free_ / _annualization_) / SD(r, _period_) *
+/// sqrt(_annualization_)
+///
+/// Where:
+///
+/// * _r_ - return of a bar
+/// * _SD_ - population [standard deviation](struct.StandardDeviation.html)
+///
+/// The ratio is 0, while all the returns in the window are equal.
+///
+/// # Parameters
+///
+/// * _period_ - number of returns in the window (integer greater than 1). Default is 252.
+/// * _risk_free_ - annual risk-free rate (finite number). Default is 0.
+/// * _annualization_ - number of bars per year, e.g. 252 for daily bars (number greater than
+///   0). Default is 252.
+///
+/// # Value type
+///
+/// The returns are `f64` by default. Any [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html)
+/// type can be used with [with_period](#method.with_period), e.g.
+/// `RollingSharpe::<f32>::with_period(252, 0.0, 252.0)`.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RollingSharpe;
+/// use ta::Next;
+///
+/// // quarterly returns
+/// let mut sharpe = RollingSharpe::new(3, 0.0, 4.0).unwrap();
+///
+/// assert_eq!(sharpe.next(0.01), 0.0);
+/// // mean 0.015, SD 0.005, annualized with sqrt(4)
+/// assert_eq!(sharpe.next(0.02), 6.0);
+/// ```
+///
+/// # Links
+///
+/// * [Sharpe ratio, Wikipedia](https://en.wikipedia.org/wiki/Sharpe_ratio)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RollingSharpe<T = f64> {
+    risk_free: f64,
+    annualization: f64,
+    sd: Sd<T>,
+    output: Option<T>,
+}
+
+impl RollingSharpe {
+    pub fn new(period: usize, risk_free: f64, annualization: f64) -> Result<Self> {
+        Self::with_period(period, risk_free, annualization)
+    }
+}
+
+impl<T: Float> RollingSharpe<T> {
+    /// Same as `new`, for any value type.
+    pub fn with_period(period: usize, risk_free: f64, annualization: f64) -> Result<Self> {
+        if period < 2 {
+            return Err(TaError::invalid_period(
+                "period",
+                period,
+                "must be at least 2",
+            ));
+        }
+        if !risk_free.is_finite() {
+            return Err(TaError::invalid_parameter(
+                "risk_free",
+                risk_free,
+                "must be finite",
+            ));
+        }
+        if !annualization.is_finite() || annualization <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "annualization",
+                annualization,
+                "must be a positive number",
+            ));
+        }
+        Ok(Self {
+            risk_free,
+            annualization,
+            sd: Sd::with_period(period)?,
+            output: None,
+        })
+    }
+
+    pub fn risk_free(&self) -> f64 {
+        self.risk_free
+    }
+
+    pub fn annualization(&self) -> f64 {
+        self.annualization
+    }
+}
+
+impl<T> Period for RollingSharpe<T> {
+    fn period(&self) -> usize {
+        self.sd.period()
+    }
+}
+
+impl<T> IsReady for RollingSharpe<T> {
+    fn bars_until_ready(&self) -> usize {
+        self.sd.bars_until_ready()
+    }
+}
+
+impl<T: Float> Next<T> for RollingSharpe<T> {
+    type Output = T;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let sd = self.sd.next(input);
+        let output = if sd > T::zero() {
+            let excess = self.sd.mean() - cast(self.risk_free / self.annualization);
+            excess / sd * cast(self.annualization.sqrt())
+        } else {
+            T::zero()
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Copy> Current for RollingSharpe<T> {
+    type Output = T;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<T: Float> Reset for RollingSharpe<T> {
+    fn reset(&mut self) {
+        self.sd.reset();
+        self.output = None;
+    }
+}
+
+impl<T> MemoryFootprint for RollingSharpe<T> {
+    fn heap_size(&self) -> usize {
+        self.sd.heap_size()
+    }
+}
+
+impl Default for RollingSharpe {
+    fn default() -> Self {
+        Self::new(252, 0.0, 252.0).unwrap()
+    }
+}
+
+impl<T> fmt::Display for RollingSharpe<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SHARPE({}, {}, {})",
+            self.sd.period(),
+            self.risk_free,
+            self.annualization
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(RollingSharpe::new(0, 0.0, 252.0).is_err());
+        assert!(RollingSharpe::new(1, 0.0, 252.0).is_err());
+        assert!(RollingSharpe::new(2, f64::NAN, 252.0).is_err());
+        assert!(RollingSharpe::new(2, 0.0, 0.0).is_err());
+        assert!(RollingSharpe::new(2, 0.0, f64::INFINITY).is_err());
+        assert!(RollingSharpe::new(2, -0.01, 12.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut sharpe = RollingSharpe::new(3, 0.0, 4.0).unwrap();
+
+        assert_eq!(sharpe.next(0.01), 0.0);
+        assert_eq!(round(sharpe.next(0.02)), 6.0);
+        assert_eq!(round(sharpe.next(0.03)), 4.899);
+        // 0.01 drops out
+        assert_eq!(round(sharpe.next(-0.03)), 0.508);
+    }
+
+    #[test]
+    fn test_f32() {
+        let mut sharpe = RollingSharpe::<f32>::with_period(3, 0.0, 4.0).unwrap();
+
+        assert_eq!(sharpe.next(0.01f32), 0.0f32);
+        assert_eq!(round(sharpe.next(0.02).into()), 6.0);
+        assert_eq!(round(sharpe.next(0.03).into()), 4.899);
+        assert_eq!(format!("{}", sharpe), "SHARPE(3, 0, 4)");
+    }
+
+    #[test]
+    fn test_risk_free() {
+        // 4% a year is 1% a quarter
+        let mut sharpe = RollingSharpe::new(3, 0.04, 4.0).unwrap();
+
+        assert_eq!(sharpe.next(0.01), 0.0);
+        assert_eq!(round(sharpe.next(0.02)), 2.0);
+        assert_eq!(round(sharpe.next(0.03)), 2.449);
+        assert_eq!(round(sharpe.next(-0.03)), -0.254);
+    }
+
+    #[test]
+    fn test_flat() {
+        let mut sharpe = RollingSharpe::new(2, 0.0, 252.0).unwrap();
+
+        assert_eq!(sharpe.next(0.01), 0.0);
+        assert_eq!(sharpe.next(0.01), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sharpe = RollingSharpe::new(3, 0.0, 4.0).unwrap();
+
+        sharpe.next(0.01);
+        sharpe.next(0.02);
+
+        sharpe.reset();
+        assert_eq!(sharpe.next(0.03), 0.0);
+        assert_eq!(round(sharpe.next(-0.03)), 0.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RollingSharpe::new(3, 0.0, 252.0).unwrap(), 0.01, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut RollingSharpe::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingSharpe::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let sharpe = RollingSharpe::new(20, 0.02, 252.0).unwrap();
+        assert_eq!(format!("{}", sharpe), "SHARPE(20, 0.02, 252)");
+    }
+}
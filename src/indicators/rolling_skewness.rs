@@ -0,0 +1,206 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::rolling_moments::RollingMoments;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rolling skewness.
+///
+/// Returns the skewness of the last _period_ values: the asymmetry of their distribution.
+/// Positive skewness means a longer right tail (rare large gains, when applied to returns),
+/// negative skewness a longer left tail (rare large losses).
+///
+/// The central moments of the window are updated incrementally as values enter and leave it,
+/// so every update takes constant time.
+///
+/// # Formula
+///
+/// skewness = m<sub>3</sub> / m<sub>2</sub><sup>3/2</sup>
+///
+/// Where:
+///
+/// * _m<sub>k</sub>_ - _k_-th central moment of the window: mean of (x<sub>i</sub> - mean)<sup>k</sup>
+///
+/// This is the population (biased) skewness. It is 0, when all the values in the window are
+/// equal.
+///
+/// # Parameters
+///
+/// * _period_ - size of the window (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RollingSkewness;
+/// use ta::Next;
+///
+/// let mut skew = RollingSkewness::new(3).unwrap();
+/// skew.next(1.0);
+/// skew.next(2.0);
+/// // symmetric window
+/// assert_eq!(skew.next(3.0), 0.0);
+/// // a large value makes a long right tail
+/// assert!(skew.next(10.0) > 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Skewness, Wikipedia](https://en.wikipedia.org/wiki/Skewness)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RollingSkewness {
+    moments: RollingMoments,
+    output: Option<f64>,
+}
+
+impl RollingSkewness {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::zero_period("period")),
+            _ => Ok(Self {
+                moments: RollingMoments::new(period),
+                output: None,
+            }),
+        }
+    }
+}
+
+impl Period for RollingSkewness {
+    fn period(&self) -> usize {
+        self.moments.period()
+    }
+}
+
+impl IsReady for RollingSkewness {
+    fn bars_until_ready(&self) -> usize {
+        self.moments.bars_until_ready()
+    }
+}
+
+impl Next<f64> for RollingSkewness {
+    type Output = f64;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.moments.push(input);
+        let output = self.moments.skewness();
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for RollingSkewness {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Current for RollingSkewness {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for RollingSkewness {
+    fn reset(&mut self) {
+        self.moments.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for RollingSkewness {
+    fn heap_size(&self) -> usize {
+        self.moments.heap_size()
+    }
+}
+
+impl Default for RollingSkewness {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for RollingSkewness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SKEW({})", self.moments.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingSkewness);
+
+    #[test]
+    fn test_new() {
+        assert!(RollingSkewness::new(0).is_err());
+        assert!(RollingSkewness::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut skew = RollingSkewness::new(4).unwrap();
+
+        assert_eq!(skew.next(1.0), 0.0);
+        assert_eq!(skew.next(2.0), 0.0);
+        assert_eq!(round(skew.next(6.0)), 0.595);
+        assert_eq!(round(skew.next(3.0)), 0.687);
+        // 1.0 drops out: [2, 6, 3, 10]
+        assert_eq!(round(skew.next(10.0)), 0.513);
+        // mirrored values flip the sign
+        let mut mirrored = RollingSkewness::new(4).unwrap();
+        for &x in &[-2.0, -6.0, -3.0] {
+            mirrored.next(x);
+        }
+        assert_eq!(round(mirrored.next(-10.0)), -0.513);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut skew = RollingSkewness::new(3).unwrap();
+
+        skew.next(&Bar::new().close(4));
+        skew.next(&Bar::new().close(4));
+        assert_eq!(skew.next(&Bar::new().close(4)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut skew = RollingSkewness::new(3).unwrap();
+
+        skew.next(1.0);
+        skew.next(7.0);
+
+        skew.reset();
+        assert_eq!(skew.next(5.0), 0.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RollingSkewness::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut RollingSkewness::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingSkewness::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let skew = RollingSkewness::new(30).unwrap();
+        assert_eq!(format!("{}", skew), "SKEW(30)");
+    }
+}
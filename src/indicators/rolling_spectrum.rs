@@ -0,0 +1,426 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec};
+use core::f64::consts::PI;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::ring_buffer::RingBuffer;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Trend removed from the window before the spectrum is computed.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detrend {
+    /// The values are used as they are.
+    None,
+    /// The mean of the window is subtracted.
+    Mean,
+    /// The least squares line through the window is subtracted.
+    Linear,
+}
+
+impl fmt::Display for Detrend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Detrend::None => write!(f, "none"),
+            Detrend::Mean => write!(f, "mean"),
+            Detrend::Linear => write!(f, "linear"),
+        }
+    }
+}
+
+/// Rolling power spectrum.
+///
+/// Computes the power spectrum of the last _window_ values with the fast Fourier transform and
+/// reports the dominant cycle, i.e. the frequency with the highest power. It can be used as a
+/// cycle detector, e.g. to adapt the periods of other indicators to the dominant cycle.
+///
+/// Without detrending the trend of the prices leaks into the low frequencies, so the
+/// dominant cycle would often be the longest one. By default the least squares line is removed
+/// from the window first.
+///
+/// # Formula
+///
+/// X<sub>k</sub> = Σ x<sub>t</sub> * e<sup>-2πikt/n</sup>
+///
+/// power<sub>k</sub> = |X<sub>k</sub>|<sup>2</sup> / n, for _k_ in [1, n/2]
+///
+/// Where _n_ is the window, and _x_ are the detrended values. The frequency of the bin _k_ is
+/// _k / n_ cycles per bar and its period is _n / k_ bars.
+///
+/// Until the window is full, the output is zero.
+///
+/// # Parameters
+///
+/// * _window_ - number of values (power of two, at least 4). Default is 64.
+/// * _detrend_ - trend removed before the transform. Default is `Detrend::Linear`.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{Detrend, RollingSpectrum};
+/// use ta::Next;
+///
+/// let mut spectrum = RollingSpectrum::new(32, Detrend::Mean).unwrap();
+///
+/// let mut out = Default::default();
+/// for t in 0..32 {
+///     // a cycle of 8 bars
+///     let price = 100.0 + (2.0 * std::f64::consts::PI * t as f64 / 8.0).sin();
+///     out = spectrum.next(price);
+/// }
+/// assert_eq!(out.dominant_period, 8.0);
+/// ```
+///
+/// # Links
+///
+/// * [Spectral density, Wikipedia](https://en.wikipedia.org/wiki/Spectral_density)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RollingSpectrum {
+    window: usize,
+    detrend: Detrend,
+    values: RingBuffer,
+    re: Box<[f64]>,
+    im: Box<[f64]>,
+    power: Box<[f64]>,
+    output: Option<RollingSpectrumOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RollingSpectrumOutput {
+    /// Period of the dominant cycle in bars.
+    pub dominant_period: f64,
+    /// Frequency of the dominant cycle in cycles per bar.
+    pub dominant_frequency: f64,
+    /// Power of the dominant cycle.
+    pub dominant_power: f64,
+    /// Sum of the power of all the frequencies.
+    pub total_power: f64,
+}
+
+impl RollingSpectrum {
+    pub fn new(window: usize, detrend: Detrend) -> Result<Self> {
+        if window < 4 || !window.is_power_of_two() {
+            return Err(TaError::invalid_period(
+                "window",
+                window,
+                "must be a power of two of at least 4",
+            ));
+        }
+        Ok(Self {
+            window,
+            detrend,
+            values: RingBuffer::new(window),
+            re: vec![0.0; window].into_boxed_slice(),
+            im: vec![0.0; window].into_boxed_slice(),
+            power: vec![0.0; window / 2].into_boxed_slice(),
+            output: None,
+        })
+    }
+
+    pub fn detrend(&self) -> Detrend {
+        self.detrend
+    }
+
+    /// Power of the frequencies _k / window_ for _k_ in [1, window / 2], from the longest
+    /// period to the shortest one.
+    pub fn spectrum(&self) -> &[f64] {
+        &self.power
+    }
+
+    /// Sum of the power of the cycles with periods in range [min_period, max_period].
+    pub fn band_power(&self, min_period: f64, max_period: f64) -> f64 {
+        self.power
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| {
+                let period = self.window as f64 / (i + 1) as f64;
+                period >= min_period && period <= max_period
+            })
+            .map(|(_, power)| power)
+            .sum()
+    }
+
+    fn compute(&mut self) -> RollingSpectrumOutput {
+        let n = self.window as f64;
+        for (re, value) in self.re.iter_mut().zip(self.values.iter()) {
+            *re = value;
+        }
+        self.im.iter_mut().for_each(|x| *x = 0.0);
+
+        match self.detrend {
+            Detrend::None => {}
+            Detrend::Mean => {
+                let mean = self.re.iter().sum::<f64>() / n;
+                self.re.iter_mut().for_each(|x| *x -= mean);
+            }
+            Detrend::Linear => {
+                let mean_t = (n - 1.0) / 2.0;
+                let mean = self.re.iter().sum::<f64>() / n;
+                let (mut cov, mut var) = (0.0, 0.0);
+                for (t, x) in self.re.iter().enumerate() {
+                    cov += (t as f64 - mean_t) * (x - mean);
+                    var += (t as f64 - mean_t).powi(2);
+                }
+                let slope = cov / var;
+                for (t, x) in self.re.iter_mut().enumerate() {
+                    *x -= mean + slope * (t as f64 - mean_t);
+                }
+            }
+        }
+
+        fft(&mut self.re, &mut self.im);
+
+        let mut output = RollingSpectrumOutput::default();
+        for k in 1..=self.window / 2 {
+            let power = (self.re[k].powi(2) + self.im[k].powi(2)) / n;
+            self.power[k - 1] = power;
+            output.total_power += power;
+            if power > output.dominant_power {
+                output.dominant_power = power;
+                output.dominant_frequency = k as f64 / n;
+                output.dominant_period = n / k as f64;
+            }
+        }
+        output
+    }
+}
+
+// In-place iterative radix-2 FFT, the length must be a power of two.
+fn fft(re: &mut [f64], im: &mut [f64]) {
+    let n = re.len();
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f64;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (w_im, w_re) = (angle * k as f64).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let t_re = re[b] * w_re - im[b] * w_im;
+                let t_im = re[b] * w_im + im[b] * w_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+impl Period for RollingSpectrum {
+    fn period(&self) -> usize {
+        self.window
+    }
+}
+
+impl IsReady for RollingSpectrum {
+    fn bars_until_ready(&self) -> usize {
+        self.window - self.values.len()
+    }
+}
+
+impl Next<f64> for RollingSpectrum {
+    type Output = RollingSpectrumOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.values.push(input);
+        let output = if !self.values.is_full() {
+            RollingSpectrumOutput::default()
+        } else {
+            self.compute()
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for RollingSpectrum {
+    type Output = RollingSpectrumOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Current for RollingSpectrum {
+    type Output = RollingSpectrumOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for RollingSpectrum {
+    fn reset(&mut self) {
+        self.values.clear();
+        self.power.iter_mut().for_each(|x| *x = 0.0);
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for RollingSpectrum {
+    fn heap_size(&self) -> usize {
+        self.values.heap_size()
+            + core::mem::size_of_val(&*self.re)
+            + core::mem::size_of_val(&*self.im)
+            + core::mem::size_of_val(&*self.power)
+    }
+}
+
+impl Default for RollingSpectrum {
+    fn default() -> Self {
+        Self::new(64, Detrend::Linear).unwrap()
+    }
+}
+
+impl fmt::Display for RollingSpectrum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SPECTRUM({}, {})", self.window, self.detrend)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(RollingSpectrum);
+
+    fn cycle(t: usize, period: f64) -> f64 {
+        (2.0 * PI * t as f64 / period).sin()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(RollingSpectrum::new(0, Detrend::None).is_err());
+        assert!(RollingSpectrum::new(2, Detrend::None).is_err());
+        assert!(RollingSpectrum::new(12, Detrend::None).is_err());
+        assert!(RollingSpectrum::new(4, Detrend::None).is_ok());
+        assert!(RollingSpectrum::new(256, Detrend::Linear).is_ok());
+    }
+
+    #[test]
+    fn test_fft() {
+        let mut re = [1.0, 2.0, 3.0, 4.0];
+        let mut im = [0.0; 4];
+        fft(&mut re, &mut im);
+
+        assert_eq!(re.map(round), [10.0, -2.0, -2.0, -2.0]);
+        assert_eq!(im.map(round), [0.0, 2.0, 0.0, -2.0]);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut spectrum = RollingSpectrum::new(32, Detrend::None).unwrap();
+
+        for t in 0..31 {
+            assert_eq!(
+                spectrum.next(cycle(t, 8.0)),
+                RollingSpectrumOutput::default()
+            );
+        }
+
+        let out = spectrum.next(cycle(31, 8.0));
+        assert_eq!(out.dominant_period, 8.0);
+        assert_eq!(out.dominant_frequency, 0.125);
+        // a unit sine has the power of n / 4
+        assert_eq!(round(out.dominant_power), 8.0);
+        assert_eq!(round(out.total_power), 8.0);
+
+        assert_eq!(spectrum.spectrum().len(), 16);
+        assert_eq!(round(spectrum.spectrum()[3]), 8.0);
+        assert_eq!(round(spectrum.band_power(6.0, 10.0)), 8.0);
+        assert_eq!(round(spectrum.band_power(10.0, 32.0)), 0.0);
+
+        // the window rolls over
+        let out = spectrum.next(cycle(32, 8.0));
+        assert_eq!(out.dominant_period, 8.0);
+    }
+
+    #[test]
+    fn test_mixed_cycles() {
+        let mut spectrum = RollingSpectrum::new(64, Detrend::Mean).unwrap();
+
+        let mut out = RollingSpectrumOutput::default();
+        for t in 0..64 {
+            out = spectrum.next(10.0 + cycle(t, 16.0) + 2.0 * cycle(t, 4.0));
+        }
+        assert_eq!(out.dominant_period, 4.0);
+        assert_eq!(round(spectrum.band_power(16.0, 16.0)), 16.0);
+        assert_eq!(round(spectrum.band_power(4.0, 4.0)), 64.0);
+    }
+
+    #[test]
+    fn test_detrend() {
+        let mut raw = RollingSpectrum::new(32, Detrend::None).unwrap();
+        let mut detrended = RollingSpectrum::new(32, Detrend::Linear).unwrap();
+
+        let (mut a, mut b) = Default::default();
+        for t in 0..32 {
+            let price = 100.0 + t as f64 + 0.5 * cycle(t, 8.0);
+            a = raw.next(price);
+            b = detrended.next(price);
+        }
+        // the trend dominates the raw spectrum
+        assert_eq!(a.dominant_period, 32.0);
+        assert_eq!(b.dominant_period, 8.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut spectrum = RollingSpectrum::new(4, Detrend::Mean).unwrap();
+
+        for t in 0..4 {
+            spectrum.next(cycle(t, 4.0));
+        }
+        assert_eq!(round(spectrum.spectrum()[0]), 1.0);
+
+        spectrum.reset();
+        assert_eq!(spectrum.spectrum(), &[0.0, 0.0]);
+        assert_eq!(spectrum.next(1.0), RollingSpectrumOutput::default());
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut RollingSpectrum::new(8, Detrend::None).unwrap(), 1.0, 8);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut RollingSpectrum::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        RollingSpectrum::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let spectrum = RollingSpectrum::default();
+        assert_eq!(format!("{}", spectrum), "SPECTRUM(64, linear)");
+    }
+}
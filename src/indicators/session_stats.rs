@@ -0,0 +1,323 @@
+use core::fmt;
+
+use crate::{
+    Close, Current, High, IsReady, Low, MemoryFootprint, Next, Open, Reset, SessionAware, Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Cumulative session statistics.
+///
+/// Tracks the running statistics of the current session, which most intraday strategies need
+/// on every bar: the session open, the running high and low, the cumulative volume, the
+/// session VWAP and the position of the close within the session range.
+///
+/// A new session is signalled explicitly with [open_session](#method.open_session): the next
+/// bar opens the new session and all statistics start over. The first bar always opens a
+/// session.
+///
+/// VWAP is calculated from the typical price _(high + low + close) / 3_, the same way as in
+/// [VolumeWeightedAveragePrice](struct.VolumeWeightedAveragePrice.html). It is `None` until
+/// some volume has been traded in the session.
+///
+/// # Formula
+///
+/// position = (close - session low) / (session high - session low)
+///
+/// The position ranges from 0 (at the session low) to 1 (at the session high). It is 0.5,
+/// when the session range is 0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SessionStats;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(open: f64, high: f64, low: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(open).high(high).low(low).close(close).volume(100.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut stats = SessionStats::new();
+///
+/// stats.next(&bar(10.0, 12.0, 9.0, 11.0));
+/// let out = stats.next(&bar(11.0, 14.0, 10.0, 13.0));
+/// assert_eq!(out.open, 10.0);
+/// assert_eq!(out.high, 14.0);
+/// assert_eq!(out.low, 9.0);
+/// assert_eq!(out.volume, 200.0);
+/// assert_eq!(out.position, 0.8);
+///
+/// stats.open_session();
+/// let out = stats.next(&bar(20.0, 21.0, 19.0, 20.0));
+/// assert_eq!(out.open, 20.0);
+/// assert_eq!(out.volume, 100.0);
+/// assert_eq!(out.vwap, Some(20.0));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    new_session: bool,
+    bars: usize,
+    open: f64,
+    high: f64,
+    low: f64,
+    price_volume: f64,
+    volume: f64,
+    output: Option<SessionStatsOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionStatsOutput {
+    /// Open of the first bar of the session.
+    pub open: f64,
+    /// Highest high of the session so far.
+    pub high: f64,
+    /// Lowest low of the session so far.
+    pub low: f64,
+    /// Cumulative volume of the session.
+    pub volume: f64,
+    /// Session VWAP, `None` while no volume has been traded.
+    pub vwap: Option<f64>,
+    /// Position of the close within the session range, from 0 to 1.
+    pub position: f64,
+    /// Number of bars in the session, including the current one.
+    pub bars: usize,
+}
+
+impl SessionStats {
+    pub fn new() -> Self {
+        Self {
+            new_session: true,
+            bars: 0,
+            open: 0.0,
+            high: 0.0,
+            low: 0.0,
+            price_volume: 0.0,
+            volume: 0.0,
+            output: None,
+        }
+    }
+
+    /// Starts a new session, which is opened by the next bar.
+    pub fn open_session(&mut self) {
+        self.new_session = true;
+    }
+
+    /// Returns the number of bars in the current session.
+    pub fn bars(&self) -> usize {
+        self.bars
+    }
+}
+
+impl SessionAware for SessionStats {
+    fn open_session(&mut self) {
+        SessionStats::open_session(self);
+    }
+}
+
+impl IsReady for SessionStats {
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
+}
+
+impl<T: Open + High + Low + Close + Volume> Next<&T> for SessionStats {
+    type Output = SessionStatsOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        if self.new_session {
+            self.new_session = false;
+            self.bars = 0;
+            self.open = input.open();
+            self.high = input.high();
+            self.low = input.low();
+            self.price_volume = 0.0;
+            self.volume = 0.0;
+        } else {
+            self.high = self.high.max(input.high());
+            self.low = self.low.min(input.low());
+        }
+
+        let typical_price = (input.high() + input.low() + input.close()) / 3.0;
+        self.price_volume += typical_price * input.volume();
+        self.volume += input.volume();
+        self.bars += 1;
+
+        let range = self.high - self.low;
+        let position = if range > 0.0 {
+            (input.close() - self.low) / range
+        } else {
+            0.5
+        };
+        let vwap = if self.volume > 0.0 {
+            Some(self.price_volume / self.volume)
+        } else {
+            None
+        };
+
+        let output = SessionStatsOutput {
+            open: self.open,
+            high: self.high,
+            low: self.low,
+            volume: self.volume,
+            vwap,
+            position,
+            bars: self.bars,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for SessionStats {
+    type Output = SessionStatsOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for SessionStats {
+    fn reset(&mut self) {
+        *self = Self::new();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for SessionStats {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for SessionStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SESSION")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_hlcv_indicator!(SessionStats);
+
+    fn bar(open: f64, high: f64, low: f64, close: f64, volume: f64) -> Bar {
+        Bar::new()
+            .open(open)
+            .high(high)
+            .low(low)
+            .close(close)
+            .volume(volume)
+    }
+
+    #[test]
+    fn test_next() {
+        let mut stats = SessionStats::new();
+
+        let out = stats.next(&bar(9.0, 10.0, 8.0, 9.0, 100.0));
+        assert_eq!(out.open, 9.0);
+        assert_eq!(out.high, 10.0);
+        assert_eq!(out.low, 8.0);
+        assert_eq!(out.volume, 100.0);
+        assert_eq!(out.vwap, Some(9.0));
+        assert_eq!(out.position, 0.5);
+        assert_eq!(out.bars, 1);
+
+        let out = stats.next(&bar(9.0, 12.0, 9.0, 11.0, 200.0));
+        assert_eq!(out.open, 9.0);
+        assert_eq!(out.high, 12.0);
+        assert_eq!(out.low, 8.0);
+        assert_eq!(out.volume, 300.0);
+        // (9 * 100 + 10.667 * 200) / 300
+        assert_eq!(round(out.vwap.unwrap()), 10.111);
+        assert_eq!(out.position, 0.75);
+
+        let out = stats.next(&bar(11.0, 11.0, 7.0, 8.0, 100.0));
+        assert_eq!(out.low, 7.0);
+        assert_eq!(round(out.vwap.unwrap()), 9.75);
+        assert_eq!(out.position, 0.2);
+        assert_eq!(out.bars, 3);
+    }
+
+    #[test]
+    fn test_session_roll() {
+        let mut stats = SessionStats::new();
+
+        stats.next(&bar(9.0, 10.0, 8.0, 9.0, 100.0));
+        stats.next(&bar(9.0, 12.0, 9.0, 11.0, 200.0));
+
+        stats.open_session();
+        assert_eq!(stats.bars(), 2);
+        let out = stats.next(&bar(20.0, 21.0, 19.0, 21.0, 50.0));
+        assert_eq!(out.open, 20.0);
+        assert_eq!(out.high, 21.0);
+        assert_eq!(out.low, 19.0);
+        assert_eq!(out.volume, 50.0);
+        assert_eq!(round(out.vwap.unwrap()), 20.333);
+        assert_eq!(out.position, 1.0);
+        assert_eq!(out.bars, 1);
+    }
+
+    #[test]
+    fn test_zero_volume() {
+        let mut stats = SessionStats::new();
+
+        let out = stats.next(&bar(10.0, 10.0, 10.0, 10.0, 0.0));
+        assert_eq!(out.vwap, None);
+        assert_eq!(out.position, 0.5);
+
+        let out = stats.next(&bar(10.0, 11.0, 10.0, 11.0, 10.0));
+        assert_eq!(round(out.vwap.unwrap()), 10.667);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut stats = SessionStats::new();
+
+        stats.next(&bar(9.0, 10.0, 8.0, 9.0, 100.0));
+        stats.next(&bar(9.0, 12.0, 9.0, 11.0, 200.0));
+
+        stats.reset();
+        assert_eq!(stats.bars(), 0);
+        let out = stats.next(&bar(5.0, 6.0, 4.0, 5.0, 10.0));
+        assert_eq!(out.open, 5.0);
+        assert_eq!(out.high, 6.0);
+        assert_eq!(out.volume, 10.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert!(SessionStats::new().is_ready());
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut SessionStats::default(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        SessionStats::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let stats = SessionStats::new();
+        assert_eq!(format!("{}", stats), "SESSION");
+    }
+}
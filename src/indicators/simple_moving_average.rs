@@ -1,7 +1,17 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use num_traits::Float;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::helpers::{cast, fmt_params, Accumulator};
+use crate::registry::Params;
+use crate::ring_buffer::RingBuffer;
+use crate::{
+    Close, Configure, Current, IsReady, MemoryFootprint, Next, NextBatch, ParamValue, Parameters,
+    Period, ProjectNext, Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +31,13 @@ use serde::{Deserialize, Serialize};
 ///
 /// * _period_ - number of periods (integer greater than 0)
 ///
+/// # Value type
+///
+/// The values are `f64` by default. Any [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html)
+/// type can be used with [with_period](#method.with_period), e.g. `f32` to halve the memory of
+/// the window: `SimpleMovingAverage::<f32>::with_period(3)`. Batches are accepted for `f32` and
+/// `f64`.
+///
 /// # Example
 ///
 /// ```
@@ -41,73 +58,199 @@ use serde::{Deserialize, Serialize};
 #[doc(alias = "SMA")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct SimpleMovingAverage {
+pub struct SimpleMovingAverage<T = f64> {
     period: usize,
-    index: usize,
-    count: usize,
-    sum: f64,
-    deque: Box<[f64]>,
+    sum: Accumulator<T>,
+    window: RingBuffer<T>,
+    output: Option<T>,
 }
 
 impl SimpleMovingAverage {
     pub fn new(period: usize) -> Result<Self> {
+        Self::with_period(period)
+    }
+
+    /// Same as `new`, with compensated summation of the window, so the average does not
+    /// drift after millions of inputs.
+    pub fn compensated(period: usize) -> Result<Self> {
+        let mut sma = Self::new(period)?;
+        sma.sum = Accumulator::new(true);
+        Ok(sma)
+    }
+}
+
+impl<T: Float> SimpleMovingAverage<T> {
+    /// Same as `new`, for any value type.
+    pub fn with_period(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::zero_period("period")),
             _ => Ok(Self {
                 period,
-                index: 0,
-                count: 0,
-                sum: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                sum: Accumulator::new(false),
+                window: RingBuffer::new(period),
+                output: None,
             }),
         }
     }
 }
 
-impl Period for SimpleMovingAverage {
+impl<T> Period for SimpleMovingAverage<T> {
     fn period(&self) -> usize {
         self.period
     }
 }
 
-impl Next<f64> for SimpleMovingAverage {
-    type Output = f64;
+impl<T: Float> Configure for SimpleMovingAverage<T> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        let mut sma = Self::with_period(period)?;
+        sma.sum = Accumulator::new(self.sum.is_compensated());
+        let count = self.window.len();
+        for value in self.window.iter().skip(count - count.min(period)) {
+            sma.next(value);
+        }
+        *self = sma;
+        Ok(())
+    }
+}
+
+impl<T> IsReady for SimpleMovingAverage<T> {
+    fn bars_until_ready(&self) -> usize {
+        self.period - self.window.len()
+    }
+}
+
+impl<T: Float> Next<T> for SimpleMovingAverage<T> {
+    type Output = T;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        if let Some(oldest) = self.window.push(input) {
+            self.sum.add(-oldest);
+        }
+        self.sum.add(input);
+        let output = self.sum.value() / cast(self.window.len());
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Float> SimpleMovingAverage<T> {
+    /// Feeds a batch like `next`, but appends the sums of the full windows instead of their
+    /// averages. Returns the position in `out` where the sums start.
+    fn next_batch_sums(&mut self, inputs: &[T], out: &mut Vec<T>) -> usize {
+        out.reserve(inputs.len());
+        // after `period` inputs the value leaving the window is in the slice, so the ring
+        // buffer has to be written only for the last `period` inputs
+        let head = inputs.len().min(self.period);
+        for &input in &inputs[..head] {
+            out.push(self.next(input));
+        }
 
-    fn next(&mut self, input: f64) -> Self::Output {
-        let old_val = self.deque[self.index];
-        self.deque[self.index] = input;
+        let start = out.len();
+        let (entering, leaving) = (&inputs[head..], &inputs[..inputs.len() - head]);
+        for (&input, &old_val) in entering.iter().zip(leaving) {
+            self.sum.add(-old_val);
+            self.sum.add(input);
+            out.push(self.sum.value());
+        }
 
-        self.index = if self.index + 1 < self.period {
-            self.index + 1
+        let tail = inputs.len() - head;
+        for &input in &inputs[inputs.len() - tail.min(self.period)..] {
+            self.window.push(input);
+        }
+        start
+    }
+}
+
+// Batches are only accepted by the primitive floats, whose divisions are vectorized with the
+// `simd` feature.
+macro_rules! impl_next_batch {
+    ($($t:ty),*) => {$(
+        impl NextBatch<$t> for SimpleMovingAverage<$t> {
+            fn next_batch(&mut self, inputs: &[$t], out: &mut Vec<Self::Output>) {
+                let start = self.next_batch_sums(inputs, out);
+                if start == out.len() {
+                    return;
+                }
+                let (sums, count) = (&mut out[start..], self.period as $t);
+                #[cfg(feature = "simd")]
+                crate::simd::div(sums, count);
+                #[cfg(not(feature = "simd"))]
+                sums.iter_mut().for_each(|sum| *sum /= count);
+                self.output = out.last().copied();
+            }
+        }
+    )*};
+}
+
+impl_next_batch!(f32, f64);
+
+impl<T: Float> ProjectNext<T> for SimpleMovingAverage<T> {
+    type Output = T;
+
+    fn project_next(&self, input: T) -> Self::Output {
+        let count = (self.window.len() + 1).min(self.period);
+        let oldest = if self.window.is_full() {
+            self.window.oldest().unwrap()
         } else {
-            0
+            T::zero()
         };
+        (self.sum.value() - oldest + input) / cast(count)
+    }
+}
 
-        if self.count < self.period {
-            self.count += 1;
+// Bars are only accepted by the primitive floats, a generic impl would overlap `Next<T>`.
+macro_rules! impl_close {
+    ($($t:ty),*) => {$(
+        impl<C: Close> Next<&C> for SimpleMovingAverage<$t> {
+            type Output = $t;
+
+            fn next(&mut self, input: &C) -> Self::Output {
+                self.next(input.close() as $t)
+            }
         }
 
-        self.sum = self.sum - old_val + input;
-        self.sum / (self.count as f64)
-    }
+        impl<C: Close> ProjectNext<&C> for SimpleMovingAverage<$t> {
+            type Output = $t;
+
+            fn project_next(&self, input: &C) -> Self::Output {
+                self.project_next(input.close() as $t)
+            }
+        }
+    )*};
 }
 
-impl<T: Close> Next<&T> for SimpleMovingAverage {
-    type Output = f64;
+impl_close!(f32, f64);
+
+impl<T: Float> Current for SimpleMovingAverage<T> {
+    type Output = T;
 
-    fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
-impl Reset for SimpleMovingAverage {
+impl<T: Float> Reset for SimpleMovingAverage<T> {
     fn reset(&mut self) {
-        self.index = 0;
-        self.count = 0;
-        self.sum = 0.0;
-        for i in 0..self.period {
-            self.deque[i] = 0.0;
-        }
+        self.sum.reset();
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl<T> MemoryFootprint for SimpleMovingAverage<T> {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl<T: Float> Parameters for SimpleMovingAverage<T> {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::with_period(params.period("period", 9)?)
     }
 }
 
@@ -117,9 +260,9 @@ impl Default for SimpleMovingAverage {
     }
 }
 
-impl fmt::Display for SimpleMovingAverage {
+impl<T: Float> fmt::Display for SimpleMovingAverage<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "SMA({})", self.period)
+        fmt_params(f, "SMA", &self.params())
     }
 }
 
@@ -161,6 +304,19 @@ mod tests {
         assert_eq!(sma.next(&bar(1.0)), 4.0);
     }
 
+    #[test]
+    fn test_project_next() {
+        let mut sma = SimpleMovingAverage::new(3).unwrap();
+
+        for &x in &[10.0, 11.0, 12.0, 13.0, 9.0] {
+            let projected = sma.project_next(x);
+            assert_eq!(sma.project_next(&Bar::new().close(x)), projected);
+            // projecting does not change the state
+            assert_eq!(sma.project_next(x), projected);
+            assert_eq!(sma.next(x), projected);
+        }
+    }
+
     #[test]
     fn test_reset() {
         let mut sma = SimpleMovingAverage::new(4).unwrap();
@@ -172,6 +328,145 @@ mod tests {
         assert_eq!(sma.next(99.0), 99.0);
     }
 
+    #[test]
+    fn test_f32() {
+        let mut sma = SimpleMovingAverage::<f32>::with_period(3).unwrap();
+        assert_eq!(sma.next(4.0f32), 4.0f32);
+        assert_eq!(sma.next(5.0), 4.5);
+        assert_eq!(sma.next(&Bar::new().close(9)), 6.0);
+        assert_eq!(sma.project_next(2.0), 16.0 / 3.0);
+        assert_eq!(format!("{}", sma), "SMA(3)");
+
+        sma.reset();
+        assert_eq!(sma.next(1.0), 1.0);
+
+        let mut out = Vec::new();
+        sma.next_batch(
+            &[2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0],
+            &mut out,
+        );
+        assert_eq!(out, [1.5, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0]);
+    }
+
+    #[test]
+    fn test_next_batch() {
+        let inputs: Vec<f64> = (0..40)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0)
+            .collect();
+
+        for period in 1..6 {
+            let mut expected = SimpleMovingAverage::new(period).unwrap();
+            let expected: Vec<f64> = inputs.iter().map(|&x| expected.next(x)).collect();
+
+            for batch in [1, 2, 3, 7, 40] {
+                let mut sma = SimpleMovingAverage::new(period).unwrap();
+                let mut out = Vec::new();
+                for chunk in inputs.chunks(batch) {
+                    sma.next_batch(chunk, &mut out);
+                }
+                assert_eq!(out, expected);
+                assert_eq!(sma.current(), out.last().copied());
+            }
+        }
+
+        // also for `f32` and the compensated sum
+        let inputs_f32: Vec<f32> = inputs.iter().map(|&x| x as f32).collect();
+        let mut sma = SimpleMovingAverage::<f32>::with_period(4).unwrap();
+        let expected: Vec<f32> = inputs_f32.iter().map(|&x| sma.next(x)).collect();
+        let mut out = Vec::new();
+        let mut sma = SimpleMovingAverage::<f32>::with_period(4).unwrap();
+        sma.next_batch(&inputs_f32, &mut out);
+        assert_eq!(out, expected);
+
+        let mut sma = SimpleMovingAverage::compensated(4).unwrap();
+        let expected: Vec<f64> = inputs.iter().map(|&x| sma.next(x)).collect();
+        let mut out = Vec::new();
+        let mut sma = SimpleMovingAverage::compensated(4).unwrap();
+        sma.next_batch(&inputs, &mut out);
+        assert_eq!(out, expected);
+
+        // the state afterwards is the same as after `next`
+        let mut sma = SimpleMovingAverage::new(3).unwrap();
+        sma.next_batch(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0], &mut Vec::new());
+        assert_eq!(sma.next(11.0), 8.0);
+        assert_eq!(sma.project_next(1.0), 19.0 / 3.0);
+    }
+
+    #[test]
+    fn test_set_period() {
+        let inputs = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0];
+        for (old, new) in [(3, 5), (5, 3), (4, 1), (2, 2), (10, 4)] {
+            let mut sma = SimpleMovingAverage::new(old).unwrap();
+            for &x in &inputs {
+                sma.next(x);
+            }
+            sma.set_period(new).unwrap();
+
+            // the same as an SMA, which has seen only the retained inputs
+            let retained = old.min(new).min(inputs.len());
+            let mut expected = SimpleMovingAverage::new(new).unwrap();
+            for &x in &inputs[inputs.len() - retained..] {
+                expected.next(x);
+            }
+            assert_eq!(sma.period(), new);
+            assert_eq!(sma.current(), expected.current());
+            assert_eq!(sma.bars_until_ready(), expected.bars_until_ready());
+            assert_eq!(sma.next(6.0), expected.next(6.0));
+        }
+    }
+
+    #[test]
+    fn test_set_period_invalid() {
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        sma.next(1.0);
+        assert_eq!(sma.set_period(0), Err(TaError::zero_period("period")));
+        assert!(matches!(
+            sma.set_multiplier(2.0),
+            Err(TaError::InvalidParameter {
+                name: "multiplier",
+                ..
+            })
+        ));
+        assert_eq!(sma.period(), 2);
+        assert_eq!(sma.next(3.0), 2.0);
+
+        let mut empty = SimpleMovingAverage::new(2).unwrap();
+        empty.set_period(3).unwrap();
+        assert_eq!(empty.current(), None);
+    }
+
+    #[test]
+    fn test_compensated() {
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        let mut compensated = SimpleMovingAverage::compensated(2).unwrap();
+        for x in [1e16, 1.0, 2.0] {
+            sma.next(x);
+            compensated.next(x);
+        }
+        // the window is [1.0, 2.0] after 1e16 left it
+        assert_eq!(sma.current(), Some(1.0));
+        assert_eq!(compensated.current(), Some(1.5));
+
+        compensated.set_period(3).unwrap();
+        compensated.next(1e16);
+        compensated.next(0.0);
+        assert_eq!(compensated.next(0.0), 1e16 / 3.0);
+        assert_eq!(compensated.next(3.0), 1.0);
+
+        compensated.reset();
+        assert_eq!(compensated.next(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut SimpleMovingAverage::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut SimpleMovingAverage::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_default() {
         SimpleMovingAverage::default();
@@ -182,4 +477,18 @@ mod tests {
         let sma = SimpleMovingAverage::new(5).unwrap();
         assert_eq!(format!("{}", sma), "SMA(5)");
     }
+
+    #[test]
+    fn test_parameters() {
+        let mut sma = SimpleMovingAverage::new(5).unwrap();
+        assert_eq!(sma.params(), [("period", ParamValue::Period(5))]);
+        sma.set_param("period", ParamValue::Period(3)).unwrap();
+        assert_eq!(sma.period(), 3);
+        assert_eq!(
+            sma.set_param("length", ParamValue::Period(3)),
+            Err(TaError::UnknownParameter {
+                name: "length".to_string()
+            })
+        );
+    }
 }
@@ -1,8 +1,15 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 use crate::errors::Result;
+use crate::helpers::fmt_params;
 use crate::indicators::{ExponentialMovingAverage, FastStochastic};
-use crate::{Close, High, Low, Next, Period, Reset};
+use crate::registry::Params;
+use crate::{
+    Close, Current, High, IsReady, Low, MemoryFootprint, Next, ParamValue, Parameters, Period,
+    Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -33,6 +40,8 @@ use serde::{Deserialize, Serialize};
 pub struct SlowStochastic {
     fast_stochastic: FastStochastic,
     ema: ExponentialMovingAverage,
+    count: usize,
+    output: Option<f64>,
 }
 
 impl SlowStochastic {
@@ -40,15 +49,35 @@ impl SlowStochastic {
         Ok(Self {
             fast_stochastic: FastStochastic::new(stochastic_period)?,
             ema: ExponentialMovingAverage::new(ema_period)?,
+            count: 0,
+            output: None,
         })
     }
 }
 
+impl Period for SlowStochastic {
+    fn period(&self) -> usize {
+        self.fast_stochastic.period() + self.ema.period() - 1
+    }
+}
+
+impl IsReady for SlowStochastic {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
+}
+
 impl Next<f64> for SlowStochastic {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        self.ema.next(self.fast_stochastic.next(input))
+        if self.count < self.period() {
+            self.count += 1;
+        }
+
+        let output = self.ema.next(self.fast_stochastic.next(input));
+        self.output = Some(output);
+        output
     }
 }
 
@@ -56,7 +85,21 @@ impl<T: High + Low + Close> Next<&T> for SlowStochastic {
     type Output = f64;
 
     fn next(&mut self, input: &T) -> Self::Output {
-        self.ema.next(self.fast_stochastic.next(input))
+        if self.count < self.period() {
+            self.count += 1;
+        }
+
+        let output = self.ema.next(self.fast_stochastic.next(input));
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for SlowStochastic {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
@@ -64,6 +107,31 @@ impl Reset for SlowStochastic {
     fn reset(&mut self) {
         self.fast_stochastic.reset();
         self.ema.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for SlowStochastic {
+    fn heap_size(&self) -> usize {
+        self.fast_stochastic.heap_size()
+    }
+}
+
+impl Parameters for SlowStochastic {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![
+            ("period", ParamValue::Period(self.fast_stochastic.period())),
+            ("ema_period", ParamValue::Period(self.ema.period())),
+        ]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period", "ema_period"])?;
+        Self::new(
+            params.period("period", 14)?,
+            params.period("ema_period", 3)?,
+        )
     }
 }
 
@@ -75,12 +143,7 @@ impl Default for SlowStochastic {
 
 impl fmt::Display for SlowStochastic {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "SLOW_STOCH({}, {})",
-            self.fast_stochastic.period(),
-            self.ema.period()
-        )
+        fmt_params(f, "SLOW_STOCH", &self.params())
     }
 }
 
@@ -139,6 +202,22 @@ mod tests {
         assert_eq!(stoch.next(10.0), 50.0);
     }
 
+    #[test]
+    fn test_period() {
+        let stoch = SlowStochastic::new(14, 3).unwrap();
+        assert_eq!(stoch.period(), 16);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut SlowStochastic::new(3, 2).unwrap(), 1.0, 4);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut SlowStochastic::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_default() {
         SlowStochastic::default();
@@ -1,7 +1,18 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use num_traits::Float;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::helpers::fmt_params;
+use crate::registry::Params;
+use crate::ring_buffer::RingBuffer;
+use crate::rolling_variance::RollingVariance;
+use crate::{
+    Close, Configure, Current, IsReady, MemoryFootprint, Next, NextBatch, ParamValue, Parameters,
+    Period, Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -23,6 +34,16 @@ use serde::{Deserialize, Serialize};
 ///
 /// * _period_ - number of periods (integer greater than 0)
 ///
+/// The mean and the squared deviations are updated with every value using Welford's
+/// algorithm, replacing the value leaving the window, so an update takes O(1) and stays
+/// precise for values which are large compared to their spread.
+///
+/// # Value type
+///
+/// The values are `f64` by default. Any [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html)
+/// type can be used with [with_period](#method.with_period), e.g.
+/// `StandardDeviation::<f32>::with_period(20)`.
+///
 /// # Example
 ///
 /// ```
@@ -41,92 +62,130 @@ use serde::{Deserialize, Serialize};
 #[doc(alias = "SD")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
-pub struct StandardDeviation {
+pub struct StandardDeviation<T = f64> {
     period: usize,
-    index: usize,
-    count: usize,
-    m: f64,
-    m2: f64,
-    deque: Box<[f64]>,
+    stats: RollingVariance<T>,
+    window: RingBuffer<T>,
+    output: Option<T>,
 }
 
 impl StandardDeviation {
     pub fn new(period: usize) -> Result<Self> {
+        Self::with_period(period)
+    }
+}
+
+impl<T: Float> StandardDeviation<T> {
+    /// Same as `new`, for any value type.
+    pub fn with_period(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::zero_period("period")),
             _ => Ok(Self {
                 period,
-                index: 0,
-                count: 0,
-                m: 0.0,
-                m2: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                stats: RollingVariance::new(),
+                window: RingBuffer::new(period),
+                output: None,
             }),
         }
     }
 
-    pub(super) fn mean(&self) -> f64 {
-        self.m
+    pub(super) fn mean(&self) -> T {
+        self.stats.mean()
+    }
+
+    /// Values staying in the window, when the next value is added.
+    pub(super) fn retained(&self) -> impl Iterator<Item = T> + '_ {
+        let skip = if self.window.is_full() { 1 } else { 0 };
+        self.window.iter().skip(skip)
     }
 }
 
-impl Period for StandardDeviation {
+impl<T> Period for StandardDeviation<T> {
     fn period(&self) -> usize {
         self.period
     }
 }
 
-impl Next<f64> for StandardDeviation {
-    type Output = f64;
-
-    fn next(&mut self, input: f64) -> Self::Output {
-        let old_val = self.deque[self.index];
-        self.deque[self.index] = input;
-
-        self.index = if self.index + 1 < self.period {
-            self.index + 1
-        } else {
-            0
-        };
-
-        if self.count < self.period {
-            self.count += 1;
-            let delta = input - self.m;
-            self.m += delta / self.count as f64;
-            let delta2 = input - self.m;
-            self.m2 += delta * delta2;
-        } else {
-            let delta = input - old_val;
-            let old_m = self.m;
-            self.m += delta / self.period as f64;
-            let delta2 = input - self.m + old_val - old_m;
-            self.m2 += delta * delta2;
+impl<T: Float> Configure for StandardDeviation<T> {
+    fn set_period(&mut self, period: usize) -> Result<()> {
+        let mut sd = Self::with_period(period)?;
+        let count = self.window.len();
+        for value in self.window.iter().skip(count - count.min(period)) {
+            sd.next(value);
         }
-        if self.m2 < 0.0 {
-            self.m2 = 0.0;
+        *self = sd;
+        Ok(())
+    }
+}
+
+impl<T> IsReady for StandardDeviation<T> {
+    fn bars_until_ready(&self) -> usize {
+        self.period - self.window.len()
+    }
+}
+
+impl<T: Float> Next<T> for StandardDeviation<T> {
+    type Output = T;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        match self.window.push(input) {
+            Some(oldest) => self.stats.replace(oldest, input),
+            None => self.stats.add(input),
         }
 
-        (self.m2 / self.count as f64).sqrt()
+        let output = self.stats.variance().sqrt();
+        self.output = Some(output);
+        output
     }
 }
 
-impl<T: Close> Next<&T> for StandardDeviation {
-    type Output = f64;
+impl<T: Float> NextBatch<T> for StandardDeviation<T> {}
+
+// Bars are only accepted by the primitive floats, a generic impl would overlap `Next<T>`.
+macro_rules! impl_close {
+    ($($t:ty),*) => {$(
+        impl<C: Close> Next<&C> for StandardDeviation<$t> {
+            type Output = $t;
+
+            fn next(&mut self, input: &C) -> Self::Output {
+                self.next(input.close() as $t)
+            }
+        }
+    )*};
+}
+
+impl_close!(f32, f64);
 
-    fn next(&mut self, input: &T) -> Self::Output {
-        self.next(input.close())
+impl<T: Copy> Current for StandardDeviation<T> {
+    type Output = T;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
     }
 }
 
-impl Reset for StandardDeviation {
+impl<T: Float> Reset for StandardDeviation<T> {
     fn reset(&mut self) {
-        self.index = 0;
-        self.count = 0;
-        self.m = 0.0;
-        self.m2 = 0.0;
-        for i in 0..self.period {
-            self.deque[i] = 0.0;
-        }
+        self.stats.clear();
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl<T> MemoryFootprint for StandardDeviation<T> {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl<T: Float> Parameters for StandardDeviation<T> {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::with_period(params.period("period", 9)?)
     }
 }
 
@@ -136,9 +195,9 @@ impl Default for StandardDeviation {
     }
 }
 
-impl fmt::Display for StandardDeviation {
+impl<T: Float> fmt::Display for StandardDeviation<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "SD({})", self.period)
+        fmt_params(f, "SD", &self.params())
     }
 }
 
@@ -193,6 +252,15 @@ mod tests {
         assert_eq!(round(sd.next(&bar(100.0))), 35.355);
     }
 
+    #[test]
+    fn test_f32() {
+        let mut sd = StandardDeviation::<f32>::with_period(4).unwrap();
+        assert_eq!(sd.next(10.0f32), 0.0f32);
+        assert_eq!(sd.next(&Bar::new().close(20)), 5.0);
+        assert_eq!(sd.next(30.0), 8.164966);
+        assert_eq!(format!("{}", sd), "SD(4)");
+    }
+
     #[test]
     fn test_next_same_values() {
         let mut sd = StandardDeviation::new(3).unwrap();
@@ -213,6 +281,38 @@ mod tests {
         assert_eq!(sd.next(20.0), 0.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut StandardDeviation::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut StandardDeviation::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_set_period() {
+        let inputs = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0];
+        let mut sd = StandardDeviation::new(5).unwrap();
+        for &x in &inputs {
+            sd.next(x);
+        }
+        sd.set_period(3).unwrap();
+
+        let mut expected = StandardDeviation::new(3).unwrap();
+        for &x in &inputs[4..] {
+            expected.next(x);
+        }
+        assert_eq!(sd.period(), 3);
+        assert_eq!(sd.current(), expected.current());
+        assert_eq!(sd.next(6.0), expected.next(6.0));
+
+        sd.set_period(6).unwrap();
+        assert_eq!(sd.bars_until_ready(), 3);
+        assert!(sd.set_period(0).is_err());
+    }
+
     #[test]
     fn test_default() {
         StandardDeviation::default();
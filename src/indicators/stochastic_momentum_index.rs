@@ -0,0 +1,299 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::{ExponentialMovingAverage as Ema, Maximum, Minimum};
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Stochastic Momentum Index (SMI).
+///
+/// An oscillator by William Blau, which refines the [stochastic](struct.FastStochastic.html):
+/// instead of the position of the close within the high/low range, it measures the distance of
+/// the close from the midpoint of the range, and smooths both the distance and the range twice
+/// with an EMA. The SMI ranges from -100 to +100, with ±40 commonly used as overbought and
+/// oversold levels. An EMA of the SMI is used as a signal line.
+///
+/// # Formula
+///
+/// D = close - (highest high + lowest low) / 2
+///
+/// R = highest high - lowest low
+///
+/// SMI = 100 * EMA(EMA(D, _first_smoothing_), _second_smoothing_) /
+/// (EMA(EMA(R, _first_smoothing_), _second_smoothing_) / 2)
+///
+/// signal = EMA(SMI, _signal_period_)
+///
+/// Highest high and lowest low are taken over _period_ bars. SMI is 0, when the smoothed range
+/// is 0.
+///
+/// # Parameters
+///
+/// * _period_ - period of the high/low range (integer greater than 0). Default is 10.
+/// * _first_smoothing_ - period of the first EMA (integer greater than 0). Default is 3.
+/// * _second_smoothing_ - period of the second EMA (integer greater than 0). Default is 3.
+/// * _signal_period_ - period of the signal line (integer greater than 0). Default is 10.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::StochasticMomentumIndex;
+/// use ta::Next;
+///
+/// let mut smi = StochasticMomentumIndex::new(2, 1, 1, 1).unwrap();
+///
+/// smi.next(10.0);
+/// // the close is at the top of the range
+/// let out = smi.next(12.0);
+/// assert_eq!(out.smi, 100.0);
+/// assert_eq!(out.signal, 100.0);
+/// ```
+///
+/// # Links
+///
+/// * [Stochastic Momentum Index, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:stochastic_momentum_index)
+///
+#[doc(alias = "SMI")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct StochasticMomentumIndex {
+    period: usize,
+    first_smoothing: usize,
+    second_smoothing: usize,
+    signal_period: usize,
+    highest: Maximum,
+    lowest: Minimum,
+    distance: (Ema, Ema),
+    range: (Ema, Ema),
+    signal: Ema,
+    count: usize,
+    output: Option<StochasticMomentumIndexOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StochasticMomentumIndexOutput {
+    pub smi: f64,
+    pub signal: f64,
+}
+
+impl StochasticMomentumIndex {
+    pub fn new(
+        period: usize,
+        first_smoothing: usize,
+        second_smoothing: usize,
+        signal_period: usize,
+    ) -> Result<Self> {
+        Ok(Self {
+            period,
+            first_smoothing,
+            second_smoothing,
+            signal_period,
+            highest: Maximum::new(period)?,
+            lowest: Minimum::new(period)?,
+            distance: (Ema::new(first_smoothing)?, Ema::new(second_smoothing)?),
+            range: (Ema::new(first_smoothing)?, Ema::new(second_smoothing)?),
+            signal: Ema::new(signal_period)?,
+            count: 0,
+            output: None,
+        })
+    }
+
+    pub fn first_smoothing(&self) -> usize {
+        self.first_smoothing
+    }
+
+    pub fn second_smoothing(&self) -> usize {
+        self.second_smoothing
+    }
+
+    pub fn signal_period(&self) -> usize {
+        self.signal_period
+    }
+
+    fn update(&mut self, high: f64, low: f64, close: f64) -> StochasticMomentumIndexOutput {
+        if self.count < self.period() {
+            self.count += 1;
+        }
+
+        let highest = self.highest.next(high);
+        let lowest = self.lowest.next(low);
+
+        let distance = close - (highest + lowest) / 2.0;
+        let distance = self.distance.1.next(self.distance.0.next(distance));
+        let range = self.range.1.next(self.range.0.next(highest - lowest));
+
+        let smi = if range != 0.0 {
+            100.0 * distance / (range / 2.0)
+        } else {
+            0.0
+        };
+
+        let output = StochasticMomentumIndexOutput {
+            smi,
+            signal: self.signal.next(smi),
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Period for StochasticMomentumIndex {
+    fn period(&self) -> usize {
+        self.period + self.first_smoothing + self.second_smoothing + self.signal_period - 3
+    }
+}
+
+impl IsReady for StochasticMomentumIndex {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
+}
+
+impl Next<f64> for StochasticMomentumIndex {
+    type Output = StochasticMomentumIndexOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.update(input, input, input)
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for StochasticMomentumIndex {
+    type Output = StochasticMomentumIndexOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.update(input.high(), input.low(), input.close())
+    }
+}
+
+impl Current for StochasticMomentumIndex {
+    type Output = StochasticMomentumIndexOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for StochasticMomentumIndex {
+    fn reset(&mut self) {
+        self.highest.reset();
+        self.lowest.reset();
+        self.distance.0.reset();
+        self.distance.1.reset();
+        self.range.0.reset();
+        self.range.1.reset();
+        self.signal.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for StochasticMomentumIndex {
+    fn heap_size(&self) -> usize {
+        self.highest.heap_size() + self.lowest.heap_size()
+    }
+}
+
+impl Default for StochasticMomentumIndex {
+    fn default() -> Self {
+        Self::new(10, 3, 3, 10).unwrap()
+    }
+}
+
+impl fmt::Display for StochasticMomentumIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "SMI({}, {}, {}, {})",
+            self.period, self.first_smoothing, self.second_smoothing, self.signal_period
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(StochasticMomentumIndex);
+
+    #[test]
+    fn test_new() {
+        assert!(StochasticMomentumIndex::new(0, 3, 3, 10).is_err());
+        assert!(StochasticMomentumIndex::new(10, 0, 3, 10).is_err());
+        assert!(StochasticMomentumIndex::new(10, 3, 0, 10).is_err());
+        assert!(StochasticMomentumIndex::new(10, 3, 3, 0).is_err());
+        assert!(StochasticMomentumIndex::new(1, 1, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut smi = StochasticMomentumIndex::new(3, 2, 2, 3).unwrap();
+
+        let outputs: Vec<StochasticMomentumIndexOutput> = [10.0, 12.0, 11.0, 13.0, 9.0, 10.0]
+            .iter()
+            .map(|&x| smi.next(x))
+            .collect();
+
+        let values: Vec<f64> = outputs.iter().map(|o| round(o.smi)).collect();
+        assert_eq!(values, vec![0.0, 100.0, 40.0, 66.667, -37.647, -50.0]);
+        let signal: Vec<f64> = outputs.iter().map(|o| round(o.signal)).collect();
+        assert_eq!(signal, vec![0.0, 50.0, 45.0, 55.833, 9.093, -20.453]);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut smi = StochasticMomentumIndex::new(2, 2, 2, 2).unwrap();
+
+        let out = smi.next(&Bar::new().high(11.0).low(9.0).close(10.0));
+        assert_eq!(out.smi, 0.0);
+
+        let out = smi.next(&Bar::new().high(13.0).low(10.0).close(12.0));
+        assert_eq!(round(out.smi), 30.769);
+        assert_eq!(round(out.signal), 20.513);
+
+        let out = smi.next(&Bar::new().high(12.0).low(10.0).close(11.0));
+        assert_eq!(round(out.smi), 4.878);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut smi = StochasticMomentumIndex::new(3, 2, 2, 3).unwrap();
+
+        smi.next(10.0);
+        smi.next(12.0);
+
+        smi.reset();
+        assert_eq!(smi.next(10.0).smi, 0.0);
+        assert_eq!(smi.next(12.0).smi, 100.0);
+    }
+
+    #[test]
+    fn test_period() {
+        let smi = StochasticMomentumIndex::new(10, 3, 3, 10).unwrap();
+        assert_eq!(smi.period(), 23);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut smi = StochasticMomentumIndex::new(3, 2, 2, 2).unwrap();
+        assert_warm_up(&mut smi, 1.0, 6);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut StochasticMomentumIndex::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        StochasticMomentumIndex::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let smi = StochasticMomentumIndex::default();
+        assert_eq!(format!("{}", smi), "SMI(10, 3, 3, 10)");
+    }
+}
@@ -0,0 +1,308 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Reset, Volume};
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Time decayed volume weighted average price (TDVWAP).
+///
+/// A [VWAP](struct.VolumeWeightedAveragePrice.html), in which the weight of every trade decays
+/// exponentially with the time elapsed since the trade, not with the number of bars. The
+/// state consists of a few sums only, so very long effective windows over tick streams need no
+/// history buffer. Irregular ticks are handled naturally: a gap of one half-life halves the
+/// weight of everything before it, no matter how many ticks arrived.
+///
+/// Inputs are tuples with a timestamp: _(time, price, volume)_ for ticks, or _(time, bar)_ for
+/// bars, where the typical price of the bar is used. Timestamps can be in any unit (e.g.
+/// seconds), as long as the half-life uses the same one. A timestamp earlier than the previous
+/// one is treated as no elapsed time.
+///
+/// # Formula
+///
+/// decay = 0.5<sup>(time - previous time) / half-life</sup>
+///
+/// PV = PV * decay + price * volume
+///
+/// V = V * decay + volume
+///
+/// TDVWAP = PV / V
+///
+/// If there is no volume yet, the latest price is returned and the indicator is not ready.
+///
+/// # Parameters
+///
+/// * _half_life_ - time, after which the weight of a trade is halved (number greater than 0).
+///   Default is 60.0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TimeDecayedVolumeWeightedAveragePrice;
+/// use ta::Next;
+///
+/// let mut vwap = TimeDecayedVolumeWeightedAveragePrice::new(10.0).unwrap();
+///
+/// assert_eq!(vwap.next((0.0, 100.0, 10.0)), 100.0);
+/// // after one half-life the first trade weighs as 5 shares
+/// assert_eq!(vwap.next((10.0, 106.0, 10.0)), 104.0);
+/// ```
+#[doc(alias = "TDVWAP")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct TimeDecayedVolumeWeightedAveragePrice {
+    half_life: f64,
+    last_time: Option<f64>,
+    sum_price_volume: f64,
+    sum_price2_volume: f64,
+    sum_volume: f64,
+    vwap: f64,
+}
+
+impl TimeDecayedVolumeWeightedAveragePrice {
+    pub fn new(half_life: f64) -> Result<Self> {
+        if !(half_life > 0.0 && half_life.is_finite()) {
+            return Err(TaError::invalid_parameter(
+                "half_life",
+                half_life,
+                "must be a positive number",
+            ));
+        }
+        Ok(Self {
+            half_life,
+            last_time: None,
+            sum_price_volume: 0.0,
+            sum_price2_volume: 0.0,
+            sum_volume: 0.0,
+            vwap: 0.0,
+        })
+    }
+
+    pub fn half_life(&self) -> f64 {
+        self.half_life
+    }
+
+    pub fn vwap(&self) -> f64 {
+        self.vwap
+    }
+
+    /// Volume weighted standard deviation of the prices around the VWAP, with the same decayed
+    /// weights.
+    pub fn std_dev(&self) -> f64 {
+        if self.sum_volume > 0.0 {
+            let variance = self.sum_price2_volume / self.sum_volume - self.vwap * self.vwap;
+            variance.max(0.0).sqrt()
+        } else {
+            0.0
+        }
+    }
+
+    /// Effective volume, i.e. the sum of the decayed volumes.
+    pub fn effective_volume(&self) -> f64 {
+        self.sum_volume
+    }
+}
+
+impl IsReady for TimeDecayedVolumeWeightedAveragePrice {
+    fn bars_until_ready(&self) -> usize {
+        if self.sum_volume > 0.0 {
+            0
+        } else {
+            1
+        }
+    }
+}
+
+impl Next<(f64, f64, f64)> for TimeDecayedVolumeWeightedAveragePrice {
+    type Output = f64;
+
+    fn next(&mut self, (time, price, volume): (f64, f64, f64)) -> Self::Output {
+        if let Some(last_time) = self.last_time {
+            let elapsed = (time - last_time).max(0.0);
+            let decay = 0.5_f64.powf(elapsed / self.half_life);
+            self.sum_price_volume *= decay;
+            self.sum_price2_volume *= decay;
+            self.sum_volume *= decay;
+        }
+        self.last_time = Some(self.last_time.map_or(time, |last| last.max(time)));
+
+        self.sum_price_volume += price * volume;
+        self.sum_price2_volume += price * price * volume;
+        self.sum_volume += volume;
+
+        self.vwap = if self.sum_volume > 0.0 {
+            self.sum_price_volume / self.sum_volume
+        } else {
+            price
+        };
+        self.vwap
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<(f64, &T)> for TimeDecayedVolumeWeightedAveragePrice {
+    type Output = f64;
+
+    fn next(&mut self, (time, input): (f64, &T)) -> Self::Output {
+        let typical_price = (input.high() + input.low() + input.close()) / 3.0;
+        self.next((time, typical_price, input.volume()))
+    }
+}
+
+impl Current for TimeDecayedVolumeWeightedAveragePrice {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.last_time.map(|_| self.vwap)
+    }
+}
+
+impl Reset for TimeDecayedVolumeWeightedAveragePrice {
+    fn reset(&mut self) {
+        self.last_time = None;
+        self.sum_price_volume = 0.0;
+        self.sum_price2_volume = 0.0;
+        self.sum_volume = 0.0;
+        self.vwap = 0.0;
+    }
+}
+
+impl MemoryFootprint for TimeDecayedVolumeWeightedAveragePrice {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for TimeDecayedVolumeWeightedAveragePrice {
+    fn default() -> Self {
+        Self::new(60.0).unwrap()
+    }
+}
+
+impl fmt::Display for TimeDecayedVolumeWeightedAveragePrice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TDVWAP({})", self.half_life)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(TimeDecayedVolumeWeightedAveragePrice::new(0.0).is_err());
+        assert!(TimeDecayedVolumeWeightedAveragePrice::new(-1.0).is_err());
+        assert!(TimeDecayedVolumeWeightedAveragePrice::new(f64::NAN).is_err());
+        assert!(TimeDecayedVolumeWeightedAveragePrice::new(f64::INFINITY).is_err());
+        assert!(TimeDecayedVolumeWeightedAveragePrice::new(0.5).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut vwap = TimeDecayedVolumeWeightedAveragePrice::new(10.0).unwrap();
+
+        assert_eq!(vwap.next((0.0, 100.0, 10.0)), 100.0);
+        // no time elapsed, so this is a plain VWAP
+        assert_eq!(vwap.next((0.0, 103.0, 20.0)), 102.0);
+        assert_eq!(vwap.effective_volume(), 30.0);
+        assert_eq!(round(vwap.std_dev()), 1.414);
+
+        // two half-lives later the previous trades weigh as 7.5 shares
+        assert_eq!(vwap.next((20.0, 110.0, 7.5)), 106.0);
+        assert_eq!(vwap.effective_volume(), 15.0);
+    }
+
+    #[test]
+    fn test_irregular_ticks() {
+        let mut many = TimeDecayedVolumeWeightedAveragePrice::new(10.0).unwrap();
+        let mut one = TimeDecayedVolumeWeightedAveragePrice::new(10.0).unwrap();
+
+        many.next((0.0, 100.0, 10.0));
+        one.next((0.0, 100.0, 10.0));
+
+        // the decay depends on the elapsed time only, not on the number of ticks
+        for i in 1..=10 {
+            many.next((i as f64, 100.0, 0.0));
+        }
+        assert_eq!(round(many.next((10.0, 110.0, 10.0))), 106.667);
+        assert_eq!(round(one.next((10.0, 110.0, 10.0))), 106.667);
+    }
+
+    #[test]
+    fn test_time_going_back() {
+        let mut vwap = TimeDecayedVolumeWeightedAveragePrice::new(10.0).unwrap();
+
+        vwap.next((10.0, 100.0, 10.0));
+        assert_eq!(vwap.next((5.0, 106.0, 10.0)), 103.0);
+        // elapsed time is measured from the latest timestamp
+        vwap.next((20.0, 100.0, 0.0));
+        assert_eq!(vwap.effective_volume(), 10.0);
+    }
+
+    #[test]
+    fn test_zero_volume() {
+        let mut vwap = TimeDecayedVolumeWeightedAveragePrice::new(10.0).unwrap();
+
+        assert_eq!(vwap.next((0.0, 100.0, 0.0)), 100.0);
+        assert_eq!(vwap.next((1.0, 101.0, 0.0)), 101.0);
+        assert_eq!(vwap.std_dev(), 0.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut vwap = TimeDecayedVolumeWeightedAveragePrice::new(10.0).unwrap();
+
+        vwap.next((0.0, 100.0, 0.0));
+        assert!(!vwap.is_ready());
+        vwap.next((1.0, 101.0, 10.0));
+        assert!(vwap.is_ready());
+        vwap.next((2.0, 102.0, 0.0));
+        assert!(vwap.is_ready());
+
+        vwap.reset();
+        assert_eq!(vwap.bars_until_ready(), 1);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut vwap = TimeDecayedVolumeWeightedAveragePrice::new(10.0).unwrap();
+
+        let bar = Bar::new().high(12.0).low(9.0).close(9.0).volume(100.0);
+        assert_eq!(vwap.next((0.0, &bar)), 10.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vwap = TimeDecayedVolumeWeightedAveragePrice::new(10.0).unwrap();
+
+        vwap.next((0.0, 100.0, 10.0));
+        vwap.next((5.0, 103.0, 20.0));
+
+        vwap.reset();
+        assert_eq!(vwap.vwap(), 0.0);
+        assert_eq!(vwap.next((100.0, 50.0, 10.0)), 50.0);
+        assert_eq!(vwap.effective_volume(), 10.0);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(
+            &mut TimeDecayedVolumeWeightedAveragePrice::default(),
+            [(0.0, 100.0, 10.0), (10.0, 106.0, 10.0)],
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        TimeDecayedVolumeWeightedAveragePrice::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let vwap = TimeDecayedVolumeWeightedAveragePrice::new(30.0).unwrap();
+        assert_eq!(format!("{}", vwap), "TDVWAP(30)");
+    }
+}
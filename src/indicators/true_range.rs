@@ -1,7 +1,14 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
-use crate::helpers::max3;
-use crate::{Close, High, Low, Next, Reset};
+use crate::errors::Result;
+use crate::helpers::{fmt_params, max3};
+use crate::registry::Params;
+use crate::{
+    Close, Current, High, IsReady, Low, MemoryFootprint, Next, NextBatch, ParamValue, Parameters,
+    Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +25,9 @@ use serde::{Deserialize, Serialize};
 ///
 /// TR = max[(high - low), abs(high - close<sub>prev</sub>), abs(low - close<sub>prev</sub>)]
 ///
+/// For a single `f64` input the range is 0, so the true range is
+/// abs(input - input<sub>prev</sub>), and 0 for the first input.
+///
 /// # Example
 ///
 /// ```
@@ -53,11 +63,32 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone)]
 pub struct TrueRange {
     prev_close: Option<f64>,
+    output: Option<f64>,
 }
 
 impl TrueRange {
     pub fn new() -> Self {
-        Self { prev_close: None }
+        Self {
+            prev_close: None,
+            output: None,
+        }
+    }
+}
+
+impl IsReady for TrueRange {
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
+}
+
+impl Parameters for TrueRange {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&[])?;
+        Ok(Self::new())
     }
 }
 
@@ -69,7 +100,7 @@ impl Default for TrueRange {
 
 impl fmt::Display for TrueRange {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "TRUE_RANGE()")
+        fmt_params(f, "TRUE_RANGE", &self.params())
     }
 }
 
@@ -82,10 +113,30 @@ impl Next<f64> for TrueRange {
             None => 0.0,
         };
         self.prev_close = Some(input);
+        self.output = Some(distance);
         distance
     }
 }
 
+impl NextBatch<f64> for TrueRange {
+    /// With the `simd` feature the distances between the inputs are vectorized, they are the
+    /// same as with `next`.
+    #[cfg(feature = "simd")]
+    fn next_batch(&mut self, inputs: &[f64], out: &mut Vec<Self::Output>) {
+        let (&first, rest) = match inputs.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+        out.reserve(inputs.len());
+        out.push(self.next(first));
+        let start = out.len();
+        out.resize(start + rest.len(), 0.0);
+        crate::simd::abs_diff(rest, &inputs[..rest.len()], &mut out[start..]);
+        self.prev_close = Some(inputs[rest.len()]);
+        self.output = out.last().copied();
+    }
+}
+
 impl<T: High + Low + Close> Next<&T> for TrueRange {
     type Output = f64;
 
@@ -100,13 +151,29 @@ impl<T: High + Low + Close> Next<&T> for TrueRange {
             None => bar.high() - bar.low(),
         };
         self.prev_close = Some(bar.close());
+        self.output = Some(max_dist);
         max_dist
     }
 }
 
+impl Current for TrueRange {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
 impl Reset for TrueRange {
     fn reset(&mut self) {
         self.prev_close = None;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for TrueRange {
+    fn heap_size(&self) -> usize {
+        0
     }
 }
 
@@ -125,6 +192,24 @@ mod tests {
         assert_eq!(round(tr.next(3.3)), 0.3);
     }
 
+    #[test]
+    fn test_next_batch() {
+        let inputs = [2.5, 3.6, 3.3, 3.3, 1.0, 4.0, 2.0, 2.5, 7.0];
+        let mut expected = TrueRange::new();
+        let expected: Vec<f64> = inputs.iter().map(|&x| expected.next(x)).collect();
+
+        for batch in [1, 2, 5, 9] {
+            let mut tr = TrueRange::new();
+            let mut out = Vec::new();
+            for chunk in inputs.chunks(batch) {
+                tr.next_batch(chunk, &mut out);
+            }
+            assert_eq!(out, expected);
+            assert_eq!(tr.current(), Some(4.5));
+            assert_eq!(tr.next(6.0), 1.0);
+        }
+    }
+
     #[test]
     fn test_next_bar() {
         let mut tr = TrueRange::new();
@@ -153,6 +238,16 @@ mod tests {
         assert_eq!(tr.next(&bar3), 45.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert!(TrueRange::new().is_ready());
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut TrueRange::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_default() {
         TrueRange::default();
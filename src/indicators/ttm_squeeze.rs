@@ -0,0 +1,341 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{
+    BollingerBands, BollingerBandsOutput, KeltnerChannel, KeltnerChannelOutput, Maximum, Minimum,
+    SimpleMovingAverage,
+};
+use crate::ring_buffer::RingBuffer;
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// TTM Squeeze.
+///
+/// An indicator by John Carter, which detects periods of low volatility, that are often
+/// followed by a strong move. The squeeze is on, when the [Bollinger Bands](struct.BollingerBands.html)
+/// are inside of the [Keltner Channel](struct.KeltnerChannel.html). The squeeze fires on the
+/// first bar, when the bands leave the channel again.
+///
+/// The direction of the move is estimated with a momentum histogram: the distance of the close
+/// from the middle of the recent range, smoothed with a linear regression.
+///
+/// # Formula
+///
+/// squeeze on = BB<sub>lower</sub> > KC<sub>lower</sub> and BB<sub>upper</sub> < KC<sub>upper</sub>
+///
+/// delta = close - ((highest high + lowest low) / 2 + SMA(close)) / 2
+///
+/// momentum = value of the linear regression line of delta over _period_ at the last bar
+///
+/// # Parameters
+///
+/// * _period_ - period of all the components (integer greater than 0). Default is 20.
+/// * _bb_multiplier_ - width of the Bollinger Bands (number greater than 0). Default is 2.0.
+/// * _kc_multiplier_ - width of the Keltner Channel (number greater than 0). Default is 1.5.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TtmSqueeze;
+/// use ta::Next;
+///
+/// let mut squeeze = TtmSqueeze::new(3, 2.0, 1.5).unwrap();
+///
+/// squeeze.next(10.0);
+/// let out = squeeze.next(11.0);
+/// assert!(!out.squeeze_on);
+/// assert_eq!(out.momentum, 0.5);
+/// ```
+///
+/// # Links
+///
+/// * [TTM Squeeze, StockCharts](https://school.stockcharts.com/doku.php?id=technical_indicators:ttm_squeeze)
+///
+#[doc(alias = "TTM")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct TtmSqueeze {
+    period: usize,
+    bb: BollingerBands,
+    kc: KeltnerChannel,
+    highest: Maximum,
+    lowest: Minimum,
+    sma: SimpleMovingAverage,
+    squeeze_on: bool,
+    /// Deltas of the close from the midpoint of the range and the SMA.
+    deltas: RingBuffer,
+    bars: usize,
+    output: Option<TtmSqueezeOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TtmSqueezeOutput {
+    /// Whether the Bollinger Bands are inside of the Keltner Channel.
+    pub squeeze_on: bool,
+    /// Whether the squeeze was on at the previous bar and is off now.
+    pub fired: bool,
+    pub momentum: f64,
+}
+
+impl TtmSqueeze {
+    pub fn new(period: usize, bb_multiplier: f64, kc_multiplier: f64) -> Result<Self> {
+        if bb_multiplier.is_nan() || bb_multiplier <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "bb_multiplier",
+                bb_multiplier,
+                "must be greater than 0",
+            ));
+        }
+        if kc_multiplier.is_nan() || kc_multiplier <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "kc_multiplier",
+                kc_multiplier,
+                "must be greater than 0",
+            ));
+        }
+        Ok(Self {
+            period,
+            bb: BollingerBands::new(period, bb_multiplier)?,
+            kc: KeltnerChannel::new(period, kc_multiplier)?,
+            highest: Maximum::new(period)?,
+            lowest: Minimum::new(period)?,
+            sma: SimpleMovingAverage::new(period)?,
+            squeeze_on: false,
+            deltas: RingBuffer::new(period),
+            bars: 0,
+            output: None,
+        })
+    }
+
+    fn update(
+        &mut self,
+        bb: BollingerBandsOutput,
+        kc: KeltnerChannelOutput,
+        high: f64,
+        low: f64,
+        close: f64,
+    ) -> TtmSqueezeOutput {
+        if self.bars < self.period() {
+            self.bars += 1;
+        }
+
+        let squeeze_on = bb.lower > kc.lower && bb.upper < kc.upper;
+        let fired = self.squeeze_on && !squeeze_on;
+        self.squeeze_on = squeeze_on;
+
+        let middle = (self.highest.next(high) + self.lowest.next(low)) / 2.0;
+        let delta = close - (middle + self.sma.next(close)) / 2.0;
+
+        self.deltas.push(delta);
+
+        let output = TtmSqueezeOutput {
+            squeeze_on,
+            fired,
+            momentum: self.regression_end(),
+        };
+        self.output = Some(output);
+        output
+    }
+
+    // Value of the least squares line through the window at the newest value.
+    fn regression_end(&self) -> f64 {
+        let n = self.deltas.len() as f64;
+
+        let (mut sum_y, mut sum_xy) = (0.0, 0.0);
+        for (i, y) in self.deltas.iter().enumerate() {
+            sum_y += y;
+            sum_xy += i as f64 * y;
+        }
+
+        if self.deltas.len() == 1 {
+            return sum_y;
+        }
+
+        let sum_x = n * (n - 1.0) / 2.0;
+        let sum_xx = n * (n - 1.0) * (2.0 * n - 1.0) / 6.0;
+        let slope = (n * sum_xy - sum_x * sum_y) / (n * sum_xx - sum_x * sum_x);
+        let intercept = (sum_y - slope * sum_x) / n;
+        intercept + slope * (n - 1.0)
+    }
+}
+
+impl Period for TtmSqueeze {
+    fn period(&self) -> usize {
+        // the momentum is a regression over deltas of the period
+        2 * self.period - 1
+    }
+}
+
+impl IsReady for TtmSqueeze {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.bars
+    }
+}
+
+impl Next<f64> for TtmSqueeze {
+    type Output = TtmSqueezeOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let bb = self.bb.next(input);
+        let kc = self.kc.next(input);
+        self.update(bb, kc, input, input, input)
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for TtmSqueeze {
+    type Output = TtmSqueezeOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let bb = self.bb.next(input);
+        let kc = self.kc.next(input);
+        self.update(bb, kc, input.high(), input.low(), input.close())
+    }
+}
+
+impl Current for TtmSqueeze {
+    type Output = TtmSqueezeOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for TtmSqueeze {
+    fn reset(&mut self) {
+        self.bb.reset();
+        self.kc.reset();
+        self.highest.reset();
+        self.lowest.reset();
+        self.sma.reset();
+        self.squeeze_on = false;
+        self.deltas.clear();
+        self.bars = 0;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for TtmSqueeze {
+    fn heap_size(&self) -> usize {
+        self.bb.heap_size()
+            + self.kc.heap_size()
+            + self.highest.heap_size()
+            + self.lowest.heap_size()
+            + self.sma.heap_size()
+            + self.deltas.heap_size()
+    }
+}
+
+impl Default for TtmSqueeze {
+    fn default() -> Self {
+        Self::new(20, 2.0, 1.5).unwrap()
+    }
+}
+
+impl fmt::Display for TtmSqueeze {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TTM({}, {}, {})",
+            self.period,
+            self.bb.multiplier(),
+            self.kc.multiplier()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(TtmSqueeze);
+
+    #[test]
+    fn test_new() {
+        assert!(TtmSqueeze::new(0, 2.0, 1.5).is_err());
+        assert!(TtmSqueeze::new(20, 0.0, 1.5).is_err());
+        assert!(TtmSqueeze::new(20, 2.0, -1.0).is_err());
+        assert!(TtmSqueeze::new(1, 2.0, 1.5).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut ttm = TtmSqueeze::new(3, 2.0, 1.5).unwrap();
+
+        let outputs: Vec<TtmSqueezeOutput> = [10.0, 11.0, 12.0, 11.5, 11.6, 11.5, 11.6, 13.0, 15.0]
+            .iter()
+            .map(|&x| ttm.next(x))
+            .collect();
+
+        let squeeze: Vec<bool> = outputs.iter().map(|o| o.squeeze_on).collect();
+        assert_eq!(
+            squeeze,
+            vec![false, false, false, false, false, true, true, false, false]
+        );
+        let fired: Vec<bool> = outputs.iter().map(|o| o.fired).collect();
+        assert_eq!(
+            fired,
+            vec![false, false, false, false, false, false, false, true, false]
+        );
+
+        let momentum: Vec<f64> = outputs.iter().map(|o| round(o.momentum)).collect();
+        assert_eq!(
+            momentum,
+            vec![0.0, 0.5, 1.0, 0.25, -0.271, -0.076, 0.042, 0.736, 1.738]
+        );
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut ttm = TtmSqueeze::new(3, 2.0, 1.5).unwrap();
+
+        // highest high 12, lowest low 9, SMA(close) 10.5
+        ttm.next(&Bar::new().high(11.0).low(9.0).close(10.0));
+        let out = ttm.next(&Bar::new().high(12.0).low(10.0).close(11.0));
+        assert_eq!(out.momentum, 0.5);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ttm = TtmSqueeze::new(3, 2.0, 1.5).unwrap();
+
+        for &x in &[10.0, 11.0, 12.0, 11.5, 11.6, 11.5] {
+            ttm.next(x);
+        }
+        assert!(ttm.next(11.6).squeeze_on);
+
+        ttm.reset();
+        let out = ttm.next(10.0);
+        assert!(!out.squeeze_on);
+        assert_eq!(out.momentum, 0.0);
+        assert_eq!(ttm.next(11.0).momentum, 0.5);
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(TtmSqueeze::new(20, 2.0, 1.5).unwrap().period(), 39);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut TtmSqueeze::new(3, 2.0, 1.5).unwrap(), 1.0, 5);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut TtmSqueeze::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        TtmSqueeze::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let ttm = TtmSqueeze::default();
+        assert_eq!(format!("{}", ttm), "TTM(20, 2, 1.5)");
+    }
+}
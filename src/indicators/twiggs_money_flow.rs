@@ -0,0 +1,247 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::ExponentialMovingAverage as Ema;
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Twiggs Money Flow (TMF).
+///
+/// A variant of the Chaikin Money Flow by Colin Twiggs. Like the
+/// [true range](struct.TrueRange.html), the range of a bar is extended to the previous close,
+/// so a gap counts as a move instead of being ignored, and the money flow is smoothed with
+/// Wilder's moving average instead of being summed over a window, which avoids jumps when a
+/// large bar leaves the window. The TMF ranges from -1 to +1: positive values show buying
+/// pressure, negative values selling pressure.
+///
+/// # Formula
+///
+/// TRH = max(high, previous close)
+///
+/// TRL = min(low, previous close)
+///
+/// AD = ((close - TRL) - (TRH - close)) / (TRH - TRL) * volume
+///
+/// TMF = Wilder(AD, _period_) / Wilder(volume, _period_)
+///
+/// Wilder's moving average with _period_ is an [EMA](struct.ExponentialMovingAverage.html)
+/// with period 2 * _period_ - 1. AD is 0 for a bar with no true range, TMF is 0 while no
+/// volume was traded.
+///
+/// # Parameters
+///
+/// * _period_ - smoothing period (integer greater than 0). Default is 21.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::TwiggsMoneyFlow;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(high: f64, low: f64, close: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(close).high(high).low(low).close(close).volume(100.0)
+///         .build().unwrap()
+/// }
+///
+/// let mut tmf = TwiggsMoneyFlow::new(21).unwrap();
+///
+/// // the close is in the middle of the range
+/// assert_eq!(tmf.next(&bar(10.0, 8.0, 9.0)), 0.0);
+/// // a gap up closing at the high
+/// assert!(tmf.next(&bar(12.0, 11.0, 12.0)) > 0.0);
+/// ```
+///
+/// # Links
+///
+/// * [Twiggs Money Flow, Incredible Charts](https://www.incrediblecharts.com/indicators/twiggs_money_flow.php)
+///
+#[doc(alias = "TMF")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct TwiggsMoneyFlow {
+    period: usize,
+    prev_close: Option<f64>,
+    money_flow: Ema,
+    volume: Ema,
+    output: Option<f64>,
+}
+
+impl TwiggsMoneyFlow {
+    pub fn new(period: usize) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::zero_period("period"));
+        }
+        Ok(Self {
+            period,
+            prev_close: None,
+            money_flow: Ema::new(2 * period - 1)?,
+            volume: Ema::new(2 * period - 1)?,
+            output: None,
+        })
+    }
+}
+
+impl Period for TwiggsMoneyFlow {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for TwiggsMoneyFlow {
+    fn bars_until_ready(&self) -> usize {
+        // the Wilder smoothing of the period is an EMA of 2 * period - 1
+        self.money_flow
+            .bars_until_ready()
+            .saturating_sub(self.period - 1)
+    }
+}
+
+impl<T: High + Low + Close + Volume> Next<&T> for TwiggsMoneyFlow {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let close = input.close();
+        let (high, low) = match self.prev_close {
+            Some(prev_close) => (input.high().max(prev_close), input.low().min(prev_close)),
+            None => (input.high(), input.low()),
+        };
+        self.prev_close = Some(close);
+
+        let range = high - low;
+        let money_flow = if range > 0.0 {
+            ((close - low) - (high - close)) / range * input.volume()
+        } else {
+            0.0
+        };
+
+        let money_flow = self.money_flow.next(money_flow);
+        let volume = self.volume.next(input.volume());
+        let output = if volume > 0.0 {
+            money_flow / volume
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for TwiggsMoneyFlow {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for TwiggsMoneyFlow {
+    fn reset(&mut self) {
+        self.prev_close = None;
+        self.money_flow.reset();
+        self.volume.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for TwiggsMoneyFlow {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for TwiggsMoneyFlow {
+    fn default() -> Self {
+        Self::new(21).unwrap()
+    }
+}
+
+impl fmt::Display for TwiggsMoneyFlow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TMF({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_hlcv_indicator!(TwiggsMoneyFlow);
+
+    fn bar(high: f64, low: f64, close: f64, volume: f64) -> Bar {
+        Bar::new().high(high).low(low).close(close).volume(volume)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(TwiggsMoneyFlow::new(0).is_err());
+        assert!(TwiggsMoneyFlow::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut tmf = TwiggsMoneyFlow::new(2).unwrap();
+
+        assert_eq!(tmf.next(&bar(10.0, 8.0, 9.0, 100.0)), 0.0);
+        // the true range extends down to the previous close 9
+        assert_eq!(round(tmf.next(&bar(12.0, 9.5, 12.0, 200.0))), 0.667);
+        // the true range extends up to the previous close 12
+        assert_eq!(tmf.next(&bar(11.0, 10.0, 10.0, 100.0)), 0.0);
+        assert_eq!(round(tmf.next(&bar(11.0, 10.0, 11.0, 100.0))), 0.444);
+    }
+
+    #[test]
+    fn test_no_range() {
+        let mut tmf = TwiggsMoneyFlow::new(2).unwrap();
+
+        assert_eq!(tmf.next(&bar(10.0, 10.0, 10.0, 100.0)), 0.0);
+        assert_eq!(tmf.next(&bar(10.0, 10.0, 10.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_no_volume() {
+        let mut tmf = TwiggsMoneyFlow::new(2).unwrap();
+
+        assert_eq!(tmf.next(&bar(12.0, 10.0, 12.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut tmf = TwiggsMoneyFlow::new(2).unwrap();
+
+        tmf.next(&bar(10.0, 8.0, 9.0, 100.0));
+        tmf.next(&bar(12.0, 9.5, 12.0, 200.0));
+
+        tmf.reset();
+        // the previous close is forgotten
+        assert_eq!(tmf.next(&bar(12.0, 10.0, 11.0, 100.0)), 0.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(2).low(1).close(1.5).volume(100.0);
+        assert_warm_up(&mut TwiggsMoneyFlow::new(3).unwrap(), &bar, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut TwiggsMoneyFlow::default(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        TwiggsMoneyFlow::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let tmf = TwiggsMoneyFlow::default();
+        assert_eq!(format!("{}", tmf), "TMF(21)");
+    }
+}
@@ -0,0 +1,132 @@
+use core::fmt;
+
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Typical price (TP).
+///
+/// Average of the high, low and close prices of a bar. Also known as HLC3, it is the price
+/// used by e.g. the CCI, the MFI and the VWAP.
+///
+/// # Formula
+///
+/// TP = (high + low + close) / 3
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{TypicalPrice, SimpleMovingAverage};
+/// use ta::{DataItem, Next};
+///
+/// let bar = DataItem::builder()
+///     .open(10.0).high(12.0).low(9.0).close(9.0).volume(100.0)
+///     .build().unwrap();
+///
+/// let mut price = TypicalPrice::new();
+/// let mut sma = SimpleMovingAverage::new(3).unwrap();
+/// assert_eq!(sma.next(price.next(&bar)), 10.0);
+/// ```
+#[doc(alias = "TP")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct TypicalPrice {
+    output: Option<f64>,
+}
+
+impl TypicalPrice {
+    pub fn new() -> Self {
+        Self { output: None }
+    }
+}
+
+impl IsReady for TypicalPrice {
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for TypicalPrice {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let output = (input.high() + input.low() + input.close()) / 3.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for TypicalPrice {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for TypicalPrice {
+    fn reset(&mut self) {
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for TypicalPrice {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for TypicalPrice {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for TypicalPrice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TP")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_hlcv_indicator!(TypicalPrice);
+
+    #[test]
+    fn test_next() {
+        let mut price = TypicalPrice::new();
+
+        assert_eq!(price.next(&Bar::new().high(12.0).low(9.0).close(9.0)), 10.0);
+        assert_eq!(
+            round(price.next(&Bar::new().high(10.0).low(8.0).close(9.5))),
+            9.167
+        );
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert!(TypicalPrice::new().is_ready());
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut TypicalPrice::default(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        TypicalPrice::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", TypicalPrice::new()), "TP");
+    }
+}
@@ -0,0 +1,268 @@
+use core::fmt;
+
+use num_traits::Float;
+
+use crate::errors::Result;
+use crate::helpers::cast;
+use crate::indicators::{RateOfChange, StandardDeviation as Sd};
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volatility Adjusted Rate of Change (VAROC).
+///
+/// The [rate of change](struct.RateOfChange.html) divided by the standard deviation of the
+/// one-bar returns. The same 10% move is a strong signal for a quiet asset and noise for a
+/// volatile one; measured in units of volatility, the momentum of different assets can be
+/// compared and ranked directly.
+///
+/// # Formula
+///
+/// VAROC = ROC(_period_) / SD(r, _sd_period_)
+///
+/// Where:
+///
+/// * _r_ - one-bar return in percent: (price<sub>t</sub> - price<sub>t-1</sub>) /
+///   price<sub>t-1</sub> * 100
+/// * _SD_ - population [standard deviation](struct.StandardDeviation.html)
+///
+/// VAROC is 0 until the returns in the window vary.
+///
+/// # Parameters
+///
+/// * _period_ - period of the rate of change (integer greater than 0). Default is 10.
+/// * _sd_period_ - number of returns in the volatility window (integer greater than 0).
+///   Default is 20.
+///
+/// # Value type
+///
+/// The values are `f64` by default. Any [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html)
+/// type can be used with [with_periods](#method.with_periods), e.g.
+/// `VolatilityAdjustedRateOfChange::<f32>::with_periods(10, 20)`.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VolatilityAdjustedRateOfChange;
+/// use ta::Next;
+///
+/// let mut varoc = VolatilityAdjustedRateOfChange::new(2, 2).unwrap();
+/// varoc.next(100.0);
+/// varoc.next(110.0);
+/// // ROC -1%, returns +10% and -10%
+/// assert_eq!(varoc.next(99.0), -0.1);
+/// ```
+#[doc(alias = "VAROC")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct VolatilityAdjustedRateOfChange<T = f64> {
+    roc: RateOfChange<T>,
+    sd: Sd<T>,
+    prev: Option<T>,
+    volatility: T,
+    count: usize,
+    output: Option<T>,
+}
+
+impl VolatilityAdjustedRateOfChange {
+    pub fn new(period: usize, sd_period: usize) -> Result<Self> {
+        Self::with_periods(period, sd_period)
+    }
+}
+
+impl<T: Float> VolatilityAdjustedRateOfChange<T> {
+    /// Same as `new`, for any value type.
+    pub fn with_periods(period: usize, sd_period: usize) -> Result<Self> {
+        Ok(Self {
+            roc: RateOfChange::with_period(period)?,
+            sd: Sd::with_period(sd_period)?,
+            prev: None,
+            volatility: T::zero(),
+            count: 0,
+            output: None,
+        })
+    }
+}
+
+impl<T> Period for VolatilityAdjustedRateOfChange<T> {
+    fn period(&self) -> usize {
+        // the first bar has no return, like the ROC has no previous value
+        self.roc.period().max(self.sd.period() + 1)
+    }
+}
+
+impl<T> IsReady for VolatilityAdjustedRateOfChange<T> {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
+}
+
+impl<T: Float> Next<T> for VolatilityAdjustedRateOfChange<T> {
+    type Output = T;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        if self.count < self.period() {
+            self.count += 1;
+        }
+
+        let roc = self.roc.next(input);
+        // the first bar has no return, it must not dilute the volatility with a 0
+        if let Some(prev) = self.prev {
+            self.volatility = self.sd.next((input - prev) / prev * cast(100));
+        }
+        self.prev = Some(input);
+
+        let output = if self.volatility > T::zero() {
+            roc / self.volatility
+        } else {
+            T::zero()
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+// Bars are only accepted by the primitive floats, a generic impl would overlap `Next<T>`.
+macro_rules! impl_close {
+    ($($t:ty),*) => {$(
+        impl<C: Close> Next<&C> for VolatilityAdjustedRateOfChange<$t> {
+            type Output = $t;
+
+            fn next(&mut self, input: &C) -> Self::Output {
+                self.next(input.close() as $t)
+            }
+        }
+    )*};
+}
+
+impl_close!(f32, f64);
+
+impl<T: Copy> Current for VolatilityAdjustedRateOfChange<T> {
+    type Output = T;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<T: Float> Reset for VolatilityAdjustedRateOfChange<T> {
+    fn reset(&mut self) {
+        self.roc.reset();
+        self.sd.reset();
+        self.prev = None;
+        self.volatility = T::zero();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl<T> MemoryFootprint for VolatilityAdjustedRateOfChange<T> {
+    fn heap_size(&self) -> usize {
+        self.roc.heap_size() + self.sd.heap_size()
+    }
+}
+
+impl Default for VolatilityAdjustedRateOfChange {
+    fn default() -> Self {
+        Self::new(10, 20).unwrap()
+    }
+}
+
+impl<T> fmt::Display for VolatilityAdjustedRateOfChange<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // the warm-up of the ROC is a bar longer than its period
+        write!(f, "VAROC({}, {})", self.roc.period() - 1, self.sd.period())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(VolatilityAdjustedRateOfChange);
+
+    #[test]
+    fn test_new() {
+        assert!(VolatilityAdjustedRateOfChange::new(0, 2).is_err());
+        assert!(VolatilityAdjustedRateOfChange::new(2, 0).is_err());
+        assert!(VolatilityAdjustedRateOfChange::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut varoc = VolatilityAdjustedRateOfChange::new(2, 2).unwrap();
+
+        assert_eq!(varoc.next(100.0), 0.0);
+        // a single return has no volatility
+        assert_eq!(varoc.next(110.0), 0.0);
+        assert_eq!(round(varoc.next(99.0)), -0.1);
+        assert_eq!(round(varoc.next(104.0)), -0.725);
+        assert_eq!(round(varoc.next(108.0)), 15.097);
+    }
+
+    #[test]
+    fn test_f32() {
+        let mut varoc = VolatilityAdjustedRateOfChange::<f32>::with_periods(2, 2).unwrap();
+
+        assert_eq!(varoc.next(100.0f32), 0.0f32);
+        assert_eq!(varoc.next(&Bar::new().close(110)), 0.0);
+        assert_eq!(round(varoc.next(99.0).into()), -0.1);
+        assert_eq!(format!("{}", varoc), "VAROC(2, 2)");
+    }
+
+    #[test]
+    fn test_flat() {
+        let mut varoc = VolatilityAdjustedRateOfChange::new(2, 3).unwrap();
+
+        for &price in &[10.0, 10.0, 10.0, 10.0] {
+            assert_eq!(varoc.next(price), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut varoc = VolatilityAdjustedRateOfChange::new(2, 2).unwrap();
+
+        varoc.next(100.0);
+        varoc.next(110.0);
+        varoc.next(99.0);
+
+        varoc.reset();
+        assert_eq!(varoc.next(100.0), 0.0);
+        assert_eq!(varoc.next(110.0), 0.0);
+    }
+
+    #[test]
+    fn test_period() {
+        let varoc = VolatilityAdjustedRateOfChange::new(10, 20).unwrap();
+        assert_eq!(varoc.period(), 21);
+        let varoc = VolatilityAdjustedRateOfChange::new(30, 20).unwrap();
+        assert_eq!(varoc.period(), 31);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut varoc = VolatilityAdjustedRateOfChange::new(3, 2).unwrap();
+        assert_warm_up(&mut varoc, 1.0, 4);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(
+            &mut VolatilityAdjustedRateOfChange::default(),
+            [2.0, 3.0, 5.0],
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        VolatilityAdjustedRateOfChange::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let varoc = VolatilityAdjustedRateOfChange::new(12, 30).unwrap();
+        assert_eq!(format!("{}", varoc), "VAROC(12, 30)");
+    }
+}
@@ -0,0 +1,316 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::AverageTrueRange;
+use crate::strategy::Side;
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volatility Stop (VStop).
+///
+/// A trailing stop placed a multiple of the ATR away from the extreme close of the current
+/// regime. In a long regime the stop trails below the highest close and only moves up, in a
+/// short regime it trails above the lowest close and only moves down. When the close crosses
+/// the stop, the regime flips to the other side, and the stop is placed anew from that close.
+///
+/// Unlike the [Chandelier Exit](struct.ChandelierExit.html), which reports both levels on every
+/// bar, the Volatility Stop holds a single side until it is stopped out, so it can be used as
+/// an always-in-the-market system.
+///
+/// # Formula
+///
+/// Long regime: stop = max(stop, highest close - ATR * _multiplier_), flip when close < stop
+///
+/// Short regime: stop = min(stop, lowest close + ATR * _multiplier_), flip when close > stop
+///
+/// The first regime is long.
+///
+/// # Parameters
+///
+/// * _period_ - period of the ATR (integer greater than 0). Default is 20.
+/// * _multiplier_ - ATR factor (number greater than 0). Default is 2.0.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VolatilityStop;
+/// use ta::strategy::Side;
+/// use ta::Next;
+///
+/// let mut vstop = VolatilityStop::new(3, 1.0).unwrap();
+///
+/// assert_eq!(vstop.next(10.0).side, Side::Long);
+/// assert_eq!(vstop.next(12.0).stop, 11.0);
+/// // the close falls below the stop, so the regime flips
+/// let out = vstop.next(9.0);
+/// assert_eq!(out.side, Side::Short);
+/// assert!(out.flipped);
+/// assert_eq!(out.stop, 11.0);
+/// ```
+#[doc(alias = "VStop")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct VolatilityStop {
+    multiplier: f64,
+    atr: AverageTrueRange,
+    state: Option<StopState>,
+    output: Option<VolatilityStopOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+struct StopState {
+    side: Side,
+    extreme: f64,
+    stop: f64,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolatilityStopOutput {
+    /// Level of the active stop.
+    pub stop: f64,
+    /// Side of the active regime.
+    pub side: Side,
+    /// Whether the regime flipped at this bar.
+    pub flipped: bool,
+}
+
+impl VolatilityStop {
+    pub fn new(period: usize, multiplier: f64) -> Result<Self> {
+        if multiplier.is_nan() || multiplier <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "multiplier",
+                multiplier,
+                "must be greater than 0",
+            ));
+        }
+        Ok(Self {
+            multiplier,
+            atr: AverageTrueRange::new(period)?,
+            state: None,
+            output: None,
+        })
+    }
+
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    fn update(&mut self, close: f64, atr: f64) -> VolatilityStopOutput {
+        let distance = atr * self.multiplier;
+        let mut state = self.state.unwrap_or(StopState {
+            side: Side::Long,
+            extreme: close,
+            stop: close - distance,
+        });
+
+        let flipped = match state.side {
+            Side::Long => {
+                state.extreme = state.extreme.max(close);
+                state.stop = state.stop.max(state.extreme - distance);
+                close < state.stop
+            }
+            Side::Short => {
+                state.extreme = state.extreme.min(close);
+                state.stop = state.stop.min(state.extreme + distance);
+                close > state.stop
+            }
+        };
+
+        if flipped {
+            state = match state.side {
+                Side::Long => StopState {
+                    side: Side::Short,
+                    extreme: close,
+                    stop: close + distance,
+                },
+                Side::Short => StopState {
+                    side: Side::Long,
+                    extreme: close,
+                    stop: close - distance,
+                },
+            };
+        }
+        self.state = Some(state);
+
+        let output = VolatilityStopOutput {
+            stop: state.stop,
+            side: state.side,
+            flipped,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Period for VolatilityStop {
+    fn period(&self) -> usize {
+        self.atr.period()
+    }
+}
+
+impl IsReady for VolatilityStop {
+    fn bars_until_ready(&self) -> usize {
+        self.atr.bars_until_ready()
+    }
+}
+
+impl Next<f64> for VolatilityStop {
+    type Output = VolatilityStopOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let atr = self.atr.next(input);
+        self.update(input, atr)
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for VolatilityStop {
+    type Output = VolatilityStopOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let atr = self.atr.next(input);
+        self.update(input.close(), atr)
+    }
+}
+
+impl Current for VolatilityStop {
+    type Output = VolatilityStopOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for VolatilityStop {
+    fn reset(&mut self) {
+        self.atr.reset();
+        self.state = None;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for VolatilityStop {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for VolatilityStop {
+    fn default() -> Self {
+        Self::new(20, 2.0).unwrap()
+    }
+}
+
+impl fmt::Display for VolatilityStop {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VSTOP({}, {})", self.atr.period(), self.multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(VolatilityStop);
+
+    #[test]
+    fn test_new() {
+        assert!(VolatilityStop::new(0, 2.0).is_err());
+        assert!(VolatilityStop::new(20, 0.0).is_err());
+        assert!(VolatilityStop::new(20, f64::NAN).is_err());
+        assert!(VolatilityStop::new(1, 0.5).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut vstop = VolatilityStop::new(3, 1.5).unwrap();
+
+        let outputs: Vec<VolatilityStopOutput> =
+            [10.0, 11.0, 12.0, 11.5, 10.0, 9.0, 8.5, 9.0, 10.5, 11.0]
+                .iter()
+                .map(|&x| vstop.next(x))
+                .collect();
+
+        use Side::*;
+        let sides: Vec<Side> = outputs.iter().map(|o| o.side).collect();
+        assert_eq!(
+            sides,
+            vec![Long, Long, Long, Long, Short, Short, Short, Short, Long, Long]
+        );
+        let flips: Vec<usize> = (0..outputs.len()).filter(|&i| outputs[i].flipped).collect();
+        assert_eq!(flips, vec![4, 8]);
+
+        let stops: Vec<f64> = outputs.iter().map(|o| round(o.stop)).collect();
+        assert_eq!(
+            stops,
+            vec![10.0, 10.25, 10.875, 11.063, 11.594, 10.547, 9.648, 9.449, 8.9, 9.825]
+        );
+    }
+
+    #[test]
+    fn test_stop_holds() {
+        let mut vstop = VolatilityStop::new(2, 1.0).unwrap();
+
+        vstop.next(10.0);
+        let stop = vstop.next(14.0).stop;
+        // a pullback, which doesn't reach the stop, doesn't lower it
+        let out = vstop.next(13.0);
+        assert_eq!(out.side, Side::Long);
+        assert!(out.stop >= stop);
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut vstop = VolatilityStop::new(3, 1.0).unwrap();
+
+        // ATR of the first bar is its range
+        let out = vstop.next(&Bar::new().high(11.0).low(9.0).close(10.0));
+        assert_eq!(out.stop, 8.0);
+        assert_eq!(out.side, Side::Long);
+
+        let out = vstop.next(&Bar::new().high(8.0).low(6.0).close(7.0));
+        assert!(out.flipped);
+        assert_eq!(out.side, Side::Short);
+        assert_eq!(out.stop, 10.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vstop = VolatilityStop::new(3, 1.0).unwrap();
+
+        vstop.next(10.0);
+        vstop.next(12.0);
+        vstop.next(9.0);
+
+        vstop.reset();
+        let out = vstop.next(20.0);
+        assert_eq!(out.stop, 20.0);
+        assert_eq!(out.side, Side::Long);
+        assert!(!out.flipped);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(2).low(1).close(1.5);
+        assert_warm_up(&mut VolatilityStop::new(3, 2.0).unwrap(), &bar, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut VolatilityStop::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        VolatilityStop::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let vstop = VolatilityStop::default();
+        assert_eq!(format!("{}", vstop), "VSTOP(20, 2)");
+    }
+}
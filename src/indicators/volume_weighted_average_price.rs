@@ -1,7 +1,13 @@
-use std::fmt;
+use crate::errors::{Result, TaError};
+use crate::helpers::Accumulator;
+use crate::ring_buffer::RingBuffer;
+use crate::rolling_variance::RollingVariance;
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset, Volume};
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use crate::{Close, High, Low, Next, Reset, Volume};
 
 #[derive(Debug)]
 pub enum VolumeWeightedAveragePriceBands {
@@ -25,25 +31,39 @@ pub enum VolumeWeightedAveragePriceBands {
 /// - The standard deviation will be zero
 /// - Band calculations (VWAP ± offset * std_dev) will equal VWAP
 /// - Upper and lower bands will be identical to VWAP until second data point is added
+///
+/// The sums of the window are updated with every bar, adding the new bar and removing the one
+/// leaving the window, so `next` takes the same time for any window size.
+///
+/// # Trigger levels
+///
+/// [solve_for_price](#method.solve_for_price) and [solve_for_band](#method.solve_for_band)
+/// return the typical price of a next bar with a given volume, at which the VWAP would reach a
+/// target or the price would touch its own band, without changing the state.
 #[doc(alias = "VWAP")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct VolumeWeightedAveragePrice {
     window: usize,
-    price_volume_history: Vec<f64>,
-    volume_history: Vec<f64>,
+    price_volume_history: RingBuffer,
+    volume_history: RingBuffer,
+    sums: WindowSums,
     vwap: f64,
     std_dev: f64,
 }
 
 impl VolumeWeightedAveragePrice {
-    pub fn new(window: usize) -> Self {
-        Self {
-            window,
-            price_volume_history: Vec::with_capacity(window),
-            volume_history: Vec::with_capacity(window),
-            vwap: 0.0,
-            std_dev: 0.0,
+    pub fn new(window: usize) -> Result<Self> {
+        match window {
+            0 => Err(TaError::zero_period("window")),
+            _ => Ok(Self {
+                window,
+                price_volume_history: RingBuffer::new(window),
+                volume_history: RingBuffer::new(window),
+                sums: WindowSums::new(),
+                vwap: 0.0,
+                std_dev: 0.0,
+            }),
         }
     }
 
@@ -58,13 +78,164 @@ impl VolumeWeightedAveragePrice {
         }
     }
 
-    fn update_vwap(&mut self) {
-        let total_pv: f64 = self.price_volume_history.iter().sum();
-        let total_volume: f64 = self.volume_history.iter().sum();
+    /// Typical price of the next bar with `volume`, which would move the VWAP to `target`.
+    /// Returns `None`, when `volume` is not positive.
+    pub fn solve_for_price(&self, target: f64, volume: f64) -> Option<f64> {
+        if volume <= 0.0 {
+            return None;
+        }
+        let sums = self.retained();
+        let total_pv = sums.price_volume.value();
+        let total_volume = sums.volume.value();
+        Some((target * (total_volume + volume) - total_pv) / volume)
+    }
 
-        if total_volume > 0.0 {
-            self.vwap = total_pv / total_volume;
+    /// Typical price of the next bar with `volume`, which would be equal to the band `offset`
+    /// standard deviations away from the VWAP in the given direction after the bar.
+    ///
+    /// The band moves with the price, so it is searched numerically: starting from the current
+    /// VWAP the nearest such price in the direction of the band is returned. Returns `None`,
+    /// when `volume` is not positive, when there is no previous bar, or when the price can not
+    /// catch up with the band.
+    pub fn solve_for_band(
+        &self,
+        offset: f64,
+        band_direction: VolumeWeightedAveragePriceBands,
+        volume: f64,
+    ) -> Option<f64> {
+        if volume <= 0.0 || self.volume_history.is_empty() {
+            return None;
         }
+        let sign = match band_direction {
+            VolumeWeightedAveragePriceBands::Up => 1.0,
+            VolumeWeightedAveragePriceBands::Down => -1.0,
+        };
+        // distance of the price beyond the band, negative until the band is reached
+        let beyond = |price: f64| {
+            let (vwap, std_dev) = self.project(price, volume);
+            sign * (price - vwap) - offset * std_dev
+        };
+
+        let start = self.vwap;
+        if beyond(start) >= 0.0 {
+            return Some(start);
+        }
+        let mut step = start.abs().max(1.0) * 0.01;
+        let mut near = start;
+        let mut far = start + sign * step;
+        let mut found = false;
+        for _ in 0..64 {
+            if beyond(far) >= 0.0 {
+                found = true;
+                break;
+            }
+            near = far;
+            step *= 2.0;
+            far = start + sign * step;
+        }
+        if !found {
+            return None;
+        }
+        for _ in 0..100 {
+            let mid = (near + far) / 2.0;
+            if beyond(mid) >= 0.0 {
+                far = mid;
+            } else {
+                near = mid;
+            }
+        }
+        Some(far)
+    }
+
+    /// Sums of the bars staying in the window, when the next bar is added.
+    fn retained(&self) -> WindowSums {
+        let mut sums = self.sums;
+        if self.volume_history.is_full() {
+            if let (Some(price_volume), Some(volume)) = (
+                self.price_volume_history.oldest(),
+                self.volume_history.oldest(),
+            ) {
+                sums.update(price_volume, volume, -1.0);
+            }
+        }
+        sums
+    }
+
+    /// VWAP and standard deviation after a bar with the given typical price and volume.
+    fn project(&self, typical_price: f64, volume: f64) -> (f64, f64) {
+        let mut sums = self.retained();
+        sums.update(typical_price * volume, volume, 1.0);
+        let vwap = sums.vwap().unwrap_or(self.vwap);
+        let std_dev = sums.std_dev(vwap).unwrap_or(self.std_dev);
+        (vwap, std_dev)
+    }
+}
+
+/// Running sums of the bars in the window of a VWAP, shared with the
+/// [fixed window](fixed/struct.VolumeWeightedAveragePrice.html) variant.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WindowSums {
+    price_volume: Accumulator,
+    volume: Accumulator,
+    /// Mean and deviations of the typical prices, bars without volume have no price.
+    prices: RollingVariance,
+}
+
+impl WindowSums {
+    pub(crate) fn new() -> Self {
+        // compensated, so removing the bars leaving the window does not drift
+        Self {
+            price_volume: Accumulator::new(true),
+            volume: Accumulator::new(true),
+            prices: RollingVariance::new(),
+        }
+    }
+
+    /// Adds a bar with `sign` 1, removes it with `sign` -1.
+    pub(crate) fn update(&mut self, price_volume: f64, volume: f64, sign: f64) {
+        self.price_volume.add(sign * price_volume);
+        self.volume.add(sign * volume);
+        if volume > 0.0 {
+            let price = price_volume / volume;
+            if sign > 0.0 {
+                self.prices.add(price);
+            } else {
+                self.prices.remove(price);
+            }
+        }
+    }
+
+    pub(crate) fn vwap(&self) -> Option<f64> {
+        let volume = self.volume.value();
+        if self.prices.count() > 0 && volume > 0.0 {
+            Some(self.price_volume.value() / volume)
+        } else {
+            None
+        }
+    }
+
+    /// Standard deviation of the prices around `mean`, `None` with less than 2 prices.
+    pub(crate) fn std_dev(&self, mean: f64) -> Option<f64> {
+        if self.prices.count() < 2 {
+            return None;
+        }
+        let count = self.prices.count() as f64;
+        // the deviations from the mean of the prices, shifted to `mean`
+        let shift = self.prices.mean() - mean;
+        Some(((self.prices.m2() + count * shift * shift) / count).sqrt())
+    }
+}
+
+impl Period for VolumeWeightedAveragePrice {
+    fn period(&self) -> usize {
+        self.window
+    }
+}
+
+impl IsReady for VolumeWeightedAveragePrice {
+    fn bars_until_ready(&self) -> usize {
+        self.window - self.volume_history.len()
     }
 }
 
@@ -75,66 +246,78 @@ impl<T: High + Low + Close + Volume> Next<&T> for VolumeWeightedAveragePrice {
         let typical_price = (input.high() + input.low() + input.close()) / 3.0;
         let price_volume = typical_price * input.volume();
 
-        self.price_volume_history.push(price_volume);
-        self.volume_history.push(input.volume());
-
-        if self.price_volume_history.len() > self.window {
-            self.price_volume_history.remove(0);
-            self.volume_history.remove(0);
+        let evicted = (
+            self.price_volume_history.push(price_volume),
+            self.volume_history.push(input.volume()),
+        );
+        if let (Some(old_price_volume), Some(old_volume)) = evicted {
+            self.sums.update(old_price_volume, old_volume, -1.0);
         }
+        self.sums.update(price_volume, input.volume(), 1.0);
 
-        self.update_vwap();
-
-        // Calculate standard deviation
-        if self.volume_history.len() >= 2 {
-            let mean = self.vwap;
-            let variance: f64 = self.price_volume_history.iter()
-                .zip(&self.volume_history)
-                .map(|(&pv, &v)| {
-                    let x = pv / v;
-                    (x - mean).powi(2)
-                })
-                .sum::<f64>() / (self.volume_history.len() as f64);
-            self.std_dev = variance.sqrt();
+        if let Some(vwap) = self.sums.vwap() {
+            self.vwap = vwap;
+        }
+        if let Some(std_dev) = self.sums.std_dev(self.vwap) {
+            self.std_dev = std_dev;
         }
 
         self.vwap
     }
 }
 
+impl Current for VolumeWeightedAveragePrice {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        if self.volume_history.is_empty() {
+            None
+        } else {
+            Some(self.vwap)
+        }
+    }
+}
+
 impl Reset for VolumeWeightedAveragePrice {
     fn reset(&mut self) {
         self.price_volume_history.clear();
         self.volume_history.clear();
+        self.sums = WindowSums::new();
         self.vwap = 0.0;
         self.std_dev = 0.0;
     }
 }
 
+impl MemoryFootprint for VolumeWeightedAveragePrice {
+    fn heap_size(&self) -> usize {
+        self.price_volume_history.heap_size() + self.volume_history.heap_size()
+    }
+}
+
 impl Default for VolumeWeightedAveragePrice {
     fn default() -> Self {
-        Self::new(14)
+        Self::new(14).unwrap()
     }
 }
 
-
 impl fmt::Display for VolumeWeightedAveragePrice {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "VWAP({})", self.window)
     }
 }
 
-
 #[cfg(test)]
 mod tests {
-    use crate::DataItem;
     use super::*;
     use crate::test_helper::*;
+    use crate::DataItem;
 
     test_hlcv_indicator!(VolumeWeightedAveragePrice);
     #[test]
     fn test_new() {
-        let vwap = VolumeWeightedAveragePrice::new(14);
+        assert!(VolumeWeightedAveragePrice::new(0).is_err());
+
+        let vwap = VolumeWeightedAveragePrice::new(14).unwrap();
         assert_eq!(vwap.window, 14);
         assert_eq!(vwap.vwap, 0.0);
         assert_eq!(vwap.std_dev, 0.0);
@@ -144,7 +327,7 @@ mod tests {
 
     #[test]
     fn test_next() {
-        let mut vwap = VolumeWeightedAveragePrice::new(3);
+        let mut vwap = VolumeWeightedAveragePrice::new(3).unwrap();
 
         let bar1 = DataItem::builder()
             .open(8.0)
@@ -190,7 +373,7 @@ mod tests {
 
     #[test]
     fn test_bands() {
-        let mut vwap = VolumeWeightedAveragePrice::new(3);
+        let mut vwap = VolumeWeightedAveragePrice::new(3).unwrap();
 
         // First data point
         let bar1 = DataItem::builder()
@@ -222,6 +405,117 @@ mod tests {
         assert!(lower_band < vwap.vwap());
     }
 
+    #[test]
+    fn test_zero_volume() {
+        let mut vwap = VolumeWeightedAveragePrice::new(3).unwrap();
+        let bar = |close: f64, volume: f64| {
+            Bar::new()
+                .high(close)
+                .low(close)
+                .close(close)
+                .volume(volume)
+        };
+
+        assert_eq!(vwap.next(&bar(10.0, 0.0)), 0.0);
+        assert_eq!(vwap.next(&bar(10.0, 100.0)), 10.0);
+        assert_eq!(vwap.next(&bar(12.0, 100.0)), 11.0);
+        assert_eq!(vwap.next(&bar(13.0, 0.0)), 11.0);
+
+        // the bars without volume are not part of the deviation
+        assert_eq!(vwap.std_dev(1.0, VolumeWeightedAveragePriceBands::Up), 12.0);
+    }
+
+    #[test]
+    fn test_solve_for_price() {
+        let mut vwap = VolumeWeightedAveragePrice::new(2).unwrap();
+
+        let bar = |price: f64, volume: f64| {
+            DataItem::builder()
+                .open(price)
+                .high(price)
+                .low(price)
+                .close(price)
+                .volume(volume)
+                .build()
+                .unwrap()
+        };
+        assert_eq!(vwap.solve_for_price(10.0, 0.0), None);
+
+        vwap.next(&bar(9.0, 100.0));
+        vwap.next(&bar(11.0, 100.0));
+        // the first bar drops out of the window
+        let price = vwap.solve_for_price(12.0, 300.0).unwrap();
+        assert!((price - 12.333).abs() < 0.001);
+        assert!((vwap.next(&bar(price, 300.0)) - 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_solve_for_band() {
+        let mut vwap = VolumeWeightedAveragePrice::new(5).unwrap();
+
+        let bar = |price: f64, volume: f64| {
+            DataItem::builder()
+                .open(price)
+                .high(price)
+                .low(price)
+                .close(price)
+                .volume(volume)
+                .build()
+                .unwrap()
+        };
+        assert_eq!(
+            vwap.solve_for_band(1.0, VolumeWeightedAveragePriceBands::Up, 100.0),
+            None
+        );
+
+        for &price in &[10.0, 11.0, 9.5, 10.5] {
+            vwap.next(&bar(price, 100.0));
+        }
+
+        let up = vwap
+            .solve_for_band(1.0, VolumeWeightedAveragePriceBands::Up, 100.0)
+            .unwrap();
+        let down = vwap
+            .solve_for_band(1.0, VolumeWeightedAveragePriceBands::Down, 100.0)
+            .unwrap();
+        assert!(up > vwap.vwap() && down < vwap.vwap());
+
+        let mut touched = vwap.clone();
+        touched.next(&bar(up, 100.0));
+        let band = touched.std_dev(1.0, VolumeWeightedAveragePriceBands::Up);
+        assert!((band - up).abs() < 1e-6);
+
+        vwap.next(&bar(down, 100.0));
+        let band = vwap.std_dev(1.0, VolumeWeightedAveragePriceBands::Down);
+        assert!((band - down).abs() < 1e-6);
+
+        // a single bar can not get 3 standard deviations away from a window of 5
+        assert_eq!(
+            vwap.solve_for_band(3.0, VolumeWeightedAveragePriceBands::Up, 100.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(VolumeWeightedAveragePrice::new(20).unwrap().period(), 20);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().high(2).low(1).close(1.5).volume(100.0);
+        assert_warm_up(&mut VolumeWeightedAveragePrice::new(3).unwrap(), &bar, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut VolumeWeightedAveragePrice::default(), &bars);
+    }
+
     #[test]
     fn test_default() {
         let vwap = VolumeWeightedAveragePrice::default();
@@ -230,13 +524,13 @@ mod tests {
 
     #[test]
     fn test_display() {
-        let vwap = VolumeWeightedAveragePrice::new(7);
+        let vwap = VolumeWeightedAveragePrice::new(7).unwrap();
         assert_eq!(format!("{}", vwap), "VWAP(7)");
     }
 
     #[test]
     fn test_window_size() {
-        let mut vwap = VolumeWeightedAveragePrice::new(2);
+        let mut vwap = VolumeWeightedAveragePrice::new(2).unwrap();
 
         let bar1 = DataItem::builder()
             .open(8.0)
@@ -272,4 +566,47 @@ mod tests {
         assert_eq!(vwap.price_volume_history.len(), 2);
         assert_eq!(vwap.volume_history.len(), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_running_sums() {
+        // the running sums give the values of the window summed up from scratch
+        let mut vwap = VolumeWeightedAveragePrice::new(5).unwrap();
+        let bars: Vec<(f64, f64)> = (0..2000)
+            .map(|i| {
+                let price = 20_000.0 + (i as f64 * 0.3).sin() * 50.0;
+                let volume = if i % 7 == 0 {
+                    0.0
+                } else {
+                    1.0 + (i % 4) as f64
+                };
+                (price, volume)
+            })
+            .collect();
+
+        for (i, &(price, volume)) in bars.iter().enumerate() {
+            let bar = Bar::new()
+                .high(price)
+                .low(price)
+                .close(price)
+                .volume(volume);
+            let output = vwap.next(&bar);
+
+            let window = &bars[(i + 1).saturating_sub(5)..=i];
+            let total_volume: f64 = window.iter().map(|&(_, v)| v).sum();
+            let total_pv: f64 = window.iter().map(|&(p, v)| p * v).sum();
+            if total_volume > 0.0 {
+                assert!((output - total_pv / total_volume).abs() < 1e-8);
+            }
+            let prices: Vec<f64> = window
+                .iter()
+                .filter(|&&(_, v)| v > 0.0)
+                .map(|&(p, _)| p)
+                .collect();
+            if prices.len() >= 2 {
+                let variance =
+                    prices.iter().map(|p| (p - output).powi(2)).sum::<f64>() / prices.len() as f64;
+                assert!((vwap.std_dev - variance.sqrt()).abs() < 1e-5);
+            }
+        }
+    }
+}
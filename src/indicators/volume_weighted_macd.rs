@@ -0,0 +1,264 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{ExponentialMovingAverage as Ema, VolumeWeightedMovingAverage as Vwma};
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volume weighted moving average convergence divergence (VW-MACD).
+///
+/// A variant of [MACD](struct.MovingAverageConvergenceDivergence.html) where the fast and slow
+/// averages are [volume weighted moving averages](struct.VolumeWeightedMovingAverage.html)
+/// instead of exponential moving averages, so periods with heavy volume pull the averages
+/// stronger than quiet ones.
+///
+/// # Formula
+///
+/// * _VW-MACD_ = VWMA(_fast_period_) - VWMA(_slow_period_)
+/// * _signal_ = EMA(_signal_period_) of VW-MACD
+/// * _histogram_ = VW-MACD - _signal_
+///
+/// # Parameters
+///
+/// * _fast_period_ - period for the fast VWMA. Default is 12.
+/// * _slow_period_ - period for the slow VWMA, greater than the fast period. Default is 26.
+/// * _signal_period_ - period for the signal EMA. Default is 9.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VolumeWeightedMacd;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(close: f64, volume: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(close).high(close).low(close).close(close).volume(volume)
+///         .build().unwrap()
+/// }
+///
+/// let mut macd = VolumeWeightedMacd::new(2, 3, 2).unwrap();
+///
+/// let out = macd.next(&bar(10.0, 100.0));
+/// assert_eq!((out.macd, out.signal, out.histogram), (0.0, 0.0, 0.0));
+///
+/// let out = macd.next(&bar(13.0, 200.0));
+/// assert_eq!(out.macd, 0.0);
+///
+/// // fast = (13 * 200 + 16 * 200) / 400 = 14.5
+/// // slow = (10 * 100 + 13 * 200 + 16 * 200) / 500 = 13.6
+/// let out = macd.next(&bar(16.0, 200.0));
+/// assert_eq!((out.macd * 10.0).round() / 10.0, 0.9);
+/// ```
+#[doc(alias = "VWMACD")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct VolumeWeightedMacd {
+    fast_vwma: Vwma,
+    slow_vwma: Vwma,
+    signal_ema: Ema,
+    count: usize,
+    output: Option<VolumeWeightedMacdOutput>,
+}
+
+impl VolumeWeightedMacd {
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Result<Self> {
+        if fast_period >= slow_period {
+            return Err(TaError::invalid_period(
+                "fast_period",
+                fast_period,
+                "must be less than slow_period",
+            ));
+        }
+        Ok(Self {
+            fast_vwma: Vwma::new(fast_period)?,
+            slow_vwma: Vwma::new(slow_period)?,
+            signal_ema: Ema::new(signal_period)?,
+            count: 0,
+            output: None,
+        })
+    }
+}
+
+impl Period for VolumeWeightedMacd {
+    fn period(&self) -> usize {
+        self.fast_vwma.period().max(self.slow_vwma.period()) + self.signal_ema.period() - 1
+    }
+}
+
+impl IsReady for VolumeWeightedMacd {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VolumeWeightedMacdOutput {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+impl From<VolumeWeightedMacdOutput> for (f64, f64, f64) {
+    fn from(mo: VolumeWeightedMacdOutput) -> Self {
+        (mo.macd, mo.signal, mo.histogram)
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for VolumeWeightedMacd {
+    type Output = VolumeWeightedMacdOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        if self.count < self.period() {
+            self.count += 1;
+        }
+
+        let fast_val = self.fast_vwma.next(input);
+        let slow_val = self.slow_vwma.next(input);
+
+        let macd = fast_val - slow_val;
+        let signal = self.signal_ema.next(macd);
+        let histogram = macd - signal;
+
+        let output = VolumeWeightedMacdOutput {
+            macd,
+            signal,
+            histogram,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for VolumeWeightedMacd {
+    type Output = VolumeWeightedMacdOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for VolumeWeightedMacd {
+    fn reset(&mut self) {
+        self.fast_vwma.reset();
+        self.slow_vwma.reset();
+        self.signal_ema.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for VolumeWeightedMacd {
+    fn heap_size(&self) -> usize {
+        self.fast_vwma.heap_size() + self.slow_vwma.heap_size()
+    }
+}
+
+impl Default for VolumeWeightedMacd {
+    fn default() -> Self {
+        Self::new(12, 26, 9).unwrap()
+    }
+}
+
+impl fmt::Display for VolumeWeightedMacd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "VWMACD({}, {}, {})",
+            self.fast_vwma.period(),
+            self.slow_vwma.period(),
+            self.signal_ema.period()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_hlcv_indicator!(VolumeWeightedMacd);
+
+    fn bar(close: f64, volume: f64) -> Bar {
+        Bar::new().close(close).volume(volume)
+    }
+
+    fn round(nums: (f64, f64, f64)) -> (f64, f64, f64) {
+        let n0 = (nums.0 * 100.0).round() / 100.0;
+        let n1 = (nums.1 * 100.0).round() / 100.0;
+        let n2 = (nums.2 * 100.0).round() / 100.0;
+        (n0, n1, n2)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeWeightedMacd::new(0, 1, 1).is_err());
+        assert!(VolumeWeightedMacd::new(1, 0, 1).is_err());
+        assert!(VolumeWeightedMacd::new(1, 1, 0).is_err());
+        assert!(VolumeWeightedMacd::new(2, 2, 1).is_err());
+        assert!(VolumeWeightedMacd::new(3, 2, 1).is_err());
+        assert!(VolumeWeightedMacd::new(1, 2, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut macd = VolumeWeightedMacd::new(2, 3, 2).unwrap();
+
+        assert_eq!(round(macd.next(&bar(10.0, 100.0)).into()), (0.0, 0.0, 0.0));
+        assert_eq!(round(macd.next(&bar(13.0, 200.0)).into()), (0.0, 0.0, 0.0));
+        // fast = 14.5, slow = 13.6, macd = 0.9, signal = 0.6, histogram = 0.3
+        assert_eq!(round(macd.next(&bar(16.0, 200.0)).into()), (0.9, 0.6, 0.3));
+        // fast = (16 * 200 + 10 * 800) / 1000 = 11.2
+        // slow = (13 * 200 + 16 * 200 + 10 * 800) / 1200 = 11.5
+        // macd = -0.3, signal = 0.6 + 2/3 * (-0.3 - 0.6) = 0.0
+        assert_eq!(
+            round(macd.next(&bar(10.0, 800.0)).into()),
+            (-0.3, 0.0, -0.3)
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut macd = VolumeWeightedMacd::new(2, 3, 2).unwrap();
+
+        macd.next(&bar(10.0, 100.0));
+        macd.next(&bar(13.0, 200.0));
+        macd.next(&bar(16.0, 200.0));
+
+        macd.reset();
+        assert_eq!(round(macd.next(&bar(10.0, 100.0)).into()), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_period() {
+        let vw_macd = VolumeWeightedMacd::new(12, 26, 9).unwrap();
+        assert_eq!(vw_macd.period(), 34);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().close(1.0).volume(100.0);
+        assert_warm_up(&mut VolumeWeightedMacd::new(3, 5, 2).unwrap(), &bar, 6);
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut VolumeWeightedMacd::default(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        VolumeWeightedMacd::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let indicator = VolumeWeightedMacd::new(13, 30, 10).unwrap();
+        assert_eq!(format!("{}", indicator), "VWMACD(13, 30, 10)");
+    }
+}
@@ -0,0 +1,230 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::helpers::fmt_params;
+use crate::registry::Params;
+use crate::ring_buffer::RingBuffer;
+use crate::{
+    Close, Current, IsReady, MemoryFootprint, Next, ParamValue, Parameters, Period, Reset, Volume,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volume weighted moving average (VWMA).
+///
+/// Average of the close prices over a window, where every price is weighted by the volume of
+/// its period.
+///
+/// # Formula
+///
+/// VWMA = Σ(close<sub>i</sub> * volume<sub>i</sub>) / Σ(volume<sub>i</sub>)
+///
+/// If the window has no volume at all, the latest close price is returned.
+///
+/// # Parameters
+///
+/// * _period_ - number of periods (integer greater than 0). Default is 20.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VolumeWeightedMovingAverage;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(close: f64, volume: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(close).high(close).low(close).close(close).volume(volume)
+///         .build().unwrap()
+/// }
+///
+/// let mut vwma = VolumeWeightedMovingAverage::new(2).unwrap();
+/// assert_eq!(vwma.next(&bar(10.0, 100.0)), 10.0);
+/// assert_eq!(vwma.next(&bar(13.0, 200.0)), 12.0);
+/// assert_eq!(vwma.next(&bar(16.0, 200.0)), 14.5);
+/// ```
+#[doc(alias = "VWMA")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct VolumeWeightedMovingAverage {
+    period: usize,
+    sum_price_volume: f64,
+    sum_volume: f64,
+    /// Pairs of price times volume and volume.
+    window: RingBuffer<(f64, f64)>,
+    output: Option<f64>,
+}
+
+impl VolumeWeightedMovingAverage {
+    pub fn new(period: usize) -> Result<Self> {
+        match period {
+            0 => Err(TaError::zero_period("period")),
+            _ => Ok(Self {
+                period,
+                sum_price_volume: 0.0,
+                sum_volume: 0.0,
+                window: RingBuffer::new(period),
+                output: None,
+            }),
+        }
+    }
+}
+
+impl Period for VolumeWeightedMovingAverage {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for VolumeWeightedMovingAverage {
+    fn bars_until_ready(&self) -> usize {
+        self.period - self.window.len()
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for VolumeWeightedMovingAverage {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let close = input.close();
+        let volume = input.volume();
+        let price_volume = close * volume;
+
+        let (old_price_volume, old_volume) = self
+            .window
+            .push((price_volume, volume))
+            .unwrap_or((0.0, 0.0));
+        self.sum_price_volume += price_volume - old_price_volume;
+        self.sum_volume += volume - old_volume;
+
+        let output = if self.sum_volume > 0.0 {
+            self.sum_price_volume / self.sum_volume
+        } else {
+            close
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for VolumeWeightedMovingAverage {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for VolumeWeightedMovingAverage {
+    fn reset(&mut self) {
+        self.sum_price_volume = 0.0;
+        self.sum_volume = 0.0;
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for VolumeWeightedMovingAverage {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl Parameters for VolumeWeightedMovingAverage {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::new(params.period("period", 20)?)
+    }
+}
+
+impl Default for VolumeWeightedMovingAverage {
+    fn default() -> Self {
+        Self::new(20).unwrap()
+    }
+}
+
+impl fmt::Display for VolumeWeightedMovingAverage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_params(f, "VWMA", &self.params())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_hlcv_indicator!(VolumeWeightedMovingAverage);
+
+    fn bar(close: f64, volume: f64) -> Bar {
+        Bar::new().close(close).volume(volume)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeWeightedMovingAverage::new(0).is_err());
+        assert!(VolumeWeightedMovingAverage::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut vwma = VolumeWeightedMovingAverage::new(3).unwrap();
+
+        assert_eq!(vwma.next(&bar(10.0, 100.0)), 10.0);
+        assert_eq!(vwma.next(&bar(20.0, 300.0)), 17.5);
+        assert_eq!(vwma.next(&bar(30.0, 100.0)), 20.0);
+        // window is full: (20 * 300 + 30 * 100 + 10 * 600) / 1000
+        assert_eq!(vwma.next(&bar(10.0, 600.0)), 15.0);
+    }
+
+    #[test]
+    fn test_zero_volume() {
+        let mut vwma = VolumeWeightedMovingAverage::new(2).unwrap();
+
+        assert_eq!(vwma.next(&bar(10.0, 0.0)), 10.0);
+        assert_eq!(vwma.next(&bar(12.0, 0.0)), 12.0);
+        assert_eq!(vwma.next(&bar(14.0, 100.0)), 14.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vwma = VolumeWeightedMovingAverage::new(3).unwrap();
+
+        vwma.next(&bar(10.0, 100.0));
+        vwma.next(&bar(20.0, 300.0));
+
+        vwma.reset();
+        assert_eq!(vwma.next(&bar(5.0, 100.0)), 5.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let bar = Bar::new().close(1.0).volume(100.0);
+        assert_warm_up(&mut VolumeWeightedMovingAverage::new(3).unwrap(), &bar, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut VolumeWeightedMovingAverage::default(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        VolumeWeightedMovingAverage::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let vwma = VolumeWeightedMovingAverage::new(5).unwrap();
+        assert_eq!(format!("{}", vwma), "VWMA(5)");
+    }
+}
@@ -0,0 +1,231 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::ExponentialMovingAverage as Ema;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Volume Zone Oscillator (VZO).
+///
+/// Developed by Walid Khalil and David Steckler. The volume of a bar counts as positive, when
+/// the close is above the previous close, and as negative, when it is below. The VZO is the
+/// smoothed signed volume as a percentage of the smoothed total volume, so unlike the
+/// [OBV](struct.OnBalanceVolume.html) it is bounded: it ranges from -100 to +100. Readings
+/// above +40 are usually taken as overbought, below -40 as oversold.
+///
+/// # Formula
+///
+/// R = sign(close - previous close) * volume
+///
+/// VZO = 100 * EMA(R, _period_) / EMA(volume, _period_)
+///
+/// The first bar has no previous close, so R is 0. VZO is 0 while no volume was traded.
+///
+/// # Parameters
+///
+/// * _period_ - period of the EMAs (integer greater than 0). Default is 14.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::VolumeZoneOscillator;
+/// use ta::{DataItem, Next};
+///
+/// fn bar(close: f64, volume: f64) -> DataItem {
+///     DataItem::builder()
+///         .open(close).high(close).low(close).close(close).volume(volume)
+///         .build().unwrap()
+/// }
+///
+/// let mut vzo = VolumeZoneOscillator::new(2).unwrap();
+/// assert_eq!(vzo.next(&bar(10.0, 100.0)), 0.0);
+/// assert_eq!(vzo.next(&bar(11.0, 200.0)), 80.0);
+/// ```
+#[doc(alias = "VZO")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct VolumeZoneOscillator {
+    period: usize,
+    prev_close: Option<f64>,
+    signed_volume: Ema,
+    volume: Ema,
+    output: Option<f64>,
+}
+
+impl VolumeZoneOscillator {
+    pub fn new(period: usize) -> Result<Self> {
+        if period == 0 {
+            return Err(TaError::zero_period("period"));
+        }
+        Ok(Self {
+            period,
+            prev_close: None,
+            signed_volume: Ema::new(period)?,
+            volume: Ema::new(period)?,
+            output: None,
+        })
+    }
+}
+
+impl Period for VolumeZoneOscillator {
+    fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl IsReady for VolumeZoneOscillator {
+    fn bars_until_ready(&self) -> usize {
+        self.volume.bars_until_ready()
+    }
+}
+
+impl<T: Close + Volume> Next<&T> for VolumeZoneOscillator {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let close = input.close();
+        let signed_volume = match self.prev_close {
+            Some(prev_close) if close > prev_close => input.volume(),
+            Some(prev_close) if close < prev_close => -input.volume(),
+            _ => 0.0,
+        };
+        self.prev_close = Some(close);
+
+        let signed_volume = self.signed_volume.next(signed_volume);
+        let volume = self.volume.next(input.volume());
+        let output = if volume > 0.0 {
+            100.0 * signed_volume / volume
+        } else {
+            0.0
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for VolumeZoneOscillator {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for VolumeZoneOscillator {
+    fn reset(&mut self) {
+        self.prev_close = None;
+        self.signed_volume.reset();
+        self.volume.reset();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for VolumeZoneOscillator {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for VolumeZoneOscillator {
+    fn default() -> Self {
+        Self::new(14).unwrap()
+    }
+}
+
+impl fmt::Display for VolumeZoneOscillator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "VZO({})", self.period)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_hlcv_indicator!(VolumeZoneOscillator);
+
+    fn bar(close: f64, volume: f64) -> Bar {
+        Bar::new().close(close).volume(volume)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(VolumeZoneOscillator::new(0).is_err());
+        assert!(VolumeZoneOscillator::new(1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut vzo = VolumeZoneOscillator::new(2).unwrap();
+
+        assert_eq!(vzo.next(&bar(10.0, 100.0)), 0.0);
+        assert_eq!(round(vzo.next(&bar(11.0, 200.0))), 80.0);
+        assert_eq!(round(vzo.next(&bar(10.5, 100.0))), -18.182);
+        // an unchanged close adds volume, but no direction
+        assert_eq!(round(vzo.next(&bar(10.5, 100.0))), -6.897);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut vzo = VolumeZoneOscillator::new(3).unwrap();
+
+        vzo.next(&bar(10.0, 100.0));
+        for i in 1..50 {
+            let output = vzo.next(&bar(10.0 + i as f64, 100.0 + i as f64));
+            assert!(output > 0.0 && output <= 100.0);
+        }
+        for i in 1..50 {
+            let output = vzo.next(&bar(60.0 - i as f64, 100.0));
+            assert!((-100.0..=100.0).contains(&output));
+        }
+        assert!(vzo.current().unwrap() < -99.0);
+    }
+
+    #[test]
+    fn test_no_volume() {
+        let mut vzo = VolumeZoneOscillator::new(2).unwrap();
+
+        assert_eq!(vzo.next(&bar(10.0, 0.0)), 0.0);
+        assert_eq!(vzo.next(&bar(12.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut vzo = VolumeZoneOscillator::new(2).unwrap();
+
+        vzo.next(&bar(10.0, 100.0));
+        vzo.next(&bar(11.0, 200.0));
+
+        vzo.reset();
+        // the previous close is forgotten
+        assert_eq!(vzo.next(&bar(12.0, 100.0)), 0.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(
+            &mut VolumeZoneOscillator::new(3).unwrap(),
+            &bar(10.0, 100.0),
+            3,
+        );
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [bar(10.0, 100.0), bar(11.0, 200.0)];
+        assert_current(&mut VolumeZoneOscillator::default(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        VolumeZoneOscillator::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let vzo = VolumeZoneOscillator::default();
+        assert_eq!(format!("{}", vzo), "VZO(14)");
+    }
+}
@@ -0,0 +1,329 @@
+use core::fmt;
+
+use crate::errors::Result;
+use crate::indicators::{ExponentialMovingAverage as Ema, SimpleMovingAverage as Sma};
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// WaveTrend oscillator (WT).
+///
+/// An oscillator popularized by LazyBear, which measures the distance of the typical price
+/// from its average in units of the average distance, similarly to the
+/// [CCI](struct.CommodityChannelIndex.html), and smooths it with an EMA. The result (WT1) is
+/// accompanied by its SMA (WT2) as a signal line. WT1 crossing above WT2 is a bullish signal,
+/// crossing below is a bearish one. Levels of ±60 are commonly used as overbought and
+/// oversold.
+///
+/// # Formula
+///
+/// ESA = EMA(typical price, _channel_period_)
+///
+/// D = EMA(|typical price - ESA|, _channel_period_)
+///
+/// CI = (typical price - ESA) / (0.015 * D)
+///
+/// WT1 = EMA(CI, _average_period_)
+///
+/// WT2 = SMA(WT1, _signal_period_)
+///
+/// Where typical price is _(high + low + close) / 3_. CI is 0, when D is 0.
+///
+/// # Parameters
+///
+/// * _channel_period_ - period of ESA and D (integer greater than 0). Default is 10.
+/// * _average_period_ - period of WT1 (integer greater than 0). Default is 21.
+/// * _signal_period_ - period of WT2 (integer greater than 0). Default is 4.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{WaveTrend, WaveTrendCross};
+/// use ta::Next;
+///
+/// let mut wt = WaveTrend::new(3, 3, 2).unwrap();
+///
+/// wt.next(10.0);
+/// wt.next(9.0);
+/// let out = wt.next(11.0);
+/// assert_eq!(out.cross, Some(WaveTrendCross::Bullish));
+/// assert!(out.wt1 > out.wt2);
+/// ```
+///
+/// # Links
+///
+/// * [WaveTrend Oscillator by LazyBear, TradingView](https://www.tradingview.com/script/2KE8wTuF-Indicator-WaveTrend-Oscillator-WT/)
+///
+#[doc(alias = "WT")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct WaveTrend {
+    channel_period: usize,
+    average_period: usize,
+    signal_period: usize,
+    esa: Ema,
+    d: Ema,
+    wt1: Ema,
+    wt2: Sma,
+    prev_diff: Option<f64>,
+    count: usize,
+    output: Option<WaveTrendOutput>,
+}
+
+/// Direction, in which WT1 crossed WT2.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaveTrendCross {
+    /// WT1 crossed above WT2.
+    Bullish,
+    /// WT1 crossed below WT2.
+    Bearish,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaveTrendOutput {
+    pub wt1: f64,
+    pub wt2: f64,
+    pub cross: Option<WaveTrendCross>,
+}
+
+impl WaveTrend {
+    pub fn new(channel_period: usize, average_period: usize, signal_period: usize) -> Result<Self> {
+        Ok(Self {
+            channel_period,
+            average_period,
+            signal_period,
+            esa: Ema::new(channel_period)?,
+            d: Ema::new(channel_period)?,
+            wt1: Ema::new(average_period)?,
+            wt2: Sma::new(signal_period)?,
+            prev_diff: None,
+            count: 0,
+            output: None,
+        })
+    }
+
+    pub fn channel_period(&self) -> usize {
+        self.channel_period
+    }
+
+    pub fn average_period(&self) -> usize {
+        self.average_period
+    }
+
+    pub fn signal_period(&self) -> usize {
+        self.signal_period
+    }
+}
+
+impl Period for WaveTrend {
+    fn period(&self) -> usize {
+        2 * self.channel_period + self.average_period + self.signal_period - 3
+    }
+}
+
+impl IsReady for WaveTrend {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
+}
+
+impl Next<f64> for WaveTrend {
+    type Output = WaveTrendOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.count < self.period() {
+            self.count += 1;
+        }
+
+        let esa = self.esa.next(input);
+        let d = self.d.next((input - esa).abs());
+        let ci = if d != 0.0 {
+            (input - esa) / (0.015 * d)
+        } else {
+            0.0
+        };
+
+        let wt1 = self.wt1.next(ci);
+        let wt2 = self.wt2.next(wt1);
+
+        let diff = wt1 - wt2;
+        let cross = match self.prev_diff {
+            Some(prev) if prev < 0.0 && diff > 0.0 => Some(WaveTrendCross::Bullish),
+            Some(prev) if prev > 0.0 && diff < 0.0 => Some(WaveTrendCross::Bearish),
+            _ => None,
+        };
+        // a touch without crossing keeps the side, on which WT1 was before
+        if diff != 0.0 || self.prev_diff.is_none() {
+            self.prev_diff = Some(diff);
+        }
+
+        let output = WaveTrendOutput { wt1, wt2, cross };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for WaveTrend {
+    type Output = WaveTrendOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next((input.high() + input.low() + input.close()) / 3.0)
+    }
+}
+
+impl Current for WaveTrend {
+    type Output = WaveTrendOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for WaveTrend {
+    fn reset(&mut self) {
+        self.esa.reset();
+        self.d.reset();
+        self.wt1.reset();
+        self.wt2.reset();
+        self.prev_diff = None;
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for WaveTrend {
+    fn heap_size(&self) -> usize {
+        self.wt2.heap_size()
+    }
+}
+
+impl Default for WaveTrend {
+    fn default() -> Self {
+        Self::new(10, 21, 4).unwrap()
+    }
+}
+
+impl fmt::Display for WaveTrend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "WT({}, {}, {})",
+            self.channel_period, self.average_period, self.signal_period
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(WaveTrend);
+
+    #[test]
+    fn test_new() {
+        assert!(WaveTrend::new(0, 21, 4).is_err());
+        assert!(WaveTrend::new(10, 0, 4).is_err());
+        assert!(WaveTrend::new(10, 21, 0).is_err());
+        assert!(WaveTrend::new(1, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut wt = WaveTrend::new(3, 3, 2).unwrap();
+
+        let outputs: Vec<WaveTrendOutput> = [10.0, 9.0, 11.0, 12.0, 11.0, 10.0, 10.5]
+            .iter()
+            .map(|&x| wt.next(x))
+            .collect();
+
+        let wt1: Vec<f64> = outputs.iter().map(|o| round(o.wt1)).collect();
+        assert_eq!(
+            wt1,
+            vec![0.0, -66.667, 16.667, 50.758, 19.823, -29.169, -16.807]
+        );
+        let wt2: Vec<f64> = outputs.iter().map(|o| round(o.wt2)).collect();
+        assert_eq!(
+            wt2,
+            vec![0.0, -33.333, -25.0, 33.712, 35.29, -4.673, -22.988]
+        );
+
+        use WaveTrendCross::*;
+        let crosses: Vec<Option<WaveTrendCross>> = outputs.iter().map(|o| o.cross).collect();
+        assert_eq!(
+            crosses,
+            vec![
+                None,
+                None,
+                Some(Bullish),
+                None,
+                Some(Bearish),
+                None,
+                Some(Bullish)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut wt = WaveTrend::new(3, 3, 2).unwrap();
+
+        // typical prices are 10 and 9
+        wt.next(&Bar::new().high(11.0).low(9.0).close(10.0));
+        let out = wt.next(&Bar::new().high(10.0).low(8.0).close(9.0));
+        assert_eq!(round(out.wt1), -66.667);
+    }
+
+    #[test]
+    fn test_flat() {
+        let mut wt = WaveTrend::new(3, 3, 2).unwrap();
+
+        for _ in 0..5 {
+            let out = wt.next(10.0);
+            assert_eq!(out.wt1, 0.0);
+            assert_eq!(out.cross, None);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut wt = WaveTrend::new(3, 3, 2).unwrap();
+
+        wt.next(10.0);
+        wt.next(9.0);
+
+        wt.reset();
+        assert_eq!(wt.next(10.0).wt1, 0.0);
+        assert_eq!(round(wt.next(9.0).wt1), -66.667);
+        assert_eq!(wt.next(11.0).cross, Some(WaveTrendCross::Bullish));
+    }
+
+    #[test]
+    fn test_period() {
+        let wt = WaveTrend::new(10, 21, 4).unwrap();
+        assert_eq!(wt.period(), 42);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut WaveTrend::new(3, 4, 2).unwrap(), 1.0, 9);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut WaveTrend::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        WaveTrend::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let wt = WaveTrend::default();
+        assert_eq!(format!("{}", wt), "WT(10, 21, 4)");
+    }
+}
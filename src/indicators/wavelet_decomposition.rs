@@ -0,0 +1,339 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::ring_buffer::RingBuffer;
+use crate::{Close, Current, IsReady, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of levels, the support of the last level is 2<sup>16</sup> values.
+const MAX_LEVELS: usize = 16;
+
+/// Multi-resolution wavelet decomposition (MODWT).
+///
+/// A streaming maximal overlap discrete wavelet transform with the Haar wavelet. Every level
+/// splits the smooth component of the previous level into a smoother trend and the detail,
+/// which was removed, so a price series is decomposed into a trend and details at scales of
+/// 2, 4, 8, ... bars. The components add up to the input exactly, so any of them can be fed
+/// into other indicators, e.g. an RSI of the trend only.
+///
+/// The transform is causal, i.e. it uses only past values. Until enough values have been seen,
+/// the missing history is assumed to be equal to the first value.
+///
+/// # Formula
+///
+/// V<sub>0</sub> = input
+///
+/// V<sub>j</sub>(t) = (V<sub>j-1</sub>(t) + V<sub>j-1</sub>(t - 2<sup>j-1</sup>)) / 2
+///
+/// W<sub>j</sub>(t) = (V<sub>j-1</sub>(t) - V<sub>j-1</sub>(t - 2<sup>j-1</sup>)) / 2
+///
+/// input = V<sub>J</sub> + W<sub>1</sub> + ... + W<sub>J</sub>
+///
+/// Where _J_ is the number of levels, V<sub>J</sub> is the trend and W<sub>j</sub> is the
+/// detail of the level _j_.
+///
+/// # Parameters
+///
+/// * _levels_ - number of levels (integer in range [1, 16]). Default is 4.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::WaveletDecomposition;
+/// use ta::Next;
+///
+/// let mut modwt = WaveletDecomposition::new(2).unwrap();
+///
+/// modwt.next(10.0);
+/// modwt.next(12.0);
+/// let out = modwt.next(14.0);
+/// assert_eq!(out.trend, 11.5);
+/// assert_eq!(out.details(), [1.0, 1.5]);
+/// assert_eq!(out.trend + out.details().iter().sum::<f64>(), 14.0);
+/// ```
+///
+/// # Links
+///
+/// * [Stationary wavelet transform, Wikipedia](https://en.wikipedia.org/wiki/Stationary_wavelet_transform)
+///
+#[doc(alias = "MODWT")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct WaveletDecomposition {
+    levels: usize,
+    // `history[j]` holds the last 2^j smooth values of the level j (the input for j = 0)
+    history: Vec<RingBuffer>,
+    trend: f64,
+    details: [f64; MAX_LEVELS],
+    count: usize,
+    output: Option<WaveletDecompositionOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaveletDecompositionOutput {
+    /// Smooth component of the last level.
+    pub trend: f64,
+    details: [f64; MAX_LEVELS],
+    levels: usize,
+}
+
+impl WaveletDecompositionOutput {
+    /// Detail components from the finest (2 bars) to the coarsest scale, one per level.
+    pub fn details(&self) -> &[f64] {
+        &self.details[..self.levels]
+    }
+}
+
+impl WaveletDecomposition {
+    pub fn new(levels: usize) -> Result<Self> {
+        if levels == 0 || levels > MAX_LEVELS {
+            return Err(TaError::invalid_period(
+                "levels",
+                levels,
+                "must be between 1 and 16",
+            ));
+        }
+        Ok(Self {
+            levels,
+            history: (0..levels).map(|j| RingBuffer::new(1 << j)).collect(),
+            trend: 0.0,
+            details: [0.0; MAX_LEVELS],
+            count: 0,
+            output: None,
+        })
+    }
+
+    pub fn levels(&self) -> usize {
+        self.levels
+    }
+
+    /// The latest trend, i.e. the smooth component of the last level.
+    pub fn trend(&self) -> f64 {
+        self.trend
+    }
+
+    /// The latest detail of the `level` in range [1, levels].
+    pub fn detail(&self, level: usize) -> Option<f64> {
+        level
+            .checked_sub(1)
+            .and_then(|j| self.details[..self.levels].get(j))
+            .copied()
+    }
+}
+
+impl Period for WaveletDecomposition {
+    fn period(&self) -> usize {
+        1 << self.levels
+    }
+}
+
+impl IsReady for WaveletDecomposition {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
+}
+
+impl Next<f64> for WaveletDecomposition {
+    type Output = WaveletDecompositionOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        if self.count < self.period() {
+            self.count += 1;
+        }
+
+        if self.history[0].is_empty() {
+            for history in self.history.iter_mut() {
+                while !history.is_full() {
+                    history.push(input);
+                }
+            }
+        }
+
+        let mut smooth = input;
+        for (history, detail) in self.history.iter_mut().zip(self.details.iter_mut()) {
+            // the histories are full, so the value leaving is 2^j steps back
+            let lagged = history.push(smooth).unwrap();
+
+            *detail = (smooth - lagged) / 2.0;
+            smooth = (smooth + lagged) / 2.0;
+        }
+        self.trend = smooth;
+
+        let output = WaveletDecompositionOutput {
+            trend: self.trend,
+            details: self.details,
+            levels: self.levels,
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<T: Close> Next<&T> for WaveletDecomposition {
+    type Output = WaveletDecompositionOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.next(input.close())
+    }
+}
+
+impl Current for WaveletDecomposition {
+    type Output = WaveletDecompositionOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for WaveletDecomposition {
+    fn reset(&mut self) {
+        self.history.iter_mut().for_each(RingBuffer::clear);
+        self.trend = 0.0;
+        self.details.iter_mut().for_each(|x| *x = 0.0);
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for WaveletDecomposition {
+    fn heap_size(&self) -> usize {
+        let history: usize = self.history.iter().map(RingBuffer::heap_size).sum();
+        self.history.capacity() * core::mem::size_of::<RingBuffer>() + history
+    }
+}
+
+impl Default for WaveletDecomposition {
+    fn default() -> Self {
+        Self::new(4).unwrap()
+    }
+}
+
+impl fmt::Display for WaveletDecomposition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MODWT({})", self.levels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(WaveletDecomposition);
+
+    #[test]
+    fn test_new() {
+        assert!(WaveletDecomposition::new(0).is_err());
+        assert!(WaveletDecomposition::new(17).is_err());
+        assert!(WaveletDecomposition::new(1).is_ok());
+        assert!(WaveletDecomposition::new(16).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut modwt = WaveletDecomposition::new(2).unwrap();
+
+        let out = modwt.next(10.0);
+        assert_eq!(out.trend, 10.0);
+        assert_eq!(out.details(), [0.0, 0.0]);
+
+        // level 1 smooths 10 and 12, level 2 smooths 11 and the padded 10
+        let out = modwt.next(12.0);
+        assert_eq!(out.trend, 10.5);
+        assert_eq!(out.details(), [1.0, 0.5]);
+
+        let out = modwt.next(14.0);
+        assert_eq!(out.trend, 11.5);
+        assert_eq!(out.details(), [1.0, 1.5]);
+
+        let out = modwt.next(8.0);
+        assert_eq!(out.trend, 11.0);
+        assert_eq!(out.details(), [-3.0, 0.0]);
+
+        assert_eq!(modwt.trend(), 11.0);
+        assert_eq!(modwt.detail(1), Some(-3.0));
+        assert_eq!(modwt.detail(2), Some(0.0));
+        assert_eq!(modwt.detail(0), None);
+        assert_eq!(modwt.detail(3), None);
+    }
+
+    #[test]
+    fn test_reconstruction() {
+        let mut modwt = WaveletDecomposition::new(5).unwrap();
+
+        for i in 0..100 {
+            let x = 100.0 + (i as f64 * 0.7).sin() * 5.0 + i as f64 * 0.1;
+            let out = modwt.next(x);
+            assert_eq!(
+                round(out.trend + out.details().iter().sum::<f64>()),
+                round(x)
+            );
+        }
+    }
+
+    #[test]
+    fn test_constant() {
+        let mut modwt = WaveletDecomposition::new(3).unwrap();
+
+        for _ in 0..10 {
+            let out = modwt.next(5.0);
+            assert_eq!(out.trend, 5.0);
+            assert_eq!(out.details(), [0.0; 3]);
+        }
+    }
+
+    #[test]
+    fn test_next_with_bars() {
+        let mut modwt = WaveletDecomposition::new(1).unwrap();
+
+        modwt.next(&Bar::new().close(4.0));
+        let out = modwt.next(&Bar::new().close(6.0));
+        assert_eq!(out.trend, 5.0);
+        assert_eq!(out.details(), [1.0]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut modwt = WaveletDecomposition::new(2).unwrap();
+
+        modwt.next(10.0);
+        modwt.next(12.0);
+
+        modwt.reset();
+        let out = modwt.next(20.0);
+        assert_eq!(out.trend, 20.0);
+        assert_eq!(out.details(), [0.0, 0.0]);
+        assert_eq!(modwt.next(22.0).trend, 20.5);
+    }
+
+    #[test]
+    fn test_period() {
+        let wavelet = WaveletDecomposition::new(4).unwrap();
+        assert_eq!(wavelet.period(), 16);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut WaveletDecomposition::new(2).unwrap(), 1.0, 4);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut WaveletDecomposition::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        WaveletDecomposition::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let modwt = WaveletDecomposition::new(3).unwrap();
+        assert_eq!(format!("{}", modwt), "MODWT(3)");
+    }
+}
@@ -0,0 +1,132 @@
+use core::fmt;
+
+use crate::{Close, Current, High, IsReady, Low, MemoryFootprint, Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Weighted close (WC).
+///
+/// Average of the high, low and close prices of a bar, in which the close counts twice, so
+/// it leans towards the close more than the [typical price](struct.TypicalPrice.html).
+///
+/// # Formula
+///
+/// WC = (high + low + 2 * close) / 4
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{WeightedClose, SimpleMovingAverage};
+/// use ta::{DataItem, Next};
+///
+/// let bar = DataItem::builder()
+///     .open(10.0).high(12.0).low(9.0).close(9.0).volume(100.0)
+///     .build().unwrap();
+///
+/// let mut price = WeightedClose::new();
+/// let mut sma = SimpleMovingAverage::new(3).unwrap();
+/// assert_eq!(sma.next(price.next(&bar)), 9.75);
+/// ```
+#[doc(alias = "WC")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct WeightedClose {
+    output: Option<f64>,
+}
+
+impl WeightedClose {
+    pub fn new() -> Self {
+        Self { output: None }
+    }
+}
+
+impl IsReady for WeightedClose {
+    fn bars_until_ready(&self) -> usize {
+        0
+    }
+}
+
+impl<T: High + Low + Close> Next<&T> for WeightedClose {
+    type Output = f64;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let output = (input.high() + input.low() + 2.0 * input.close()) / 4.0;
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Current for WeightedClose {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for WeightedClose {
+    fn reset(&mut self) {
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for WeightedClose {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for WeightedClose {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for WeightedClose {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WC")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_hlcv_indicator!(WeightedClose);
+
+    #[test]
+    fn test_next() {
+        let mut price = WeightedClose::new();
+
+        assert_eq!(price.next(&Bar::new().high(12.0).low(9.0).close(9.0)), 9.75);
+        assert_eq!(
+            round(price.next(&Bar::new().high(10.0).low(8.0).close(9.5))),
+            9.25
+        );
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert!(WeightedClose::new().is_ready());
+    }
+
+    #[test]
+    fn test_current() {
+        let bars = [
+            Bar::new().high(4).low(2).close(3).volume(100.0),
+            Bar::new().high(6).low(3).close(5).volume(200.0),
+        ];
+        assert_current(&mut WeightedClose::default(), &bars);
+    }
+
+    #[test]
+    fn test_default() {
+        WeightedClose::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", WeightedClose::new()), "WC");
+    }
+}
@@ -1,7 +1,15 @@
-use std::fmt;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
 
 use crate::errors::{Result, TaError};
-use crate::{Close, Next, Period, Reset};
+use crate::helpers::fmt_params;
+use crate::registry::Params;
+use crate::ring_buffer::RingBuffer;
+use crate::{
+    Close, Current, IsReady, MemoryFootprint, Next, NextBatch, ParamValue, Parameters, Period,
+    ProjectNext, Reset,
+};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -37,32 +45,29 @@ use serde::{Deserialize, Serialize};
 ///
 /// * [Weighted moving average, Wikipedia](https://en.wikipedia.org/wiki/Moving_average#Weighted_moving_average)
 ///
-
 #[doc(alias = "WMA")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone)]
 pub struct WeightedMovingAverage {
     period: usize,
-    index: usize,
-    count: usize,
     weight: f64,
     sum: f64,
     sum_flat: f64,
-    deque: Box<[f64]>,
+    window: RingBuffer,
+    output: Option<f64>,
 }
 
 impl WeightedMovingAverage {
     pub fn new(period: usize) -> Result<Self> {
         match period {
-            0 => Err(TaError::InvalidParameter),
+            0 => Err(TaError::zero_period("period")),
             _ => Ok(Self {
                 period,
-                index: 0,
-                count: 0,
                 weight: 0.0,
                 sum: 0.0,
                 sum_flat: 0.0,
-                deque: vec![0.0; period].into_boxed_slice(),
+                window: RingBuffer::new(period),
+                output: None,
             }),
         }
     }
@@ -74,28 +79,58 @@ impl Period for WeightedMovingAverage {
     }
 }
 
+impl IsReady for WeightedMovingAverage {
+    fn bars_until_ready(&self) -> usize {
+        self.period - self.window.len()
+    }
+}
+
 impl Next<f64> for WeightedMovingAverage {
     type Output = f64;
 
     fn next(&mut self, input: f64) -> Self::Output {
-        let old_val: f64 = self.deque[self.index];
-        self.deque[self.index] = input;
-
-        self.index = if self.index + 1 < self.period {
-            self.index + 1
-        } else {
-            0
-        };
-
-        if self.count < self.period {
-            self.count += 1;
-            self.weight = self.count as f64;
+        let old_val = self.window.push(input);
+        if old_val.is_none() {
+            self.weight = self.window.len() as f64;
             self.sum += input * self.weight
         } else {
             self.sum = self.sum - self.sum_flat + (input * self.weight);
         }
-        self.sum_flat = self.sum_flat - old_val + input;
-        self.sum / (self.weight * (self.weight + 1.0) / 2.0)
+        self.sum_flat = self.sum_flat - old_val.unwrap_or(0.0) + input;
+        let output = self.sum / (self.weight * (self.weight + 1.0) / 2.0);
+        self.output = Some(output);
+        output
+    }
+}
+
+impl NextBatch<f64> for WeightedMovingAverage {
+    /// With the `simd` feature the divisions of the full windows are vectorized, the running
+    /// sums are updated in the same order as with `next`.
+    #[cfg(feature = "simd")]
+    fn next_batch(&mut self, inputs: &[f64], out: &mut Vec<Self::Output>) {
+        out.reserve(inputs.len());
+        let head = inputs.len().min(self.period);
+        for &input in &inputs[..head] {
+            out.push(self.next(input));
+        }
+        if head == inputs.len() {
+            return;
+        }
+
+        let (entering, leaving) = (&inputs[head..], &inputs[..inputs.len() - head]);
+        let start = out.len();
+        for (&input, &old_val) in entering.iter().zip(leaving) {
+            self.sum = self.sum - self.sum_flat + (input * self.weight);
+            self.sum_flat = self.sum_flat - old_val + input;
+            out.push(self.sum);
+        }
+        crate::simd::div(&mut out[start..], self.weight * (self.weight + 1.0) / 2.0);
+
+        let skip = entering.len().saturating_sub(self.period);
+        for &input in &entering[skip..] {
+            self.window.push(input);
+        }
+        self.output = out.last().copied();
     }
 }
 
@@ -107,16 +142,63 @@ impl<T: Close> Next<&T> for WeightedMovingAverage {
     }
 }
 
+impl ProjectNext<f64> for WeightedMovingAverage {
+    type Output = f64;
+
+    fn project_next(&self, input: f64) -> Self::Output {
+        let (weight, sum) = if !self.window.is_full() {
+            let weight = (self.window.len() + 1) as f64;
+            (weight, self.sum + input * weight)
+        } else {
+            (
+                self.weight,
+                self.sum - self.sum_flat + (input * self.weight),
+            )
+        };
+        sum / (weight * (weight + 1.0) / 2.0)
+    }
+}
+
+impl<T: Close> ProjectNext<&T> for WeightedMovingAverage {
+    type Output = f64;
+
+    fn project_next(&self, input: &T) -> Self::Output {
+        self.project_next(input.close())
+    }
+}
+
+impl Current for WeightedMovingAverage {
+    type Output = f64;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
 impl Reset for WeightedMovingAverage {
     fn reset(&mut self) {
-        self.index = 0;
-        self.count = 0;
         self.weight = 0.0;
         self.sum = 0.0;
         self.sum_flat = 0.0;
-        for i in 0..self.period {
-            self.deque[i] = 0.0;
-        }
+        self.window.clear();
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for WeightedMovingAverage {
+    fn heap_size(&self) -> usize {
+        self.window.heap_size()
+    }
+}
+
+impl Parameters for WeightedMovingAverage {
+    fn params(&self) -> Vec<(&'static str, ParamValue)> {
+        vec![("period", ParamValue::Period(self.period))]
+    }
+
+    fn from_params(params: &Params) -> Result<Self> {
+        params.allow(&["period"])?;
+        Self::new(params.period("period", 9)?)
     }
 }
 
@@ -128,7 +210,7 @@ impl Default for WeightedMovingAverage {
 
 impl fmt::Display for WeightedMovingAverage {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "WMA({})", self.period)
+        fmt_params(f, "WMA", &self.params())
     }
 }
 
@@ -161,6 +243,47 @@ mod tests {
         assert_eq!(wma.next(&bar2), 4.0);
     }
 
+    #[test]
+    fn test_next_batch() {
+        let inputs: Vec<f64> = (0..40)
+            .map(|i| 100.0 + (i as f64 * 0.7).sin() * 5.0)
+            .collect();
+
+        for period in 1..6 {
+            let mut expected = WeightedMovingAverage::new(period).unwrap();
+            let expected: Vec<f64> = inputs.iter().map(|&x| expected.next(x)).collect();
+
+            for batch in [1, 2, 3, 7, 40] {
+                let mut wma = WeightedMovingAverage::new(period).unwrap();
+                let mut out = Vec::new();
+                for chunk in inputs.chunks(batch) {
+                    wma.next_batch(chunk, &mut out);
+                }
+                assert_eq!(out, expected);
+                assert_eq!(wma.current(), out.last().copied());
+                assert_eq!(wma.bars_until_ready(), 0);
+            }
+        }
+
+        // the window afterwards is the same as after `next`
+        let mut wma = WeightedMovingAverage::new(3).unwrap();
+        wma.next_batch(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0], &mut Vec::new());
+        assert_eq!(wma.next(11.0), (6.0 + 14.0 + 33.0) / 6.0);
+    }
+
+    #[test]
+    fn test_project_next() {
+        let mut wma = WeightedMovingAverage::new(3).unwrap();
+
+        for &x in &[10.0, 11.0, 12.0, 13.0, 9.0] {
+            let projected = wma.project_next(x);
+            assert_eq!(wma.project_next(&Bar::new().close(x)), projected);
+            // projecting does not change the state
+            assert_eq!(wma.project_next(x), projected);
+            assert_eq!(wma.next(x), projected);
+        }
+    }
+
     #[test]
     fn test_reset() {
         let mut wma = WeightedMovingAverage::new(5).unwrap();
@@ -175,6 +298,16 @@ mod tests {
         assert_eq!(wma.next(4.0), 4.0);
     }
 
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut WeightedMovingAverage::new(3).unwrap(), 1.0, 3);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut WeightedMovingAverage::default(), [2.0, 3.0, 5.0]);
+    }
+
     #[test]
     fn test_default() {
         WeightedMovingAverage::default();
@@ -0,0 +1,309 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::{BollingerBands, Maximum};
+use crate::{Close, Current, IsReady, Low, MemoryFootprint, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Williams VIX Fix (WVF).
+///
+/// A synthetic volatility index created by Larry Williams, which imitates the VIX for any
+/// instrument. It measures how far the low of a bar fell below the highest close of the recent
+/// period. Spikes of the WVF happen during sell-offs, so it is used to spot market bottoms.
+///
+/// A spike is detected with two thresholds (as in the popular CM Williams VIX Fix script): the
+/// upper [Bollinger Band](struct.BollingerBands.html) of the WVF and the range high, a fraction
+/// of the highest WVF over a longer lookback period.
+///
+/// # Formula
+///
+/// WVF = (highest close over _period_ - low) / highest close over _period_ * 100
+///
+/// upper band = SMA(WVF) + _multiplier_ * SD(WVF) over _bb_period_
+///
+/// range high = highest WVF over _lookback_ * _percentile_
+///
+/// # Parameters
+///
+/// * _period_ - period of the highest close (integer greater than 0). Default is 22.
+/// * _bb_period_ - period of the Bollinger Bands of WVF (integer greater than 0). Default is 20.
+/// * _multiplier_ - width of the Bollinger Bands (number greater than 0). Default is 2.0.
+/// * _lookback_ - period of the highest WVF (integer greater than 0). Default is 50.
+/// * _percentile_ - fraction of the highest WVF used as range high (number in range (0, 1]).
+///   Default is 0.85.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::WilliamsVixFix;
+/// use ta::{DataItem, Next};
+///
+/// let mut wvf = WilliamsVixFix::new(3, 3, 2.0, 5, 0.85).unwrap();
+///
+/// let bar = |low: f64, close: f64| {
+///     DataItem::builder()
+///         .open(close).high(close).low(low).close(close).volume(1000.0)
+///         .build().unwrap()
+/// };
+///
+/// wvf.next(&bar(99.0, 100.0));
+/// wvf.next(&bar(99.0, 100.0));
+/// // the low is 20% below the highest close
+/// let out = wvf.next(&bar(80.0, 85.0));
+/// assert_eq!(out.wvf, 20.0);
+/// assert!(out.spike);
+/// ```
+#[doc(alias = "WVF")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct WilliamsVixFix {
+    percentile: f64,
+    highest_close: Maximum,
+    bb: BollingerBands,
+    highest_wvf: Maximum,
+    count: usize,
+    output: Option<WilliamsVixFixOutput>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WilliamsVixFixOutput {
+    pub wvf: f64,
+    pub upper_band: f64,
+    pub range_high: f64,
+    /// Whether WVF is at or above one of the thresholds.
+    pub spike: bool,
+}
+
+impl WilliamsVixFix {
+    pub fn new(
+        period: usize,
+        bb_period: usize,
+        multiplier: f64,
+        lookback: usize,
+        percentile: f64,
+    ) -> Result<Self> {
+        if multiplier.is_nan() || multiplier <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "multiplier",
+                multiplier,
+                "must be greater than 0",
+            ));
+        }
+        if !(percentile > 0.0 && percentile <= 1.0) {
+            return Err(TaError::invalid_parameter(
+                "percentile",
+                percentile,
+                "must be greater than 0 and at most 1",
+            ));
+        }
+        Ok(Self {
+            percentile,
+            highest_close: Maximum::new(period)?,
+            bb: BollingerBands::new(bb_period, multiplier)?,
+            highest_wvf: Maximum::new(lookback)?,
+            count: 0,
+            output: None,
+        })
+    }
+
+    fn update(&mut self, low: f64, close: f64) -> WilliamsVixFixOutput {
+        if self.count < self.period() {
+            self.count += 1;
+        }
+
+        let highest = self.highest_close.next(close);
+        let wvf = if highest != 0.0 {
+            (highest - low) / highest * 100.0
+        } else {
+            0.0
+        };
+        let upper_band = self.bb.next(wvf).upper;
+        let range_high = self.highest_wvf.next(wvf) * self.percentile;
+
+        let output = WilliamsVixFixOutput {
+            wvf,
+            upper_band,
+            range_high,
+            spike: wvf > 0.0 && (wvf >= upper_band || wvf >= range_high),
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl Period for WilliamsVixFix {
+    fn period(&self) -> usize {
+        self.highest_close.period() + self.bb.period().max(self.highest_wvf.period()) - 1
+    }
+}
+
+impl IsReady for WilliamsVixFix {
+    fn bars_until_ready(&self) -> usize {
+        self.period() - self.count
+    }
+}
+
+impl Next<f64> for WilliamsVixFix {
+    type Output = WilliamsVixFixOutput;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        self.update(input, input)
+    }
+}
+
+impl<T: Low + Close> Next<&T> for WilliamsVixFix {
+    type Output = WilliamsVixFixOutput;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        self.update(input.low(), input.close())
+    }
+}
+
+impl Current for WilliamsVixFix {
+    type Output = WilliamsVixFixOutput;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl Reset for WilliamsVixFix {
+    fn reset(&mut self) {
+        self.highest_close.reset();
+        self.bb.reset();
+        self.highest_wvf.reset();
+        self.count = 0;
+        self.output = None;
+    }
+}
+
+impl MemoryFootprint for WilliamsVixFix {
+    fn heap_size(&self) -> usize {
+        self.highest_close.heap_size() + self.bb.heap_size() + self.highest_wvf.heap_size()
+    }
+}
+
+impl Default for WilliamsVixFix {
+    fn default() -> Self {
+        Self::new(22, 20, 2.0, 50, 0.85).unwrap()
+    }
+}
+
+impl fmt::Display for WilliamsVixFix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "WVF({}, {}, {}, {}, {})",
+            self.highest_close.period(),
+            self.bb.period(),
+            self.bb.multiplier(),
+            self.highest_wvf.period(),
+            self.percentile
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    test_indicator!(WilliamsVixFix);
+
+    fn bar(low: f64, close: f64) -> Bar {
+        Bar::new().high(close).low(low).close(close)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(WilliamsVixFix::new(0, 20, 2.0, 50, 0.85).is_err());
+        assert!(WilliamsVixFix::new(22, 0, 2.0, 50, 0.85).is_err());
+        assert!(WilliamsVixFix::new(22, 20, 0.0, 50, 0.85).is_err());
+        assert!(WilliamsVixFix::new(22, 20, 2.0, 0, 0.85).is_err());
+        assert!(WilliamsVixFix::new(22, 20, 2.0, 50, 0.0).is_err());
+        assert!(WilliamsVixFix::new(22, 20, 2.0, 50, 1.1).is_err());
+        assert!(WilliamsVixFix::new(1, 1, 0.5, 1, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut wvf = WilliamsVixFix::new(2, 3, 2.0, 3, 0.5).unwrap();
+
+        let out = wvf.next(&bar(9.0, 10.0));
+        assert_eq!(out.wvf, 10.0);
+        assert_eq!(out.range_high, 5.0);
+        assert!(out.spike);
+
+        // highest close is 10.0
+        let out = wvf.next(&bar(9.5, 9.8));
+        assert_eq!(round(out.wvf), 5.0);
+        assert_eq!(out.range_high, 5.0);
+        assert_eq!(round(out.upper_band), 12.5);
+        assert!(out.spike);
+
+        // highest close is 9.9
+        let out = wvf.next(&bar(9.7, 9.9));
+        assert_eq!(round(out.wvf), 2.02);
+        assert!(!out.spike);
+
+        // highest close is 9.9, the first WVF leaves the range high window
+        let out = wvf.next(&bar(8.0, 9.0));
+        assert_eq!(round(out.wvf), 19.192);
+        assert_eq!(round(out.range_high), 9.596);
+        assert!(out.spike);
+    }
+
+    #[test]
+    fn test_flat_market() {
+        let mut wvf = WilliamsVixFix::new(2, 3, 2.0, 3, 0.85).unwrap();
+
+        for _ in 0..5 {
+            let out = wvf.next(10.0);
+            assert_eq!(out.wvf, 0.0);
+            assert!(!out.spike);
+        }
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut wvf = WilliamsVixFix::new(2, 3, 2.0, 3, 0.5).unwrap();
+
+        wvf.next(&bar(9.0, 10.0));
+        wvf.next(&bar(5.0, 6.0));
+
+        wvf.reset();
+        let out = wvf.next(&bar(9.0, 10.0));
+        assert_eq!(out.wvf, 10.0);
+        assert_eq!(out.upper_band, 10.0);
+    }
+
+    #[test]
+    fn test_period() {
+        let wvf = WilliamsVixFix::new(22, 20, 2.0, 50, 0.85).unwrap();
+        assert_eq!(wvf.period(), 71);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        let mut wvf = WilliamsVixFix::new(3, 2, 2.0, 4, 0.85).unwrap();
+        assert_warm_up(&mut wvf, 1.0, 6);
+    }
+
+    #[test]
+    fn test_current() {
+        assert_current(&mut WilliamsVixFix::default(), [2.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_default() {
+        WilliamsVixFix::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let wvf = WilliamsVixFix::default();
+        assert_eq!(format!("{}", wvf), "WVF(22, 20, 2, 50, 0.85)");
+    }
+}
@@ -0,0 +1,229 @@
+//! Indicators over iterators.
+//!
+//! The [IndicatorIteratorExt](trait.IndicatorIteratorExt.html) trait adds
+//! [indicate](trait.IndicatorIteratorExt.html#method.indicate) to every iterator over prices,
+//! bars or references to bars. It feeds the items into an indicator and yields its outputs
+//! lazily, so running an indicator over historical data is a single expression.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::{RelativeStrengthIndex as Rsi, SimpleMovingAverage as Sma};
+//! use ta::iter::IndicatorIteratorExt;
+//! use ta::DataItem;
+//!
+//! # fn main() -> ta::errors::Result<()> {
+//! let closes = vec![10.0, 11.0, 12.0, 11.5];
+//! let sma: Vec<f64> = closes.iter().copied().indicate(Sma::new(2)?).collect();
+//! assert_eq!(sma, vec![10.0, 10.5, 11.5, 11.75]);
+//!
+//! let bars: Vec<DataItem> = closes
+//!     .iter()
+//!     .map(|&close| {
+//!         DataItem::builder()
+//!             .open(close).high(close).low(close).close(close).volume(1000.0)
+//!             .build()
+//!     })
+//!     .collect::<Result<_, _>>()?;
+//!
+//! // by reference or by value
+//! let by_ref: Vec<f64> = bars.iter().indicate(Rsi::new(14)?).collect();
+//! let by_value: Vec<f64> = bars.into_iter().indicate(Rsi::new(14)?).collect();
+//! assert_eq!(by_ref, by_value);
+//! # Ok(())
+//! # }
+//! ```
+
+use core::iter::FusedIterator;
+
+use crate::{DataItem, Next};
+
+/// Item of an iterator, which can be fed into the indicator `I`.
+///
+/// Implemented for `f64`, owned [DataItem](../struct.DataItem.html)s and references to any
+/// input the indicator takes by reference, e.g. `&DataItem`.
+pub trait IndicatorInput<I> {
+    type Output;
+
+    fn feed(self, indicator: &mut I) -> Self::Output;
+}
+
+impl<I: Next<f64>> IndicatorInput<I> for f64 {
+    type Output = I::Output;
+
+    fn feed(self, indicator: &mut I) -> Self::Output {
+        indicator.next(self)
+    }
+}
+
+impl<I, O> IndicatorInput<I> for DataItem
+where
+    I: for<'a> Next<&'a DataItem, Output = O>,
+{
+    type Output = O;
+
+    fn feed(self, indicator: &mut I) -> Self::Output {
+        indicator.next(&self)
+    }
+}
+
+impl<'a, T, I: Next<&'a T>> IndicatorInput<I> for &'a T {
+    type Output = I::Output;
+
+    fn feed(self, indicator: &mut I) -> Self::Output {
+        indicator.next(self)
+    }
+}
+
+/// Adds [indicate](#method.indicate) to every iterator.
+pub trait IndicatorIteratorExt: Iterator + Sized {
+    /// Feeds the items into `indicator` and yields its outputs.
+    ///
+    /// The indicator is fed lazily, one item per call of `next`. Use
+    /// [indicator](struct.Indicate.html#method.indicator) to inspect its state afterwards.
+    fn indicate<I>(self, indicator: I) -> Indicate<Self, I>
+    where
+        Self::Item: IndicatorInput<I>,
+    {
+        Indicate {
+            iter: self,
+            indicator,
+        }
+    }
+}
+
+impl<It: Iterator> IndicatorIteratorExt for It {}
+
+/// Iterator over the outputs of an indicator, see
+/// [indicate](trait.IndicatorIteratorExt.html#method.indicate).
+#[derive(Debug, Clone)]
+pub struct Indicate<It, I> {
+    iter: It,
+    indicator: I,
+}
+
+impl<It, I> Indicate<It, I> {
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    /// Returns the indicator, e.g. to keep feeding it with live data after the history.
+    pub fn into_indicator(self) -> I {
+        self.indicator
+    }
+}
+
+impl<It, I> Iterator for Indicate<It, I>
+where
+    It: Iterator,
+    It::Item: IndicatorInput<I>,
+{
+    type Item = <It::Item as IndicatorInput<I>>::Output;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        Some(item.feed(&mut self.indicator))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<It, I> ExactSizeIterator for Indicate<It, I>
+where
+    It: ExactSizeIterator,
+    It::Item: IndicatorInput<I>,
+{
+}
+
+impl<It, I> FusedIterator for Indicate<It, I>
+where
+    It: FusedIterator,
+    It::Item: IndicatorInput<I>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        BollingerBands, ExponentialMovingAverage as Ema, SimpleMovingAverage as Sma,
+    };
+    use crate::test_helper::*;
+    use crate::Current;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    fn bars() -> Vec<DataItem> {
+        [10.0, 11.0, 12.0, 11.5, 13.0]
+            .iter()
+            .map(|&close| {
+                DataItem::builder()
+                    .open(close)
+                    .high(close + 1.0)
+                    .low(close - 1.0)
+                    .close(close)
+                    .volume(1000.0)
+                    .build()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_f64() {
+        let outputs: Vec<f64> = [4.0, 5.0, 6.0, 6.0]
+            .iter()
+            .copied()
+            .indicate(Sma::new(2).unwrap())
+            .collect();
+        assert_eq!(outputs, vec![4.0, 4.5, 5.5, 6.0]);
+    }
+
+    #[test]
+    fn test_same_as_next() {
+        let mut ema = Ema::new(3).unwrap();
+        let expected: Vec<f64> = bars().iter().map(|bar| ema.next(bar)).collect();
+
+        let by_ref: Vec<f64> = bars().iter().indicate(Ema::new(3).unwrap()).collect();
+        let by_value: Vec<f64> = bars().into_iter().indicate(Ema::new(3).unwrap()).collect();
+        assert_eq!(by_ref, expected);
+        assert_eq!(by_value, expected);
+    }
+
+    #[test]
+    fn test_other_bars() {
+        let bars = [Bar::new().close(2.0), Bar::new().close(4.0)];
+        let outputs: Vec<f64> = bars.iter().indicate(Sma::new(2).unwrap()).collect();
+        assert_eq!(outputs, vec![2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_struct_outputs() {
+        let bands: Vec<_> = [1.0, 3.0]
+            .iter()
+            .copied()
+            .indicate(BollingerBands::new(2, 1.0).unwrap())
+            .collect();
+        assert_eq!(bands[1].average, 2.0);
+        assert_eq!(bands[1].upper, 3.0);
+    }
+
+    #[test]
+    fn test_lazy() {
+        let mut iter = [1.0, 2.0, 3.0]
+            .iter()
+            .copied()
+            .indicate(Sma::new(3).unwrap());
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.indicator().current(), None);
+
+        assert_eq!(iter.next(), Some(1.0));
+        assert_eq!(iter.len(), 2);
+        assert_eq!(iter.indicator().current(), Some(1.0));
+
+        let mut sma = iter.into_indicator();
+        assert_eq!(sma.next(4.0), 2.5);
+    }
+}
@@ -0,0 +1,387 @@
+//! Supervised learning targets generated from bar series.
+//!
+//! Unlike indicators, labels look into the future, so they are computed over a whole slice of
+//! bars at once. Every function returns a `Vec` aligned with the input: the element with index
+//! _i_ is the label of the bar _i_, so it can be zipped with the features computed on the same
+//! bars. The label is `None` when there are not enough future bars to know it.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::labels::{forward_returns, Barrier, TripleBarrier};
+//! use ta::DataItem;
+//!
+//! let closes = [10.0, 11.0, 12.1, 11.0];
+//! let returns = forward_returns(&closes, 2);
+//! assert_eq!(returns.len(), closes.len());
+//! assert_eq!(returns[2], None);
+//!
+//! let bars: Vec<DataItem> = [10.0, 10.5, 12.0, 9.0]
+//!     .iter()
+//!     .map(|&close| {
+//!         DataItem::builder()
+//!             .open(close).high(close + 0.5).low(close - 0.5).close(close).volume(100.0)
+//!             .build().unwrap()
+//!     })
+//!     .collect();
+//!
+//! // take profit at 1 ATR, stop at 1 ATR, hold at most 2 bars
+//! let barrier = TripleBarrier::new(1.0, 1.0, 2, 3).unwrap();
+//! let labels = barrier.label(&bars);
+//! assert_eq!(labels[0].unwrap().hit, Barrier::ProfitTake);
+//! assert_eq!(labels[0].unwrap().label(), 1);
+//! assert_eq!(labels[3], None);
+//! ```
+//!
+//! # Links
+//!
+//! * [Triple barrier method, M. López de Prado: Advances in Financial Machine Learning](https://www.wiley.com/en-us/Advances+in+Financial+Machine+Learning-p-9781119482086)
+
+use crate::errors::{Result, TaError};
+use crate::indicators::AverageTrueRange;
+use crate::{Close, High, Low, Next};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Returns the `horizon`-bar forward return of every close: _close<sub>i+h</sub> / close<sub>i</sub> - 1_.
+///
+/// The last `horizon` elements are `None`.
+pub fn forward_returns(closes: &[f64], horizon: usize) -> Vec<Option<f64>> {
+    (0..closes.len())
+        .map(|i| {
+            closes
+                .get(i + horizon)
+                .map(|future| future / closes[i] - 1.0)
+        })
+        .collect()
+}
+
+/// The barrier that ended a trade.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Barrier {
+    /// The profit-take (upper for long trades) barrier was touched first.
+    ProfitTake,
+    /// The stop-loss (lower for long trades) barrier was touched first.
+    StopLoss,
+    /// None of the horizontal barriers was touched within the holding period.
+    Timeout,
+}
+
+/// Outcome of a trade opened at the close of a bar.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BarrierLabel {
+    pub hit: Barrier,
+    /// Number of bars from the entry bar to the exit bar.
+    pub bars_held: usize,
+    /// Return of the trade in its direction, e.g. positive for a falling price and a short trade.
+    pub ret: f64,
+}
+
+impl BarrierLabel {
+    /// Classification target: 1 for profit-take, -1 for stop-loss and 0 for timeout.
+    pub fn label(&self) -> i8 {
+        match self.hit {
+            Barrier::ProfitTake => 1,
+            Barrier::StopLoss => -1,
+            Barrier::Timeout => 0,
+        }
+    }
+}
+
+/// Triple barrier labeling.
+///
+/// For every bar a trade is opened at its close. The trade is closed when the price touches
+/// the profit-take barrier, the stop-loss barrier or when _max_holding_ bars have passed
+/// (the vertical barrier). The horizontal barriers are placed at a multiple of the
+/// [ATR](../indicators/struct.AverageTrueRange.html) known at the entry bar, so no future data
+/// is used to size them.
+///
+/// If a single bar touches both horizontal barriers, the stop-loss is assumed to be hit first.
+/// Trades hitting a horizontal barrier exit at the barrier price, timed out trades exit at the
+/// close of the last bar.
+///
+/// # Parameters
+///
+/// * _profit_take_ - distance of the profit-take barrier in ATRs (greater than 0)
+/// * _stop_loss_ - distance of the stop-loss barrier in ATRs (greater than 0)
+/// * _max_holding_ - number of bars until the vertical barrier (integer greater than 0)
+/// * _atr_period_ - period of the ATR (integer greater than 0)
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct TripleBarrier {
+    profit_take: f64,
+    stop_loss: f64,
+    max_holding: usize,
+    atr_period: usize,
+}
+
+impl TripleBarrier {
+    pub fn new(
+        profit_take: f64,
+        stop_loss: f64,
+        max_holding: usize,
+        atr_period: usize,
+    ) -> Result<Self> {
+        if profit_take.is_nan() || profit_take <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "profit_take",
+                profit_take,
+                "must be greater than 0",
+            ));
+        }
+        if stop_loss.is_nan() || stop_loss <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "stop_loss",
+                stop_loss,
+                "must be greater than 0",
+            ));
+        }
+        if max_holding == 0 {
+            return Err(TaError::zero_period("max_holding"));
+        }
+        if atr_period == 0 {
+            return Err(TaError::zero_period("atr_period"));
+        }
+        Ok(Self {
+            profit_take,
+            stop_loss,
+            max_holding,
+            atr_period,
+        })
+    }
+
+    pub fn profit_take(&self) -> f64 {
+        self.profit_take
+    }
+
+    pub fn stop_loss(&self) -> f64 {
+        self.stop_loss
+    }
+
+    pub fn max_holding(&self) -> usize {
+        self.max_holding
+    }
+
+    pub fn atr_period(&self) -> usize {
+        self.atr_period
+    }
+
+    /// Labels a long trade opened at every bar.
+    pub fn label<T: High + Low + Close>(&self, bars: &[T]) -> Vec<Option<BarrierLabel>> {
+        let atrs = self.atrs(bars);
+        (0..bars.len())
+            .map(|i| self.trade(bars, i, atrs[i], 1.0))
+            .collect()
+    }
+
+    /// Labels trades in the direction given by a primary model.
+    ///
+    /// `sides` is aligned with `bars`: a positive value opens a long trade, a negative value
+    /// opens a short trade, zero opens no trade (the label is `None`).
+    pub fn label_sides<T: High + Low + Close>(
+        &self,
+        bars: &[T],
+        sides: &[f64],
+    ) -> Vec<Option<BarrierLabel>> {
+        let atrs = self.atrs(bars);
+        (0..bars.len())
+            .map(|i| match sides.get(i) {
+                Some(&side) if side > 0.0 => self.trade(bars, i, atrs[i], 1.0),
+                Some(&side) if side < 0.0 => self.trade(bars, i, atrs[i], -1.0),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Meta-labels for the signals of a primary model.
+    ///
+    /// The meta-label answers whether acting on the primary signal would have been profitable:
+    /// it is `Some(true)`, when the trade taken in the direction of `sides` ends with a positive
+    /// return, `Some(false)` otherwise. Bars without a signal or without enough future bars are
+    /// `None`. A secondary model trained on these labels learns when to trust the primary one.
+    pub fn meta_label<T: High + Low + Close>(
+        &self,
+        bars: &[T],
+        sides: &[f64],
+    ) -> Vec<Option<bool>> {
+        self.label_sides(bars, sides)
+            .into_iter()
+            .map(|label| label.map(|label| label.ret > 0.0))
+            .collect()
+    }
+
+    fn atrs<T: High + Low + Close>(&self, bars: &[T]) -> Vec<f64> {
+        let mut atr = AverageTrueRange::new(self.atr_period).unwrap();
+        bars.iter().map(|bar| atr.next(bar)).collect()
+    }
+
+    fn trade<T: High + Low + Close>(
+        &self,
+        bars: &[T],
+        entry: usize,
+        atr: f64,
+        side: f64,
+    ) -> Option<BarrierLabel> {
+        let price = bars[entry].close();
+        let take = price + side * self.profit_take * atr;
+        let stop = price - side * self.stop_loss * atr;
+        let ret = |exit: f64| side * (exit / price - 1.0);
+
+        for (offset, bar) in bars[entry + 1..].iter().take(self.max_holding).enumerate() {
+            let bars_held = offset + 1;
+            let (favorable, adverse) = if side > 0.0 {
+                (bar.high(), bar.low())
+            } else {
+                (bar.low(), bar.high())
+            };
+            let hit = if side * (adverse - stop) <= 0.0 {
+                Some((Barrier::StopLoss, stop))
+            } else if side * (favorable - take) >= 0.0 {
+                Some((Barrier::ProfitTake, take))
+            } else if bars_held == self.max_holding {
+                Some((Barrier::Timeout, bar.close()))
+            } else {
+                None
+            };
+            if let Some((hit, exit)) = hit {
+                return Some(BarrierLabel {
+                    hit,
+                    bars_held,
+                    ret: ret(exit),
+                });
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(high: f64, low: f64, close: f64) -> Bar {
+        Bar::new().high(high).low(low).close(close)
+    }
+
+    #[test]
+    fn test_forward_returns() {
+        let closes = [10.0, 12.0, 9.0, 9.0];
+
+        let returns: Vec<_> = forward_returns(&closes, 1)
+            .iter()
+            .map(|r| r.map(round))
+            .collect();
+        assert_eq!(returns, vec![Some(0.2), Some(-0.25), Some(0.0), None]);
+
+        let returns: Vec<_> = forward_returns(&closes, 3)
+            .iter()
+            .map(|r| r.map(round))
+            .collect();
+        assert_eq!(returns, vec![Some(-0.1), None, None, None]);
+        assert_eq!(forward_returns(&closes, 10), vec![None; 4]);
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(TripleBarrier::new(0.0, 1.0, 1, 1).is_err());
+        assert!(TripleBarrier::new(1.0, 0.0, 1, 1).is_err());
+        assert!(TripleBarrier::new(1.0, f64::NAN, 1, 1).is_err());
+        assert!(TripleBarrier::new(1.0, 1.0, 0, 1).is_err());
+        assert!(TripleBarrier::new(1.0, 1.0, 1, 0).is_err());
+        assert!(TripleBarrier::new(2.0, 1.0, 10, 14).is_ok());
+    }
+
+    #[test]
+    fn test_label() {
+        // the first bar has ATR = high - low = 2
+        let bars = [
+            bar(11.0, 9.0, 10.0),
+            bar(11.5, 9.5, 11.0),
+            bar(12.8, 11.5, 12.5), // touches 10 + 2 * 1
+            bar(12.5, 6.0, 7.0),
+            bar(8.0, 6.5, 7.5),
+        ];
+        let barrier = TripleBarrier::new(1.0, 1.0, 3, 1).unwrap();
+        let labels = barrier.label(&bars);
+
+        assert_eq!(labels.len(), bars.len());
+        let first = labels[0].unwrap();
+        assert_eq!(first.hit, Barrier::ProfitTake);
+        assert_eq!(first.bars_held, 2);
+        assert_eq!(round(first.ret), 0.2);
+
+        // entry at 11 with ATR 2: the stop at 9 is hit by the 4th bar
+        let second = labels[1].unwrap();
+        assert_eq!(second.hit, Barrier::StopLoss);
+        assert_eq!(second.label(), -1);
+        assert_eq!(second.bars_held, 2);
+
+        assert_eq!(labels[3], None);
+        assert_eq!(labels[4], None);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let bars = [
+            bar(10.5, 9.5, 10.0),
+            bar(10.5, 9.5, 10.2),
+            bar(10.5, 9.5, 10.1),
+        ];
+        let barrier = TripleBarrier::new(2.0, 2.0, 2, 1).unwrap();
+        let labels = barrier.label(&bars);
+
+        let first = labels[0].unwrap();
+        assert_eq!(first.hit, Barrier::Timeout);
+        assert_eq!(first.label(), 0);
+        assert_eq!(first.bars_held, 2);
+        assert_eq!(round(first.ret), 0.01);
+        assert_eq!(labels[1], None);
+    }
+
+    #[test]
+    fn test_both_barriers_in_one_bar() {
+        let bars = [bar(10.5, 9.5, 10.0), bar(12.0, 8.0, 10.0)];
+        let barrier = TripleBarrier::new(1.0, 1.0, 5, 1).unwrap();
+
+        assert_eq!(barrier.label(&bars)[0].unwrap().hit, Barrier::StopLoss);
+    }
+
+    #[test]
+    fn test_label_sides() {
+        let bars = [
+            bar(10.5, 9.5, 10.0),
+            bar(10.2, 8.5, 8.8), // short profit-take at 10 - 1
+            bar(9.0, 8.0, 8.5),
+        ];
+        let barrier = TripleBarrier::new(1.0, 1.0, 1, 1).unwrap();
+
+        let labels = barrier.label_sides(&bars, &[-1.0, 0.0]);
+        let first = labels[0].unwrap();
+        assert_eq!(first.hit, Barrier::ProfitTake);
+        assert_eq!(round(first.ret), 0.1);
+        assert_eq!(labels[1], None);
+        assert_eq!(labels[2], None);
+
+        let labels = barrier.label_sides(&bars, &[1.0]);
+        assert_eq!(labels[0].unwrap().hit, Barrier::StopLoss);
+    }
+
+    #[test]
+    fn test_meta_label() {
+        let bars = [
+            bar(10.5, 9.5, 10.0),
+            bar(10.2, 8.5, 8.8),
+            bar(9.0, 8.0, 8.5),
+            bar(9.0, 8.0, 8.5),
+        ];
+        let barrier = TripleBarrier::new(1.0, 1.0, 1, 1).unwrap();
+
+        let meta = barrier.meta_label(&bars, &[-1.0, 1.0, 0.0, 1.0]);
+        assert_eq!(meta, vec![Some(true), Some(false), None, None]);
+    }
+}
@@ -10,6 +10,30 @@
 //! Since `Next<T>` is a generic trait, most of the indicators can work with both input types: `f64` and more complex
 //! structures like [DataItem](struct.DataItem.html).
 //!
+//! [IsReady](trait.IsReady.html) tells whether an indicator is past its warm-up, so its outputs
+//! are meaningful, and [TryNext<T>](trait.TryNext.html) returns `None` for the outputs of the
+//! warm-up. [Current](trait.Current.html) returns the last output again, so several consumers
+//! can share an indicator without calling `next` twice. [Snapshot](trait.Snapshot.html) takes
+//! checkpoints of the state, e.g. to branch a backtest and return to the checkpoint later.
+//! [MemoryFootprint](trait.MemoryFootprint.html) reports the bytes the state of an indicator
+//! takes, to budget the memory of many instances, e.g. one per symbol and timeframe.
+//!
+//! Moving averages and RSI implement [ProjectNext<T>](trait.ProjectNext.html) as well, which
+//! tells the value the indicator would have for a hypothetical next input. EMA, RSI and
+//! Bollinger Bands implement [SolveForInput](trait.SolveForInput.html), which goes the other way
+//! and finds the input producing a target output. [NextBatch<T>](trait.NextBatch.html) feeds a
+//! whole slice of inputs at once, so long backtests do not pay the overhead of every call.
+//! [Configure](trait.Configure.html) changes the period or the multiplier of an indicator at
+//! runtime, keeping as much of its state as the new parameters allow. The indicators of the
+//! [registry](registry/index.html) list and change their parameters by name through
+//! [Parameters](trait.Parameters.html).
+//!
+//! A `DataItem` can carry an optional timestamp, read through the
+//! [Timestamped](trait.Timestamped.html) trait by tools working with time instead of bar counts.
+//! Futures and order flow data can add the [open interest](trait.OpenInterest.html), the
+//! [trade count](trait.TradeCount.html) and the volume traded at the
+//! [bid](trait.BidVolume.html) and at the [ask](trait.AskVolume.html).
+//!
 //! # Example
 //! ```
 //! use ta::indicators::ExponentialMovingAverage;
@@ -24,6 +48,35 @@
 //! assert_eq!(ema.next(6.25), 4.25);
 //! ```
 //!
+//! # Value types
+//!
+//! The indicators taking values are generic over the value type, any
+//! [Float](https://docs.rs/num-traits/0.2/num_traits/float/trait.Float.html) type like `f32`
+//! halves the memory of their state: `SimpleMovingAverage`, `ExponentialMovingAverage`,
+//! `StandardDeviation`, `BollingerBands`, `MovingAverageConvergenceDivergence`,
+//! `PercentagePriceOscillator`, `RelativeStrengthIndex`, `RateOfChange`,
+//! `VolatilityAdjustedRateOfChange`, `RollingSharpe` and `ElderImpulse`. `f64` stays the
+//! default, so `new` and the type without parameters compute in `f64`, `with_period` or
+//! `with_periods` create them for another type. They accept bars in `f32` and `f64`.
+//!
+//! The other indicators compute in `f64` so far, among them those reading the high, low and
+//! volume of bars like the `AverageTrueRange` and the `KeltnerChannel`.
+//!
+//! # no_std
+//!
+//! The default `std` feature can be disabled to use the crate in `#![no_std]` environments
+//! with an allocator, e.g. embedded gateways or WASM runtimes. Float functions like `sqrt`
+//! then come from [libm](https://docs.rs/libm), so results may differ from the `std` build
+//! in the last bits.
+//!
+//! # SIMD
+//!
+//! The `simd` feature vectorizes the independent parts of `NextBatch::next_batch` of the
+//! `SimpleMovingAverage`, the `WeightedMovingAverage` and the `TrueRange` with the SIMD lanes
+//! of the [wide](https://docs.rs/wide) crate, on stable Rust: the divisions of the averages
+//! and the distances of the true range. The running sums keep the order of `next`, so the
+//! outputs are exactly the same as with `next`.
+//!
 //! # List of indicators
 //!
 //! * Trend
@@ -31,36 +84,280 @@
 //!   * [Simple Moving Average (SMA)](crate::indicators::SimpleMovingAverage)
 //!   * [Weighted Moving Average (WMA)](crate::indicators::WeightedMovingAverage)
 //!   * [Volume Weighted Average Price (VWAP)](crate::indicators::VolumeWeightedAveragePrice)
+//!   * [Time Decayed Volume Weighted Average Price (TDVWAP)](indicators/struct.TimeDecayedVolumeWeightedAveragePrice.html)
+//!   * [Volume Weighted Moving Average (VWMA)](crate::indicators::VolumeWeightedMovingAverage)
 //! * Oscillators
 //!   * [Relative Strength Index (RSI)](indicators/struct.RelativeStrengthIndex.html)
 //!   * [Fast Stochastic](indicators/struct.FastStochastic.html)
 //!   * [Slow Stochastic](indicators/struct.SlowStochastic.html)
+//!   * [Stochastic Momentum Index (SMI)](indicators/struct.StochasticMomentumIndex.html)
+//!   * [DeMarker (DeM)](indicators/struct.DeMarker.html)
 //!   * [Moving Average Convergence Divergence (MACD)](indicators/struct.MovingAverageConvergenceDivergence.html)
+//!   * [Volume Weighted MACD (VW-MACD)](indicators/struct.VolumeWeightedMacd.html)
+//!   * [Elder Impulse System](indicators/struct.ElderImpulse.html)
 //!   * [Percentage Price Oscillator (PPO)](indicators/struct.PercentagePriceOscillator.html)
 //!   * [Commodity Channel Index (CCI)](indicators/struct.CommodityChannelIndex.html)
 //!   * [Money Flow Index (MFI)](indicators/struct.MoneyFlowIndex.html)
 //! * Other
+//!   * [Typical Price (TP)](indicators/struct.TypicalPrice.html)
+//!   * [Median Price (MP)](indicators/struct.MedianPrice.html)
+//!   * [Weighted Close (WC)](indicators/struct.WeightedClose.html)
 //!   * [Standard Deviation (SD)](indicators/struct.StandardDeviation.html)
 //!   * [Mean Absolute Deviation (MAD)](indicators/struct.MeanAbsoluteDeviation.html)
+//!   * [Rolling Median](indicators/struct.RollingMedian.html)
+//!   * [Rolling Quantile](indicators/struct.RollingQuantile.html)
+//!   * [Rolling Skewness](indicators/struct.RollingSkewness.html)
+//!   * [Rolling Kurtosis](indicators/struct.RollingKurtosis.html)
+//!   * [Rolling Sharpe Ratio](indicators/struct.RollingSharpe.html)
+//!   * [Rolling Drawdown](indicators/struct.RollingDrawdown.html)
+//!   * [Wavelet Decomposition (MODWT)](indicators/struct.WaveletDecomposition.html)
+//!   * [Rolling Spectrum](indicators/struct.RollingSpectrum.html)
 //!   * [Bollinger Bands (BB)](indicators/struct.BollingerBands.html)
+//!   * [Band Position (%B)](indicators/struct.BandPosition.html)
 //!   * [Chandelier Exit (CE)](indicators/struct.ChandelierExit.html)
+//!   * [Volatility Stop (VStop)](indicators/struct.VolatilityStop.html)
 //!   * [Keltner Channel (KC)](indicators/struct.KeltnerChannel.html)
+//!   * [Williams VIX Fix (WVF)](indicators/struct.WilliamsVixFix.html)
+//!   * [TTM Squeeze](indicators/struct.TtmSqueeze.html)
 //!   * [Maximum](indicators/struct.Maximum.html)
 //!   * [Minimum](indicators/struct.Minimum.html)
+//!   * [Pivot High/Low](indicators/struct.PivotHighLow.html)
+//!   * [Market Structure](indicators/struct.MarketStructure.html)
+//!   * [Fibonacci Levels](indicators/struct.FibonacciLevels.html)
 //!   * [True Range](indicators/struct.TrueRange.html)
 //!   * [Average True Range (ATR)](indicators/struct.AverageTrueRange.html)
+//!   * [Normalized Average True Range (NATR)](indicators/struct.NormalizedAverageTrueRange.html)
+//!   * [Average Daily Range (ADR)](indicators/struct.AverageDailyRange.html)
+//!   * [WaveTrend (WT)](indicators/struct.WaveTrend.html)
 //!   * [Efficiency Ratio (ER)](indicators/struct.EfficiencyRatio.html)
 //!   * [Rate of Change (ROC)](indicators/struct.RateOfChange.html)
+//!   * [Volatility Adjusted Rate of Change (VAROC)](indicators/struct.VolatilityAdjustedRateOfChange.html)
+//!   * [Momentum Score](indicators/struct.MomentumScore.html)
 //!   * [On Balance Volume (OBV)](indicators/struct.OnBalanceVolume.html)
+//!   * [Intraday Intensity (II)](indicators/struct.IntradayIntensity.html)
+//!   * [Twiggs Money Flow (TMF)](indicators/struct.TwiggsMoneyFlow.html)
+//!   * [Volume Zone Oscillator (VZO)](indicators/struct.VolumeZoneOscillator.html)
+//!   * [Cumulative Delta](indicators/struct.CumulativeDelta.html)
+//!   * [Rolling Delta](indicators/struct.RollingDelta.html)
+//!   * [Prior Session Levels (PSL)](indicators/struct.PriorSessionLevels.html)
+//!   * [Session Stats](indicators/struct.SessionStats.html)
+//!   * [Divergence Detector](indicators/struct.DivergenceDetector.html)
+//!   * [Rolling Information Coefficient (IC)](indicators/struct.RollingIC.html)
+//!   * [Gap Guard](indicators/struct.GapGuard.html)
+//!   * [Cross](indicators/struct.Cross.html)
+//! * Breadth
+//!   * [Advance/Decline Line (ADL)](indicators/breadth/struct.AdvanceDeclineLine.html)
+//!   * [McClellan Oscillator](indicators/breadth/struct.McClellanOscillator.html)
+//!   * [Arms Index (TRIN)](indicators/breadth/struct.ArmsIndex.html)
+//! * Fixed windows (period known at compile time, no allocation)
+//!   * [Simple Moving Average (SMA)](indicators/fixed/struct.SimpleMovingAverage.html)
+//!   * [Weighted Moving Average (WMA)](indicators/fixed/struct.WeightedMovingAverage.html)
+//!   * [Standard Deviation (SD)](indicators/fixed/struct.StandardDeviation.html)
+//!   * [Maximum](indicators/fixed/struct.Maximum.html)
+//!   * [Minimum](indicators/fixed/struct.Minimum.html)
+//!   * [Rate of Change (ROC)](indicators/fixed/struct.RateOfChange.html)
+//!   * [Volume Weighted Average Price (VWAP)](indicators/fixed/struct.VolumeWeightedAveragePrice.html)
+//!
+//! # Arrow
+//!
+//! The [arrow](arrow/index.html) module (`arrow` feature) computes an
+//! [IndicatorSet](registry/struct.IndicatorSet.html) over the OHLCV columns of Arrow record
+//! batches, e.g. read from Parquet files or an Arrow Flight stream, and returns the outputs as
+//! record batches.
+//!
+//! # Bars
+//!
+//! The [bars](bars/index.html) module builds bars from other bars or from trades: the
+//! [Resampler](bars/struct.Resampler.html) turns e.g. 5 minute bars into hourly bars by their
+//! timestamps, the [BarAggregator](bars/struct.BarAggregator.html) builds time, tick,
+//! volume or dollar bars from single trades and the
+//! [RangeBarBuilder](bars/struct.RangeBarBuilder.html) builds range bars. The
+//! [GapFiller](bars/struct.GapFiller.html) fills in missing bars of patchy feeds.
+//! [Mtf](bars/struct.Mtf.html) runs an indicator on a higher timeframe, while it is fed the
+//! bars of a lower one.
+//!
+//! # Buffer
+//!
+//! A [PriceBuffer](buffer/struct.PriceBuffer.html) stores the last bars once for many window
+//! indicators, e.g. of a screener, which read their windows from it instead of keeping their
+//! own copies of the history.
+//!
+//! # Cache
+//!
+//! The [cache](cache/index.html) module (`std` feature) memoizes batch computations, so
+//! repeated runs of the same indicator over the same data are not recomputed.
+//!
+//! # Calendar
+//!
+//! The [calendar](calendar/index.html) module tells when the sessions of a market open and
+//! close, from session times, trading days, holidays and an offset to UTC, or around the
+//! clock for crypto markets. Session-aware indicators, e.g. the session VWAP of the
+//! [SessionStats](indicators/struct.SessionStats.html), start over at the sessions it reports.
+//!
+//! # Columns
+//!
+//! The [columns](columns/index.html) module runs an
+//! [IndicatorSet](registry/struct.IndicatorSet.html) over bars stored by column, e.g. the
+//! OHLCV columns of a dataframe or a record batch, picked by their standard names, or over
+//! bars stored by row, e.g. a 2-D array, and returns a named column per output. Batches of a
+//! stream are computed one after the other, the indicators continue from their state.
+//!
+//! # Combinators
+//!
+//! The [combinators](combinators/index.html) module derives indicators from other ones:
+//! `rsi.then(ema)` smooths the RSI with an EMA and `fast.diff(slow)` is the spread of two
+//! moving averages.
+//!
+//! # Decimal
+//!
+//! The [decimal](decimal/index.html) module (`decimal` feature) computes cumulative indicators
+//! like the OBV and the anchored VWAP in exact decimal arithmetic, for accounting-sensitive
+//! uses where the drift of binary floats is unacceptable.
+//!
+//! # Fixtures
+//!
+//! The [fixtures](fixtures/index.html) module (`fixtures` feature) generates golden output
+//! fixtures for indicator configurations from an input CSV and asserts against them later, so
+//! the behavior of the chosen indicators can be locked in across crate upgrades.
+//!
+//! # Iterators
+//!
+//! The [iter](iter/index.html) module runs indicators over iterators:
+//! `closes.into_iter().indicate(rsi)` yields the RSI of every close lazily.
 //!
+//! # Labels
+//!
+//! The [labels](labels/index.html) module generates targets for supervised learning:
+//! forward returns, triple barrier labels and meta-labels.
+//!
+//! # Live bars
+//!
+//! The [live](live/index.html) module wraps an indicator into a
+//! [LiveBar](live/struct.LiveBar.html), so the updates of a candle that is still forming can
+//! replace the last input instead of being counted as new bars. With a rollback buffer it
+//! reverts the last _n_ inputs, e.g. to replay them after a correction of the feed.
+//!
+//! # Models
+//!
+//! The [models](models/index.html) module contains online models, which learn one bar at a
+//! time to combine features into a signal, e.g. an online logistic regression.
+//!
+//! # ndarray
+//!
+//! The [ndarray](ndarray/index.html) module (`ndarray` feature) computes an
+//! [IndicatorSet](registry/struct.IndicatorSet.html) over a 2-D array of bars, or an indicator
+//! over a 1-D array of values, and returns the outputs as arrays.
+//!
+//! # Parallel
+//!
+//! The [parallel](parallel/index.html) module (`parallel` feature) computes indicators over
+//! many independent series, e.g. the bars of every symbol of a screener, on all cores with
+//! [rayon](https://docs.rs/rayon).
+//!
+//! # Patterns
+//!
+//! The [patterns](patterns/index.html) module recognizes candlestick patterns: doji, hammer,
+//! shooting star, engulfing, harami, morning/evening star, three white soldiers/black crows.
+//!
+//! # Polars
+//!
+//! The [polars](polars/index.html) module (`polars` feature) computes an
+//! [IndicatorSet](registry/struct.IndicatorSet.html) over the OHLCV columns of a polars
+//! `DataFrame` and returns the outputs as a dataframe, or an indicator over a `Series`.
+//!
+//! # Policy
+//!
+//! The [policy](policy/index.html) module applies an explicit policy to NaN, infinite and
+//! zero-volume inputs: skip them, carry the last valid input forward, return an error, or
+//! propagate them, so a single bad tick does not silently poison an indicator.
+//!
+//! # Registry
+//!
+//! The [registry](registry/index.html) module creates indicators from a name and a list of
+//! parameters, e.g. `rsi` with `period = 14`, behind an object-safe trait, so pipelines can be
+//! configured at runtime and extended with custom indicators. An
+//! [IndicatorSet](registry/struct.IndicatorSet.html) feeds every bar into many indicators at
+//! once and returns their values by name.
+//!
+//! # Revision
+//!
+//! The [revision](revision/index.html) module recomputes indicator outputs after historical
+//! bars were corrected and reports only the outputs and signals that changed.
+//!
+//! # Sensitivity
+//!
+//! The [sensitivity](sensitivity/index.html) module perturbs input prices with noise or a few
+//! ticks and reports how much the outputs of an indicator and the signals derived from them
+//! change, which shows how robust the indicator is to differences between data feeds.
+//!
+//! # State
+//!
+//! The [state](state/index.html) module (`serde` feature) tags serialized indicators with a
+//! layout version, so long-running services can persist their indicators across restarts and
+//! detect snapshots written by an incompatible version.
+//!
+//! # Strategy
+//!
+//! The [strategy](strategy/index.html) module helps to express multi-phase strategies as
+//! state machines driven by indicator values, and keeps track of the trade intents they emit
+//! until the intents expire. Composite signals built from weighted rules can explain every
+//! decision rule by rule for audit logs.
+//!
+//! # Validation
+//!
+//! The [validation](validation/index.html) module splits samples for cross-validation
+//! without leakage: purged K-fold and walk-forward splits with an embargo. It also benchmarks
+//! strategies against random baselines.
+//!
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 #[cfg(test)]
 #[macro_use]
 mod test_helper;
 
 mod helpers;
+mod ring_buffer;
+mod rolling_extremum;
+mod rolling_variance;
+#[cfg(feature = "simd")]
+mod simd;
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod bars;
+pub mod buffer;
+#[cfg(feature = "std")]
+pub mod cache;
+pub mod calendar;
+pub mod columns;
+pub mod combinators;
+#[cfg(feature = "decimal")]
+pub mod decimal;
 pub mod errors;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
 pub mod indicators;
+pub mod iter;
+pub mod labels;
+pub mod live;
+pub mod models;
+#[cfg(feature = "ndarray")]
+pub mod ndarray;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod patterns;
+#[cfg(feature = "polars")]
+pub mod polars;
+pub mod policy;
+pub mod registry;
+pub mod revision;
+pub mod sensitivity;
+#[cfg(feature = "serde")]
+pub mod state;
+pub mod strategy;
+pub mod validation;
 
 mod traits;
 pub use crate::traits::*;
@@ -0,0 +1,354 @@
+//! Live bars, which are still forming.
+//!
+//! A live feed updates the last candle many times before it closes. Passing every update to
+//! [next](../trait.Next.html) would count it as a new bar each time and fill the windows of the
+//! indicator with intrabar prices. [LiveBar](struct.LiveBar.html) keeps the state of the
+//! indicator before the last bar, so the last bar can be [replaced](../trait.Update.html) by
+//! its newer version or [undone](struct.LiveBar.html#method.undo_last).
+//!
+//! Exchange feeds also send corrections of trades or bars, which were already processed.
+//! [with_rollback](struct.LiveBar.html#method.with_rollback) keeps the states before the last
+//! _n_ inputs, so the indicator can [roll back](struct.LiveBar.html#method.rollback) to the
+//! corrected input and replay the inputs after it, instead of replaying the whole history.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::live::LiveBar;
+//! use ta::{Next, Update};
+//!
+//! let mut sma = LiveBar::new(SimpleMovingAverage::new(2).unwrap());
+//! sma.next(10.0);
+//!
+//! // the second bar is forming
+//! assert_eq!(sma.next(11.0), 10.5);
+//! assert_eq!(sma.replace(12.0), 11.0);
+//! assert_eq!(sma.replace(11.5), 10.75);
+//!
+//! // the second bar closed at 11.5, the third one starts
+//! assert_eq!(sma.next(13.0), 12.25);
+//! ```
+
+use alloc::collections::VecDeque;
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Current, IsReady, Next, Period, Reset, Update};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Wraps an indicator, so that its last inputs can be replaced or undone.
+///
+/// Every call of `next` keeps a copy of the indicator state before the input, which
+/// [replace](../trait.Update.html#tymethod.replace) and `undo_last` restore. By default only
+/// the last input can be revised: after an undo the copy is gone until the next input.
+/// [with_rollback](#method.with_rollback) keeps the copies of the last _n_ inputs, so that
+/// they can be undone one after another or at once with [rollback](#method.rollback). The
+/// copies are recycled, so a full history does not allocate for further inputs.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::live::LiveBar;
+/// use ta::Next;
+///
+/// let sma = SimpleMovingAverage::new(2).unwrap();
+/// let mut sma = LiveBar::new(sma).with_rollback(3).unwrap();
+/// for close in [10.0, 11.0, 12.0, 13.0] {
+///     sma.next(close);
+/// }
+///
+/// // the feed corrects the close of 11 to 15, revert it and the inputs after it
+/// assert_eq!(sma.rollback(3), 3);
+/// assert_eq!(sma.next(15.0), 12.5);
+/// assert_eq!(sma.next(12.0), 13.5);
+/// assert_eq!(sma.next(13.0), 12.5);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct LiveBar<I> {
+    indicator: I,
+    history: VecDeque<I>,
+    depth: usize,
+}
+
+impl<I> LiveBar<I> {
+    pub fn new(indicator: I) -> Self {
+        Self {
+            indicator,
+            history: VecDeque::new(),
+            depth: 1,
+        }
+    }
+
+    /// Keeps the states before the last `depth` inputs instead of only the last one.
+    pub fn with_rollback(mut self, depth: usize) -> Result<Self> {
+        if depth == 0 {
+            return Err(TaError::zero_period("depth"));
+        }
+        self.depth = depth;
+        while self.history.len() > depth {
+            self.history.pop_front();
+        }
+        Ok(self)
+    }
+
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    /// Maximum number of inputs, which can be undone.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Number of inputs, which can be undone now.
+    pub fn revertible(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Restores the state before the last input. Does nothing, if there is no input to undo.
+    pub fn undo_last(&mut self) {
+        self.rollback(1);
+    }
+
+    /// Restores the state before the last `n` inputs, or before as many of them as possible,
+    /// and returns the number of undone inputs.
+    pub fn rollback(&mut self, n: usize) -> usize {
+        let n = n.min(self.history.len());
+        if n > 0 {
+            let keep = self.history.len() - n;
+            self.history.truncate(keep + 1);
+            if let Some(state) = self.history.pop_back() {
+                self.indicator = state;
+            }
+        }
+        n
+    }
+}
+
+impl<I: Period> Period for LiveBar<I> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: IsReady> IsReady for LiveBar<I> {
+    fn bars_until_ready(&self) -> usize {
+        self.indicator.bars_until_ready()
+    }
+}
+
+impl<I, T> Next<T> for LiveBar<I>
+where
+    I: Next<T> + Clone,
+{
+    type Output = I::Output;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let state = if self.history.len() == self.depth {
+            self.history.pop_front().map(|mut state| {
+                state.clone_from(&self.indicator);
+                state
+            })
+        } else {
+            None
+        };
+        let state = state.unwrap_or_else(|| self.indicator.clone());
+        self.history.push_back(state);
+        self.indicator.next(input)
+    }
+}
+
+impl<I, T> Update<T> for LiveBar<I>
+where
+    I: Next<T> + Clone,
+{
+    fn replace(&mut self, input: T) -> Self::Output {
+        match self.history.back() {
+            Some(previous) => {
+                self.indicator.clone_from(previous);
+                self.indicator.next(input)
+            }
+            None => self.next(input),
+        }
+    }
+}
+
+impl<I: Current> Current for LiveBar<I> {
+    type Output = I::Output;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.indicator.current()
+    }
+}
+
+impl<I: Reset> Reset for LiveBar<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.history.clear();
+    }
+}
+
+impl<I: Default> Default for LiveBar<I> {
+    fn default() -> Self {
+        Self::new(I::default())
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for LiveBar<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LIVE({})", self.indicator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{AverageTrueRange, SimpleMovingAverage};
+    use crate::test_helper::*;
+
+    fn sma(period: usize) -> SimpleMovingAverage {
+        SimpleMovingAverage::new(period).unwrap()
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut live = LiveBar::new(sma(3));
+        let mut closed = sma(3);
+
+        for close in [1.0, 2.0, 3.0] {
+            assert_eq!(live.next(close), closed.next(close));
+        }
+        live.next(10.0);
+        live.replace(20.0);
+        assert_eq!(live.replace(4.0), closed.next(4.0));
+        assert_eq!(live.next(5.0), closed.next(5.0));
+    }
+
+    #[test]
+    fn test_replace_bars() {
+        let bar = |high: f64, low: f64, close: f64| Bar::new().high(high).low(low).close(close);
+        let mut live = LiveBar::new(AverageTrueRange::new(3).unwrap());
+        let mut closed = AverageTrueRange::new(3).unwrap();
+
+        live.next(&bar(10.0, 8.0, 9.0));
+        closed.next(&bar(10.0, 8.0, 9.0));
+        live.next(&bar(9.5, 9.0, 9.2));
+        live.replace(&bar(12.0, 9.0, 11.0));
+        assert_eq!(
+            live.replace(&bar(11.0, 9.0, 10.0)),
+            closed.next(&bar(11.0, 9.0, 10.0))
+        );
+    }
+
+    #[test]
+    fn test_replace_first() {
+        let mut live = LiveBar::new(sma(2));
+        assert_eq!(live.replace(4.0), 4.0);
+        assert_eq!(live.replace(6.0), 6.0);
+        assert_eq!(live.next(8.0), 7.0);
+    }
+
+    #[test]
+    fn test_undo_last() {
+        let mut live = LiveBar::new(sma(2));
+        live.next(2.0);
+        live.next(100.0);
+
+        live.undo_last();
+        assert_eq!(live.current(), Some(2.0));
+        assert_eq!(live.next(4.0), 3.0);
+
+        // only the last input can be undone
+        live.undo_last();
+        live.undo_last();
+        assert_eq!(live.current(), Some(2.0));
+
+        let mut empty = LiveBar::new(sma(2));
+        empty.undo_last();
+        assert_eq!(empty.current(), None);
+    }
+
+    #[test]
+    fn test_rollback() {
+        let mut live = LiveBar::new(sma(2)).with_rollback(3).unwrap();
+        let mut replayed = sma(2);
+        for close in [1.0, 2.0, 3.0, 4.0, 5.0] {
+            live.next(close);
+        }
+        assert_eq!(live.revertible(), 3);
+
+        assert_eq!(live.rollback(2), 2);
+        for close in [1.0, 2.0, 3.0] {
+            replayed.next(close);
+        }
+        assert_eq!(live.current(), replayed.current());
+        assert_eq!(live.next(10.0), replayed.next(10.0));
+
+        // the history is limited to the depth
+        assert_eq!(live.revertible(), 2);
+        assert_eq!(live.rollback(5), 2);
+        assert_eq!(live.current(), Some(1.5));
+        assert_eq!(live.rollback(1), 0);
+    }
+
+    #[test]
+    fn test_rollback_replace() {
+        let mut live = LiveBar::new(sma(2)).with_rollback(2).unwrap();
+        live.next(2.0);
+        live.next(4.0);
+        assert_eq!(live.replace(8.0), 5.0);
+        live.undo_last();
+        live.undo_last();
+        assert_eq!(live.current(), None);
+    }
+
+    #[test]
+    fn test_with_rollback() {
+        assert!(LiveBar::new(sma(2)).with_rollback(0).is_err());
+
+        let mut live = LiveBar::new(sma(2)).with_rollback(4).unwrap();
+        for close in [1.0, 2.0, 3.0, 4.0] {
+            live.next(close);
+        }
+        let live = live.with_rollback(2).unwrap();
+        assert_eq!(live.depth(), 2);
+        assert_eq!(live.revertible(), 2);
+        assert_eq!(LiveBar::new(sma(2)).depth(), 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut live = LiveBar::new(sma(2));
+        live.next(2.0);
+        live.next(4.0);
+
+        live.reset();
+        live.undo_last();
+        assert_eq!(live.current(), None);
+        assert_eq!(live.next(6.0), 6.0);
+    }
+
+    #[test]
+    fn test_is_ready() {
+        assert_warm_up(&mut LiveBar::new(sma(3)), 1.0, 3);
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(LiveBar::new(sma(5)).period(), 5);
+    }
+
+    #[test]
+    fn test_default() {
+        LiveBar::<SimpleMovingAverage>::default();
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", LiveBar::new(sma(3))), "LIVE(SMA(3))");
+    }
+}
@@ -0,0 +1,236 @@
+//! Online models combining features into trading signals.
+//!
+//! The models learn one bar at a time, so they adapt to changing markets without being
+//! refitted on the whole history. Features are plain `f64` slices, e.g. the outputs of several
+//! indicators computed on the same bar, and the targets can be taken from the
+//! [labels](../labels/index.html) module.
+//!
+//! Labels look into the future, so a model must be updated with the features of a bar only
+//! once its label is known, e.g. `horizon` bars later for forward returns.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::models::OnlineLogisticRegression;
+//!
+//! let mut model = OnlineLogisticRegression::new(2, 0.5, 0.0).unwrap();
+//! assert_eq!(model.predict(&[1.0, -1.0]), 0.5);
+//!
+//! // the first feature predicts up moves
+//! for _ in 0..50 {
+//!     model.update(&[1.0, 0.0], true);
+//!     model.update(&[-1.0, 0.0], false);
+//! }
+//! assert!(model.predict(&[1.0, 0.0]) > 0.9);
+//! assert!(model.predict(&[-1.0, 0.0]) < 0.1);
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::Reset;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Logistic regression trained with stochastic gradient descent.
+///
+/// Estimates the probability of a binary outcome (e.g. a positive forward return or a
+/// profitable [meta-label](../labels/struct.TripleBarrier.html#method.meta_label)) as
+/// _σ(w · x + b)_. Every [update](#method.update) takes one gradient step of the log loss
+/// with L2 regularization of the weights:
+///
+/// _w = w - learning_rate * ((p - y) * x + l2 * w)_
+///
+/// The features should have comparable scales (e.g. z-scores or oscillators scaled to -1..1),
+/// otherwise the features with large values dominate the steps.
+///
+/// # Parameters
+///
+/// * _features_ - number of features (integer greater than 0)
+/// * _learning_rate_ - size of the gradient steps (number greater than 0)
+/// * _l2_ - strength of the L2 regularization (number greater or equal to 0)
+///
+/// # Links
+///
+/// * [Logistic regression, Wikipedia](https://en.wikipedia.org/wiki/Logistic_regression)
+/// * [Stochastic gradient descent, Wikipedia](https://en.wikipedia.org/wiki/Stochastic_gradient_descent)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct OnlineLogisticRegression {
+    learning_rate: f64,
+    l2: f64,
+    weights: Vec<f64>,
+    bias: f64,
+}
+
+impl OnlineLogisticRegression {
+    pub fn new(features: usize, learning_rate: f64, l2: f64) -> Result<Self> {
+        if features == 0 {
+            return Err(TaError::zero_period("features"));
+        }
+        if learning_rate.is_nan() || learning_rate <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "learning_rate",
+                learning_rate,
+                "must be greater than 0",
+            ));
+        }
+        if l2.is_nan() || l2 < 0.0 {
+            return Err(TaError::invalid_parameter("l2", l2, "must not be negative"));
+        }
+        Ok(Self {
+            learning_rate,
+            l2,
+            weights: vec![0.0; features],
+            bias: 0.0,
+        })
+    }
+
+    pub fn features(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn learning_rate(&self) -> f64 {
+        self.learning_rate
+    }
+
+    pub fn l2(&self) -> f64 {
+        self.l2
+    }
+
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    pub fn bias(&self) -> f64 {
+        self.bias
+    }
+
+    /// Probability of the positive outcome for the given features.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of features differs from the one the model was created with.
+    pub fn predict(&self, features: &[f64]) -> f64 {
+        assert_eq!(
+            features.len(),
+            self.weights.len(),
+            "wrong number of features"
+        );
+        let z = self
+            .weights
+            .iter()
+            .zip(features)
+            .fold(self.bias, |z, (w, x)| z + w * x);
+        sigmoid(z)
+    }
+
+    /// Learns from the outcome of one sample and returns the probability predicted before the
+    /// update.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of features differs from the one the model was created with.
+    pub fn update(&mut self, features: &[f64], outcome: bool) -> f64 {
+        let p = self.predict(features);
+        let error = p - if outcome { 1.0 } else { 0.0 };
+        for (w, x) in self.weights.iter_mut().zip(features) {
+            *w -= self.learning_rate * (error * x + self.l2 * *w);
+        }
+        self.bias -= self.learning_rate * error;
+        p
+    }
+}
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+impl Reset for OnlineLogisticRegression {
+    fn reset(&mut self) {
+        self.weights.iter_mut().for_each(|w| *w = 0.0);
+        self.bias = 0.0;
+    }
+}
+
+impl fmt::Display for OnlineLogisticRegression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "LOGREG({}, {}, {})",
+            self.features(),
+            self.learning_rate,
+            self.l2
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    #[test]
+    fn test_new() {
+        assert!(OnlineLogisticRegression::new(0, 0.1, 0.0).is_err());
+        assert!(OnlineLogisticRegression::new(1, 0.0, 0.0).is_err());
+        assert!(OnlineLogisticRegression::new(1, f64::NAN, 0.0).is_err());
+        assert!(OnlineLogisticRegression::new(1, 0.1, -1.0).is_err());
+        assert!(OnlineLogisticRegression::new(1, 0.1, 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_update() {
+        let mut model = OnlineLogisticRegression::new(2, 0.5, 0.0).unwrap();
+
+        // the prediction is made before the step
+        assert_eq!(model.update(&[1.0, 2.0], true), 0.5);
+        assert_eq!(model.weights(), &[0.25, 0.5]);
+        assert_eq!(model.bias(), 0.25);
+        assert_eq!(round(model.predict(&[1.0, 2.0])), 0.818);
+
+        model.update(&[1.0, 2.0], false);
+        assert_eq!(round(model.weights()[0]), -0.159);
+        assert_eq!(round(model.bias()), -0.159);
+    }
+
+    #[test]
+    fn test_l2() {
+        let mut plain = OnlineLogisticRegression::new(1, 0.1, 0.0).unwrap();
+        let mut regularized = OnlineLogisticRegression::new(1, 0.1, 1.0).unwrap();
+        for _ in 0..100 {
+            plain.update(&[1.0], true);
+            regularized.update(&[1.0], true);
+        }
+        assert!(regularized.weights()[0] < plain.weights()[0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_wrong_number_of_features() {
+        let model = OnlineLogisticRegression::new(2, 0.1, 0.0).unwrap();
+        model.predict(&[1.0]);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut model = OnlineLogisticRegression::new(2, 0.1, 0.0).unwrap();
+        model.update(&[1.0, -1.0], true);
+
+        model.reset();
+        assert_eq!(model.weights(), &[0.0, 0.0]);
+        assert_eq!(model.bias(), 0.0);
+        assert_eq!(model.predict(&[1.0, -1.0]), 0.5);
+    }
+
+    #[test]
+    fn test_display() {
+        let model = OnlineLogisticRegression::new(3, 0.05, 0.001).unwrap();
+        assert_eq!(format!("{}", model), "LOGREG(3, 0.05, 0.001)");
+    }
+}
@@ -0,0 +1,181 @@
+//! Indicators computed over [ndarray](https://docs.rs/ndarray) arrays (`ndarray` feature).
+//!
+//! [compute](fn.compute.html) runs the indicators of an
+//! [IndicatorSet](../registry/struct.IndicatorSet.html) over a 2-D view of bars, whose rows
+//! are the bars and whose columns are open, high, low, close and volume, and returns an array
+//! per output, named like the outputs of the set. [apply](fn.apply.html) feeds a 1-D view of
+//! values to an indicator, e.g. the closes to an RSI, and returns the array of its outputs, so
+//! the results compare element by element with the arrays of other libraries like TA-Lib.
+//!
+//! Views in row-major or column-major order are read in place, other views, e.g. slices with a
+//! step, are copied into row-major order first.
+//!
+//! # Example
+//!
+//! ```
+//! use ndarray::{array, Array1};
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::registry::IndicatorSet;
+//!
+//! let bars = array![
+//!     [10.0, 10.5, 9.5, 10.0, 100.0],
+//!     [11.0, 11.5, 10.5, 11.0, 120.0],
+//!     [12.0, 12.5, 11.5, 12.0, 80.0],
+//! ];
+//!
+//! let mut set = IndicatorSet::new();
+//! set.create("tr", "tr", &[]).unwrap();
+//! let results = ta::ndarray::compute(&mut set, bars.view()).unwrap();
+//! assert_eq!(results[0], ("tr".to_string(), array![1.0, 1.5, 1.5]));
+//!
+//! let mut sma = SimpleMovingAverage::new(2).unwrap();
+//! let sma: Array1<f64> = ta::ndarray::apply(&mut sma, bars.column(3));
+//! assert_eq!(sma, array![10.0, 10.5, 11.5]);
+//! ```
+
+use ndarray::{Array1, ArrayView1, ArrayView2, Axis};
+
+use crate::columns::{self, OhlcvColumns, OhlcvRows};
+use crate::errors::{Result, TaError};
+use crate::registry::IndicatorSet;
+use crate::Next;
+
+/// Feeds all the rows of `bars` to the indicators of `set`, and returns a named array of
+/// values per output, in the order of the indicators in the set.
+///
+/// The indicators continue from their current state, like feeding the rows with
+/// [next](../trait.Next.html). Fails with
+/// [LengthMismatch](../errors/enum.TaError.html#variant.LengthMismatch), if `bars` doesn't have
+/// 5 columns, and with the error of the first invalid bar, e.g. a high below the low.
+pub fn compute(
+    set: &mut IndicatorSet,
+    bars: ArrayView2<f64>,
+) -> Result<Vec<(String, Array1<f64>)>> {
+    if bars.ncols() != OhlcvRows::COLUMNS {
+        return Err(TaError::LengthMismatch {
+            expected: OhlcvRows::COLUMNS,
+            actual: bars.ncols(),
+        });
+    }
+
+    let column = |index| bars.index_axis_move(Axis(1), index).to_slice();
+    let results = if let Some(values) = bars.to_slice() {
+        columns::compute_rows(set, &OhlcvRows::new(values)?)?
+    } else if let [Some(open), Some(high), Some(low), Some(close), Some(volume)] =
+        [0, 1, 2, 3, 4].map(column)
+    {
+        let columns = OhlcvColumns {
+            open,
+            high,
+            low,
+            close,
+            volume,
+        };
+        columns::compute(set, &columns)?
+    } else {
+        let bars = bars.as_standard_layout();
+        let values = bars.as_slice().expect("standard layout");
+        columns::compute_rows(set, &OhlcvRows::new(values)?)?
+    };
+
+    Ok(results
+        .into_iter()
+        .map(|(name, values)| (name, Array1::from(values)))
+        .collect())
+}
+
+/// Feeds every value of `values` to `indicator`, and returns the array of its outputs.
+pub fn apply<I: Next<f64>>(indicator: &mut I, values: ArrayView1<f64>) -> Array1<I::Output> {
+    values.iter().map(|&value| indicator.next(value)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{MovingAverageConvergenceDivergence as Macd, RelativeStrengthIndex};
+    use ndarray::{s, Array2, ShapeBuilder};
+
+    #[rustfmt::skip]
+    const BARS: [f64; 25] = [
+        10.0, 10.5, 9.5, 10.0, 100.0,
+        11.0, 11.5, 10.5, 11.0, 120.0,
+        12.0, 12.5, 11.5, 12.0, 80.0,
+        11.0, 11.5, 10.5, 11.0, 90.0,
+        12.5, 13.0, 12.0, 12.5, 150.0,
+    ];
+
+    fn bars() -> Array2<f64> {
+        Array2::from_shape_vec((5, 5), BARS.to_vec()).unwrap()
+    }
+
+    fn set() -> IndicatorSet {
+        let mut set = IndicatorSet::new();
+        set.create("rsi", "rsi", &[("period", 3.0)]).unwrap();
+        set.create("macd", "macd", &[]).unwrap();
+        set.create("atr", "atr", &[("period", 2.0)]).unwrap();
+        set
+    }
+
+    fn expected() -> Vec<(String, Array1<f64>)> {
+        let rows = OhlcvRows::new(&BARS).unwrap();
+        columns::compute_rows(&mut set(), &rows)
+            .unwrap()
+            .into_iter()
+            .map(|(name, values)| (name, Array1::from(values)))
+            .collect()
+    }
+
+    #[test]
+    fn test_compute() {
+        let results = compute(&mut set(), bars().view()).unwrap();
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[1].0, "macd.macd");
+        assert_eq!(results, expected());
+    }
+
+    #[test]
+    fn test_layouts() {
+        let mut column_major = Array2::zeros((5, 5).f());
+        column_major.assign(&bars());
+        assert!(column_major.column(0).as_slice().is_some());
+        assert_eq!(
+            compute(&mut set(), column_major.view()).unwrap(),
+            expected()
+        );
+
+        // every other row of a larger array
+        let mut strided = Array2::zeros((10, 5));
+        strided.slice_mut(s![..;2, ..]).assign(&bars());
+        let strided = strided.slice(s![..;2, ..]);
+        assert_eq!(compute(&mut set(), strided).unwrap(), expected());
+    }
+
+    #[test]
+    fn test_columns() {
+        let bars = Array2::<f64>::zeros((3, 4));
+        assert_eq!(
+            compute(&mut set(), bars.view()),
+            Err(TaError::LengthMismatch {
+                expected: 5,
+                actual: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let bars = bars();
+        let closes = bars.column(3);
+
+        let mut rsi = RelativeStrengthIndex::new(3).unwrap();
+        let outputs = apply(&mut rsi, closes);
+        let mut rsi = RelativeStrengthIndex::new(3).unwrap();
+        let expected: Vec<f64> = closes.iter().map(|&close| rsi.next(close)).collect();
+        assert_eq!(outputs.to_vec(), expected);
+
+        // any output type
+        let outputs = apply(&mut Macd::default(), closes);
+        assert_eq!(outputs.len(), 5);
+        assert_eq!(outputs[0].macd, 0.0);
+    }
+}
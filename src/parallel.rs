@@ -0,0 +1,213 @@
+//! Indicators computed over many independent series in parallel.
+//!
+//! Screeners run the same indicators over the history of hundreds of symbols, and the series of
+//! one symbol doesn't depend on the others. [compute_many](fn.compute_many.html) feeds every
+//! series of values into its own indicator state and spreads the series over the available
+//! cores, [compute_many_bars](fn.compute_many_bars.html) does the same for series of bars, e.g.
+//! with an [IndicatorSet](../registry/struct.IndicatorSet.html) per symbol.
+//!
+//! The states are updated in place, so a screener can keep them and feed only the new bars on
+//! the next run. The outputs are the same as feeding the series one after another with `next`.
+//! The series run on the global [rayon](https://docs.rs/rayon) thread pool, whose work stealing
+//! balances series of different lengths. Call the functions within `ThreadPool::install` to
+//! run them on a pool of your own.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::parallel::compute_many;
+//!
+//! let closes = vec![vec![1.0, 2.0, 3.0], vec![10.0, 20.0], vec![5.0]];
+//! let mut states = vec![SimpleMovingAverage::new(2).unwrap(); closes.len()];
+//!
+//! let outputs = compute_many(&mut states, &closes).unwrap();
+//! assert_eq!(outputs[0], vec![1.0, 1.5, 2.5]);
+//! assert_eq!(outputs[1], vec![10.0, 15.0]);
+//! assert_eq!(outputs[2], vec![5.0]);
+//! ```
+
+use rayon::prelude::*;
+
+use crate::errors::{Result, TaError};
+use crate::Next;
+
+/// Feeds every series of values into the state at the same position, in parallel, and returns
+/// the outputs per series.
+///
+/// Returns `TaError::LengthMismatch` if the number of series differs from the number of
+/// states, without updating any state.
+pub fn compute_many<I, T, S, O>(states: &mut [I], series: &[S]) -> Result<Vec<Vec<O>>>
+where
+    I: Next<T, Output = O> + Send,
+    T: Copy,
+    S: AsRef<[T]> + Sync,
+    O: Send,
+{
+    spread(states, series, |state, series| {
+        series
+            .as_ref()
+            .iter()
+            .map(|&input| state.next(input))
+            .collect()
+    })
+}
+
+/// Feeds every series of bars into the state at the same position, in parallel, and returns
+/// the outputs per series.
+///
+/// Returns `TaError::LengthMismatch` if the number of series differs from the number of
+/// states, without updating any state.
+pub fn compute_many_bars<I, B, S, O>(states: &mut [I], series: &[S]) -> Result<Vec<Vec<O>>>
+where
+    I: for<'a> Next<&'a B, Output = O> + Send,
+    S: AsRef<[B]> + Sync,
+    O: Send,
+{
+    spread(states, series, |state, series| {
+        series.as_ref().iter().map(|bar| state.next(bar)).collect()
+    })
+}
+
+fn spread<I, S, O, F>(states: &mut [I], series: &[S], compute: F) -> Result<Vec<Vec<O>>>
+where
+    I: Send,
+    S: Sync,
+    O: Send,
+    F: Fn(&mut I, &S) -> Vec<O> + Sync,
+{
+    if series.len() != states.len() {
+        return Err(TaError::LengthMismatch {
+            expected: states.len(),
+            actual: series.len(),
+        });
+    }
+
+    Ok(states
+        .par_iter_mut()
+        .zip(series)
+        .map(|(state, series)| compute(state, series))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, TrueRange};
+    use crate::registry::IndicatorSet;
+    use crate::test_helper::*;
+    use crate::{Current, DataItem};
+
+    fn series(count: usize) -> Vec<Vec<f64>> {
+        (0..count)
+            .map(|s| {
+                (0..s % 7 + 1)
+                    .map(|i| (s * 3 + i * 5 % 11) as f64)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compute_many() {
+        let series = series(37);
+        let mut states = vec![ExponentialMovingAverage::new(3).unwrap(); series.len()];
+        let outputs = compute_many(&mut states, &series).unwrap();
+
+        assert_eq!(outputs.len(), series.len());
+        for ((outputs, series), state) in outputs.iter().zip(&series).zip(&states) {
+            let mut expected = ExponentialMovingAverage::new(3).unwrap();
+            let expected: Vec<f64> = series.iter().map(|&x| expected.next(x)).collect();
+            assert_eq!(outputs, &expected);
+            // the states keep going from the end of their series
+            assert_eq!(state.current(), expected.last().copied());
+        }
+    }
+
+    #[test]
+    fn test_thread_pools() {
+        // independent of the cores of the machine running the tests
+        let series = series(10);
+        let run = |threads| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let mut states = vec![ExponentialMovingAverage::new(3).unwrap(); series.len()];
+            let outputs = pool.install(|| compute_many(&mut states, &series).unwrap());
+            let states: Vec<_> = states.iter().map(|state| state.current()).collect();
+            (outputs, states)
+        };
+        let expected = run(1);
+        for threads in [2, 3, 4, 16] {
+            assert_eq!(run(threads), expected);
+        }
+    }
+
+    #[test]
+    fn test_compute_many_bars() {
+        let bars: Vec<Vec<Bar>> = series(9)
+            .iter()
+            .map(|s| {
+                s.iter()
+                    .map(|&x| Bar::new().high(x + 1.0).low(x).close(x))
+                    .collect()
+            })
+            .collect();
+        let mut states = vec![TrueRange::new(); bars.len()];
+        let outputs = compute_many_bars(&mut states, &bars).unwrap();
+
+        for (outputs, bars) in outputs.iter().zip(&bars) {
+            let mut expected = TrueRange::new();
+            let expected: Vec<f64> = bars.iter().map(|bar| expected.next(bar)).collect();
+            assert_eq!(outputs, &expected);
+        }
+    }
+
+    #[test]
+    fn test_indicator_sets() {
+        let bar = |close: f64| {
+            DataItem::builder()
+                .open(close)
+                .high(close + 1.0)
+                .low(close - 1.0)
+                .close(close)
+                .volume(100.0)
+                .build()
+                .unwrap()
+        };
+        let bars: Vec<Vec<DataItem>> = vec![vec![bar(10.0), bar(12.0)], vec![bar(20.0)]];
+        let mut sets: Vec<IndicatorSet> = (0..2)
+            .map(|_| {
+                let mut set = IndicatorSet::new();
+                set.create("fast", "ema", &[("period", 3.0)]).unwrap();
+                set
+            })
+            .collect();
+
+        let outputs = compute_many_bars(&mut sets, &bars).unwrap();
+        assert_eq!(outputs[0][1]["fast"], 11.0);
+        assert_eq!(outputs[1][0]["fast"], 20.0);
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        let mut states = vec![ExponentialMovingAverage::new(3).unwrap(); 2];
+        let result = compute_many(&mut states, &series(3));
+        assert_eq!(
+            result.unwrap_err(),
+            TaError::LengthMismatch {
+                expected: 2,
+                actual: 3
+            }
+        );
+        assert_eq!(states[0].current(), None);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut states: Vec<ExponentialMovingAverage> = Vec::new();
+        let series: Vec<Vec<f64>> = Vec::new();
+        assert!(compute_many(&mut states, &series).unwrap().is_empty());
+    }
+}
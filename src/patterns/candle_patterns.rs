@@ -0,0 +1,461 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::patterns::{Pattern, PatternSet};
+use crate::{Close, High, Low, MemoryFootprint, Next, Open, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Candlestick pattern recognizer.
+///
+/// Returns the [set of patterns](struct.PatternSet.html) completed by every bar. The shapes are
+/// measured relative to the range (high - low) of a bar:
+///
+/// * doji - body not larger than _doji_body_ of the range
+/// * hammer - small body (not larger than _small_body_ of the range), lower shadow at least
+///   _shadow_ratio_ times the body and upper shadow not larger than _doji_body_ of the range
+/// * shooting star - the same as hammer with the shadows swapped
+/// * bullish engulfing - bearish bar followed by a bullish bar, whose larger body covers the
+///   previous body
+/// * bullish harami - long bearish bar (body larger than _small_body_ of the range) followed by
+///   a bullish bar, whose body lies within the previous body
+/// * morning star - long bearish bar, a small body not above its close, and a bullish bar
+///   closing above the midpoint of the first body
+/// * three white soldiers - three long bullish bars, each opening within the previous body and
+///   closing higher
+///
+/// The bearish patterns (engulfing, harami, evening star, three black crows) mirror the bullish
+/// ones. A bar with no range (open = high = low = close) is a doji.
+///
+/// # Parameters
+///
+/// * _doji_body_ - maximum body of a doji relative to the range (number in range (0, 1)).
+///   Default is 0.1.
+/// * _small_body_ - maximum small body relative to the range (number in range
+///   [_doji_body_, 1)). Default is 0.3.
+/// * _shadow_ratio_ - minimum length of a hammer shadow relative to the body (number greater
+///   than 0). Default is 2.0.
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::{CandlePatterns, Pattern};
+/// use ta::{DataItem, Next};
+///
+/// let mut patterns = CandlePatterns::new(0.1, 0.3, 2.0).unwrap();
+///
+/// let hammer = DataItem::builder()
+///     .open(9.8).high(10.0).low(7.0).close(10.0).volume(100.0)
+///     .build().unwrap();
+/// assert!(patterns.next(&hammer).contains(Pattern::Hammer));
+/// ```
+///
+/// # Links
+///
+/// * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+///
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct CandlePatterns {
+    doji_body: f64,
+    small_body: f64,
+    shadow_ratio: f64,
+    prev: Option<Candle>,
+    prev2: Option<Candle>,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+struct Candle {
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+}
+
+impl Candle {
+    fn body(&self) -> f64 {
+        (self.close - self.open).abs()
+    }
+
+    fn range(&self) -> f64 {
+        self.high - self.low
+    }
+
+    fn body_top(&self) -> f64 {
+        self.open.max(self.close)
+    }
+
+    fn body_bottom(&self) -> f64 {
+        self.open.min(self.close)
+    }
+
+    fn midpoint(&self) -> f64 {
+        (self.open + self.close) / 2.0
+    }
+
+    fn upper_shadow(&self) -> f64 {
+        self.high - self.body_top()
+    }
+
+    fn lower_shadow(&self) -> f64 {
+        self.body_bottom() - self.low
+    }
+
+    fn is_bullish(&self) -> bool {
+        self.close > self.open
+    }
+
+    fn is_bearish(&self) -> bool {
+        self.close < self.open
+    }
+}
+
+impl CandlePatterns {
+    pub fn new(doji_body: f64, small_body: f64, shadow_ratio: f64) -> Result<Self> {
+        if doji_body <= 0.0 || doji_body >= 1.0 {
+            return Err(TaError::invalid_parameter(
+                "doji_body",
+                doji_body,
+                "must be between 0 and 1",
+            ));
+        }
+        if small_body < doji_body || small_body >= 1.0 {
+            return Err(TaError::invalid_parameter(
+                "small_body",
+                small_body,
+                "must be between doji_body and 1",
+            ));
+        }
+        if shadow_ratio <= 0.0 {
+            return Err(TaError::invalid_parameter(
+                "shadow_ratio",
+                shadow_ratio,
+                "must be greater than 0",
+            ));
+        }
+        Ok(Self {
+            doji_body,
+            small_body,
+            shadow_ratio,
+            prev: None,
+            prev2: None,
+        })
+    }
+
+    pub fn doji_body(&self) -> f64 {
+        self.doji_body
+    }
+
+    pub fn small_body(&self) -> f64 {
+        self.small_body
+    }
+
+    pub fn shadow_ratio(&self) -> f64 {
+        self.shadow_ratio
+    }
+
+    fn is_doji(&self, c: &Candle) -> bool {
+        c.body() <= self.doji_body * c.range()
+    }
+
+    fn is_small(&self, c: &Candle) -> bool {
+        c.body() <= self.small_body * c.range()
+    }
+
+    fn is_long(&self, c: &Candle) -> bool {
+        !self.is_small(c)
+    }
+
+    fn is_hammer(&self, c: &Candle) -> bool {
+        c.range() > 0.0
+            && self.is_small(c)
+            && c.lower_shadow() >= self.shadow_ratio * c.body()
+            && c.upper_shadow() <= self.doji_body * c.range()
+    }
+
+    fn is_shooting_star(&self, c: &Candle) -> bool {
+        c.range() > 0.0
+            && self.is_small(c)
+            && c.upper_shadow() >= self.shadow_ratio * c.body()
+            && c.lower_shadow() <= self.doji_body * c.range()
+    }
+
+    fn two_bar(&self, set: &mut PatternSet, prev: &Candle, cur: &Candle) {
+        if prev.is_bearish() && cur.is_bullish() {
+            if cur.open <= prev.close && cur.close >= prev.open && cur.body() > prev.body() {
+                set.insert(Pattern::BullishEngulfing);
+            }
+            if self.is_long(prev) && cur.open > prev.close && cur.close < prev.open {
+                set.insert(Pattern::BullishHarami);
+            }
+        }
+        if prev.is_bullish() && cur.is_bearish() {
+            if cur.open >= prev.close && cur.close <= prev.open && cur.body() > prev.body() {
+                set.insert(Pattern::BearishEngulfing);
+            }
+            if self.is_long(prev) && cur.open < prev.close && cur.close > prev.open {
+                set.insert(Pattern::BearishHarami);
+            }
+        }
+    }
+
+    fn three_bar(&self, set: &mut PatternSet, first: &Candle, star: &Candle, cur: &Candle) {
+        if first.is_bearish()
+            && self.is_long(first)
+            && self.is_small(star)
+            && star.body_top() <= first.close
+            && cur.is_bullish()
+            && cur.close > first.midpoint()
+        {
+            set.insert(Pattern::MorningStar);
+        }
+        if first.is_bullish()
+            && self.is_long(first)
+            && self.is_small(star)
+            && star.body_bottom() >= first.close
+            && cur.is_bearish()
+            && cur.close < first.midpoint()
+        {
+            set.insert(Pattern::EveningStar);
+        }
+
+        let candles = [first, star, cur];
+        let soldiers = candles.iter().all(|c| c.is_bullish() && self.is_long(c))
+            && candles.windows(2).all(|w| {
+                w[1].open > w[0].open && w[1].open <= w[0].close && w[1].close > w[0].close
+            });
+        if soldiers {
+            set.insert(Pattern::ThreeWhiteSoldiers);
+        }
+        let crows = candles.iter().all(|c| c.is_bearish() && self.is_long(c))
+            && candles.windows(2).all(|w| {
+                w[1].open < w[0].open && w[1].open >= w[0].close && w[1].close < w[0].close
+            });
+        if crows {
+            set.insert(Pattern::ThreeBlackCrows);
+        }
+    }
+}
+
+impl<T: Open + High + Low + Close> Next<&T> for CandlePatterns {
+    type Output = PatternSet;
+
+    fn next(&mut self, input: &T) -> Self::Output {
+        let cur = Candle {
+            open: input.open(),
+            high: input.high(),
+            low: input.low(),
+            close: input.close(),
+        };
+
+        let mut set = PatternSet::new();
+        if self.is_doji(&cur) {
+            set.insert(Pattern::Doji);
+        }
+        if self.is_hammer(&cur) {
+            set.insert(Pattern::Hammer);
+        }
+        if self.is_shooting_star(&cur) {
+            set.insert(Pattern::ShootingStar);
+        }
+        if let Some(prev) = self.prev {
+            self.two_bar(&mut set, &prev, &cur);
+            if let Some(prev2) = self.prev2 {
+                self.three_bar(&mut set, &prev2, &prev, &cur);
+            }
+        }
+
+        self.prev2 = self.prev;
+        self.prev = Some(cur);
+        set
+    }
+}
+
+impl Reset for CandlePatterns {
+    fn reset(&mut self) {
+        self.prev = None;
+        self.prev2 = None;
+    }
+}
+
+impl MemoryFootprint for CandlePatterns {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl Default for CandlePatterns {
+    fn default() -> Self {
+        Self::new(0.1, 0.3, 2.0).unwrap()
+    }
+}
+
+impl fmt::Display for CandlePatterns {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "CP({}, {}, {})",
+            self.doji_body, self.small_body, self.shadow_ratio
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn bar(open: f64, high: f64, low: f64, close: f64) -> Bar {
+        Bar::new().open(open).high(high).low(low).close(close)
+    }
+
+    fn patterns(bars: &[Bar]) -> PatternSet {
+        let mut recognizer = CandlePatterns::default();
+        bars.iter().map(|bar| recognizer.next(bar)).last().unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(CandlePatterns::new(0.0, 0.3, 2.0).is_err());
+        assert!(CandlePatterns::new(1.0, 0.3, 2.0).is_err());
+        assert!(CandlePatterns::new(0.2, 0.1, 2.0).is_err());
+        assert!(CandlePatterns::new(0.1, 1.0, 2.0).is_err());
+        assert!(CandlePatterns::new(0.1, 0.3, 0.0).is_err());
+        assert!(CandlePatterns::new(0.1, 0.1, 0.5).is_ok());
+    }
+
+    #[test]
+    fn test_doji() {
+        let set = patterns(&[bar(10.0, 11.0, 9.0, 10.1)]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![Pattern::Doji]);
+
+        // no range
+        assert!(patterns(&[bar(10.0, 10.0, 10.0, 10.0)]).contains(Pattern::Doji));
+        assert!(patterns(&[bar(10.0, 11.0, 9.0, 10.5)]).is_empty());
+    }
+
+    #[test]
+    fn test_hammer_and_shooting_star() {
+        let set = patterns(&[bar(9.5, 10.0, 7.0, 10.0)]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![Pattern::Hammer]);
+
+        let set = patterns(&[bar(10.0, 13.0, 9.5, 9.5)]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![Pattern::ShootingStar]);
+
+        // dragonfly doji
+        let set = patterns(&[bar(10.0, 10.0, 7.0, 10.0)]);
+        assert!(set.contains(Pattern::Doji));
+        assert!(set.contains(Pattern::Hammer));
+
+        // the upper shadow is too long
+        assert!(patterns(&[bar(9.5, 10.5, 7.0, 10.0)]).is_empty());
+    }
+
+    #[test]
+    fn test_engulfing() {
+        let set = patterns(&[bar(10.0, 10.5, 7.5, 8.0), bar(7.8, 10.8, 7.6, 10.6)]);
+        assert!(set.contains(Pattern::BullishEngulfing));
+
+        let set = patterns(&[bar(8.0, 10.5, 7.5, 10.0), bar(10.2, 10.4, 7.2, 7.4)]);
+        assert!(set.contains(Pattern::BearishEngulfing));
+
+        // the body does not cover the previous open
+        let set = patterns(&[bar(10.0, 10.5, 7.5, 8.0), bar(7.8, 10.8, 7.6, 9.9)]);
+        assert!(!set.contains(Pattern::BullishEngulfing));
+    }
+
+    #[test]
+    fn test_harami() {
+        let set = patterns(&[bar(12.0, 12.5, 7.5, 8.0), bar(9.0, 11.0, 8.5, 10.5)]);
+        assert!(set.contains(Pattern::BullishHarami));
+
+        let set = patterns(&[bar(8.0, 12.5, 7.5, 12.0), bar(11.0, 11.5, 9.0, 9.5)]);
+        assert!(set.contains(Pattern::BearishHarami));
+
+        // the previous body is small
+        let set = patterns(&[bar(10.2, 12.5, 7.5, 10.0), bar(10.05, 11.0, 9.5, 10.15)]);
+        assert!(!set.contains(Pattern::BullishHarami));
+    }
+
+    #[test]
+    fn test_stars() {
+        let set = patterns(&[
+            bar(12.0, 12.2, 9.8, 10.0),
+            bar(9.8, 10.0, 9.0, 9.7),
+            bar(10.0, 11.6, 9.9, 11.5),
+        ]);
+        assert!(set.contains(Pattern::MorningStar));
+
+        let set = patterns(&[
+            bar(10.0, 12.2, 9.8, 12.0),
+            bar(12.2, 13.0, 12.0, 12.3),
+            bar(12.0, 12.1, 10.4, 10.5),
+        ]);
+        assert!(set.contains(Pattern::EveningStar));
+
+        // the third bar closes below the midpoint of the first one
+        let set = patterns(&[
+            bar(12.0, 12.2, 9.8, 10.0),
+            bar(9.8, 10.0, 9.0, 9.7),
+            bar(10.0, 11.0, 9.9, 10.9),
+        ]);
+        assert!(!set.contains(Pattern::MorningStar));
+    }
+
+    #[test]
+    fn test_three_soldiers_and_crows() {
+        let set = patterns(&[
+            bar(10.0, 11.1, 9.9, 11.0),
+            bar(10.5, 12.1, 10.4, 12.0),
+            bar(11.5, 13.1, 11.4, 13.0),
+        ]);
+        assert!(set.contains(Pattern::ThreeWhiteSoldiers));
+
+        let set = patterns(&[
+            bar(13.0, 13.1, 11.9, 12.0),
+            bar(12.5, 12.6, 10.9, 11.0),
+            bar(11.5, 11.6, 9.9, 10.0),
+        ]);
+        assert!(set.contains(Pattern::ThreeBlackCrows));
+
+        // the second bar opens above the first close
+        let set = patterns(&[
+            bar(10.0, 11.1, 9.9, 11.0),
+            bar(11.2, 12.1, 11.1, 12.0),
+            bar(11.5, 13.1, 11.4, 13.0),
+        ]);
+        assert!(!set.contains(Pattern::ThreeWhiteSoldiers));
+    }
+
+    #[test]
+    fn test_thresholds() {
+        let mut strict = CandlePatterns::new(0.05, 0.3, 2.0).unwrap();
+        assert!(strict.next(&bar(10.0, 11.0, 9.0, 10.15)).is_empty());
+
+        let mut loose = CandlePatterns::new(0.2, 0.3, 2.0).unwrap();
+        assert!(loose
+            .next(&bar(10.0, 11.0, 9.0, 10.15))
+            .contains(Pattern::Doji));
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut recognizer = CandlePatterns::default();
+
+        recognizer.next(&bar(10.0, 10.5, 7.5, 8.0));
+        recognizer.reset();
+        assert!(recognizer.next(&bar(7.8, 10.8, 7.6, 10.6)).is_empty());
+    }
+
+    #[test]
+    fn test_default() {
+        CandlePatterns::default();
+    }
+
+    #[test]
+    fn test_display() {
+        let recognizer = CandlePatterns::new(0.1, 0.25, 3.0).unwrap();
+        assert_eq!(format!("{}", recognizer), "CP(0.1, 0.25, 3)");
+    }
+}
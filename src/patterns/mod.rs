@@ -0,0 +1,43 @@
+//! Candlestick pattern recognition.
+//!
+//! [CandlePatterns](struct.CandlePatterns.html) consumes OHLC bars one by one and returns the
+//! set of [patterns](enum.Pattern.html) completed by every bar. Single bar patterns (doji,
+//! hammer, shooting star) look only at the current bar, the others at the last two or three
+//! bars. Several patterns can match the same bar, e.g. a dragonfly doji is a hammer as well.
+//!
+//! The patterns are recognized by the shape of the candles only. The trend context (e.g. a
+//! hammer being bullish only after a decline) is left to the caller, who can combine the
+//! patterns with an indicator of their choice.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::patterns::{CandlePatterns, Pattern};
+//! use ta::{DataItem, Next};
+//!
+//! let bar = |open: f64, high: f64, low: f64, close: f64| {
+//!     DataItem::builder()
+//!         .open(open).high(high).low(low).close(close).volume(100.0)
+//!         .build().unwrap()
+//! };
+//!
+//! let mut patterns = CandlePatterns::default();
+//!
+//! let matched = patterns.next(&bar(10.0, 10.5, 7.5, 8.0));
+//! assert!(matched.is_empty());
+//!
+//! // the bullish body covers the whole body of the previous bearish bar
+//! let matched = patterns.next(&bar(7.8, 10.8, 7.6, 10.6));
+//! assert!(matched.contains(Pattern::BullishEngulfing));
+//! assert_eq!(matched.len(), 1);
+//! ```
+//!
+//! # Links
+//!
+//! * [Candlestick pattern, Wikipedia](https://en.wikipedia.org/wiki/Candlestick_pattern)
+
+mod candle_patterns;
+pub use self::candle_patterns::CandlePatterns;
+
+mod pattern;
+pub use self::pattern::{Pattern, PatternSet, PatternSetIter};
@@ -0,0 +1,199 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Candlestick pattern.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    Doji,
+    Hammer,
+    ShootingStar,
+    BullishEngulfing,
+    BearishEngulfing,
+    BullishHarami,
+    BearishHarami,
+    MorningStar,
+    EveningStar,
+    ThreeWhiteSoldiers,
+    ThreeBlackCrows,
+}
+
+impl Pattern {
+    /// All the patterns in the order of their bits in a [PatternSet](struct.PatternSet.html).
+    pub const ALL: [Pattern; 11] = [
+        Pattern::Doji,
+        Pattern::Hammer,
+        Pattern::ShootingStar,
+        Pattern::BullishEngulfing,
+        Pattern::BearishEngulfing,
+        Pattern::BullishHarami,
+        Pattern::BearishHarami,
+        Pattern::MorningStar,
+        Pattern::EveningStar,
+        Pattern::ThreeWhiteSoldiers,
+        Pattern::ThreeBlackCrows,
+    ];
+
+    /// Number of bars the pattern is made of.
+    pub fn bars(&self) -> usize {
+        match self {
+            Pattern::Doji | Pattern::Hammer | Pattern::ShootingStar => 1,
+            Pattern::BullishEngulfing
+            | Pattern::BearishEngulfing
+            | Pattern::BullishHarami
+            | Pattern::BearishHarami => 2,
+            Pattern::MorningStar
+            | Pattern::EveningStar
+            | Pattern::ThreeWhiteSoldiers
+            | Pattern::ThreeBlackCrows => 3,
+        }
+    }
+
+    fn bit(self) -> u16 {
+        1 << self as u16
+    }
+}
+
+/// Set of patterns matched by a bar, stored as a bitset.
+///
+/// # Example
+///
+/// ```
+/// use ta::patterns::{Pattern, PatternSet};
+///
+/// let mut set = PatternSet::new();
+/// set.insert(Pattern::Doji);
+/// set.insert(Pattern::Hammer);
+///
+/// assert!(set.contains(Pattern::Hammer));
+/// assert!(!set.contains(Pattern::ShootingStar));
+/// assert_eq!(set.iter().collect::<Vec<_>>(), vec![Pattern::Doji, Pattern::Hammer]);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PatternSet(u16);
+
+impl PatternSet {
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    pub fn insert(&mut self, pattern: Pattern) {
+        self.0 |= pattern.bit();
+    }
+
+    pub fn remove(&mut self, pattern: Pattern) {
+        self.0 &= !pattern.bit();
+    }
+
+    pub fn contains(&self, pattern: Pattern) -> bool {
+        self.0 & pattern.bit() != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Raw bits, where the bit _i_ stands for `Pattern::ALL[i]`.
+    pub fn bits(&self) -> u16 {
+        self.0
+    }
+
+    pub fn iter(&self) -> PatternSetIter {
+        PatternSetIter {
+            set: *self,
+            index: 0,
+        }
+    }
+}
+
+impl IntoIterator for PatternSet {
+    type Item = Pattern;
+    type IntoIter = PatternSetIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Pattern> for PatternSet {
+    fn from_iter<I: IntoIterator<Item = Pattern>>(iter: I) -> Self {
+        let mut set = PatternSet::new();
+        for pattern in iter {
+            set.insert(pattern);
+        }
+        set
+    }
+}
+
+/// Iterator over the patterns of a [PatternSet](struct.PatternSet.html).
+#[derive(Debug, Clone)]
+pub struct PatternSetIter {
+    set: PatternSet,
+    index: usize,
+}
+
+impl Iterator for PatternSetIter {
+    type Item = Pattern;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < Pattern::ALL.len() {
+            let pattern = Pattern::ALL[self.index];
+            self.index += 1;
+            if self.set.contains(pattern) {
+                return Some(pattern);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bits_follow_all() {
+        for (i, &pattern) in Pattern::ALL.iter().enumerate() {
+            assert_eq!(pattern.bit(), 1 << i);
+        }
+    }
+
+    #[test]
+    fn test_pattern_set() {
+        let mut set = PatternSet::new();
+        assert!(set.is_empty());
+
+        set.insert(Pattern::ThreeBlackCrows);
+        set.insert(Pattern::Doji);
+        set.insert(Pattern::Doji);
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.bits(), 0b100_0000_0001);
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            vec![Pattern::Doji, Pattern::ThreeBlackCrows]
+        );
+
+        set.remove(Pattern::Doji);
+        assert!(!set.contains(Pattern::Doji));
+        assert!(set.contains(Pattern::ThreeBlackCrows));
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let set: PatternSet = Pattern::ALL.iter().copied().collect();
+        assert_eq!(set.len(), Pattern::ALL.len());
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), Pattern::ALL.to_vec());
+    }
+
+    #[test]
+    fn test_bars() {
+        assert_eq!(Pattern::Hammer.bars(), 1);
+        assert_eq!(Pattern::BearishHarami.bars(), 2);
+        assert_eq!(Pattern::MorningStar.bars(), 3);
+    }
+}
@@ -0,0 +1,220 @@
+//! Indicators computed over [polars](https://docs.rs/polars) dataframes (`polars` feature).
+//!
+//! [compute](fn.compute.html) picks the OHLCV columns of a `DataFrame` by their standard names
+//! `open`, `high`, `low`, `close` and `volume`, ignoring the case, runs the indicators of an
+//! [IndicatorSet](../registry/struct.IndicatorSet.html) over all the rows with
+//! [columns::compute](../columns/fn.compute.html), and returns a dataframe with a column per
+//! output, named like the outputs of the set. The result has as many rows as the input, so it
+//! goes back next to the bars with `hstack`. [apply](fn.apply.html) feeds a single `Series`
+//! to an indicator taking values, e.g. the closes to an SMA.
+//!
+//! `Float64` columns in a single chunk are read in place, other numeric columns are cast to
+//! `Float64` first. Nulls are rejected, fill or drop them before.
+//!
+//! # Example
+//!
+//! ```
+//! use polars::prelude::*;
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::registry::IndicatorSet;
+//!
+//! let bars = df!(
+//!     "Open" => [10.0, 11.0, 12.0],
+//!     "High" => [10.5, 11.5, 12.5],
+//!     "Low" => [9.5, 10.5, 11.5],
+//!     "Close" => [10.0, 11.0, 12.0],
+//!     "Volume" => [100, 120, 80],
+//! )
+//! .unwrap();
+//!
+//! let mut set = IndicatorSet::new();
+//! set.create("sma", "sma", &[("period", 2.0)]).unwrap();
+//! set.create("tr", "tr", &[]).unwrap();
+//!
+//! let results = ta::polars::compute(&mut set, &bars).unwrap();
+//! assert_eq!(results.get_column_names(), ["sma", "tr"]);
+//! let sma: Vec<f64> = results["sma"].f64().unwrap().into_no_null_iter().collect();
+//! assert_eq!(sma, [10.0, 10.5, 11.5]);
+//!
+//! let mut sma = SimpleMovingAverage::new(2).unwrap();
+//! let close = bars["Close"].as_materialized_series();
+//! assert_eq!(ta::polars::apply(&mut sma, close).unwrap().name(), "SMA(2)");
+//! ```
+
+use core::fmt::Display;
+
+use polars::prelude::{Column, DataFrame, DataType, NamedFrom, Series};
+
+use crate::columns::{self, OhlcvColumns};
+use crate::errors::{Result, TaError};
+use crate::registry::IndicatorSet;
+use crate::Next;
+
+/// Feeds all the rows of the OHLCV columns of `frame` to the indicators of `set`, and returns
+/// a dataframe with a column per output, in the order of the indicators in the set.
+///
+/// The indicators continue from their current state, like feeding the rows with
+/// [next](../trait.Next.html). Fails with
+/// [DataItemIncomplete](../errors/enum.TaError.html#variant.DataItemIncomplete) for the first
+/// missing column, with [DataItemInvalid](../errors/enum.TaError.html#variant.DataItemInvalid)
+/// for a column, which is not numeric or has nulls, and with the error of the first invalid
+/// bar, e.g. a high below the low.
+pub fn compute(set: &mut IndicatorSet, frame: &DataFrame) -> Result<DataFrame> {
+    let open = float64(frame, "open")?;
+    let high = float64(frame, "high")?;
+    let low = float64(frame, "low")?;
+    let close = float64(frame, "close")?;
+    let volume = float64(frame, "volume")?;
+    let columns = OhlcvColumns {
+        open: values(&open, "open")?,
+        high: values(&high, "high")?,
+        low: values(&low, "low")?,
+        close: values(&close, "close")?,
+        volume: values(&volume, "volume")?,
+    };
+
+    let results = columns::compute(set, &columns)?
+        .into_iter()
+        .map(|(name, values)| Series::new(name.into(), values).into())
+        .collect();
+    // the outputs of a set have distinct names and a value per row
+    Ok(DataFrame::new(frame.height(), results).expect("columns of the frame height"))
+}
+
+/// Feeds every value of `series` to `indicator`, and returns the outputs as a `Float64`
+/// series named after the indicator, e.g. `SMA(14)`.
+///
+/// Fails with [DataItemInvalid](../errors/enum.TaError.html#variant.DataItemInvalid), if the
+/// series is not numeric or has nulls.
+pub fn apply<I>(indicator: &mut I, series: &Series) -> Result<Series>
+where
+    I: Next<f64, Output = f64> + Display,
+{
+    let column = Column::from(series.clone())
+        .cast(&DataType::Float64)
+        .map_err(|_| TaError::DataItemInvalid { field: "value" })?
+        .rechunk();
+    let outputs: Vec<f64> = values(&column, "value")?
+        .iter()
+        .map(|&value| indicator.next(value))
+        .collect();
+    Ok(Series::new(indicator.to_string().into(), outputs))
+}
+
+/// The column named `field`, ignoring the case, as `Float64` in a single chunk.
+fn float64(frame: &DataFrame, field: &'static str) -> Result<Column> {
+    let column = frame
+        .columns()
+        .iter()
+        .find(|column| column.name().eq_ignore_ascii_case(field))
+        .ok_or(TaError::DataItemIncomplete { field })?;
+    let column = column
+        .cast(&DataType::Float64)
+        .map_err(|_| TaError::DataItemInvalid { field })?;
+    Ok(column.rechunk())
+}
+
+/// The values of a `Float64` column in a single chunk, borrowed.
+fn values<'a>(column: &'a Column, field: &'static str) -> Result<&'a [f64]> {
+    column
+        .f64()
+        .and_then(|values| values.cont_slice())
+        .map_err(|_| TaError::DataItemInvalid { field })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::columns::OhlcvColumns;
+    use crate::indicators::ExponentialMovingAverage;
+    use polars::df;
+
+    const HIGH: [f64; 5] = [10.5, 11.5, 12.5, 11.5, 13.0];
+    const LOW: [f64; 5] = [9.5, 10.5, 11.5, 10.5, 12.0];
+    const CLOSE: [f64; 5] = [10.0, 11.0, 12.0, 11.0, 12.5];
+    const VOLUME: [f64; 5] = [100.0, 120.0, 80.0, 90.0, 150.0];
+
+    fn frame() -> DataFrame {
+        df!(
+            "timestamp" => [1i64, 2, 3, 4, 5],
+            "open" => CLOSE,
+            "high" => HIGH,
+            "low" => LOW,
+            "close" => CLOSE,
+            "volume" => [100i64, 120, 80, 90, 150],
+        )
+        .unwrap()
+    }
+
+    fn set() -> IndicatorSet {
+        let mut set = IndicatorSet::new();
+        set.create("rsi", "rsi", &[("period", 3.0)]).unwrap();
+        set.create("macd", "macd", &[]).unwrap();
+        set
+    }
+
+    fn column(frame: &DataFrame, name: &str) -> Vec<f64> {
+        frame[name].f64().unwrap().into_no_null_iter().collect()
+    }
+
+    #[test]
+    fn test_compute() {
+        let results = compute(&mut set(), &frame()).unwrap();
+        assert_eq!(results.height(), 5);
+
+        let columns = OhlcvColumns {
+            open: &CLOSE,
+            high: &HIGH,
+            low: &LOW,
+            close: &CLOSE,
+            volume: &VOLUME,
+        };
+        let expected = columns::compute(&mut set(), &columns).unwrap();
+        assert_eq!(results.width(), expected.len());
+        for (name, values) in expected {
+            assert_eq!(column(&results, &name), values);
+        }
+    }
+
+    #[test]
+    fn test_chunks() {
+        let mut frame = frame();
+        frame.vstack_mut(&self::frame()).unwrap();
+        assert_eq!(frame["close"].n_chunks(), 2);
+
+        let results = compute(&mut set(), &frame).unwrap();
+        assert_eq!(results.height(), 10);
+    }
+
+    #[test]
+    fn test_missing_column() {
+        let frame = frame().drop("volume").unwrap();
+        assert_eq!(
+            compute(&mut set(), &frame).unwrap_err(),
+            TaError::DataItemIncomplete { field: "volume" }
+        );
+    }
+
+    #[test]
+    fn test_nulls() {
+        let mut frame = frame();
+        let high = Series::new("high".into(), [Some(10.5), None, Some(12.5), None, None]);
+        frame.replace("high", high.into()).unwrap();
+        assert_eq!(
+            compute(&mut set(), &frame).unwrap_err(),
+            TaError::DataItemInvalid { field: "high" }
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        let series = apply(&mut ema, frame()["close"].as_materialized_series()).unwrap();
+        assert_eq!(series.name(), "EMA(3)");
+
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        let expected: Vec<f64> = CLOSE.iter().map(|&close| ema.next(close)).collect();
+        let outputs: Vec<f64> = series.f64().unwrap().into_no_null_iter().collect();
+        assert_eq!(outputs, expected);
+    }
+}
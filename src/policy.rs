@@ -0,0 +1,371 @@
+//! Explicit handling of invalid inputs.
+//!
+//! Indicators compute with whatever they are fed: a single NaN close poisons an EMA and every
+//! output after it, and a bar without volume has no volume weighted price. Instead of checking
+//! every input by hand, wrap the indicator into [Guarded](struct.Guarded.html) with one of the
+//! [input policies](enum.InputPolicy.html):
+//!
+//! * `Skip` ignores the invalid input and returns the last output again
+//! * `Propagate` feeds the invalid input into the indicator anyway, like an unwrapped one
+//! * `CarryForward` feeds the last valid input again instead
+//! * `Error` returns an error and leaves the indicator unchanged
+//!
+//! Prices are invalid, when they are NaN or infinite. Bars are invalid, when any of their
+//! prices or their volume is NaN or infinite, or when the volume is not greater than 0.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::errors::TaError;
+//! use ta::indicators::ExponentialMovingAverage as Ema;
+//! use ta::policy::{Guarded, InputPolicy};
+//! use ta::Next;
+//!
+//! let mut ema = Guarded::new(Ema::new(3).unwrap(), InputPolicy::Skip);
+//! assert_eq!(ema.next(2.0), Ok(2.0));
+//! assert_eq!(ema.next(f64::NAN), Ok(2.0));
+//! assert_eq!(ema.next(4.0), Ok(3.0));
+//!
+//! let mut ema = Guarded::new(Ema::new(3).unwrap(), InputPolicy::Error);
+//! assert_eq!(
+//!     ema.next(f64::INFINITY),
+//!     Err(TaError::DataItemInvalid { field: "value" })
+//! );
+//! ```
+
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Close, Current, High, IsReady, Low, Next, Open, Period, Reset, Volume};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// What to do with an invalid input.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputPolicy {
+    /// Does not feed the input and returns the last output of the indicator again, or a
+    /// `NotReady` error before the first output.
+    Skip,
+    /// Feeds the input into the indicator anyway.
+    Propagate,
+    /// Feeds the last valid input again, or returns an error before the first valid input.
+    CarryForward,
+    /// Does not feed the input and returns an error.
+    Error,
+}
+
+impl fmt::Display for InputPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputPolicy::Skip => write!(f, "skip"),
+            InputPolicy::Propagate => write!(f, "propagate"),
+            InputPolicy::CarryForward => write!(f, "carry forward"),
+            InputPolicy::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Tells whether an input can be fed into an indicator.
+pub trait ValidInput {
+    /// Name of the first invalid field of the input, `value` for a plain price, or `None`
+    /// for a valid input.
+    fn invalid_field(&self) -> Option<&'static str>;
+
+    fn is_valid(&self) -> bool {
+        self.invalid_field().is_none()
+    }
+}
+
+impl ValidInput for f64 {
+    fn invalid_field(&self) -> Option<&'static str> {
+        if self.is_finite() {
+            None
+        } else {
+            Some("value")
+        }
+    }
+}
+
+impl<T: Open + High + Low + Close + Volume> ValidInput for T {
+    fn invalid_field(&self) -> Option<&'static str> {
+        if !self.open().is_finite() {
+            Some("open")
+        } else if !self.high().is_finite() {
+            Some("high")
+        } else if !self.low().is_finite() {
+            Some("low")
+        } else if !self.close().is_finite() {
+            Some("close")
+        } else if !(self.volume().is_finite() && self.volume() > 0.0) {
+            Some("volume")
+        } else {
+            None
+        }
+    }
+}
+
+/// Indicator applying an [InputPolicy](enum.InputPolicy.html) to its inputs.
+///
+/// Takes `f64` inputs or references to bars `&T`. The output is `Ok` with the output of the
+/// indicator, or an error, when the policy does not produce an output for an invalid input:
+/// `NotReady`, when skipping before the first output, and `DataItemInvalid` with the
+/// invalid field otherwise. Valid inputs are always fed into the indicator.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Guarded<I, T = f64> {
+    indicator: I,
+    policy: InputPolicy,
+    last_valid: Option<T>,
+}
+
+impl<I, T> Guarded<I, T> {
+    pub fn new(indicator: I, policy: InputPolicy) -> Self {
+        Self {
+            indicator,
+            policy,
+            last_valid: None,
+        }
+    }
+
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn policy(&self) -> InputPolicy {
+        self.policy
+    }
+
+    fn on_invalid<O>(&self, field: &'static str) -> Result<O>
+    where
+        I: Current<Output = O>,
+    {
+        match self.policy {
+            InputPolicy::Skip => self.indicator.current().ok_or(TaError::NotReady),
+            _ => Err(TaError::DataItemInvalid { field }),
+        }
+    }
+}
+
+impl<I: Period, T> Period for Guarded<I, T> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: IsReady, T> IsReady for Guarded<I, T> {
+    fn bars_until_ready(&self) -> usize {
+        self.indicator.bars_until_ready()
+    }
+}
+
+impl<I, O> Next<f64> for Guarded<I, f64>
+where
+    I: Next<f64, Output = O> + Current<Output = O>,
+{
+    type Output = Result<O>;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let field = match input.invalid_field() {
+            None => {
+                self.last_valid = Some(input);
+                return Ok(self.indicator.next(input));
+            }
+            Some(field) => field,
+        };
+        match (self.policy, self.last_valid) {
+            (InputPolicy::Propagate, _) => Ok(self.indicator.next(input)),
+            (InputPolicy::CarryForward, Some(last)) => Ok(self.indicator.next(last)),
+            _ => self.on_invalid(field),
+        }
+    }
+}
+
+impl<'a, I, O, T> Next<&'a T> for Guarded<I, T>
+where
+    I: for<'b> Next<&'b T, Output = O> + Current<Output = O>,
+    T: ValidInput + Clone,
+{
+    type Output = Result<O>;
+
+    fn next(&mut self, input: &'a T) -> Self::Output {
+        let field = match input.invalid_field() {
+            None => {
+                // only carrying forward needs a copy of the bar
+                if self.policy == InputPolicy::CarryForward {
+                    self.last_valid = Some(input.clone());
+                }
+                return Ok(self.indicator.next(input));
+            }
+            Some(field) => field,
+        };
+        match (self.policy, &self.last_valid) {
+            (InputPolicy::Propagate, _) => Ok(self.indicator.next(input)),
+            (InputPolicy::CarryForward, Some(last)) => Ok(self.indicator.next(last)),
+            _ => self.on_invalid(field),
+        }
+    }
+}
+
+impl<I: Current, T> Current for Guarded<I, T> {
+    type Output = I::Output;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.indicator.current()
+    }
+}
+
+impl<I: Reset, T> Reset for Guarded<I, T> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.last_valid = None;
+    }
+}
+
+impl<I: fmt::Display, T> fmt::Display for Guarded<I, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GUARDED({}, {})", self.indicator, self.policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        ExponentialMovingAverage as Ema, OnBalanceVolume, SimpleMovingAverage as Sma,
+    };
+    use crate::test_helper::*;
+    use InputPolicy::*;
+
+    fn bar(close: f64, volume: f64) -> Bar {
+        Bar::new()
+            .open(close)
+            .high(close)
+            .low(close)
+            .close(close)
+            .volume(volume)
+    }
+
+    #[test]
+    fn test_valid_input() {
+        assert!(1.0.is_valid());
+        assert!(!f64::NAN.is_valid());
+        assert!(!f64::NEG_INFINITY.is_valid());
+
+        assert!(bar(10.0, 1.0).is_valid());
+        assert!(!bar(10.0, 0.0).is_valid());
+        assert!(!bar(10.0, f64::NAN).is_valid());
+        assert!(!bar(f64::INFINITY, 1.0).is_valid());
+        assert!(!bar(10.0, 1.0).high(f64::NAN).is_valid());
+        assert_eq!(bar(10.0, 1.0).low(f64::NAN).invalid_field(), Some("low"));
+        assert_eq!(bar(10.0, 0.0).invalid_field(), Some("volume"));
+    }
+
+    #[test]
+    fn test_skip() {
+        let mut ema = Guarded::new(Ema::new(3).unwrap(), Skip);
+        assert_eq!(ema.next(f64::NAN), Err(TaError::NotReady));
+        assert_eq!(ema.next(2.0), Ok(2.0));
+        assert_eq!(ema.next(f64::NAN), Ok(2.0));
+        assert_eq!(ema.next(4.0), Ok(3.0));
+        assert_eq!(ema.bars_until_ready(), 1);
+    }
+
+    #[test]
+    fn test_propagate() {
+        let mut ema = Guarded::new(Ema::new(3).unwrap(), Propagate);
+        assert_eq!(ema.next(2.0), Ok(2.0));
+        assert!(ema.next(f64::NAN).unwrap().is_nan());
+        assert!(ema.next(4.0).unwrap().is_nan());
+    }
+
+    #[test]
+    fn test_carry_forward() {
+        let mut sma = Guarded::new(Sma::new(2).unwrap(), CarryForward);
+        assert_eq!(
+            sma.next(f64::NAN),
+            Err(TaError::DataItemInvalid { field: "value" })
+        );
+        assert_eq!(sma.next(2.0), Ok(2.0));
+        assert_eq!(sma.next(4.0), Ok(3.0));
+        assert_eq!(sma.next(f64::INFINITY), Ok(4.0));
+        assert_eq!(sma.next(6.0), Ok(5.0));
+    }
+
+    #[test]
+    fn test_error() {
+        let mut sma = Guarded::new(Sma::new(2).unwrap(), Error);
+        assert_eq!(sma.next(2.0), Ok(2.0));
+        assert_eq!(
+            sma.next(f64::NAN),
+            Err(TaError::DataItemInvalid { field: "value" })
+        );
+        assert_eq!(sma.current(), Some(2.0));
+        assert_eq!(sma.next(4.0), Ok(3.0));
+    }
+
+    #[test]
+    fn test_bars() {
+        let mut obv = Guarded::new(OnBalanceVolume::new(), Skip);
+        assert_eq!(obv.next(&bar(10.0, 100.0)), Ok(100.0));
+        assert_eq!(obv.next(&bar(11.0, 0.0)), Ok(100.0));
+        assert_eq!(obv.next(&bar(12.0, 50.0)), Ok(150.0));
+
+        let mut obv = Guarded::new(OnBalanceVolume::new(), Error);
+        assert_eq!(
+            obv.next(&bar(f64::NAN, 100.0)),
+            Err(TaError::DataItemInvalid { field: "open" })
+        );
+    }
+
+    #[test]
+    fn test_bars_carry_forward() {
+        let item = |close: f64, volume: f64| {
+            crate::DataItem::builder()
+                .open(close)
+                .high(close)
+                .low(close)
+                .close(close)
+                .volume(volume)
+                .build()
+                .unwrap()
+        };
+        let mut sma = Guarded::new(Sma::new(2).unwrap(), CarryForward);
+        assert_eq!(sma.next(&item(2.0, 10.0)), Ok(2.0));
+        assert_eq!(sma.next(&item(4.0, 0.0)), Ok(2.0));
+        assert_eq!(sma.next(&item(6.0, 10.0)), Ok(4.0));
+
+        sma.reset();
+        assert_eq!(
+            sma.next(&item(6.0, 0.0)),
+            Err(TaError::DataItemInvalid { field: "volume" })
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sma = Guarded::new(Sma::new(2).unwrap(), CarryForward);
+        assert_eq!(sma.next(2.0), Ok(2.0));
+
+        sma.reset();
+        assert_eq!(sma.current(), None);
+        assert_eq!(
+            sma.next(f64::NAN),
+            Err(TaError::DataItemInvalid { field: "value" })
+        );
+    }
+
+    #[test]
+    fn test_period() {
+        let sma = Guarded::<_, f64>::new(Sma::new(4).unwrap(), Skip);
+        assert_eq!(sma.period(), 4);
+        assert_eq!(sma.policy(), Skip);
+        assert_eq!(sma.indicator().period(), 4);
+    }
+
+    #[test]
+    fn test_display() {
+        let sma = Guarded::<_, f64>::new(Sma::new(4).unwrap(), CarryForward);
+        assert_eq!(format!("{}", sma), "GUARDED(SMA(4), carry forward)");
+    }
+}
@@ -0,0 +1,790 @@
+//! Indicators created by name at runtime.
+//!
+//! Config-driven pipelines and plugin-style strategies choose their indicators from data, e.g.
+//! `rsi` with `period = 14` from a config file, so the concrete type is not known at compile
+//! time. [DynIndicator](trait.DynIndicator.html) is an object-safe view of an indicator: it
+//! takes a [DataItem](../struct.DataItem.html) and returns all outputs of the bar as a
+//! `Vec<f64>`. [create](fn.create.html) builds the built-in indicators from a name and a list
+//! of parameters, and a [Registry](struct.Registry.html) adds custom constructors on top. An
+//! [IndicatorSet](struct.IndicatorSet.html) runs many named indicators on the same feed and
+//! returns all of their values by name.
+//!
+//! # Built-in indicators
+//!
+//! | Name | Parameters (defaults) | Outputs |
+//! |------|-----------------------|---------|
+//! | `sma`, `ema`, `wma`, `roc`, `sd`, `mad` | `period` (9) | `value` |
+//! | `rsi`, `max`, `min`, `er`, `atr`, `natr`, `mfi`, `fast_stochastic` | `period` (14) | `value` |
+//! | `cci`, `vwma` | `period` (20) | `value` |
+//! | `slow_stochastic` | `period` (14), `ema_period` (3) | `value` |
+//! | `tr`, `obv` | | `value` |
+//! | `macd` | `fast_period` (12), `slow_period` (26), `signal_period` (9) | `macd`, `signal`, `histogram` |
+//! | `ppo` | `fast_period` (12), `slow_period` (26), `signal_period` (9) | `ppo`, `signal`, `histogram` |
+//! | `bb` | `period` (9), `multiplier` (2) | `average`, `upper`, `lower` |
+//! | `kc` | `period` (10), `multiplier` (2) | `average`, `upper`, `lower` |
+//! | `chandelier` | `period` (22), `multiplier` (3) | `long`, `short` |
+//!
+//! The indicators are built with [from_params](../trait.Parameters.html#tymethod.from_params),
+//! and report their parameters by the same names with
+//! [params](../trait.Parameters.html#tymethod.params). Missing parameters take their default
+//! value. Unknown names and unknown parameters are
+//! reported as [UnknownIndicator](../errors/enum.TaError.html#variant.UnknownIndicator) and
+//! [UnknownParameter](../errors/enum.TaError.html#variant.UnknownParameter), periods that are
+//! not whole numbers as [InvalidParameter](../errors/enum.TaError.html#variant.InvalidParameter),
+//! and parameters rejected by the indicator itself with the error of its constructor.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::registry::create;
+//! use ta::DataItem;
+//!
+//! let mut indicators = vec![
+//!     create("rsi", &[("period", 14.0)]).unwrap(),
+//!     create("bb", &[("period", 20.0), ("multiplier", 2.5)]).unwrap(),
+//! ];
+//! assert_eq!(indicators[1].output_names(), ["average", "upper", "lower"]);
+//!
+//! let bar = DataItem::builder()
+//!     .high(10.5).low(9.5).close(10.0).open(10.0).volume(1000.0)
+//!     .build().unwrap();
+//! for indicator in indicators.iter_mut() {
+//!     let values = indicator.next_values(&bar);
+//!     println!("{} = {:?}", indicator, values);
+//! }
+//! ```
+
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::*;
+use crate::{DataItem, Next, Parameters, Reset};
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box, collections::BTreeMap, format, string::String, string::ToString, vec, vec::Vec,
+};
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Object-safe indicator over data items, with all outputs of a bar as a list of values.
+///
+/// Implemented for every indicator, which takes a `&DataItem` and whose output implements
+/// [OutputValues](trait.OutputValues.html). Indicators are `Send`, so an
+/// [IndicatorSet](struct.IndicatorSet.html) can move to another thread.
+pub trait DynIndicator: Reset + fmt::Display + Send {
+    /// Names of the values returned by `next_values`, in the same order.
+    fn output_names(&self) -> &'static [&'static str];
+
+    fn next_values(&mut self, input: &DataItem) -> Vec<f64>;
+}
+
+/// Indicator created at runtime, see [create](fn.create.html).
+pub type BoxedIndicator = Box<dyn DynIndicator>;
+
+impl<I, O> DynIndicator for I
+where
+    I: for<'a> Next<&'a DataItem, Output = O> + Reset + fmt::Display + Send,
+    O: OutputValues,
+{
+    fn output_names(&self) -> &'static [&'static str] {
+        O::NAMES
+    }
+
+    fn next_values(&mut self, input: &DataItem) -> Vec<f64> {
+        self.next(input).values()
+    }
+}
+
+impl Next<&DataItem> for BoxedIndicator {
+    type Output = Vec<f64>;
+
+    fn next(&mut self, input: &DataItem) -> Self::Output {
+        self.next_values(input)
+    }
+}
+
+impl Reset for BoxedIndicator {
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+}
+
+/// Output of an indicator as a flat list of values.
+pub trait OutputValues {
+    /// Names of the values, in the same order as `values`.
+    const NAMES: &'static [&'static str];
+
+    fn values(&self) -> Vec<f64>;
+}
+
+impl OutputValues for f64 {
+    const NAMES: &'static [&'static str] = &["value"];
+
+    fn values(&self) -> Vec<f64> {
+        vec![*self]
+    }
+}
+
+impl OutputValues for MovingAverageConvergenceDivergenceOutput {
+    const NAMES: &'static [&'static str] = &["macd", "signal", "histogram"];
+
+    fn values(&self) -> Vec<f64> {
+        vec![self.macd, self.signal, self.histogram]
+    }
+}
+
+impl OutputValues for PercentagePriceOscillatorOutput {
+    const NAMES: &'static [&'static str] = &["ppo", "signal", "histogram"];
+
+    fn values(&self) -> Vec<f64> {
+        vec![self.ppo, self.signal, self.histogram]
+    }
+}
+
+impl OutputValues for BollingerBandsOutput {
+    const NAMES: &'static [&'static str] = &["average", "upper", "lower"];
+
+    fn values(&self) -> Vec<f64> {
+        vec![self.average, self.upper, self.lower]
+    }
+}
+
+impl OutputValues for KeltnerChannelOutput {
+    const NAMES: &'static [&'static str] = &["average", "upper", "lower"];
+
+    fn values(&self) -> Vec<f64> {
+        vec![self.average, self.upper, self.lower]
+    }
+}
+
+impl OutputValues for ChandelierExitOutput {
+    const NAMES: &'static [&'static str] = &["long", "short"];
+
+    fn values(&self) -> Vec<f64> {
+        vec![self.long, self.short]
+    }
+}
+
+/// Named parameters of an indicator constructor.
+#[derive(Debug, Clone, Copy)]
+pub struct Params<'a> {
+    values: &'a [(&'a str, f64)],
+}
+
+impl<'a> Params<'a> {
+    pub fn new(values: &'a [(&'a str, f64)]) -> Self {
+        Self { values }
+    }
+
+    /// Value of the parameter, the last one if it is given more than once.
+    pub fn get(&self, key: &str) -> Option<f64> {
+        self.values
+            .iter()
+            .rev()
+            .find(|(k, _)| *k == key)
+            .map(|&(_, value)| value)
+    }
+
+    /// Fails, if a parameter is not one of `keys`.
+    pub fn allow(&self, keys: &[&str]) -> Result<()> {
+        match self.values.iter().find(|(k, _)| !keys.contains(k)) {
+            None => Ok(()),
+            Some((k, _)) => Err(TaError::UnknownParameter {
+                name: k.to_string(),
+            }),
+        }
+    }
+
+    /// Parameter as a period. Fails, if it is not a non-negative whole number.
+    pub fn period(&self, key: &'static str, default: usize) -> Result<usize> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(value) if value >= 0.0 && value.fract() == 0.0 && value <= u32::MAX as f64 => {
+                Ok(value as usize)
+            }
+            Some(value) => Err(TaError::invalid_parameter(
+                key,
+                value,
+                "must be a non-negative whole number",
+            )),
+        }
+    }
+
+    /// Parameter as a number. Fails, if it is not finite.
+    pub fn float(&self, key: &'static str, default: f64) -> Result<f64> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(value) if value.is_finite() => Ok(value),
+            Some(value) => Err(TaError::invalid_parameter(key, value, "must be finite")),
+        }
+    }
+}
+
+/// Constructor of an indicator from its parameters.
+pub type Constructor = fn(&Params) -> Result<BoxedIndicator>;
+
+fn boxed<I: DynIndicator + 'static>(indicator: I) -> Result<BoxedIndicator> {
+    Ok(Box::new(indicator))
+}
+
+/// Names of the built-in indicators, see the [module documentation](index.html).
+pub const BUILTIN: &[&str] = &[
+    "atr",
+    "bb",
+    "cci",
+    "chandelier",
+    "ema",
+    "er",
+    "fast_stochastic",
+    "kc",
+    "macd",
+    "mad",
+    "max",
+    "mfi",
+    "min",
+    "natr",
+    "obv",
+    "ppo",
+    "roc",
+    "rsi",
+    "sd",
+    "slow_stochastic",
+    "sma",
+    "tr",
+    "vwma",
+    "wma",
+];
+
+/// Creates a built-in indicator by its name, see the [module documentation](index.html).
+pub fn create(name: &str, params: &[(&str, f64)]) -> Result<BoxedIndicator> {
+    let params = &Params::new(params);
+    match name {
+        "sma" => boxed(SimpleMovingAverage::<f64>::from_params(params)?),
+        "ema" => boxed(ExponentialMovingAverage::<f64>::from_params(params)?),
+        "wma" => boxed(WeightedMovingAverage::from_params(params)?),
+        "roc" => boxed(RateOfChange::<f64>::from_params(params)?),
+        "sd" => boxed(StandardDeviation::<f64>::from_params(params)?),
+        "mad" => boxed(MeanAbsoluteDeviation::from_params(params)?),
+        "rsi" => boxed(RelativeStrengthIndex::<f64>::from_params(params)?),
+        "max" => boxed(Maximum::from_params(params)?),
+        "min" => boxed(Minimum::from_params(params)?),
+        "er" => boxed(EfficiencyRatio::from_params(params)?),
+        "atr" => boxed(AverageTrueRange::from_params(params)?),
+        "natr" => boxed(NormalizedAverageTrueRange::from_params(params)?),
+        "mfi" => boxed(MoneyFlowIndex::from_params(params)?),
+        "fast_stochastic" => boxed(FastStochastic::from_params(params)?),
+        "cci" => boxed(CommodityChannelIndex::from_params(params)?),
+        "vwma" => boxed(VolumeWeightedMovingAverage::from_params(params)?),
+        "slow_stochastic" => boxed(SlowStochastic::from_params(params)?),
+        "tr" => boxed(TrueRange::from_params(params)?),
+        "obv" => boxed(OnBalanceVolume::from_params(params)?),
+        "macd" => boxed(MovingAverageConvergenceDivergence::<f64>::from_params(
+            params,
+        )?),
+        "ppo" => boxed(PercentagePriceOscillator::<f64>::from_params(params)?),
+        "bb" => boxed(BollingerBands::<f64>::from_params(params)?),
+        "kc" => boxed(KeltnerChannel::from_params(params)?),
+        "chandelier" => boxed(ChandelierExit::from_params(params)?),
+        _ => Err(TaError::UnknownIndicator {
+            name: name.to_string(),
+        }),
+    }
+}
+
+/// Built-in indicators extended with custom constructors.
+///
+/// A custom constructor takes precedence over a built-in indicator with the same name.
+///
+/// # Example
+///
+/// ```
+/// use ta::errors::Result;
+/// use ta::indicators::ExponentialMovingAverage;
+/// use ta::registry::{BoxedIndicator, Params, Registry};
+///
+/// // a smoother EMA, configured by its half-life instead of its period
+/// fn half_life_ema(params: &Params) -> Result<BoxedIndicator> {
+///     params.allow(&["half_life"])?;
+///     let period = params.period("half_life", 5)? * 3;
+///     Ok(Box::new(ExponentialMovingAverage::new(period)?))
+/// }
+///
+/// let mut registry = Registry::new();
+/// registry.register("hl_ema", half_life_ema);
+///
+/// let ema = registry.create("hl_ema", &[("half_life", 4.0)]).unwrap();
+/// assert_eq!(ema.to_string(), "EMA(12)");
+/// assert!(registry.create("rsi", &[]).is_ok());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    constructors: BTreeMap<String, Constructor>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a constructor, replacing a constructor registered before with the same name.
+    pub fn register(&mut self, name: &str, constructor: Constructor) {
+        self.constructors.insert(name.to_string(), constructor);
+    }
+
+    /// Creates a custom or a built-in indicator by its name.
+    pub fn create(&self, name: &str, params: &[(&str, f64)]) -> Result<BoxedIndicator> {
+        match self.constructors.get(name) {
+            Some(constructor) => constructor(&Params::new(params)),
+            None => create(name, params),
+        }
+    }
+
+    /// Names of the custom and the built-in indicators, sorted and without duplicates.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .constructors
+            .keys()
+            .map(String::as_str)
+            .chain(BUILTIN.iter().copied())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+}
+
+impl fmt::Display for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "REGISTRY({})", self.names().join(", "))
+    }
+}
+
+/// Named indicators, which are all fed the same bars.
+///
+/// Dashboards and screeners compute many indicators on every bar of a feed. The set feeds a
+/// bar into all of its indicators with one call of [next](../trait.Next.html) and returns all
+/// values by name. An indicator with a single output is reported under its name, and every
+/// output of an indicator with several outputs as `name.output`, e.g. `bb.upper`.
+///
+/// # Example
+///
+/// ```
+/// use ta::registry::{create, IndicatorSet};
+/// use ta::{DataItem, Next};
+///
+/// let mut set = IndicatorSet::new();
+/// set.insert("fast", create("ema", &[("period", 3.0)]).unwrap());
+/// set.create("bb", "bb", &[("period", 3.0)]).unwrap();
+///
+/// let bar = DataItem::builder()
+///     .open(10.0).high(10.5).low(9.5).close(10.0).volume(1000.0)
+///     .build().unwrap();
+/// let values = set.next(&bar);
+/// assert_eq!(values["fast"], 10.0);
+/// assert_eq!(values["bb.upper"], 10.0);
+/// assert_eq!(values.len(), 4);
+/// ```
+#[derive(Default)]
+pub struct IndicatorSet {
+    indicators: Vec<(String, BoxedIndicator)>,
+}
+
+impl IndicatorSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an indicator, replacing an indicator added before with the same name.
+    pub fn insert(&mut self, name: &str, indicator: BoxedIndicator) {
+        match self.indicators.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = indicator,
+            None => self.indicators.push((name.to_string(), indicator)),
+        }
+    }
+
+    /// Adds a built-in indicator by its kind, see [create](fn.create.html).
+    pub fn create(&mut self, name: &str, kind: &str, params: &[(&str, f64)]) -> Result<()> {
+        self.insert(name, create(kind, params)?);
+        Ok(())
+    }
+
+    /// Removes an indicator and returns it.
+    pub fn remove(&mut self, name: &str) -> Option<BoxedIndicator> {
+        let index = self.indicators.iter().position(|(n, _)| n == name)?;
+        Some(self.indicators.remove(index).1)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn DynIndicator> {
+        self.indicators
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, indicator)| &**indicator)
+    }
+
+    /// Names of the indicators, in the order they were added.
+    pub fn names(&self) -> Vec<&str> {
+        self.indicators.iter().map(|(n, _)| n.as_str()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.indicators.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indicators.is_empty()
+    }
+
+    /// The indicators with their names, in the order they were added.
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&str, &mut BoxedIndicator)> {
+        self.indicators
+            .iter_mut()
+            .map(|(name, indicator)| (name.as_str(), indicator))
+    }
+}
+
+impl Next<&DataItem> for IndicatorSet {
+    type Output = BTreeMap<String, f64>;
+
+    fn next(&mut self, input: &DataItem) -> Self::Output {
+        let mut values = BTreeMap::new();
+        for (name, indicator) in self.indicators.iter_mut() {
+            let outputs = indicator.next_values(input);
+            match indicator.output_names() {
+                [_] => {
+                    values.insert(name.clone(), outputs[0]);
+                }
+                output_names => {
+                    for (output, value) in output_names.iter().zip(outputs) {
+                        values.insert(format!("{}.{}", name, output), value);
+                    }
+                }
+            }
+        }
+        values
+    }
+}
+
+impl Reset for IndicatorSet {
+    fn reset(&mut self) {
+        for (_, indicator) in self.indicators.iter_mut() {
+            indicator.reset();
+        }
+    }
+}
+
+impl fmt::Debug for IndicatorSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IndicatorSet({})", self)
+    }
+}
+
+impl fmt::Display for IndicatorSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "INDICATOR_SET(")?;
+        for (i, (name, indicator)) in self.indicators.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", name, indicator)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParamValue;
+
+    fn bars() -> Vec<DataItem> {
+        (0..40)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.6).sin() * 4.0 + i as f64 * 0.2;
+                DataItem::builder()
+                    .open(close - 0.2)
+                    .high(close + 1.0 + (i % 3) as f64)
+                    .low(close - 1.0 - (i % 2) as f64)
+                    .close(close)
+                    .volume(1000.0 + (i % 5) as f64 * 200.0)
+                    .build()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn assert_same<I, O>(name: &str, params: &[(&str, f64)], mut expected: I)
+    where
+        I: for<'a> Next<&'a DataItem, Output = O> + fmt::Display,
+        O: OutputValues,
+    {
+        let mut indicator = create(name, params).unwrap();
+        assert_eq!(indicator.to_string(), expected.to_string());
+        for bar in bars().iter() {
+            let values = indicator.next_values(bar);
+            assert_eq!(values.len(), indicator.output_names().len());
+            assert_eq!(values, expected.next(bar).values());
+        }
+    }
+
+    #[test]
+    fn test_create() {
+        assert_same(
+            "sma",
+            &[("period", 5.0)],
+            SimpleMovingAverage::new(5).unwrap(),
+        );
+        assert_same("rsi", &[], RelativeStrengthIndex::new(14).unwrap());
+        assert_same("atr", &[("period", 3.0)], AverageTrueRange::new(3).unwrap());
+        assert_same(
+            "slow_stochastic",
+            &[("period", 7.0), ("ema_period", 2.0)],
+            SlowStochastic::new(7, 2).unwrap(),
+        );
+        assert_same("obv", &[], OnBalanceVolume::new());
+        assert_same(
+            "macd",
+            &[("fast_period", 3.0), ("slow_period", 6.0)],
+            MovingAverageConvergenceDivergence::new(3, 6, 9).unwrap(),
+        );
+        assert_same(
+            "bb",
+            &[("period", 10.0), ("multiplier", 1.5)],
+            BollingerBands::new(10, 1.5).unwrap(),
+        );
+        assert_same("chandelier", &[], ChandelierExit::new(22, 3.0).unwrap());
+    }
+
+    #[test]
+    fn test_create_builtin() {
+        for name in BUILTIN {
+            let mut indicator = create(name, &[]).unwrap();
+            let values = indicator.next_values(&bars()[0]);
+            assert_eq!(values.len(), indicator.output_names().len(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_output_names() {
+        assert_eq!(create("ema", &[]).unwrap().output_names(), ["value"]);
+        assert_eq!(
+            create("ppo", &[]).unwrap().output_names(),
+            ["ppo", "signal", "histogram"]
+        );
+        assert_eq!(
+            create("kc", &[]).unwrap().output_names(),
+            ["average", "upper", "lower"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_params() {
+        let error = |name: &str, params: &[(&str, f64)]| create(name, params).err();
+        let unknown = |name: &str| {
+            Some(TaError::UnknownIndicator {
+                name: name.to_string(),
+            })
+        };
+        let unknown_param = |name: &str| {
+            Some(TaError::UnknownParameter {
+                name: name.to_string(),
+            })
+        };
+        let invalid = |error: Option<TaError>, param: &str| match error {
+            Some(TaError::InvalidParameter { name, .. }) => name == param,
+            _ => false,
+        };
+
+        assert_eq!(error("unknown", &[]), unknown("unknown"));
+        assert_eq!(error("SMA", &[]), unknown("SMA"));
+        assert_eq!(error("sma", &[("length", 3.0)]), unknown_param("length"));
+        assert_eq!(error("tr", &[("period", 3.0)]), unknown_param("period"));
+        assert_eq!(
+            error("sma", &[("period", 0.0)]),
+            Some(TaError::zero_period("period"))
+        );
+        assert!(invalid(error("sma", &[("period", 2.5)]), "period"));
+        assert!(invalid(error("sma", &[("period", -3.0)]), "period"));
+        assert!(invalid(error("sma", &[("period", f64::NAN)]), "period"));
+        assert!(invalid(
+            error("bb", &[("multiplier", f64::INFINITY)]),
+            "multiplier"
+        ));
+        assert!(matches!(
+            error("macd", &[("fast_period", 26.0), ("slow_period", 26.0)]),
+            Some(TaError::InvalidPeriod {
+                name: "fast_period",
+                value: 26,
+                ..
+            })
+        ));
+        assert!(invalid(error("kc", &[("multiplier", -2.0)]), "multiplier"));
+    }
+
+    fn assert_rebuilt<I: Parameters + fmt::Display>(indicator: I) {
+        let values: Vec<(&str, f64)> = indicator
+            .params()
+            .iter()
+            .map(|&(name, value)| (name, value.as_f64()))
+            .collect();
+        let rebuilt = I::from_params(&Params::new(&values)).unwrap();
+        assert_eq!(rebuilt.to_string(), indicator.to_string());
+        assert_eq!(rebuilt.params(), indicator.params());
+    }
+
+    #[test]
+    fn test_parameters() {
+        assert_rebuilt(SimpleMovingAverage::new(4).unwrap());
+        assert_rebuilt(AverageTrueRange::new(5).unwrap());
+        assert_rebuilt(CommodityChannelIndex::new(6).unwrap());
+        assert_rebuilt(SlowStochastic::new(7, 2).unwrap());
+        assert_rebuilt(TrueRange::new());
+        assert_rebuilt(OnBalanceVolume::new());
+        assert_rebuilt(PercentagePriceOscillator::new(3, 5, 2).unwrap());
+        assert_rebuilt(KeltnerChannel::new(12, 1.5).unwrap());
+        assert_rebuilt(ChandelierExit::new(10, 2.5).unwrap());
+
+        // the parameter names are the ones of create
+        let mut slow = SlowStochastic::new(7, 2).unwrap();
+        slow.set_param("ema_period", ParamValue::Period(4)).unwrap();
+        assert_eq!(
+            create("slow_stochastic", &[("period", 7.0), ("ema_period", 4.0)])
+                .unwrap()
+                .to_string(),
+            slow.to_string()
+        );
+    }
+
+    #[test]
+    fn test_params() {
+        let params = Params::new(&[("period", 3.0), ("multiplier", 1.5), ("period", 4.0)]);
+
+        assert_eq!(params.get("period"), Some(4.0));
+        assert_eq!(params.get("other"), None);
+        assert_eq!(params.period("period", 9), Ok(4));
+        assert_eq!(params.period("other", 9), Ok(9));
+        assert_eq!(
+            params.period("multiplier", 9),
+            Err(TaError::invalid_parameter(
+                "multiplier",
+                1.5,
+                "must be a non-negative whole number"
+            ))
+        );
+        assert_eq!(params.float("multiplier", 2.0), Ok(1.5));
+        assert!(params.allow(&["period", "multiplier"]).is_ok());
+        assert!(params.allow(&["period"]).is_err());
+    }
+
+    #[test]
+    fn test_boxed_next_and_reset() {
+        let bars = bars();
+        let mut boxed = create("ema", &[("period", 3.0)]).unwrap();
+        let first = Next::next(&mut boxed, &bars[0]);
+        Next::next(&mut boxed, &bars[1]);
+
+        boxed.reset();
+        assert_eq!(Next::next(&mut boxed, &bars[0]), first);
+    }
+
+    fn fast_sma(params: &Params) -> Result<BoxedIndicator> {
+        params.allow(&[])?;
+        boxed(SimpleMovingAverage::new(2)?)
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::new();
+        registry.register("fast_sma", fast_sma);
+        registry.register("rsi", fast_sma);
+
+        assert_eq!(
+            registry.create("fast_sma", &[]).unwrap().to_string(),
+            "SMA(2)"
+        );
+        assert_eq!(registry.create("rsi", &[]).unwrap().to_string(), "SMA(2)");
+        assert_eq!(registry.create("ema", &[]).unwrap().to_string(), "EMA(9)");
+        assert!(registry.create("fast_sma", &[("period", 3.0)]).is_err());
+        assert!(registry.create("unknown", &[]).is_err());
+
+        let names = registry.names();
+        assert_eq!(names.len(), BUILTIN.len() + 1);
+        assert!(names.contains(&"fast_sma"));
+    }
+
+    #[test]
+    fn test_default() {
+        Registry::default();
+    }
+
+    #[test]
+    fn test_indicator_set() {
+        let mut set = IndicatorSet::new();
+        assert!(set.is_empty());
+        set.create("rsi", "rsi", &[("period", 5.0)]).unwrap();
+        set.create("macd", "macd", &[]).unwrap();
+        set.insert("trend", create("sma", &[("period", 4.0)]).unwrap());
+        assert!(set.create("bad", "sma", &[("period", 0.0)]).is_err());
+        assert_eq!(set.names(), ["rsi", "macd", "trend"]);
+
+        let mut rsi = RelativeStrengthIndex::new(5).unwrap();
+        let mut macd = MovingAverageConvergenceDivergence::default();
+        for bar in bars().iter() {
+            let values = set.next(bar);
+            assert_eq!(values.len(), 5);
+            assert_eq!(values["rsi"], rsi.next(bar));
+            assert_eq!(values["macd.histogram"], macd.next(bar).histogram);
+            assert!(values.contains_key("trend"));
+        }
+    }
+
+    #[test]
+    fn test_indicator_set_insert_remove() {
+        let mut set = IndicatorSet::new();
+        set.create("ma", "sma", &[]).unwrap();
+        set.create("ma", "ema", &[]).unwrap();
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.get("ma").unwrap().to_string(), "EMA(9)");
+        assert!(set.get("other").is_none());
+
+        assert_eq!(set.remove("ma").unwrap().to_string(), "EMA(9)");
+        assert!(set.remove("ma").is_none());
+        assert!(set.next(&bars()[0]).is_empty());
+    }
+
+    #[test]
+    fn test_indicator_set_reset() {
+        let bars = bars();
+        let mut set = IndicatorSet::new();
+        set.create("ema", "ema", &[("period", 3.0)]).unwrap();
+        let first = set.next(&bars[0]);
+        set.next(&bars[1]);
+
+        set.reset();
+        assert_eq!(set.next(&bars[0]), first);
+    }
+
+    #[test]
+    fn test_indicator_set_display() {
+        let mut set = IndicatorSet::new();
+        set.create("fast", "ema", &[("period", 3.0)]).unwrap();
+        set.create("bands", "bb", &[]).unwrap();
+        assert_eq!(
+            format!("{}", set),
+            "INDICATOR_SET(fast: EMA(3), bands: BB(9, 2))"
+        );
+        assert_eq!(
+            format!("{:?}", IndicatorSet::new()),
+            "IndicatorSet(INDICATOR_SET())"
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let mut registry = Registry::new();
+        registry.register("custom", fast_sma);
+        let display = format!("{}", registry);
+        assert!(display.starts_with("REGISTRY(atr, bb, cci, chandelier, custom, ema"));
+    }
+}
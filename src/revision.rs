@@ -0,0 +1,273 @@
+//! Recomputation of indicator outputs after a revision of historical data.
+//!
+//! Data vendors sometimes correct bars that were already published. Instead of rebuilding all
+//! stored outputs, [recompute](fn.recompute.html) replays the revised inputs through an
+//! indicator and reports only the outputs that changed, so a research database can be patched
+//! in place.
+//!
+//! Most indicators depend on the previous bars, so the replay has to start from the indicator
+//! state before the first revised bar. Keep a clone of the indicator as a checkpoint (or use a
+//! fresh indicator and replay the whole history), and pass the outputs stored from that point
+//! on. A correction usually changes the following outputs as well, e.g. forever for an EMA, or
+//! for _period_ bars for an SMA, so the replay runs to the end of the stored outputs.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::revision::recompute;
+//! use ta::Next;
+//!
+//! let closes = [1.0, 2.0, 3.0, 4.0, 5.0];
+//! let mut sma = SimpleMovingAverage::new(2).unwrap();
+//! let outputs: Vec<f64> = closes.iter().map(|&x| sma.next(x)).collect();
+//!
+//! // the vendor corrects the third close
+//! let revised = [1.0, 2.0, 5.0, 4.0, 5.0];
+//! let mut sma = SimpleMovingAverage::new(2).unwrap();
+//! let diff = recompute(&mut sma, revised.iter().copied(), &outputs).unwrap();
+//!
+//! let changed: Vec<usize> = diff.changes().iter().map(|c| c.index).collect();
+//! assert_eq!(changed, vec![2, 3]);
+//! assert_eq!(diff.changes()[0].new, 3.5);
+//! ```
+
+use crate::errors::{Result, TaError};
+use crate::Next;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An output, which differs after the revision.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OutputChange<O> {
+    /// Index of the output in the slice of stored outputs.
+    pub index: usize,
+    pub old: O,
+    pub new: O,
+}
+
+/// Outputs changed by a revision, ordered by index.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RevisionDiff<O> {
+    changes: Vec<OutputChange<O>>,
+}
+
+impl<O> RevisionDiff<O> {
+    pub fn changes(&self) -> &[OutputChange<O>] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Index of the first changed output.
+    pub fn first_changed(&self) -> Option<usize> {
+        self.changes.first().map(|change| change.index)
+    }
+
+    /// Index of the last changed output.
+    pub fn last_changed(&self) -> Option<usize> {
+        self.changes.last().map(|change| change.index)
+    }
+
+    /// Returns the changes of a signal derived from the outputs, e.g. of a crossover flag.
+    ///
+    /// Only the outputs, for which the signal differs before and after the revision, are
+    /// reported.
+    pub fn signal_changes<S, F>(&self, signal: F) -> Vec<OutputChange<S>>
+    where
+        S: PartialEq,
+        F: Fn(&O) -> S,
+    {
+        self.changes
+            .iter()
+            .filter_map(|change| {
+                let old = signal(&change.old);
+                let new = signal(&change.new);
+                (old != new).then_some(OutputChange {
+                    index: change.index,
+                    old,
+                    new,
+                })
+            })
+            .collect()
+    }
+}
+
+impl<O: Clone> RevisionDiff<O> {
+    /// Writes the new values into the stored outputs.
+    pub fn apply(&self, outputs: &mut [O]) {
+        for change in &self.changes {
+            outputs[change.index] = change.new.clone();
+        }
+    }
+}
+
+/// Replays the revised `inputs` through `indicator` and compares the results with `outputs`,
+/// computed before the revision.
+///
+/// The indicator must be in the state it had before the first stored output was computed.
+/// After the call it holds the state after the last input, so it can continue with new bars.
+///
+/// Returns `TaError::LengthMismatch` if the number of inputs differs from the number of
+/// outputs.
+pub fn recompute<I, T, O>(
+    indicator: &mut I,
+    inputs: impl IntoIterator<Item = T>,
+    outputs: &[O],
+) -> Result<RevisionDiff<O>>
+where
+    I: Next<T, Output = O>,
+    O: PartialEq + Clone,
+{
+    let mut changes = Vec::new();
+    let mut inputs = inputs.into_iter();
+
+    for (index, old) in outputs.iter().enumerate() {
+        let input = inputs.next().ok_or(TaError::LengthMismatch {
+            expected: outputs.len(),
+            actual: index,
+        })?;
+        let new = indicator.next(input);
+        if new != *old {
+            changes.push(OutputChange {
+                index,
+                old: old.clone(),
+                new,
+            });
+        }
+    }
+
+    if inputs.next().is_some() {
+        return Err(TaError::LengthMismatch {
+            expected: outputs.len(),
+            actual: outputs.len() + 1 + inputs.count(),
+        });
+    }
+
+    Ok(RevisionDiff { changes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{ExponentialMovingAverage, SimpleMovingAverage};
+    use crate::test_helper::*;
+
+    fn outputs<I: Next<f64, Output = f64>>(mut indicator: I, inputs: &[f64]) -> Vec<f64> {
+        inputs.iter().map(|&x| indicator.next(x)).collect()
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let closes = [1.0, 2.0, 3.0];
+        let stored = outputs(SimpleMovingAverage::new(2).unwrap(), &closes);
+
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        let diff = recompute(&mut sma, closes.iter().copied(), &stored).unwrap();
+        assert!(diff.is_empty());
+        assert_eq!(diff.first_changed(), None);
+    }
+
+    #[test]
+    fn test_changes() {
+        let closes = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let stored = outputs(SimpleMovingAverage::new(3).unwrap(), &closes);
+
+        let mut revised = closes;
+        revised[1] = 5.0;
+        let mut sma = SimpleMovingAverage::new(3).unwrap();
+        let diff = recompute(&mut sma, revised.iter().copied(), &stored).unwrap();
+
+        // the revised bar affects 3 outputs
+        assert_eq!(diff.first_changed(), Some(1));
+        assert_eq!(diff.last_changed(), Some(3));
+        assert_eq!(
+            diff.changes()[0],
+            OutputChange {
+                index: 1,
+                old: 1.5,
+                new: 3.0
+            }
+        );
+
+        // the indicator continues from the revised state
+        assert_eq!(sma.next(7.0), 6.0);
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let closes = [10.0, 11.0, 12.0, 13.0, 14.0];
+        let mut ema = ExponentialMovingAverage::new(3).unwrap();
+        ema.next(closes[0]);
+        ema.next(closes[1]);
+        let mut checkpoint = ema.clone();
+        let stored: Vec<f64> = closes[2..].iter().map(|&x| ema.next(x)).collect();
+
+        // replay only the bars after the checkpoint
+        let revised = [12.0, 15.0, 14.0];
+        let diff = recompute(&mut checkpoint, revised.iter().copied(), &stored).unwrap();
+        let changed: Vec<usize> = diff.changes().iter().map(|c| c.index).collect();
+        assert_eq!(changed, vec![1, 2]);
+        assert_eq!(round(diff.changes()[0].new), 13.125);
+
+        let mut patched = stored.clone();
+        diff.apply(&mut patched);
+        assert_eq!(
+            patched,
+            outputs(
+                ExponentialMovingAverage::new(3).unwrap(),
+                &[10.0, 11.0, 12.0, 15.0, 14.0]
+            )[2..]
+        );
+    }
+
+    #[test]
+    fn test_signal_changes() {
+        let closes = [1.0, 2.0, 3.0, 4.0];
+        let stored = outputs(SimpleMovingAverage::new(2).unwrap(), &closes);
+
+        let revised = [1.0, 2.0, 3.2, 4.0];
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        let diff = recompute(&mut sma, revised.iter().copied(), &stored).unwrap();
+        assert_eq!(diff.changes().len(), 2);
+
+        // only one output crosses the level
+        let signals = diff.signal_changes(|&sma| sma > 3.5);
+        assert_eq!(
+            signals,
+            vec![OutputChange {
+                index: 3,
+                old: false,
+                new: true
+            }]
+        );
+    }
+
+    #[test]
+    fn test_length_mismatch() {
+        let stored = [1.0, 1.5];
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        assert_eq!(
+            recompute(&mut sma, [1.0].iter().copied(), &stored).err(),
+            Some(TaError::LengthMismatch {
+                expected: 2,
+                actual: 1
+            })
+        );
+
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        assert_eq!(
+            recompute(&mut sma, [1.0, 2.0, 3.0, 4.0].iter().copied(), &stored).err(),
+            Some(TaError::LengthMismatch {
+                expected: 2,
+                actual: 4
+            })
+        );
+    }
+}
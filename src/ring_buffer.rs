@@ -0,0 +1,276 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::MemoryFootprint;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Fixed-capacity circular buffer keeping the last values of a window.
+///
+/// Pushing into a full buffer overwrites the oldest value in place and returns it, so moving
+/// the window is O(1), unlike removing the first element of a `Vec`. The storage grows up to
+/// the capacity with the first values and is never reallocated after that, also in a clone or
+/// a deserialized buffer, which reserve the full capacity up front.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        from = "SerializedRingBuffer<T>",
+        bound(deserialize = "T: Deserialize<'de>")
+    )
+)]
+#[derive(Debug)]
+pub struct RingBuffer<T = f64> {
+    capacity: usize,
+    values: Vec<T>,
+    /// Position of the oldest value once the buffer is full.
+    head: usize,
+}
+
+/// The fields of a deserialized buffer, before the capacity is reserved.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct SerializedRingBuffer<T> {
+    capacity: usize,
+    values: Vec<T>,
+    head: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<SerializedRingBuffer<T>> for RingBuffer<T> {
+    fn from(buffer: SerializedRingBuffer<T>) -> Self {
+        let mut values = buffer.values;
+        values.reserve_exact(buffer.capacity.saturating_sub(values.len()));
+        Self {
+            capacity: buffer.capacity,
+            values,
+            head: buffer.head,
+        }
+    }
+}
+
+impl<T: Clone> Clone for RingBuffer<T> {
+    fn clone(&self) -> Self {
+        let mut values = Vec::with_capacity(self.capacity);
+        values.extend_from_slice(&self.values);
+        Self {
+            capacity: self.capacity,
+            values,
+            head: self.head,
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.values.clear();
+        self.values.reserve_exact(source.capacity);
+        self.values.extend_from_slice(&source.values);
+        self.capacity = source.capacity;
+        self.head = source.head;
+    }
+}
+
+impl<T> RingBuffer<T> {
+    /// Creates an empty buffer. The capacity is validated by the indicators as their period,
+    /// so it is greater than 0.
+    pub fn new(capacity: usize) -> Self {
+        debug_assert!(capacity > 0);
+        Self {
+            capacity,
+            values: Vec::with_capacity(capacity),
+            head: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.values.len() == self.capacity
+    }
+
+    /// Appends a value and returns the oldest one, if it left the window.
+    pub fn push(&mut self, value: T) -> Option<T> {
+        if self.values.len() < self.capacity {
+            self.values.push(value);
+            return None;
+        }
+        let oldest = core::mem::replace(&mut self.values[self.head], value);
+        self.head = if self.head + 1 < self.capacity {
+            self.head + 1
+        } else {
+            0
+        };
+        Some(oldest)
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.head = 0;
+    }
+}
+
+impl<T: Copy> RingBuffer<T> {
+    /// The oldest value, which the next push into a full buffer replaces.
+    pub fn oldest(&self) -> Option<T> {
+        self.values.get(self.head).copied()
+    }
+
+    /// The value `index` places after the oldest one.
+    pub fn get(&self, index: usize) -> Option<T> {
+        let len = self.values.len();
+        if index >= len {
+            return None;
+        }
+        let position = self.head + index;
+        Some(
+            self.values[if position < len {
+                position
+            } else {
+                position - len
+            }],
+        )
+    }
+
+    /// The value `ago` places before the latest one, which is 0 places ago.
+    pub fn get_back(&self, ago: usize) -> Option<T> {
+        self.len()
+            .checked_sub(ago + 1)
+            .and_then(|index| self.get(index))
+    }
+
+    /// The values, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = T> + Clone + '_ {
+        let (tail, head) = self.values.split_at(self.head);
+        head.iter().chain(tail.iter()).copied()
+    }
+}
+
+impl<T> MemoryFootprint for RingBuffer<T> {
+    fn heap_size(&self) -> usize {
+        self.values.capacity() * core::mem::size_of::<T>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    fn values(buffer: &RingBuffer) -> Vec<f64> {
+        buffer.iter().collect()
+    }
+
+    #[test]
+    fn test_push() {
+        let mut buffer = RingBuffer::new(3);
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.push(1.0), None);
+        assert_eq!(buffer.push(2.0), None);
+        assert_eq!(buffer.push(3.0), None);
+        assert!(buffer.is_full());
+        assert_eq!(buffer.push(4.0), Some(1.0));
+        assert_eq!(buffer.push(5.0), Some(2.0));
+        assert_eq!(values(&buffer), vec![3.0, 4.0, 5.0]);
+        assert_eq!(buffer.len(), 3);
+    }
+
+    #[test]
+    fn test_oldest() {
+        let mut buffer = RingBuffer::new(2);
+        assert_eq!(buffer.oldest(), None);
+        buffer.push(1.0);
+        assert_eq!(buffer.oldest(), Some(1.0));
+        buffer.push(2.0);
+        buffer.push(3.0);
+        assert_eq!(buffer.oldest(), Some(2.0));
+        buffer.push(4.0);
+        assert_eq!(buffer.oldest(), Some(3.0));
+    }
+
+    #[test]
+    fn test_get() {
+        let mut buffer = RingBuffer::new(3);
+        buffer.push(1.0);
+        buffer.push(2.0);
+        assert_eq!(buffer.get(1), Some(2.0));
+        assert_eq!(buffer.get(2), None);
+        for value in 3..6 {
+            buffer.push(value as f64);
+        }
+        assert_eq!(buffer.get(0), Some(3.0));
+        assert_eq!(buffer.get(2), Some(5.0));
+        assert_eq!(buffer.get(3), None);
+        assert_eq!(buffer.get_back(0), Some(5.0));
+        assert_eq!(buffer.get_back(2), Some(3.0));
+        assert_eq!(buffer.get_back(3), None);
+    }
+
+    #[test]
+    fn test_capacity_one() {
+        let mut buffer = RingBuffer::new(1);
+        buffer.push(1.0);
+        assert_eq!(buffer.push(2.0), Some(1.0));
+        assert_eq!(values(&buffer), vec![2.0]);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut buffer = RingBuffer::new(2);
+        for value in 0..5 {
+            buffer.push(value as f64);
+        }
+        buffer.clear();
+        assert!(buffer.is_empty());
+        buffer.push(7.0);
+        assert_eq!(values(&buffer), vec![7.0]);
+    }
+
+    #[test]
+    fn test_clone_keeps_capacity() {
+        let mut buffer = RingBuffer::new(5);
+        buffer.push(1.0);
+        buffer.push(2.0);
+
+        let mut clone = buffer.clone();
+        assert_eq!(clone.heap_size(), 5 * 8);
+        clone.clone_from(&buffer);
+        assert_eq!(clone.heap_size(), 5 * 8);
+        assert_eq!(values(&clone), vec![1.0, 2.0]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize_keeps_capacity() {
+        let mut buffer = RingBuffer::new(5);
+        for value in 0..7 {
+            buffer.push(value as f64);
+        }
+        buffer.clear();
+        buffer.push(1.0);
+
+        let json = serde_json::to_string(&buffer).unwrap();
+        let deserialized: RingBuffer = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.heap_size(), 5 * 8);
+        assert_eq!(values(&deserialized), vec![1.0]);
+    }
+
+    #[test]
+    fn test_heap_size() {
+        let mut buffer = RingBuffer::new(5);
+        assert_eq!(buffer.heap_size(), 5 * 8);
+        for value in 0..12 {
+            buffer.push(value as f64);
+        }
+        assert_eq!(buffer.heap_size(), 5 * 8);
+    }
+}
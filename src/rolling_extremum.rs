@@ -0,0 +1,127 @@
+use alloc::collections::VecDeque;
+
+use crate::MemoryFootprint;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Highest or lowest value of the last _period_ values, in O(1) amortized per value.
+///
+/// A monotonic deque keeps only the values, which can still become the extremum: a new value
+/// removes all the values behind it, which it beats, as they leave the window before it. The
+/// front of the deque is the extremum, and leaves the deque, when it leaves the window. Every
+/// value is pushed and removed once, instead of scanning the window, when the extremum leaves.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RollingExtremum {
+    period: usize,
+    highest: bool,
+    /// Number of values seen so far.
+    seen: usize,
+    /// Positions and values of the candidates, oldest first.
+    deque: VecDeque<(usize, f64)>,
+}
+
+impl RollingExtremum {
+    pub fn highest(period: usize) -> Self {
+        Self::new(period, true)
+    }
+
+    pub fn lowest(period: usize) -> Self {
+        Self::new(period, false)
+    }
+
+    fn new(period: usize, highest: bool) -> Self {
+        debug_assert!(period > 0);
+        Self {
+            period,
+            highest,
+            seen: 0,
+            deque: VecDeque::with_capacity(period),
+        }
+    }
+
+    /// Number of values in the window.
+    pub fn len(&self) -> usize {
+        self.seen.min(self.period)
+    }
+
+    /// Adds a value and returns the extremum of the window.
+    pub fn push(&mut self, value: f64) -> f64 {
+        // the leaving value goes first, so the deque never exceeds its initial capacity
+        while let Some(&(position, _)) = self.deque.front() {
+            if position + self.period > self.seen {
+                break;
+            }
+            self.deque.pop_front();
+        }
+        while let Some(&(_, last)) = self.deque.back() {
+            let beaten = if self.highest {
+                last <= value
+            } else {
+                last >= value
+            };
+            if !beaten {
+                break;
+            }
+            self.deque.pop_back();
+        }
+        self.deque.push_back((self.seen, value));
+        self.seen += 1;
+        self.deque[0].1
+    }
+
+    pub fn clear(&mut self) {
+        self.seen = 0;
+        self.deque.clear();
+    }
+}
+
+impl MemoryFootprint for RollingExtremum {
+    fn heap_size(&self) -> usize {
+        self.deque.capacity() * core::mem::size_of::<(usize, f64)>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highest() {
+        let mut max = RollingExtremum::highest(3);
+        let outputs: [f64; 7] = [4.0, 1.0, 5.0, 3.0, 4.0, 0.0, -1.0].map(|x| max.push(x));
+        assert_eq!(outputs, [4.0, 4.0, 5.0, 5.0, 5.0, 4.0, 4.0]);
+        assert_eq!(max.len(), 3);
+    }
+
+    #[test]
+    fn test_lowest() {
+        let mut min = RollingExtremum::lowest(2);
+        let outputs: [f64; 5] = [3.0, 1.0, 2.0, 2.0, 5.0].map(|x| min.push(x));
+        assert_eq!(outputs, [3.0, 1.0, 1.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn test_same_as_scan() {
+        let values: [f64; 64] = core::array::from_fn(|i| ((i * 37) % 17) as f64);
+        let mut max = RollingExtremum::highest(5);
+        let mut min = RollingExtremum::lowest(5);
+        for (i, &value) in values.iter().enumerate() {
+            let window = &values[(i + 1).saturating_sub(5)..=i];
+            let highest = window.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let lowest = window.iter().copied().fold(f64::INFINITY, f64::min);
+            assert_eq!(max.push(value), highest);
+            assert_eq!(min.push(value), lowest);
+        }
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut max = RollingExtremum::highest(3);
+        max.push(10.0);
+        max.clear();
+        assert_eq!(max.len(), 0);
+        assert_eq!(max.push(1.0), 1.0);
+    }
+}
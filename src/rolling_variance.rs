@@ -0,0 +1,154 @@
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::helpers::cast;
+
+/// Mean and variance of a window, updated incrementally with Welford's algorithm.
+///
+/// Values are added, removed or replaced one at a time, so the variance of a moving window is
+/// updated in O(1), without summing up the window again. Unlike the difference of the sum of
+/// squares and the squared sum, the sum of squared deviations `m2` does not lose its precision,
+/// when the values are large compared to their spread.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub struct RollingVariance<T = f64> {
+    count: usize,
+    mean: T,
+    /// Sum of the squared deviations from the mean.
+    m2: T,
+}
+
+impl<T: Float> RollingVariance<T> {
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            mean: T::zero(),
+            m2: T::zero(),
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn mean(&self) -> T {
+        self.mean
+    }
+
+    /// Sum of the squared deviations from the mean.
+    pub fn m2(&self) -> T {
+        self.m2
+    }
+
+    /// Population variance, 0 without values.
+    pub fn variance(&self) -> T {
+        match self.count {
+            0 => T::zero(),
+            count => self.m2 / cast(count),
+        }
+    }
+
+    pub fn add(&mut self, value: T) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean = self.mean + delta / cast(self.count);
+        self.m2 = self.m2 + delta * (value - self.mean);
+        self.clamp();
+    }
+
+    /// Removes a value added before.
+    pub fn remove(&mut self, value: T) {
+        self.count -= 1;
+        if self.count == 0 {
+            self.clear();
+            return;
+        }
+        let delta = value - self.mean;
+        self.mean = self.mean - delta / cast(self.count);
+        self.m2 = self.m2 - delta * (value - self.mean);
+        self.clamp();
+    }
+
+    /// Replaces a value added before with a new one, keeping the count.
+    pub fn replace(&mut self, old: T, new: T) {
+        let delta = new - old;
+        let old_mean = self.mean;
+        self.mean = self.mean + delta / cast(self.count);
+        self.m2 = self.m2 + delta * (new - self.mean + old - old_mean);
+        self.clamp();
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    // rounding may push the sum of squares of equal values just below 0
+    fn clamp(&mut self) {
+        if self.m2 < T::zero() {
+            self.m2 = T::zero();
+        }
+    }
+}
+
+impl<T: Float> Default for RollingVariance<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive(values: &[f64]) -> (f64, f64) {
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let variance =
+            values.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / values.len() as f64;
+        (mean, variance)
+    }
+
+    #[test]
+    fn test_add() {
+        let mut variance = RollingVariance::new();
+        assert_eq!(variance.variance(), 0.0);
+        for &x in &[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            variance.add(x);
+        }
+        assert_eq!(variance.count(), 8);
+        assert_eq!(variance.mean(), 5.0);
+        assert_eq!(variance.variance(), 4.0);
+    }
+
+    #[test]
+    fn test_remove_and_replace() {
+        let values: [f64; 40] = core::array::from_fn(|i| 1e6 + ((i * 7) % 11) as f64);
+        let mut removed = RollingVariance::new();
+        let mut replaced = RollingVariance::new();
+        for (i, &x) in values.iter().enumerate() {
+            removed.add(x);
+            if i >= 5 {
+                removed.remove(values[i - 5]);
+                replaced.replace(values[i - 5], x);
+            } else {
+                replaced.add(x);
+            }
+            let (mean, variance) = naive(&values[(i + 1).saturating_sub(5)..=i]);
+            for stats in &[removed, replaced] {
+                assert!((stats.mean() - mean).abs() < 1e-6);
+                assert!((stats.variance() - variance).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_remove_last() {
+        let mut variance = RollingVariance::new();
+        variance.add(3.0);
+        variance.remove(3.0);
+        assert_eq!(
+            (variance.count(), variance.mean(), variance.m2()),
+            (0, 0.0, 0.0)
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Sensitivity of indicators to noise in the input prices.
+//!
+//! Prices of the same instrument differ slightly between data vendors: ticks are filtered
+//! differently, bars are built from different feeds, adjustments are rounded differently.
+//! [SensitivityAnalysis](struct.SensitivityAnalysis.html) shows how much such differences
+//! matter for an indicator: it perturbs the prices many times, replays them through the
+//! indicator and reports the distribution of the output changes together with the number of
+//! flips of a signal derived from the output.
+//!
+//! Comparing the reports of several indicators, or of one indicator with different
+//! parameters, shows which of them are robust to feed noise, e.g. a long SMA barely moves,
+//! while a short stochastic or a crossover close to its threshold does.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::SimpleMovingAverage;
+//! use ta::sensitivity::{Perturbation, SensitivityAnalysis};
+//!
+//! let prices: Vec<f64> = (0..100).map(|i| 100.0 + (i as f64 * 0.3).sin() * 2.0).collect();
+//!
+//! // shift every price by up to 2 ticks of 0.01
+//! let perturbation = Perturbation::Ticks { size: 0.01, max: 2 };
+//! let analysis = SensitivityAnalysis::new(perturbation, 50, 42).unwrap();
+//!
+//! let short = SimpleMovingAverage::new(2).unwrap();
+//! let long = SimpleMovingAverage::new(20).unwrap();
+//! let short = analysis.analyze(&short, &prices, |sma| sma > 100.0);
+//! let long = analysis.analyze(&long, &prices, |sma| sma > 100.0);
+//!
+//! // an average of two prices moves by 2 ticks at most
+//! assert!(short.change.max < 0.0201);
+//! assert!(long.change.mean < short.change.mean);
+//! ```
+
+use crate::errors::{Result, TaError};
+use crate::helpers::SplitMix64;
+use crate::{Next, Reset};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How the prices are perturbed. Every price is perturbed independently.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Perturbation {
+    /// Multiplies a price by `1 + e`, where `e` is uniformly distributed in
+    /// [-fraction, fraction], e.g. 0.001 for noise up to 0.1%.
+    Relative(f64),
+    /// Shifts a price by a whole number of ticks, uniformly distributed in [-max, max].
+    Ticks { size: f64, max: u32 },
+}
+
+impl Perturbation {
+    fn apply(&self, price: f64, rng: &mut SplitMix64) -> f64 {
+        match *self {
+            Perturbation::Relative(fraction) => {
+                price * (1.0 + fraction * (2.0 * rng.uniform() - 1.0))
+            }
+            Perturbation::Ticks { size, max } => {
+                let ticks = rng.below(2 * max as usize + 1) as f64 - max as f64;
+                price + ticks * size
+            }
+        }
+    }
+}
+
+/// Distribution of the absolute changes of the outputs.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ChangeDistribution {
+    pub mean: f64,
+    pub median: f64,
+    /// 95th percentile.
+    pub p95: f64,
+    pub max: f64,
+    /// Mean of the signed changes. Far from 0, when the indicator reacts to the noise
+    /// asymmetrically.
+    pub bias: f64,
+}
+
+/// Result of a [SensitivityAnalysis](struct.SensitivityAnalysis.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SensitivityReport {
+    /// Number of perturbed runs.
+    pub runs: usize,
+    /// Number of bars in every run.
+    pub bars: usize,
+    /// Changes of the outputs over all bars of all runs, compared to the unperturbed run.
+    pub change: ChangeDistribution,
+    /// Number of bars (over all runs), at which the signal differs from the unperturbed run.
+    pub signal_flips: usize,
+    /// Share of the bars, at which the signal differs from the unperturbed run.
+    pub flip_rate: f64,
+}
+
+/// Sensitivity analysis of an indicator to noise in the input prices.
+///
+/// The indicator is replayed over the original prices and over _runs_ perturbed copies of
+/// them, every time from a reset clone, so the passed indicator is not changed. The runs are
+/// driven by a seeded pseudo random generator, so results are reproducible.
+///
+/// # Parameters
+///
+/// * _perturbation_ - how the prices are perturbed (fraction or tick size greater than 0,
+///   at least 1 tick)
+/// * _runs_ - number of perturbed runs (integer greater than 0)
+/// * _seed_ - seed of the random generator
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct SensitivityAnalysis {
+    perturbation: Perturbation,
+    runs: usize,
+    seed: u64,
+}
+
+impl SensitivityAnalysis {
+    pub fn new(perturbation: Perturbation, runs: usize, seed: u64) -> Result<Self> {
+        match perturbation {
+            Perturbation::Relative(fraction) if !(fraction.is_finite() && fraction > 0.0) => {
+                return Err(TaError::invalid_parameter(
+                    "fraction",
+                    fraction,
+                    "must be a positive number",
+                ));
+            }
+            Perturbation::Ticks { size, .. } if !(size.is_finite() && size > 0.0) => {
+                return Err(TaError::invalid_parameter(
+                    "size",
+                    size,
+                    "must be a positive number",
+                ));
+            }
+            Perturbation::Ticks { max: 0, .. } => return Err(TaError::zero_period("max")),
+            _ => {}
+        }
+        if runs == 0 {
+            return Err(TaError::zero_period("runs"));
+        }
+        Ok(Self {
+            perturbation,
+            runs,
+            seed,
+        })
+    }
+
+    pub fn perturbation(&self) -> Perturbation {
+        self.perturbation
+    }
+
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Analyzes an indicator together with a signal derived from its output, e.g. whether
+    /// the RSI is above 70. Pass `|_| ()` to analyze the outputs only.
+    pub fn analyze<I, S, F>(&self, indicator: &I, prices: &[f64], signal: F) -> SensitivityReport
+    where
+        I: Clone + Reset + Next<f64, Output = f64>,
+        S: PartialEq,
+        F: Fn(f64) -> S,
+    {
+        let replay = |prices: &mut dyn Iterator<Item = f64>| -> Vec<f64> {
+            let mut indicator = indicator.clone();
+            indicator.reset();
+            prices.map(|price| indicator.next(price)).collect()
+        };
+
+        let base = replay(&mut prices.iter().copied());
+        let base_signals: Vec<S> = base.iter().map(|&x| signal(x)).collect();
+
+        let mut rng = SplitMix64::new(self.seed);
+        let mut changes = Vec::with_capacity(self.runs * prices.len());
+        let mut signal_flips = 0;
+        for _ in 0..self.runs {
+            let outputs = replay(
+                &mut prices
+                    .iter()
+                    .map(|&price| self.perturbation.apply(price, &mut rng)),
+            );
+            for ((&output, &base_output), base_signal) in
+                outputs.iter().zip(&base).zip(&base_signals)
+            {
+                changes.push(output - base_output);
+                if signal(output) != *base_signal {
+                    signal_flips += 1;
+                }
+            }
+        }
+
+        let samples = changes.len();
+        SensitivityReport {
+            runs: self.runs,
+            bars: prices.len(),
+            change: change_distribution(changes),
+            signal_flips,
+            flip_rate: if samples > 0 {
+                signal_flips as f64 / samples as f64
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+fn change_distribution(changes: Vec<f64>) -> ChangeDistribution {
+    if changes.is_empty() {
+        return ChangeDistribution::default();
+    }
+    let n = changes.len() as f64;
+    let bias = changes.iter().sum::<f64>() / n;
+    let mut abs: Vec<f64> = changes.iter().map(|x| x.abs()).collect();
+    abs.sort_by(|a, b| a.total_cmp(b));
+
+    ChangeDistribution {
+        mean: abs.iter().sum::<f64>() / n,
+        median: quantile(&abs, 0.5),
+        p95: quantile(&abs, 0.95),
+        max: abs[abs.len() - 1],
+        bias,
+    }
+}
+
+/// Quantile of sorted values, linearly interpolated between the closest ranks.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let rank = pos.floor() as usize;
+    let fraction = pos - rank as f64;
+    match sorted.get(rank + 1) {
+        Some(next) => sorted[rank] + (next - sorted[rank]) * fraction,
+        None => sorted[rank],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::SimpleMovingAverage;
+
+    fn prices() -> Vec<f64> {
+        (0..50).map(|i| 10.0 + (i as f64 * 0.5).sin()).collect()
+    }
+
+    #[test]
+    fn test_new() {
+        let ticks = Perturbation::Ticks { size: 0.01, max: 1 };
+        assert!(SensitivityAnalysis::new(ticks, 0, 1).is_err());
+        assert!(SensitivityAnalysis::new(Perturbation::Relative(0.0), 10, 1).is_err());
+        assert!(SensitivityAnalysis::new(Perturbation::Relative(f64::NAN), 10, 1).is_err());
+        let zero_ticks = Perturbation::Ticks { size: 0.01, max: 0 };
+        assert!(SensitivityAnalysis::new(zero_ticks, 10, 1).is_err());
+        let zero_size = Perturbation::Ticks { size: 0.0, max: 1 };
+        assert!(SensitivityAnalysis::new(zero_size, 10, 1).is_err());
+        assert!(SensitivityAnalysis::new(ticks, 1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_ticks() {
+        let perturbation = Perturbation::Ticks { size: 0.5, max: 1 };
+        let analysis = SensitivityAnalysis::new(perturbation, 20, 7).unwrap();
+        let identity = SimpleMovingAverage::new(1).unwrap();
+
+        let report = analysis.analyze(&identity, &prices(), |_| ());
+        assert_eq!(report.runs, 20);
+        assert_eq!(report.bars, 50);
+        // every output moves by 0 or 1 tick
+        assert_eq!(report.change.max, 0.5);
+        assert!(report.change.mean > 0.1 && report.change.mean < 0.4);
+        assert_eq!(report.change.p95, 0.5);
+        assert_eq!(report.signal_flips, 0);
+        assert_eq!(report.flip_rate, 0.0);
+    }
+
+    #[test]
+    fn test_relative() {
+        let analysis = SensitivityAnalysis::new(Perturbation::Relative(0.01), 20, 7).unwrap();
+        let identity = SimpleMovingAverage::new(1).unwrap();
+        let sma = SimpleMovingAverage::new(10).unwrap();
+
+        let identity = analysis.analyze(&identity, &prices(), |_| ());
+        let sma = analysis.analyze(&sma, &prices(), |_| ());
+        // prices are below 11
+        assert!(identity.change.max <= 0.11);
+        assert!(identity.change.max > identity.change.median);
+        // averaging cancels the noise out
+        assert!(sma.change.mean < identity.change.mean);
+        assert!(sma.change.p95 < identity.change.p95);
+    }
+
+    #[test]
+    fn test_signal_flips() {
+        let perturbation = Perturbation::Ticks { size: 0.1, max: 1 };
+        let analysis = SensitivityAnalysis::new(perturbation, 10, 3).unwrap();
+        let identity = SimpleMovingAverage::new(1).unwrap();
+        let prices = [10.0, 10.05, 12.0, 9.95, 8.0];
+
+        // a threshold far from the prices never flips
+        let report = analysis.analyze(&identity, &prices, |x| x > 20.0);
+        assert_eq!(report.signal_flips, 0);
+
+        // a threshold right between the prices flips often
+        let report = analysis.analyze(&identity, &prices, |x| x > 10.0);
+        assert!(report.signal_flips > 0);
+        assert!(report.flip_rate > 0.0 && report.flip_rate <= 0.6);
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let perturbation = Perturbation::Relative(0.001);
+        let sma = SimpleMovingAverage::new(3).unwrap();
+
+        let a = SensitivityAnalysis::new(perturbation, 5, 11).unwrap();
+        let b = SensitivityAnalysis::new(perturbation, 5, 11).unwrap();
+        assert_eq!(
+            a.analyze(&sma, &prices(), |x| x > 10.0),
+            b.analyze(&sma, &prices(), |x| x > 10.0)
+        );
+    }
+
+    #[test]
+    fn test_indicator_not_changed() {
+        let analysis = SensitivityAnalysis::new(Perturbation::Relative(0.01), 5, 1).unwrap();
+        let mut sma = SimpleMovingAverage::new(2).unwrap();
+        sma.next(4.0);
+
+        analysis.analyze(&sma, &prices(), |_| ());
+        assert_eq!(sma.next(6.0), 5.0);
+    }
+
+    #[test]
+    fn test_empty() {
+        let analysis = SensitivityAnalysis::new(Perturbation::Relative(0.01), 5, 1).unwrap();
+        let sma = SimpleMovingAverage::new(2).unwrap();
+
+        let report = analysis.analyze(&sma, &[], |_| ());
+        assert_eq!(report.bars, 0);
+        assert_eq!(report.change, ChangeDistribution::default());
+        assert_eq!(report.flip_rate, 0.0);
+    }
+
+    #[test]
+    fn test_quantile() {
+        assert_eq!(quantile(&[1.0], 0.95), 1.0);
+        assert_eq!(quantile(&[1.0, 2.0, 3.0], 0.5), 2.0);
+        assert_eq!(quantile(&[0.0, 1.0, 2.0, 3.0], 0.5), 1.5);
+    }
+}
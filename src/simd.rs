@@ -0,0 +1,167 @@
+//! Lane-wise kernels of the batch implementations (`simd` feature).
+//!
+//! The kernels load `f64` values into `f64x4` and `f32` values into `f32x8` vectors of the
+//! [wide](https://docs.rs/wide) crate, which compiles them to SSE/AVX, NEON or WASM SIMD
+//! instructions on stable Rust. Only the parts of a batch without a dependency between the
+//! outputs go through them, e.g. the distances between the inputs or the final division,
+//! while the running sums stay sequential in the order of `next`. Subtraction, absolute value
+//! and division are rounded the same in every lane as in scalar code, so the outputs are
+//! exactly the same as with `next`.
+
+use core::ops::{Div, Sub};
+
+use num_traits::Float;
+use wide::{f32x8, f64x4};
+
+/// A SIMD vector of `LANES` values of type `Scalar`.
+pub trait Lanes: Copy + Sub<Output = Self> + Div<Output = Self> {
+    type Scalar: Float;
+    const LANES: usize;
+
+    fn splat(value: Self::Scalar) -> Self;
+
+    fn abs(self) -> Self;
+
+    /// Loads up to `LANES` values, the missing lanes are 0.
+    fn load(values: &[Self::Scalar]) -> Self;
+
+    /// Stores the first `out.len()` lanes.
+    fn store(self, out: &mut [Self::Scalar]);
+}
+
+macro_rules! impl_lanes {
+    ($vector:ty, $scalar:ty, $lanes:expr) => {
+        impl Lanes for $vector {
+            type Scalar = $scalar;
+            const LANES: usize = $lanes;
+
+            fn splat(value: $scalar) -> Self {
+                <$vector>::splat(value)
+            }
+
+            fn abs(self) -> Self {
+                <$vector>::abs(self)
+            }
+
+            fn load(values: &[$scalar]) -> Self {
+                let mut lanes = [0.0; $lanes];
+                lanes[..values.len()].copy_from_slice(values);
+                <$vector>::new(lanes)
+            }
+
+            fn store(self, out: &mut [$scalar]) {
+                out.copy_from_slice(&self.to_array()[..out.len()]);
+            }
+        }
+    };
+}
+
+impl_lanes!(f64x4, f64, 4);
+impl_lanes!(f32x8, f32, 8);
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for f64 {}
+    impl Sealed for f32 {}
+}
+
+/// A value type with a SIMD vector, implemented for `f64` and `f32` only.
+pub trait SimdFloat: Float + sealed::Sealed {
+    type Vector: Lanes<Scalar = Self>;
+}
+
+impl SimdFloat for f64 {
+    type Vector = f64x4;
+}
+
+impl SimdFloat for f32 {
+    type Vector = f32x8;
+}
+
+/// `out[i] = f(a[i], b[i])` for vectors of `V`.
+fn zip_lanes<V: Lanes>(
+    a: &[V::Scalar],
+    b: &[V::Scalar],
+    out: &mut [V::Scalar],
+    f: impl Fn(V, V) -> V,
+) {
+    let chunks = a.chunks(V::LANES).zip(b.chunks(V::LANES));
+    for ((a, b), out) in chunks.zip(out.chunks_mut(V::LANES)) {
+        f(V::load(a), V::load(b)).store(out);
+    }
+}
+
+/// `values[i] = f(values[i])` for vectors of `V`.
+fn map_lanes<V: Lanes>(values: &mut [V::Scalar], f: impl Fn(V) -> V) {
+    for chunk in values.chunks_mut(V::LANES) {
+        f(V::load(chunk)).store(chunk);
+    }
+}
+
+/// `out[i] = |a[i] - b[i]|`
+pub fn abs_diff<T: SimdFloat>(a: &[T], b: &[T], out: &mut [T]) {
+    debug_assert!(a.len() == out.len() && b.len() == out.len());
+    zip_lanes::<T::Vector>(a, b, out, |a, b| (a - b).abs())
+}
+
+/// `values[i] = values[i] / divisor`
+pub fn div<T: SimdFloat>(values: &mut [T], divisor: T) {
+    let divisor = T::Vector::splat(divisor);
+    map_lanes::<T::Vector>(values, |values| values / divisor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::{vec, vec::Vec};
+
+    fn values(n: usize) -> Vec<f64> {
+        (0..n).map(|i| (i * i % 7) as f64 - 3.0).collect()
+    }
+
+    #[test]
+    fn test_lengths() {
+        // lengths with and without a remainder
+        for n in [0, 3, 4, 9] {
+            let (a, b) = (values(n), values(n + 1)[1..].to_vec());
+            let mut out = vec![0.0; n];
+            abs_diff(&a, &b, &mut out);
+            let expected: Vec<f64> = a.iter().zip(&b).map(|(a, b)| (a - b).abs()).collect();
+            assert_eq!(out, expected);
+        }
+    }
+
+    #[test]
+    fn test_abs_diff() {
+        let mut out = vec![0.0; 5];
+        abs_diff(
+            &[1.0, 5.0, 2.0, 2.0, -1.0],
+            &[3.0, 1.0, 2.0, 0.5, 1.0],
+            &mut out,
+        );
+        assert_eq!(out, vec![2.0, 4.0, 0.0, 1.5, 2.0]);
+    }
+
+    #[test]
+    fn test_div() {
+        let mut values = vec![2.0, 4.0, 6.0, 8.0, 10.0, 12.0];
+        div(&mut values, 2.0);
+        assert_eq!(values, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    }
+
+    #[test]
+    fn test_f32() {
+        let a: Vec<f32> = (0..11).map(|i| i as f32 * 1.5).collect();
+        let b: Vec<f32> = (0..11).map(|i| (i * i) as f32).collect();
+        let mut out = vec![0.0; 11];
+        abs_diff(&a, &b, &mut out);
+        let expected: Vec<f32> = a.iter().zip(&b).map(|(a, b)| (a - b).abs()).collect();
+        assert_eq!(out, expected);
+
+        div(&mut out, 4.0);
+        let expected: Vec<f32> = expected.iter().map(|value| value / 4.0).collect();
+        assert_eq!(out, expected);
+    }
+}
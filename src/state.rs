@@ -0,0 +1,322 @@
+//! Versioned snapshots of the indicator state (`serde` feature).
+//!
+//! Every indicator serializes its full state: parameters, windows, counters and smoothed
+//! values. A service can persist its indicators on shutdown and continue after a restart
+//! with exactly the same outputs, instead of replaying weeks of bars.
+//!
+//! The serialized layout of an indicator changes, when its fields change between versions of
+//! the crate. [Versioned](struct.Versioned.html) stores the state together with
+//! [STATE_VERSION](constant.STATE_VERSION.html), and refuses to deserialize a state written
+//! with a different layout version, so a stale snapshot fails loudly instead of being read
+//! into the wrong fields. Such a snapshot has to be rebuilt by replaying the bars.
+//!
+//! The indicators of the [decimal](../decimal/index.html) module deserialize only from
+//! self-describing formats like JSON, since `Decimal` accepts both strings and numbers.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::indicators::RelativeStrengthIndex;
+//! use ta::state::Versioned;
+//! use ta::Next;
+//!
+//! let mut rsi = RelativeStrengthIndex::new(3).unwrap();
+//! rsi.next(10.0);
+//! rsi.next(10.5);
+//!
+//! let snapshot = Versioned::new(rsi.clone());
+//! # let bytes = bincode::serialize(&snapshot).unwrap();
+//! # let snapshot: Versioned<RelativeStrengthIndex> = bincode::deserialize(&bytes).unwrap();
+//! // store the snapshot with any serde format, load it after the restart
+//! let mut restored = snapshot.into_inner();
+//!
+//! assert_eq!(restored.next(10.0), rsi.next(10.0));
+//! ```
+
+use serde::de::{Deserializer, Error};
+use serde::{Deserialize, Serialize};
+
+/// Layout version of the serialized indicator state.
+///
+/// Incremented, when the fields of an indicator change in an incompatible way.
+pub const STATE_VERSION: u32 = 12;
+
+/// State of an indicator, tagged with the [layout version](constant.STATE_VERSION.html).
+///
+/// Deserialization fails, if the stored version differs from `STATE_VERSION`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Versioned<I> {
+    #[serde(deserialize_with = "current_version")]
+    version: u32,
+    state: I,
+}
+
+impl<I> Versioned<I> {
+    pub fn new(state: I) -> Self {
+        Self {
+            version: STATE_VERSION,
+            state,
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn state(&self) -> &I {
+        &self.state
+    }
+
+    pub fn into_inner(self) -> I {
+        self.state
+    }
+}
+
+fn current_version<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    let version = u32::deserialize(deserializer)?;
+    if version == STATE_VERSION {
+        Ok(version)
+    } else {
+        Err(D::Error::custom(format_args!(
+            "unsupported state version {}, expected {}",
+            version, STATE_VERSION
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::breadth::*;
+    use crate::indicators::*;
+    use crate::live::LiveBar;
+    use crate::test_helper::*;
+    use crate::{MemoryFootprint, Next};
+    use core::fmt::Debug;
+    use serde::de::DeserializeOwned;
+
+    /// Feeds half of the inputs, restores the indicator from its serialized snapshot and
+    /// checks that both continue with the same outputs.
+    fn assert_round_trip<I, T>(indicator: I, inputs: &[T])
+    where
+        I: Next<T> + Clone + Serialize + DeserializeOwned,
+        I::Output: PartialEq + Debug,
+        T: Copy,
+    {
+        assert_round_trip_with(indicator, inputs, |snapshot| {
+            bincode::deserialize(&bincode::serialize(snapshot).unwrap()).unwrap()
+        });
+    }
+
+    fn assert_round_trip_with<I, T, F>(mut indicator: I, inputs: &[T], round_trip: F)
+    where
+        I: Next<T> + Clone,
+        I::Output: PartialEq + Debug,
+        T: Copy,
+        F: Fn(&Versioned<I>) -> Versioned<I>,
+    {
+        let (before, after) = inputs.split_at(inputs.len() / 2);
+        for &input in before {
+            indicator.next(input);
+        }
+
+        let restored = round_trip(&Versioned::new(indicator.clone()));
+        assert_eq!(restored.version(), STATE_VERSION);
+        let mut restored = restored.into_inner();
+
+        for &input in after {
+            assert_eq!(restored.next(input), indicator.next(input));
+        }
+    }
+
+    fn close(i: usize) -> f64 {
+        100.0 + (i as f64 * 0.7).sin() * 5.0 + i as f64 * 0.1
+    }
+
+    fn bars() -> Vec<Bar> {
+        (0..60)
+            .map(|i| {
+                let close = close(i);
+                Bar::new()
+                    .open(close - 0.3)
+                    .high(close + 1.0 + (i % 3) as f64)
+                    .low(close - 1.0 - (i % 2) as f64)
+                    .close(close)
+                    .volume(1000.0 + (i % 7) as f64 * 100.0)
+                    .buy_volume(500.0 + (i % 5) as f64 * 50.0)
+                    .sell_volume(500.0 + (i % 3) as f64 * 60.0)
+            })
+            .collect()
+    }
+
+    macro_rules! assert_round_trips {
+        ($inputs:expr, $($indicator:ty),* $(,)?) => {$(
+            assert_round_trip(<$indicator>::default(), $inputs);
+        )*};
+    }
+
+    #[test]
+    fn test_round_trip_bars() {
+        let bars = bars();
+        let bars: Vec<&Bar> = bars.iter().collect();
+        assert_round_trips!(
+            &bars,
+            AverageDailyRange,
+            AverageTrueRange,
+            BandPosition<BollingerBands>,
+            BollingerBands,
+            ChandelierExit,
+            CommodityChannelIndex,
+            CumulativeDelta,
+            DeMarker,
+            EfficiencyRatio,
+            ElderImpulse,
+            ExponentialMovingAverage,
+            FastStochastic,
+            fixed::SimpleMovingAverage<5>,
+            fixed::VolumeWeightedAveragePrice<5>,
+            IntradayIntensity,
+            KeltnerChannel,
+            LiveBar<SimpleMovingAverage>,
+            MarketStructure,
+            Maximum,
+            MeanAbsoluteDeviation,
+            MedianPrice,
+            Minimum,
+            MomentumScore,
+            MoneyFlowIndex,
+            MovingAverageConvergenceDivergence,
+            NormalizedAverageTrueRange,
+            OnBalanceVolume,
+            PercentagePriceOscillator,
+            PivotHighLow,
+            PriorSessionLevels,
+            RateOfChange,
+            RelativeStrengthIndex,
+            RollingDelta,
+            RollingDrawdown,
+            RollingKurtosis,
+            RollingMedian,
+            RollingQuantile,
+            RollingSkewness,
+            RollingSpectrum,
+            SessionStats,
+            SimpleMovingAverage,
+            SlowStochastic,
+            StandardDeviation,
+            StochasticMomentumIndex,
+            TrueRange,
+            TtmSqueeze,
+            TwiggsMoneyFlow,
+            TypicalPrice,
+            VolatilityAdjustedRateOfChange,
+            VolatilityStop,
+            VolumeWeightedAveragePrice,
+            VolumeWeightedMacd,
+            VolumeWeightedMovingAverage,
+            VolumeZoneOscillator,
+            WaveTrend,
+            WaveletDecomposition,
+            WeightedClose,
+            WeightedMovingAverage,
+            WilliamsVixFix,
+        );
+    }
+
+    #[test]
+    fn test_round_trip_tuples() {
+        let returns: Vec<f64> = (1..60).map(|i| close(i) / close(i - 1) - 1.0).collect();
+        assert_round_trip(RollingSharpe::default(), &returns);
+
+        let pairs: Vec<(f64, f64)> = (0..60).map(|i| (close(i), (i % 4) as f64)).collect();
+        assert_round_trip(RollingIC::default(), &pairs);
+        assert_round_trip(DivergenceDetector::default(), &pairs);
+
+        let trades: Vec<(f64, f64, f64)> = (0..60)
+            .map(|i| (i as f64, close(i), (100 + i % 7 * 10) as f64))
+            .collect();
+        assert_round_trip(TimeDecayedVolumeWeightedAveragePrice::default(), &trades);
+
+        // a gap of 20 time units every 10 inputs
+        let timed: Vec<(f64, f64)> = (0..60)
+            .map(|i| ((i * 3 + i / 10 * 20) as f64, close(i)))
+            .collect();
+        let sma = SimpleMovingAverage::new(5).unwrap();
+        assert_round_trip(GapGuard::new(sma, 8.0, GapPolicy::Reset).unwrap(), &timed);
+    }
+
+    #[test]
+    fn test_round_trip_breadth() {
+        let items: Vec<BreadthItem> = (0..40)
+            .map(|i| {
+                BreadthItem::new(
+                    1000.0 + (i % 9) as f64 * 50.0,
+                    900.0 + (i % 4) as f64 * 80.0,
+                )
+                .unwrap()
+                .with_volume(5000.0 + i as f64 * 10.0, 4000.0)
+                .unwrap()
+            })
+            .collect();
+        let items: Vec<&BreadthItem> = items.iter().collect();
+        assert_round_trips!(&items, AdvanceDeclineLine, ArmsIndex, McClellanOscillator);
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_round_trip_decimal() {
+        use crate::decimal::{self, DecimalItem};
+        use rust_decimal::Decimal;
+
+        // decimals deserialize only from self-describing formats
+        fn json<I: Serialize + DeserializeOwned>(snapshot: &Versioned<I>) -> Versioned<I> {
+            serde_json::from_str(&serde_json::to_string(snapshot).unwrap()).unwrap()
+        }
+
+        let items: Vec<DecimalItem> = (0..20)
+            .map(|i| {
+                let close = Decimal::new(10_000 + (i * 37 % 23), 2);
+                DecimalItem::builder()
+                    .open(close)
+                    .high(close)
+                    .low(close)
+                    .close(close)
+                    .volume(Decimal::new(i * 3 % 5, 1))
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        let items: Vec<&DecimalItem> = items.iter().collect();
+        assert_round_trip_with(decimal::OnBalanceVolume::new(), &items, json);
+        assert_round_trip_with(
+            decimal::AnchoredVolumeWeightedAveragePrice::new(),
+            &items,
+            json,
+        );
+
+        let deltas: Vec<Decimal> = (0..20).map(|i| Decimal::new(i * 7 % 11 - 5, 1)).collect();
+        assert_round_trip_with(decimal::CumulativeDelta::new(), &deltas, json);
+    }
+
+    #[test]
+    fn test_restored_buffers() {
+        // the scratch buffers are restored with their capacity, so `next` does not allocate
+        let mut ic = RollingIC::new(20, 1).unwrap();
+        ic.next((1.0, 2.0));
+        let bytes = bincode::serialize(&Versioned::new(ic.clone())).unwrap();
+        let restored: Versioned<RollingIC> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(restored.into_inner().heap_size(), ic.heap_size());
+    }
+
+    #[test]
+    fn test_version_mismatch() {
+        let mut sma = SimpleMovingAverage::new(3).unwrap();
+        sma.next(1.0);
+        let mut snapshot = Versioned::new(sma);
+        snapshot.version = STATE_VERSION + 1;
+
+        let bytes = bincode::serialize(&snapshot).unwrap();
+        let error = bincode::deserialize::<Versioned<SimpleMovingAverage>>(&bytes).unwrap_err();
+        assert!(error.to_string().contains("unsupported state version"));
+    }
+}
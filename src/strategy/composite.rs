@@ -0,0 +1,369 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::Next;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+type Value<C> = Box<dyn Fn(&C) -> f64>;
+
+/// How a rule compares its value with the threshold.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// The rule passes, when the value is greater than the threshold.
+    Above,
+    /// The rule passes, when the value is less than the threshold.
+    Below,
+}
+
+impl Comparison {
+    fn passes(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Above => value > threshold,
+            Comparison::Below => value < threshold,
+        }
+    }
+}
+
+/// Evaluation of a single rule on a bar.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleOutcome {
+    pub name: String,
+    pub value: f64,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    pub passed: bool,
+    pub weight: f64,
+    /// Weight added to the score: the weight, when the rule passed, 0 otherwise.
+    pub contribution: f64,
+}
+
+/// Result of a bar.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompositeSignalOutput {
+    /// Sum of the weights of the passed rules.
+    pub score: f64,
+    /// Whether the score reached the minimum score.
+    pub signal: bool,
+    /// Breakdown of every rule in the order they were added, only in explain mode.
+    pub rules: Option<Vec<RuleOutcome>>,
+}
+
+/// Signal composed of weighted threshold rules.
+///
+/// Every rule takes a value from the input (e.g. an indicator value computed on the bar) and
+/// compares it with a threshold. The score is the sum of the weights of the passed rules, and
+/// the signal is on, when the score reaches the minimum score.
+///
+/// In explain mode every output additionally carries the breakdown of all rules: the value,
+/// the threshold, whether the rule passed and its weight. Together with the final decision it
+/// can be serialized into an audit log, so an automated signal can be justified later.
+/// Explain mode is off by default, because the breakdown allocates on every bar.
+///
+/// The input `C` is anything the rules need, e.g. a bar together with indicator values
+/// computed on it.
+///
+/// # Example
+///
+/// ```
+/// use ta::strategy::{Comparison, CompositeSignal};
+/// use ta::Next;
+///
+/// // inputs computed from indicators: (rsi, close, sma)
+/// let mut signal = CompositeSignal::builder(2.0)
+///     .rule("oversold", |&(rsi, _, _): &(f64, f64, f64)| rsi, Comparison::Below, 30.0, 1.0)
+///     .rule("uptrend", |&(_, close, sma)| close - sma, Comparison::Above, 0.0, 1.5)
+///     .explain(true)
+///     .build()
+///     .unwrap();
+///
+/// let out = signal.next(&(25.0, 101.0, 100.0));
+/// assert_eq!(out.score, 2.5);
+/// assert!(out.signal);
+///
+/// let out = signal.next(&(35.0, 101.0, 100.0));
+/// assert!(!out.signal);
+/// let rules = out.rules.unwrap();
+/// assert_eq!(rules[0].name, "oversold");
+/// assert_eq!(rules[0].value, 35.0);
+/// assert!(!rules[0].passed);
+/// assert_eq!(rules[1].contribution, 1.5);
+/// ```
+pub struct CompositeSignal<C> {
+    min_score: f64,
+    explain: bool,
+    rules: Vec<Rule<C>>,
+}
+
+struct Rule<C> {
+    name: String,
+    value: Value<C>,
+    comparison: Comparison,
+    threshold: f64,
+    weight: f64,
+}
+
+impl<C> CompositeSignal<C> {
+    pub fn builder(min_score: f64) -> CompositeSignalBuilder<C> {
+        CompositeSignalBuilder::new(min_score)
+    }
+
+    pub fn min_score(&self) -> f64 {
+        self.min_score
+    }
+
+    /// Returns `true` in explain mode.
+    pub fn is_explaining(&self) -> bool {
+        self.explain
+    }
+
+    /// Turns explain mode on or off.
+    pub fn set_explain(&mut self, explain: bool) {
+        self.explain = explain;
+    }
+}
+
+impl<C> Next<&C> for CompositeSignal<C> {
+    type Output = CompositeSignalOutput;
+
+    fn next(&mut self, input: &C) -> Self::Output {
+        let mut score = 0.0;
+        let mut outcomes = Vec::new();
+        for rule in self.rules.iter() {
+            let value = (rule.value)(input);
+            let passed = rule.comparison.passes(value, rule.threshold);
+            let contribution = if passed { rule.weight } else { 0.0 };
+            score += contribution;
+
+            if self.explain {
+                outcomes.push(RuleOutcome {
+                    name: rule.name.clone(),
+                    value,
+                    comparison: rule.comparison,
+                    threshold: rule.threshold,
+                    passed,
+                    weight: rule.weight,
+                    contribution,
+                });
+            }
+        }
+
+        CompositeSignalOutput {
+            score,
+            signal: score >= self.min_score,
+            rules: if self.explain { Some(outcomes) } else { None },
+        }
+    }
+}
+
+impl<C> fmt::Debug for CompositeSignal<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CompositeSignal")
+            .field("min_score", &self.min_score)
+            .field("explain", &self.explain)
+            .field(
+                "rules",
+                &self.rules.iter().map(|r| &r.name).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+pub struct CompositeSignalBuilder<C> {
+    min_score: f64,
+    explain: bool,
+    rules: Vec<Rule<C>>,
+}
+
+impl<C> CompositeSignalBuilder<C> {
+    pub fn new(min_score: f64) -> Self {
+        Self {
+            min_score,
+            explain: false,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Adds a rule, which passes, when the value taken from the input compares with the
+    /// threshold, and then adds its weight to the score.
+    pub fn rule<N, F>(
+        mut self,
+        name: N,
+        value: F,
+        comparison: Comparison,
+        threshold: f64,
+        weight: f64,
+    ) -> Self
+    where
+        N: Into<String>,
+        F: Fn(&C) -> f64 + 'static,
+    {
+        self.rules.push(Rule {
+            name: name.into(),
+            value: Box::new(value),
+            comparison,
+            threshold,
+            weight,
+        });
+        self
+    }
+
+    /// Turns explain mode on or off.
+    pub fn explain(mut self, explain: bool) -> Self {
+        self.explain = explain;
+        self
+    }
+
+    /// Returns an error, when there are no rules, a weight is negative or not finite, or a
+    /// threshold or the minimum score is NaN.
+    pub fn build(self) -> Result<CompositeSignal<C>> {
+        if self.rules.is_empty() {
+            return Err(TaError::invalid_period(
+                "rules",
+                0,
+                "at least one rule is required",
+            ));
+        }
+        for rule in &self.rules {
+            if !rule.weight.is_finite() || rule.weight < 0.0 {
+                return Err(TaError::invalid_parameter(
+                    "weight",
+                    rule.weight,
+                    "must be a finite number not below 0",
+                ));
+            }
+            if rule.threshold.is_nan() {
+                return Err(TaError::invalid_parameter(
+                    "threshold",
+                    rule.threshold,
+                    "must not be NaN",
+                ));
+            }
+        }
+        if self.min_score.is_nan() {
+            return Err(TaError::invalid_parameter(
+                "min_score",
+                self.min_score,
+                "must not be NaN",
+            ));
+        }
+        Ok(CompositeSignal {
+            min_score: self.min_score,
+            explain: self.explain,
+            rules: self.rules,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (rsi, momentum)
+    fn signal() -> CompositeSignal<(f64, f64)> {
+        CompositeSignal::builder(1.0)
+            .rule(
+                "rsi",
+                |&(rsi, _): &(f64, f64)| rsi,
+                Comparison::Below,
+                30.0,
+                0.6,
+            )
+            .rule("momentum", |&(_, m)| m, Comparison::Above, 0.0, 0.4)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_build() {
+        let empty = CompositeSignal::<f64>::builder(1.0).build();
+        assert!(empty.is_err());
+
+        let rule = |weight: f64, threshold: f64| {
+            CompositeSignal::builder(1.0)
+                .rule("x", |&x: &f64| x, Comparison::Above, threshold, weight)
+                .build()
+        };
+        assert!(rule(-1.0, 0.0).is_err());
+        assert!(rule(f64::INFINITY, 0.0).is_err());
+        assert!(rule(1.0, f64::NAN).is_err());
+        assert!(rule(0.0, 0.0).is_ok());
+
+        let nan_score = CompositeSignal::builder(f64::NAN)
+            .rule("x", |&x: &f64| x, Comparison::Above, 0.0, 1.0)
+            .build();
+        assert!(nan_score.is_err());
+    }
+
+    #[test]
+    fn test_next() {
+        let mut signal = signal();
+
+        let out = signal.next(&(25.0, 1.0));
+        assert_eq!(out.score, 1.0);
+        assert!(out.signal);
+        assert_eq!(out.rules, None);
+
+        let out = signal.next(&(25.0, -1.0));
+        assert_eq!(out.score, 0.6);
+        assert!(!out.signal);
+
+        let out = signal.next(&(50.0, 1.0));
+        assert_eq!(out.score, 0.4);
+        assert!(!out.signal);
+
+        // thresholds are exclusive
+        assert_eq!(signal.next(&(30.0, 0.0)).score, 0.0);
+    }
+
+    #[test]
+    fn test_explain() {
+        let mut signal = signal();
+        assert!(!signal.is_explaining());
+        signal.set_explain(true);
+        assert!(signal.is_explaining());
+
+        let out = signal.next(&(25.0, -2.0));
+        assert!(!out.signal);
+        assert_eq!(
+            out.rules.unwrap(),
+            vec![
+                RuleOutcome {
+                    name: "rsi".to_string(),
+                    value: 25.0,
+                    comparison: Comparison::Below,
+                    threshold: 30.0,
+                    passed: true,
+                    weight: 0.6,
+                    contribution: 0.6,
+                },
+                RuleOutcome {
+                    name: "momentum".to_string(),
+                    value: -2.0,
+                    comparison: Comparison::Above,
+                    threshold: 0.0,
+                    passed: false,
+                    weight: 0.4,
+                    contribution: 0.0,
+                },
+            ]
+        );
+
+        signal.set_explain(false);
+        assert_eq!(signal.next(&(25.0, -2.0)).rules, None);
+    }
+
+    #[test]
+    fn test_debug() {
+        let signal = signal();
+        assert_eq!(
+            format!("{:?}", signal),
+            "CompositeSignal { min_score: 1.0, explain: false, rules: [\"rsi\", \"momentum\"] }"
+        );
+    }
+}
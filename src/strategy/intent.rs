@@ -0,0 +1,226 @@
+use crate::errors::{Result, TaError};
+use crate::Reset;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Long,
+    Short,
+}
+
+/// How long a trade intent stays valid.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeInForce {
+    /// Valid until it is taken from the book.
+    GoodTillCancelled,
+    /// Valid for the given number of bars after the bar it was submitted on (greater than 0).
+    GoodForBars(usize),
+    /// Valid until the end of the session, i.e. up to and including the next bar that closes a
+    /// session.
+    SessionEnd,
+}
+
+/// Order a strategy wants to place.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeIntent {
+    pub side: Side,
+    /// Limit price, `None` for a market order.
+    pub price: Option<f64>,
+    pub time_in_force: TimeInForce,
+}
+
+/// Intent waiting in an [IntentBook](struct.IntentBook.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PendingIntent {
+    pub intent: TradeIntent,
+    /// Number of bars passed since the intent was submitted.
+    pub bars_pending: usize,
+}
+
+/// Book of pending trade intents honoring their time in force.
+///
+/// Intents are submitted at the close of a bar and are valid from the next bar on. After every
+/// bar [next_bar](#method.next_bar) ages the pending intents and removes the expired ones.
+/// Whatever executes the intents (e.g. a backtest loop) takes them out of the book with
+/// [take](#method.take), when they are filled or cancelled.
+///
+/// # Example
+///
+/// ```
+/// use ta::strategy::{IntentBook, Side, TimeInForce, TradeIntent};
+///
+/// let mut book = IntentBook::new();
+/// book.submit(TradeIntent {
+///     side: Side::Long,
+///     price: Some(101.5),
+///     time_in_force: TimeInForce::GoodForBars(2),
+/// })
+/// .unwrap();
+///
+/// // two bars pass without a fill
+/// assert!(book.next_bar(false).is_empty());
+/// let expired = book.next_bar(false);
+/// assert_eq!(expired.len(), 1);
+/// assert!(book.pending().is_empty());
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct IntentBook {
+    pending: Vec<PendingIntent>,
+}
+
+impl IntentBook {
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+        }
+    }
+
+    /// Adds an intent to the book. Fails for a `GoodForBars(0)` time in force and for a limit
+    /// price that is not a positive number.
+    pub fn submit(&mut self, intent: TradeIntent) -> Result<()> {
+        if let Some(price) = intent.price {
+            if price.is_nan() || price <= 0.0 {
+                return Err(TaError::invalid_parameter(
+                    "price",
+                    price,
+                    "must be greater than 0",
+                ));
+            }
+        }
+        if intent.time_in_force == TimeInForce::GoodForBars(0) {
+            return Err(TaError::zero_period("time_in_force"));
+        }
+        self.pending.push(PendingIntent {
+            intent,
+            bars_pending: 0,
+        });
+        Ok(())
+    }
+
+    /// Pending intents in the order of submission.
+    pub fn pending(&self) -> &[PendingIntent] {
+        &self.pending
+    }
+
+    /// Removes the pending intent at `index`, e.g. when it was filled or cancelled.
+    pub fn take(&mut self, index: usize) -> Option<TradeIntent> {
+        if index < self.pending.len() {
+            Some(self.pending.remove(index).intent)
+        } else {
+            None
+        }
+    }
+
+    /// Ends a bar: ages the pending intents and returns the ones, which expired with this bar.
+    /// `session_end` tells whether the bar closed a session.
+    pub fn next_bar(&mut self, session_end: bool) -> Vec<TradeIntent> {
+        let mut expired = Vec::new();
+        self.pending.retain_mut(|pending| {
+            pending.bars_pending += 1;
+            let alive = match pending.intent.time_in_force {
+                TimeInForce::GoodTillCancelled => true,
+                TimeInForce::GoodForBars(bars) => pending.bars_pending < bars,
+                TimeInForce::SessionEnd => !session_end,
+            };
+            if !alive {
+                expired.push(pending.intent);
+            }
+            alive
+        });
+        expired
+    }
+}
+
+impl Reset for IntentBook {
+    fn reset(&mut self) {
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn intent(time_in_force: TimeInForce) -> TradeIntent {
+        TradeIntent {
+            side: Side::Long,
+            price: None,
+            time_in_force,
+        }
+    }
+
+    #[test]
+    fn test_submit() {
+        let mut book = IntentBook::new();
+
+        assert!(book.submit(intent(TimeInForce::GoodForBars(0))).is_err());
+        let mut limit = intent(TimeInForce::GoodTillCancelled);
+        limit.price = Some(0.0);
+        assert!(book.submit(limit).is_err());
+        assert!(book.pending().is_empty());
+
+        limit.price = Some(10.0);
+        assert!(book.submit(limit).is_ok());
+        assert_eq!(book.pending()[0].bars_pending, 0);
+    }
+
+    #[test]
+    fn test_good_for_bars() {
+        let mut book = IntentBook::new();
+        book.submit(intent(TimeInForce::GoodForBars(3))).unwrap();
+
+        assert!(book.next_bar(false).is_empty());
+        assert!(book.next_bar(true).is_empty());
+        assert_eq!(book.pending()[0].bars_pending, 2);
+        assert_eq!(
+            book.next_bar(false),
+            vec![intent(TimeInForce::GoodForBars(3))]
+        );
+        assert!(book.pending().is_empty());
+    }
+
+    #[test]
+    fn test_session_end() {
+        let mut book = IntentBook::new();
+        book.submit(intent(TimeInForce::SessionEnd)).unwrap();
+        book.submit(intent(TimeInForce::GoodTillCancelled)).unwrap();
+
+        assert!(book.next_bar(false).is_empty());
+        assert_eq!(book.next_bar(true), vec![intent(TimeInForce::SessionEnd)]);
+
+        // good till cancelled survives any number of bars and sessions
+        for _ in 0..10 {
+            assert!(book.next_bar(true).is_empty());
+        }
+        assert_eq!(book.pending().len(), 1);
+    }
+
+    #[test]
+    fn test_take() {
+        let mut book = IntentBook::new();
+        book.submit(intent(TimeInForce::GoodForBars(1))).unwrap();
+        book.submit(intent(TimeInForce::SessionEnd)).unwrap();
+
+        assert_eq!(book.take(0), Some(intent(TimeInForce::GoodForBars(1))));
+        assert_eq!(book.take(1), None);
+        assert!(book.next_bar(false).is_empty());
+        assert_eq!(book.pending().len(), 1);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut book = IntentBook::new();
+        book.submit(intent(TimeInForce::GoodTillCancelled)).unwrap();
+
+        book.reset();
+        assert!(book.pending().is_empty());
+    }
+}
@@ -0,0 +1,36 @@
+//! Helpers for expressing trading strategies.
+//!
+//! [StrategyStateMachine](struct.StrategyStateMachine.html) describes a multi-phase strategy
+//! as a set of states and guarded transitions, e.g. "wait for a squeeze → wait for a breakout
+//! → manage the position". It is fed one input per bar, like an indicator, so a strategy can be
+//! tested deterministically by feeding it a fixed sequence of inputs.
+//!
+//! [TradeIntent](struct.TradeIntent.html) is an order a strategy wants to place together with
+//! its [time in force](enum.TimeInForce.html). [IntentBook](struct.IntentBook.html) keeps the
+//! pending intents and drops them, when they expire, so setups that are valid only for a short
+//! time after triggering are not acted upon later.
+//!
+//! [CompositeSignal](struct.CompositeSignal.html) combines weighted threshold rules into a
+//! score and a signal. In explain mode it reports the value, threshold and outcome of every
+//! rule with the decision, so automated signals can be audited.
+//!
+//! [Signal](enum.Signal.html) is the common vocabulary of buy, sell and neutral signals.
+//! [Threshold](struct.Threshold.html) and [BandTouch](struct.BandTouch.html) turn the raw
+//! outputs of an oscillator or a band indicator into signals, and every
+//! [Signaler](trait.Signaler.html) can be consumed without knowing the indicator behind it.
+
+mod state_machine;
+pub use self::state_machine::{
+    StateTransition, StrategyStateMachine, StrategyStateMachineBuilder, StrategyStateMachineOutput,
+};
+
+mod intent;
+pub use self::intent::{IntentBook, PendingIntent, Side, TimeInForce, TradeIntent};
+
+mod composite;
+pub use self::composite::{
+    Comparison, CompositeSignal, CompositeSignalBuilder, CompositeSignalOutput, RuleOutcome,
+};
+
+mod signal;
+pub use self::signal::{BandTouch, Signal, Signaler, Threshold};
@@ -0,0 +1,450 @@
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::indicators::CrossEvent;
+use crate::{Bands, Close, Current, IsReady, Next, Period, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Trading signal of a bar.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Signal {
+    Buy,
+    Sell,
+    #[default]
+    Neutral,
+}
+
+impl From<CrossEvent> for Signal {
+    /// Crossing above is a buy, crossing below a sell.
+    fn from(event: CrossEvent) -> Self {
+        match event {
+            CrossEvent::Above => Signal::Buy,
+            CrossEvent::Below => Signal::Sell,
+            CrossEvent::None => Signal::Neutral,
+        }
+    }
+}
+
+/// Anything emitting a [Signal](enum.Signal.html) per input.
+///
+/// Implemented for every resettable [Next](../trait.Next.html) with `Signal` output, so
+/// consumers of signals, e.g. a backtester, can take any signaler without knowing the
+/// indicator behind it.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RelativeStrengthIndex as Rsi;
+/// use ta::strategy::{Signal, Signaler, Threshold};
+///
+/// fn buys<S: Signaler<f64>>(signaler: &mut S, closes: &[f64]) -> usize {
+///     closes
+///         .iter()
+///         .filter(|&&close| signaler.next(close) == Signal::Buy)
+///         .count()
+/// }
+///
+/// let mut oversold = Threshold::new(Rsi::new(3).unwrap(), 30.0, 70.0).unwrap();
+/// assert_eq!(buys(&mut oversold, &[10.0, 9.0, 8.0, 7.0]), 3);
+/// ```
+pub trait Signaler<T>: Next<T, Output = Signal> + Reset {}
+
+impl<S: Next<T, Output = Signal> + Reset, T> Signaler<T> for S {}
+
+/// Buy and sell signals from an indicator crossing thresholds.
+///
+/// The signal is a buy, when the value of the indicator is below the lower threshold
+/// (oversold), a sell, when it is above the upper threshold (overbought), and neutral
+/// otherwise, on the thresholds and for NaN values. Signals are emitted from the first bar on,
+/// check [is_ready](../trait.IsReady.html) to ignore the warm-up of the indicator.
+///
+/// # Errors
+///
+/// Returns an error, when a threshold is NaN or the lower threshold is not less than the
+/// upper one.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::RelativeStrengthIndex as Rsi;
+/// use ta::strategy::{Signal, Threshold};
+/// use ta::Next;
+///
+/// let mut signal = Threshold::new(Rsi::new(3).unwrap(), 30.0, 70.0).unwrap();
+/// assert_eq!(signal.next(10.0), Signal::Neutral);
+/// assert_eq!(signal.next(10.5), Signal::Sell);
+/// assert_eq!(signal.next(10.0), Signal::Neutral);
+/// assert_eq!(signal.next(9.5), Signal::Buy);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Threshold<I> {
+    indicator: I,
+    lower: f64,
+    upper: f64,
+    output: Option<Signal>,
+}
+
+impl<I> Threshold<I> {
+    pub fn new(indicator: I, lower: f64, upper: f64) -> Result<Self> {
+        if lower.is_nan() {
+            return Err(TaError::invalid_parameter(
+                "lower",
+                lower,
+                "must not be NaN",
+            ));
+        }
+        if upper.is_nan() || lower >= upper {
+            return Err(TaError::invalid_parameter(
+                "upper",
+                upper,
+                "must be above the lower threshold",
+            ));
+        }
+        Ok(Self {
+            indicator,
+            lower,
+            upper,
+            output: None,
+        })
+    }
+
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+}
+
+impl<I: Period> Period for Threshold<I> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: IsReady> IsReady for Threshold<I> {
+    fn bars_until_ready(&self) -> usize {
+        self.indicator.bars_until_ready()
+    }
+}
+
+impl<I: Next<T, Output = f64>, T> Next<T> for Threshold<I> {
+    type Output = Signal;
+
+    fn next(&mut self, input: T) -> Self::Output {
+        let value = self.indicator.next(input);
+        let output = if value < self.lower {
+            Signal::Buy
+        } else if value > self.upper {
+            Signal::Sell
+        } else {
+            Signal::Neutral
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<I> Current for Threshold<I> {
+    type Output = Signal;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<I: Reset> Reset for Threshold<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.output = None;
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for Threshold<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "THRESHOLD({}, {}, {})",
+            self.indicator, self.lower, self.upper
+        )
+    }
+}
+
+/// Buy and sell signals from the price touching the bands of a band indicator.
+///
+/// Wraps an indicator, whose output implements [Bands](../trait.Bands.html), e.g.
+/// [Bollinger Bands](../indicators/struct.BollingerBands.html) or
+/// [Keltner Channel](../indicators/struct.KeltnerChannel.html). The signal is a buy, when the
+/// price is at or below the lower band, a sell, when it is at or above the upper band, and
+/// neutral otherwise. For bars the price is the close. When the bands have no width, e.g. on
+/// the first bar of Bollinger Bands, the signal is neutral.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::BollingerBands;
+/// use ta::strategy::{BandTouch, Signal};
+/// use ta::Next;
+///
+/// let mut signal = BandTouch::new(BollingerBands::new(3, 1.0).unwrap());
+/// assert_eq!(signal.next(10.0), Signal::Neutral);
+/// // bands are 10.0 and 12.0
+/// assert_eq!(signal.next(12.0), Signal::Sell);
+/// assert_eq!(signal.next(11.0), Signal::Neutral);
+/// assert_eq!(signal.next(9.0), Signal::Buy);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct BandTouch<I> {
+    indicator: I,
+    output: Option<Signal>,
+}
+
+impl<I> BandTouch<I> {
+    pub fn new(indicator: I) -> Self {
+        Self {
+            indicator,
+            output: None,
+        }
+    }
+
+    pub fn indicator(&self) -> &I {
+        &self.indicator
+    }
+
+    fn signal<B: Bands>(&mut self, price: f64, bands: B) -> Signal {
+        let (upper, lower) = (bands.upper(), bands.lower());
+        let has_width = upper > lower;
+        let output = if has_width && price <= lower {
+            Signal::Buy
+        } else if has_width && price >= upper {
+            Signal::Sell
+        } else {
+            Signal::Neutral
+        };
+        self.output = Some(output);
+        output
+    }
+}
+
+impl<I: Period> Period for BandTouch<I> {
+    fn period(&self) -> usize {
+        self.indicator.period()
+    }
+}
+
+impl<I: IsReady> IsReady for BandTouch<I> {
+    fn bars_until_ready(&self) -> usize {
+        self.indicator.bars_until_ready()
+    }
+}
+
+impl<I> Next<f64> for BandTouch<I>
+where
+    I: Next<f64>,
+    I::Output: Bands,
+{
+    type Output = Signal;
+
+    fn next(&mut self, input: f64) -> Self::Output {
+        let bands = self.indicator.next(input);
+        self.signal(input, bands)
+    }
+}
+
+impl<'a, I, T> Next<&'a T> for BandTouch<I>
+where
+    I: Next<&'a T>,
+    I::Output: Bands,
+    T: Close,
+{
+    type Output = Signal;
+
+    fn next(&mut self, input: &'a T) -> Self::Output {
+        let bands = self.indicator.next(input);
+        self.signal(input.close(), bands)
+    }
+}
+
+impl<I> Current for BandTouch<I> {
+    type Output = Signal;
+
+    fn current(&self) -> Option<Self::Output> {
+        self.output
+    }
+}
+
+impl<I: Reset> Reset for BandTouch<I> {
+    fn reset(&mut self) {
+        self.indicator.reset();
+        self.output = None;
+    }
+}
+
+impl<I: Default> Default for BandTouch<I> {
+    fn default() -> Self {
+        Self::new(I::default())
+    }
+}
+
+impl<I: fmt::Display> fmt::Display for BandTouch<I> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BAND_TOUCH({})", self.indicator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::{
+        BollingerBands, Cross, KeltnerChannel, RelativeStrengthIndex as Rsi,
+        SimpleMovingAverage as Sma,
+    };
+    use crate::test_helper::*;
+    use Signal::*;
+
+    #[test]
+    fn test_from_cross_event() {
+        let mut cross = Cross::new();
+        let signals: Vec<Signal> = [(1.0, 2.0), (3.0, 2.0), (1.0, 2.0)]
+            .iter()
+            .map(|&pair| cross.next(pair).into())
+            .collect();
+        assert_eq!(signals, vec![Neutral, Buy, Sell]);
+    }
+
+    #[test]
+    fn test_threshold_new() {
+        let sma = || Sma::new(2).unwrap();
+        assert!(Threshold::new(sma(), 70.0, 30.0).is_err());
+        assert!(Threshold::new(sma(), 30.0, 30.0).is_err());
+        assert!(Threshold::new(sma(), f64::NAN, 30.0).is_err());
+        assert!(Threshold::new(sma(), 30.0, f64::NAN).is_err());
+        assert!(Threshold::new(sma(), -1.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_threshold_next() {
+        // an SMA(1) passes the values through
+        let mut signal = Threshold::new(Sma::new(1).unwrap(), 30.0, 70.0).unwrap();
+        assert_eq!(signal.next(20.0), Buy);
+        assert_eq!(signal.next(30.0), Neutral);
+        assert_eq!(signal.next(50.0), Neutral);
+        assert_eq!(signal.next(70.0), Neutral);
+        assert_eq!(signal.next(80.0), Sell);
+        assert_eq!(signal.next(f64::NAN), Neutral);
+    }
+
+    #[test]
+    fn test_threshold_bars() {
+        let mut signal = Threshold::new(Sma::new(2).unwrap(), 9.0, 11.0).unwrap();
+        assert_eq!(signal.next(&Bar::new().close(8.0)), Buy);
+        assert_eq!(signal.next(&Bar::new().close(12.0)), Neutral);
+        assert_eq!(signal.next(&Bar::new().close(12.0)), Sell);
+    }
+
+    #[test]
+    fn test_threshold_reset() {
+        let mut signal = Threshold::new(Sma::new(2).unwrap(), 9.0, 11.0).unwrap();
+        signal.next(20.0);
+
+        signal.reset();
+        assert_eq!(signal.current(), None);
+        assert_eq!(signal.next(10.0), Neutral);
+    }
+
+    #[test]
+    fn test_threshold_is_ready() {
+        let mut signal = Threshold::new(Rsi::new(3).unwrap(), 30.0, 70.0).unwrap();
+        assert_eq!(signal.period(), 3);
+        assert_warm_up(&mut signal, 1.0, 3);
+    }
+
+    #[test]
+    fn test_threshold_current() {
+        let mut signal = Threshold::new(Sma::new(2).unwrap(), 9.0, 11.0).unwrap();
+        assert_current(&mut signal, [8.0, 10.0, 14.0]);
+    }
+
+    #[test]
+    fn test_band_touch_next() {
+        let mut signal = BandTouch::new(BollingerBands::new(3, 1.0).unwrap());
+        // bands without width
+        assert_eq!(signal.next(10.0), Neutral);
+        assert_eq!(signal.next(10.0), Neutral);
+        // the lower band is touched, not crossed
+        signal.reset();
+        signal.next(10.0);
+        assert_eq!(signal.next(8.0), Buy);
+    }
+
+    #[test]
+    fn test_band_touch_bars() {
+        let bar = |close: f64| Bar::new().high(close + 1.0).low(close - 1.0).close(close);
+        let mut signal = BandTouch::new(KeltnerChannel::new(3, 0.5).unwrap());
+        assert_eq!(signal.next(&bar(10.0)), Neutral);
+        assert_eq!(signal.next(&bar(14.0)), Sell);
+        assert_eq!(signal.next(&bar(4.0)), Buy);
+    }
+
+    #[test]
+    fn test_band_touch_reset() {
+        let mut signal = BandTouch::new(BollingerBands::new(3, 1.0).unwrap());
+        signal.next(10.0);
+        signal.next(12.0);
+
+        signal.reset();
+        assert_eq!(signal.current(), None);
+        assert_eq!(signal.next(12.0), Neutral);
+    }
+
+    #[test]
+    fn test_band_touch_is_ready() {
+        let mut signal = BandTouch::new(BollingerBands::new(4, 2.0).unwrap());
+        assert_eq!(signal.period(), 4);
+        assert_warm_up(&mut signal, 1.0, 4);
+    }
+
+    #[test]
+    fn test_band_touch_current() {
+        let mut signal = BandTouch::new(BollingerBands::new(3, 1.0).unwrap());
+        assert_current(&mut signal, [10.0, 12.0, 11.0, 9.0]);
+    }
+
+    #[test]
+    fn test_signaler() {
+        fn signals<S: Signaler<f64>>(signaler: &mut S, inputs: &[f64]) -> Vec<Signal> {
+            signaler.reset();
+            inputs.iter().map(|&input| signaler.next(input)).collect()
+        }
+
+        let mut threshold = Threshold::new(Sma::new(1).unwrap(), 0.0, 1.0).unwrap();
+        assert_eq!(signals(&mut threshold, &[-1.0, 2.0]), vec![Buy, Sell]);
+        let mut band_touch = BandTouch::<BollingerBands>::default();
+        assert_eq!(signals(&mut band_touch, &[1.0]), vec![Neutral]);
+    }
+
+    #[test]
+    fn test_display() {
+        let threshold = Threshold::new(Rsi::new(14).unwrap(), 30.0, 70.5).unwrap();
+        assert_eq!(format!("{}", threshold), "THRESHOLD(RSI(14), 30, 70.5)");
+        let band_touch = BandTouch::new(BollingerBands::new(20, 2.0).unwrap());
+        assert_eq!(format!("{}", band_touch), "BAND_TOUCH(BB(20, 2))");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&Sell).unwrap();
+        assert_eq!(json, "\"Sell\"");
+        assert_eq!(serde_json::from_str::<Signal>(&json).unwrap(), Sell);
+    }
+}
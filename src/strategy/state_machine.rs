@@ -0,0 +1,408 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::{Next, Reset};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+type Guard<C> = Box<dyn Fn(&C) -> bool>;
+type Hook<S, C> = Box<dyn FnMut(S, &C)>;
+
+/// State machine driven by one input per bar.
+///
+/// Every bar the transitions leaving the current state are checked in the order they were
+/// added, and the first one whose guard accepts the input is taken. At most one transition
+/// happens per bar, so a strategy moves through its phases one bar at a time. A timeout is a
+/// transition taken, when the machine stays in a state for the given number of bars.
+///
+/// When a transition happens, the exit hooks of the old state run before the entry hooks of
+/// the new one. Both get the state they are attached to and the input, which caused the
+/// transition. Hooks are meant for side effects like logging or placing orders. They are not
+/// called on [reset](#method.reset).
+///
+/// The input `C` is anything the guards need, e.g. a bar together with indicator values
+/// computed on it.
+///
+/// # Example
+///
+/// ```
+/// use ta::strategy::StrategyStateMachine;
+/// use ta::Next;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// enum Phase {
+///     Idle,
+///     Squeeze,
+///     Long,
+/// }
+///
+/// // inputs computed from indicators: (bandwidth, close, upper band)
+/// let mut strategy = StrategyStateMachine::builder(Phase::Idle)
+///     .transition(Phase::Idle, Phase::Squeeze, |&(bandwidth, _, _): &(f64, f64, f64)| bandwidth < 0.05)
+///     .transition(Phase::Squeeze, Phase::Long, |&(_, close, upper)| close > upper)
+///     .timeout(Phase::Squeeze, 10, Phase::Idle)
+///     .transition(Phase::Long, Phase::Idle, |&(_, close, upper)| close < upper * 0.95)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(strategy.next(&(0.10, 100.0, 105.0)).state, Phase::Idle);
+/// assert_eq!(strategy.next(&(0.04, 100.0, 102.0)).state, Phase::Squeeze);
+/// let out = strategy.next(&(0.04, 103.0, 102.0));
+/// assert_eq!(out.state, Phase::Long);
+/// let transition = out.transition.unwrap();
+/// assert_eq!((transition.from, transition.to), (Phase::Squeeze, Phase::Long));
+/// ```
+pub struct StrategyStateMachine<S, C> {
+    initial: S,
+    state: S,
+    bars_in_state: usize,
+    transitions: Vec<Transition<S, C>>,
+    on_entry: Vec<(S, Hook<S, C>)>,
+    on_exit: Vec<(S, Hook<S, C>)>,
+}
+
+enum Condition<C> {
+    Guard(Guard<C>),
+    Timeout(usize),
+}
+
+struct Transition<S, C> {
+    from: S,
+    to: S,
+    condition: Condition<C>,
+}
+
+/// State after a bar.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrategyStateMachineOutput<S> {
+    pub state: S,
+    /// Transition taken on this bar.
+    pub transition: Option<StateTransition<S>>,
+    /// Number of bars spent in the current state, including this one.
+    pub bars_in_state: usize,
+}
+
+/// Transition between two states.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateTransition<S> {
+    pub from: S,
+    pub to: S,
+}
+
+impl<S: Copy + PartialEq, C> StrategyStateMachine<S, C> {
+    pub fn builder(initial: S) -> StrategyStateMachineBuilder<S, C> {
+        StrategyStateMachineBuilder::new(initial)
+    }
+
+    pub fn state(&self) -> S {
+        self.state
+    }
+
+    pub fn bars_in_state(&self) -> usize {
+        self.bars_in_state
+    }
+
+    fn find_transition(&self, input: &C) -> Option<S> {
+        self.transitions
+            .iter()
+            .filter(|t| t.from == self.state)
+            .find(|t| match &t.condition {
+                Condition::Guard(guard) => guard(input),
+                Condition::Timeout(bars) => self.bars_in_state >= *bars,
+            })
+            .map(|t| t.to)
+    }
+}
+
+impl<S: Copy + PartialEq, C> Next<&C> for StrategyStateMachine<S, C> {
+    type Output = StrategyStateMachineOutput<S>;
+
+    fn next(&mut self, input: &C) -> Self::Output {
+        let from = self.state;
+        let transition = self.find_transition(input).map(|to| {
+            for (state, hook) in self.on_exit.iter_mut() {
+                if *state == from {
+                    hook(from, input);
+                }
+            }
+            for (state, hook) in self.on_entry.iter_mut() {
+                if *state == to {
+                    hook(to, input);
+                }
+            }
+            self.state = to;
+            self.bars_in_state = 0;
+            StateTransition { from, to }
+        });
+        self.bars_in_state += 1;
+
+        StrategyStateMachineOutput {
+            state: self.state,
+            transition,
+            bars_in_state: self.bars_in_state,
+        }
+    }
+}
+
+impl<S: Copy, C> Reset for StrategyStateMachine<S, C> {
+    fn reset(&mut self) {
+        self.state = self.initial;
+        self.bars_in_state = 0;
+    }
+}
+
+impl<S: fmt::Debug, C> fmt::Debug for StrategyStateMachine<S, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("StrategyStateMachine")
+            .field("state", &self.state)
+            .field("bars_in_state", &self.bars_in_state)
+            .field("transitions", &self.transitions.len())
+            .finish()
+    }
+}
+
+pub struct StrategyStateMachineBuilder<S, C> {
+    initial: S,
+    transitions: Vec<Transition<S, C>>,
+    on_entry: Vec<(S, Hook<S, C>)>,
+    on_exit: Vec<(S, Hook<S, C>)>,
+}
+
+impl<S: Copy + PartialEq, C> StrategyStateMachineBuilder<S, C> {
+    pub fn new(initial: S) -> Self {
+        Self {
+            initial,
+            transitions: Vec::new(),
+            on_entry: Vec::new(),
+            on_exit: Vec::new(),
+        }
+    }
+
+    /// Adds a transition taken, when `guard` accepts the input.
+    pub fn transition<F>(mut self, from: S, to: S, guard: F) -> Self
+    where
+        F: Fn(&C) -> bool + 'static,
+    {
+        self.transitions.push(Transition {
+            from,
+            to,
+            condition: Condition::Guard(Box::new(guard)),
+        });
+        self
+    }
+
+    /// Adds a transition taken on the bar after `bars` bars were spent in the `from` state.
+    pub fn timeout(mut self, from: S, bars: usize, to: S) -> Self {
+        self.transitions.push(Transition {
+            from,
+            to,
+            condition: Condition::Timeout(bars),
+        });
+        self
+    }
+
+    /// Adds a hook called, when the machine enters `state`.
+    pub fn on_entry<F>(mut self, state: S, hook: F) -> Self
+    where
+        F: FnMut(S, &C) + 'static,
+    {
+        self.on_entry.push((state, Box::new(hook)));
+        self
+    }
+
+    /// Adds a hook called, when the machine leaves `state`.
+    pub fn on_exit<F>(mut self, state: S, hook: F) -> Self
+    where
+        F: FnMut(S, &C) + 'static,
+    {
+        self.on_exit.push((state, Box::new(hook)));
+        self
+    }
+
+    pub fn build(self) -> Result<StrategyStateMachine<S, C>> {
+        let zero_timeout = self
+            .transitions
+            .iter()
+            .any(|t| matches!(t.condition, Condition::Timeout(0)));
+        if zero_timeout {
+            return Err(TaError::zero_period("timeout"));
+        }
+        Ok(StrategyStateMachine {
+            initial: self.initial,
+            state: self.initial,
+            bars_in_state: 0,
+            transitions: self.transitions,
+            on_entry: self.on_entry,
+            on_exit: self.on_exit,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Phase {
+        Flat,
+        Armed,
+        Long,
+    }
+
+    fn machine() -> StrategyStateMachine<Phase, f64> {
+        StrategyStateMachine::builder(Phase::Flat)
+            .transition(Phase::Flat, Phase::Armed, |&x| x > 1.0)
+            .transition(Phase::Armed, Phase::Long, |&x| x > 2.0)
+            .timeout(Phase::Armed, 2, Phase::Flat)
+            .transition(Phase::Long, Phase::Flat, |&x| x < 0.0)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_build() {
+        let zero_timeout = StrategyStateMachine::<Phase, f64>::builder(Phase::Flat)
+            .timeout(Phase::Armed, 0, Phase::Flat)
+            .build();
+        assert!(zero_timeout.is_err());
+        assert_eq!(machine().state(), Phase::Flat);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut sm = machine();
+
+        let out = sm.next(&0.5);
+        assert_eq!(out.state, Phase::Flat);
+        assert_eq!(out.transition, None);
+        assert_eq!(out.bars_in_state, 1);
+
+        let out = sm.next(&1.5);
+        assert_eq!(
+            out.transition,
+            Some(StateTransition {
+                from: Phase::Flat,
+                to: Phase::Armed
+            })
+        );
+        assert_eq!(out.bars_in_state, 1);
+
+        let out = sm.next(&3.0);
+        assert_eq!(
+            out.transition,
+            Some(StateTransition {
+                from: Phase::Armed,
+                to: Phase::Long
+            })
+        );
+        assert_eq!(sm.next(&1.0).bars_in_state, 2);
+        assert_eq!(sm.next(&-1.0).state, Phase::Flat);
+    }
+
+    #[test]
+    fn test_one_transition_per_bar() {
+        let mut sm = machine();
+
+        assert_eq!(sm.next(&3.0).state, Phase::Armed);
+        assert_eq!(sm.next(&3.0).state, Phase::Long);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let mut sm = machine();
+
+        sm.next(&1.5);
+        assert_eq!(sm.next(&1.5).state, Phase::Armed);
+        let out = sm.next(&1.5);
+        assert_eq!(
+            out.transition,
+            Some(StateTransition {
+                from: Phase::Armed,
+                to: Phase::Flat
+            })
+        );
+    }
+
+    #[test]
+    fn test_transition_order() {
+        let mut sm = StrategyStateMachine::builder(Phase::Flat)
+            .transition(Phase::Flat, Phase::Long, |&x: &f64| x > 2.0)
+            .transition(Phase::Flat, Phase::Armed, |&x| x > 1.0)
+            .build()
+            .unwrap();
+
+        assert_eq!(sm.next(&3.0).state, Phase::Long);
+    }
+
+    #[test]
+    fn test_hooks() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let (entry_log, exit_log) = (log.clone(), log.clone());
+
+        let mut sm = StrategyStateMachine::builder(Phase::Flat)
+            .transition(Phase::Flat, Phase::Long, |&x: &f64| x > 1.0)
+            .transition(Phase::Long, Phase::Flat, |&x| x < 0.0)
+            .on_entry(Phase::Long, move |state, &x| {
+                entry_log
+                    .borrow_mut()
+                    .push(format!("enter {:?} at {}", state, x))
+            })
+            .on_exit(Phase::Long, move |state, &x| {
+                exit_log
+                    .borrow_mut()
+                    .push(format!("exit {:?} at {}", state, x))
+            })
+            .build()
+            .unwrap();
+
+        sm.next(&0.5);
+        sm.next(&1.5);
+        sm.next(&1.0);
+        sm.next(&-1.0);
+
+        assert_eq!(
+            *log.borrow(),
+            vec![
+                "enter Long at 1.5".to_string(),
+                "exit Long at -1".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut sm = machine();
+
+        sm.next(&1.5);
+        sm.next(&3.0);
+
+        sm.reset();
+        assert_eq!(sm.state(), Phase::Flat);
+        assert_eq!(sm.bars_in_state(), 0);
+        assert_eq!(sm.next(&3.0).state, Phase::Armed);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_output() {
+        let mut machine = machine();
+        let out = machine.next(&1.5);
+
+        let json = serde_json::to_string(&out).unwrap();
+        assert_eq!(
+            json,
+            r#"{"state":"Armed","transition":{"from":"Flat","to":"Armed"},"bars_in_state":1}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<StrategyStateMachineOutput<Phase>>(&json).unwrap(),
+            out
+        );
+    }
+}
@@ -1,12 +1,14 @@
-use super::{Close, High, Low, Open, Volume};
+use super::{BuyVolume, Close, Current, High, IsReady, Low, Next, Open, Reset, SellVolume, Volume};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Bar {
     open: f64,
     high: f64,
     low: f64,
     close: f64,
     volume: f64,
+    buy_volume: f64,
+    sell_volume: f64,
 }
 
 impl Bar {
@@ -17,13 +19,15 @@ impl Bar {
             low: 0.0,
             high: 0.0,
             volume: 0.0,
+            buy_volume: 0.0,
+            sell_volume: 0.0,
         }
     }
 
-    //pub fn open<T: Into<f64>>(mut self, val :T ) -> Self {
-    //    self.open = val.into();
-    //    self
-    //}
+    pub fn open<T: Into<f64>>(mut self, val: T) -> Self {
+        self.open = val.into();
+        self
+    }
 
     pub fn high<T: Into<f64>>(mut self, val: T) -> Self {
         self.high = val.into();
@@ -44,6 +48,16 @@ impl Bar {
         self.volume = val;
         self
     }
+
+    pub fn buy_volume(mut self, val: f64) -> Self {
+        self.buy_volume = val;
+        self
+    }
+
+    pub fn sell_volume(mut self, val: f64) -> Self {
+        self.sell_volume = val;
+        self
+    }
 }
 
 impl Open for Bar {
@@ -76,10 +90,43 @@ impl Volume for Bar {
     }
 }
 
+impl BuyVolume for Bar {
+    fn buy_volume(&self) -> f64 {
+        self.buy_volume
+    }
+}
+
+impl SellVolume for Bar {
+    fn sell_volume(&self) -> f64 {
+        self.sell_volume
+    }
+}
+
 pub fn round(num: f64) -> f64 {
     (num * 1000.0).round() / 1000.00
 }
 
+/// Feeds `input` until the indicator is ready and checks that it takes `bars` bars, and that
+/// reset starts the warm-up again.
+pub fn assert_warm_up<I, T>(indicator: &mut I, input: T, bars: usize)
+where
+    I: Next<T> + IsReady + Reset,
+    T: Copy,
+{
+    for i in 0..bars {
+        assert_eq!(indicator.bars_until_ready(), bars - i);
+        assert!(!indicator.is_ready());
+        indicator.next(input);
+    }
+    assert_eq!(indicator.bars_until_ready(), 0);
+    assert!(indicator.is_ready());
+    indicator.next(input);
+    assert!(indicator.is_ready());
+
+    indicator.reset();
+    assert_eq!(indicator.bars_until_ready(), bars);
+}
+
 macro_rules! test_indicator {
     ($i:tt) => {
         #[test]
@@ -100,7 +147,7 @@ macro_rules! test_indicator {
             assert_eq!(indicator.next(12.3), first_output);
 
             // ensure Display is implemented
-            format!("{}", indicator);
+            let _ = format!("{}", indicator);
         }
     };
 }
@@ -109,11 +156,7 @@ macro_rules! test_hlcv_indicator {
     ($i:tt) => {
         #[test]
         fn test_hlcv() {
-            let test_value = Bar::new()
-                .high(10.0)
-                .low(8.0)
-                .close(9.0)
-                .volume(1000.0);
+            let test_value = Bar::new().high(10.0).low(8.0).close(9.0).volume(1000.0);
 
             let mut indicator = $i::default();
 
@@ -121,11 +164,7 @@ macro_rules! test_hlcv_indicator {
             let first_output = indicator.next(&test_value);
 
             // Test subsequent output
-            let second_bar = Bar::new()
-                .high(11.0)
-                .low(9.0)
-                .close(10.0)
-                .volume(1500.0);
+            let second_bar = Bar::new().high(11.0).low(9.0).close(10.0).volume(1500.0);
             indicator.next(&second_bar);
 
             // Test reset functionality
@@ -133,7 +172,23 @@ macro_rules! test_hlcv_indicator {
             assert_eq!(indicator.next(&test_value), first_output);
 
             // Test display implementation
-            format!("{}", indicator);
+            let _ = format!("{}", indicator);
         }
     };
-}
\ No newline at end of file
+}
+
+/// Checks that `current()` returns the last output of `next()` and is cleared by a reset.
+pub fn assert_current<I, T>(indicator: &mut I, inputs: impl IntoIterator<Item = T>)
+where
+    I: Next<T> + Current<Output = <I as Next<T>>::Output> + Reset,
+    <I as Next<T>>::Output: PartialEq + core::fmt::Debug,
+{
+    assert_eq!(indicator.current(), None);
+    for input in inputs {
+        let output = indicator.next(input);
+        assert_eq!(indicator.current(), Some(output));
+    }
+
+    indicator.reset();
+    assert_eq!(indicator.current(), None);
+}
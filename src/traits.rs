@@ -1,30 +1,427 @@
 // Indicator traits
 //
 
+#[cfg(not(feature = "std"))]
+use alloc::{string::ToString, vec::Vec};
+use core::fmt;
+
+use crate::errors::{Result, TaError};
+use crate::registry::Params;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Resets an indicator to the initial state.
 pub trait Reset {
     fn reset(&mut self);
 }
 
 /// Return the period used by the indicator.
+///
+/// It is the number of bars the indicator needs until all of its windows are filled, so
+/// generic code can pre-feed the right amount of history. Indicators combining several
+/// windows return the longest warm-up: parallel windows take the longest of them, chained
+/// windows add up, e.g. MACD(12, 26, 9) needs 26 + 9 - 1 = 34 bars.
 pub trait Period {
     fn period(&self) -> usize;
 }
 
+/// Tells whether the outputs of an indicator are meaningful.
+///
+/// During the warm-up an indicator returns outputs computed from fewer bars than it needs, e.g.
+/// the standard deviation of VWAP is 0 before the second bar. Indicators implementing
+/// [Period](trait.Period.html) are ready after `period()` bars, indicators without a warm-up
+/// like OBV are always ready. [Reset](trait.Reset.html) starts the warm-up again.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{IsReady, Next};
+///
+/// let mut sma = SimpleMovingAverage::new(3).unwrap();
+/// sma.next(10.0);
+/// assert_eq!(sma.bars_until_ready(), 2);
+///
+/// sma.next(11.0);
+/// sma.next(12.0);
+/// assert!(sma.is_ready());
+/// ```
+pub trait IsReady {
+    fn is_ready(&self) -> bool {
+        self.bars_until_ready() == 0
+    }
+
+    /// Number of bars the indicator needs until it is ready, 0 when it is ready.
+    fn bars_until_ready(&self) -> usize;
+}
+
 /// Consumes a data item of type `T` and returns `Output`.
 ///
 /// Typically `T` can be `f64` or a struct similar to [DataItem](struct.DataItem.html), that implements
 /// traits necessary to calculate value of a particular indicator.
 ///
 /// In most cases `Output` is `f64`, but sometimes it can be different. For example for
-/// [MACD](indicators/struct.MovingAverageConvergenceDivergence.html) it is `(f64, f64, f64)` since
-/// MACD returns 3 values.
+/// [MACD](indicators/struct.MovingAverageConvergenceDivergence.html) it is
+/// [MovingAverageConvergenceDivergenceOutput](indicators/struct.MovingAverageConvergenceDivergenceOutput.html)
+/// since MACD returns 3 values.
 ///
 pub trait Next<T> {
     type Output;
     fn next(&mut self, input: T) -> Self::Output;
 }
 
+/// Consumes a slice of data items like [Next<T>](trait.Next.html) and appends the outputs to
+/// `out`.
+///
+/// The default implementation calls `next` for every input. Indicators override it, where a
+/// batch can skip work `next` has to do on every call, e.g. the SMA reads the values leaving
+/// its window from the slice instead of its ring buffer. The outputs and the state afterwards
+/// are the same as with `next`, so batches and single inputs can be mixed.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Next, NextBatch};
+///
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// let mut out = Vec::new();
+/// sma.next_batch(&[1.0, 2.0, 4.0], &mut out);
+/// assert_eq!(out, vec![1.0, 1.5, 3.0]);
+/// assert_eq!(sma.next(6.0), 5.0);
+/// ```
+pub trait NextBatch<T: Copy>: Next<T> {
+    fn next_batch(&mut self, inputs: &[T], out: &mut Vec<Self::Output>) {
+        out.reserve(inputs.len());
+        for &input in inputs {
+            out.push(self.next(input));
+        }
+    }
+}
+
+/// Consumes a data item like [Next<T>](trait.Next.html), but returns `None` while the indicator
+/// is not [ready](trait.IsReady.html).
+///
+/// Implemented for every indicator implementing both `Next<T>` and `IsReady`, so pipelines can
+/// skip the warm-up outputs without counting bars themselves.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::TryNext;
+///
+/// let mut sma = SimpleMovingAverage::new(3).unwrap();
+/// assert_eq!(sma.try_next(1.0), None);
+/// assert_eq!(sma.try_next(2.0), None);
+/// assert_eq!(sma.try_next(3.0), Some(2.0));
+/// assert_eq!(sma.try_next(4.0), Some(3.0));
+/// ```
+pub trait TryNext<T> {
+    type Output;
+    fn try_next(&mut self, input: T) -> Option<Self::Output>;
+}
+
+impl<I, T> TryNext<T> for I
+where
+    I: Next<T> + IsReady,
+{
+    type Output = I::Output;
+
+    fn try_next(&mut self, input: T) -> Option<Self::Output> {
+        let output = self.next(input);
+        if self.is_ready() {
+            Some(output)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the last output of an indicator, without consuming an input.
+///
+/// Several consumers can read the value of a shared indicator without driving
+/// [next](trait.Next.html) again. `current()` is `None` before the first input and after a
+/// [reset](trait.Reset.html).
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Current, Next};
+///
+/// let mut sma = SimpleMovingAverage::new(2).unwrap();
+/// assert_eq!(sma.current(), None);
+///
+/// sma.next(10.0);
+/// sma.next(11.0);
+/// assert_eq!(sma.current(), Some(10.5));
+/// ```
+pub trait Current {
+    type Output;
+    fn current(&self) -> Option<Self::Output>;
+}
+
+/// Revises the last input of an indicator, e.g. a bar that is still forming.
+///
+/// `replace(x)` returns the output `next(x)` would have returned instead of the last input,
+/// and continues from that state. Wrap an indicator into a
+/// [LiveBar](live/struct.LiveBar.html) to revise its inputs.
+pub trait Update<T>: Next<T> {
+    fn replace(&mut self, input: T) -> Self::Output;
+}
+
+/// Changes the parameters of an indicator at runtime, without building it again.
+///
+/// Changing the period keeps as much of the state as the new period allows:
+///
+/// * indicators with a window, e.g. the SMA, keep the last inputs fitting into the new window
+///   and continue as if they had seen only those inputs, so a longer period warms up again
+///   for the missing inputs
+/// * exponentially smoothed indicators, e.g. the EMA, keep their value and continue smoothing
+///   it with the new factor, and are ready after at most the new period
+///
+/// A new multiplier takes effect with the next input. An invalid parameter returns an error
+/// and leaves the indicator unchanged, and so does `set_multiplier` of indicators without a
+/// multiplier.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::SimpleMovingAverage;
+/// use ta::{Configure, Current, Next, Period};
+///
+/// let mut sma = SimpleMovingAverage::new(4).unwrap();
+/// for close in [1.0, 2.0, 3.0, 4.0] {
+///     sma.next(close);
+/// }
+///
+/// sma.set_period(2).unwrap();
+/// assert_eq!(sma.period(), 2);
+/// assert_eq!(sma.current(), Some(3.5));
+/// assert_eq!(sma.next(5.0), 4.5);
+/// assert!(sma.set_period(0).is_err());
+/// ```
+pub trait Configure {
+    fn set_period(&mut self, period: usize) -> Result<()>;
+
+    fn set_multiplier(&mut self, multiplier: f64) -> Result<()> {
+        Err(TaError::invalid_parameter(
+            "multiplier",
+            multiplier,
+            "the indicator has no multiplier",
+        ))
+    }
+}
+
+/// Value of an indicator parameter.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamValue {
+    /// A period, window length or other count.
+    Period(usize),
+    /// Any other number, e.g. a multiplier.
+    Float(f64),
+}
+
+impl ParamValue {
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            ParamValue::Period(period) => period as f64,
+            ParamValue::Float(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParamValue::Period(period) => write!(f, "{}", period),
+            ParamValue::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Exposes the configuration of an indicator as named, typed parameters.
+///
+/// Tooling can list the parameters of any indicator, e.g. to log or display them, and change
+/// them by name, e.g. to search for the best parameters. The names are the ones
+/// [create](registry/fn.create.html) takes, and `from_params` is the constructor it uses, so
+/// an indicator can be rebuilt from its parameters. Missing parameters take their default
+/// value.
+///
+/// `set_param` builds the indicator again, so it starts over. [Configure](trait.Configure.html)
+/// changes the period or the multiplier and keeps the state instead.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::BollingerBands;
+/// use ta::{ParamValue, Parameters};
+///
+/// let mut bb = BollingerBands::new(20, 2.0).unwrap();
+/// assert_eq!(
+///     bb.params(),
+///     [("period", ParamValue::Period(20)), ("multiplier", ParamValue::Float(2.0))]
+/// );
+///
+/// bb.set_param("multiplier", ParamValue::Float(2.5)).unwrap();
+/// assert_eq!(bb.to_string(), "BB(20, 2.5)");
+/// assert!(bb.set_param("length", ParamValue::Period(10)).is_err());
+/// ```
+pub trait Parameters {
+    /// Names and values of the parameters, in the order of the constructor arguments.
+    fn params(&self) -> Vec<(&'static str, ParamValue)>;
+
+    /// Builds the indicator from named parameters.
+    fn from_params(params: &Params) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Changes a parameter and builds the indicator again. Fails with `UnknownParameter`, if
+    /// the indicator has no parameter `name`, and with the error of the constructor, if it
+    /// rejects the value. The indicator is unchanged on failure.
+    fn set_param(&mut self, name: &str, value: ParamValue) -> Result<()>
+    where
+        Self: Sized,
+    {
+        let mut values: Vec<(&str, f64)> = self
+            .params()
+            .iter()
+            .map(|&(name, value)| (name, value.as_f64()))
+            .collect();
+        match values.iter_mut().find(|(param, _)| *param == name) {
+            Some(param) => param.1 = value.as_f64(),
+            None => {
+                return Err(TaError::UnknownParameter {
+                    name: name.to_string(),
+                })
+            }
+        }
+        *self = Self::from_params(&Params::new(&values))?;
+        Ok(())
+    }
+}
+
+/// Takes checkpoints of the state of an indicator and restores them later.
+///
+/// A backtest can branch at a bar: evaluate a what-if path, restore the checkpoint and
+/// continue with the actual bars, without serde. The same checkpoint can be restored any
+/// number of times. Implemented for every indicator, and every type implementing `Reset` and
+/// `Clone`, e.g. a pipeline of indicators.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::ExponentialMovingAverage;
+/// use ta::{Next, Snapshot};
+///
+/// let mut ema = ExponentialMovingAverage::new(3).unwrap();
+/// ema.next(2.0);
+/// let checkpoint = ema.snapshot();
+///
+/// // what if the next close is 10.0
+/// assert_eq!(ema.next(10.0), 6.0);
+///
+/// ema.restore(&checkpoint);
+/// assert_eq!(ema.next(4.0), 3.0);
+/// ```
+pub trait Snapshot {
+    type State;
+    fn snapshot(&self) -> Self::State;
+    fn restore(&mut self, state: &Self::State);
+}
+
+/// Opaque state of an indicator, taken by [Snapshot](trait.Snapshot.html).
+#[derive(Debug, Clone)]
+pub struct Checkpoint<I>(I);
+
+impl<I: Reset + Clone> Snapshot for I {
+    type State = Checkpoint<I>;
+
+    fn snapshot(&self) -> Self::State {
+        Checkpoint(self.clone())
+    }
+
+    fn restore(&mut self, state: &Self::State) {
+        self.clone_from(&state.0);
+    }
+}
+
+/// Memory the state of an indicator takes.
+///
+/// `heap_size` is the memory allocated on the heap, mostly the windows, which grow with the
+/// period, e.g. a ring buffer of `period` values for an SMA, and 0 for indicators without a
+/// window like EMA. `memory_footprint` adds the inline size of the struct, so it is what one
+/// more instance costs: an application keeping an indicator per symbol and timeframe can
+/// budget its memory, and compare the costs of window sizes.
+///
+/// The windows are allocated at construction, so both sizes do not change with `next`.
+///
+/// # Example
+///
+/// ```
+/// use ta::indicators::{ExponentialMovingAverage, SimpleMovingAverage};
+/// use ta::MemoryFootprint;
+///
+/// let sma = SimpleMovingAverage::new(200).unwrap();
+/// assert_eq!(sma.heap_size(), 200 * 8);
+/// assert!(sma.memory_footprint() > 200 * 8);
+///
+/// let ema = ExponentialMovingAverage::new(200).unwrap();
+/// assert_eq!(ema.heap_size(), 0);
+/// ```
+pub trait MemoryFootprint {
+    /// Bytes allocated on the heap by the indicator.
+    fn heap_size(&self) -> usize;
+
+    /// Bytes of the indicator: its inline size and the bytes on the heap.
+    fn memory_footprint(&self) -> usize {
+        core::mem::size_of_val(self) + self.heap_size()
+    }
+}
+
+/// Computes the output an indicator would return for the next input, without changing its
+/// state.
+///
+/// It answers "what if" questions about the next bar, e.g. what the RSI would be, if the next
+/// bar closed at a given price. `project_next(x)` returns the same value as `next(x)` would.
+pub trait ProjectNext<T> {
+    type Output;
+    fn project_next(&self, input: T) -> Self::Output;
+}
+
+/// Finds the input, for which the next output of an indicator would be equal to a target value.
+///
+/// The state of the indicator is not changed, so the result can be used to place a limit order
+/// at the price, where the indicator would cross a level. Returns `None`, when no input
+/// produces the target.
+pub trait SolveForInput {
+    fn solve_for_input(&self, target: f64) -> Option<f64>;
+}
+
+/// Indicator, which starts over or rolls its levels at session boundaries, e.g. a session VWAP.
+///
+/// A [Sessions](calendar/struct.Sessions.html) wrapper calls it at the boundaries a
+/// [trading calendar](calendar/trait.TradingCalendar.html) reports, so the boundaries do not
+/// need to be signalled by hand.
+pub trait SessionAware {
+    /// A new session starts with the next input.
+    fn open_session(&mut self);
+
+    /// The current session ended, the next inputs are outside of a session until the next one
+    /// opens. Does nothing by default.
+    fn close_session(&mut self) {}
+}
+
+/// Upper, middle and lower band of a band indicator output, e.g. of
+/// [Bollinger Bands](indicators/struct.BollingerBandsOutput.html).
+pub trait Bands {
+    fn upper(&self) -> f64;
+    fn middle(&self) -> f64;
+    fn lower(&self) -> f64;
+}
+
 /// Open price of a particular period.
 pub trait Open {
     fn open(&self) -> f64;
@@ -49,3 +446,70 @@ pub trait High {
 pub trait Volume {
     fn volume(&self) -> f64;
 }
+
+/// Market breadth of a particular period: the number of advancing and declining issues of an
+/// index or exchange.
+pub trait Breadth {
+    fn advances(&self) -> f64;
+    fn declines(&self) -> f64;
+}
+
+/// Volume traded in the advancing and declining issues of a particular period.
+pub trait BreadthVolume {
+    fn advancing_volume(&self) -> f64;
+    fn declining_volume(&self) -> f64;
+}
+
+/// Volume of a particular period traded at the ask, i.e. by aggressive buyers.
+///
+/// Only available with order flow (footprint) data, so [DataItem](struct.DataItem.html) does
+/// not implement it.
+pub trait BuyVolume {
+    fn buy_volume(&self) -> f64;
+}
+
+/// Volume of a particular period traded at the bid, i.e. by aggressive sellers.
+///
+/// Only available with order flow (footprint) data, so [DataItem](struct.DataItem.html) does
+/// not implement it.
+pub trait SellVolume {
+    fn sell_volume(&self) -> f64;
+}
+
+/// Open interest at the end of a particular period, `None` when the input carries none.
+///
+/// The number of outstanding contracts of futures and options, used by indicators confirming
+/// price moves with the flow of new positions.
+pub trait OpenInterest {
+    fn open_interest(&self) -> Option<f64>;
+}
+
+/// Number of trades of a particular period, `None` when the input carries none.
+pub trait TradeCount {
+    fn trade_count(&self) -> Option<u64>;
+}
+
+/// Volume of a particular period traded at the bid, `None` when the input carries none.
+///
+/// The same as [SellVolume](trait.SellVolume.html), for inputs like
+/// [DataItem](struct.DataItem.html), which carry the order flow only optionally.
+pub trait BidVolume {
+    fn bid_volume(&self) -> Option<f64>;
+}
+
+/// Volume of a particular period traded at the ask, `None` when the input carries none.
+///
+/// The same as [BuyVolume](trait.BuyVolume.html), for inputs like
+/// [DataItem](struct.DataItem.html), which carry the order flow only optionally.
+pub trait AskVolume {
+    fn ask_volume(&self) -> Option<f64>;
+}
+
+/// Time of a particular period, `None` when the input carries no time.
+///
+/// The unit is up to the caller, as long as all inputs of an indicator use the same one, e.g.
+/// milliseconds since the Unix epoch. Session-aware indicators and resampling read it to find
+/// the boundaries of sessions and of higher timeframe bars.
+pub trait Timestamped {
+    fn timestamp(&self) -> Option<i64>;
+}
@@ -0,0 +1,526 @@
+//! Cross-validation splits for time series samples.
+//!
+//! Samples built from bars overlap in time: the features of a sample look back and its label
+//! (e.g. a [triple barrier label](../labels/struct.TripleBarrier.html)) looks forward. Plain
+//! K-fold cross-validation leaks information through such overlaps, so the splitters here
+//! remove the leaking samples from the training set:
+//!
+//! * purging drops training samples whose time span overlaps the span of the test set
+//! * embargo additionally drops a number of training samples right after the test set
+//!
+//! Every sample is described by its span `(start, end)`, e.g. the time of the bar the sample is
+//! taken at and the time its label is known. Times can be anything ordered: bar indices,
+//! timestamps, etc. Samples must be sorted by the start time.
+//!
+//! [RandomizedBenchmark](struct.RandomizedBenchmark.html) is a sanity check for a strategy: it
+//! compares the strategy with random strategies, which trade as often, but without its edge.
+//!
+//! # Example
+//!
+//! ```
+//! use ta::validation::PurgedKFold;
+//!
+//! // each label looks 1 bar forward
+//! let spans: Vec<(usize, usize)> = (0..6).map(|i| (i, i + 1)).collect();
+//!
+//! let splits = PurgedKFold::new(3, 0).unwrap().split(&spans);
+//! assert_eq!(splits.len(), 3);
+//! assert_eq!(splits[1].test, vec![2, 3]);
+//! // sample 1 is purged, because its label ends at the start of the test set,
+//! // and sample 4, because it starts where the last test label ends
+//! assert_eq!(splits[1].train, vec![0, 5]);
+//! ```
+//!
+//! # Links
+//!
+//! * [Purged K-fold cross-validation, M. López de Prado: Advances in Financial Machine Learning](https://www.wiley.com/en-us/Advances+in+Financial+Machine+Learning-p-9781119482086)
+
+use crate::errors::{Result, TaError};
+use crate::helpers::SplitMix64;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Indices of the training and the test samples.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Split {
+    pub train: Vec<usize>,
+    pub test: Vec<usize>,
+}
+
+/// Purged K-fold cross-validation.
+///
+/// Splits the samples into _folds_ contiguous test sets of (almost) equal size. For every test
+/// set, the training set is made of all other samples, except those overlapping the test set and
+/// the _embargo_ samples following it.
+///
+/// # Parameters
+///
+/// * _folds_ - number of folds (integer greater than 1)
+/// * _embargo_ - number of samples after a test set excluded from training
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct PurgedKFold {
+    folds: usize,
+    embargo: usize,
+}
+
+impl PurgedKFold {
+    pub fn new(folds: usize, embargo: usize) -> Result<Self> {
+        if folds < 2 {
+            return Err(TaError::invalid_period(
+                "folds",
+                folds,
+                "must be at least 2",
+            ));
+        }
+        Ok(Self { folds, embargo })
+    }
+
+    pub fn folds(&self) -> usize {
+        self.folds
+    }
+
+    pub fn embargo(&self) -> usize {
+        self.embargo
+    }
+
+    /// Returns one split per fold. Folds without test samples (when there are less samples than
+    /// folds) are left out.
+    pub fn split<T: PartialOrd + Copy>(&self, spans: &[(T, T)]) -> Vec<Split> {
+        let n = spans.len();
+        let mut splits = Vec::with_capacity(self.folds);
+        let mut start = 0;
+        for fold in 0..self.folds {
+            let size = n / self.folds + usize::from(fold < n % self.folds);
+            let end = start + size;
+            if size > 0 {
+                let embargo_end = (end + self.embargo).min(n);
+                let test = test_span(spans, start, end);
+                let train = (0..start)
+                    .chain(embargo_end..n)
+                    .filter(|&i| !overlaps(spans[i], test))
+                    .collect();
+                splits.push(Split {
+                    train,
+                    test: (start..end).collect(),
+                });
+            }
+            start = end;
+        }
+        splits
+    }
+}
+
+/// Walk-forward cross-validation.
+///
+/// Moves a test window of _test_size_ samples forward through the samples and trains only on
+/// the samples before it. The training set is either expanding (all previous samples) or
+/// rolling (at most _train_size_ previous samples). Training samples overlapping the test
+/// window are purged, and the last _embargo_ samples before the test window are skipped.
+///
+/// # Parameters
+///
+/// * _test_size_ - number of samples in every test window (integer greater than 0)
+/// * _train_size_ - maximum number of training samples, `None` for an expanding window
+/// * _embargo_ - number of samples between the training and the test set
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct WalkForward {
+    test_size: usize,
+    train_size: Option<usize>,
+    embargo: usize,
+}
+
+impl WalkForward {
+    pub fn new(test_size: usize, train_size: Option<usize>, embargo: usize) -> Result<Self> {
+        if test_size == 0 {
+            return Err(TaError::zero_period("test_size"));
+        }
+        if train_size == Some(0) {
+            return Err(TaError::zero_period("train_size"));
+        }
+        Ok(Self {
+            test_size,
+            train_size,
+            embargo,
+        })
+    }
+
+    pub fn test_size(&self) -> usize {
+        self.test_size
+    }
+
+    pub fn train_size(&self) -> Option<usize> {
+        self.train_size
+    }
+
+    pub fn embargo(&self) -> usize {
+        self.embargo
+    }
+
+    /// Returns one split per test window. The samples before the first test window are used
+    /// only for training: the first window starts after _train_size_ + _embargo_ samples for a
+    /// rolling window, or after _test_size_ + _embargo_ samples for an expanding one. A trailing
+    /// window shorter than _test_size_ is left out.
+    pub fn split<T: PartialOrd + Copy>(&self, spans: &[(T, T)]) -> Vec<Split> {
+        let n = spans.len();
+        let warmup = self.train_size.unwrap_or(self.test_size) + self.embargo;
+        let mut splits = Vec::new();
+        let mut start = warmup;
+        while start + self.test_size <= n {
+            let end = start + self.test_size;
+            let train_end = start - self.embargo;
+            let train_start = match self.train_size {
+                Some(size) => train_end.saturating_sub(size),
+                None => 0,
+            };
+            let test = test_span(spans, start, end);
+            let train: Vec<usize> = (train_start..train_end)
+                .filter(|&i| !overlaps(spans[i], test))
+                .collect();
+            if !train.is_empty() {
+                splits.push(Split {
+                    train,
+                    test: (start..end).collect(),
+                });
+            }
+            start = end;
+        }
+        splits
+    }
+}
+
+/// Span of the samples `start..end`: from the start of the first to the latest end.
+fn test_span<T: PartialOrd + Copy>(spans: &[(T, T)], start: usize, end: usize) -> (T, T) {
+    let test_end = spans[start..end]
+        .iter()
+        .map(|span| span.1)
+        .fold(spans[start].1, |max, t| if t > max { t } else { max });
+    (spans[start].0, test_end)
+}
+
+/// Whether the span of a sample overlaps the span of a test set.
+fn overlaps<T: PartialOrd>(sample: (T, T), test: (T, T)) -> bool {
+    sample.0 <= test.1 && sample.1 >= test.0
+}
+
+/// Statistics of a strategy result compared to random baselines.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BaselineStats {
+    /// Mean result of the baseline runs.
+    pub mean: f64,
+    /// Standard deviation of the baseline results.
+    pub std_dev: f64,
+    /// Distance of the strategy result from the mean in standard deviations (0 if the baseline
+    /// results do not vary).
+    pub z_score: f64,
+    /// Share of the baseline runs doing at least as well as the strategy (with the strategy
+    /// itself counted as a run), i.e. the probability to get such a result by luck.
+    pub p_value: f64,
+}
+
+/// Result of a [RandomizedBenchmark](struct.RandomizedBenchmark.html).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchmarkReport {
+    /// Result of the strategy: the sum of `position * return` over all bars.
+    pub actual: f64,
+    /// The same positions applied to randomly shuffled returns.
+    pub shuffled_returns: BaselineStats,
+    /// The trades of the strategy placed at random times over the same returns.
+    pub random_entries: BaselineStats,
+}
+
+/// Randomized strategy benchmark.
+///
+/// Runs a strategy against two kinds of random baselines:
+///
+/// * shuffled returns - the positions of the strategy are kept, but the returns are shuffled,
+///   which destroys any relation between the signals and the following returns
+/// * random entries - the returns are kept, but the trades of the strategy (runs of the same
+///   position, including the flat periods between them) are shuffled, so the random strategy
+///   has the same number of trades, the same holding periods and the same exposure
+///
+/// A strategy with a real edge should beat most of the baseline runs, i.e. have a small
+/// p-value. The runs are driven by a seeded pseudo random generator, so results are
+/// reproducible.
+///
+/// # Parameters
+///
+/// * _runs_ - number of runs of every baseline (integer greater than 0)
+/// * _seed_ - seed of the random generator
+///
+/// # Example
+///
+/// ```
+/// use ta::validation::RandomizedBenchmark;
+///
+/// let returns: Vec<f64> = (0..200).map(|i| if i % 3 == 0 { 0.01 } else { -0.005 }).collect();
+/// // long exactly on the bars with a positive return
+/// let positions: Vec<f64> = returns.iter().map(|&r| if r > 0.0 { 1.0 } else { 0.0 }).collect();
+///
+/// let report = RandomizedBenchmark::new(100, 42).unwrap().run(&returns, &positions);
+/// assert!(report.random_entries.p_value < 0.05);
+/// assert!(report.shuffled_returns.z_score > 2.0);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct RandomizedBenchmark {
+    runs: usize,
+    seed: u64,
+}
+
+impl RandomizedBenchmark {
+    pub fn new(runs: usize, seed: u64) -> Result<Self> {
+        if runs == 0 {
+            return Err(TaError::zero_period("runs"));
+        }
+        Ok(Self { runs, seed })
+    }
+
+    pub fn runs(&self) -> usize {
+        self.runs
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Benchmarks a strategy.
+    ///
+    /// `positions[i]` is the position held over `returns[i]`, e.g. 1.0 for long, -1.0 for
+    /// short and 0.0 for flat. It must be decided before the return is known, so usually it
+    /// is the signal of the previous bar. Extra elements of the longer slice are ignored.
+    pub fn run(&self, returns: &[f64], positions: &[f64]) -> BenchmarkReport {
+        let n = returns.len().min(positions.len());
+        let (returns, positions) = (&returns[..n], &positions[..n]);
+        let actual = pnl(returns, positions);
+        let mut rng = SplitMix64::new(self.seed);
+
+        let mut shuffled = returns.to_vec();
+        let shuffled_results: Vec<f64> = (0..self.runs)
+            .map(|_| {
+                rng.shuffle(&mut shuffled);
+                pnl(&shuffled, positions)
+            })
+            .collect();
+
+        let mut trades: Vec<&[f64]> = positions.chunk_by(|a, b| a == b).collect();
+        let mut random_positions = Vec::with_capacity(n);
+        let random_results: Vec<f64> = (0..self.runs)
+            .map(|_| {
+                rng.shuffle(&mut trades);
+                random_positions.clear();
+                trades
+                    .iter()
+                    .for_each(|trade| random_positions.extend_from_slice(trade));
+                pnl(returns, &random_positions)
+            })
+            .collect();
+
+        BenchmarkReport {
+            actual,
+            shuffled_returns: baseline_stats(actual, &shuffled_results),
+            random_entries: baseline_stats(actual, &random_results),
+        }
+    }
+}
+
+fn pnl(returns: &[f64], positions: &[f64]) -> f64 {
+    returns.iter().zip(positions).map(|(r, p)| r * p).sum()
+}
+
+fn baseline_stats(actual: f64, results: &[f64]) -> BaselineStats {
+    let n = results.len() as f64;
+    let mean = results.iter().sum::<f64>() / n;
+    let std_dev = (results.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+    let z_score = if std_dev > 0.0 {
+        (actual - mean) / std_dev
+    } else {
+        0.0
+    };
+    let at_least_as_good = results.iter().filter(|&&x| x >= actual).count();
+    BaselineStats {
+        mean,
+        std_dev,
+        z_score,
+        p_value: (at_least_as_good + 1) as f64 / (n + 1.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helper::*;
+
+    fn spans(n: usize, horizon: usize) -> Vec<(usize, usize)> {
+        (0..n).map(|i| (i, i + horizon)).collect()
+    }
+
+    #[test]
+    fn test_purged_k_fold_new() {
+        assert!(PurgedKFold::new(0, 0).is_err());
+        assert!(PurgedKFold::new(1, 0).is_err());
+        assert!(PurgedKFold::new(2, 0).is_ok());
+    }
+
+    #[test]
+    fn test_purged_k_fold() {
+        let splits = PurgedKFold::new(3, 0).unwrap().split(&spans(7, 0));
+
+        // plain K-fold, when the spans do not overlap
+        assert_eq!(splits[0].test, vec![0, 1, 2]);
+        assert_eq!(splits[0].train, vec![3, 4, 5, 6]);
+        assert_eq!(splits[1].test, vec![3, 4]);
+        assert_eq!(splits[1].train, vec![0, 1, 2, 5, 6]);
+        assert_eq!(splits[2].test, vec![5, 6]);
+        assert_eq!(splits[2].train, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_purged_k_fold_purge_and_embargo() {
+        let folds = PurgedKFold::new(3, 1).unwrap();
+        let splits = folds.split(&spans(9, 2));
+
+        // test 3..=5 spans 3..=7: samples 1 and 2 end within it, 6 is embargoed,
+        // 7 starts within it
+        assert_eq!(splits[1].test, vec![3, 4, 5]);
+        assert_eq!(splits[1].train, vec![0, 8]);
+        // test 0..=2 spans 0..=4: sample 3 is embargoed, 4 starts within it
+        assert_eq!(splits[0].train, vec![5, 6, 7, 8]);
+        // test 6..=8 spans 6..=10: samples 4 and 5 end within it
+        assert_eq!(splits[2].train, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_purged_k_fold_timestamps() {
+        let spans = [(100, 160), (160, 220), (220, 280), (280, 340)];
+        let splits = PurgedKFold::new(2, 0).unwrap().split(&spans);
+
+        assert_eq!(splits[0].train, vec![3]);
+        assert_eq!(splits[1].train, vec![0]);
+    }
+
+    #[test]
+    fn test_purged_k_fold_few_samples() {
+        let splits = PurgedKFold::new(5, 0).unwrap().split(&spans(3, 0));
+        assert_eq!(splits.len(), 3);
+        assert!(PurgedKFold::new(2, 0)
+            .unwrap()
+            .split(&spans(0, 0))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_walk_forward_new() {
+        assert!(WalkForward::new(0, None, 0).is_err());
+        assert!(WalkForward::new(1, Some(0), 0).is_err());
+        assert!(WalkForward::new(1, Some(1), 0).is_ok());
+        assert!(WalkForward::new(1, None, 3).is_ok());
+    }
+
+    #[test]
+    fn test_walk_forward_expanding() {
+        let splits = WalkForward::new(2, None, 0).unwrap().split(&spans(7, 0));
+
+        assert_eq!(splits.len(), 2);
+        assert_eq!(splits[0].train, vec![0, 1]);
+        assert_eq!(splits[0].test, vec![2, 3]);
+        assert_eq!(splits[1].train, vec![0, 1, 2, 3]);
+        assert_eq!(splits[1].test, vec![4, 5]);
+    }
+
+    #[test]
+    fn test_walk_forward_rolling() {
+        let splits = WalkForward::new(1, Some(2), 1).unwrap().split(&spans(6, 0));
+
+        assert_eq!(splits.len(), 3);
+        assert_eq!(splits[0].train, vec![0, 1]);
+        assert_eq!(splits[0].test, vec![3]);
+        assert_eq!(splits[2].train, vec![2, 3]);
+        assert_eq!(splits[2].test, vec![5]);
+    }
+
+    #[test]
+    fn test_walk_forward_purge() {
+        let splits = WalkForward::new(2, None, 0).unwrap().split(&spans(6, 1));
+
+        // sample 1 ends at 2, where the test window starts
+        assert_eq!(splits[0].train, vec![0]);
+        assert_eq!(splits[0].test, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_randomized_benchmark_new() {
+        assert!(RandomizedBenchmark::new(0, 1).is_err());
+        assert!(RandomizedBenchmark::new(1, 1).is_ok());
+    }
+
+    #[test]
+    fn test_randomized_benchmark_flat() {
+        let report = RandomizedBenchmark::new(10, 1)
+            .unwrap()
+            .run(&[0.01, -0.02, 0.03], &[0.0, 0.0, 0.0]);
+
+        assert_eq!(report.actual, 0.0);
+        assert_eq!(report.shuffled_returns.mean, 0.0);
+        assert_eq!(report.random_entries.z_score, 0.0);
+        // every run is as good as doing nothing
+        assert_eq!(report.random_entries.p_value, 1.0);
+    }
+
+    #[test]
+    fn test_randomized_benchmark_edge() {
+        let returns: Vec<f64> = (0..100)
+            .map(|i| if i % 4 == 0 { 0.02 } else { -0.01 })
+            .collect();
+        let positions: Vec<f64> = returns
+            .iter()
+            .map(|&r| if r > 0.0 { 1.0 } else { 0.0 })
+            .collect();
+
+        let report = RandomizedBenchmark::new(200, 7)
+            .unwrap()
+            .run(&returns, &positions);
+        assert_eq!(round(report.actual), 0.5);
+        assert!(report.shuffled_returns.mean < report.actual);
+        assert!(report.shuffled_returns.p_value < 0.01);
+        assert!(report.random_entries.p_value < 0.01);
+    }
+
+    #[test]
+    fn test_randomized_benchmark_matches_trades() {
+        // the random entries keep the exposure, so with constant returns they equal the actual
+        let returns = [0.01; 10];
+        let positions = [0.0, 1.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+        let report = RandomizedBenchmark::new(20, 3)
+            .unwrap()
+            .run(&returns, &positions);
+        assert_eq!(round(report.random_entries.mean), round(report.actual));
+        assert_eq!(round(report.random_entries.std_dev), 0.0);
+    }
+
+    #[test]
+    fn test_randomized_benchmark_reproducible() {
+        let returns = [0.01, -0.02, 0.015, 0.0, -0.01, 0.02];
+        let positions = [1.0, 1.0, -1.0, 0.0, 1.0, -1.0];
+        let benchmark = RandomizedBenchmark::new(50, 11).unwrap();
+
+        assert_eq!(
+            benchmark.run(&returns, &positions),
+            benchmark.run(&returns, &positions)
+        );
+        // the longer slice is cut
+        assert_eq!(
+            benchmark.run(&returns, &positions[..4]).actual,
+            benchmark.run(&returns[..4], &positions[..4]).actual
+        );
+    }
+}
@@ -0,0 +1,300 @@
+//! After construction no `next` call of an indicator allocates, so the cost per bar is
+//! deterministic. A counting global allocator checks it for every indicator, through the
+//! warm-up, many times around the window and after a reset. Clones and restored snapshots
+//! keep the capacity of their windows, so they do not allocate either.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use ta::indicators::breadth::{AdvanceDeclineLine, ArmsIndex, BreadthItem, McClellanOscillator};
+use ta::indicators::{
+    AverageDailyRange, AverageTrueRange, BandPosition, BollingerBands, ChandelierExit,
+    CommodityChannelIndex, Cross, Crossover, CumulativeDelta, DeMarker, DivergenceDetector,
+    EfficiencyRatio, ElderImpulse, ExponentialMovingAverage, FastStochastic, GapGuard, GapPolicy,
+    IntradayIntensity, KeltnerChannel, MarketStructure, Maximum, MeanAbsoluteDeviation,
+    MedianPrice, Minimum, MomentumScore, MoneyFlowIndex, MovingAverageConvergenceDivergence,
+    NormalizedAverageTrueRange, OnBalanceVolume, PercentagePriceOscillator, PivotHighLow,
+    PriorSessionLevels, RateOfChange, RelativeStrengthIndex, RollingDelta, RollingDrawdown,
+    RollingIC, RollingKurtosis, RollingMedian, RollingQuantile, RollingSharpe, RollingSkewness,
+    RollingSpectrum, SessionStats, SimpleMovingAverage, SlowStochastic, StandardDeviation,
+    StochasticMomentumIndex, TimeDecayedVolumeWeightedAveragePrice, TrueRange, TtmSqueeze,
+    TwiggsMoneyFlow, TypicalPrice, VolatilityAdjustedRateOfChange, VolatilityStop,
+    VolumeWeightedAveragePrice, VolumeWeightedMacd, VolumeWeightedMovingAverage,
+    VolumeZoneOscillator, WaveTrend, WaveletDecomposition, WeightedClose, WeightedMovingAverage,
+    WilliamsVixFix,
+};
+use ta::{Close, DataItem, Next, Reset, Snapshot, Volume};
+
+type FixedMaximum = ta::indicators::fixed::Maximum<14>;
+type FixedMinimum = ta::indicators::fixed::Minimum<14>;
+type FixedRateOfChange = ta::indicators::fixed::RateOfChange<9>;
+type FixedSimpleMovingAverage = ta::indicators::fixed::SimpleMovingAverage<9>;
+type FixedStandardDeviation = ta::indicators::fixed::StandardDeviation<20>;
+type FixedWeightedMovingAverage = ta::indicators::fixed::WeightedMovingAverage<9>;
+type FixedVolumeWeightedAveragePrice = ta::indicators::fixed::VolumeWeightedAveragePrice<14>;
+
+const BARS: usize = 1_000;
+
+thread_local! {
+    // const initialized, so counting never allocates itself
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts the allocations of the current thread, the tests run on threads of their own.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Number of allocations of the current thread while running `f`.
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+fn bars() -> Vec<DataItem> {
+    (0..BARS)
+        .map(|i| {
+            let x = i as f64;
+            let close = 100.0 + (x * 0.3).sin() * 10.0 + (x * 0.05).cos() * 20.0;
+            let range = 1.0 + (x * 0.7).sin().abs() * 3.0;
+            DataItem::builder()
+                .open(close - range / 3.0)
+                .high(close + range / 2.0)
+                .low(close - range / 2.0)
+                .close(close)
+                .volume(1_000.0 + (x * 1.3).cos() * 500.0)
+                .build()
+                .unwrap()
+        })
+        .collect()
+}
+
+/// Asserts that no `next` call allocates, neither in the first run over `inputs` nor after a
+/// reset.
+fn assert_no_allocations<I: Next<T> + Reset, T: Copy>(name: &str, mut indicator: I, inputs: &[T]) {
+    for run in ["first", "after reset"] {
+        let count = allocations(|| {
+            for &input in inputs {
+                std::hint::black_box(indicator.next(input));
+            }
+        });
+        assert_eq!(count, 0, "{} allocated in the {} run", name, run);
+        indicator.reset();
+    }
+}
+
+macro_rules! no_allocations {
+    ($($indicator:ident), *) => {
+        $(
+            #[test]
+            #[allow(non_snake_case)]
+            fn $indicator() {
+                let bars = bars();
+                let bars: Vec<&DataItem> = bars.iter().collect();
+                assert_no_allocations(stringify!($indicator), $indicator::default(), &bars);
+            }
+        )*
+    }
+}
+
+no_allocations!(
+    AverageDailyRange,
+    AverageTrueRange,
+    BollingerBands,
+    ChandelierExit,
+    CommodityChannelIndex,
+    DeMarker,
+    EfficiencyRatio,
+    ElderImpulse,
+    ExponentialMovingAverage,
+    FastStochastic,
+    FixedMaximum,
+    FixedMinimum,
+    FixedRateOfChange,
+    FixedSimpleMovingAverage,
+    FixedStandardDeviation,
+    FixedVolumeWeightedAveragePrice,
+    FixedWeightedMovingAverage,
+    IntradayIntensity,
+    KeltnerChannel,
+    MarketStructure,
+    Maximum,
+    MeanAbsoluteDeviation,
+    MedianPrice,
+    Minimum,
+    MomentumScore,
+    MoneyFlowIndex,
+    MovingAverageConvergenceDivergence,
+    NormalizedAverageTrueRange,
+    OnBalanceVolume,
+    PercentagePriceOscillator,
+    PivotHighLow,
+    PriorSessionLevels,
+    RateOfChange,
+    RelativeStrengthIndex,
+    RollingDrawdown,
+    RollingKurtosis,
+    RollingMedian,
+    RollingQuantile,
+    RollingSkewness,
+    RollingSpectrum,
+    SessionStats,
+    SimpleMovingAverage,
+    SlowStochastic,
+    StandardDeviation,
+    StochasticMomentumIndex,
+    TrueRange,
+    TtmSqueeze,
+    TwiggsMoneyFlow,
+    TypicalPrice,
+    VolatilityAdjustedRateOfChange,
+    VolatilityStop,
+    VolumeWeightedAveragePrice,
+    VolumeWeightedMacd,
+    VolumeWeightedMovingAverage,
+    VolumeZoneOscillator,
+    WaveTrend,
+    WaveletDecomposition,
+    WeightedClose,
+    WeightedMovingAverage,
+    WilliamsVixFix
+);
+
+#[test]
+fn value_pairs() {
+    let pairs: Vec<(f64, f64)> = bars()
+        .iter()
+        .map(|bar| (bar.close(), bar.volume()))
+        .collect();
+    assert_no_allocations("Cross", Cross::new(), &pairs);
+    assert_no_allocations("DivergenceDetector", DivergenceDetector::default(), &pairs);
+    assert_no_allocations("RollingIC", RollingIC::default(), &pairs);
+
+    // a gap of 5 bars every 100 bars resets the guarded indicator
+    let timed: Vec<(f64, f64)> = bars()
+        .iter()
+        .enumerate()
+        .map(|(i, bar)| ((i + i / 100 * 5) as f64, bar.close()))
+        .collect();
+    let guard = GapGuard::new(SimpleMovingAverage::new(20).unwrap(), 1.0, GapPolicy::Reset);
+    assert_no_allocations("GapGuard", guard.unwrap(), &timed);
+}
+
+#[test]
+fn ticks() {
+    let ticks: Vec<(f64, f64, f64)> = bars()
+        .iter()
+        .enumerate()
+        .map(|(i, bar)| (i as f64, bar.close(), bar.volume()))
+        .collect();
+    let tdvwap = TimeDecayedVolumeWeightedAveragePrice::default();
+    assert_no_allocations("TimeDecayedVolumeWeightedAveragePrice", tdvwap, &ticks);
+}
+
+#[test]
+fn breadth() {
+    let items: Vec<BreadthItem> = bars()
+        .iter()
+        .map(|bar| {
+            let advances = 1_500.0 + (bar.close() - 100.0) * 20.0;
+            BreadthItem::new(advances, 3_000.0 - advances)
+                .unwrap()
+                .with_volume(bar.volume() * advances, bar.volume() * (3_000.0 - advances))
+                .unwrap()
+        })
+        .collect();
+    let items: Vec<&BreadthItem> = items.iter().collect();
+    assert_no_allocations("AdvanceDeclineLine", AdvanceDeclineLine::default(), &items);
+    assert_no_allocations(
+        "McClellanOscillator",
+        McClellanOscillator::default(),
+        &items,
+    );
+    assert_no_allocations("ArmsIndex", ArmsIndex::default(), &items);
+}
+
+#[test]
+fn values() {
+    let closes: Vec<f64> = bars().iter().map(|bar| bar.close()).collect();
+    assert_no_allocations("RollingSharpe", RollingSharpe::default(), &closes);
+    assert_no_allocations("RollingMedian", RollingMedian::new(200).unwrap(), &closes);
+    assert_no_allocations(
+        "RollingQuantile",
+        RollingQuantile::new(64, 0.9).unwrap(),
+        &closes,
+    );
+
+    // the deltas of buy and sell volume
+    let deltas: Vec<f64> = closes.windows(2).map(|w| (w[1] - w[0]) * 100.0).collect();
+    assert_no_allocations("CumulativeDelta", CumulativeDelta::default(), &deltas);
+    assert_no_allocations("RollingDelta", RollingDelta::default(), &deltas);
+
+    let position = BandPosition::new(BollingerBands::default());
+    assert_no_allocations("BandPosition", position, &closes);
+    let crossover = Crossover::new(
+        SimpleMovingAverage::new(5).unwrap(),
+        ExponentialMovingAverage::new(10).unwrap(),
+    );
+    assert_no_allocations("Crossover", crossover, &closes);
+}
+
+/// Warms `indicator` up with half of `inputs`, so its windows are neither empty nor full.
+fn half_warmed<I: Next<T>, T: Copy>(mut indicator: I, inputs: &[T]) -> I {
+    for &input in &inputs[..inputs.len() / 2] {
+        indicator.next(input);
+    }
+    indicator
+}
+
+#[test]
+fn clones() {
+    let bars = bars();
+    let closes: Vec<f64> = bars.iter().map(|bar| bar.close()).collect();
+    let bars: Vec<&DataItem> = bars.iter().collect();
+
+    let sma = half_warmed(SimpleMovingAverage::new(50).unwrap(), &closes[..50]);
+    assert_no_allocations("cloned SMA", sma.clone(), &closes);
+
+    let vwap = half_warmed(VolumeWeightedAveragePrice::new(50).unwrap(), &bars[..50]);
+    assert_no_allocations("cloned VWAP", vwap.clone(), &bars);
+
+    let sd = half_warmed(StandardDeviation::new(50).unwrap(), &closes[..50]);
+    let mut restored = StandardDeviation::new(50).unwrap();
+    restored.restore(&sd.snapshot());
+    assert_no_allocations("restored SD", restored, &closes);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialized() {
+    use ta::state::Versioned;
+
+    let closes: Vec<f64> = bars().iter().map(|bar| bar.close()).collect();
+    let sd = half_warmed(StandardDeviation::new(50).unwrap(), &closes[..50]);
+    let bytes = bincode::serialize(&Versioned::new(sd)).unwrap();
+    let snapshot: Versioned<StandardDeviation> = bincode::deserialize(&bytes).unwrap();
+    assert_no_allocations("deserialized SD", snapshot.into_inner(), &closes);
+}
+
+#[test]
+fn counts_allocations() {
+    assert_eq!(allocations(|| drop(std::hint::black_box(vec![1.0; 4]))), 1);
+}
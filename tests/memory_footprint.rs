@@ -0,0 +1,239 @@
+//! `heap_size` of every indicator is the memory it keeps on the heap: a global allocator
+//! counting the live bytes of the thread checks it after a construction and a run over bars.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use ta::combinators::Combine;
+use ta::indicators::{
+    AverageDailyRange, AverageTrueRange, BollingerBands, ChandelierExit, CommodityChannelIndex,
+    Cross, DeMarker, DivergenceDetector, EfficiencyRatio, ElderImpulse, ExponentialMovingAverage,
+    FastStochastic, IntradayIntensity, KeltnerChannel, MarketStructure, Maximum,
+    MeanAbsoluteDeviation, MedianPrice, Minimum, MomentumScore, MoneyFlowIndex,
+    MovingAverageConvergenceDivergence, NormalizedAverageTrueRange, OnBalanceVolume,
+    PercentagePriceOscillator, PivotHighLow, PriorSessionLevels, RateOfChange,
+    RelativeStrengthIndex, RollingDrawdown, RollingIC, RollingKurtosis, RollingMedian,
+    RollingQuantile, RollingSharpe, RollingSkewness, RollingSpectrum, SessionStats,
+    SimpleMovingAverage, SlowStochastic, StandardDeviation, StochasticMomentumIndex, TrueRange,
+    TtmSqueeze, TwiggsMoneyFlow, TypicalPrice, VolatilityAdjustedRateOfChange, VolatilityStop,
+    VolumeWeightedAveragePrice, VolumeWeightedMacd, VolumeWeightedMovingAverage,
+    VolumeZoneOscillator, WaveTrend, WaveletDecomposition, WeightedClose, WeightedMovingAverage,
+    WilliamsVixFix,
+};
+use ta::{Close, DataItem, MemoryFootprint, Next, Volume};
+
+type FixedMaximum = ta::indicators::fixed::Maximum<14>;
+type FixedMinimum = ta::indicators::fixed::Minimum<14>;
+type FixedRateOfChange = ta::indicators::fixed::RateOfChange<9>;
+type FixedSimpleMovingAverage = ta::indicators::fixed::SimpleMovingAverage<9>;
+type FixedStandardDeviation = ta::indicators::fixed::StandardDeviation<20>;
+type FixedWeightedMovingAverage = ta::indicators::fixed::WeightedMovingAverage<9>;
+type FixedVolumeWeightedAveragePrice = ta::indicators::fixed::VolumeWeightedAveragePrice<14>;
+
+const BARS: usize = 500;
+
+thread_local! {
+    // const initialized, so counting never allocates itself
+    static LIVE_BYTES: Cell<isize> = const { Cell::new(0) };
+}
+
+/// Counts the allocated bytes of the current thread, the tests run on threads of their own.
+struct CountingAllocator;
+
+fn count(bytes: isize) {
+    let _ = LIVE_BYTES.try_with(|live| live.set(live.get() + bytes));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(layout.size() as isize);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        count(-(layout.size() as isize));
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size as isize - layout.size() as isize);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs `f` and returns its result with the bytes it left allocated.
+fn live_bytes<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = LIVE_BYTES.with(Cell::get);
+    let result = f();
+    (result, (LIVE_BYTES.with(Cell::get) - before) as usize)
+}
+
+fn bars() -> Vec<DataItem> {
+    (0..BARS)
+        .map(|i| {
+            let x = i as f64;
+            let close = 100.0 + (x * 0.3).sin() * 10.0 + (x * 0.05).cos() * 20.0;
+            let range = 1.0 + (x * 0.7).sin().abs() * 3.0;
+            DataItem::builder()
+                .open(close - range / 3.0)
+                .high(close + range / 2.0)
+                .low(close - range / 2.0)
+                .close(close)
+                .volume(1_000.0 + (x * 1.3).cos() * 500.0)
+                .build()
+                .unwrap()
+        })
+        .collect()
+}
+
+/// Asserts that `heap_size` is the memory the indicator keeps after construction, and after a
+/// run over `inputs`.
+fn assert_heap_size<I, T, F>(name: &str, new: F, inputs: &[T])
+where
+    I: Next<T> + MemoryFootprint,
+    T: Copy,
+    F: Fn() -> I,
+{
+    let (indicator, bytes) = live_bytes(&new);
+    assert_eq!(indicator.heap_size(), bytes, "{} after construction", name);
+    assert_eq!(
+        indicator.memory_footprint(),
+        std::mem::size_of::<I>() + bytes
+    );
+
+    let (indicator, bytes) = live_bytes(|| {
+        let mut indicator = new();
+        for &input in inputs {
+            std::hint::black_box(indicator.next(input));
+        }
+        indicator
+    });
+    assert_eq!(
+        indicator.heap_size(),
+        bytes,
+        "{} after {} inputs",
+        name,
+        inputs.len()
+    );
+}
+
+macro_rules! heap_size {
+    ($($indicator:ident), *) => {
+        $(
+            #[test]
+            #[allow(non_snake_case)]
+            fn $indicator() {
+                let bars = bars();
+                let bars: Vec<&DataItem> = bars.iter().collect();
+                assert_heap_size(stringify!($indicator), $indicator::default, &bars);
+            }
+        )*
+    }
+}
+
+heap_size!(
+    AverageDailyRange,
+    AverageTrueRange,
+    BollingerBands,
+    ChandelierExit,
+    CommodityChannelIndex,
+    DeMarker,
+    EfficiencyRatio,
+    ElderImpulse,
+    ExponentialMovingAverage,
+    FastStochastic,
+    FixedMaximum,
+    FixedMinimum,
+    FixedRateOfChange,
+    FixedSimpleMovingAverage,
+    FixedStandardDeviation,
+    FixedVolumeWeightedAveragePrice,
+    FixedWeightedMovingAverage,
+    IntradayIntensity,
+    KeltnerChannel,
+    MarketStructure,
+    Maximum,
+    MeanAbsoluteDeviation,
+    MedianPrice,
+    Minimum,
+    MomentumScore,
+    MoneyFlowIndex,
+    MovingAverageConvergenceDivergence,
+    NormalizedAverageTrueRange,
+    OnBalanceVolume,
+    PercentagePriceOscillator,
+    PivotHighLow,
+    PriorSessionLevels,
+    RateOfChange,
+    RelativeStrengthIndex,
+    RollingDrawdown,
+    RollingKurtosis,
+    RollingMedian,
+    RollingQuantile,
+    RollingSkewness,
+    RollingSpectrum,
+    SessionStats,
+    SimpleMovingAverage,
+    SlowStochastic,
+    StandardDeviation,
+    StochasticMomentumIndex,
+    TrueRange,
+    TtmSqueeze,
+    TwiggsMoneyFlow,
+    TypicalPrice,
+    VolatilityAdjustedRateOfChange,
+    VolatilityStop,
+    VolumeWeightedAveragePrice,
+    VolumeWeightedMacd,
+    VolumeWeightedMovingAverage,
+    VolumeZoneOscillator,
+    WaveTrend,
+    WaveletDecomposition,
+    WeightedClose,
+    WeightedMovingAverage,
+    WilliamsVixFix
+);
+
+#[test]
+fn value_pairs() {
+    let pairs: Vec<(f64, f64)> = bars()
+        .iter()
+        .map(|bar| (bar.close(), bar.volume()))
+        .collect();
+    assert_heap_size("Cross", Cross::new, &pairs);
+    assert_heap_size("DivergenceDetector", DivergenceDetector::default, &pairs);
+    assert_heap_size("RollingIC", RollingIC::default, &pairs);
+}
+
+#[test]
+fn values() {
+    let closes: Vec<f64> = bars().iter().map(|bar| bar.close()).collect();
+    assert_heap_size("RollingSharpe", RollingSharpe::default, &closes);
+    assert_heap_size(
+        "RollingQuantile",
+        || RollingQuantile::new(64, 0.9).unwrap(),
+        &closes,
+    );
+    assert_heap_size(
+        "SMA.then(SD)",
+        || {
+            let sma = SimpleMovingAverage::new(20).unwrap();
+            sma.then(StandardDeviation::new(50).unwrap())
+        },
+        &closes,
+    );
+}
+
+#[test]
+fn grows_with_the_period() {
+    let small = SimpleMovingAverage::new(14).unwrap();
+    let large = SimpleMovingAverage::new(200).unwrap();
+    assert_eq!(large.heap_size() - small.heap_size(), (200 - 14) * 8);
+    assert_eq!(
+        large.memory_footprint() - small.memory_footprint(),
+        (200 - 14) * 8
+    );
+}
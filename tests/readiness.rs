@@ -0,0 +1,285 @@
+//! `Period` is the number of bars an indicator needs until it is ready, so generic code can
+//! pre-feed the right amount of history. Every indicator implementing both `Period` and
+//! `IsReady` is checked to report the same warm-up through both, and composites are checked
+//! to become ready together with the indicators they are built from.
+
+use ta::indicators::{
+    AverageDailyRange, AverageTrueRange, BandPosition, BollingerBands, ChandelierExit,
+    CommodityChannelIndex, Crossover, DeMarker, DivergenceDetector, EfficiencyRatio, ElderImpulse,
+    ExponentialMovingAverage, FastStochastic, GapGuard, GapPolicy, IntradayIntensity,
+    KeltnerChannel, MarketStructure, Maximum, MeanAbsoluteDeviation, Minimum, MomentumScore,
+    MoneyFlowIndex, MovingAverageConvergenceDivergence, NormalizedAverageTrueRange,
+    PercentagePriceOscillator, PivotHighLow, RateOfChange, RelativeStrengthIndex, RollingDelta,
+    RollingDrawdown, RollingIC, RollingKurtosis, RollingMedian, RollingQuantile, RollingSharpe,
+    RollingSkewness, RollingSpectrum, SimpleMovingAverage, SlowStochastic, StandardDeviation,
+    StochasticMomentumIndex, TtmSqueeze, TwiggsMoneyFlow, VolatilityAdjustedRateOfChange,
+    VolatilityStop, VolumeWeightedAveragePrice, VolumeWeightedMacd, VolumeWeightedMovingAverage,
+    VolumeZoneOscillator, WaveTrend, WaveletDecomposition, WeightedMovingAverage, WilliamsVixFix,
+};
+use ta::{Close, DataItem, IsReady, Next, Period, Reset, Volume};
+
+type FixedMaximum = ta::indicators::fixed::Maximum<14>;
+type FixedMinimum = ta::indicators::fixed::Minimum<14>;
+type FixedRateOfChange = ta::indicators::fixed::RateOfChange<9>;
+type FixedSimpleMovingAverage = ta::indicators::fixed::SimpleMovingAverage<9>;
+type FixedStandardDeviation = ta::indicators::fixed::StandardDeviation<20>;
+type FixedWeightedMovingAverage = ta::indicators::fixed::WeightedMovingAverage<9>;
+type FixedVolumeWeightedAveragePrice = ta::indicators::fixed::VolumeWeightedAveragePrice<14>;
+
+const BARS: usize = 1_000;
+
+fn bars() -> Vec<DataItem> {
+    (0..BARS)
+        .map(|i| {
+            let x = i as f64;
+            let close = 100.0 + (x * 0.3).sin() * 10.0 + (x * 0.05).cos() * 20.0;
+            let range = 1.0 + (x * 0.7).sin().abs() * 3.0;
+            DataItem::builder()
+                .open(close - range / 3.0)
+                .high(close + range / 2.0)
+                .low(close - range / 2.0)
+                .close(close)
+                .volume(1_000.0 + (x * 1.3).cos() * 500.0)
+                .build()
+                .unwrap()
+        })
+        .collect()
+}
+
+fn closes() -> Vec<f64> {
+    bars().iter().map(|bar| bar.close()).collect()
+}
+
+/// Asserts that the indicator is ready after exactly `period()` inputs, also after a reset.
+fn assert_period_is_warm_up<I, T>(name: &str, mut indicator: I, inputs: &[T])
+where
+    I: Next<T> + Period + IsReady + Reset,
+    T: Copy,
+{
+    let period = indicator.period();
+    for run in ["first", "after reset"] {
+        for (i, &input) in inputs[..period].iter().enumerate() {
+            assert_eq!(
+                indicator.bars_until_ready(),
+                period - i,
+                "{} in the {} run after {} of {} bars",
+                name,
+                run,
+                i,
+                period
+            );
+            indicator.next(input);
+        }
+        assert!(
+            indicator.is_ready(),
+            "{} not ready in the {} run",
+            name,
+            run
+        );
+        indicator.reset();
+    }
+}
+
+macro_rules! period_is_warm_up {
+    ($($indicator:ident), *) => {
+        $(
+            #[test]
+            #[allow(non_snake_case)]
+            fn $indicator() {
+                let bars = bars();
+                let bars: Vec<&DataItem> = bars.iter().collect();
+                assert_period_is_warm_up(stringify!($indicator), $indicator::default(), &bars);
+            }
+        )*
+    }
+}
+
+period_is_warm_up!(
+    AverageDailyRange,
+    AverageTrueRange,
+    BollingerBands,
+    ChandelierExit,
+    CommodityChannelIndex,
+    DeMarker,
+    EfficiencyRatio,
+    ElderImpulse,
+    ExponentialMovingAverage,
+    FastStochastic,
+    FixedMaximum,
+    FixedMinimum,
+    FixedRateOfChange,
+    FixedSimpleMovingAverage,
+    FixedStandardDeviation,
+    FixedVolumeWeightedAveragePrice,
+    FixedWeightedMovingAverage,
+    IntradayIntensity,
+    KeltnerChannel,
+    MarketStructure,
+    Maximum,
+    MeanAbsoluteDeviation,
+    Minimum,
+    MomentumScore,
+    MoneyFlowIndex,
+    MovingAverageConvergenceDivergence,
+    NormalizedAverageTrueRange,
+    PercentagePriceOscillator,
+    PivotHighLow,
+    RateOfChange,
+    RelativeStrengthIndex,
+    RollingDrawdown,
+    RollingKurtosis,
+    RollingMedian,
+    RollingQuantile,
+    RollingSkewness,
+    RollingSpectrum,
+    SimpleMovingAverage,
+    SlowStochastic,
+    StandardDeviation,
+    StochasticMomentumIndex,
+    TtmSqueeze,
+    TwiggsMoneyFlow,
+    VolatilityAdjustedRateOfChange,
+    VolatilityStop,
+    VolumeWeightedAveragePrice,
+    VolumeWeightedMacd,
+    VolumeWeightedMovingAverage,
+    VolumeZoneOscillator,
+    WaveTrend,
+    WaveletDecomposition,
+    WeightedMovingAverage,
+    WilliamsVixFix
+);
+
+#[test]
+fn values() {
+    let closes = closes();
+    assert_period_is_warm_up("RollingSharpe", RollingSharpe::default(), &closes);
+    assert_period_is_warm_up("RollingDelta", RollingDelta::default(), &closes);
+    assert_period_is_warm_up(
+        "BandPosition",
+        BandPosition::new(BollingerBands::default()),
+        &closes,
+    );
+    assert_period_is_warm_up(
+        "Crossover",
+        Crossover::new(
+            SimpleMovingAverage::new(5).unwrap(),
+            ExponentialMovingAverage::new(10).unwrap(),
+        ),
+        &closes,
+    );
+}
+
+#[test]
+fn value_pairs() {
+    let pairs: Vec<(f64, f64)> = bars()
+        .iter()
+        .map(|bar| (bar.close(), bar.volume()))
+        .collect();
+    assert_period_is_warm_up("DivergenceDetector", DivergenceDetector::default(), &pairs);
+    assert_period_is_warm_up("RollingIC", RollingIC::default(), &pairs);
+
+    let timed: Vec<(f64, f64)> = closes()
+        .into_iter()
+        .enumerate()
+        .map(|(i, close)| (i as f64, close))
+        .collect();
+    let guard = GapGuard::new(RateOfChange::new(5).unwrap(), 1.0, GapPolicy::Reset).unwrap();
+    assert_period_is_warm_up("GapGuard", guard, &timed);
+}
+
+/// Feeds `inputs` to a composite and to the indicators it is built from, and asserts after
+/// every input that the composite is ready exactly when all of them are.
+fn assert_ready_with<C, T>(
+    name: &str,
+    mut composite: C,
+    inputs: &[T],
+    mut parts: impl FnMut(T) -> bool,
+) where
+    C: Next<T> + IsReady,
+    T: Copy,
+{
+    for (i, &input) in inputs.iter().enumerate() {
+        composite.next(input);
+        assert_eq!(
+            composite.is_ready(),
+            parts(input),
+            "{} after {} inputs",
+            name,
+            i + 1
+        );
+    }
+}
+
+#[test]
+fn composites() {
+    let closes = closes();
+
+    let (mut roc, mut sd) = (
+        RateOfChange::new(3).unwrap(),
+        StandardDeviation::new(5).unwrap(),
+    );
+    let mut prev = None;
+    let varoc = VolatilityAdjustedRateOfChange::new(3, 5).unwrap();
+    assert_ready_with("VAROC(3, 5)", varoc, &closes, |close| {
+        roc.next(close);
+        if let Some(prev) = prev.replace(close) {
+            sd.next((close - prev) / prev * 100.0);
+        }
+        roc.is_ready() && sd.is_ready()
+    });
+
+    let (mut roc, mut sd) = (
+        RateOfChange::new(8).unwrap(),
+        StandardDeviation::new(2).unwrap(),
+    );
+    let mut prev = None;
+    let varoc = VolatilityAdjustedRateOfChange::new(8, 2).unwrap();
+    assert_ready_with("VAROC(8, 2)", varoc, &closes, |close| {
+        roc.next(close);
+        if let Some(prev) = prev.replace(close) {
+            sd.next((close - prev) / prev * 100.0);
+        }
+        roc.is_ready() && sd.is_ready()
+    });
+
+    let mut lookbacks = [3, 6].map(|lookback| RateOfChange::new(lookback).unwrap());
+    let score = MomentumScore::with_horizons(&[(3, 1.0), (6, 1.0)], 1).unwrap();
+    assert_ready_with("MomentumScore", score, &closes, |close| {
+        lookbacks.iter_mut().for_each(|roc| {
+            roc.next(close);
+        });
+        lookbacks.iter().all(|roc| roc.is_ready())
+    });
+
+    let mut sd = StandardDeviation::new(4).unwrap();
+    let bb = BollingerBands::new(4, 2.0).unwrap();
+    assert_ready_with("BB", bb, &closes, |close| {
+        sd.next(close);
+        sd.is_ready()
+    });
+
+    let (mut fast, mut slow) = (
+        ExponentialMovingAverage::new(3).unwrap(),
+        ExponentialMovingAverage::new(6).unwrap(),
+    );
+    let mut signal = ExponentialMovingAverage::new(4).unwrap();
+    let macd = MovingAverageConvergenceDivergence::new(3, 6, 4).unwrap();
+    assert_ready_with("MACD", macd, &closes, |close| {
+        let line = fast.next(close) - slow.next(close);
+        // the signal line averages the MACD line once it is ready
+        if slow.is_ready() {
+            signal.next(line);
+        }
+        slow.is_ready() && signal.is_ready()
+    });
+
+    let mut ema = ExponentialMovingAverage::new(5).unwrap();
+    let mut macd = MovingAverageConvergenceDivergence::new(3, 6, 4).unwrap();
+    let impulse = ElderImpulse::new(5, 3, 6, 4).unwrap();
+    assert_ready_with("ElderImpulse", impulse, &closes, |close| {
+        ema.next(close);
+        macd.next(close);
+        ema.is_ready() && macd.is_ready()
+    });
+}